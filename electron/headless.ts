@@ -0,0 +1,166 @@
+import path from 'path';
+import { randomUUID } from 'crypto';
+import { fileURLToPath } from 'url';
+import { startRemoteServer } from './remote/server.js';
+import { spawnAgent, getAgentMeta, type EventSink } from './ipc/pty.js';
+import { createTask, deleteTask } from './ipc/tasks.js';
+import { listAgents } from './ipc/agents.js';
+import { queuePrompt } from './ipc/prompt-queue.js';
+import { runHeadless } from './ipc/headless-exec.js';
+import { setSessionLogRoot } from './ipc/session-log.js';
+import { setAgentStopRoot } from './ipc/agent-stop.js';
+import { getStateDir } from './ipc/persistence.js';
+import {
+  assertString,
+  assertInt,
+  assertOptionalString,
+  assertOptionalBoolean,
+} from './ipc/validate.js';
+
+const __dirname = path.dirname(fileURLToPath(import.meta.url));
+
+const DEFAULT_COLS = 120;
+const DEFAULT_ROWS = 40;
+
+interface HeadlessTask {
+  id: string;
+  name: string;
+  projectRoot: string;
+  branchName: string;
+  worktreePath: string;
+  agentId: string;
+  agentDefId: string;
+}
+
+/** Stand-in for the BrowserWindow: there is no renderer to push events to. */
+const nullSink: EventSink = {
+  isDestroyed: () => false,
+  webContents: { send: () => {} },
+};
+
+/**
+ * Run task/agent orchestration without a window, behind the remote HTTP/WebSocket
+ * API. Tasks are created with `POST /api/tasks` and removed with
+ * `DELETE /api/tasks/:id`; `POST /api/tasks/:id/exec` runs the task's agent
+ * once in print mode and returns its parsed result. Agents are viewed and
+ * driven from the remote SPA or any WebSocket client, exactly as in the
+ * desktop app's remote access mode.
+ */
+export function startHeadless(opts: { port: number; host?: string; token?: string }): void {
+  const tasks = new Map<string, HeadlessTask>();
+  setSessionLogRoot(getStateDir());
+  setAgentStopRoot(getStateDir());
+
+  const server = startRemoteServer({
+    port: opts.port,
+    host: opts.host,
+    token: opts.token,
+    staticDir: path.join(__dirname, '..', 'dist-remote'),
+    getTaskName: (taskId) => tasks.get(taskId)?.name ?? taskId,
+    getAgentStatus: (agentId) => ({
+      status: getAgentMeta(agentId) ? 'running' : 'exited',
+      exitCode: null,
+      lastLine: '',
+    }),
+    tasks: {
+      async createTask(body) {
+        const args = (body ?? {}) as Record<string, unknown>;
+        assertString(args.name, 'name');
+        assertString(args.projectRoot, 'projectRoot');
+        if (!path.isAbsolute(args.projectRoot) || args.projectRoot.includes('..'))
+          throw new Error('projectRoot must be an absolute path');
+        assertOptionalString(args.agent, 'agent');
+        assertOptionalString(args.prompt, 'prompt');
+        assertOptionalString(args.branchPrefix, 'branchPrefix');
+        assertOptionalBoolean(args.skipPermissions, 'skipPermissions');
+        assertOptionalBoolean(args.autoRestart, 'autoRestart');
+        if (args.maxRestarts !== undefined) assertInt(args.maxRestarts, 'maxRestarts');
+
+        const agents = await listAgents();
+        const agentDef = agents.find((a) => a.id === (args.agent ?? 'claude-code'));
+        if (!agentDef) throw new Error(`Unknown agent: ${args.agent}`);
+
+        const created = await createTask(
+          args.name,
+          args.projectRoot,
+          [],
+          args.branchPrefix ?? 'task',
+        );
+        const agentId = randomUUID();
+        const permissionArgs = args.skipPermissions ? agentDef.skip_permissions_args : [];
+        spawnAgent(nullSink, {
+          taskId: created.id,
+          agentId,
+          command: agentDef.command,
+          args: [...agentDef.args, ...permissionArgs],
+          cwd: created.worktree_path,
+          env: {},
+          cols: DEFAULT_COLS,
+          rows: DEFAULT_ROWS,
+          // Crashed agents are resumed with their resume args, with backoff
+          supervise: args.autoRestart
+            ? {
+                resumeArgs: [
+                  ...(agentDef.resume_args.length ? agentDef.resume_args : agentDef.args),
+                  ...permissionArgs,
+                ],
+                maxRestarts: args.maxRestarts,
+              }
+            : undefined,
+          onOutput: { __CHANNEL_ID__: agentId },
+        });
+        // Delivered once the agent has settled at its input prompt
+        if (args.prompt) queuePrompt(nullSink, agentId, args.prompt);
+
+        const task: HeadlessTask = {
+          id: created.id,
+          name: args.name,
+          projectRoot: args.projectRoot,
+          branchName: created.branch_name,
+          worktreePath: created.worktree_path,
+          agentId,
+          agentDefId: agentDef.id,
+        };
+        tasks.set(task.id, task);
+        return task;
+      },
+
+      async deleteTask(taskId, force) {
+        const task = tasks.get(taskId);
+        if (!task) throw new Error(`Task not found: ${taskId}`);
+        const result = await deleteTask({
+          taskId,
+          agentIds: [task.agentId],
+          branchName: task.branchName,
+          deleteBranch: true,
+          projectRoot: task.projectRoot,
+          // No desktop trash on a headless box
+          permanent: true,
+          force,
+        });
+        tasks.delete(taskId);
+        return result;
+      },
+
+      async execTask(taskId, body) {
+        const task = tasks.get(taskId);
+        if (!task) throw new Error(`Task not found: ${taskId}`);
+        const args = (body ?? {}) as Record<string, unknown>;
+        assertString(args.prompt, 'prompt');
+        const agentDef = (await listAgents()).find((a) => a.id === task.agentDefId);
+        if (!agentDef) throw new Error(`Unknown agent: ${task.agentDefId}`);
+        return runHeadless(nullSink, {
+          runId: randomUUID(),
+          agentDefId: agentDef.id,
+          command: agentDef.command,
+          prompt: args.prompt,
+          cwd: task.worktreePath,
+          env: agentDef.env,
+        });
+      },
+    },
+  });
+
+  console.log(`[headless] Listening on port ${server.port}`);
+  console.log(`[headless] ${server.url}`);
+}
@@ -0,0 +1,111 @@
+import { IPC } from './channels.js';
+import { countAgentSlotsInUse, getAgentMeta, type EventSink } from './pty.js';
+
+/** An agent spawn waiting for a free slot. */
+interface QueuedSpawn {
+  agentId: string;
+  taskId: string;
+  queued_at: number;
+  start: () => void;
+}
+
+/** What the renderer sees of a waiting spawn; `position` is 1-based. */
+export interface QueuedAgent {
+  agentId: string;
+  taskId: string;
+  position: number;
+  queued_at: number;
+}
+
+let sink: EventSink | null = null;
+/** Most agents allowed to run at once; 0 means no limit. */
+let limit = 0;
+const waiting: QueuedSpawn[] = [];
+
+export function listQueuedAgents(): QueuedAgent[] {
+  return waiting.map((q, i) => ({
+    agentId: q.agentId,
+    taskId: q.taskId,
+    position: i + 1,
+    queued_at: q.queued_at,
+  }));
+}
+
+function sendQueueChanged(): void {
+  if (!sink || sink.isDestroyed()) return;
+  sink.webContents.send(IPC.AgentQueueChanged, listQueuedAgents());
+}
+
+function hasFreeSlot(): boolean {
+  return limit === 0 || countAgentSlotsInUse() < limit;
+}
+
+function startEntry(entry: QueuedSpawn): void {
+  try {
+    entry.start();
+  } catch (err) {
+    // start() reports its own failures to the terminal; don't block the queue on one
+    console.warn(`Queued agent ${entry.agentId} failed to start:`, err);
+  }
+}
+
+/** Start waiting spawns, oldest first, while slots are free. */
+export function drainAgentQueue(): void {
+  let changed = false;
+  while (waiting.length > 0 && hasFreeSlot()) {
+    const entry = waiting.shift() as QueuedSpawn;
+    changed = true;
+    startEntry(entry);
+  }
+  if (changed) sendQueueChanged();
+}
+
+export function setAgentConcurrencyLimit(win: EventSink, max: number): void {
+  sink = win;
+  limit = Math.max(0, max);
+  drainAgentQueue();
+}
+
+/**
+ * Run `start` now if the limit allows, otherwise queue it until a running
+ * agent exits. Returns the queue position, or 0 when it started right away.
+ * A newer request for the same agent replaces the waiting one.
+ */
+export function requestAgentSpawn(
+  win: EventSink,
+  agentId: string,
+  taskId: string,
+  start: () => void,
+): number {
+  sink = win;
+  const existing = waiting.findIndex((q) => q.agentId === agentId);
+  if (existing !== -1) waiting.splice(existing, 1);
+  // Restarting a running agent replaces its process and keeps its slot
+  if (getAgentMeta(agentId) || (waiting.length === 0 && hasFreeSlot())) {
+    if (existing !== -1) sendQueueChanged();
+    start();
+    return 0;
+  }
+  waiting.push({ agentId, taskId, queued_at: Date.now(), start });
+  sendQueueChanged();
+  return waiting.length;
+}
+
+/** Drop a waiting spawn, e.g. when its terminal is closed. */
+export function cancelQueuedSpawn(agentId: string): boolean {
+  const index = waiting.findIndex((q) => q.agentId === agentId);
+  if (index === -1) return false;
+  waiting.splice(index, 1);
+  sendQueueChanged();
+  return true;
+}
+
+/** Start a waiting spawn right away, ignoring the limit. */
+export function startQueuedSpawnNow(agentId: string): boolean {
+  const index = waiting.findIndex((q) => q.agentId === agentId);
+  if (index === -1) return false;
+  const [entry] = waiting.splice(index, 1);
+  sendQueueChanged();
+  startEntry(entry);
+  return true;
+}
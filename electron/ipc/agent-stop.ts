@@ -0,0 +1,62 @@
+import fs from 'fs';
+import path from 'path';
+
+// An agent marks the end of its turn by touching the file named in its
+// PARALLEL_CODE_AGENT_STOP_FILE env var, e.g. from Claude Code's Stop hook.
+// The prompt queue sends the next prompt once the file is newer than the
+// last one it sent.
+
+export const AGENT_STOP_FILE_ENV = 'PARALLEL_CODE_AGENT_STOP_FILE';
+
+let stopRoot: string | null = null;
+
+/** Enable stop signals under `dir`. Until called, agents get no stop file. */
+export function setAgentStopRoot(dir: string): void {
+  stopRoot = path.join(dir, 'agent-stop');
+}
+
+function stopFilePath(agentId: string): string | null {
+  if (!stopRoot || !/^[A-Za-z0-9_-]+$/.test(agentId)) return null;
+  return path.join(stopRoot, agentId);
+}
+
+/** Where a newly spawned agent signals a finished turn, or null when signals are off. */
+export function agentStopFile(agentId: string): string | null {
+  const file = stopFilePath(agentId);
+  if (!file) return null;
+  try {
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    fs.rmSync(file, { force: true });
+  } catch (err) {
+    console.warn('Failed to set up the agent stop signal:', err);
+    return null;
+  }
+  return file;
+}
+
+/** When the agent last signalled a finished turn (ms), or null if it hasn't. */
+export function agentStoppedAt(agentId: string): number | null {
+  const file = stopFilePath(agentId);
+  if (!file) return null;
+  try {
+    return fs.statSync(file).mtimeMs;
+  } catch {
+    return null;
+  }
+}
+
+export function clearAgentStop(agentId: string): void {
+  const file = stopFilePath(agentId);
+  if (file) fs.rmSync(file, { force: true });
+}
+
+/**
+ * Args that make Claude Code touch the stop file from a Stop hook. Its
+ * `--settings` are merged with the user's own, so their hooks still run.
+ */
+export function stopHookArgs(command: string): string[] {
+  if (path.basename(command) !== 'claude') return [];
+  const file = `"$${AGENT_STOP_FILE_ENV}"`;
+  const hook = { type: 'command', command: `[ -z ${file} ] || touch ${file}` };
+  return ['--settings', JSON.stringify({ hooks: { Stop: [{ hooks: [hook] }] } })];
+}
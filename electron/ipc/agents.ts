@@ -1,9 +1,13 @@
 import { execFile } from 'child_process';
+import fs from 'fs';
+import path from 'path';
 import { promisify } from 'util';
+import { getStateDir } from './persistence.js';
+import { assertString, assertStringArray } from './validate.js';
 
 const execFileAsync = promisify(execFile);
 
-interface AgentDef {
+export interface AgentDef {
   id: string;
   name: string;
   command: string;
@@ -11,6 +15,9 @@ interface AgentDef {
   resume_args: string[];
   skip_permissions_args: string[];
   description: string;
+  env?: Record<string, string>;
+  /** True for user-defined agents loaded from agents.json. */
+  custom?: boolean;
   available?: boolean;
 }
 
@@ -67,14 +74,163 @@ let cachedAgents: AgentDef[] | null = null;
 let cacheTime = 0;
 const AGENT_CACHE_TTL = 30_000;
 
+// --- User-defined agents, persisted next to the app state ---
+
+function customAgentsPath(): string {
+  return path.join(getStateDir(), 'agents.json');
+}
+
+/** The valid agents in agents.json, and whether anything else in it was skipped. */
+function parseCustomAgents(): { agents: AgentDef[]; skipped: boolean } {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(fs.readFileSync(customAgentsPath(), 'utf8'));
+  } catch (err) {
+    return { agents: [], skipped: (err as NodeJS.ErrnoException).code !== 'ENOENT' };
+  }
+  if (!Array.isArray(parsed)) return { agents: [], skipped: true };
+  const agents = parsed.filter((a): a is AgentDef => {
+    try {
+      validateAgentDef(a);
+      return true;
+    } catch {
+      return false;
+    }
+  });
+  return { agents, skipped: agents.length < parsed.length };
+}
+
+function readCustomAgents(): AgentDef[] {
+  return parseCustomAgents().agents;
+}
+
+function writeCustomAgents(agents: AgentDef[]): void {
+  const file = customAgentsPath();
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  // Rewriting drops what couldn't be read (hand edits, a newer version's
+  // entries); keep the file as it was next to it
+  if (parseCustomAgents().skipped) fs.copyFileSync(file, file + '.bak');
+  const tmp = file + '.tmp';
+  fs.writeFileSync(tmp, JSON.stringify(agents, null, 2), 'utf8');
+  fs.renameSync(tmp, file);
+  cachedAgents = null;
+}
+
+function validateAgentDef(def: unknown): asserts def is AgentDef {
+  if (typeof def !== 'object' || def === null) throw new Error('agent must be an object');
+  const a = def as Record<string, unknown>;
+  assertString(a.id, 'id');
+  if (!/^[a-z0-9][a-z0-9-]*$/.test(a.id)) throw new Error(`Invalid agent id: ${a.id}`);
+  assertString(a.name, 'name');
+  assertString(a.command, 'command');
+  if (!a.command.trim() || /[;&|`$(){}\n]/.test(a.command)) {
+    throw new Error(`Invalid agent command: ${a.command}`);
+  }
+  assertStringArray(a.args, 'args');
+  assertStringArray(a.resume_args, 'resume_args');
+  assertStringArray(a.skip_permissions_args, 'skip_permissions_args');
+  assertString(a.description, 'description');
+  if (a.env != null) {
+    if (typeof a.env !== 'object') throw new Error('env must be an object');
+    for (const v of Object.values(a.env)) assertString(v, 'env value');
+  }
+}
+
+function toStoredAgent(def: AgentDef): AgentDef {
+  return {
+    id: def.id,
+    name: def.name,
+    command: def.command,
+    args: def.args,
+    resume_args: def.resume_args,
+    skip_permissions_args: def.skip_permissions_args,
+    description: def.description,
+    ...(def.env && Object.keys(def.env).length > 0 ? { env: def.env } : {}),
+  };
+}
+
+export function addAgent(def: unknown): AgentDef {
+  validateAgentDef(def);
+  const agents = readCustomAgents();
+  if (agents.some((a) => a.id === def.id)) throw new Error(`Agent already exists: ${def.id}`);
+  const stored = toStoredAgent(def);
+  writeCustomAgents([...agents, stored]);
+  return { ...stored, custom: true };
+}
+
+export function updateAgent(agentId: string, def: unknown): AgentDef {
+  validateAgentDef(def);
+  const agents = readCustomAgents();
+  const idx = agents.findIndex((a) => a.id === agentId);
+  if (idx < 0) throw new Error(`Custom agent not found: ${agentId}`);
+  if (def.id !== agentId && agents.some((a) => a.id === def.id)) {
+    throw new Error(`Agent already exists: ${def.id}`);
+  }
+  agents[idx] = toStoredAgent(def);
+  writeCustomAgents(agents);
+  return { ...agents[idx], custom: true };
+}
+
+export function removeAgent(agentId: string): void {
+  const agents = readCustomAgents();
+  const remaining = agents.filter((a) => a.id !== agentId);
+  if (remaining.length === agents.length) throw new Error(`Custom agent not found: ${agentId}`);
+  writeCustomAgents(remaining);
+}
+
+/** User-defined agents as stored, e.g. for a config export. */
+export function listCustomAgents(): AgentDef[] {
+  return readCustomAgents();
+}
+
+/**
+ * The custom agents in a config export that may be imported. Invalid entries
+ * are skipped; ones using a built-in agent's id are refused, since they would
+ * replace the command that agent runs.
+ */
+export function checkImportedAgents(defs: unknown[]): { agents: AgentDef[]; refused: string[] } {
+  const builtinIds = new Set(DEFAULT_AGENTS.map((a) => a.id));
+  const agents: AgentDef[] = [];
+  const refused: string[] = [];
+  for (const def of defs) {
+    try {
+      validateAgentDef(def);
+    } catch {
+      continue;
+    }
+    if (builtinIds.has(def.id)) refused.push(def.id);
+    else agents.push(toStoredAgent(def));
+  }
+  return { agents, refused };
+}
+
+/**
+ * Add agents the user accepted from a config export; one with the id of an
+ * existing custom agent replaces it. Returns how many were taken.
+ */
+export function importAgents(defs: unknown[]): number {
+  const { agents: accepted } = checkImportedAgents(defs);
+  const agents = readCustomAgents();
+  for (const stored of accepted) {
+    const idx = agents.findIndex((a) => a.id === stored.id);
+    if (idx >= 0) agents[idx] = stored;
+    else agents.push(stored);
+  }
+  if (accepted.length > 0) writeCustomAgents(agents);
+  return accepted.length;
+}
+
+/** Built-in agents merged with user-defined ones; a custom agent with a built-in id wins. */
 export async function listAgents(): Promise<AgentDef[]> {
   const now = Date.now();
   if (cachedAgents && now - cacheTime < AGENT_CACHE_TTL) {
     return cachedAgents;
   }
 
+  const custom = readCustomAgents().map((a) => ({ ...a, custom: true }));
+  const customIds = new Set(custom.map((a) => a.id));
   cachedAgents = await Promise.all(
-    DEFAULT_AGENTS.map(async (agent) => ({
+    [...DEFAULT_AGENTS.filter((a) => !customIds.has(a.id)), ...custom].map(async (agent) => ({
       ...agent,
       available: await isCommandAvailable(agent.command),
     })),
@@ -0,0 +1,181 @@
+import fs from 'fs';
+import path from 'path';
+import {
+  applyPatch,
+  branchExists,
+  getBranchLog,
+  getTaskPatch,
+  getUncommittedPatch,
+} from './git.js';
+import { createTask, deleteTask } from './tasks.js';
+import { copySessionLogs } from './session-log.js';
+import { copyRecordings } from './recording.js';
+
+// Archived tasks live in archive/<taskId>/ under the app data dir: task.json
+// (the record below), changes.patch (everything the branch changed since it
+// left main, committed or not), uncommitted.patch (only what wasn't committed),
+// commits.log and the agents' session logs.
+
+const SAFE_ID = /^[A-Za-z0-9_-]+$/;
+
+let archiveRoot: string | null = null;
+
+export function setArchiveRoot(dir: string): void {
+  archiveRoot = path.join(dir, 'archive');
+}
+
+export interface ArchivedTask {
+  id: string;
+  name: string;
+  project_root: string;
+  branch_name: string;
+  /** Whether the branch was left in the repo when the worktree was removed. */
+  branch_kept: boolean;
+  archived_at: number;
+  /** Size of changes.patch; 0 when the task changed nothing. */
+  patch_bytes: number;
+  /** The renderer's task record, handed back on restore. */
+  task: unknown;
+}
+
+function archiveDir(id: string): string {
+  if (!archiveRoot) throw new Error('Task archive is not available');
+  if (!SAFE_ID.test(id)) throw new Error(`Invalid archive id: ${id}`);
+  return path.join(archiveRoot, id);
+}
+
+function readRecord(dir: string): ArchivedTask {
+  return JSON.parse(fs.readFileSync(path.join(dir, 'task.json'), 'utf8')) as ArchivedTask;
+}
+
+export interface ArchiveTaskOpts {
+  taskId: string;
+  name: string;
+  projectRoot: string;
+  branchName: string;
+  worktreePath: string;
+  agentIds: string[];
+  keepBranch: boolean;
+  permanent: boolean;
+  task: unknown;
+}
+
+/**
+ * Save what a task did (its patch, commit log, session logs and recordings)
+ * to the archive, then remove its worktree like closing it does. Nothing is
+ * removed if the archive can't be written.
+ */
+export async function archiveTask(opts: ArchiveTaskOpts): Promise<ArchivedTask> {
+  const dir = archiveDir(opts.taskId);
+  const [patch, uncommitted, log] = await Promise.all([
+    getTaskPatch(opts.worktreePath),
+    getUncommittedPatch(opts.worktreePath),
+    getBranchLog(opts.worktreePath),
+  ]);
+  const record: ArchivedTask = {
+    id: opts.taskId,
+    name: opts.name,
+    project_root: opts.projectRoot,
+    branch_name: opts.branchName,
+    branch_kept: opts.keepBranch,
+    archived_at: Date.now(),
+    patch_bytes: Buffer.byteLength(patch),
+    task: opts.task,
+  };
+  try {
+    fs.mkdirSync(dir, { recursive: true });
+    fs.writeFileSync(path.join(dir, 'changes.patch'), patch);
+    fs.writeFileSync(path.join(dir, 'uncommitted.patch'), uncommitted);
+    fs.writeFileSync(path.join(dir, 'commits.log'), log);
+    copySessionLogs(opts.taskId, path.join(dir, 'logs'));
+    for (const agentId of opts.agentIds) copyRecordings(agentId, path.join(dir, 'recordings'));
+    fs.writeFileSync(path.join(dir, 'task.json'), JSON.stringify(record, null, 2));
+  } catch (e) {
+    fs.rmSync(dir, { recursive: true, force: true });
+    throw new Error(`Could not write the task archive: ${e}`);
+  }
+
+  await deleteTask({
+    taskId: opts.taskId,
+    agentIds: opts.agentIds,
+    branchName: opts.branchName,
+    deleteBranch: !opts.keepBranch,
+    projectRoot: opts.projectRoot,
+    permanent: opts.permanent,
+    force: true,
+  });
+  return record;
+}
+
+/** Archived tasks, newest first; unreadable entries are skipped. */
+export function listArchived(projectRoot?: string): ArchivedTask[] {
+  if (!archiveRoot || !fs.existsSync(archiveRoot)) return [];
+  const records: ArchivedTask[] = [];
+  for (const name of fs.readdirSync(archiveRoot)) {
+    if (!SAFE_ID.test(name)) continue;
+    try {
+      const record = readRecord(path.join(archiveRoot, name));
+      if (!projectRoot || record.project_root === projectRoot) records.push(record);
+    } catch (err) {
+      console.warn(`Skipping unreadable archived task ${name}:`, err);
+    }
+  }
+  return records.sort((a, b) => b.archived_at - a.archived_at);
+}
+
+export interface RestoreArchivedOpts {
+  id: string;
+  symlinkDirs: string[];
+  branchPrefix: string;
+  copyPaths: string[];
+  clonePaths: string[];
+  hardlinkPaths: string[];
+  worktreeBaseDir: string | null;
+}
+
+/**
+ * Bring an archived task back in a fresh worktree: on its kept branch with
+ * the uncommitted changes reapplied when that branch still exists, otherwise
+ * on a new branch from HEAD with all archived changes applied, uncommitted.
+ * Patches apply three-way; the archive entry is removed afterwards unless one
+ * failed to apply.
+ */
+export async function restoreArchivedTask(opts: RestoreArchivedOpts): Promise<{
+  archived: ArchivedTask;
+  task: Awaited<ReturnType<typeof createTask>>;
+  conflicted_files: string[];
+  patch_error: string | null;
+}> {
+  const dir = archiveDir(opts.id);
+  const archived = readRecord(dir);
+  const onBranch =
+    archived.branch_kept && (await branchExists(archived.project_root, archived.branch_name));
+  const task = await createTask(
+    archived.name,
+    archived.project_root,
+    opts.symlinkDirs,
+    opts.branchPrefix,
+    opts.copyPaths,
+    opts.clonePaths,
+    false,
+    onBranch ? archived.branch_name : null,
+    opts.worktreeBaseDir,
+    opts.hardlinkPaths,
+  );
+  let conflicted: string[] = [];
+  let patchError: string | null = null;
+  const patch = fs.readFileSync(
+    path.join(dir, onBranch ? 'uncommitted.patch' : 'changes.patch'),
+    'utf8',
+  );
+  if (patch.trim()) {
+    try {
+      conflicted = (await applyPatch(task.worktree_path, patch, true)).conflicted_files;
+    } catch (e) {
+      patchError = e instanceof Error ? e.message : String(e);
+    }
+  }
+  // A patch that didn't apply stays in the archive rather than being lost
+  if (!patchError) fs.rmSync(dir, { recursive: true, force: true });
+  return { archived, task, conflicted_files: conflicted, patch_error: patchError };
+}
@@ -0,0 +1,96 @@
+import { execFile } from 'child_process';
+import { promisify } from 'util';
+import { IPC } from './channels.js';
+import { getWorktreeStatus } from './git.js';
+import type { EventSink } from './pty.js';
+
+const exec = promisify(execFile);
+
+interface AutoFetcher {
+  win: EventSink;
+  projectRoot: string;
+  intervalMs: number;
+  timer: ReturnType<typeof setInterval>;
+  /** Last status sent per worktree, serialized, so only changes are emitted. */
+  worktrees: Map<string, string | null>;
+  running: boolean;
+}
+
+const fetchers = new Map<string, AutoFetcher>();
+
+/** Lower bound on the fetch interval so a bad setting can't hammer the remote. */
+const MIN_INTERVAL_MS = 60_000;
+const FETCH_TIMEOUT_MS = 60_000;
+
+async function tick(projectId: string, fetcher: AutoFetcher): Promise<void> {
+  if (fetcher.running) return;
+  fetcher.running = true;
+  try {
+    await exec('git', ['fetch', '--prune', '--quiet', 'origin'], {
+      cwd: fetcher.projectRoot,
+      timeout: FETCH_TIMEOUT_MS,
+      // Never block on a credential prompt in the background
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+    }).catch((err) => console.warn(`Auto-fetch failed for ${fetcher.projectRoot}:`, err));
+
+    for (const [worktreePath, last] of fetcher.worktrees) {
+      // Stopped or replaced while fetching
+      if (fetchers.get(projectId) !== fetcher) return;
+      const status = await getWorktreeStatus(worktreePath).catch(() => null);
+      if (!status) continue; // worktree removed
+      const serialized = JSON.stringify(status);
+      if (serialized === last || !fetcher.worktrees.has(worktreePath)) continue;
+      fetcher.worktrees.set(worktreePath, serialized);
+      if (!fetcher.win.isDestroyed()) {
+        fetcher.win.webContents.send(IPC.WorktreeStatusChanged, { worktreePath, status });
+      }
+    }
+  } finally {
+    fetcher.running = false;
+  }
+}
+
+/**
+ * Periodically fetch origin for a project and push the ahead/behind status of
+ * its worktrees when it changes. Calling again updates the interval and the
+ * tracked worktrees in place.
+ */
+export function startAutoFetch(
+  win: EventSink,
+  projectId: string,
+  projectRoot: string,
+  intervalMinutes: number,
+  worktreePaths: string[],
+): void {
+  const intervalMs = Math.max(MIN_INTERVAL_MS, intervalMinutes * 60_000);
+  const existing = fetchers.get(projectId);
+  if (existing && existing.projectRoot === projectRoot && existing.intervalMs === intervalMs) {
+    const previous = existing.worktrees;
+    existing.worktrees = new Map(worktreePaths.map((p) => [p, previous.get(p) ?? null]));
+    return;
+  }
+  stopAutoFetch(projectId);
+
+  const fetcher: AutoFetcher = {
+    win,
+    projectRoot,
+    intervalMs,
+    timer: setInterval(() => void tick(projectId, fetcher), intervalMs),
+    worktrees: new Map(worktreePaths.map((p) => [p, null])),
+    running: false,
+  };
+  fetcher.timer.unref?.();
+  fetchers.set(projectId, fetcher);
+  void tick(projectId, fetcher);
+}
+
+export function stopAutoFetch(projectId: string): void {
+  const fetcher = fetchers.get(projectId);
+  if (!fetcher) return;
+  clearInterval(fetcher.timer);
+  fetchers.delete(projectId);
+}
+
+export function stopAllAutoFetchers(): void {
+  for (const projectId of [...fetchers.keys()]) stopAutoFetch(projectId);
+}
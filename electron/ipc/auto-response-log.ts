@@ -0,0 +1,55 @@
+import fs from 'fs';
+import path from 'path';
+import { getStateDir } from './persistence.js';
+
+export interface AutoResponseAuditEntry {
+  at: number;
+  task_id: string;
+  agent_id: string;
+  pattern: string;
+  matched: string;
+  response: string;
+}
+
+const LOG_FILE = 'auto-responses.log';
+/** Rotate once the log passes this size; one previous generation is kept. */
+const MAX_LOG_BYTES = 1024 * 1024;
+
+function logPath(): string {
+  return path.join(getStateDir(), LOG_FILE);
+}
+
+/** Append one auto-response to the JSONL audit log. */
+export function appendAutoResponseAudit(entry: AutoResponseAuditEntry): void {
+  const file = logPath();
+  try {
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    if (fs.existsSync(file) && fs.statSync(file).size > MAX_LOG_BYTES) {
+      fs.renameSync(file, `${file}.1`);
+    }
+    fs.appendFileSync(file, JSON.stringify(entry) + '\n', 'utf8');
+  } catch (err) {
+    console.warn('Failed to write auto-response audit log:', err);
+  }
+}
+
+/** Most recent audit entries, newest first. */
+export function readAutoResponseLog(limit: number): AutoResponseAuditEntry[] {
+  let content: string;
+  try {
+    content = fs.readFileSync(logPath(), 'utf8');
+  } catch {
+    return [];
+  }
+  const entries: AutoResponseAuditEntry[] = [];
+  const lines = content.split('\n');
+  for (let i = lines.length - 1; i >= 0 && entries.length < limit; i--) {
+    if (!lines[i]) continue;
+    try {
+      entries.push(JSON.parse(lines[i]) as AutoResponseAuditEntry);
+    } catch {
+      /* skip a torn line */
+    }
+  }
+  return entries;
+}
@@ -0,0 +1,72 @@
+import { describe, it, expect } from 'vitest';
+import { parseBlamePorcelain } from './blame.js';
+
+const A = 'a'.repeat(40);
+const B = 'b'.repeat(40);
+
+const OUTPUT = [
+  `${A} 1 1 2`,
+  'author Ada',
+  'author-mail <ada@example.com>',
+  'author-time 1700000000',
+  'author-tz +0000',
+  'summary Initial commit',
+  'filename f.ts',
+  '\tconst a = 1;',
+  `${A} 2 2`,
+  '\tconst b = 2;',
+  `${B} 5 3 1`,
+  'author Bob',
+  'author-mail <bob@example.com>',
+  'author-time 1710000000',
+  'summary Tweak',
+  'previous ' + A + ' f.ts',
+  'filename f.ts',
+  '\tconst c = 3;',
+  '',
+].join('\n');
+
+describe('parseBlamePorcelain', () => {
+  it('returns one entry per line with commit metadata', () => {
+    expect(parseBlamePorcelain(OUTPUT)).toEqual([
+      {
+        line: 1,
+        hash: A,
+        author: 'Ada',
+        author_email: 'ada@example.com',
+        author_time: 1700000000,
+        summary: 'Initial commit',
+        uncommitted: false,
+      },
+      {
+        line: 2,
+        hash: A,
+        author: 'Ada',
+        author_email: 'ada@example.com',
+        author_time: 1700000000,
+        summary: 'Initial commit',
+        uncommitted: false,
+      },
+      {
+        line: 3,
+        hash: B,
+        author: 'Bob',
+        author_email: 'bob@example.com',
+        author_time: 1710000000,
+        summary: 'Tweak',
+        uncommitted: false,
+      },
+    ]);
+  });
+
+  it('flags uncommitted lines', () => {
+    const zero = '0'.repeat(40);
+    const out = `${zero} 1 1 1\nauthor Not Committed Yet\nsummary Version of f.ts\n\tx\n`;
+    expect(parseBlamePorcelain(out)[0].uncommitted).toBe(true);
+  });
+
+  it('does not read content lines as headers', () => {
+    const out = `${A} 1 1 1\nauthor Ada\nsummary s\n\t${B} 1 1 1\n`;
+    expect(parseBlamePorcelain(out)).toHaveLength(1);
+  });
+});
@@ -0,0 +1,62 @@
+// Parses `git blame --porcelain` output into one entry per line.
+
+export interface BlameLine {
+  /** Line number in the blamed revision. */
+  line: number;
+  hash: string;
+  author: string;
+  author_email: string;
+  /** Author time, unix seconds. */
+  author_time: number;
+  summary: string;
+  /** The line was not committed yet (blame of the working tree). */
+  uncommitted: boolean;
+}
+
+interface CommitMeta {
+  author: string;
+  author_email: string;
+  author_time: number;
+  summary: string;
+}
+
+const HEADER = /^([0-9a-f]{40,64}) \d+ (\d+)(?: \d+)?$/;
+const UNCOMMITTED = /^0+$/;
+
+export function parseBlamePorcelain(output: string): BlameLine[] {
+  const commits = new Map<string, CommitMeta>();
+  const lines: BlameLine[] = [];
+  let current: { hash: string; line: number } | null = null;
+  let meta: CommitMeta | null = null;
+
+  for (const raw of output.split('\n')) {
+    if (raw.startsWith('\t')) {
+      // Content line ends the entry
+      if (current && meta) {
+        const uncommitted = UNCOMMITTED.test(current.hash);
+        lines.push({ line: current.line, hash: current.hash, ...meta, uncommitted });
+      }
+      current = null;
+      continue;
+    }
+    const header = HEADER.exec(raw);
+    if (header && !current) {
+      current = { hash: header[1], line: Number(header[2]) };
+      meta = commits.get(current.hash) ?? null;
+      if (!meta) {
+        meta = { author: '', author_email: '', author_time: 0, summary: '' };
+        commits.set(current.hash, meta);
+      }
+      continue;
+    }
+    if (!meta) continue;
+    const space = raw.indexOf(' ');
+    const key = space === -1 ? raw : raw.slice(0, space);
+    const value = space === -1 ? '' : raw.slice(space + 1);
+    if (key === 'author') meta.author = value;
+    else if (key === 'author-mail') meta.author_email = value.replace(/^<|>$/g, '');
+    else if (key === 'author-time') meta.author_time = Number(value);
+    else if (key === 'summary') meta.summary = value;
+  }
+  return lines;
+}
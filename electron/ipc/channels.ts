@@ -1,42 +1,162 @@
 export enum IPC {
   // Agent/PTY
   SpawnAgent = 'spawn_agent',
+  SpawnShell = 'spawn_shell',
   WriteToAgent = 'write_to_agent',
+  PasteToAgent = 'paste_to_agent',
   ResizeAgent = 'resize_agent',
   PauseAgent = 'pause_agent',
   ResumeAgent = 'resume_agent',
   KillAgent = 'kill_agent',
+  TerminateAgent = 'terminate_agent',
+  SendSignal = 'send_signal',
+  SetAgentPriority = 'set_agent_priority',
+  PasteImage = 'paste_image',
+  GetSessionLog = 'get_session_log',
+  ExportSessionRecording = 'export_session_recording',
+  RunHeadless = 'run_headless',
+  CancelHeadless = 'cancel_headless',
   CountRunningAgents = 'count_running_agents',
   KillAllAgents = 'kill_all_agents',
   ListAgents = 'list_agents',
+  AddAgent = 'add_agent',
+  UpdateAgent = 'update_agent',
+  RemoveAgent = 'remove_agent',
+  AgentSupervision = 'agent_supervision',
+  GetAutoResponseLog = 'get_auto_response_log',
+
+  // Prompt queue
+  QueuePrompt = 'queue_prompt',
+  ListQueuedPrompts = 'list_queued_prompts',
+  RemoveQueuedPrompt = 'remove_queued_prompt',
+  ReorderQueuedPrompt = 'reorder_queued_prompt',
+  SendNextQueuedPrompt = 'send_next_queued_prompt',
+  PromptQueueChanged = 'prompt_queue_changed',
+
+  // Agent spawn queue
+  SetAgentConcurrencyLimit = 'set_agent_concurrency_limit',
+  StartQueuedAgent = 'start_queued_agent',
+  AgentQueueChanged = 'agent_queue_changed',
+
+  // Scheduled task starts
+  ScheduleTaskStart = 'schedule_task_start',
+  CancelScheduledTaskStart = 'cancel_scheduled_task_start',
+  ScheduledTaskStartFired = 'scheduled_task_start_fired',
+  UsageLimitDetected = 'usage_limit_detected',
 
   // Task
   CreateTask = 'create_task',
+  CreateTaskBatch = 'create_task_batch',
+  DuplicateTask = 'duplicate_task',
+  ArchiveTask = 'archive_task',
+  ListArchivedTasks = 'list_archived_tasks',
+  RestoreArchivedTask = 'restore_archived_task',
   DeleteTask = 'delete_task',
+  PreviewDeleteTask = 'preview_delete_task',
+  DetectSetupCommand = 'detect_setup_command',
+
+  // Jobs
+  RunScript = 'run_script',
+  CancelJob = 'cancel_job',
+  ListJobs = 'list_jobs',
+  GetJobOutput = 'get_job_output',
+  RemoveTaskJobs = 'remove_task_jobs',
+  OpenPreview = 'open_preview',
+  JobChanged = 'job_changed',
+  JobOutput = 'job_output',
 
   // Git
   GetChangedFiles = 'get_changed_files',
   GetChangedFilesFromBranch = 'get_changed_files_from_branch',
   GetAllFileDiffs = 'get_all_file_diffs',
   GetAllFileDiffsFromBranch = 'get_all_file_diffs_from_branch',
+  GetMergePreview = 'get_merge_preview',
   GetFileDiff = 'get_file_diff',
+  GetFileDiffRange = 'get_file_diff_range',
+  GetFileAtRevision = 'get_file_at_revision',
+  GetFileDiffStructured = 'get_file_diff_structured',
+  GetAllFileDiffsStructured = 'get_all_file_diffs_structured',
   GetFileDiffFromBranch = 'get_file_diff_from_branch',
   GetGitignoredDirs = 'get_gitignored_dirs',
+  ScanIgnoredEntries = 'scan_ignored_entries',
   GetWorktreeStatus = 'get_worktree_status',
+  GetAllWorktreeStatuses = 'get_all_worktree_statuses',
   CheckMergeStatus = 'check_merge_status',
+  CreateIntegrationBranch = 'create_integration_branch',
+  CheckCrossTaskConflicts = 'check_cross_task_conflicts',
+  CompareWorktrees = 'compare_worktrees',
+  GetWorktreeCrossDiff = 'get_worktree_cross_diff',
+  GetConflictDetails = 'get_conflict_details',
+  ResolveConflict = 'resolve_conflict',
+  ContinueMerge = 'continue_merge',
+  AbortMerge = 'abort_merge',
+  ContinueRebase = 'continue_rebase',
+  AbortRebase = 'abort_rebase',
+  VerifyConflictResolution = 'verify_conflict_resolution',
   MergeTask = 'merge_task',
+  ListBranches = 'list_branches',
+  DiscoverWorktrees = 'discover_worktrees',
+  RepairWorktrees = 'repair_worktrees',
+  RemoveWorktreeOrphans = 'remove_worktree_orphans',
+  FindTaskLeftovers = 'find_task_leftovers',
+  RemoveTaskLeftovers = 'remove_task_leftovers',
   GetBranchLog = 'get_branch_log',
+  GenerateCommitMessage = 'generate_commit_message',
+  GetCommitList = 'get_commit_list',
+  GetCommitDiff = 'get_commit_diff',
+  GetBlame = 'get_blame',
+  CreateSnapshot = 'create_snapshot',
+  ListSnapshots = 'list_snapshots',
+  RestoreSnapshot = 'restore_snapshot',
+  DeleteSnapshot = 'delete_snapshot',
+  GetUndoableOperation = 'get_undoable_operation',
+  UndoLastOperation = 'undo_last_operation',
+  ExportTask = 'export_task',
   PushTask = 'push_task',
+  DeleteRemoteBranch = 'delete_remote_branch',
+  ListRemotes = 'list_remotes',
+  CreatePullRequest = 'create_pull_request',
+  DetectForge = 'detect_forge',
+  ListForgeTokenHosts = 'list_forge_token_hosts',
+  SetForgeToken = 'set_forge_token',
+  GetCiStatus = 'get_ci_status',
   RebaseTask = 'rebase_task',
+  PullMain = 'pull_main',
+  StartAutoFetch = 'start_auto_fetch',
+  StopAutoFetch = 'stop_auto_fetch',
+  WorktreeStatusChanged = 'worktree_status_changed',
+  WatchWorktree = 'watch_worktree',
+  UnwatchWorktree = 'unwatch_worktree',
+  WorktreeChanged = 'worktree_changed',
+  WorktreeWatchFailed = 'worktree_watch_failed',
   GetMainBranch = 'get_main_branch',
   GetCurrentBranch = 'get_current_branch',
   CheckIsGitRepo = 'check_is_git_repo',
   CommitAll = 'commit_all',
+  CommitWorktree = 'commit_worktree',
+  StageFile = 'stage_file',
+  UnstageFile = 'unstage_file',
+  DiscardFileChanges = 'discard_file_changes',
+  GetUnstagedHunks = 'get_unstaged_hunks',
+  StageHunks = 'stage_hunks',
   DiscardUncommitted = 'discard_uncommitted',
+  StashChanges = 'stash_changes',
+  ListStashes = 'list_stashes',
+  PopStash = 'pop_stash',
+  TransferChanges = 'transfer_changes',
+  ApplyPatch = 'apply_patch',
 
   // Persistence
-  SaveAppState = 'save_app_state',
+  SaveSettings = 'save_settings',
+  SaveProject = 'save_project',
+  SaveTask = 'save_task',
   LoadAppState = 'load_app_state',
+  StateSaveConflict = 'state_save_conflict',
+  ResolveStateConflict = 'resolve_state_conflict',
+  StateReadOnlyChanged = 'state_read_only_changed',
+  ExportState = 'export_state',
+  ImportState = 'import_state',
+  ImportAgents = 'import_agents',
 
   // Window
   WindowIsFocused = '__window_is_focused',
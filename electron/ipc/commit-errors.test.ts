@@ -0,0 +1,62 @@
+import { describe, it, expect } from 'vitest';
+import { commitFailure, commitFlags, signFlags } from './commit-errors.js';
+
+function gitError(stderr: string, stdout = ''): Error & { stderr: string; stdout: string } {
+  return Object.assign(new Error(`Command failed: git commit\n${stderr}`), { stderr, stdout });
+}
+
+describe('signFlags', () => {
+  it('leaves signing to git config unless overridden', () => {
+    expect(signFlags(undefined)).toEqual([]);
+    expect(signFlags(true)).toEqual(['--gpg-sign']);
+    expect(signFlags(false)).toEqual(['--no-gpg-sign']);
+  });
+
+  it('adds --no-verify when hooks are skipped', () => {
+    expect(commitFlags()).toEqual([]);
+    expect(commitFlags({ sign: true, skipHooks: true })).toEqual(['--gpg-sign', '--no-verify']);
+  });
+});
+
+describe('commitFailure', () => {
+  it('explains gpg and ssh signing failures', () => {
+    const gpg = commitFailure(
+      'Commit',
+      gitError('error: gpg failed to sign the data\nfatal: failed to write commit object\n'),
+    );
+    expect(gpg.message).toMatch(/^Commit failed: the commit could not be signed\./);
+    expect(gpg.message).toContain('gpg failed to sign the data');
+
+    const ssh = commitFailure('Merge', gitError("Couldn't load public key ~/.ssh/id.pub\n"));
+    expect(ssh.message).toMatch(/could not be signed/);
+  });
+
+  it('keeps the output of a failing hook from both streams', () => {
+    const lint = 'src/a.ts\n  3:1  error  no-unused-vars\n';
+    const husky = 'husky - pre-commit script failed (code 1)\n';
+    const err = commitFailure('Commit', gitError(husky, lint));
+    expect(err.message).toMatch(/^Commit failed: a git hook rejected the commit\./);
+    expect(err.message).toContain('3:1  error  no-unused-vars');
+    expect(err.message).toContain('husky - pre-commit script failed');
+  });
+
+  it('reports conflicts in hook-like paths as conflicts', () => {
+    const out =
+      'Auto-merging src/hooks/pre-commit.ts\n' +
+      'CONFLICT (content): Merge conflict in src/hooks/pre-commit.ts\n' +
+      'Automatic merge failed; fix conflicts and then commit the result.\n';
+    const err = commitFailure('Merge', gitError('', out));
+    expect(err.message).toMatch(/^Merge failed: there are merge conflicts\./);
+    expect(err.message).toContain('src/hooks/pre-commit.ts');
+  });
+
+  it('does not mistake hook-named paths for a rejecting hook', () => {
+    const err = commitFailure('Commit', gitError("error: pathspec 'src/hooks' did not match\n"));
+    expect(err.message).toBe("Commit failed: error: pathspec 'src/hooks' did not match");
+  });
+
+  it('passes other failures through with their stderr', () => {
+    const err = commitFailure('Commit', gitError('nothing to commit, working tree clean\n'));
+    expect(err.message).toBe('Commit failed: nothing to commit, working tree clean');
+  });
+});
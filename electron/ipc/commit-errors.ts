@@ -0,0 +1,69 @@
+// Options and error messages shared by the app's own commits (squash and
+// merge commits, worktree commits). Git's raw "Command failed: ..." output
+// hides why a commit failed, so the common causes get a readable message.
+
+const SIGNING_FAILURE_RE =
+  /gpg failed to sign|error: (?:gpg|ssh-keygen)|cannot run gpg|no secret key|signing failed|couldn't (?:load|find) (?:public )?key|incorrect passphrase/i;
+
+// Hook runners print their own banner; git itself only names the hook when it
+// can't run it, so a failing hook is otherwise recognisable by its output alone.
+// Only the runners' failure lines count: hook names also turn up in file paths.
+const HOOK_FAILURE_RE =
+  /hook declined|\b(?:pre-commit|commit-msg|pre-merge-commit|prepare-commit-msg)(?: hook| script)?\b[^\n]*\b(?:failed|exited with)|^husky - |^- hook id: |\blint-staged\b[^\n]*\bfailed|\blefthook\b[^\n]*\b(?:failed|error)/im;
+
+// A merge or cherry-pick that stopped on conflicts; checked first, since the
+// conflicting paths may themselves look like hook output.
+const CONFLICT_RE = /^CONFLICT \(|^Automatic merge failed|fix conflicts and then commit/m;
+
+const HOOK_OUTPUT_LINES = 30;
+
+export interface CommitOptions {
+  /** Force signing on or off; unset follows the repo's git config. */
+  sign?: boolean;
+  /** Pass --no-verify so pre-commit, commit-msg and pre-merge-commit hooks don't run. */
+  skipHooks?: boolean;
+}
+
+/** Flags forcing signing on or off; none leaves it to commit.gpgsign and gpg.format. */
+export function signFlags(sign: boolean | undefined): string[] {
+  if (sign === undefined) return [];
+  return [sign ? '--gpg-sign' : '--no-gpg-sign'];
+}
+
+/** Flags for `git commit` and `git merge` from the project's commit options. */
+export function commitFlags(opts: CommitOptions = {}): string[] {
+  return [...signFlags(opts.sign), ...(opts.skipHooks ? ['--no-verify'] : [])];
+}
+
+export function lastLines(text: string, count: number): string {
+  return text.split('\n').filter(Boolean).slice(-count).join('\n');
+}
+
+/**
+ * A readable error for a failed `git commit` or `git merge`. Hooks write to
+ * both streams, so their stdout is kept alongside stderr.
+ */
+export function commitFailure(action: string, err: unknown): Error {
+  const e = err as { stdout?: string; stderr?: string; message?: string };
+  const stderr = (e.stderr ?? '').trim();
+  const stdout = (e.stdout ?? '').trim();
+  if (SIGNING_FAILURE_RE.test(stderr)) {
+    return new Error(
+      `${action} failed: the commit could not be signed.\n${lastLines(stderr, 3)}\n` +
+        'Check user.signingkey and gpg.format, and that your gpg or ssh agent can sign ' +
+        'without prompting, or turn signing off for this project.',
+    );
+  }
+  const output = [stdout, stderr].filter(Boolean).join('\n');
+  if (CONFLICT_RE.test(output)) {
+    return new Error(`${action} failed: there are merge conflicts.\n${lastLines(output, 10)}`);
+  }
+  if (HOOK_FAILURE_RE.test(output)) {
+    const tail = lastLines(output, HOOK_OUTPUT_LINES);
+    return new Error(
+      `${action} failed: a git hook rejected the commit.\n${tail}\n` +
+        'Fix the reported problems, or skip hooks for app commits in the project settings.',
+    );
+  }
+  return new Error(`${action} failed: ${output || e.message || String(err)}`);
+}
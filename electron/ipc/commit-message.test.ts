@@ -0,0 +1,33 @@
+import { describe, it, expect } from 'vitest';
+import { buildCommitMessagePrompt, cleanCommitMessage } from './commit-message.js';
+
+describe('buildCommitMessagePrompt', () => {
+  it('includes the branch log and diff', () => {
+    const prompt = buildCommitMessagePrompt('- a1 add palette\n', 'diff --git a/x b/x\n+dark');
+    expect(prompt).toContain('Branch commits:\n- a1 add palette\n');
+    expect(prompt).toContain('Diff:\ndiff --git a/x b/x\n+dark');
+  });
+
+  it('truncates large diffs and says so', () => {
+    const prompt = buildCommitMessagePrompt('', 'x'.repeat(70_000));
+    expect(prompt).toContain('(none)');
+    expect(prompt).toContain('Diff (first 60000 of 70000 chars):');
+    expect(prompt.length).toBeLessThan(61_000);
+  });
+});
+
+describe('cleanCommitMessage', () => {
+  it('unwraps fences, quotes and lead-ins', () => {
+    expect(cleanCommitMessage('```\nfeat: add dark mode\n\nBody\n```')).toBe(
+      'feat: add dark mode\n\nBody',
+    );
+    expect(cleanCommitMessage('Commit message:\nfix(ui): align buttons')).toBe(
+      'fix(ui): align buttons',
+    );
+    expect(cleanCommitMessage('"chore: bump deps"\n')).toBe('chore: bump deps');
+  });
+
+  it('leaves a plain message alone', () => {
+    expect(cleanCommitMessage('feat: x\n\n- "quoted" detail')).toBe('feat: x\n\n- "quoted" detail');
+  });
+});
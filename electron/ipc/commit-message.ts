@@ -0,0 +1,35 @@
+// Prompt and output handling for agent-written squash commit messages.
+
+/** Diff budget in the prompt; the headless runner refuses prompts over 100k chars. */
+const MAX_DIFF_CHARS = 60_000;
+
+const INSTRUCTIONS = `Write a git commit message for squash-merging the branch below into main.
+Use the conventional commit format: a "type(scope): summary" title of at most 72
+characters, a blank line, then a short body explaining what changed and why.
+Base it on the diff; the branch's own commit subjects are only hints.
+Reply with the commit message only: no preamble, no code fences.`;
+
+/** Agent prompt from `get_branch_log` output and the branch's diff against main. */
+export function buildCommitMessagePrompt(log: string, diff: string): string {
+  const truncated = diff.length > MAX_DIFF_CHARS;
+  const shownDiff = truncated ? diff.slice(0, MAX_DIFF_CHARS) : diff;
+  return [
+    INSTRUCTIONS,
+    '',
+    'Branch commits:',
+    log.trim() || '(none)',
+    '',
+    truncated ? `Diff (first ${MAX_DIFF_CHARS} of ${diff.length} chars):` : 'Diff:',
+    shownDiff,
+  ].join('\n');
+}
+
+/** Strip what agents wrap around an answer: fences, quotes, a "Commit message:" lead-in. */
+export function cleanCommitMessage(raw: string): string {
+  let text = raw.trim();
+  const fenced = /^```[\w-]*\n([\s\S]*?)\n```$/.exec(text);
+  if (fenced) text = fenced[1].trim();
+  text = text.replace(/^(?:here is (?:the|a) )?commit message:?\s*\n/i, '');
+  if (/^(["'])[\s\S]*\1$/.test(text)) text = text.slice(1, -1);
+  return text.trim();
+}
@@ -0,0 +1,61 @@
+import { describe, it, expect } from 'vitest';
+import { parseConflictHunks, hasConflictMarkers } from './conflict-markers.js';
+
+describe('parseConflictHunks', () => {
+  it('parses a default-style conflict region', () => {
+    const content = [
+      'a',
+      '<<<<<<< HEAD',
+      'ours 1',
+      'ours 2',
+      '=======',
+      'theirs',
+      '>>>>>>> feature',
+      'b',
+      '',
+    ].join('\n');
+    expect(parseConflictHunks(content)).toEqual([
+      {
+        start_line: 2,
+        end_line: 7,
+        ours_label: 'HEAD',
+        theirs_label: 'feature',
+        ours: 'ours 1\nours 2\n',
+        base: null,
+        theirs: 'theirs\n',
+      },
+    ]);
+  });
+
+  it('captures the base section of diff3-style conflicts', () => {
+    const content = '<<<<<<< ours\nx\n||||||| base\ny\n=======\nz\n>>>>>>> theirs\n';
+    const [hunk] = parseConflictHunks(content);
+    expect(hunk.ours).toBe('x\n');
+    expect(hunk.base).toBe('y\n');
+    expect(hunk.theirs).toBe('z\n');
+  });
+
+  it('returns multiple regions and treats empty sides as empty strings', () => {
+    const content = '<<<<<<< a\n=======\nt\n>>>>>>> b\nmid\n<<<<<<< a\no\n=======\n>>>>>>> b\n';
+    const hunks = parseConflictHunks(content);
+    expect(hunks).toHaveLength(2);
+    expect(hunks[0].ours).toBe('');
+    expect(hunks[1].theirs).toBe('');
+    expect(hunks[1].start_line).toBe(6);
+  });
+
+  it('ignores unterminated regions', () => {
+    expect(parseConflictHunks('<<<<<<< a\nx\n=======\ny\n')).toEqual([]);
+  });
+});
+
+describe('hasConflictMarkers', () => {
+  it('detects leftover markers', () => {
+    expect(hasConflictMarkers('ok\n<<<<<<< HEAD\n')).toBe(true);
+    expect(hasConflictMarkers('ok\n>>>>>>> theirs\n')).toBe(true);
+  });
+
+  it('ignores lines that merely start with marker characters', () => {
+    expect(hasConflictMarkers('<<<<<<<<<< not a marker\n=======\n')).toBe(false);
+  });
+});
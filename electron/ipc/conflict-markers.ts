@@ -0,0 +1,92 @@
+// Parses git conflict markers in a working-tree file into structured hunks.
+// Handles both the default "merge" style and diff3/zdiff3 (with a base
+// section after `|||||||`).
+
+export interface ConflictHunk {
+  /** 1-based line of the `<<<<<<<` marker. */
+  start_line: number;
+  /** 1-based line of the `>>>>>>>` marker. */
+  end_line: number;
+  ours_label: string;
+  theirs_label: string;
+  ours: string;
+  /** Common ancestor content; null unless the conflict was written in diff3 style. */
+  base: string | null;
+  theirs: string;
+}
+
+const OURS = '<<<<<<<';
+const BASE = '|||||||';
+const SEP = '=======';
+const THEIRS = '>>>>>>>';
+
+function isMarker(line: string, marker: string): boolean {
+  return line.startsWith(marker) && (line.length === 7 || line[7] === ' ' || line[7] === '\r');
+}
+
+function label(line: string): string {
+  return line.slice(8).trim();
+}
+
+function joinLines(lines: string[]): string {
+  return lines.length ? lines.join('\n') + '\n' : '';
+}
+
+/** Extract all complete conflict regions from a file's content. */
+export function parseConflictHunks(content: string): ConflictHunk[] {
+  const lines = content.split('\n');
+  const hunks: ConflictHunk[] = [];
+  let i = 0;
+  while (i < lines.length) {
+    if (!isMarker(lines[i], OURS)) {
+      i++;
+      continue;
+    }
+    const start = i;
+    const ours: string[] = [];
+    let base: string[] | null = null;
+    const theirs: string[] = [];
+    let section: 'ours' | 'base' | 'theirs' = 'ours';
+    let end = -1;
+    for (i = start + 1; i < lines.length; i++) {
+      const line = lines[i].replace(/\r$/, '');
+      if (section === 'ours' && isMarker(line, BASE)) {
+        section = 'base';
+        base = [];
+      } else if (section !== 'theirs' && line === SEP) {
+        section = 'theirs';
+      } else if (section === 'theirs' && isMarker(line, THEIRS)) {
+        end = i;
+        break;
+      } else if (section === 'ours' && isMarker(line, OURS)) {
+        // Unterminated region followed by a new one; restart from here
+        break;
+      } else if (section === 'ours') {
+        ours.push(lines[i]);
+      } else if (section === 'base') {
+        base?.push(lines[i]);
+      } else {
+        theirs.push(lines[i]);
+      }
+    }
+    if (end === -1) continue;
+    hunks.push({
+      start_line: start + 1,
+      end_line: end + 1,
+      ours_label: label(lines[start]),
+      theirs_label: label(lines[end]),
+      ours: joinLines(ours),
+      base: base ? joinLines(base) : null,
+      theirs: joinLines(theirs),
+    });
+    i = end + 1;
+  }
+  return hunks;
+}
+
+/** Whether the content still contains any conflict marker line. */
+export function hasConflictMarkers(content: string): boolean {
+  return content
+    .split('\n')
+    .some((line) => isMarker(line, OURS) || isMarker(line, THEIRS) || isMarker(line, BASE));
+}
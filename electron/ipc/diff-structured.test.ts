@@ -0,0 +1,154 @@
+import { describe, it, expect } from 'vitest';
+import {
+  parseDiffFiles,
+  parseHunks,
+  intralineChanges,
+  addIntralineChanges,
+} from './diff-structured.js';
+
+const PATCH = [
+  '--- a/f.ts',
+  '+++ b/f.ts',
+  '@@ -1,3 +1,3 @@',
+  ' import x;',
+  '-const a = 1;',
+  '+const b = 1;',
+  ' done();',
+  '@@ -10 +10,2 @@',
+  ' tail',
+  '+added',
+  '',
+].join('\n');
+
+describe('parseHunks', () => {
+  it('numbers lines on both sides', () => {
+    const hunks = parseHunks(PATCH);
+    expect(hunks).toHaveLength(2);
+    expect(hunks[0].lines).toEqual([
+      { kind: 'context', old_no: 1, new_no: 1, content: 'import x;' },
+      { kind: 'remove', old_no: 2, new_no: null, content: 'const a = 1;' },
+      { kind: 'add', old_no: null, new_no: 2, content: 'const b = 1;' },
+      { kind: 'context', old_no: 3, new_no: 3, content: 'done();' },
+    ]);
+  });
+
+  it('defaults omitted hunk counts to 1', () => {
+    const hunk = parseHunks(PATCH)[1];
+    expect([hunk.old_start, hunk.old_count, hunk.new_start, hunk.new_count]).toEqual([
+      10, 1, 10, 2,
+    ]);
+  });
+});
+
+describe('parseDiffFiles', () => {
+  it('reads paths, status and modes of each file', () => {
+    const raw = [
+      'diff --git a/old.ts b/new.ts',
+      'similarity index 90%',
+      'rename from old.ts',
+      'rename to new.ts',
+      'diff --git a/run.sh b/run.sh',
+      'old mode 100644',
+      'new mode 100755',
+      'diff --git a/gone.txt b/gone.txt',
+      'deleted file mode 100644',
+      'index 45b983b..0000000',
+      '--- a/gone.txt',
+      '+++ /dev/null',
+      '@@ -1 +0,0 @@',
+      '-hi',
+      'diff --git a/img.png b/img.png',
+      'index 1111111..2222222 100644',
+      'Binary files a/img.png and b/img.png differ',
+      '',
+    ].join('\n');
+    const files = parseDiffFiles(raw).map(({ hunks, ...meta }) => ({
+      ...meta,
+      hunks: hunks.length,
+    }));
+    expect(files).toEqual([
+      {
+        old_path: 'old.ts',
+        new_path: 'new.ts',
+        status: 'R',
+        old_mode: null,
+        new_mode: null,
+        binary: false,
+        hunks: 0,
+      },
+      {
+        old_path: 'run.sh',
+        new_path: 'run.sh',
+        status: 'M',
+        old_mode: '100644',
+        new_mode: '100755',
+        binary: false,
+        hunks: 0,
+      },
+      {
+        old_path: 'gone.txt',
+        new_path: null,
+        status: 'D',
+        old_mode: '100644',
+        new_mode: null,
+        binary: false,
+        hunks: 1,
+      },
+      {
+        old_path: 'img.png',
+        new_path: 'img.png',
+        status: 'M',
+        old_mode: '100644',
+        new_mode: '100644',
+        binary: true,
+        hunks: 0,
+      },
+    ]);
+  });
+
+  it('does not mistake removed lines for file headers', () => {
+    const raw = '--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1 @@\n--- comment\n select 1;\n';
+    const [file] = parseDiffFiles(raw);
+    expect(file.new_path).toBe('q.sql');
+    expect(file.hunks[0].lines[0]).toMatchObject({ kind: 'remove', content: '-- comment' });
+  });
+
+  it('flags lines without a trailing newline', () => {
+    const raw = '--- a/f\n+++ b/f\n@@ -1 +1 @@\n-a\n\\ No newline at end of file\n+a\n';
+    const lines = parseDiffFiles(raw)[0].hunks[0].lines;
+    expect(lines[0].no_newline).toBe(true);
+    expect(lines[1].no_newline).toBeUndefined();
+  });
+
+  it('unquotes paths with special characters', () => {
+    const raw = 'diff --git "a/caf\\303\\251.txt" "b/caf\\303\\251.txt"\nnew file mode 100644\n';
+    expect(parseDiffFiles(raw)[0]).toMatchObject({ new_path: 'café.txt', status: 'A' });
+  });
+});
+
+describe('intralineChanges', () => {
+  it('marks only the changed word', () => {
+    expect(intralineChanges('const a = 1;', 'const b = 1;')).toEqual({
+      old: [[6, 7]],
+      new: [[6, 7]],
+    });
+  });
+
+  it('merges adjacent changed tokens into one range', () => {
+    expect(intralineChanges('let x = foo;', 'let x = bar.baz;')?.new).toEqual([[8, 15]]);
+  });
+
+  it('gives up on dissimilar lines', () => {
+    expect(intralineChanges('return value;', 'throw new Error()')).toBeNull();
+  });
+});
+
+describe('addIntralineChanges', () => {
+  it('pairs removed and added lines of a change block', () => {
+    const [hunk] = parseHunks(PATCH);
+    addIntralineChanges(hunk);
+    expect(hunk.lines[1].changes).toEqual([[6, 7]]);
+    expect(hunk.lines[2].changes).toEqual([[6, 7]]);
+    expect(hunk.lines[0].changes).toBeUndefined();
+  });
+});
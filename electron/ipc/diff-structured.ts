@@ -0,0 +1,326 @@
+// Parses `git diff` output into typed files, hunks and lines, and computes
+// intraline (word level) change ranges between removed and added lines, so the
+// renderer can highlight the changed words instead of whole lines. Free of
+// Node APIs: the renderer's diff views parse raw diffs with it too.
+
+export type DiffLineKind = 'context' | 'add' | 'remove';
+
+export interface DiffLine {
+  kind: DiffLineKind;
+  old_no: number | null;
+  new_no: number | null;
+  content: string;
+  /** The line has no trailing newline (`\ No newline at end of file`). */
+  no_newline?: boolean;
+  /**
+   * Changed `[start, end)` offsets within `content`. Only set on removed/added
+   * lines paired with a similar counterpart; absent means the whole line changed.
+   */
+  changes?: [number, number][];
+}
+
+export interface DiffHunk {
+  header: string;
+  old_start: number;
+  old_count: number;
+  new_start: number;
+  new_count: number;
+  lines: DiffLine[];
+}
+
+export interface DiffFile {
+  /** Null for added files. */
+  old_path: string | null;
+  /** Null for deleted files. */
+  new_path: string | null;
+  status: 'A' | 'M' | 'D' | 'R' | 'C';
+  old_mode: string | null;
+  new_mode: string | null;
+  binary: boolean;
+  hunks: DiffHunk[];
+}
+
+const HUNK_HEADER = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/;
+
+/** Lines longer than this are marked as wholly changed; the token LCS is quadratic. */
+const MAX_INTRALINE_LENGTH = 1000;
+/** Pairs sharing less than this fraction of their text aren't worth highlighting by word. */
+const MIN_SIMILARITY = 0.4;
+
+const C_ESCAPES: Record<string, string> = { n: '\n', t: '\t', '"': '"', '\\': '\\' };
+
+/** Undo git's C-style quoting of paths with special characters. */
+function unquotePath(raw: string): string {
+  if (!raw.startsWith('"') || !raw.endsWith('"')) return raw;
+  const encoder = new TextEncoder();
+  const bytes: number[] = [];
+  const body = raw.slice(1, -1);
+  for (let i = 0; i < body.length; i++) {
+    if (body[i] !== '\\') {
+      bytes.push(...encoder.encode(body[i]));
+    } else if (/[0-7]{3}/.test(body.slice(i + 1, i + 4))) {
+      bytes.push(parseInt(body.slice(i + 1, i + 4), 8));
+      i += 3;
+    } else {
+      bytes.push(...encoder.encode(C_ESCAPES[body[i + 1]] ?? body[i + 1] ?? ''));
+      i++;
+    }
+  }
+  return new TextDecoder().decode(new Uint8Array(bytes));
+}
+
+/** Path from a `---`/`+++` line; null for /dev/null. */
+function markerPath(rest: string): string | null {
+  const p = unquotePath(rest.replace(/\t.*$/, ''));
+  if (p === '/dev/null') return null;
+  return p.replace(/^[ab]\//, '');
+}
+
+/** Paths from `diff --git a/X b/Y` when both sides are the same unquoted path. */
+function gitHeaderPath(rest: string): string | null {
+  if (rest.startsWith('"')) {
+    const m = /^("(?:[^"\\]|\\.)*") /.exec(rest);
+    return m ? unquotePath(m[1]).replace(/^a\//, '') : null;
+  }
+  const len = (rest.length - 1) / 2;
+  const a = rest.slice(0, len);
+  const b = rest.slice(len + 1);
+  return a.startsWith('a/') && b.startsWith('b/') && a.slice(2) === b.slice(2) ? a.slice(2) : null;
+}
+
+function newFile(): DiffFile {
+  return {
+    old_path: null,
+    new_path: null,
+    status: 'M',
+    old_mode: null,
+    new_mode: null,
+    binary: false,
+    hunks: [],
+  };
+}
+
+/**
+ * Parse `git diff` output — one or more files, with or without `diff --git`
+ * headers. Hunk line counts decide where a hunk ends, so removed lines that
+ * look like headers (`--- x`) are not misread.
+ */
+export function parseDiffFiles(raw: string): DiffFile[] {
+  const files: DiffFile[] = [];
+  let file: DiffFile | null = null;
+  let headerPath: string | null = null;
+  let hunk: DiffHunk | null = null;
+  let oldLeft = 0;
+  let newLeft = 0;
+  let oldNo = 0;
+  let newNo = 0;
+
+  const finishFile = () => {
+    if (!file) return;
+    if (file.old_path === null && file.status !== 'A') file.old_path = headerPath;
+    if (file.new_path === null && file.status !== 'D') file.new_path = headerPath;
+    if (file.status === 'M' && file.old_path === null && file.new_path !== null) file.status = 'A';
+    if (file.status === 'M' && file.new_path === null && file.old_path !== null) file.status = 'D';
+    files.push(file);
+    file = null;
+    headerPath = null;
+  };
+
+  const lines = raw.split('\n');
+  if (lines[lines.length - 1] === '') lines.pop();
+  for (const line of lines) {
+    if (hunk && (oldLeft > 0 || newLeft > 0)) {
+      const prefix = line[0] ?? ' ';
+      const content = line.slice(1);
+      if (prefix === '+') {
+        hunk.lines.push({ kind: 'add', old_no: null, new_no: newNo++, content });
+        newLeft--;
+        continue;
+      }
+      if (prefix === '-') {
+        hunk.lines.push({ kind: 'remove', old_no: oldNo++, new_no: null, content });
+        oldLeft--;
+        continue;
+      }
+      if (prefix === ' ') {
+        hunk.lines.push({ kind: 'context', old_no: oldNo++, new_no: newNo++, content });
+        oldLeft--;
+        newLeft--;
+        continue;
+      }
+    }
+    if (line.startsWith('\\')) {
+      const last = hunk?.lines[hunk.lines.length - 1];
+      if (last) last.no_newline = true;
+      continue;
+    }
+
+    const m = HUNK_HEADER.exec(line);
+    if (m) {
+      if (!file) file = newFile();
+      hunk = {
+        header: line,
+        old_start: Number(m[1]),
+        old_count: m[2] === undefined ? 1 : Number(m[2]),
+        new_start: Number(m[3]),
+        new_count: m[4] === undefined ? 1 : Number(m[4]),
+        lines: [],
+      };
+      file.hunks.push(hunk);
+      oldLeft = hunk.old_count;
+      newLeft = hunk.new_count;
+      oldNo = hunk.old_start;
+      newNo = hunk.new_start;
+      continue;
+    }
+
+    if (line.startsWith('diff --git ')) {
+      finishFile();
+      file = newFile();
+      headerPath = gitHeaderPath(line.slice('diff --git '.length));
+      hunk = null;
+      continue;
+    }
+    if (line.startsWith('--- ')) {
+      // A header-less diff (or the next file of one) starts at its `---` line
+      if (!file || file.hunks.length > 0) {
+        finishFile();
+        file = newFile();
+      }
+      file.old_path = markerPath(line.slice(4));
+      if (file.old_path === null) file.status = 'A';
+      hunk = null;
+      continue;
+    }
+    if (!file) continue;
+    if (line.startsWith('+++ ')) {
+      file.new_path = markerPath(line.slice(4));
+      if (file.new_path === null) file.status = 'D';
+    } else if (line.startsWith('new file mode ')) {
+      file.status = 'A';
+      file.new_mode = line.slice('new file mode '.length);
+    } else if (line.startsWith('deleted file mode ')) {
+      file.status = 'D';
+      file.old_mode = line.slice('deleted file mode '.length);
+    } else if (line.startsWith('old mode ')) {
+      file.old_mode = line.slice('old mode '.length);
+    } else if (line.startsWith('new mode ')) {
+      file.new_mode = line.slice('new mode '.length);
+    } else if (line.startsWith('rename from ') || line.startsWith('copy from ')) {
+      file.status = line.startsWith('rename') ? 'R' : 'C';
+      file.old_path = unquotePath(line.slice(line.indexOf(' from ') + 6));
+    } else if (line.startsWith('rename to ') || line.startsWith('copy to ')) {
+      file.new_path = unquotePath(line.slice(line.indexOf(' to ') + 4));
+    } else if (line.startsWith('index ')) {
+      const mode = / (\d{6})$/.exec(line);
+      if (mode) {
+        file.old_mode ??= mode[1];
+        file.new_mode ??= mode[1];
+      }
+    } else if (line.startsWith('Binary files ') || line === 'GIT binary patch') {
+      file.binary = true;
+    }
+  }
+  finishFile();
+  return files;
+}
+
+/** Parse the hunks of a unified diff, ignoring file boundaries. */
+export function parseHunks(patch: string): DiffHunk[] {
+  return parseDiffFiles(patch).flatMap((f) => f.hunks);
+}
+
+function tokenize(text: string): string[] {
+  return text.match(/\w+|\s+|[^\w\s]/g) ?? [];
+}
+
+/** Mark tokens of `a` and `b` that are not part of their longest common subsequence. */
+function diffTokens(a: string[], b: string[]): [boolean[], boolean[]] {
+  const n = a.length;
+  const m = b.length;
+  const lcs: number[][] = Array.from({ length: n + 1 }, () => new Array<number>(m + 1).fill(0));
+  for (let i = n - 1; i >= 0; i--) {
+    for (let j = m - 1; j >= 0; j--) {
+      lcs[i][j] = a[i] === b[j] ? lcs[i + 1][j + 1] + 1 : Math.max(lcs[i + 1][j], lcs[i][j + 1]);
+    }
+  }
+  const changedA = new Array<boolean>(n).fill(true);
+  const changedB = new Array<boolean>(m).fill(true);
+  let i = 0;
+  let j = 0;
+  while (i < n && j < m) {
+    if (a[i] === b[j]) {
+      changedA[i++] = false;
+      changedB[j++] = false;
+    } else if (lcs[i + 1][j] >= lcs[i][j + 1]) {
+      i++;
+    } else {
+      j++;
+    }
+  }
+  return [changedA, changedB];
+}
+
+function toRanges(tokens: string[], changed: boolean[]): [number, number][] {
+  const ranges: [number, number][] = [];
+  let offset = 0;
+  tokens.forEach((token, k) => {
+    const end = offset + token.length;
+    if (changed[k]) {
+      const last = ranges[ranges.length - 1];
+      if (last && last[1] === offset) last[1] = end;
+      else ranges.push([offset, end]);
+    }
+    offset = end;
+  });
+  return ranges;
+}
+
+function changedLength(ranges: [number, number][]): number {
+  return ranges.reduce((sum, [start, end]) => sum + end - start, 0);
+}
+
+/** Word-level change ranges between a removed and an added line, or null if too dissimilar. */
+export function intralineChanges(
+  oldText: string,
+  newText: string,
+): { old: [number, number][]; new: [number, number][] } | null {
+  if (oldText.length > MAX_INTRALINE_LENGTH || newText.length > MAX_INTRALINE_LENGTH) return null;
+  const a = tokenize(oldText);
+  const b = tokenize(newText);
+  const [changedA, changedB] = diffTokens(a, b);
+  const oldRanges = toRanges(a, changedA);
+  const newRanges = toRanges(b, changedB);
+  const unchanged = oldText.length - changedLength(oldRanges);
+  const total = Math.max(oldText.length, newText.length);
+  if (total > 0 && unchanged / total < MIN_SIMILARITY) return null;
+  return { old: oldRanges, new: newRanges };
+}
+
+/**
+ * Pair each run of removed lines with the added lines that follow it
+ * (first with first, and so on) and record their intraline changes.
+ */
+export function addIntralineChanges(hunk: DiffHunk): void {
+  const { lines } = hunk;
+  let i = 0;
+  while (i < lines.length) {
+    if (lines[i].kind !== 'remove') {
+      i++;
+      continue;
+    }
+    const removeStart = i;
+    while (i < lines.length && lines[i].kind === 'remove') i++;
+    const addStart = i;
+    while (i < lines.length && lines[i].kind === 'add') i++;
+    const pairs = Math.min(addStart - removeStart, i - addStart);
+    for (let k = 0; k < pairs; k++) {
+      const removed = lines[removeStart + k];
+      const added = lines[addStart + k];
+      const changes = intralineChanges(removed.content, added.content);
+      if (!changes) continue;
+      removed.changes = changes.old;
+      added.changes = changes.new;
+    }
+  }
+}
@@ -0,0 +1,73 @@
+import { safeStorage } from 'electron';
+import fs from 'fs';
+import path from 'path';
+
+// Forge API tokens, one per host they were entered for, encrypted with the
+// OS keychain (safeStorage) in forge-tokens.json under the app data dir. They
+// stay in the main process: the renderer only learns which hosts have one.
+
+const HOST_RE = /^[a-z0-9]([a-z0-9.-]*[a-z0-9])?(:\d+)?$/;
+
+let tokensPath: string | null = null;
+
+export function setForgeTokenRoot(dir: string): void {
+  tokensPath = path.join(dir, 'forge-tokens.json');
+}
+
+/** Lowercased host, or an error for anything that isn't a bare `host[:port]`. */
+export function normalizeForgeHost(host: string): string {
+  const normalized = host.trim().toLowerCase();
+  if (!HOST_RE.test(normalized)) throw new Error(`Invalid host: ${host}`);
+  return normalized;
+}
+
+function readStore(): Record<string, string> {
+  if (!tokensPath) return {};
+  try {
+    const parsed = JSON.parse(fs.readFileSync(tokensPath, 'utf8')) as unknown;
+    return parsed && typeof parsed === 'object' ? (parsed as Record<string, string>) : {};
+  } catch {
+    return {};
+  }
+}
+
+function writeStore(store: Record<string, string>): void {
+  if (!tokensPath) throw new Error('Token storage is not available');
+  const tmpPath = tokensPath + '.tmp';
+  fs.writeFileSync(tmpPath, JSON.stringify(store), { encoding: 'utf8', mode: 0o600 });
+  fs.renameSync(tmpPath, tokensPath);
+}
+
+/** Hosts that have a saved token, sorted. */
+export function listForgeTokenHosts(): string[] {
+  return Object.keys(readStore()).sort();
+}
+
+/** Save (or with an empty token, remove) the token sent to `host` and nowhere else. */
+export function setForgeToken(host: string, token: string): void {
+  const key = normalizeForgeHost(host);
+  const store = readStore();
+  if (token.trim()) {
+    if (!safeStorage.isEncryptionAvailable()) {
+      throw new Error(
+        'No OS keychain is available to store the token; log in to the forge CLI instead',
+      );
+    }
+    store[key] = safeStorage.encryptString(token.trim()).toString('base64');
+  } else {
+    delete store[key];
+  }
+  writeStore(store);
+}
+
+/** The token entered for exactly this host, if any. */
+export function getForgeToken(host: string): string | undefined {
+  const encrypted = readStore()[host.toLowerCase()];
+  if (!encrypted || !safeStorage.isEncryptionAvailable()) return undefined;
+  try {
+    return safeStorage.decryptString(Buffer.from(encrypted, 'base64'));
+  } catch (err) {
+    console.warn(`Failed to decrypt the token for ${host}:`, err);
+    return undefined;
+  }
+}
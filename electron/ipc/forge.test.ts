@@ -0,0 +1,123 @@
+import { describe, it, expect } from 'vitest';
+import {
+  forgeTokenFor,
+  githubCheckState,
+  gitlabPipelineState,
+  parseForgeRemote,
+  parseRemoteList,
+  summarizeCi,
+} from './forge.js';
+
+describe('parseForgeRemote', () => {
+  it('parses https, scp-like and ssh GitHub remotes', () => {
+    const expected = {
+      kind: 'github',
+      host: 'github.com',
+      owner: 'johannesjo',
+      repo: 'parallel-code',
+    };
+    expect(parseForgeRemote('https://github.com/johannesjo/parallel-code.git')).toEqual(expected);
+    expect(parseForgeRemote('git@github.com:johannesjo/parallel-code.git\n')).toEqual(expected);
+    expect(parseForgeRemote('ssh://git@github.com/johannesjo/parallel-code')).toEqual(expected);
+    const withUser = 'https://x-access-token@github.com/johannesjo/parallel-code/';
+    expect(parseForgeRemote(withUser)).toEqual(expected);
+  });
+
+  it('keeps dots in repo names', () => {
+    expect(parseForgeRemote('git@github.com:org/site.github.io.git')?.repo).toBe(
+      'site.github.io',
+    );
+  });
+
+  it('detects GitLab, including nested groups and self-hosted instances', () => {
+    expect(parseForgeRemote('git@gitlab.com:group/sub/repo.git')).toEqual({
+      kind: 'gitlab',
+      host: 'gitlab.com',
+      owner: 'group/sub',
+      repo: 'repo',
+    });
+    expect(parseForgeRemote('ssh://git@gitlab.example.com:2222/team/repo.git')).toEqual({
+      kind: 'gitlab',
+      host: 'gitlab.example.com',
+      owner: 'team',
+      repo: 'repo',
+    });
+  });
+
+  it('detects Bitbucket', () => {
+    expect(parseForgeRemote('https://me@bitbucket.org/workspace/repo.git')).toEqual({
+      kind: 'bitbucket',
+      host: 'bitbucket.org',
+      owner: 'workspace',
+      repo: 'repo',
+    });
+  });
+
+  it('rejects unknown hosts and local paths', () => {
+    expect(parseForgeRemote('git@example.com:org/repo.git')).toBeNull();
+    expect(parseForgeRemote('/srv/git/repo.git')).toBeNull();
+    expect(parseForgeRemote('git@github.com:repo.git')).toBeNull();
+  });
+});
+
+describe('forgeTokenFor', () => {
+  const tokens = (host: string) => ({ 'gitlab.example.com': 'glpat-self' })[host];
+  const env = { GITLAB_TOKEN: 'glpat-env' };
+  const remote = (host: string) => ({ kind: 'gitlab' as const, host, owner: 'o', repo: 'r' });
+
+  it('sends a token only to the host it was entered for', () => {
+    expect(forgeTokenFor(remote('gitlab.example.com'), tokens, env)).toBe('glpat-self');
+    expect(forgeTokenFor(remote('gitlab.attacker.io'), tokens, env)).toBeUndefined();
+  });
+
+  it("sends the environment token only to the forge's own host", () => {
+    expect(forgeTokenFor(remote('gitlab.com'), tokens, env)).toBe('glpat-env');
+    expect(forgeTokenFor(remote('gitlab.com'), tokens, {})).toBeUndefined();
+  });
+});
+
+describe('CI states', () => {
+  it('maps GitHub check runs and GitLab pipelines', () => {
+    expect(githubCheckState('in_progress', null)).toBe('pending');
+    expect(githubCheckState('completed', 'skipped')).toBe('success');
+    expect(githubCheckState('completed', 'timed_out')).toBe('failure');
+    expect(gitlabPipelineState('running')).toBe('pending');
+    expect(gitlabPipelineState('canceled')).toBe('failure');
+  });
+
+  it('lets a failure win over pending and pending over success', () => {
+    expect(summarizeCi(['success', 'pending', 'failure'])).toEqual({
+      state: 'failure',
+      passed: 1,
+      failed: 1,
+      pending: 1,
+    });
+    expect(summarizeCi(['success', 'pending']).state).toBe('pending');
+    expect(summarizeCi(['success', 'none']).state).toBe('success');
+    expect(summarizeCi([]).state).toBe('none');
+  });
+});
+
+describe('parseRemoteList', () => {
+  it('merges fetch and push lines per remote', () => {
+    const output = [
+      'origin\tgit@github.com:org/repo.git (fetch)',
+      'origin\tgit@github.com:org/repo.git (push)',
+      'myfork\thttps://github.com/me/repo.git (fetch)',
+      'myfork\tgit@github.com:me/repo.git (push)',
+      '',
+    ].join('\n');
+    expect(parseRemoteList(output)).toEqual([
+      {
+        name: 'origin',
+        fetch_url: 'git@github.com:org/repo.git',
+        push_url: 'git@github.com:org/repo.git',
+      },
+      {
+        name: 'myfork',
+        fetch_url: 'https://github.com/me/repo.git',
+        push_url: 'git@github.com:me/repo.git',
+      },
+    ]);
+  });
+});
@@ -0,0 +1,592 @@
+import { execFile } from 'child_process';
+import { promisify } from 'util';
+import { getMainBranch, recordPushedCommit } from './git.js';
+
+const exec = promisify(execFile);
+
+const PUSH_TIMEOUT_MS = 120_000;
+const CLI_TIMEOUT_MS = 60_000;
+const CI_CACHE_MS = 30_000;
+
+export type ForgeKind = 'github' | 'gitlab' | 'bitbucket';
+
+export interface ForgeRemote {
+  kind: ForgeKind;
+  host: string;
+  /** Everything before the repo name; GitLab allows nested groups here. */
+  owner: string;
+  repo: string;
+}
+
+export interface ForgeInfo {
+  kind: ForgeKind;
+  name: string;
+  /** What the forge calls a review request, e.g. "merge request". */
+  request_label: string;
+}
+
+export interface PullRequestResult {
+  kind: ForgeKind;
+  url: string;
+  number: number;
+}
+
+export type CiState = 'success' | 'failure' | 'pending' | 'none';
+
+export interface CiStatus {
+  state: CiState;
+  passed: number;
+  failed: number;
+  pending: number;
+  /** Page of the first failing (else first) check or pipeline. */
+  url: string | null;
+  /** Why there is no status, e.g. the branch was never pushed. */
+  error: string | null;
+  /** The remote's host has no saved token and isn't the forge's own, so it wasn't asked. */
+  host_not_configured: boolean;
+  checked_at: number;
+}
+
+/** The API token entered for a host, if any. */
+export type ForgeTokenLookup = (host: string) => string | undefined;
+
+export interface GitRemote {
+  name: string;
+  fetch_url: string;
+  push_url: string;
+}
+
+interface CreatePullRequestArgs {
+  projectRoot: string;
+  branch: string;
+  /** Remote to push to; a fork of origin for fork-based workflows. Defaults to origin. */
+  remote?: string;
+  title: string;
+  body: string;
+  draft: boolean;
+  /** API tokens per host, used when the forge's CLI is missing or logged out. */
+  tokens?: ForgeTokenLookup;
+}
+
+interface ForgeRequest extends CreatePullRequestArgs {
+  base: string;
+  /** Repository the branch was pushed to; differs from the target for forks. */
+  head: ForgeRemote;
+}
+
+function isFork(target: ForgeRemote, head: ForgeRemote): boolean {
+  return target.owner !== head.owner || target.repo !== head.repo;
+}
+
+interface ForgeProvider {
+  info: ForgeInfo;
+  /** The forge's own host; the only one its token environment variables are sent to. */
+  defaultHost: string;
+  /** Environment variables checked for a token when none is configured. */
+  tokenEnv: string[];
+  matchesHost(host: string): boolean;
+  /** Name of the forge's CLI, when there is one worth trying first. */
+  cli?: string;
+  apiBase(remote: ForgeRemote): string;
+  authHeaders(token: string): Record<string, string>;
+  /** Create through the forge's CLI; null when it is missing or not logged in. */
+  createWithCli?(remote: ForgeRemote, req: ForgeRequest): Promise<PullRequestResult | null>;
+  createWithApi(remote: ForgeRemote, req: ForgeRequest, token: string): Promise<PullRequestResult>;
+  /** Checks or pipelines of a pushed commit, with the page to open for details. */
+  ciChecks(
+    remote: ForgeRemote,
+    sha: string,
+    get: (path: string) => Promise<unknown>,
+  ): Promise<{ checks: { state: CiState; url: string | null }[] }>;
+}
+
+const REMOTE_RE =
+  /^(?:(?:https?|ssh):\/\/(?:[^@/]+@)?([^/:]+)(?::\d+)?\/|(?:[^@/]+@)?([^/:]+):)([^/].*?)(?:\.git)?\/?$/;
+
+function numberFromUrl(url: string): number {
+  const m = url.match(/\/(?:pull|pull-requests|merge_requests)\/(\d+)/);
+  return m ? Number(m[1]) : 0;
+}
+
+/** Run a forge CLI that prints the new request's URL; null when unusable. */
+async function runCli(
+  command: string,
+  args: string[],
+  cwd: string,
+  notLoggedIn: RegExp,
+): Promise<string | null> {
+  try {
+    const { stdout } = await exec(command, args, { cwd, timeout: CLI_TIMEOUT_MS });
+    const urls = stdout.match(/https?:\/\/\S+/g);
+    return urls ? urls[urls.length - 1] : stdout.trim();
+  } catch (err) {
+    const e = err as { code?: string | number; stderr?: string };
+    if (e.code === 'ENOENT') return null;
+    if (notLoggedIn.test(e.stderr ?? '')) return null;
+    throw new Error((e.stderr ?? '').trim() || String(err));
+  }
+}
+
+export function githubCheckState(status: string, conclusion: string | null): CiState {
+  if (status !== 'completed') return 'pending';
+  return ['success', 'neutral', 'skipped'].includes(conclusion ?? '') ? 'success' : 'failure';
+}
+
+export function githubStatusState(state: string): CiState {
+  if (state === 'success') return 'success';
+  return state === 'pending' ? 'pending' : 'failure';
+}
+
+export function gitlabPipelineState(status: string): CiState {
+  if (status === 'success') return 'success';
+  if (status === 'failed' || status === 'canceled') return 'failure';
+  return status === 'skipped' ? 'none' : 'pending';
+}
+
+export function bitbucketStatusState(state: string): CiState {
+  if (state === 'SUCCESSFUL') return 'success';
+  return state === 'INPROGRESS' ? 'pending' : 'failure';
+}
+
+/** Overall state of a commit's checks: any failure wins, then anything still running. */
+export function summarizeCi(
+  states: CiState[],
+): Pick<CiStatus, 'state' | 'passed' | 'failed' | 'pending'> {
+  const passed = states.filter((s) => s === 'success').length;
+  const failed = states.filter((s) => s === 'failure').length;
+  const pending = states.filter((s) => s === 'pending').length;
+  const state: CiState =
+    failed > 0 ? 'failure' : pending > 0 ? 'pending' : passed > 0 ? 'success' : 'none';
+  return { state, passed, failed, pending };
+}
+
+async function postJson<T>(
+  url: string,
+  headers: Record<string, string>,
+  body: unknown,
+): Promise<{ ok: boolean; status: string; data: T }> {
+  const res = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json', 'User-Agent': 'parallel-code', ...headers },
+    body: JSON.stringify(body),
+  });
+  const data = (await res.json().catch(() => ({}))) as T;
+  return { ok: res.ok, status: res.statusText, data };
+}
+
+const github: ForgeProvider = {
+  info: { kind: 'github', name: 'GitHub', request_label: 'pull request' },
+  defaultHost: 'github.com',
+  tokenEnv: ['GH_TOKEN', 'GITHUB_TOKEN'],
+  matchesHost: (host) => host === 'github.com',
+  cli: 'gh',
+  apiBase: () => 'https://api.github.com/',
+  authHeaders: (token) => ({
+    Accept: 'application/vnd.github+json',
+    Authorization: `Bearer ${token}`,
+    'X-GitHub-Api-Version': '2022-11-28',
+  }),
+
+  async createWithCli(remote, req) {
+    const head = isFork(remote, req.head) ? `${req.head.owner}:${req.branch}` : req.branch;
+    // --repo keeps gh from asking which remote is the base when there are several
+    const args = ['pr', 'create', '--repo', `${remote.owner}/${remote.repo}`];
+    args.push('--head', head, '--base', req.base, '--title', req.title, '--body', req.body);
+    if (req.draft) args.push('--draft');
+    const url = await runCli('gh', args, req.projectRoot, /gh auth login|not logged in/i);
+    return url === null ? null : { kind: 'github', url, number: numberFromUrl(url) };
+  },
+
+  async createWithApi(remote, req, token) {
+    const { ok, status, data } = await postJson<{
+      html_url?: string;
+      number?: number;
+      message?: string;
+      errors?: { message?: string }[];
+    }>(
+      `${github.apiBase(remote)}repos/${remote.owner}/${remote.repo}/pulls`,
+      github.authHeaders(token),
+      {
+        title: req.title,
+        body: req.body,
+        head: isFork(remote, req.head) ? `${req.head.owner}:${req.branch}` : req.branch,
+        base: req.base,
+        draft: req.draft,
+      },
+    );
+    if (!ok || !data.html_url) {
+      const detail = data.errors?.map((e) => e.message).filter(Boolean).join('; ');
+      throw new Error(`GitHub API: ${data.message ?? status}${detail ? ` (${detail})` : ''}`);
+    }
+    const url = data.html_url;
+    return { kind: 'github', url, number: data.number ?? numberFromUrl(url) };
+  },
+
+  async ciChecks(remote, sha, get) {
+    const repo = `repos/${remote.owner}/${remote.repo}/commits/${sha}`;
+    const [runs, combined] = (await Promise.all([
+      get(`${repo}/check-runs?per_page=100`),
+      get(`${repo}/status`),
+    ])) as [
+      { check_runs?: { status: string; conclusion: string | null; html_url?: string }[] },
+      { statuses?: { state: string; target_url?: string | null }[] },
+    ];
+    return {
+      checks: [
+        ...(runs.check_runs ?? []).map((c) => ({
+          state: githubCheckState(c.status, c.conclusion),
+          url: c.html_url ?? null,
+        })),
+        // Legacy commit statuses, still used by many external CI services
+        ...(combined.statuses ?? []).map((st) => ({
+          state: githubStatusState(st.state),
+          url: st.target_url ?? null,
+        })),
+      ],
+    };
+  },
+};
+
+const gitlab: ForgeProvider = {
+  info: { kind: 'gitlab', name: 'GitLab', request_label: 'merge request' },
+  defaultHost: 'gitlab.com',
+  tokenEnv: ['GITLAB_TOKEN'],
+  // gitlab.com and self-hosted instances that carry the name in their host
+  matchesHost: (host) => /(^|\.)gitlab\./.test(host),
+  cli: 'glab',
+  apiBase: (remote) => `https://${remote.host}/api/v4/`,
+  authHeaders: (token) => ({ 'PRIVATE-TOKEN': token }),
+
+  async createWithCli(remote, req) {
+    const args = ['mr', 'create', '--source-branch', req.branch, '--target-branch', req.base];
+    args.push('--title', req.title, '--description', req.body, '--yes');
+    if (isFork(remote, req.head)) {
+      const url = (r: ForgeRemote) => `https://${r.host}/${r.owner}/${r.repo}`;
+      args.push('--repo', url(remote), '--head', url(req.head));
+    }
+    if (req.draft) args.push('--draft');
+    const url = await runCli('glab', args, req.projectRoot, /glab auth login|401|unauthorized/i);
+    return url === null ? null : { kind: 'gitlab', url, number: numberFromUrl(url) };
+  },
+
+  async createWithApi(remote, req, token) {
+    const projectPath = (r: ForgeRemote) => encodeURIComponent(`${r.owner}/${r.repo}`);
+    // Fork MRs are created on the fork and name the upstream project by id
+    let targetProjectId: number | undefined;
+    if (isFork(remote, req.head)) {
+      const res = await fetch(`${gitlab.apiBase(remote)}projects/${projectPath(remote)}`, {
+        headers: { 'User-Agent': 'parallel-code', ...gitlab.authHeaders(token) },
+      });
+      const target = (await res.json().catch(() => ({}))) as { id?: number };
+      if (!res.ok || !target.id) throw new Error(`GitLab API: ${res.status} ${res.statusText}`);
+      targetProjectId = target.id;
+    }
+    const { ok, status, data } = await postJson<{
+      web_url?: string;
+      iid?: number;
+      message?: string | string[];
+      error?: string;
+    }>(
+      `${gitlab.apiBase(remote)}projects/${projectPath(req.head)}/merge_requests`,
+      gitlab.authHeaders(token),
+      {
+        target_project_id: targetProjectId,
+        source_branch: req.branch,
+        target_branch: req.base,
+        // The Draft: prefix works on every GitLab version, the draft field does not
+        title: req.draft ? `Draft: ${req.title}` : req.title,
+        description: req.body,
+      },
+    );
+    if (!ok || !data.web_url) {
+      const message = [data.message ?? data.error ?? status].flat().join('; ');
+      throw new Error(`GitLab API: ${message}`);
+    }
+    const url = data.web_url;
+    return { kind: 'gitlab', url, number: data.iid ?? numberFromUrl(url) };
+  },
+
+  async ciChecks(remote, sha, get) {
+    const project = encodeURIComponent(`${remote.owner}/${remote.repo}`);
+    // The newest pipeline for the commit is the one that counts
+    const pipelines = (await get(`projects/${project}/pipelines?sha=${sha}&per_page=1`)) as {
+      status: string;
+      web_url?: string;
+    }[];
+    return {
+      checks: (Array.isArray(pipelines) ? pipelines : []).map((p) => ({
+        state: gitlabPipelineState(p.status),
+        url: p.web_url ?? null,
+      })),
+    };
+  },
+};
+
+const bitbucket: ForgeProvider = {
+  info: { kind: 'bitbucket', name: 'Bitbucket', request_label: 'pull request' },
+  defaultHost: 'bitbucket.org',
+  tokenEnv: ['BITBUCKET_TOKEN'],
+  matchesHost: (host) => host === 'bitbucket.org',
+  apiBase: () => 'https://api.bitbucket.org/2.0/',
+  // "user:app-password" uses basic auth, anything else is an access token
+  authHeaders: (token) => ({
+    Authorization: token.includes(':')
+      ? `Basic ${Buffer.from(token).toString('base64')}`
+      : `Bearer ${token}`,
+  }),
+
+  async createWithApi(remote, req, token) {
+    const { ok, status, data } = await postJson<{
+      id?: number;
+      links?: { html?: { href?: string } };
+      error?: { message?: string };
+    }>(
+      `${bitbucket.apiBase(remote)}repositories/${remote.owner}/${remote.repo}/pullrequests`,
+      bitbucket.authHeaders(token),
+      {
+        title: req.title,
+        description: req.body,
+        source: {
+          branch: { name: req.branch },
+          repository: { full_name: `${req.head.owner}/${req.head.repo}` },
+        },
+        destination: { branch: { name: req.base } },
+        draft: req.draft,
+      },
+    );
+    const url = data.links?.html?.href;
+    if (!ok || !url) throw new Error(`Bitbucket API: ${data.error?.message ?? status}`);
+    return { kind: 'bitbucket', url, number: data.id ?? numberFromUrl(url) };
+  },
+
+  async ciChecks(remote, sha, get) {
+    const path = `repositories/${remote.owner}/${remote.repo}/commit/${sha}/statuses?pagelen=100`;
+    const data = (await get(path)) as { values?: { state: string; url?: string }[] };
+    return {
+      checks: (data.values ?? []).map((st) => ({
+        state: bitbucketStatusState(st.state),
+        url: st.url ?? null,
+      })),
+    };
+  },
+};
+
+const PROVIDERS: ForgeProvider[] = [github, gitlab, bitbucket];
+
+/** Forge, host and repo of a remote URL (https, ssh or scp-like form). */
+export function parseForgeRemote(remoteUrl: string): ForgeRemote | null {
+  const m = remoteUrl.trim().match(REMOTE_RE);
+  if (!m) return null;
+  const host = (m[1] ?? m[2]).toLowerCase();
+  const provider = PROVIDERS.find((p) => p.matchesHost(host));
+  const slash = m[3].lastIndexOf('/');
+  if (!provider || slash <= 0) return null;
+  return {
+    kind: provider.info.kind,
+    host,
+    owner: m[3].slice(0, slash),
+    repo: m[3].slice(slash + 1),
+  };
+}
+
+/**
+ * The token to send to a remote's host: the one entered for exactly that
+ * host, else the forge's token environment variable when the host is the
+ * forge's own. Self-hosted instances only get a token entered for them.
+ */
+export function forgeTokenFor(
+  remote: ForgeRemote,
+  tokens: ForgeTokenLookup,
+  env: Record<string, string | undefined> = process.env,
+): string | undefined {
+  const provider = PROVIDERS.find((p) => p.info.kind === remote.kind);
+  if (!provider) return undefined;
+  const saved = tokens(remote.host);
+  if (saved) return saved;
+  if (remote.host !== provider.defaultHost) return undefined;
+  return provider.tokenEnv.map((v) => env[v]).find(Boolean);
+}
+
+/** Parse `git remote -v` into one entry per remote, in listed order. */
+export function parseRemoteList(output: string): GitRemote[] {
+  const remotes = new Map<string, GitRemote>();
+  for (const line of output.split('\n')) {
+    const m = line.match(/^(\S+)\s+(.+?)\s+\((fetch|push)\)$/);
+    if (!m) continue;
+    const remote = remotes.get(m[1]) ?? { name: m[1], fetch_url: m[2], push_url: m[2] };
+    if (m[3] === 'fetch') remote.fetch_url = m[2];
+    else remote.push_url = m[2];
+    remotes.set(m[1], remote);
+  }
+  return [...remotes.values()];
+}
+
+export async function listRemotes(projectRoot: string): Promise<GitRemote[]> {
+  const { stdout } = await exec('git', ['remote', '-v'], { cwd: projectRoot });
+  return parseRemoteList(stdout);
+}
+
+async function forgeRemote(projectRoot: string, name = 'origin'): Promise<ForgeRemote | null> {
+  try {
+    const { stdout } = await exec('git', ['remote', 'get-url', '--', name], { cwd: projectRoot });
+    return parseForgeRemote(stdout);
+  } catch {
+    return null;
+  }
+}
+
+/** The forge hosting the project's origin, or null when it is not a known one. */
+export async function detectForge(projectRoot: string): Promise<ForgeInfo | null> {
+  const remote = await forgeRemote(projectRoot);
+  return remote ? (PROVIDERS.find((p) => p.info.kind === remote.kind)?.info ?? null) : null;
+}
+
+/**
+ * Push the branch (to origin or a fork) and open a pull or merge request
+ * against the main branch of the repository origin points to. Uses the
+ * forge's CLI (gh, glab) when it is installed and logged in, otherwise its
+ * REST API with the configured token or the forge's token environment variable.
+ */
+export async function createPullRequest(args: CreatePullRequestArgs): Promise<PullRequestResult> {
+  const remote = await forgeRemote(args.projectRoot);
+  const provider = remote && PROVIDERS.find((p) => p.info.kind === remote.kind);
+  if (!remote || !provider) {
+    throw new Error('origin is not a GitHub, GitLab or Bitbucket repository');
+  }
+  const pushRemote = args.remote ?? 'origin';
+  const head = pushRemote === 'origin' ? remote : await forgeRemote(args.projectRoot, pushRemote);
+  if (!head || head.kind !== remote.kind) {
+    throw new Error(`${pushRemote} is not a ${provider.info.name} repository like origin`);
+  }
+
+  try {
+    await exec('git', ['push', '-u', pushRemote, '--', args.branch], {
+      cwd: args.projectRoot,
+      timeout: PUSH_TIMEOUT_MS,
+      // No terminal to answer a credential prompt; fail instead of waiting for the timeout
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+    });
+  } catch (err) {
+    const stderr = (err as { stderr?: string }).stderr?.trim();
+    throw new Error(`git push failed: ${stderr?.split('\n').pop() || err}`);
+  }
+  await recordPushedCommit(args.projectRoot, pushRemote, args.branch);
+
+  const req: ForgeRequest = { ...args, base: await getMainBranch(args.projectRoot), head };
+  const viaCli = await provider.createWithCli?.(remote, req);
+  if (viaCli) return viaCli;
+
+  const token = forgeTokenFor(remote, args.tokens ?? (() => undefined));
+  if (!token) {
+    const cli = provider.cli ? `Install and log in to the ${provider.cli} CLI, or set` : 'Set';
+    throw new Error(`${cli} a token for ${remote.host} in Settings`);
+  }
+  return provider.createWithApi(remote, req, token);
+}
+
+async function getJson(
+  provider: ForgeProvider,
+  remote: ForgeRemote,
+  path: string,
+  projectRoot: string,
+  token: string | undefined,
+): Promise<unknown> {
+  if (token) {
+    const res = await fetch(provider.apiBase(remote) + path, {
+      headers: { 'User-Agent': 'parallel-code', ...provider.authHeaders(token) },
+    });
+    if (!res.ok) throw new Error(`${provider.info.name} API: ${res.status} ${res.statusText}`);
+    return res.json();
+  }
+  if (!provider.cli) throw new Error(`No ${provider.info.name} token set`);
+  try {
+    const { stdout } = await exec(provider.cli, ['api', path], {
+      cwd: projectRoot,
+      timeout: CLI_TIMEOUT_MS,
+    });
+    return JSON.parse(stdout);
+  } catch (err) {
+    const e = err as { code?: string | number; stderr?: string };
+    if (e.code === 'ENOENT') {
+      throw new Error(`Install the ${provider.cli} CLI or set a ${provider.info.name} token`);
+    }
+    throw new Error((e.stderr ?? '').trim() || String(err));
+  }
+}
+
+const ciCache = new Map<string, { sha: string; status: CiStatus }>();
+
+function noCi(error: string, hostNotConfigured = false): CiStatus {
+  return {
+    state: 'none',
+    passed: 0,
+    failed: 0,
+    pending: 0,
+    url: null,
+    error,
+    host_not_configured: hostNotConfigured,
+    checked_at: Date.now(),
+  };
+}
+
+/**
+ * CI state of the branch as last pushed to the remote (origin by default):
+ * GitHub checks and commit statuses, the latest GitLab pipeline, or Bitbucket
+ * build statuses. Results are cached briefly per branch unless `force` is set.
+ * Only the forge's own host or one the user saved a token for is asked: this
+ * runs unprompted, and a remote URL may name any host.
+ */
+export async function getCiStatus(
+  projectRoot: string,
+  branch: string,
+  tokens: ForgeTokenLookup = () => undefined,
+  force = false,
+  remoteName = 'origin',
+): Promise<CiStatus> {
+  let sha: string;
+  try {
+    const ref = `refs/remotes/${remoteName}/${branch}`;
+    const { stdout } = await exec('git', ['rev-parse', '--verify', '--quiet', ref], {
+      cwd: projectRoot,
+    });
+    sha = stdout.trim();
+  } catch {
+    return noCi('Branch has not been pushed');
+  }
+
+  const key = `${projectRoot}\0${remoteName}\0${branch}`;
+  const cached = ciCache.get(key);
+  if (!force && cached?.sha === sha && Date.now() - cached.status.checked_at < CI_CACHE_MS) {
+    return cached.status;
+  }
+
+  const remote = await forgeRemote(projectRoot, remoteName);
+  const provider = remote && PROVIDERS.find((p) => p.info.kind === remote.kind);
+  if (!remote || !provider) {
+    return noCi(`${remoteName} is not a GitHub, GitLab or Bitbucket repository`);
+  }
+
+  if (remote.host !== provider.defaultHost && !tokens(remote.host)) {
+    return noCi(`Save a token for ${remote.host} in Settings to check its CI`, true);
+  }
+  const token = forgeTokenFor(remote, tokens);
+  let status: CiStatus;
+  try {
+    const { checks } = await provider.ciChecks(remote, sha, (path) =>
+      getJson(provider, remote, path, projectRoot, token),
+    );
+    const summary = summarizeCi(checks.map((c) => c.state));
+    const first = checks.find((c) => c.state === 'failure' && c.url) ?? checks.find((c) => c.url);
+    status = {
+      ...summary,
+      url: first?.url ?? null,
+      error: null,
+      host_not_configured: false,
+      checked_at: Date.now(),
+    };
+  } catch (err) {
+    status = noCi(err instanceof Error ? err.message : String(err));
+  }
+  ciCache.set(key, { sha, status });
+  return status;
+}
@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest';
+import { parsePorcelainV2 } from './git-status.js';
+
+describe('parsePorcelainV2', () => {
+  it('reads the branch, its upstream and every kind of changed path', () => {
+    const out = [
+      '# branch.oid 1234567890abcdef1234567890abcdef12345678',
+      '# branch.head feature/x',
+      '# branch.upstream origin/feature/x',
+      '# branch.ab +2 -1',
+      '1 .M N... 100644 100644 100644 aaaa bbbb src/a file.ts',
+      '2 R. N... 100644 100644 100644 aaaa aaaa R100 src/new.ts',
+      'src/old.ts',
+      'u UU N... 100644 100644 100644 100644 aaaa bbbb cccc conflict.ts',
+      '? notes.md',
+      '! dist/out.js',
+      '',
+    ].join('\0');
+    expect(parsePorcelainV2(out)).toEqual({
+      branch: 'feature/x',
+      upstream: 'origin/feature/x',
+      upstreamAheadBehind: { ahead: 2, behind: 1 },
+      paths: ['src/a file.ts', 'src/new.ts', 'conflict.ts', 'notes.md'],
+    });
+  });
+
+  it('reports a detached HEAD without an upstream', () => {
+    const out = '# branch.oid 1234\0# branch.head (detached)\0';
+    expect(parsePorcelainV2(out)).toEqual({
+      branch: null,
+      upstream: null,
+      upstreamAheadBehind: null,
+      paths: [],
+    });
+  });
+});
@@ -0,0 +1,46 @@
+// Parses `git status --porcelain=v2 --branch -z`, which reports the branch,
+// its upstream with ahead/behind counts and every changed path in one call.
+
+export interface PorcelainStatus {
+  /** Current branch; null when HEAD is detached. */
+  branch: string | null;
+  /** Upstream ref, e.g. `origin/feature`; null when none is configured. */
+  upstream: string | null;
+  /** HEAD vs the upstream; null without one or when it's gone. */
+  upstreamAheadBehind: { ahead: number; behind: number } | null;
+  /** Changed, staged, unmerged and untracked paths (renames by their new path). */
+  paths: string[];
+}
+
+/** Fields before the path in each entry type; renames carry one extra (the score). */
+const FIELDS_BEFORE_PATH: Record<string, number> = { '1': 8, '2': 9, u: 10, '?': 1 };
+
+export function parsePorcelainV2(stdout: string): PorcelainStatus {
+  const status: PorcelainStatus = {
+    branch: null,
+    upstream: null,
+    upstreamAheadBehind: null,
+    paths: [],
+  };
+  const records = stdout.split('\0');
+  for (let i = 0; i < records.length; i++) {
+    const record = records[i];
+    if (record.startsWith('# ')) {
+      const [key, ...rest] = record.slice(2).split(' ');
+      const value = rest.join(' ');
+      if (key === 'branch.head' && value !== '(detached)') status.branch = value;
+      else if (key === 'branch.upstream') status.upstream = value;
+      else if (key === 'branch.ab') {
+        const m = /^\+(\d+) -(\d+)$/.exec(value);
+        if (m) status.upstreamAheadBehind = { ahead: Number(m[1]), behind: Number(m[2]) };
+      }
+      continue;
+    }
+    const skip = FIELDS_BEFORE_PATH[record[0]];
+    if (skip === undefined) continue; // ignored files and the empty trailing record
+    status.paths.push(record.split(' ').slice(skip).join(' '));
+    // A rename's original path follows as its own record
+    if (record[0] === '2') i++;
+  }
+  return status;
+}
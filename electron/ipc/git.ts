@@ -1,8 +1,38 @@
 import { execFile, spawn } from 'child_process';
 import { promisify } from 'util';
+import { pipeline } from 'stream/promises';
 import fs from 'fs';
+import os from 'os';
 import path from 'path';
-import type { BrowserWindow } from 'electron';
+import { shell, type BrowserWindow } from 'electron';
+import {
+  runPostMergeHooks,
+  runPreMergeChecks,
+  runHookCommand,
+  type HookResult,
+} from './hooks.js';
+import { splitPatch, selectHunks, type FilePatch, type PatchHunk } from './patch-hunks.js';
+import { parseDiffFiles, addIntralineChanges, type DiffFile } from './diff-structured.js';
+import { matchesAnyGlob } from './glob.js';
+import { parsePorcelainV2 } from './git-status.js';
+import { parseBlamePorcelain, type BlameLine } from './blame.js';
+import { parseLfsPointer, collapseLfsPointerDiffs } from './lfs.js';
+import { commitFailure, commitFlags, lastLines, type CommitOptions } from './commit-errors.js';
+import {
+  findLeftovers,
+  isInsideDir,
+  parsePrunableWorktrees,
+  scanWorktreeFolder,
+  type PrunableWorktree,
+  type TaskLeftover,
+  type WorktreeFolderScan,
+} from './worktree-repair.js';
+import {
+  parseConflictHunks,
+  hasConflictMarkers,
+  type ConflictHunk,
+} from './conflict-markers.js';
+import { isDirInUse } from './pty.js';
 
 const exec = promisify(execFile);
 
@@ -15,6 +45,35 @@ export interface ChangedFile {
   lines_removed: number;
   status: string;
   committed: boolean;
+  staged?: boolean;
+  binary?: boolean;
+  /** Tracked by Git LFS; the line counts of its pointer are left out. */
+  lfs?: boolean;
+  /** Source path of a renamed or copied file. */
+  old_path?: string;
+  /** Matches a project's generated-file pattern (lockfiles, build output). */
+  generated?: boolean;
+}
+
+/** Options shared by the diff commands. */
+export interface DiffOptions {
+  /** `all` ignores all whitespace (-w), `change` only changes in amount (-b). */
+  ignore_whitespace?: 'all' | 'change';
+  /** Lines of context around each hunk (-U<n>); git's default is 3. */
+  context_lines?: number;
+  /** Detect renames (-M) or turn detection off; unset follows git config. */
+  detect_renames?: boolean;
+}
+
+/** git diff arguments for `opts`; context lines only apply to patch output. */
+function diffOptionArgs(opts: DiffOptions = {}, patch = true): string[] {
+  const args: string[] = [];
+  if (opts.ignore_whitespace === 'all') args.push('-w');
+  else if (opts.ignore_whitespace === 'change') args.push('-b');
+  if (patch) args.push(`-U${opts.context_lines ?? 3}`);
+  if (opts.detect_renames === true) args.push('-M');
+  else if (opts.detect_renames === false) args.push('--no-renames');
+  return args;
 }
 
 // --- TTL Caches ---
@@ -106,8 +165,69 @@ const SYMLINK_CANDIDATES = [
 /** Entries inside `.claude` that must NOT be symlinked (kept per-worktree). */
 const CLAUDE_DIR_EXCLUDE = new Set(['plans', 'settings.local.json']);
 
+/**
+ * Reject candidate paths that could escape the repo/worktree directory.
+ * Nested relative paths (e.g. `.cache/pip`) are allowed.
+ */
+function isSafeCandidatePath(name: string): boolean {
+  if (!name || name === '.' || path.isAbsolute(name)) return false;
+  if (name.includes('\\') || name.includes('\0')) return false;
+  return name.split('/').every((seg) => seg.length > 0 && seg !== '.' && seg !== '..');
+}
+
 // --- Internal helpers ---
 
+/**
+ * Copy-on-write clone of `source` to `target` via `cp` (clonefile on APFS,
+ * FICLONE reflinks on btrfs/XFS). Returns false, leaving nothing behind, if
+ * the platform or filesystem can't clone. Best effort on macOS: `cp -c` makes
+ * a full copy where clonefile fails, which is only ruled out across volumes.
+ */
+async function reflinkClone(source: string, target: string): Promise<boolean> {
+  let flags: string[];
+  if (process.platform === 'darwin') {
+    const sourceDev = (await fs.promises.stat(source)).dev;
+    if (sourceDev !== (await fs.promises.stat(path.dirname(target))).dev) return false;
+    flags = ['-a', '-c'];
+  } else if (process.platform === 'linux') {
+    flags = ['-a', '--reflink=always'];
+  } else {
+    return false;
+  }
+  try {
+    await exec('cp', [...flags, '--', source, target]);
+    return true;
+  } catch {
+    fs.rmSync(target, { recursive: true, force: true });
+    return false;
+  }
+}
+
+/**
+ * Recreate `source` at `target` with every file hard-linked to the original.
+ * Fast and space-free, but the copies share inodes: tools that rewrite files
+ * in place change the source too, while ones that replace files don't.
+ * Falls back to a plain copy across filesystems.
+ */
+async function hardlinkTree(source: string, target: string): Promise<void> {
+  const stat = await fs.promises.lstat(source);
+  if (stat.isSymbolicLink()) {
+    await fs.promises.symlink(await fs.promises.readlink(source), target);
+  } else if (stat.isDirectory()) {
+    await fs.promises.mkdir(target, { recursive: true });
+    for (const entry of await fs.promises.readdir(source)) {
+      await hardlinkTree(path.join(source, entry), path.join(target, entry));
+    }
+  } else {
+    try {
+      await fs.promises.link(source, target);
+    } catch (err) {
+      if ((err as NodeJS.ErrnoException).code !== 'EXDEV') throw err;
+      await fs.promises.copyFile(source, target);
+    }
+  }
+}
+
 async function detectMainBranch(repoRoot: string): Promise<string> {
   const key = cacheKey(repoRoot);
   const cached = mainBranchCache.get(key);
@@ -244,13 +364,26 @@ function normalizeStatusPath(raw: string): string {
   return destination.replace(/^"|"$/g, '').replace(/\\(.)/g, '$1');
 }
 
+/** Destination of a numstat rename path: `old => new` or `dir/{old => new}/file`. */
+function numstatPath(raw: string): string {
+  const braced = /^(.*)\{(.*) => (.*)\}(.*)$/.exec(raw);
+  if (braced) return normalizeStatusPath((braced[1] + braced[3] + braced[4]).replace('//', '/'));
+  const arrow = raw.indexOf(' => ');
+  return normalizeStatusPath(arrow === -1 ? raw : raw.slice(arrow + 4));
+}
+
 /** Parse combined `git diff --raw --numstat` output into status and numstat maps. */
 function parseDiffRawNumstat(output: string): {
   statusMap: Map<string, string>;
   numstatMap: Map<string, [number, number]>;
+  binaryPaths: Set<string>;
+  /** Renamed/copied destination path -> source path. */
+  renames: Map<string, string>;
 } {
   const statusMap = new Map<string, string>();
   const numstatMap = new Map<string, [number, number]>();
+  const binaryPaths = new Set<string>();
+  const renames = new Map<string, string>();
 
   for (const line of output.split('\n')) {
     if (line.startsWith(':')) {
@@ -261,23 +394,28 @@ function parseDiffRawNumstat(output: string): {
         const rawPath = parts[parts.length - 1];
         const p = normalizeStatusPath(rawPath);
         if (p) statusMap.set(p, statusLetter);
+        // Renames and copies list "source\tdestination"
+        if (p && parts.length >= 3) renames.set(p, normalizeStatusPath(parts[1]));
       }
       continue;
     }
     // --numstat format: "added\tremoved\tpath"
     const parts = line.split('\t');
-    if (parts.length >= 3) {
+    if (parts.length >= 3 && parts[0] === '-' && parts[1] === '-') {
+      // numstat reports binary files as "-\t-\tpath"
+      const p = numstatPath(parts[parts.length - 1]);
+      if (p) binaryPaths.add(p);
+    } else if (parts.length >= 3) {
       const added = parseInt(parts[0], 10);
       const removed = parseInt(parts[1], 10);
       if (!isNaN(added) && !isNaN(removed)) {
-        const rawPath = parts[parts.length - 1];
-        const p = normalizeStatusPath(rawPath);
+        const p = numstatPath(parts[parts.length - 1]);
         if (p) numstatMap.set(p, [added, removed]);
       }
     }
   }
 
-  return { statusMap, numstatMap };
+  return { statusMap, numstatMap, binaryPaths, renames };
 }
 
 function parseConflictPath(line: string): string | null {
@@ -355,15 +493,140 @@ function shallowSymlinkDir(source: string, target: string, exclude: Set<string>)
   }
 }
 
+/** Linked worktrees of a repo (the main worktree is left out) with their branches. */
+async function listLinkedWorktrees(
+  repoRoot: string,
+): Promise<{ path: string; branch: string | null }[]> {
+  const { stdout } = await exec('git', ['worktree', 'list', '--porcelain'], {
+    cwd: repoRoot,
+    maxBuffer: MAX_BUFFER,
+  });
+  const worktrees: { path: string; branch: string | null }[] = [];
+  for (const block of stdout.split('\n\n')) {
+    const lines = block.split('\n');
+    const wtPath = lines.find((l) => l.startsWith('worktree '))?.slice('worktree '.length);
+    if (!wtPath) continue;
+    const ref = lines.find((l) => l.startsWith('branch '))?.slice('branch '.length);
+    worktrees.push({
+      path: wtPath,
+      branch: ref?.startsWith('refs/heads/') ? ref.slice('refs/heads/'.length) : null,
+    });
+  }
+  // The first entry is always the main worktree
+  return worktrees.slice(1);
+}
+
+/**
+ * Where a task's worktree lives. Worktrees are found through git so tasks
+ * keep working after the project's worktree directory setting changes; the
+ * default layout is the fallback for worktrees that no longer exist.
+ */
+export async function taskWorktreePath(repoRoot: string, branchName: string): Promise<string> {
+  const linked = await listLinkedWorktrees(repoRoot).catch(() => []);
+  return (
+    linked.find((w) => w.branch === branchName)?.path ?? `${repoRoot}/.worktrees/${branchName}`
+  );
+}
+
+/**
+ * Directory that holds a project's task worktrees: `<repo>/.worktrees` by
+ * default, or the configured one (`~` expands to the home directory,
+ * relative paths are taken from the repo root).
+ */
+function worktreeBaseDir(repoRoot: string, configured: string | null): string {
+  const dir = configured?.trim();
+  if (!dir) return `${repoRoot}/.worktrees`;
+  if (dir === '~' || dir.startsWith('~/')) return path.join(os.homedir(), dir.slice(1));
+  return path.resolve(repoRoot, dir);
+}
+
+/**
+ * First of `name`, `name-2`, `name-3`, ... that is free as a new task branch:
+ * no such branch, nothing at its worktree path, and no other worktree nested
+ * inside or around that path. Invalid names, and names under an existing
+ * branch (`task` blocks `task/x`), are rejected.
+ */
+async function freeTaskBranchName(repoRoot: string, name: string, base: string): Promise<string> {
+  try {
+    await exec('git', ['check-ref-format', '--branch', name], { cwd: repoRoot });
+  } catch {
+    throw new Error(`Invalid branch name: ${name}`);
+  }
+  const relative = path.relative(base, path.join(base, name));
+  if (!relative || relative.startsWith('..') || path.isAbsolute(relative)) {
+    throw new Error(`Branch name '${name}' would put the worktree outside ${base}`);
+  }
+  const parts = name.split('/');
+  for (let i = 1; i < parts.length; i++) {
+    const ancestor = parts.slice(0, i).join('/');
+    if (await refExists(repoRoot, `refs/heads/${ancestor}`)) {
+      throw new Error(
+        `Branch '${ancestor}' exists, so '${name}' can't be created. Change the branch prefix.`,
+      );
+    }
+  }
+
+  const linked = await listLinkedWorktrees(repoRoot).catch(() => []);
+  const taken = async (candidate: string): Promise<boolean> => {
+    const target = path.join(base, candidate);
+    if (fs.existsSync(target)) return true;
+    if (await refExists(repoRoot, `refs/heads/${candidate}`)) return true;
+    const { stdout } = await exec(
+      'git',
+      ['for-each-ref', '--count=1', `refs/heads/${candidate}/`],
+      { cwd: repoRoot },
+    );
+    if (stdout.trim()) return true;
+    return linked.some(
+      (w) => w.path.startsWith(target + path.sep) || target.startsWith(w.path + path.sep),
+    );
+  };
+  for (let n = 1; n <= 100; n++) {
+    const candidate = n === 1 ? name : `${name}-${n}`;
+    if (!(await taken(candidate))) return candidate;
+  }
+  throw new Error(`Branch '${name}' and its numbered variants already exist`);
+}
+
 // --- Public functions (used by tasks.ts and register.ts) ---
 
+/**
+ * Create a task worktree. A new branch that collides with an existing branch
+ * or worktree directory gets a numeric suffix; the returned `branch` is the
+ * name actually used.
+ */
 export async function createWorktree(
   repoRoot: string,
   branchName: string,
   symlinkDirs: string[],
   forceClean = false,
+  copyPaths: string[] = [],
+  clonePaths: string[] = [],
+  /** Check out `branchName` as it is instead of creating it from HEAD. */
+  existingBranch = false,
+  /** Project's worktree directory setting; null keeps `<repo>/.worktrees`. */
+  baseDir: string | null = null,
+  hardlinkPaths: string[] = [],
+  /** Commit or branch a new branch starts from; null is the repo root's HEAD. */
+  startPoint: string | null = null,
 ): Promise<{ path: string; branch: string }> {
-  const worktreePath = `${repoRoot}/.worktrees/${branchName}`;
+  // A worktree that already has the branch checked out (e.g. left behind by a
+  // previous install) is adopted as is
+  let adopted: string | null = null;
+  if (existingBranch) {
+    const linked = await listLinkedWorktrees(repoRoot).catch(() => []);
+    adopted = linked.find((w) => w.branch === branchName)?.path ?? null;
+    // ...unless it belongs to a task that is still running in it
+    if (adopted && isDirInUse(adopted)) {
+      throw new Error(`Branch "${branchName}" is checked out in a worktree another task is using`);
+    }
+  }
+  const base = worktreeBaseDir(repoRoot, baseDir);
+  // forceClean replaces whatever is there, so only fresh task branches need a free name
+  if (!existingBranch && !forceClean) {
+    branchName = await freeTaskBranchName(repoRoot, branchName, base);
+  }
+  const worktreePath = adopted ?? path.join(base, branchName);
 
   if (forceClean) {
     // Clean up stale worktree/branch from a previous session that wasn't properly removed
@@ -386,18 +649,29 @@ export async function createWorktree(
     }
   }
 
-  // Create fresh worktree with new branch
-  await exec('git', ['worktree', 'add', '-b', branchName, worktreePath], { cwd: repoRoot });
+  if (existingBranch) {
+    if (!adopted) {
+      await exec('git', ['worktree', 'add', worktreePath, branchName], { cwd: repoRoot });
+    }
+  } else {
+    // Create fresh worktree with new branch
+    const from = startPoint ? [startPoint] : [];
+    await exec('git', ['worktree', 'add', '-b', branchName, worktreePath, ...from], {
+      cwd: repoRoot,
+    });
+  }
+  if (!adopted) await pullLfsObjects(worktreePath);
 
-  // Symlink selected directories
+  // Symlink selected files/directories
   for (const name of symlinkDirs) {
     // Reject names that could escape the worktree directory
-    if (name.includes('/') || name.includes('\\') || name.includes('..') || name === '.') continue;
+    if (!isSafeCandidatePath(name)) continue;
     const source = path.join(repoRoot, name);
     const target = path.join(worktreePath, name);
     try {
       if (!fs.existsSync(source)) continue;
       if (fs.existsSync(target)) continue;
+      fs.mkdirSync(path.dirname(target), { recursive: true });
 
       if (name === '.claude') {
         // Shallow-symlink: real dir with per-entry symlinks, excluding per-worktree entries
@@ -406,23 +680,222 @@ export async function createWorktree(
         fs.symlinkSync(source, target);
       }
     } catch (err) {
-      console.warn(`Failed to symlink directory '${name}' into worktree:`, err);
+      console.warn(`Failed to symlink '${name}' into worktree:`, err);
+    }
+  }
+
+  // Copy selected files/directories (independent per-worktree state)
+  for (const name of copyPaths) {
+    if (!isSafeCandidatePath(name)) continue;
+    const source = path.join(repoRoot, name);
+    const target = path.join(worktreePath, name);
+    try {
+      if (!fs.existsSync(source)) continue;
+      if (fs.existsSync(target)) continue;
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      if (await reflinkClone(source, target)) continue;
+      await fs.promises.cp(source, target, { recursive: true, verbatimSymlinks: true });
+    } catch (err) {
+      console.warn(`Failed to copy '${name}' into worktree:`, err);
+    }
+  }
+
+  // Clone selected heavy directories copy-on-write so each task gets an
+  // isolated node_modules/target without paying for a full copy. Filesystems
+  // without reflink support fall back to the shared symlink.
+  for (const name of clonePaths) {
+    if (!isSafeCandidatePath(name)) continue;
+    const source = path.join(repoRoot, name);
+    const target = path.join(worktreePath, name);
+    try {
+      if (!fs.existsSync(source)) continue;
+      if (fs.existsSync(target)) continue;
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      if (await reflinkClone(source, target)) continue;
+      console.warn(`Filesystem can't clone '${name}'; symlinking instead`);
+      fs.symlinkSync(source, target);
+    } catch (err) {
+      console.warn(`Failed to clone '${name}' into worktree:`, err);
+    }
+  }
+
+  // Hard-linked copies: an independent directory tree without the disk cost
+  for (const name of hardlinkPaths) {
+    if (!isSafeCandidatePath(name)) continue;
+    const source = path.join(repoRoot, name);
+    const target = path.join(worktreePath, name);
+    try {
+      if (!fs.existsSync(source)) continue;
+      if (fs.existsSync(target)) continue;
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      await hardlinkTree(source, target);
+    } catch (err) {
+      console.warn(`Failed to hardlink '${name}' into worktree:`, err);
     }
   }
 
   return { path: worktreePath, branch: branchName };
 }
 
+/** A large LFS checkout may take a while, but a stalled one mustn't hold up the task. */
+const LFS_PULL_TIMEOUT_MS = 5 * 60_000;
+
+/**
+ * Check out the real content of LFS-tracked files in a new worktree. Without
+ * the LFS filters configured, `worktree add` leaves pointer files behind.
+ * Failures are logged; the task still works with pointers.
+ */
+async function pullLfsObjects(worktreePath: string): Promise<void> {
+  // Attribute pathspecs see every .gitattributes, and work without git-lfs installed
+  const { stdout: lfsFiles } = await exec(
+    'git',
+    ['ls-files', '-z', '--', ':(attr:filter=lfs)'],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  ).catch(() => ({ stdout: '' }));
+  if (!lfsFiles) return;
+  try {
+    await exec('git', ['lfs', 'version'], { cwd: worktreePath });
+  } catch {
+    console.warn('Repository uses Git LFS but git-lfs is not installed');
+    return;
+  }
+  try {
+    // No `lfs install`: pull checks the files out itself, and install trips over existing hooks
+    await exec('git', ['lfs', 'pull'], {
+      cwd: worktreePath,
+      maxBuffer: MAX_BUFFER,
+      timeout: LFS_PULL_TIMEOUT_MS,
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+    });
+  } catch (err) {
+    console.warn('git lfs pull failed in new worktree:', err);
+  }
+}
+
+export interface DiscoveredBranch {
+  branch: string;
+  /** The branch's linked worktree, or null if it has none. */
+  worktree_path: string | null;
+  /** Last commit time, unix seconds. */
+  committed_at: number;
+}
+
+/**
+ * Branches of the project that could become tasks: linked worktrees and
+ * local branches not checked out anywhere, newest first. Main and the branch
+ * checked out in the project root are left out. The caller filters out
+ * branches that already belong to a task.
+ */
+export async function discoverWorktrees(projectRoot: string): Promise<DiscoveredBranch[]> {
+  const mainBranch = await detectMainBranch(projectRoot).catch(() => null);
+  const rootBranch = await getCurrentBranchName(projectRoot).catch(() => null);
+  const linked = new Map<string, string>();
+  for (const w of await listLinkedWorktrees(projectRoot)) {
+    if (w.branch) linked.set(w.branch, w.path);
+  }
+
+  const { stdout: refsOut } = await exec(
+    'git',
+    ['for-each-ref', '--format=%(refname:short)%1f%(committerdate:unix)', 'refs/heads'],
+    { cwd: projectRoot, maxBuffer: MAX_BUFFER },
+  );
+  const found: DiscoveredBranch[] = [];
+  for (const line of refsOut.split('\n')) {
+    if (!line) continue;
+    const [branch, time] = line.split('\x1f');
+    if (branch === mainBranch || branch === rootBranch) continue;
+    found.push({
+      branch,
+      worktree_path: linked.get(branch) ?? null,
+      committed_at: Number(time) || 0,
+    });
+  }
+  return found.sort((a, b) => b.committed_at - a.committed_at);
+}
+
+/** Local and remote-tracking branch names, for picking a task's starting branch. */
+export async function listBranches(repoRoot: string): Promise<string[]> {
+  const { stdout } = await exec(
+    'git',
+    ['for-each-ref', '--format=%(refname:short)', 'refs/heads', 'refs/remotes'],
+    { cwd: repoRoot, maxBuffer: MAX_BUFFER },
+  );
+  return stdout.split('\n').filter((name) => name && !name.endsWith('/HEAD'));
+}
+
+/**
+ * Turn what the user typed as a task's starting point into a local branch:
+ * an existing local branch is used as is, `<remote>/<branch>` gets a local
+ * tracking branch, and anything else (e.g. `pull/123/head`) is fetched from
+ * origin into a new branch called `fallbackName`.
+ */
+export async function resolveTaskBranch(
+  repoRoot: string,
+  ref: string,
+  fallbackName: string,
+): Promise<string> {
+  const name = ref.replace(/^refs\/(heads\/)?/, '');
+  if (await refExists(repoRoot, `refs/heads/${name}`)) return name;
+
+  const { stdout: remotesOut } = await exec('git', ['remote'], { cwd: repoRoot });
+  const remote = remotesOut
+    .split('\n')
+    .filter(Boolean)
+    .find((r) => name.startsWith(`${r}/`));
+  if (remote) {
+    const localName = name.slice(remote.length + 1);
+    await exec('git', ['fetch', '--quiet', remote, '--', localName], {
+      cwd: repoRoot,
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+    }).catch((e) => console.warn(`git fetch ${remote} ${localName} failed:`, e));
+    if (await refExists(repoRoot, `refs/remotes/${name}`)) {
+      if (await refExists(repoRoot, `refs/heads/${localName}`)) return localName;
+      await exec('git', ['branch', '--track', localName, `refs/remotes/${name}`], {
+        cwd: repoRoot,
+      });
+      return localName;
+    }
+  }
+
+  try {
+    await exec('git', ['fetch', '--quiet', 'origin', '--', ref], {
+      cwd: repoRoot,
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+    });
+  } catch (e) {
+    throw new Error(`Branch or ref '${ref}' not found locally or on origin: ${e}`);
+  }
+  await exec('git', ['branch', fallbackName, 'FETCH_HEAD'], { cwd: repoRoot });
+  return fallbackName;
+}
+
+/**
+ * Remove a task worktree (and optionally its branch). With `trash`, the
+ * worktree directory is moved to the OS trash instead of being destroyed, so
+ * uncommitted work can be recovered; returns the trashed path in that case.
+ */
 export async function removeWorktree(
   repoRoot: string,
   branchName: string,
   deleteBranch: boolean,
-): Promise<void> {
-  const worktreePath = `${repoRoot}/.worktrees/${branchName}`;
+  trash = false,
+): Promise<{ trashed_path: string | null }> {
+  let trashedPath: string | null = null;
 
-  if (!fs.existsSync(repoRoot)) return;
+  if (!fs.existsSync(repoRoot)) return { trashed_path: null };
+  const worktreePath = await taskWorktreePath(repoRoot, branchName);
 
-  if (fs.existsSync(worktreePath)) {
+  if (fs.existsSync(worktreePath) && trash) {
+    try {
+      await shell.trashItem(worktreePath);
+      trashedPath = worktreePath;
+    } catch (err) {
+      // Don't silently fall back to permanent deletion — that's what trash mode guards against
+      throw new Error(
+        `Could not move worktree to trash (${err}). Enable permanent deletion in Settings to remove it.`,
+      );
+    }
+  } else if (fs.existsSync(worktreePath)) {
     try {
       await exec('git', ['worktree', 'remove', '--force', worktreePath], { cwd: repoRoot });
     } catch {
@@ -445,14 +918,267 @@ export async function removeWorktree(
       const msg = String(e);
       if (!msg.toLowerCase().includes('not found')) throw e;
     }
+    await deletePushedRefs(repoRoot, branchName);
+    await deleteBranchSnapshots(repoRoot, branchName);
+  }
+
+  return { trashed_path: trashedPath };
+}
+
+/** Forget the pushes recorded for a deleted branch so their commits can be collected. */
+async function deletePushedRefs(repoRoot: string, branchName: string): Promise<void> {
+  const { stdout } = await exec('git', ['remote'], { cwd: repoRoot }).catch(() => ({ stdout: '' }));
+  for (const remote of stdout.split('\n').filter(Boolean)) {
+    await exec('git', ['update-ref', '-d', pushedRef(remote, branchName)], {
+      cwd: repoRoot,
+    }).catch(() => {});
   }
 }
 
+export interface WorktreeRepairReport {
+  /** What `git worktree repair` fixed, one line per link. */
+  repaired: string[];
+  /** Worktrees whose directory was moved or deleted; their metadata has been pruned. */
+  pruned: PrunableWorktree[];
+  /** Directories in the worktree folder that aren't a worktree of the repo anymore. */
+  orphaned_dirs: string[];
+  /** Branches under the task prefix that no worktree has checked out. */
+  orphaned_branches: string[];
+}
+
+async function gitCommonDir(repoRoot: string): Promise<string> {
+  const { stdout } = await exec('git', ['rev-parse', '--git-common-dir'], { cwd: repoRoot });
+  return path.resolve(repoRoot, stdout.trim());
+}
+
+/** Scanned directories that aren't a registered worktree anymore. */
+function orphanedWorktreeDirs(
+  projectRoot: string,
+  base: string,
+  scan: WorktreeFolderScan,
+  registered: { path: string }[],
+): string[] {
+  const registeredPaths = new Set(registered.map((w) => path.resolve(w.path)));
+  const dirs = scan.linked.filter((d) => !registeredPaths.has(path.resolve(d)));
+  // Strays are only ours to report in a worktree folder inside the repo; a
+  // shared folder elsewhere may hold anything
+  if (isInsideDir(base, projectRoot)) dirs.push(...scan.strays);
+  return dirs;
+}
+
+/** Branches under `<prefix>/`, minus whatever the project root has checked out. */
+async function listTaskBranches(projectRoot: string, branchPrefix: string): Promise<string[]> {
+  const rootBranch = await getCurrentBranchName(projectRoot).catch(() => null);
+  const { stdout } = await exec(
+    'git',
+    ['for-each-ref', '--format=%(refname:short)', `refs/heads/${branchPrefix}/`],
+    { cwd: projectRoot, maxBuffer: MAX_BUFFER },
+  );
+  return stdout.split('\n').filter((b) => b && b !== rootBranch);
+}
+
+/**
+ * Bring `.git/worktrees` back in line with the disk after a crash or after
+ * worktrees were moved or deleted by hand: repair the links of worktrees
+ * still in the worktree folder, prune those that are gone, and report what's
+ * left over. Nothing besides stale metadata is removed.
+ */
+export async function repairWorktrees(
+  projectRoot: string,
+  worktreeDir: string | null,
+  branchPrefix: string,
+): Promise<WorktreeRepairReport> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    const base = worktreeBaseDir(projectRoot, worktreeDir);
+    const adminDir = path.join(await gitCommonDir(projectRoot), 'worktrees');
+    const scan = await scanWorktreeFolder(base, adminDir);
+
+    const { stdout: repairOut, stderr: repairErr } = await exec(
+      'git',
+      ['worktree', 'repair', ...scan.linked],
+      { cwd: projectRoot },
+    );
+    const repaired = `${repairOut}\n${repairErr}`.split('\n').filter((l) => l.trim());
+
+    const { stdout: listOut } = await exec('git', ['worktree', 'list', '--porcelain'], {
+      cwd: projectRoot,
+      maxBuffer: MAX_BUFFER,
+    });
+    const pruned = parsePrunableWorktrees(listOut);
+    if (pruned.length > 0) await exec('git', ['worktree', 'prune'], { cwd: projectRoot });
+
+    const registered = await listLinkedWorktrees(projectRoot);
+    const checkedOut = new Set(registered.map((w) => w.branch).filter(Boolean));
+    const taskBranches = await listTaskBranches(projectRoot, branchPrefix);
+
+    return {
+      repaired,
+      pruned,
+      orphaned_dirs: orphanedWorktreeDirs(projectRoot, base, scan, registered),
+      orphaned_branches: taskBranches.filter((b) => !checkedOut.has(b)),
+    };
+  });
+}
+
+export interface TaskLeftoverInfo extends TaskLeftover {
+  /** Disk usage of `path` in bytes; null for a bare branch or when it couldn't be measured. */
+  size_bytes: number | null;
+}
+
+/**
+ * Task branches and worktree directories that none of `knownBranches` (the
+ * branches of every task the app has saved) accounts for, largest first.
+ */
+export async function findTaskLeftovers(
+  projectRoot: string,
+  worktreeDir: string | null,
+  branchPrefix: string,
+  knownBranches: string[],
+): Promise<TaskLeftoverInfo[]> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  const leftovers = await withWorktreeLock(lockKey, async () => {
+    const base = worktreeBaseDir(projectRoot, worktreeDir);
+    const adminDir = path.join(await gitCommonDir(projectRoot), 'worktrees');
+    const scan = await scanWorktreeFolder(base, adminDir);
+    const registered = await listLinkedWorktrees(projectRoot);
+    return findLeftovers(
+      await listTaskBranches(projectRoot, branchPrefix),
+      registered,
+      orphanedWorktreeDirs(projectRoot, base, scan, registered),
+      new Set(knownBranches),
+    );
+  });
+
+  const result = await Promise.all(
+    leftovers.map(async (l) => ({
+      ...l,
+      size_bytes: l.path ? await diskUsage(l.path) : null,
+    })),
+  );
+  return result.sort((a, b) => (b.size_bytes ?? 0) - (a.size_bytes ?? 0));
+}
+
+/**
+ * Delete leftovers reported by `findTaskLeftovers`: the worktree first, then
+ * its branch. Paths must be a linked worktree or sit inside the worktree
+ * folder; a branch checked out in some other worktree is kept. Failures are
+ * collected rather than thrown.
+ */
+export async function removeTaskLeftovers(
+  projectRoot: string,
+  worktreeDir: string | null,
+  leftovers: TaskLeftover[],
+): Promise<{ removed: number; errors: string[] }> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    const base = worktreeBaseDir(projectRoot, worktreeDir);
+    const registered = await listLinkedWorktrees(projectRoot);
+    let removed = 0;
+    const errors: string[] = [];
+
+    for (const { branch, path: dir } of leftovers) {
+      const label = branch ?? dir ?? '';
+      const worktree = dir
+        ? registered.find((w) => path.resolve(w.path) === path.resolve(dir))
+        : undefined;
+      const elsewhere = registered.find((w) => w.branch === branch && w !== worktree);
+      if (branch && elsewhere) {
+        errors.push(`${branch}: checked out in ${elsewhere.path}`);
+        continue;
+      }
+      try {
+        if (worktree) {
+          await exec('git', ['worktree', 'remove', '--force', worktree.path], { cwd: projectRoot });
+        } else if (dir) {
+          if (!isInsideDir(path.resolve(dir), base)) {
+            errors.push(`${dir}: not inside ${base}`);
+            continue;
+          }
+          await fs.promises.rm(dir, { recursive: true, force: true });
+        }
+        if (branch) await exec('git', ['branch', '-D', '--', branch], { cwd: projectRoot });
+        removed++;
+      } catch (e) {
+        errors.push(`${label}: ${e}`);
+      }
+    }
+    invalidateMergeBaseCache();
+    return { removed, errors };
+  });
+}
+
+/**
+ * Delete leftovers reported by `repairWorktrees`. Directories must be inside
+ * the worktree folder and not a registered worktree; branches must not be
+ * checked out anywhere. Failures are collected rather than thrown.
+ */
+export async function removeWorktreeOrphans(
+  projectRoot: string,
+  worktreeDir: string | null,
+  dirs: string[],
+  branches: string[],
+): Promise<{ removed: number; errors: string[] }> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    const base = worktreeBaseDir(projectRoot, worktreeDir);
+    const registered = await listLinkedWorktrees(projectRoot);
+    const registeredPaths = new Set(registered.map((w) => path.resolve(w.path)));
+    const checkedOut = new Set(registered.map((w) => w.branch).filter(Boolean));
+    let removed = 0;
+    const errors: string[] = [];
+
+    for (const dir of dirs) {
+      if (!isInsideDir(path.resolve(dir), base) || registeredPaths.has(path.resolve(dir))) {
+        errors.push(`${dir}: not an orphaned directory in ${base}`);
+        continue;
+      }
+      try {
+        await fs.promises.rm(dir, { recursive: true, force: true });
+        removed++;
+      } catch (e) {
+        errors.push(`${dir}: ${e}`);
+      }
+    }
+    for (const branch of branches) {
+      if (checkedOut.has(branch)) {
+        errors.push(`${branch}: checked out in a worktree`);
+        continue;
+      }
+      try {
+        await exec('git', ['branch', '-D', '--', branch], { cwd: projectRoot });
+        removed++;
+      } catch (e) {
+        errors.push(`${branch}: ${e}`);
+      }
+    }
+    invalidateMergeBaseCache();
+    return { removed, errors };
+  });
+}
+
 // --- IPC command functions ---
 
-export async function getGitIgnoredDirs(projectRoot: string): Promise<string[]> {
+/**
+ * Return the link candidates that exist in the project root and are gitignored.
+ * `extraCandidates` (files or directories, relative paths) are appended to the
+ * built-in list; `excludedCandidates` are removed from it.
+ */
+export async function getGitIgnoredDirs(
+  projectRoot: string,
+  extraCandidates: string[] = [],
+  excludedCandidates: string[] = [],
+): Promise<string[]> {
+  const excluded = new Set(excludedCandidates);
+  const candidates = [...new Set([...SYMLINK_CANDIDATES, ...extraCandidates])].filter(
+    (name) => !excluded.has(name) && isSafeCandidatePath(name),
+  );
   const results: string[] = [];
-  for (const name of SYMLINK_CANDIDATES) {
+  for (const name of candidates) {
     const dirPath = path.join(projectRoot, name);
     try {
       await fs.promises.stat(dirPath); // throws if entry doesn't exist
@@ -469,41 +1195,141 @@ export async function getGitIgnoredDirs(projectRoot: string): Promise<string[]>
   return results;
 }
 
-export async function getMainBranch(projectRoot: string): Promise<string> {
-  return detectMainBranch(projectRoot);
-}
-
-export async function getCurrentBranch(projectRoot: string): Promise<string> {
-  return getCurrentBranchName(projectRoot);
+export interface IgnoredEntry {
+  path: string;
+  is_dir: boolean;
+  /** Disk usage in bytes; null when it couldn't be measured. */
+  size_bytes: number | null;
 }
 
-export async function getChangedFiles(worktreePath: string): Promise<ChangedFile[]> {
-  // Pin HEAD first so merge-base and diff use the same immutable commit
-  const headHash = await pinHead(worktreePath);
-  const base = await detectMergeBase(worktreePath, headHash).catch(() => headHash);
+/** `du` runs at once while scanning, and how long one may take before its size is unknown. */
+const DU_CONCURRENCY = 4;
+const DU_TIMEOUT_MS = 30_000;
 
-  // git diff --raw --numstat <base> <head> — committed changes only (immutable)
-  let diffStr = '';
+async function diskUsage(target: string): Promise<number | null> {
+  if (process.platform === 'win32') return null;
   try {
-    const { stdout } = await exec('git', ['diff', '--raw', '--numstat', base, headHash], {
-      cwd: worktreePath,
-      maxBuffer: MAX_BUFFER,
-    });
+    const { stdout } = await exec('du', ['-sk', '--', target], { timeout: DU_TIMEOUT_MS });
+    const kb = parseInt(stdout, 10);
+    return Number.isFinite(kb) ? kb * 1024 : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Every gitignored entry at the top level of the project, largest first, as
+ * the pool to pick worktree link candidates from. The entry holding the task
+ * worktrees is left out, since sizing it would count every other task.
+ */
+export async function scanIgnoredEntries(
+  projectRoot: string,
+  worktreeDir: string | null = null,
+): Promise<IgnoredEntry[]> {
+  const base = path.relative(projectRoot, worktreeBaseDir(projectRoot, worktreeDir));
+  const worktreeEntry =
+    base && !base.startsWith('..') && !path.isAbsolute(base) ? base.split(path.sep)[0] : null;
+  const entries = (await fs.promises.readdir(projectRoot, { withFileTypes: true })).filter(
+    (e) => e.name !== '.git' && e.name !== '.worktrees' && e.name !== worktreeEntry,
+  );
+  if (entries.length === 0) return [];
+  let out: string;
+  try {
+    const input = entries.map((e) => e.name).join('\0') + '\0';
+    out = await gitWithInput(projectRoot, ['check-ignore', '-z', '--stdin'], input);
+  } catch {
+    return []; // exit code 1: nothing is ignored
+  }
+  const ignored = new Set(out.split('\0').filter(Boolean));
+  const queue = entries.filter((e) => ignored.has(e.name));
+  const result: IgnoredEntry[] = [];
+  const worker = async () => {
+    for (let e = queue.shift(); e !== undefined; e = queue.shift()) {
+      result.push({
+        path: e.name,
+        is_dir: e.isDirectory(),
+        size_bytes: await diskUsage(path.join(projectRoot, e.name)),
+      });
+    }
+  };
+  await Promise.all(Array.from({ length: DU_CONCURRENCY }, worker));
+  return result.sort((a, b) => (b.size_bytes ?? 0) - (a.size_bytes ?? 0));
+}
+
+export async function getMainBranch(projectRoot: string): Promise<string> {
+  return detectMainBranch(projectRoot);
+}
+
+export async function branchExists(repoRoot: string, branchName: string): Promise<boolean> {
+  return refExists(repoRoot, `refs/heads/${branchName}`);
+}
+
+export async function getHeadCommit(worktreePath: string): Promise<string> {
+  const { stdout } = await exec('git', ['rev-parse', 'HEAD'], { cwd: worktreePath });
+  return stdout.trim();
+}
+
+export async function getCurrentBranch(projectRoot: string): Promise<string> {
+  return getCurrentBranchName(projectRoot);
+}
+
+/** The given repo-relative paths that `.gitattributes` routes through Git LFS. */
+async function lfsTrackedPaths(cwd: string, paths: string[]): Promise<Set<string>> {
+  const tracked = new Set<string>();
+  if (paths.length === 0) return tracked;
+  const input = paths.join('\0') + '\0';
+  let out: string;
+  try {
+    out = await gitWithInput(cwd, ['check-attr', '-z', '--stdin', 'filter'], input);
+  } catch {
+    return tracked;
+  }
+  // -z output is path, attribute, value triples
+  const fields = out.split('\0');
+  for (let i = 0; i + 2 < fields.length; i += 3) {
+    if (fields[i + 2] === 'lfs') tracked.add(fields[i]);
+  }
+  return tracked;
+}
+
+export async function getChangedFiles(
+  worktreePath: string,
+  opts: DiffOptions = {},
+  generatedPatterns: string[] = [],
+): Promise<ChangedFile[]> {
+  // Pin HEAD first so merge-base and diff use the same immutable commit
+  const headHash = await pinHead(worktreePath);
+  const base = await detectMergeBase(worktreePath, headHash).catch(() => headHash);
+
+  // git diff --raw --numstat <base> <head> — committed changes only (immutable)
+  const optionArgs = diffOptionArgs(opts, false);
+  let diffStr = '';
+  try {
+    const { stdout } = await exec(
+      'git',
+      ['diff', '--raw', '--numstat', ...optionArgs, base, headHash],
+      { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+    );
     diffStr = stdout;
   } catch {
     /* empty */
   }
 
-  const { statusMap: committedStatusMap, numstatMap: committedNumstatMap } =
-    parseDiffRawNumstat(diffStr);
+  const {
+    statusMap: committedStatusMap,
+    numstatMap: committedNumstatMap,
+    binaryPaths: committedBinaryPaths,
+    renames: committedRenames,
+  } = parseDiffRawNumstat(diffStr);
 
   // git diff --raw --numstat <headHash> — tracked uncommitted changes (HEAD vs working tree).
   // Compares HEAD tree directly to the working tree, so it does not need the index
   // write lock and works reliably even while an agent holds it.
   // git ls-files --others --exclude-standard — untracked files (no index lock needed).
   // Both commands run in parallel since they are independent.
-  const [uncommittedResult, untrackedResult] = await Promise.all([
-    exec('git', ['diff', '--raw', '--numstat', headHash], {
+  // git diff --cached --name-only <headHash> — which of those changes are staged.
+  const [uncommittedResult, untrackedResult, stagedResult] = await Promise.all([
+    exec('git', ['diff', '--raw', '--numstat', ...optionArgs, headHash], {
       cwd: worktreePath,
       maxBuffer: MAX_BUFFER,
     }).catch(() => ({ stdout: '' })),
@@ -511,10 +1337,18 @@ export async function getChangedFiles(worktreePath: string): Promise<ChangedFile
       cwd: worktreePath,
       maxBuffer: MAX_BUFFER,
     }).catch(() => ({ stdout: '' })),
+    exec('git', ['diff', '--cached', '--name-only', '-z', headHash], {
+      cwd: worktreePath,
+      maxBuffer: MAX_BUFFER,
+    }).catch(() => ({ stdout: '' })),
   ]);
 
-  const { statusMap: uncommittedStatusMap, numstatMap: uncommittedNumstatMap } =
-    parseDiffRawNumstat(uncommittedResult.stdout);
+  const {
+    statusMap: uncommittedStatusMap,
+    numstatMap: uncommittedNumstatMap,
+    binaryPaths: uncommittedBinaryPaths,
+    renames: uncommittedRenames,
+  } = parseDiffRawNumstat(uncommittedResult.stdout);
 
   const untrackedPaths = new Set<string>();
   for (const line of untrackedResult.stdout.split('\n')) {
@@ -560,13 +1394,16 @@ export async function getChangedFiles(worktreePath: string): Promise<ChangedFile
   }
 
   // Untracked (new) files: count all lines as added
+  const untrackedBinaryPaths = new Set<string>();
   for (const p of untrackedPaths) {
     if (seen.has(p)) continue;
     let added = 0;
     const fullPath = path.join(worktreePath, p);
     try {
       const stat = await fs.promises.stat(fullPath);
-      if (stat.isFile() && stat.size < MAX_BUFFER) {
+      if (stat.isFile() && (await isBinaryFile(fullPath))) {
+        untrackedBinaryPaths.add(p);
+      } else if (stat.isFile() && stat.size < MAX_BUFFER) {
         const content = await fs.promises.readFile(fullPath, 'utf8');
         const lines = content.split('\n');
         added = content.endsWith('\n') ? lines.length - 1 : lines.length;
@@ -577,6 +1414,37 @@ export async function getChangedFiles(worktreePath: string): Promise<ChangedFile
     files.push({ path: p, lines_added: added, lines_removed: 0, status: '?', committed: false });
   }
 
+  for (const f of files) {
+    if (
+      committedBinaryPaths.has(f.path) ||
+      uncommittedBinaryPaths.has(f.path) ||
+      untrackedBinaryPaths.has(f.path)
+    ) {
+      f.binary = true;
+    }
+  }
+
+  // Edits to LFS files show up as pointer text changes; count them as binary
+  const lfsPaths = await lfsTrackedPaths(worktreePath, files.map((f) => f.path));
+  for (const f of files) {
+    if (!lfsPaths.has(f.path)) continue;
+    f.lfs = true;
+    f.binary = true;
+    f.lines_added = 0;
+    f.lines_removed = 0;
+  }
+
+  for (const f of files) {
+    const oldPath = uncommittedRenames.get(f.path) ?? committedRenames.get(f.path);
+    if (oldPath) f.old_path = oldPath;
+    if (matchesAnyGlob(f.path, generatedPatterns)) f.generated = true;
+  }
+
+  const stagedPaths = new Set(stagedResult.stdout.split('\0').filter(Boolean));
+  for (const f of files) {
+    if (!f.committed && stagedPaths.has(f.path)) f.staged = true;
+  }
+
   files.sort((a, b) => {
     if (a.committed !== b.committed) return a.committed ? -1 : 1;
     return a.path.localeCompare(b.path);
@@ -585,7 +1453,10 @@ export async function getChangedFiles(worktreePath: string): Promise<ChangedFile
   return files;
 }
 
-export async function getAllFileDiffs(worktreePath: string): Promise<string> {
+export async function getAllFileDiffs(
+  worktreePath: string,
+  opts: DiffOptions = {},
+): Promise<string> {
   const headHash = await pinHead(worktreePath);
   const base = await detectMergeBase(worktreePath, headHash).catch(() => headHash);
 
@@ -593,11 +1464,11 @@ export async function getAllFileDiffs(worktreePath: string): Promise<string> {
   // This avoids duplicate entries when a file has both committed and uncommitted changes.
   let combinedDiff = '';
   try {
-    const { stdout } = await exec('git', ['diff', '-U3', base], {
+    const { stdout } = await exec('git', ['diff', ...diffOptionArgs(opts), base], {
       cwd: worktreePath,
       maxBuffer: MAX_BUFFER,
     });
-    combinedDiff = stdout;
+    combinedDiff = collapseLfsPointerDiffs(stdout);
   } catch {
     /* empty */
   }
@@ -613,32 +1484,8 @@ export async function getAllFileDiffs(worktreePath: string): Promise<string> {
       if (!line.startsWith('??')) continue;
       const filePath = normalizeStatusPath(line.slice(3));
       if (!filePath) continue;
-      const fullPath = path.join(worktreePath, filePath);
-      try {
-        const stat = await fs.promises.stat(fullPath);
-        if (!stat.isFile() || stat.size >= MAX_BUFFER) continue;
-        if (await isBinaryFile(fullPath)) {
-          untrackedParts.push(
-            `diff --git a/${filePath} b/${filePath}\nnew file mode 100644\nBinary files /dev/null and b/${filePath} differ\n`,
-          );
-          continue;
-        }
-        const content = await fs.promises.readFile(fullPath, 'utf8');
-        const lines = content.split('\n');
-        const lineCount = content.endsWith('\n') ? lines.length - 1 : lines.length;
-        const pseudoLines: string[] = [];
-        pseudoLines.push(`diff --git a/${filePath} b/${filePath}`);
-        pseudoLines.push('new file mode 100644');
-        pseudoLines.push('--- /dev/null');
-        pseudoLines.push(`+++ b/${filePath}`);
-        pseudoLines.push(`@@ -0,0 +1,${lineCount} @@`);
-        for (let i = 0; i < lineCount; i++) {
-          pseudoLines.push(`+${lines[i]}`);
-        }
-        untrackedParts.push(pseudoLines.join('\n') + '\n');
-      } catch {
-        /* skip unreadable files */
-      }
+      const part = await untrackedFileDiff(worktreePath, filePath);
+      if (part) untrackedParts.push(part);
     }
   } catch {
     /* empty */
@@ -648,17 +1495,46 @@ export async function getAllFileDiffs(worktreePath: string): Promise<string> {
   return parts.join('\n');
 }
 
+/** Pseudo-diff adding an untracked file; null when it is unreadable or too large. */
+async function untrackedFileDiff(worktreePath: string, filePath: string): Promise<string | null> {
+  const fullPath = path.join(worktreePath, filePath);
+  try {
+    const stat = await fs.promises.stat(fullPath);
+    if (!stat.isFile() || stat.size >= MAX_BUFFER) return null;
+    if (await isBinaryFile(fullPath)) {
+      return `diff --git a/${filePath} b/${filePath}\nnew file mode 100644\nBinary files /dev/null and b/${filePath} differ\n`;
+    }
+    const content = await fs.promises.readFile(fullPath, 'utf8');
+    const lines = content.split('\n');
+    const lineCount = content.endsWith('\n') ? lines.length - 1 : lines.length;
+    const pseudoLines: string[] = [];
+    pseudoLines.push(`diff --git a/${filePath} b/${filePath}`);
+    pseudoLines.push('new file mode 100644');
+    pseudoLines.push('--- /dev/null');
+    pseudoLines.push(`+++ b/${filePath}`);
+    pseudoLines.push(`@@ -0,0 +1,${lineCount} @@`);
+    for (let i = 0; i < lineCount; i++) {
+      pseudoLines.push(`+${lines[i]}`);
+    }
+    return pseudoLines.join('\n') + '\n';
+  } catch {
+    return null;
+  }
+}
+
 export async function getAllFileDiffsFromBranch(
   projectRoot: string,
   branchName: string,
+  opts: DiffOptions = {},
 ): Promise<string> {
   const mainBranch = await detectMainBranch(projectRoot);
   try {
-    const { stdout } = await exec('git', ['diff', '-U3', `${mainBranch}...${branchName}`], {
-      cwd: projectRoot,
-      maxBuffer: MAX_BUFFER,
-    });
-    return stdout;
+    const { stdout } = await exec(
+      'git',
+      ['diff', ...diffOptionArgs(opts), `${mainBranch}...${branchName}`],
+      { cwd: projectRoot, maxBuffer: MAX_BUFFER },
+    );
+    return collapseLfsPointerDiffs(stdout);
   } catch {
     return '';
   }
@@ -668,9 +1544,273 @@ interface FileDiffResult {
   diff: string;
   oldContent: string;
   newContent: string;
+  truncated?: DiffTruncation;
+  /** Binary file: contents are left empty rather than sent as garbage text. */
+  binary?: boolean;
+  image?: DiffImage;
+  /** Object sizes in bytes for Git LFS files; null for a missing side. */
+  lfs?: { old_size: number | null; new_size: number | null };
+}
+
+/** Base64 before/after data for image files, null for a missing or oversized side. */
+interface DiffImage {
+  mime: string;
+  old: string | null;
+  new: string | null;
+}
+
+const IMAGE_MIME_TYPES: Record<string, string> = {
+  '.png': 'image/png',
+  '.jpg': 'image/jpeg',
+  '.jpeg': 'image/jpeg',
+  '.gif': 'image/gif',
+  '.webp': 'image/webp',
+  '.avif': 'image/avif',
+  '.bmp': 'image/bmp',
+  '.ico': 'image/x-icon',
+  '.svg': 'image/svg+xml',
+};
+/** Images larger than this are not sent for the visual before/after view. */
+const MAX_IMAGE_BYTES = 5 * 1024 * 1024;
+
+function isBinaryDiff(diff: string): boolean {
+  return /^(Binary files .* differ|GIT binary patch)$/m.test(diff);
+}
+
+/** Image data at the merge base and on disk, or undefined for non-image files. */
+async function readImageSides(
+  worktreePath: string,
+  filePath: string,
+  base: string,
+  diskPath: string | null,
+): Promise<DiffImage | undefined> {
+  const mime = IMAGE_MIME_TYPES[path.extname(filePath).toLowerCase()];
+  if (!mime) return undefined;
+  // An LFS pointer that could not be resolved to the real image counts as missing
+  const imageData = (buf: Buffer) =>
+    parseLfsPointer(buf.subarray(0, 1024).toString('utf8')) ? null : buf.toString('base64');
+  let oldData: string | null = null;
+  try {
+    // --filters runs the smudge filter, so LFS images come back as image data
+    const { stdout } = await exec('git', ['cat-file', '--filters', `${base}:${filePath}`], {
+      cwd: worktreePath,
+      maxBuffer: MAX_IMAGE_BYTES,
+      encoding: 'buffer',
+    });
+    oldData = imageData(stdout);
+  } catch {
+    /* new file, or too large */
+  }
+  let newData: string | null = null;
+  if (diskPath) {
+    try {
+      const stat = await fs.promises.stat(diskPath);
+      if (stat.size <= MAX_IMAGE_BYTES) {
+        newData = imageData(await fs.promises.readFile(diskPath));
+      }
+    } catch {
+      /* deleted meanwhile */
+    }
+  }
+  return { mime, old: oldData, new: newData };
+}
+
+/** Set when a diff result was cut down to keep IPC payloads small. */
+interface DiffTruncation {
+  total_bytes: number;
+  total_lines: number;
+  /** Leading diff lines included. */
+  shown_lines: number;
+  /** oldContent/newContent were dropped for being too large. */
+  content_omitted: boolean;
+}
+
+/** Diffs larger than this are truncated (lockfiles, vendored bundles). */
+const LARGE_DIFF_BYTES = 256 * 1024;
+/** File contents larger than this are left out of diff results. */
+const LARGE_CONTENT_BYTES = 1024 * 1024;
+/** Most diff lines returned per `getFileDiffRange` page. */
+const MAX_DIFF_PAGE_LINES = 5000;
+
+function splitDiffLines(diff: string): string[] {
+  const lines = diff.split('\n');
+  if (lines[lines.length - 1] === '') lines.pop();
+  return lines;
+}
+
+/** How many leading lines fit in `maxBytes` of UTF-8, newlines included. */
+function leadingLinesWithin(lines: string[], maxBytes: number): number {
+  let shown = 0;
+  let size = 0;
+  while (shown < lines.length && size + Buffer.byteLength(lines[shown]) + 1 <= maxBytes) {
+    size += Buffer.byteLength(lines[shown]) + 1;
+    shown++;
+  }
+  return shown;
+}
+
+/** Cut an oversized diff result down to its first lines and drop huge contents. */
+function summarizeLargeDiff(result: FileDiffResult): FileDiffResult {
+  const contentOmitted =
+    Buffer.byteLength(result.oldContent) > LARGE_CONTENT_BYTES ||
+    Buffer.byteLength(result.newContent) > LARGE_CONTENT_BYTES;
+  if (Buffer.byteLength(result.diff) <= LARGE_DIFF_BYTES && !contentOmitted) return result;
+
+  const lines = splitDiffLines(result.diff);
+  const shown = leadingLinesWithin(lines, LARGE_DIFF_BYTES);
+  return {
+    diff: lines
+      .slice(0, shown)
+      .map((l) => l + '\n')
+      .join(''),
+    oldContent: contentOmitted ? '' : result.oldContent,
+    newContent: contentOmitted ? '' : result.newContent,
+    truncated: {
+      total_bytes: Buffer.byteLength(result.diff),
+      total_lines: lines.length,
+      shown_lines: shown,
+      content_omitted: contentOmitted,
+    },
+  };
+}
+
+/**
+ * Cut each oversized file of a combined diff down to its leading lines, ended
+ * by a `\ Diff truncated: <shown> of <total> lines` marker (read by the
+ * renderer's diff parser). The rest pages in through `getFileDiffRange`.
+ */
+export function truncateLargeFileDiffs(diff: string): string {
+  return diff
+    .split(/^(?=diff --git )/m)
+    .map((block) => {
+      if (Buffer.byteLength(block) <= LARGE_DIFF_BYTES) return block;
+      const lines = splitDiffLines(block);
+      const shown = leadingLinesWithin(lines, LARGE_DIFF_BYTES);
+      return (
+        lines
+          .slice(0, shown)
+          .map((l) => l + '\n')
+          .join('') + `\\ Diff truncated: ${shown} of ${lines.length} lines\n`
+      );
+    })
+    .join('');
+}
+
+export interface FileAtRevision {
+  /** Commit hash the content was read from. */
+  rev: string;
+  exists: boolean;
+  binary: boolean;
+  /** File content; empty when missing or binary. */
+  content: string;
+}
+
+/**
+ * Read a file as of a revision — any ref or commit, defaulting to the merge
+ * base with the main branch — for side-by-side diff views.
+ */
+export async function getFileAtRevision(
+  worktreePath: string,
+  filePath: string,
+  rev?: string,
+): Promise<FileAtRevision> {
+  const target = rev ?? (await detectMergeBase(worktreePath));
+  const { stdout: resolved } = await exec(
+    'git',
+    ['rev-parse', '--verify', '--end-of-options', `${target}^{commit}`],
+    { cwd: worktreePath },
+  );
+  const commit = resolved.trim();
+  let blob: Buffer;
+  try {
+    const { stdout } = await exec('git', ['show', `${commit}:${filePath}`], {
+      cwd: worktreePath,
+      maxBuffer: MAX_BUFFER,
+      encoding: 'buffer',
+    });
+    blob = stdout;
+  } catch {
+    return { rev: commit, exists: false, binary: false, content: '' };
+  }
+  // Same heuristic as git: a NUL byte in the first 8000 bytes means binary
+  const binary = blob.subarray(0, 8000).includes(0);
+  return { rev: commit, exists: true, binary, content: binary ? '' : blob.toString('utf8') };
+}
+
+/**
+ * Blame lines `startLine..endLine` (inclusive) of a file as it was at the
+ * merge base, i.e. who last touched the main-branch lines the task changes.
+ * Files that didn't exist there have no blame.
+ */
+export async function getBlame(
+  worktreePath: string,
+  filePath: string,
+  startLine: number,
+  endLine: number,
+): Promise<BlameLine[]> {
+  const base = await detectMergeBase(worktreePath, await pinHead(worktreePath));
+  try {
+    const { stdout } = await exec(
+      'git',
+      [
+        'blame',
+        '--porcelain',
+        '-L',
+        `${Math.max(startLine, 1)},${Math.max(endLine, startLine, 1)}`,
+        base,
+        '--',
+        filePath,
+      ],
+      { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+    );
+    return parseBlamePorcelain(stdout);
+  } catch {
+    // New file, or the range starts past the end of the base version
+    return [];
+  }
+}
+
+/** Diff of one file against the merge base; huge diffs come back truncated. */
+export async function getFileDiff(
+  worktreePath: string,
+  filePath: string,
+  opts: DiffOptions = {},
+): Promise<FileDiffResult> {
+  return summarizeLargeDiff(await readFileDiff(worktreePath, filePath, opts));
+}
+
+/**
+ * A page of one file's block of `getAllFileDiffs` (merge base to working
+ * tree), counted in lines from its `diff --git` header: the rest of a block
+ * `truncateLargeFileDiffs` cut short.
+ */
+export async function getFileDiffRange(
+  worktreePath: string,
+  filePath: string,
+  offset: number,
+  limit: number,
+  opts: DiffOptions = {},
+): Promise<{ lines: string[]; offset: number; total_lines: number }> {
+  if (offset < 0) throw new Error('offset must not be negative');
+  const headHash = await pinHead(worktreePath);
+  const base = await detectMergeBase(worktreePath, headHash).catch(() => headHash);
+  const { stdout } = await exec('git', ['diff', ...diffOptionArgs(opts), base, '--', filePath], {
+    cwd: worktreePath,
+    maxBuffer: MAX_BUFFER,
+  });
+  const diff = stdout
+    ? collapseLfsPointerDiffs(stdout)
+    : ((await untrackedFileDiff(worktreePath, filePath)) ?? '');
+  const lines = splitDiffLines(diff);
+  const count = Math.min(Math.max(limit, 0), MAX_DIFF_PAGE_LINES);
+  return { lines: lines.slice(offset, offset + count), offset, total_lines: lines.length };
 }
 
-export async function getFileDiff(worktreePath: string, filePath: string): Promise<FileDiffResult> {
+async function readFileDiff(
+  worktreePath: string,
+  filePath: string,
+  opts: DiffOptions,
+): Promise<FileDiffResult> {
   // Pin HEAD first so merge-base and all reads use the same immutable commit
   const headHash = await pinHead(worktreePath);
   const base = await detectMergeBase(worktreePath, headHash).catch(() => headHash);
@@ -707,10 +1847,12 @@ export async function getFileDiff(worktreePath: string, filePath: string): Promi
   // Read disk content
   const fullPath = path.join(worktreePath, filePath);
   let diskContent = '';
+  let diskSize = 0;
   try {
     const stat = await fs.promises.stat(fullPath);
     if (stat.isFile()) {
       fileExistsOnDisk = true;
+      diskSize = stat.size;
       if (stat.size < MAX_BUFFER) {
         diskContent = await fs.promises.readFile(fullPath, 'utf8');
         fileContentReadable = true;
@@ -743,7 +1885,8 @@ export async function getFileDiff(worktreePath: string, filePath: string): Promi
   // Generate diff between base and HEAD for committed files (immutable, no race)
   let diff = '';
   try {
-    const { stdout } = await exec('git', ['diff', base, headHash, '--', filePath], {
+    const args = ['diff', ...diffOptionArgs(opts), base, headHash, '--', filePath];
+    const { stdout } = await exec('git', args, {
       cwd: worktreePath,
       maxBuffer: MAX_BUFFER,
     });
@@ -783,58 +1926,573 @@ export async function getFileDiff(worktreePath: string, filePath: string): Promi
     diff = pseudoLines.join('\n') + '\n';
   }
 
-  return { diff, oldContent, newContent };
+  const image = await readImageSides(
+    worktreePath,
+    filePath,
+    base,
+    fileExistsOnDisk ? fullPath : null,
+  );
+
+  // LFS files are stored as pointers: show the object sizes, not the pointer text.
+  // Without LFS installed the worktree holds the pointer itself.
+  const oldPointer = parseLfsPointer(oldContent);
+  const headPointer = parseLfsPointer(committedContent);
+  if (oldPointer || headPointer) {
+    let newSize: number | null = null;
+    if (fileExistsOnDisk) newSize = parseLfsPointer(diskContent)?.size ?? diskSize;
+    else if (!isUncommittedDeletion) newSize = headPointer?.size ?? null;
+    const collapsed = collapseLfsPointerDiffs(diff);
+    return {
+      diff: isBinaryDiff(collapsed)
+        ? collapsed
+        : `Binary files a/${filePath} and b/${filePath} differ\n`,
+      oldContent: '',
+      newContent: '',
+      binary: true,
+      lfs: { old_size: oldPointer?.size ?? null, new_size: newSize },
+      ...(image && { image }),
+    };
+  }
+
+  if (isBinaryDiff(diff)) {
+    return { diff, oldContent: '', newContent: '', binary: true, ...(image && { image }) };
+  }
+  return { diff, oldContent, newContent, ...(image && { image }) };
+}
+
+function withIntralineChanges(files: DiffFile[]): DiffFile[] {
+  for (const file of files) for (const hunk of file.hunks) addIntralineChanges(hunk);
+  return files;
 }
 
-export async function getWorktreeStatus(
+/**
+ * `getFileDiff`'s patch as a typed file diff with intraline change ranges;
+ * null when the file is unchanged.
+ */
+export async function getFileDiffStructured(
   worktreePath: string,
-): Promise<{ has_committed_changes: boolean; has_uncommitted_changes: boolean }> {
-  const { stdout: statusOut } = await exec('git', ['status', '--porcelain'], {
-    cwd: worktreePath,
-    maxBuffer: MAX_BUFFER,
-  });
-  const hasUncommittedChanges = statusOut.trim().length > 0;
+  filePath: string,
+  opts: DiffOptions = {},
+): Promise<DiffFile | null> {
+  const { diff } = await getFileDiff(worktreePath, filePath, opts);
+  return withIntralineChanges(parseDiffFiles(diff))[0] ?? null;
+}
 
-  const mainBranch = await detectMainBranch(worktreePath).catch(() => 'HEAD');
-  let hasCommittedChanges = false;
+/** `getAllFileDiffs` as typed file diffs with intraline change ranges. */
+export async function getAllFileDiffsStructured(
+  worktreePath: string,
+  opts: DiffOptions = {},
+): Promise<DiffFile[]> {
+  return withIntralineChanges(parseDiffFiles(await getAllFileDiffs(worktreePath, opts)));
+}
+
+export interface AheadBehind {
+  ahead: number;
+  behind: number;
+}
+
+export interface WorktreeStatus {
+  has_committed_changes: boolean;
+  has_uncommitted_changes: boolean;
+  /** Commits on HEAD that the local main branch doesn't have. */
+  commits_ahead_of_main: number;
+  /** Commits on the local main branch that HEAD doesn't have (rebase needed). */
+  commits_behind_main: number;
+  /** HEAD vs origin/<main>; null without a remote-tracking main branch. */
+  origin_main: AheadBehind | null;
+  has_remote_branch: boolean;
+  /** HEAD vs origin/<branch>; null when the branch was never pushed. */
+  remote_ahead_behind: AheadBehind | null;
+}
+
+/** Count commits only in HEAD (ahead) and only in `base` (behind), or null if `base` is unknown. */
+async function aheadBehind(worktreePath: string, base: string): Promise<AheadBehind | null> {
   try {
-    const { stdout: logOut } = await exec('git', ['log', `${mainBranch}..HEAD`, '--oneline'], {
-      cwd: worktreePath,
-    });
-    hasCommittedChanges = logOut.trim().length > 0;
+    const { stdout } = await exec(
+      'git',
+      ['rev-list', '--left-right', '--count', `${base}...HEAD`],
+      { cwd: worktreePath },
+    );
+    const [behind, ahead] = stdout.trim().split(/\s+/).map(Number);
+    return { ahead: ahead || 0, behind: behind || 0 };
   } catch {
-    /* ignore */
+    return null;
   }
+}
+
+async function readWorktreeStatus(
+  worktreePath: string,
+  mainBranch: string,
+): Promise<{ status: WorktreeStatus; uncommittedPaths: string[] }> {
+  // One status call covers the changed files, the branch and its upstream
+  const { stdout: statusOut } = await exec(
+    'git',
+    ['status', '--porcelain=v2', '--branch', '-z'],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  const porcelain = parsePorcelainV2(statusOut);
+  const uncommittedPaths = porcelain.paths;
+
+  // A missing ref makes rev-list fail, so no separate existence checks
+  const vsMain = (await aheadBehind(worktreePath, mainBranch)) ?? { ahead: 0, behind: 0 };
+  const originMain = await aheadBehind(worktreePath, `refs/remotes/origin/${mainBranch}`);
+  const branch = porcelain.branch;
+  const remoteAheadBehind = !branch
+    ? null
+    : porcelain.upstream === `origin/${branch}`
+      ? porcelain.upstreamAheadBehind
+      : await aheadBehind(worktreePath, `refs/remotes/origin/${branch}`);
+  const hasRemoteBranch = remoteAheadBehind !== null;
 
   return {
-    has_committed_changes: hasCommittedChanges,
-    has_uncommitted_changes: hasUncommittedChanges,
+    status: {
+      has_committed_changes: vsMain.ahead > 0,
+      has_uncommitted_changes: uncommittedPaths.length > 0,
+      commits_ahead_of_main: vsMain.ahead,
+      commits_behind_main: vsMain.behind,
+      origin_main: originMain,
+      has_remote_branch: hasRemoteBranch,
+      remote_ahead_behind: remoteAheadBehind,
+    },
+    uncommittedPaths,
   };
 }
 
-/** Stage all changes and commit in a worktree. */
-export async function commitAll(worktreePath: string, message: string): Promise<void> {
-  await exec('git', ['add', '-A'], { cwd: worktreePath });
-  await exec('git', ['commit', '-m', message], { cwd: worktreePath });
+export async function getWorktreeStatus(worktreePath: string): Promise<WorktreeStatus> {
+  const mainBranch = await detectMainBranch(worktreePath).catch(() => 'HEAD');
+  return (await readWorktreeStatus(worktreePath, mainBranch)).status;
 }
 
-/** Discard all uncommitted changes in a worktree (keeps committed work). */
-export async function discardUncommitted(worktreePath: string): Promise<void> {
-  await exec('git', ['checkout', '.'], { cwd: worktreePath });
-  await exec('git', ['clean', '-fd'], { cwd: worktreePath });
+export interface WorktreeStatusSummary extends WorktreeStatus {
+  /** Files changed vs main, committed or not (including untracked). */
+  changed_files: number;
 }
 
-export async function checkMergeStatus(
+/** Worktrees inspected at once across all getAllWorktreeStatuses calls. */
+const STATUS_CONCURRENCY = 4;
+let statusSlotsInUse = 0;
+const statusSlotWaiters: (() => void)[] = [];
+
+/** Run `fn` once fewer than STATUS_CONCURRENCY worktrees are being inspected app-wide. */
+async function withStatusSlot<T>(fn: () => Promise<T>): Promise<T> {
+  if (statusSlotsInUse >= STATUS_CONCURRENCY) {
+    await new Promise<void>((resolve) => statusSlotWaiters.push(resolve));
+  } else {
+    statusSlotsInUse++;
+  }
+  try {
+    return await fn();
+  } finally {
+    // Hand the slot straight to the next waiter, or give it back
+    const next = statusSlotWaiters.shift();
+    if (next) next();
+    else statusSlotsInUse--;
+  }
+}
+
+async function summarizeWorktree(
   worktreePath: string,
-): Promise<{ main_ahead_count: number; conflicting_files: string[] }> {
-  const mainBranch = await detectMainBranch(worktreePath);
+  mainBranch: string,
+): Promise<WorktreeStatusSummary> {
+  const { status, uncommittedPaths } = await readWorktreeStatus(worktreePath, mainBranch);
+  const changed = new Set(uncommittedPaths);
+  if (status.has_committed_changes) {
+    const { stdout } = await exec('git', ['diff', '--name-only', '-z', `${mainBranch}...HEAD`], {
+      cwd: worktreePath,
+      maxBuffer: MAX_BUFFER,
+    });
+    for (const p of stdout.split('\0')) if (p) changed.add(p);
+  }
+  return { ...status, changed_files: changed.size };
+}
 
-  let mainAheadCount = 0;
+/**
+ * Status, ahead/behind and changed-file counts for many worktrees of one
+ * project in a single call. Worktrees that can't be read (removed) map to null.
+ */
+export async function getAllWorktreeStatuses(
+  projectRoot: string,
+  worktreePaths: string[],
+): Promise<Record<string, WorktreeStatusSummary | null>> {
+  const mainBranch = await detectMainBranch(projectRoot).catch(() => 'HEAD');
+  const result: Record<string, WorktreeStatusSummary | null> = {};
+  await Promise.all(
+    [...new Set(worktreePaths)].map(async (p) => {
+      result[p] = await withStatusSlot(() => summarizeWorktree(p, mainBranch)).catch(() => null);
+    }),
+  );
+  return result;
+}
+
+/**
+ * Count what deleting a worktree would destroy: commits not on the main branch
+ * and uncommitted (including untracked) files. Missing worktrees count as zero.
+ */
+export async function countWorktreeChanges(
+  worktreePath: string,
+): Promise<{ unmerged_commits: number; uncommitted_files: number }> {
+  if (!fs.existsSync(worktreePath)) return { unmerged_commits: 0, uncommitted_files: 0 };
+
+  const { stdout: statusOut } = await exec('git', ['status', '--porcelain'], {
+    cwd: worktreePath,
+    maxBuffer: MAX_BUFFER,
+  });
+  const uncommittedFiles = statusOut.split('\n').filter((l) => l.trim().length > 0).length;
+
+  const mainBranch = await detectMainBranch(worktreePath).catch(() => 'HEAD');
+  let unmergedCommits = 0;
   try {
-    const { stdout } = await exec('git', ['rev-list', '--count', `HEAD..${mainBranch}`], {
+    const { stdout } = await exec('git', ['rev-list', '--count', `${mainBranch}..HEAD`], {
       cwd: worktreePath,
     });
-    mainAheadCount = parseInt(stdout.trim(), 10) || 0;
+    unmergedCommits = parseInt(stdout.trim(), 10) || 0;
+  } catch {
+    /* ignore */
+  }
+
+  return { unmerged_commits: unmergedCommits, uncommitted_files: uncommittedFiles };
+}
+
+/** Stage all changes and commit in a worktree. */
+export async function commitAll(
+  worktreePath: string,
+  message: string,
+  opts?: CommitOptions,
+): Promise<void> {
+  await commitWorktree(worktreePath, message, undefined, opts);
+}
+
+/**
+ * Stage and commit uncommitted changes in a worktree, returning the new
+ * commit's hash. With `paths`, only those files are committed (including
+ * untracked and deleted ones); anything else stays uncommitted. `opts`
+ * override the repo's signing config and can skip its commit hooks.
+ */
+export async function commitWorktree(
+  worktreePath: string,
+  message: string,
+  paths?: string[],
+  opts?: CommitOptions,
+): Promise<string> {
+  if (!message.trim()) throw new Error('Commit message is empty');
+  if (paths && paths.length === 0) throw new Error('No files selected to commit');
+  const pathspec = paths ? ['--', ...paths] : [];
+  await exec('git', ['add', '-A', ...pathspec], { cwd: worktreePath });
+  // `commit -- <paths>` commits only those paths even if other files are staged
+  try {
+    await exec('git', ['commit', ...commitFlags(opts), '-m', message, ...pathspec], {
+      cwd: worktreePath,
+    });
+  } catch (e) {
+    throw commitFailure('Commit', e);
+  }
+  const { stdout } = await exec('git', ['rev-parse', 'HEAD'], { cwd: worktreePath });
+  return stdout.trim();
+}
+
+/** Stage one file's changes (including deletion) in a worktree. */
+export async function stageFile(worktreePath: string, filePath: string): Promise<void> {
+  await exec('git', ['add', '-A', '--', filePath], { cwd: worktreePath });
+}
+
+/** Unstage one file, keeping its changes in the working tree. */
+export async function unstageFile(worktreePath: string, filePath: string): Promise<void> {
+  await exec('git', ['reset', '-q', 'HEAD', '--', filePath], { cwd: worktreePath });
+}
+
+/** Unstaged changes of one file (worktree vs index), untracked files as all-new. */
+async function unstagedFilePatch(worktreePath: string, filePath: string): Promise<FilePatch> {
+  const { stdout: tracked } = await exec('git', ['ls-files', '--', filePath], {
+    cwd: worktreePath,
+  });
+  const args = tracked.trim()
+    ? ['diff', '--no-color', '--no-ext-diff', '--', filePath]
+    : ['diff', '--no-color', '--no-ext-diff', '--no-index', '--', '/dev/null', filePath];
+  try {
+    const { stdout } = await exec('git', args, { cwd: worktreePath, maxBuffer: MAX_BUFFER });
+    return splitPatch(stdout);
+  } catch (err) {
+    // --no-index exits 1 when the files differ
+    const stdout = (err as { stdout?: string }).stdout;
+    if (typeof stdout === 'string' && stdout) return splitPatch(stdout);
+    throw err;
+  }
+}
+
+/** Hunks of a file's unstaged changes, with ids accepted by `stageHunks`. */
+export async function getUnstagedHunks(
+  worktreePath: string,
+  filePath: string,
+): Promise<PatchHunk[]> {
+  return (await unstagedFilePatch(worktreePath, filePath)).hunks;
+}
+
+/** Stage only the given hunks of a file's unstaged changes. */
+export async function stageHunks(
+  worktreePath: string,
+  filePath: string,
+  hunkIds: string[],
+): Promise<void> {
+  const patch = await unstagedFilePatch(worktreePath, filePath);
+  const known = new Set(patch.hunks.map((h) => h.id));
+  const missing = hunkIds.filter((id) => !known.has(id));
+  if (missing.length) {
+    throw new Error('File changed since its hunks were listed; refresh and retry');
+  }
+  if (hunkIds.length === 0) return;
+  await new Promise<void>((resolve, reject) => {
+    const child = execFile(
+      'git',
+      ['apply', '--cached', '--whitespace=nowarn', '-'],
+      { cwd: worktreePath },
+      (err, _stdout, stderr) => (err ? reject(new Error(stderr.trim() || err.message)) : resolve()),
+    );
+    child.stdin?.end(selectHunks(patch, hunkIds));
+  });
+}
+
+/**
+ * Discard one file's uncommitted changes: restore it from HEAD, or delete it
+ * if it doesn't exist there (untracked or newly added).
+ */
+export async function discardFileChanges(worktreePath: string, filePath: string): Promise<void> {
+  const inHead = await exec('git', ['cat-file', '-e', `HEAD:${filePath}`], {
+    cwd: worktreePath,
+  }).then(
+    () => true,
+    () => false,
+  );
+  if (inHead) {
+    await exec('git', ['checkout', 'HEAD', '--', filePath], { cwd: worktreePath });
+    return;
+  }
+  await exec('git', ['rm', '--cached', '-q', '--ignore-unmatch', '--', filePath], {
+    cwd: worktreePath,
+  });
+  await exec('git', ['clean', '-f', '-q', '--', filePath], { cwd: worktreePath });
+}
+
+export interface StashEntry {
+  /** `stash@{n}`; stashes are shared by all worktrees of a repo. */
+  ref: string;
+  /** Branch the stash was taken on, when git recorded one. */
+  branch: string | null;
+  message: string;
+  /** Unix seconds. */
+  created_at: number;
+}
+
+/** Stash everything uncommitted, untracked files included. Resolves to false if clean. */
+async function stashChangesUnlocked(cwd: string, message: string): Promise<boolean> {
+  const { stdout } = await exec('git', ['stash', 'push', '--include-untracked', '-m', message], {
+    cwd,
+  });
+  return !stdout.includes('No local changes to save');
+}
+
+export async function stashChanges(cwd: string, message?: string): Promise<boolean> {
+  const lockKey = await detectRepoLockKey(cwd).catch(() => cwd);
+  return withWorktreeLock(lockKey, () =>
+    stashChangesUnlocked(cwd, message?.trim() || 'parallel-code stash'),
+  );
+}
+
+export async function listStashes(cwd: string): Promise<StashEntry[]> {
+  const { stdout } = await exec('git', ['stash', 'list', '--format=%gd%x1f%gs%x1f%ct'], {
+    cwd,
+    maxBuffer: MAX_BUFFER,
+  });
+  return stdout
+    .split('\n')
+    .filter(Boolean)
+    .map((line) => {
+      const [ref, subject, time] = line.split('\x1f');
+      // "On <branch>: <message>" or "WIP on <branch>: <hash> <subject>"
+      const m = /^(?:WIP on|On) ([^:]+): (.*)$/.exec(subject);
+      return {
+        ref,
+        branch: m && m[1] !== '(no branch)' ? m[1] : null,
+        message: m ? m[2] : subject,
+        created_at: Number(time) || 0,
+      };
+    });
+}
+
+/**
+ * Move every uncommitted change (untracked files included) from one worktree
+ * of a repo into another. Stashes are shared between worktrees, so this is a
+ * stash in the source and a pop in the target; when the pop fails the target
+ * is cleaned and the changes go back to the source. Resolves to false if the
+ * source was clean.
+ */
+export async function moveUncommittedChanges(fromPath: string, toPath: string): Promise<boolean> {
+  const lockKey = await detectRepoLockKey(fromPath).catch(() => fromPath);
+  return withWorktreeLock(lockKey, async () => {
+    const branch = await getCurrentBranchName(toPath).catch(() => 'new worktree');
+    if (!(await stashChangesUnlocked(fromPath, `parallel-code: moving to ${branch}`))) return false;
+    try {
+      await exec('git', ['stash', 'pop', '--index'], { cwd: toPath });
+      return true;
+    } catch (e) {
+      await exec('git', ['reset', '--hard', '-q'], { cwd: toPath }).catch(() => {});
+      await exec('git', ['clean', '-fdq'], { cwd: toPath }).catch(() => {});
+      await exec('git', ['stash', 'pop', '--index'], { cwd: fromPath }).catch((popErr) =>
+        console.warn('Failed to restore moved changes; they are kept in the stash:', popErr),
+      );
+      throw new Error(`Could not move uncommitted changes: ${e}`);
+    }
+  });
+}
+
+export interface PatchApplyResult {
+  /** Files the patch touched. */
+  files: string[];
+  /** Files left with conflict markers by a three-way apply. */
+  conflicted_files: string[];
+}
+
+function gitWithInput(cwd: string, args: string[], input: string): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const child = execFile('git', args, { cwd, maxBuffer: MAX_BUFFER }, (err, stdout, stderr) =>
+      err ? reject(new Error(stderr.trim() || err.message)) : resolve(stdout),
+    );
+    child.stdin?.end(input);
+  });
+}
+
+/**
+ * Apply a patch to a worktree's files. With `threeWay`, hunks that don't
+ * apply are merged using the blobs recorded in the patch and leave conflict
+ * markers instead of failing; a three-way apply also stages what it applied.
+ */
+async function applyPatchUnlocked(
+  worktreePath: string,
+  patch: string,
+  threeWay: boolean,
+): Promise<PatchApplyResult> {
+  const numstat = await gitWithInput(worktreePath, ['apply', '--numstat', '-'], patch);
+  const files = numstat
+    .split('\n')
+    .filter(Boolean)
+    .map((line) => numstatPath(line.split('\t').slice(2).join('\t')));
+  const args = ['apply', '--whitespace=nowarn', ...(threeWay ? ['--3way'] : []), '-'];
+  try {
+    await gitWithInput(worktreePath, args, patch);
+    return { files, conflicted_files: [] };
+  } catch (e) {
+    const conflicted = threeWay ? await listUnmergedFiles(worktreePath) : [];
+    if (conflicted.length === 0) throw new Error(`Patch does not apply: ${e}`);
+    return { files, conflicted_files: conflicted.map((f) => f.path) };
+  }
+}
+
+/** Apply a pasted patch (plain diff or format-patch mail) to a worktree. */
+export async function applyPatch(
+  worktreePath: string,
+  patch: string,
+  threeWay: boolean,
+): Promise<PatchApplyResult> {
+  if (!patch.trim()) throw new Error('Patch is empty');
+  // Pasted text often loses the final newline, which git apply reports as corrupt
+  const text = patch.endsWith('\n') ? patch : `${patch}\n`;
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, () => applyPatchUnlocked(worktreePath, text, threeWay));
+}
+
+/**
+ * A binary patch of a worktree's changes since it left main, committed or
+ * not, optionally limited to some files.
+ */
+export async function getTaskPatch(
+  worktreePath: string,
+  files: string[] | null = null,
+): Promise<string> {
+  const base = await detectMergeBase(worktreePath);
+  const tree = await writeWorkingTree(worktreePath);
+  const { stdout } = await exec(
+    'git',
+    ['diff', '--binary', '--no-color', '--no-ext-diff', base, tree, '--', ...(files ?? [])],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  return stdout;
+}
+
+/**
+ * Copy one task's work into another: the source branch's changes since it
+ * left main, committed or not, optionally limited to some files, applied
+ * three-way to the target's working tree. The source is left as it is.
+ */
+export async function transferChanges(
+  sourcePath: string,
+  targetPath: string,
+  files: string[] | null,
+): Promise<PatchApplyResult> {
+  const patch = await getTaskPatch(sourcePath, files);
+  if (!patch.trim()) return { files: [], conflicted_files: [] };
+  const lockKey = await detectRepoLockKey(targetPath).catch(() => targetPath);
+  return withWorktreeLock(lockKey, () => applyPatchUnlocked(targetPath, patch, true));
+}
+
+/** A binary patch of a worktree's uncommitted changes, untracked files included. */
+export async function getUncommittedPatch(worktreePath: string): Promise<string> {
+  const tree = await writeWorkingTree(worktreePath);
+  const { stdout } = await exec(
+    'git',
+    ['diff', '--binary', '--no-color', '--no-ext-diff', 'HEAD', tree],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  return stdout;
+}
+
+/**
+ * Copy a worktree's uncommitted changes (untracked files included) into
+ * another worktree of the same repo, applied three-way so a target on a
+ * different commit gets conflict markers instead of a failure. The source is
+ * left as it is.
+ */
+export async function copyUncommittedChanges(
+  fromPath: string,
+  toPath: string,
+): Promise<PatchApplyResult> {
+  const patch = await getUncommittedPatch(fromPath);
+  if (!patch.trim()) return { files: [], conflicted_files: [] };
+  const lockKey = await detectRepoLockKey(toPath).catch(() => toPath);
+  return withWorktreeLock(lockKey, () => applyPatchUnlocked(toPath, patch, true));
+}
+
+/** Apply and drop a stash (the newest by default); a conflicting pop keeps the stash. */
+export async function popStash(cwd: string, ref = 'stash@{0}'): Promise<void> {
+  if (!/^stash@\{\d+\}$/.test(ref)) throw new Error(`Invalid stash ref: ${ref}`);
+  const lockKey = await detectRepoLockKey(cwd).catch(() => cwd);
+  await withWorktreeLock(lockKey, async () => {
+    try {
+      await exec('git', ['stash', 'pop', '--index', ref], { cwd });
+    } catch (e) {
+      throw new Error(`Stash pop failed; the stash was kept: ${e}`);
+    }
+  });
+}
+
+/** Discard all uncommitted changes in a worktree (keeps committed work). */
+export async function discardUncommitted(worktreePath: string): Promise<void> {
+  await createSnapshot(worktreePath, 'Before discarding uncommitted changes').catch((err) =>
+    console.warn('Snapshot before discard failed:', err),
+  );
+  await exec('git', ['checkout', '.'], { cwd: worktreePath });
+  await exec('git', ['clean', '-fd'], { cwd: worktreePath });
+}
+
+export async function checkMergeStatus(
+  worktreePath: string,
+): Promise<{ main_ahead_count: number; conflicting_files: string[] }> {
+  const mainBranch = await detectMainBranch(worktreePath);
+
+  let mainAheadCount = 0;
+  try {
+    const { stdout } = await exec('git', ['rev-list', '--count', `HEAD..${mainBranch}`], {
+      cwd: worktreePath,
+    });
+    mainAheadCount = parseInt(stdout.trim(), 10) || 0;
   } catch {
     /* ignore */
   }
@@ -856,16 +2514,486 @@ export async function checkMergeStatus(
   return { main_ahead_count: mainAheadCount, conflicting_files: conflictingFiles };
 }
 
+/**
+ * Merge two commits in memory with `git merge-tree`, touching no worktree.
+ * The tree is only a clean merge when no files conflict.
+ */
+async function mergeTrees(
+  cwd: string,
+  ours: string,
+  theirs: string,
+): Promise<{ tree: string; conflicting_files: string[] }> {
+  const args = ['merge-tree', '--write-tree', '--name-only', '--no-messages', ours, theirs];
+  let stdout: string;
+  try {
+    ({ stdout } = await exec('git', args, { cwd, maxBuffer: MAX_BUFFER }));
+  } catch (err) {
+    // Exit code 1 means the merge has conflicts; anything else is a real failure
+    const e = err as { code?: number; stdout?: string };
+    if (e.code !== 1 || typeof e.stdout !== 'string') throw err;
+    stdout = e.stdout;
+  }
+  const [tree, ...files] = stdout.split('\n').filter(Boolean);
+  return { tree, conflicting_files: [...new Set(files)] };
+}
+
+export interface MergePreview {
+  main_branch: string;
+  /** Unified diff from current main to the tree the merge would produce. */
+  diff: string;
+  /** Changed on both sides and merged without conflicts; worth a second look. */
+  auto_merged_files: string[];
+  /** These appear in `diff` with conflict markers. */
+  conflicting_files: string[];
+}
+
+async function changedSince(cwd: string, base: string, rev: string): Promise<Set<string>> {
+  const { stdout } = await exec('git', ['diff', '--name-only', '--no-renames', base, rev], {
+    cwd,
+    maxBuffer: MAX_BUFFER,
+  });
+  return new Set(stdout.split('\n').filter(Boolean));
+}
+
+/**
+ * What main would contain after merging `branchName`: the merge is done in
+ * memory with `merge-tree --write-tree` and the result diffed against main.
+ * Unlike the branch diff, this includes main's own changes to files both
+ * sides touched, as git would combine them.
+ */
+export async function getMergePreview(
+  projectRoot: string,
+  branchName: string,
+  opts: DiffOptions = {},
+): Promise<MergePreview> {
+  const mainBranch = await detectMainBranch(projectRoot);
+  const { tree, conflicting_files } = await mergeTrees(projectRoot, mainBranch, branchName);
+  const { stdout: diff } = await exec('git', ['diff', ...diffOptionArgs(opts), mainBranch, tree], {
+    cwd: projectRoot,
+    maxBuffer: MAX_BUFFER,
+  });
+
+  const { stdout: base } = await exec('git', ['merge-base', mainBranch, branchName], {
+    cwd: projectRoot,
+  });
+  const [onMain, onBranch] = await Promise.all([
+    changedSince(projectRoot, base.trim(), mainBranch),
+    changedSince(projectRoot, base.trim(), branchName),
+  ]);
+  const conflicting = new Set(conflicting_files);
+  const autoMerged = [...onBranch].filter((f) => onMain.has(f) && !conflicting.has(f));
+
+  return {
+    main_branch: mainBranch,
+    diff: collapseLfsPointerDiffs(diff),
+    auto_merged_files: autoMerged,
+    conflicting_files,
+  };
+}
+
+export interface BranchConflict {
+  branch_a: string;
+  branch_b: string;
+  files: string[];
+}
+
+/**
+ * Merge every pair of the given branches in memory and report the pairs that
+ * conflict, with the conflicting files. Nothing is checked out or written
+ * besides loose objects.
+ */
+export async function checkCrossTaskConflicts(
+  projectRoot: string,
+  branches: string[],
+): Promise<BranchConflict[]> {
+  const conflicts: BranchConflict[] = [];
+  for (let i = 0; i < branches.length; i++) {
+    for (let j = i + 1; j < branches.length; j++) {
+      const { conflicting_files } = await mergeTrees(projectRoot, branches[i], branches[j]);
+      if (conflicting_files.length > 0) {
+        conflicts.push({ branch_a: branches[i], branch_b: branches[j], files: conflicting_files });
+      }
+    }
+  }
+  return conflicts;
+}
+
+export interface WorktreeDiffStats {
+  worktree_path: string;
+  commits: number;
+  files_changed: number;
+  lines_added: number;
+  lines_removed: number;
+  /** Why this worktree couldn't be measured; the counts are zero then. */
+  error?: string;
+}
+
+/**
+ * How much each worktree changed since it branched off main, counting
+ * uncommitted and untracked files, so attempts at the same task compare fairly.
+ * A worktree that can't be measured (e.g. it was removed) carries an error
+ * instead of failing the whole comparison.
+ */
+export async function compareWorktrees(worktreePaths: string[]): Promise<WorktreeDiffStats[]> {
+  const results = await Promise.allSettled(
+    worktreePaths.map(async (worktreePath) => {
+      const head = await pinHead(worktreePath);
+      const base = await detectMergeBase(worktreePath, head).catch(() => head);
+      const tree = await writeWorkingTree(worktreePath);
+      const [{ stdout: numstat }, { stdout: count }] = await Promise.all([
+        exec('git', ['diff', '--numstat', base, tree], {
+          cwd: worktreePath,
+          maxBuffer: MAX_BUFFER,
+        }),
+        exec('git', ['rev-list', '--count', `${base}..${head}`], { cwd: worktreePath }),
+      ]);
+      const stats: WorktreeDiffStats = {
+        worktree_path: worktreePath,
+        commits: parseInt(count.trim(), 10) || 0,
+        files_changed: 0,
+        lines_added: 0,
+        lines_removed: 0,
+      };
+      for (const line of numstat.split('\n')) {
+        const parts = line.split('\t');
+        if (parts.length < 3) continue;
+        stats.files_changed++;
+        stats.lines_added += parseInt(parts[0], 10) || 0;
+        stats.lines_removed += parseInt(parts[1], 10) || 0;
+      }
+      return stats;
+    }),
+  );
+  return results.map((result, i) =>
+    result.status === 'fulfilled'
+      ? result.value
+      : {
+          worktree_path: worktreePaths[i],
+          commits: 0,
+          files_changed: 0,
+          lines_added: 0,
+          lines_removed: 0,
+          error: result.reason instanceof Error ? result.reason.message : String(result.reason),
+        },
+  );
+}
+
+/**
+ * Unified diff from one worktree's current state to another's, uncommitted
+ * and untracked files included. Both must be worktrees of the same repo.
+ */
+export async function getWorktreeCrossDiff(
+  fromWorktree: string,
+  toWorktree: string,
+  opts: DiffOptions = {},
+): Promise<string> {
+  const [fromTree, toTree] = await Promise.all([
+    writeWorkingTree(fromWorktree),
+    writeWorkingTree(toWorktree),
+  ]);
+  const { stdout } = await exec('git', ['diff', ...diffOptionArgs(opts), fromTree, toTree], {
+    cwd: toWorktree,
+    maxBuffer: MAX_BUFFER,
+  });
+  return collapseLfsPointerDiffs(stdout);
+}
+
+export interface IntegrationBranchResult {
+  branch: string;
+  merged: string[];
+  /** Branches left out because they conflict with what was merged before them. */
+  skipped: { branch: string; conflicting_files: string[] }[];
+  /** Which merged branches each skipped branch conflicts with, and on which files. */
+  conflicts: BranchConflict[];
+}
+
+/**
+ * Build a throwaway branch off main that merges the given task branches in
+ * order, so their combined changes can be tried before deciding how to merge
+ * them. Merges happen in memory; a branch that conflicts with the result so
+ * far is skipped and its pairwise conflicts with the merged ones reported.
+ */
+export async function createIntegrationBranch(
+  projectRoot: string,
+  taskBranches: string[],
+  name: string,
+  replace = false,
+): Promise<IntegrationBranchResult> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    if (!replace && (await refExists(projectRoot, `refs/heads/${name}`))) {
+      throw new Error(`Branch ${name} already exists`);
+    }
+    const mainBranch = await detectMainBranch(projectRoot);
+    let head = await revParse(projectRoot, mainBranch);
+    const merged: string[] = [];
+    const skipped: IntegrationBranchResult['skipped'] = [];
+    const conflicts: BranchConflict[] = [];
+
+    for (const branch of taskBranches) {
+      const result = await mergeTrees(projectRoot, head, branch);
+      if (result.conflicting_files.length > 0) {
+        skipped.push({ branch, conflicting_files: result.conflicting_files });
+        for (const other of merged) {
+          const pair = await mergeTrees(projectRoot, other, branch);
+          if (pair.conflicting_files.length > 0) {
+            conflicts.push({ branch_a: other, branch_b: branch, files: pair.conflicting_files });
+          }
+        }
+        continue;
+      }
+      const tip = await revParse(projectRoot, branch);
+      const { stdout } = await exec(
+        'git',
+        ['commit-tree', result.tree, '-p', head, '-p', tip, '-m', `Merge ${branch} into ${name}`],
+        { cwd: projectRoot },
+      );
+      head = stdout.trim();
+      merged.push(branch);
+    }
+
+    await exec('git', ['branch', '-f', name, head], { cwd: projectRoot });
+    return { branch: name, merged, skipped, conflicts };
+  });
+}
+
+export type ConflictOperation = 'merge' | 'rebase' | 'cherry-pick' | 'revert';
+
+export interface ConflictFile {
+  path: string;
+  /** Porcelain XY code, e.g. UU (both modified), AA (both added), DU (deleted by us). */
+  status: string;
+  binary: boolean;
+  /** Conflict regions still present in the working-tree file. */
+  hunks: ConflictHunk[];
+}
+
+export interface ConflictDetails {
+  /** The operation in progress, or null when nothing is being merged. */
+  operation: ConflictOperation | null;
+  files: ConflictFile[];
+}
+
+const UNMERGED_CODES = new Set(['DD', 'AU', 'UD', 'UA', 'DU', 'AA', 'UU']);
+
+async function gitPathExists(worktreePath: string, gitPath: string): Promise<boolean> {
+  try {
+    const { stdout } = await exec('git', ['rev-parse', '--git-path', gitPath], {
+      cwd: worktreePath,
+    });
+    await fs.promises.access(path.resolve(worktreePath, stdout.trim()));
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+async function detectConflictOperation(worktreePath: string): Promise<ConflictOperation | null> {
+  if (
+    (await gitPathExists(worktreePath, 'rebase-merge')) ||
+    (await gitPathExists(worktreePath, 'rebase-apply'))
+  )
+    return 'rebase';
+  if (await gitPathExists(worktreePath, 'MERGE_HEAD')) return 'merge';
+  if (await gitPathExists(worktreePath, 'CHERRY_PICK_HEAD')) return 'cherry-pick';
+  if (await gitPathExists(worktreePath, 'REVERT_HEAD')) return 'revert';
+  return null;
+}
+
+async function listUnmergedFiles(
+  worktreePath: string,
+): Promise<{ path: string; status: string }[]> {
+  const { stdout } = await exec('git', ['status', '--porcelain', '-z'], {
+    cwd: worktreePath,
+    maxBuffer: MAX_BUFFER,
+  });
+  const files: { path: string; status: string }[] = [];
+  for (const entry of stdout.split('\0')) {
+    const status = entry.slice(0, 2);
+    if (UNMERGED_CODES.has(status)) files.push({ path: entry.slice(3), status });
+  }
+  return files;
+}
+
+/**
+ * Structured view of an in-progress conflicted merge/rebase: each unmerged
+ * file with its conflict regions (ours/base/theirs) parsed from the markers.
+ */
+export async function getConflictDetails(worktreePath: string): Promise<ConflictDetails> {
+  const operation = await detectConflictOperation(worktreePath);
+  const unmerged = await listUnmergedFiles(worktreePath);
+  const files: ConflictFile[] = [];
+  for (const { path: filePath, status } of unmerged) {
+    const fullPath = path.join(worktreePath, filePath);
+    let binary = false;
+    let hunks: ConflictHunk[] = [];
+    // Deleted on one side: there may be no working-tree file to parse
+    if (fs.existsSync(fullPath)) {
+      binary = await isBinaryFile(fullPath);
+      if (!binary) hunks = parseConflictHunks(await fs.promises.readFile(fullPath, 'utf8'));
+    }
+    files.push({ path: filePath, status, binary, hunks });
+  }
+  return { operation, files };
+}
+
+export type ConflictResolution = 'ours' | 'theirs' | { content: string };
+
+/**
+ * Resolve one conflicted file and stage it: take our or their side as a
+ * whole, or write explicit merged content. Note that during a rebase "ours"
+ * is the branch being rebased onto and "theirs" the task's commit.
+ */
+export async function resolveConflict(
+  worktreePath: string,
+  filePath: string,
+  resolution: ConflictResolution,
+): Promise<void> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, async () => {
+    const { stdout } = await exec('git', ['ls-files', '-u', '--', filePath], {
+      cwd: worktreePath,
+    });
+    if (!stdout.trim()) throw new Error(`${filePath} is not in conflict`);
+
+    if (typeof resolution === 'object') {
+      await fs.promises.writeFile(path.join(worktreePath, filePath), resolution.content);
+      await exec('git', ['add', '--', filePath], { cwd: worktreePath });
+      return;
+    }
+
+    // ls-files -u lines: "<mode> <object> <stage>\t<path>"; stage 2 = ours, 3 = theirs
+    const stage = resolution === 'ours' ? '2' : '3';
+    const sideExists = stdout
+      .split('\n')
+      .some((line) => line.split('\t')[0].split(' ')[2] === stage);
+    if (sideExists) {
+      await exec('git', ['checkout', `--${resolution}`, '--', filePath], { cwd: worktreePath });
+      await exec('git', ['add', '--', filePath], { cwd: worktreePath });
+    } else {
+      // That side deleted the file
+      await exec('git', ['rm', '--quiet', '--', filePath], { cwd: worktreePath });
+    }
+  });
+}
+
+// Continue commands reuse the prepared commit message instead of opening an editor
+const NO_EDITOR_ENV = { ...process.env, GIT_EDITOR: 'true' };
+
+async function assertNoUnmergedFiles(worktreePath: string): Promise<void> {
+  const unmerged = await listUnmergedFiles(worktreePath);
+  if (unmerged.length > 0) {
+    throw new Error(`Unresolved conflicts remain in: ${unmerged.map((f) => f.path).join(', ')}`);
+  }
+}
+
+/** Commit a conflicted merge once every file has been resolved. */
+export async function continueMerge(worktreePath: string): Promise<void> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, async () => {
+    await assertNoUnmergedFiles(worktreePath);
+    await exec('git', ['merge', '--continue'], { cwd: worktreePath, env: NO_EDITOR_ENV });
+    invalidateMergeBaseCache();
+  });
+}
+
+export async function abortMerge(worktreePath: string): Promise<void> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, async () => {
+    await exec('git', ['merge', '--abort'], { cwd: worktreePath });
+  });
+}
+
+/**
+ * How a task lands on main: a merge commit, one squashed commit, or the task
+ * rebased onto main and fast-forwarded so history stays linear.
+ */
+export type MergeStrategy = 'merge' | 'squash' | 'rebase';
+
+/**
+ * Run `fn` in a throwaway detached checkout of `commit`, so it sees exactly
+ * what's committed. Top-level symlinks and ignored directories of the task's
+ * worktree (dependencies, build caches) are linked in, so checks can run.
+ */
+async function withTemporaryCheckout<T>(
+  projectRoot: string,
+  commit: string,
+  taskWorktree: string | null,
+  fn: (checkoutPath: string) => Promise<T>,
+): Promise<T> {
+  const checkoutPath = fs.mkdtempSync(path.join(os.tmpdir(), 'parallel-code-checkout-'));
+  try {
+    await exec('git', ['worktree', 'add', '--detach', '--force', checkoutPath, commit], {
+      cwd: projectRoot,
+    });
+    if (taskWorktree && fs.existsSync(taskWorktree)) {
+      const { stdout } = await exec(
+        'git',
+        ['ls-files', '--others', '--ignored', '--exclude-standard', '--directory', '-z'],
+        { cwd: taskWorktree, maxBuffer: MAX_BUFFER },
+      ).catch(() => ({ stdout: '' }));
+      const ignoredDirs = stdout
+        .split('\0')
+        .filter((p) => p.endsWith('/') && p.indexOf('/') === p.length - 1)
+        .map((p) => p.slice(0, -1));
+      const linked = fs
+        .readdirSync(taskWorktree, { withFileTypes: true })
+        .filter((e) => e.isSymbolicLink())
+        .map((e) => e.name);
+      for (const name of new Set([...linked, ...ignoredDirs])) {
+        const target = path.join(checkoutPath, name);
+        if (fs.existsSync(target)) continue;
+        try {
+          fs.symlinkSync(fs.realpathSync(path.join(taskWorktree, name)), target);
+        } catch (e) {
+          console.warn(`Failed to link ${name} into the temporary checkout:`, e);
+        }
+      }
+    }
+    return await fn(checkoutPath);
+  } finally {
+    await exec('git', ['worktree', 'remove', '--force', checkoutPath], { cwd: projectRoot }).catch(
+      () => fs.rmSync(checkoutPath, { recursive: true, force: true }),
+    );
+    await exec('git', ['worktree', 'prune'], { cwd: projectRoot }).catch(() => {});
+  }
+}
+
+/** How much of a failed check's output the merge error carries. */
+const FAILED_CHECK_OUTPUT_LINES = 40;
+
 export async function mergeTask(
   projectRoot: string,
   branchName: string,
-  squash: boolean,
+  strategy: MergeStrategy,
+  /** Squash or merge commit message; git's default when null. Unused by `rebase`. */
   message: string | null,
   cleanup: boolean,
-): Promise<{ main_branch: string; lines_added: number; lines_removed: number }> {
+  postMergeCommands: string[] = [],
+  stashRootChanges = false,
+  /** Checks run on a clean checkout of the branch; failing ones refuse the merge unless `warn`. */
+  preMergeCommands: string[] = [],
+  preMergeGate: 'block' | 'warn' = 'block',
+  onCheckOutput?: (text: string) => void,
+  /** Signing and hook options for the squash or merge commit, and the rebased commits. */
+  commitOpts?: CommitOptions,
+  /** Cleanup keeps the branch: the task checked out one that existed before it. */
+  keepBranch = false,
+): Promise<{
+  main_branch: string;
+  lines_added: number;
+  lines_removed: number;
+  pre_merge_checks: HookResult[];
+  post_merge_hooks: HookResult[];
+  /** Stashed root changes didn't apply cleanly afterwards and are still in the stash. */
+  stash_conflict: boolean;
+}> {
   const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+  let stashConflict = false;
+  let preMergeChecks: HookResult[] = [];
 
-  return withWorktreeLock(lockKey, async () => {
+  const merged = await withWorktreeLock(lockKey, async () => {
     const mainBranch = await detectMainBranch(projectRoot);
     const { linesAdded, linesRemoved } = await computeBranchDiffStats(
       projectRoot,
@@ -877,16 +3005,69 @@ export async function mergeTask(
     const { stdout: statusOut } = await exec('git', ['status', '--porcelain'], {
       cwd: projectRoot,
     });
-    if (statusOut.trim())
+    const rootDirty = statusOut.trim().length > 0;
+    if (rootDirty && !stashRootChanges)
       throw new Error(
         'Project root has uncommitted changes. Please commit or stash them before merging.',
       );
 
     const originalBranch = await getCurrentBranchName(projectRoot).catch(() => null);
 
-    // Checkout main
-    await exec('git', ['checkout', mainBranch], { cwd: projectRoot });
+    // Recorded for undo once the merge has landed
+    const branchHead = await revParse(projectRoot, `refs/heads/${branchName}`);
+
+    // Under the lock, on exactly the commit being merged: uncommitted files in
+    // the task's worktree aren't merged, so they mustn't make a check pass
+    if (preMergeCommands.length > 0) {
+      const taskWorktree = await taskWorktreePath(projectRoot, branchName);
+      preMergeChecks = await withTemporaryCheckout(projectRoot, branchHead, taskWorktree, (dir) =>
+        runPreMergeChecks(preMergeCommands, dir, branchName, onCheckOutput),
+      );
+      const failed = preMergeChecks.find((c) => c.exit_code !== 0);
+      if (failed && preMergeGate === 'block') {
+        const output = lastLines(failed.output, FAILED_CHECK_OUTPUT_LINES);
+        throw new Error(
+          `Pre-merge check failed: ${failed.command} (exit code ${failed.exit_code ?? 'none'})` +
+            (output ? `\n${output}` : ''),
+        );
+      }
+    }
+    // Missing when main only exists as origin/<main> and checkout creates it
+    const mainHead = await revParse(projectRoot, `refs/heads/${mainBranch}`).catch(
+      () => undefined,
+    );
+
+    // Rebase in the task's worktree before touching the root, so a conflict leaves main alone
+    if (strategy === 'rebase') {
+      const worktreePath = await taskWorktreePath(projectRoot, branchName);
+      // Rebasing rewrites the files an agent may be editing; only do it on a clean tree
+      const { stdout: worktreeStatus } = await exec('git', ['status', '--porcelain'], {
+        cwd: worktreePath,
+        maxBuffer: MAX_BUFFER,
+      });
+      if (worktreeStatus.trim()) {
+        throw new Error(
+          "The task's worktree has uncommitted changes. Commit or discard them before " +
+            'rebasing, or merge without rebasing.',
+        );
+      }
+      try {
+        await exec('git', ['rebase', ...commitFlags(commitOpts), mainBranch], {
+          cwd: worktreePath,
+        });
+      } catch (e) {
+        await exec('git', ['rebase', '--abort'], { cwd: worktreePath }).catch((recoverErr) =>
+          console.warn('git rebase --abort failed:', recoverErr),
+        );
+        throw commitFailure('Rebase onto main', e);
+      }
+    }
+
+    const stashed =
+      rootDirty &&
+      (await stashChangesUnlocked(projectRoot, `parallel-code: before merging ${branchName}`));
 
+    // Put the root back on its branch and bring stashed changes back
     const restoreBranch = async () => {
       if (originalBranch) {
         try {
@@ -895,9 +3076,25 @@ export async function mergeTask(
           console.warn(`Failed to restore branch '${originalBranch}':`, e);
         }
       }
+      if (stashed) {
+        try {
+          await exec('git', ['stash', 'pop', '--index'], { cwd: projectRoot });
+        } catch (e) {
+          console.warn('Failed to restore stashed root changes:', e);
+          stashConflict = true;
+        }
+      }
     };
 
-    if (squash) {
+    // Checkout main
+    try {
+      await exec('git', ['checkout', mainBranch], { cwd: projectRoot });
+    } catch (e) {
+      await restoreBranch();
+      throw e;
+    }
+
+    if (strategy === 'squash') {
       try {
         await exec('git', ['merge', '--squash', '--', branchName], { cwd: projectRoot });
       } catch (e) {
@@ -909,36 +3106,140 @@ export async function mergeTask(
       }
       const msg = message ?? 'Squash merge';
       try {
-        await exec('git', ['commit', '-m', msg], { cwd: projectRoot });
+        await exec('git', ['commit', ...commitFlags(commitOpts), '-m', msg], { cwd: projectRoot });
       } catch (e) {
         await exec('git', ['reset', '--hard', 'HEAD'], { cwd: projectRoot }).catch((recoverErr) =>
           console.warn('git reset --hard failed during commit recovery:', recoverErr),
         );
         await restoreBranch();
-        throw new Error(`Commit failed: ${e}`);
+        throw commitFailure('Commit', e);
+      }
+    } else if (strategy === 'rebase') {
+      try {
+        await exec('git', ['merge', '--ff-only', '--', branchName], { cwd: projectRoot });
+      } catch (e) {
+        await restoreBranch();
+        throw new Error(`Fast-forward failed: ${e}`);
       }
     } else {
+      // Fast-forwards when main hasn't moved; the message applies to a merge commit
+      const messageArgs = message ? ['-m', message] : ['--no-edit'];
       try {
-        await exec('git', ['merge', '--', branchName], { cwd: projectRoot });
+        await exec(
+          'git',
+          ['merge', ...commitFlags(commitOpts), ...messageArgs, '--', branchName],
+          { cwd: projectRoot },
+        );
       } catch (e) {
         await exec('git', ['merge', '--abort'], { cwd: projectRoot }).catch((recoverErr) =>
           console.warn('git merge --abort failed:', recoverErr),
         );
         await restoreBranch();
-        throw new Error(`Merge failed: ${e}`);
+        throw commitFailure('Merge', e);
       }
     }
 
     invalidateMergeBaseCache();
 
+    const mergeCommit = await pinHead(projectRoot);
+    await saveUndoRecord(projectRoot, {
+      operation: 'merge',
+      created_at: Date.now(),
+      branch: branchName,
+      branch_head: branchHead,
+      main_branch: mainBranch,
+      main_head: mainHead,
+      merged_head: mergeCommit,
+    }).catch((e) => console.warn('Failed to record the merge for undo:', e));
+
     if (cleanup) {
-      await removeWorktree(projectRoot, branchName, true);
+      await removeWorktree(projectRoot, branchName, !keepBranch);
     }
 
     await restoreBranch();
 
-    return { main_branch: mainBranch, lines_added: linesAdded, lines_removed: linesRemoved };
+    return { mainBranch, linesAdded, linesRemoved, mergeCommit };
+  });
+
+  // Hooks run outside the repo lock so a slow deploy doesn't block other merges.
+  const postMergeHooks =
+    postMergeCommands.length > 0
+      ? await runPostMergeHooks(postMergeCommands, {
+          projectRoot,
+          branchName,
+          mainBranch: merged.mainBranch,
+          mergeCommit: merged.mergeCommit,
+          strategy,
+          linesAdded: merged.linesAdded,
+          linesRemoved: merged.linesRemoved,
+        })
+      : [];
+
+  return {
+    main_branch: merged.mainBranch,
+    lines_added: merged.linesAdded,
+    lines_removed: merged.linesRemoved,
+    pre_merge_checks: preMergeChecks,
+    post_merge_hooks: postMergeHooks,
+    stash_conflict: stashConflict,
+  };
+}
+
+export type BranchExportFormat = 'patch' | 'bundle';
+
+/**
+ * Write a branch to `outPath`: its commits since main as one `git am`-able
+ * mbox, or a self-contained bundle of the whole branch. Uncommitted changes
+ * are not included.
+ */
+export async function exportBranch(
+  projectRoot: string,
+  branchName: string,
+  format: BranchExportFormat,
+  outPath: string,
+): Promise<void> {
+  const branchRef = `refs/heads/${branchName}`;
+  if (format === 'bundle') {
+    await exec('git', ['bundle', 'create', outPath, branchRef], {
+      cwd: projectRoot,
+      maxBuffer: MAX_BUFFER,
+    });
+    return;
+  }
+  const mainBranch = await detectMainBranch(projectRoot);
+  const { stdout: base } = await exec('git', ['merge-base', mainBranch, branchRef], {
+    cwd: projectRoot,
+  });
+  const range = `${base.trim()}..${branchRef}`;
+  const { stdout: count } = await exec('git', ['rev-list', '--count', range], {
+    cwd: projectRoot,
+  });
+  if (parseInt(count, 10) === 0) throw new Error(`Branch '${branchName}' has no commits to export`);
+
+  // Streamed to the file: a branch with large binaries can exceed any buffer
+  const proc = spawn('git', ['format-patch', '--stdout', '--binary', range], {
+    cwd: projectRoot,
+    stdio: ['ignore', 'pipe', 'pipe'],
+  });
+  let stderrBuf = '';
+  proc.stderr?.on('data', (chunk: Buffer) => {
+    stderrBuf = (stderrBuf + chunk.toString('utf8')).slice(-STDERR_CAP);
   });
+  const exited = new Promise<void>((resolve, reject) => {
+    proc.on('error', (err) => reject(new Error(`git format-patch failed: ${err.message}`)));
+    proc.on('close', (code, signal) => {
+      if (code === 0) return resolve();
+      const lastLine = stderrBuf.trim().split('\n').pop() || '';
+      reject(new Error(lastLine || `git format-patch exited with ${signal ?? `code ${code}`}`));
+    });
+  });
+  try {
+    await Promise.all([pipeline(proc.stdout, fs.createWriteStream(outPath)), exited]);
+  } catch (e) {
+    proc.kill();
+    await fs.promises.rm(outPath, { force: true });
+    throw e;
+  }
 }
 
 export async function getBranchLog(worktreePath: string): Promise<string> {
@@ -958,9 +3259,111 @@ export async function getBranchLog(worktreePath: string): Promise<string> {
   }
 }
 
+export interface CommitInfo {
+  hash: string;
+  short_hash: string;
+  author_name: string;
+  author_email: string;
+  /** Author date, ISO 8601. */
+  date: string;
+  subject: string;
+  files_changed: number;
+  lines_added: number;
+  lines_removed: number;
+}
+
+/** Most commits returned by one getCommitList call. */
+const MAX_COMMIT_PAGE = 500;
+
+/** Parse `git log --numstat` output whose records start with \x1e and separate fields with \x1f. */
+function parseCommitLog(stdout: string): CommitInfo[] {
+  const commits: CommitInfo[] = [];
+  for (const record of stdout.split('\x1e')) {
+    if (!record.trim()) continue;
+    const [header, ...statLines] = record.split('\n');
+    const [hash, shortHash, authorName, authorEmail, date, subject] = header.split('\x1f');
+    let files = 0;
+    let added = 0;
+    let removed = 0;
+    for (const line of statLines) {
+      const m = /^(\d+|-)\t(\d+|-)\t/.exec(line);
+      if (!m) continue;
+      files++;
+      // Binary files report '-' for both counts
+      added += parseInt(m[1], 10) || 0;
+      removed += parseInt(m[2], 10) || 0;
+    }
+    commits.push({
+      hash,
+      short_hash: shortHash,
+      author_name: authorName,
+      author_email: authorEmail,
+      date,
+      subject: subject ?? '',
+      files_changed: files,
+      lines_added: added,
+      lines_removed: removed,
+    });
+  }
+  return commits;
+}
+
+/**
+ * List commits newest first, with per-commit line stats. The range defaults to
+ * the task's own commits (`<main>..HEAD`).
+ */
+export async function getCommitList(
+  worktreePath: string,
+  range: string | null,
+  limit = 50,
+  offset = 0,
+): Promise<CommitInfo[]> {
+  const revRange = range ?? `${await detectMainBranch(worktreePath).catch(() => 'HEAD')}..HEAD`;
+  const { stdout } = await exec(
+    'git',
+    [
+      'log',
+      `--max-count=${Math.min(Math.max(limit, 1), MAX_COMMIT_PAGE)}`,
+      `--skip=${Math.max(offset, 0)}`,
+      '--numstat',
+      '--no-renames',
+      '--pretty=format:%x1e%H%x1f%h%x1f%an%x1f%ae%x1f%aI%x1f%s',
+      '--end-of-options',
+      revRange,
+      '--',
+    ],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  return parseCommitLog(stdout);
+}
+
+/** Unified diff of a single commit against its first parent. */
+export async function getCommitDiff(
+  worktreePath: string,
+  hash: string,
+  opts: DiffOptions = {},
+): Promise<string> {
+  const { stdout } = await exec(
+    'git',
+    [
+      'show',
+      '--format=',
+      '--diff-merges=first-parent',
+      ...diffOptionArgs(opts),
+      '--end-of-options',
+      hash,
+      '--',
+    ],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  return stdout;
+}
+
 export async function getChangedFilesFromBranch(
   projectRoot: string,
   branchName: string,
+  opts: DiffOptions = {},
+  generatedPatterns: string[] = [],
 ): Promise<ChangedFile[]> {
   const mainBranch = await detectMainBranch(projectRoot);
 
@@ -968,7 +3371,13 @@ export async function getChangedFilesFromBranch(
   try {
     const { stdout } = await exec(
       'git',
-      ['diff', '--raw', '--numstat', `${mainBranch}...${branchName}`],
+      [
+        'diff',
+        '--raw',
+        '--numstat',
+        ...diffOptionArgs(opts, false),
+        `${mainBranch}...${branchName}`,
+      ],
       { cwd: projectRoot, maxBuffer: MAX_BUFFER },
     );
     diffStr = stdout;
@@ -976,7 +3385,7 @@ export async function getChangedFilesFromBranch(
     return [];
   }
 
-  const { statusMap, numstatMap } = parseDiffRawNumstat(diffStr);
+  const { statusMap, numstatMap, binaryPaths } = parseDiffRawNumstat(diffStr);
 
   const files: ChangedFile[] = [];
 
@@ -991,6 +3400,10 @@ export async function getChangedFilesFromBranch(
     files.push({ path: p, lines_added: 0, lines_removed: 0, status, committed: true });
   }
 
+  for (const f of files) {
+    if (binaryPaths.has(f.path)) f.binary = true;
+    if (matchesAnyGlob(f.path, generatedPatterns)) f.generated = true;
+  }
   files.sort((a, b) => a.path.localeCompare(b.path));
   return files;
 }
@@ -999,6 +3412,7 @@ export async function getFileDiffFromBranch(
   projectRoot: string,
   branchName: string,
   filePath: string,
+  opts: DiffOptions = {},
 ): Promise<FileDiffResult> {
   const mainBranch = await detectMainBranch(projectRoot);
 
@@ -1006,7 +3420,7 @@ export async function getFileDiffFromBranch(
   try {
     const { stdout } = await exec(
       'git',
-      ['diff', `${mainBranch}...${branchName}`, '--', filePath],
+      ['diff', ...diffOptionArgs(opts), `${mainBranch}...${branchName}`, '--', filePath],
       { cwd: projectRoot, maxBuffer: MAX_BUFFER },
     );
     diff = stdout;
@@ -1047,17 +3461,66 @@ export async function getFileDiffFromBranch(
     /* file doesn't exist on branch */
   }
 
-  return { diff, oldContent, newContent };
+  if (isBinaryDiff(diff)) {
+    return { diff, oldContent: '', newContent: '', binary: true };
+  }
+  return summarizeLargeDiff({ diff, oldContent, newContent });
+}
+
+/** Where the commit last pushed from here is kept, per remote and branch: the force-push lease. */
+const PUSHED_REF_PREFIX = 'refs/parallel-code/pushed';
+
+function pushedRef(remote: string, branchName: string): string {
+  return `${PUSHED_REF_PREFIX}/${remote}/${branchName}`;
+}
+
+/** Remember the branch's current commit as what was last pushed to `remote`. */
+export async function recordPushedCommit(
+  projectRoot: string,
+  remote: string,
+  branchName: string,
+  commit = `refs/heads/${branchName}`,
+): Promise<void> {
+  await exec('git', ['update-ref', pushedRef(remote, branchName), commit], {
+    cwd: projectRoot,
+  }).catch((err) => console.warn('Failed to record the pushed commit:', err));
 }
 
-export function pushTask(
+export async function pushTask(
   win: BrowserWindow,
   projectRoot: string,
   branchName: string,
   channelId: string,
+  forceWithLease = false,
+  remote = 'origin',
 ): Promise<void> {
-  return new Promise((resolve, reject) => {
-    const proc = spawn('git', ['push', '--progress', '-u', 'origin', '--', branchName], {
+  const { stdout: head } = await exec(
+    'git',
+    ['rev-parse', '--verify', `refs/heads/${branchName}`],
+    { cwd: projectRoot },
+  );
+  const args = ['push', '--progress', '-u'];
+  if (forceWithLease) {
+    // A lease only overwrites the remote branch if it is still where we last
+    // pushed it, which is what pushing a rebased task needs. The tracking ref
+    // moves on every fetch, so it alone would also accept commits fetched but
+    // never looked at; without a push of our own, require them to be included.
+    const leased = await exec(
+      'git',
+      ['rev-parse', '--verify', '--quiet', pushedRef(remote, branchName)],
+      { cwd: projectRoot },
+    ).then(
+      (r) => r.stdout.trim(),
+      () => '',
+    );
+    args.push(
+      ...(leased
+        ? [`--force-with-lease=refs/heads/${branchName}:${leased}`]
+        : ['--force-with-lease', '--force-if-includes']),
+    );
+  }
+  await new Promise<void>((resolve, reject) => {
+    const proc = spawn('git', [...args, remote, '--', branchName], {
       cwd: projectRoot,
       stdio: ['ignore', 'pipe', 'pipe'],
     });
@@ -1105,22 +3568,574 @@ export function pushTask(
       reject(new Error(`git push failed: ${err.message}`));
     });
   });
+  await recordPushedCommit(projectRoot, remote, branchName, head.trim());
+}
+
+/** Delete a branch on a remote. A branch that is already gone counts as deleted. */
+export async function deleteRemoteBranch(
+  projectRoot: string,
+  branchName: string,
+  remote = 'origin',
+): Promise<void> {
+  try {
+    await exec('git', ['push', remote, `:refs/heads/${branchName}`], {
+      cwd: projectRoot,
+      timeout: 60_000,
+    });
+  } catch (e) {
+    const stderr = (e as { stderr?: string }).stderr ?? '';
+    if (/remote ref does not exist/i.test(stderr)) {
+      // Drop the stale remote-tracking ref so the task no longer looks pushed
+      await exec('git', ['branch', '-d', '-r', `${remote}/${branchName}`], {
+        cwd: projectRoot,
+      }).catch(() => {});
+      return;
+    }
+    throw new Error(`Deleting ${remote}/${branchName} failed: ${stderr.trim() || e}`);
+  }
+}
+
+export interface RebaseResult {
+  status: 'done' | 'conflicted';
+  conflicting_files: string[];
+  /** Position of the commit being applied while the rebase is paused. */
+  step: { current: number; total: number } | null;
+}
+
+async function readRebaseStep(
+  worktreePath: string,
+): Promise<{ current: number; total: number } | null> {
+  for (const dir of ['rebase-merge', 'rebase-apply']) {
+    try {
+      const { stdout } = await exec('git', ['rev-parse', '--git-path', dir], { cwd: worktreePath });
+      const stateDir = path.resolve(worktreePath, stdout.trim());
+      const [msgnum, end] = dir === 'rebase-merge' ? ['msgnum', 'end'] : ['next', 'last'];
+      const current = parseInt(await fs.promises.readFile(path.join(stateDir, msgnum), 'utf8'));
+      const total = parseInt(await fs.promises.readFile(path.join(stateDir, end), 'utf8'));
+      if (Number.isFinite(current) && Number.isFinite(total)) return { current, total };
+    } catch {
+      /* not this kind of rebase */
+    }
+  }
+  return null;
+}
+
+async function rebaseState(worktreePath: string): Promise<RebaseResult> {
+  const unmerged = await listUnmergedFiles(worktreePath);
+  if (unmerged.length === 0 && !(await detectConflictOperation(worktreePath))) {
+    return { status: 'done', conflicting_files: [], step: null };
+  }
+  return {
+    status: 'conflicted',
+    conflicting_files: unmerged.map((f) => f.path),
+    step: await readRebaseStep(worktreePath),
+  };
+}
+
+/**
+ * Continue a paused rebase after its conflicts were resolved. Resolves with
+ * the new state: done, or paused again on the next conflicting commit.
+ */
+export async function continueRebase(worktreePath: string): Promise<RebaseResult> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, async () => {
+    await assertNoUnmergedFiles(worktreePath);
+    try {
+      // Later commits that conflict get the same diff3 markers as the first one
+      await exec('git', ['-c', 'merge.conflictStyle=diff3', 'rebase', '--continue'], {
+        cwd: worktreePath,
+        env: NO_EDITOR_ENV,
+      });
+    } catch (e) {
+      const state = await rebaseState(worktreePath);
+      if (state.conflicting_files.length === 0) throw new Error(`Rebase failed: ${e}`);
+      return state;
+    }
+    invalidateMergeBaseCache();
+    return rebaseState(worktreePath);
+  });
+}
+
+export interface ConflictVerification {
+  ok: boolean;
+  /** Files git still reports as unmerged. */
+  unresolved_files: string[];
+  /** Files that still contain conflict markers. */
+  files_with_markers: string[];
+  /** Result of the project's verify command, when one is configured and ran. */
+  check: HookResult | null;
+}
+
+/**
+ * Check that someone (typically the task's agent) resolved `files`: no
+ * conflict markers left, everything staged, and the optional verify command
+ * (e.g. a build) passes. Marker-free files left unstaged are staged here.
+ */
+export async function verifyConflictResolution(
+  worktreePath: string,
+  files: string[],
+  verifyCommand: string | null,
+): Promise<ConflictVerification> {
+  const filesWithMarkers: string[] = [];
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  // Only the staging takes the lock; a slow verify command shouldn't hold up other git work
+  const unresolved = await withWorktreeLock(lockKey, async () => {
+    for (const filePath of files) {
+      const fullPath = path.join(worktreePath, filePath);
+      const exists = fs.existsSync(fullPath);
+      if (exists && !(await isBinaryFile(fullPath))) {
+        if (hasConflictMarkers(await fs.promises.readFile(fullPath, 'utf8'))) {
+          filesWithMarkers.push(filePath);
+          continue;
+        }
+      }
+      const { stdout } = await exec('git', ['ls-files', '-u', '--', filePath], {
+        cwd: worktreePath,
+      });
+      if (stdout.trim()) {
+        await exec('git', exists ? ['add', '--', filePath] : ['rm', '--quiet', '--', filePath], {
+          cwd: worktreePath,
+        });
+      }
+    }
+    return (await listUnmergedFiles(worktreePath)).map((f) => f.path);
+  });
+
+  const clean = filesWithMarkers.length === 0 && unresolved.length === 0;
+  const check =
+    clean && verifyCommand ? await runHookCommand(verifyCommand, worktreePath, {}) : null;
+  return {
+    ok: clean && (check === null || check.exit_code === 0),
+    unresolved_files: unresolved,
+    files_with_markers: filesWithMarkers,
+    check,
+  };
+}
+
+export async function abortRebase(worktreePath: string): Promise<void> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, async () => {
+    await exec('git', ['rebase', '--abort'], { cwd: worktreePath });
+  });
 }
 
-export async function rebaseTask(worktreePath: string): Promise<void> {
+/**
+ * Rebase the worktree's branch onto main. On conflict the rebase is aborted,
+ * unless `pauseOnConflict` is set: then it is left stopped on the conflicting
+ * commit (with diff3 markers) and the conflicted state is returned.
+ */
+export async function rebaseTask(
+  worktreePath: string,
+  pauseOnConflict = false,
+): Promise<RebaseResult> {
   const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
 
   return withWorktreeLock(lockKey, async () => {
     const mainBranch = await detectMainBranch(worktreePath);
+    const snapshot = await createSnapshot(worktreePath, 'Before rebase onto main');
+    await saveUndoRecord(worktreePath, {
+      operation: 'rebase',
+      created_at: Date.now(),
+      branch: await getCurrentBranchName(worktreePath),
+      branch_head: await revParse(worktreePath, 'HEAD'),
+      worktree_path: worktreePath,
+      snapshot_id: snapshot.id,
+    });
     try {
-      await exec('git', ['rebase', mainBranch], { cwd: worktreePath });
+      await exec('git', ['-c', 'merge.conflictStyle=diff3', 'rebase', mainBranch], {
+        cwd: worktreePath,
+      });
     } catch (e) {
+      if (pauseOnConflict) {
+        const state = await rebaseState(worktreePath);
+        if (state.conflicting_files.length > 0) return state;
+      }
       await exec('git', ['rebase', '--abort'], { cwd: worktreePath }).catch((recoverErr) =>
         console.warn('git rebase --abort failed:', recoverErr),
       );
       throw new Error(`Rebase failed: ${e}`);
     }
     invalidateMergeBaseCache();
+    return { status: 'done', conflicting_files: [], step: null };
+  });
+}
+
+export interface PullMainResult {
+  main_branch: string;
+  /** Commits the local main branch moved forward by; 0 when already up to date. */
+  pulled_commits: number;
+}
+
+/**
+ * Fetch origin and fast-forward the local main branch to origin/<main>, so
+ * tasks rebase onto the latest main. Refuses when local main has diverged.
+ */
+export async function pullMain(projectRoot: string): Promise<PullMainResult> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    const mainBranch = await detectMainBranch(projectRoot);
+    try {
+      await exec('git', ['fetch', 'origin', '--', mainBranch], {
+        cwd: projectRoot,
+        timeout: 60_000,
+        env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+      });
+    } catch (e) {
+      throw new Error(`Fetch failed: ${e}`);
+    }
+
+    const { stdout } = await exec(
+      'git',
+      ['rev-list', '--left-right', '--count', `${mainBranch}...origin/${mainBranch}`],
+      { cwd: projectRoot },
+    );
+    const [localOnly, remoteOnly] = stdout.trim().split(/\s+/).map(Number);
+    if (localOnly > 0) {
+      throw new Error(
+        `Local ${mainBranch} has diverged from origin/${mainBranch} ` +
+          `(${localOnly} local commit${localOnly > 1 ? 's' : ''}). Reconcile it manually.`,
+      );
+    }
+    if (!remoteOnly) return { main_branch: mainBranch, pulled_commits: 0 };
+
+    const currentBranch = await getCurrentBranchName(projectRoot).catch(() => null);
+    if (currentBranch === mainBranch) {
+      await exec('git', ['merge', '--ff-only', `origin/${mainBranch}`], { cwd: projectRoot });
+    } else {
+      // Not checked out here: update the ref directly (fetch refuses non-fast-forwards)
+      await exec('git', ['fetch', 'origin', `${mainBranch}:${mainBranch}`], {
+        cwd: projectRoot,
+        timeout: 60_000,
+        env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+      });
+    }
+    invalidateMergeBaseCache();
+    return { main_branch: mainBranch, pulled_commits: remoteOnly };
+  });
+}
+
+// --- Worktree snapshots ---
+
+const SNAPSHOT_REF_PREFIX = 'refs/parallel-code/snapshots';
+/** Oldest snapshots of a branch beyond this count are pruned on create. */
+const MAX_SNAPSHOTS_PER_BRANCH = 50;
+/** Snapshot commits are internal; don't depend on the user's identity being configured. */
+const SNAPSHOT_IDENTITY = {
+  GIT_AUTHOR_NAME: 'Parallel Code',
+  GIT_AUTHOR_EMAIL: 'snapshots@parallel-code.local',
+  GIT_COMMITTER_NAME: 'Parallel Code',
+  GIT_COMMITTER_EMAIL: 'snapshots@parallel-code.local',
+};
+
+export interface WorktreeSnapshot {
+  /** `<branch>/<timestamp>`; pass back to restoreSnapshot. */
+  id: string;
+  label: string;
+  /** Unix ms. */
+  created_at: number;
+  /** The commit HEAD pointed to when the snapshot was taken. */
+  head: string;
+}
+
+async function snapshotBranchPrefix(worktreePath: string): Promise<string> {
+  const branch = await getCurrentBranchName(worktreePath).catch(() => 'detached');
+  return `${SNAPSHOT_REF_PREFIX}/${branch}`;
+}
+
+/**
+ * Write the full working tree (staged, unstaged and untracked, but not ignored
+ * files) as a tree object and return its hash. The worktree and its index are
+ * left untouched.
+ */
+async function writeWorkingTree(worktreePath: string): Promise<string> {
+  const { stdout: indexPath } = await exec('git', ['rev-parse', '--git-path', 'index'], {
+    cwd: worktreePath,
+  });
+  // Stage into a copy of the index so the real one is untouched; copying keeps
+  // git's stat cache so unchanged files aren't rehashed.
+  const tmpIndex = path.join(
+    path.dirname(path.resolve(worktreePath, indexPath.trim())),
+    `parallel-code-snapshot-${process.pid}-${Date.now()}`,
+  );
+  try {
+    await fs.promises.copyFile(path.resolve(worktreePath, indexPath.trim()), tmpIndex);
+    const env = { ...process.env, GIT_INDEX_FILE: tmpIndex };
+    await exec('git', ['add', '-A'], { cwd: worktreePath, env, maxBuffer: MAX_BUFFER });
+    return (await exec('git', ['write-tree'], { cwd: worktreePath, env })).stdout.trim();
+  } finally {
+    await fs.promises.rm(tmpIndex, { force: true });
+  }
+}
+
+/**
+ * Record HEAD plus the full working tree as a commit under
+ * refs/parallel-code/snapshots. Nothing in the worktree or its index changes.
+ */
+export async function createSnapshot(
+  worktreePath: string,
+  label: string,
+): Promise<WorktreeSnapshot> {
+  const head = (await exec('git', ['rev-parse', 'HEAD'], { cwd: worktreePath })).stdout.trim();
+  const tree = await writeWorkingTree(worktreePath);
+  const { stdout: commit } = await exec('git', ['commit-tree', tree, '-p', head, '-m', label], {
+    cwd: worktreePath,
+    env: { ...process.env, ...SNAPSHOT_IDENTITY },
+  });
+
+  const prefix = await snapshotBranchPrefix(worktreePath);
+  const createdAt = Date.now();
+  await exec('git', ['update-ref', `${prefix}/${createdAt}`, commit.trim()], {
+    cwd: worktreePath,
+  });
+
+  const existing = await listSnapshots(worktreePath);
+  for (const old of existing.slice(MAX_SNAPSHOTS_PER_BRANCH)) {
+    await deleteSnapshot(worktreePath, old.id).catch(() => {});
+  }
+  return {
+    id: `${prefix.slice(SNAPSHOT_REF_PREFIX.length + 1)}/${createdAt}`,
+    label,
+    created_at: createdAt,
+    head,
+  };
+}
+
+/** Snapshots of the worktree's current branch, newest first. */
+export async function listSnapshots(worktreePath: string): Promise<WorktreeSnapshot[]> {
+  const prefix = await snapshotBranchPrefix(worktreePath);
+  const { stdout } = await exec(
+    'git',
+    ['for-each-ref', '--format=%(refname)%1f%(parent)%1f%(subject)', `${prefix}/`],
+    { cwd: worktreePath, maxBuffer: MAX_BUFFER },
+  );
+  return stdout
+    .split('\n')
+    .filter(Boolean)
+    .map((line) => {
+      const [refname, parent, label] = line.split('\x1f');
+      const id = refname.slice(SNAPSHOT_REF_PREFIX.length + 1);
+      return {
+        id,
+        label,
+        created_at: Number(id.slice(id.lastIndexOf('/') + 1)) || 0,
+        head: parent.split(' ')[0],
+      };
+    })
+    .sort((a, b) => b.created_at - a.created_at);
+}
+
+function snapshotRef(id: string): string {
+  if (id.split('/').some((part) => part === '..' || part === '')) {
+    throw new Error(`Invalid snapshot id: ${id}`);
+  }
+  return `${SNAPSHOT_REF_PREFIX}/${id}`;
+}
+
+export async function deleteSnapshot(worktreePath: string, id: string): Promise<void> {
+  await exec('git', ['update-ref', '-d', snapshotRef(id)], { cwd: worktreePath });
+}
+
+/** Drop every snapshot of a branch, e.g. once its task's worktree is removed. */
+export async function deleteBranchSnapshots(repoRoot: string, branchName: string): Promise<void> {
+  const { stdout } = await exec(
+    'git',
+    ['for-each-ref', '--format=%(refname)', `${SNAPSHOT_REF_PREFIX}/${branchName}/`],
+    { cwd: repoRoot, maxBuffer: MAX_BUFFER },
+  ).catch(() => ({ stdout: '' }));
+  const refs = stdout.split('\n').filter(Boolean);
+  if (refs.length === 0) return;
+  const input = refs.map((ref) => `delete ${ref}\n`).join('');
+  await new Promise<void>((resolve) => {
+    const proc = execFile('git', ['update-ref', '--stdin'], { cwd: repoRoot }, (err) => {
+      if (err) console.warn(`Failed to delete the snapshots of ${branchName}:`, err);
+      resolve();
+    });
+    proc.stdin?.end(input);
+  });
+}
+
+/**
+ * Put the worktree back to a snapshot: HEAD moves to the snapshot's commit and
+ * the working tree matches the snapshot (all changes come back unstaged).
+ * The current state is snapshotted first, so a restore can itself be undone.
+ */
+export async function restoreSnapshot(worktreePath: string, id: string): Promise<void> {
+  const lockKey = await detectRepoLockKey(worktreePath).catch(() => worktreePath);
+  return withWorktreeLock(lockKey, () => applySnapshot(worktreePath, id));
+}
+
+/** restoreSnapshot without taking the repo lock, for callers that already hold it. */
+async function applySnapshot(worktreePath: string, id: string): Promise<void> {
+  const { stdout } = await exec('git', ['rev-parse', '--verify', `${snapshotRef(id)}^{commit}`], {
+    cwd: worktreePath,
+  });
+  const snapshot = stdout.trim();
+  await createSnapshot(worktreePath, 'Before restoring a snapshot');
+
+  await exec('git', ['reset', '--soft', '-q', `${snapshot}^1`], { cwd: worktreePath });
+  // Index and files become exactly the snapshot's tree, deletions included;
+  // whatever is untracked after that wasn't in the snapshot (ignored files stay)
+  await exec('git', ['read-tree', '-u', '--reset', `${snapshot}^{tree}`], {
+    cwd: worktreePath,
+    maxBuffer: MAX_BUFFER,
+  });
+  await exec('git', ['clean', '-fdq'], { cwd: worktreePath });
+  await exec('git', ['reset', '-q'], { cwd: worktreePath });
+  invalidateMergeBaseCache();
+}
+
+// --- Undo for merge and rebase ---
+
+/** Lives in the repo's common git dir, so every worktree of the project sees it. */
+const UNDO_RECORD_FILE = 'parallel-code-undo.json';
+const UNDO_REF_PREFIX = 'refs/parallel-code/undo';
+
+export interface UndoableOperation {
+  operation: 'merge' | 'rebase';
+  /** Unix ms. */
+  created_at: number;
+  branch: string;
+}
+
+interface UndoRecord extends UndoableOperation {
+  /** Tip of the task branch before the operation. */
+  branch_head: string;
+  /** Merge only: the main branch and its tip before the merge. */
+  main_branch?: string;
+  main_head?: string;
+  /** Merge only: main's tip right after the merge; undo is refused once main moved on. */
+  merged_head?: string;
+  /** Rebase only: the worktree and a snapshot of it before the rebase. */
+  worktree_path?: string;
+  snapshot_id?: string;
+}
+
+async function undoRecordPath(repoPath: string): Promise<string> {
+  return path.join(await detectRepoLockKey(repoPath), UNDO_RECORD_FILE);
+}
+
+async function revParse(cwd: string, rev: string): Promise<string> {
+  const { stdout } = await exec('git', ['rev-parse', '--verify', '--end-of-options', rev], { cwd });
+  return stdout.trim();
+}
+
+/** Replace the recorded operation; the backup refs keep its commits from being pruned. */
+async function saveUndoRecord(repoPath: string, record: UndoRecord): Promise<void> {
+  await exec('git', ['update-ref', `${UNDO_REF_PREFIX}/branch`, record.branch_head], {
+    cwd: repoPath,
+  });
+  if (record.main_head) {
+    await exec('git', ['update-ref', `${UNDO_REF_PREFIX}/main`, record.main_head], {
+      cwd: repoPath,
+    });
+  }
+  await fs.promises.writeFile(await undoRecordPath(repoPath), JSON.stringify(record));
+}
+
+async function readUndoRecord(repoPath: string): Promise<UndoRecord | null> {
+  try {
+    const raw = await fs.promises.readFile(await undoRecordPath(repoPath), 'utf8');
+    return JSON.parse(raw) as UndoRecord;
+  } catch {
+    return null;
+  }
+}
+
+async function clearUndoRecord(repoPath: string): Promise<void> {
+  await fs.promises.rm(await undoRecordPath(repoPath), { force: true });
+}
+
+/**
+ * Whether undoing the record would throw away later work: main has moved past
+ * the merge result (or the record predates `merged_head` being kept).
+ */
+async function isUndoRecordStale(projectRoot: string, record: UndoRecord): Promise<boolean> {
+  if (record.operation !== 'merge') return false;
+  if (!record.main_branch || !record.merged_head) return true;
+  const tip = await revParse(projectRoot, `refs/heads/${record.main_branch}`).catch(() => null);
+  return tip !== record.merged_head;
+}
+
+/** The last merge or rebase that undoLastOperation would revert, if any. */
+export async function getUndoableOperation(projectRoot: string): Promise<UndoableOperation | null> {
+  const record = await readUndoRecord(projectRoot);
+  if (!record) return null;
+  if (await isUndoRecordStale(projectRoot, record)) {
+    await clearUndoRecord(projectRoot);
+    return null;
+  }
+  return { operation: record.operation, created_at: record.created_at, branch: record.branch };
+}
+
+async function refExists(cwd: string, ref: string): Promise<boolean> {
+  return exec('git', ['show-ref', '--verify', '--quiet', ref], { cwd }).then(
+    () => true,
+    () => false,
+  );
+}
+
+/**
+ * Revert the last merge or rebase recorded for the project: a merge moves main
+ * back and recreates the task branch if cleanup deleted it; a rebase puts the
+ * worktree back to its pre-rebase snapshot. A merge is only undone while main
+ * is still at the merge result; otherwise the record is dropped.
+ */
+export async function undoLastOperation(projectRoot: string): Promise<UndoableOperation> {
+  const lockKey = await detectRepoLockKey(projectRoot).catch(() => projectRoot);
+
+  return withWorktreeLock(lockKey, async () => {
+    const record = await readUndoRecord(projectRoot);
+    if (!record) throw new Error('Nothing to undo.');
+    if (await isUndoRecordStale(projectRoot, record)) {
+      await clearUndoRecord(projectRoot);
+      throw new Error(
+        `${record.main_branch ?? 'Main'} has commits since the merge; undoing would lose them.`,
+      );
+    }
+    // The merge created main locally from origin/<main>; there is no earlier tip to restore
+    if (record.operation === 'merge' && !record.main_head) {
+      throw new Error(
+        `${record.main_branch ?? 'Main'} did not exist locally before the merge, so it can't be ` +
+          'moved back. Reset or delete it by hand.',
+      );
+    }
+
+    if (record.operation === 'merge' && record.main_branch && record.main_head) {
+      const current = await getCurrentBranchName(projectRoot).catch(() => null);
+      if (current === record.main_branch) {
+        const { stdout } = await exec('git', ['status', '--porcelain'], { cwd: projectRoot });
+        if (stdout.trim()) {
+          throw new Error('Project root has uncommitted changes. Commit or stash them first.');
+        }
+        await exec('git', ['reset', '--hard', '-q', record.main_head], { cwd: projectRoot });
+      } else {
+        await exec('git', ['update-ref', `refs/heads/${record.main_branch}`, record.main_head], {
+          cwd: projectRoot,
+        });
+      }
+      if (!(await refExists(projectRoot, `refs/heads/${record.branch}`))) {
+        await exec('git', ['branch', '--', record.branch, record.branch_head], {
+          cwd: projectRoot,
+        });
+      }
+    } else if (record.operation === 'rebase') {
+      const worktree = record.worktree_path;
+      if (worktree && fs.existsSync(worktree) && record.snapshot_id) {
+        if ((await detectConflictOperation(worktree)) === 'rebase') {
+          await exec('git', ['rebase', '--abort'], { cwd: worktree }).catch(() => {});
+        }
+        await applySnapshot(worktree, record.snapshot_id);
+      } else {
+        await exec('git', ['update-ref', `refs/heads/${record.branch}`, record.branch_head], {
+          cwd: projectRoot,
+        });
+      }
+    }
+
+    await clearUndoRecord(projectRoot);
+    invalidateMergeBaseCache();
+    return { operation: record.operation, created_at: record.created_at, branch: record.branch };
   });
 }
 
@@ -0,0 +1,34 @@
+import { describe, it, expect } from 'vitest';
+import { matchesAnyGlob } from './glob.js';
+
+describe('matchesAnyGlob', () => {
+  it('matches slash-less patterns against the file name at any depth', () => {
+    expect(matchesAnyGlob('package-lock.json', ['package-lock.json'])).toBe(true);
+    expect(matchesAnyGlob('apps/web/package-lock.json', ['package-lock.json'])).toBe(true);
+    expect(matchesAnyGlob('src/__snapshots__/a.test.ts.snap', ['*.snap'])).toBe(true);
+    expect(matchesAnyGlob('my-package-lock.json', ['package-lock.json'])).toBe(false);
+  });
+
+  it('anchors patterns containing a slash to the repo root', () => {
+    expect(matchesAnyGlob('dist/index.js', ['dist/**'])).toBe(true);
+    expect(matchesAnyGlob('dist/a/b.js', ['dist/**'])).toBe(true);
+    expect(matchesAnyGlob('pkg/dist/index.js', ['dist/**'])).toBe(false);
+    expect(matchesAnyGlob('pkg/dist/index.js', ['**/dist/**'])).toBe(true);
+  });
+
+  it('keeps single stars within one segment', () => {
+    expect(matchesAnyGlob('src/gen/a.ts', ['src/gen/*.ts'])).toBe(true);
+    expect(matchesAnyGlob('src/gen/sub/a.ts', ['src/gen/*.ts'])).toBe(false);
+  });
+
+  it('treats a trailing slash as a directory', () => {
+    expect(matchesAnyGlob('vendor/lib.js', ['vendor/'])).toBe(true);
+    expect(matchesAnyGlob('vendor.js', ['vendor/'])).toBe(false);
+  });
+
+  it('escapes regex characters and ignores blank patterns', () => {
+    expect(matchesAnyGlob('a+b.txt', ['a+b.txt'])).toBe(true);
+    expect(matchesAnyGlob('aab.txt', ['a+b.txt'])).toBe(false);
+    expect(matchesAnyGlob('x', ['', '  '])).toBe(false);
+  });
+});
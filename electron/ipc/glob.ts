@@ -0,0 +1,41 @@
+// Minimal gitignore-style glob matching for repo-relative paths: `*` and `?`
+// stay within a path segment, `**` spans segments, patterns without a slash
+// match the file name at any depth and a trailing slash matches a directory.
+
+function escapeRegExp(ch: string): string {
+  return /[.+^${}()|[\]\\]/.test(ch) ? `\\${ch}` : ch;
+}
+
+export function globToRegExp(pattern: string): RegExp {
+  let p = pattern.trim();
+  if (p.endsWith('/')) p += '**';
+  const anchored = p.includes('/');
+  if (p.startsWith('/')) p = p.slice(1);
+
+  let re = '';
+  for (let i = 0; i < p.length; i++) {
+    const ch = p[i];
+    if (ch === '*' && p[i + 1] === '*') {
+      i++;
+      if (p[i + 1] === '/') {
+        // `**/` matches zero or more leading directories
+        i++;
+        re += '(?:.*/)?';
+      } else {
+        re += '.*';
+      }
+    } else if (ch === '*') {
+      re += '[^/]*';
+    } else if (ch === '?') {
+      re += '[^/]';
+    } else {
+      re += escapeRegExp(ch);
+    }
+  }
+  return new RegExp(anchored ? `^${re}$` : `(?:^|/)${re}$`);
+}
+
+/** Whether `filePath` matches any of the patterns; blank patterns are ignored. */
+export function matchesAnyGlob(filePath: string, patterns: readonly string[]): boolean {
+  return patterns.some((p) => p.trim() !== '' && globToRegExp(p).test(filePath));
+}
@@ -0,0 +1,195 @@
+import { spawn, type ChildProcess } from 'child_process';
+import { validateCommand, type EventSink } from './pty.js';
+
+/**
+ * Non-interactive invocation per agent: print/exec mode with machine-readable
+ * output where the CLI supports it. The user's extra args go before the
+ * prompt, which follows `--` (or is bound to its flag) so a prompt starting
+ * with a dash isn't read as an option. Agents not listed here can't run headless.
+ */
+const HEADLESS_ARGS: Record<string, (prompt: string, extra: string[]) => string[]> = {
+  'claude-code': (prompt, extra) => [
+    '-p',
+    '--output-format',
+    'stream-json',
+    '--verbose',
+    ...extra,
+    '--',
+    prompt,
+  ],
+  codex: (prompt, extra) => ['exec', '--json', ...extra, '--', prompt],
+  gemini: (prompt, extra) => [...extra, `--prompt=${prompt}`],
+  opencode: (prompt, extra) => ['run', ...extra, '--', prompt],
+};
+
+export function hasHeadlessMode(agentDefId: string): boolean {
+  return Object.prototype.hasOwnProperty.call(HEADLESS_ARGS, agentDefId);
+}
+
+export interface HeadlessRunResult {
+  exit_code: number | null;
+  is_error: boolean;
+  /** The agent's final answer: the stream-json result, or plain stdout. */
+  result: string;
+  session_id: string | null;
+  cost_usd: number | null;
+  /** Number of structured events parsed from stdout. */
+  events: number;
+  stderr: string;
+}
+
+interface HeadlessRequest {
+  runId: string;
+  agentDefId: string;
+  command: string;
+  prompt: string;
+  cwd: string;
+  extraArgs?: string[];
+  env?: Record<string, string>;
+  /** Progress messages go to `channel:${channelId}` when set. */
+  channelId?: string;
+  timeoutMs?: number;
+}
+
+const MAX_PROMPT_LENGTH = 100_000;
+const DEFAULT_TIMEOUT_MS = 30 * 60_000;
+const STDERR_CAP = 8 * 1024;
+const KILL_GRACE_MS = 5_000;
+
+const activeRuns = new Map<string, ChildProcess>();
+
+/**
+ * Run an agent once without a PTY and resolve with its parsed result. Each
+ * stdout line that parses as JSON is forwarded as an `event`, other lines as
+ * `stdout`; `done` carries the final result.
+ */
+export function runHeadless(win: EventSink, req: HeadlessRequest): Promise<HeadlessRunResult> {
+  if (!hasHeadlessMode(req.agentDefId)) {
+    throw new Error(`Agent ${req.agentDefId} has no headless mode`);
+  }
+  const buildArgs = HEADLESS_ARGS[req.agentDefId];
+  if (req.prompt.length > MAX_PROMPT_LENGTH) {
+    throw new Error(`Prompt too long (${req.prompt.length} chars, max ${MAX_PROMPT_LENGTH})`);
+  }
+  if (activeRuns.has(req.runId)) throw new Error(`Run already active: ${req.runId}`);
+  validateCommand(req.command);
+
+  const env: Record<string, string> = {};
+  for (const [k, v] of Object.entries(process.env)) {
+    if (v !== undefined) env[k] = v;
+  }
+  // Clear env vars that prevent nested agent sessions
+  delete env.CLAUDECODE;
+  delete env.CLAUDE_CODE_SESSION;
+  delete env.CLAUDE_CODE_ENTRYPOINT;
+  Object.assign(env, req.env ?? {});
+
+  const proc = spawn(req.command, buildArgs(req.prompt, req.extraArgs ?? []), {
+    cwd: req.cwd,
+    env,
+    stdio: ['ignore', 'pipe', 'pipe'],
+  });
+  activeRuns.set(req.runId, proc);
+
+  const send = (msg: unknown) => {
+    if (req.channelId && !win.isDestroyed()) {
+      win.webContents.send(`channel:${req.channelId}`, msg);
+    }
+  };
+
+  const result: HeadlessRunResult = {
+    exit_code: null,
+    is_error: false,
+    result: '',
+    session_id: null,
+    cost_usd: null,
+    events: 0,
+    stderr: '',
+  };
+  let plainStdout = '';
+  let structuredResult: string | null = null;
+  let pending = '';
+
+  const handleLine = (line: string) => {
+    if (!line.trim()) return;
+    let event: Record<string, unknown> | null = null;
+    try {
+      const parsed: unknown = JSON.parse(line);
+      if (parsed && typeof parsed === 'object') event = parsed as Record<string, unknown>;
+    } catch {
+      /* not JSON */
+    }
+    if (!event) {
+      plainStdout += line + '\n';
+      send({ type: 'stdout', text: line });
+      return;
+    }
+    result.events++;
+    if (typeof event.session_id === 'string') result.session_id = event.session_id;
+    // Claude stream-json final message
+    if (event.type === 'result') {
+      if (typeof event.result === 'string') structuredResult = event.result;
+      if (event.is_error === true) result.is_error = true;
+      if (typeof event.total_cost_usd === 'number') result.cost_usd = event.total_cost_usd;
+    }
+    // Codex exec --json: the last agent message is the answer
+    const item = event.item as Record<string, unknown> | undefined;
+    if (item?.type === 'agent_message' && typeof item.text === 'string') {
+      structuredResult = item.text;
+    }
+    send({ type: 'event', event });
+  };
+
+  proc.stdout?.on('data', (chunk: Buffer) => {
+    pending += chunk.toString('utf8');
+    const lines = pending.split('\n');
+    pending = lines.pop() ?? '';
+    for (const line of lines) handleLine(line);
+  });
+
+  proc.stderr?.on('data', (chunk: Buffer) => {
+    const text = chunk.toString('utf8');
+    result.stderr = (result.stderr + text).slice(-STDERR_CAP);
+    send({ type: 'stderr', text });
+  });
+
+  const timeoutMs = req.timeoutMs ?? DEFAULT_TIMEOUT_MS;
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(() => {
+      result.is_error = true;
+      result.stderr += `\nTimed out after ${Math.round(timeoutMs / 1000)}s`;
+      stopRun(proc);
+    }, timeoutMs);
+
+    proc.on('close', (code) => {
+      clearTimeout(timer);
+      activeRuns.delete(req.runId);
+      if (pending) handleLine(pending);
+      result.exit_code = code;
+      if (code !== 0) result.is_error = true;
+      result.result = structuredResult ?? plainStdout.trimEnd();
+      send({ type: 'done', result });
+      resolve(result);
+    });
+
+    proc.on('error', (err) => {
+      clearTimeout(timer);
+      activeRuns.delete(req.runId);
+      send({ type: 'stderr', text: err.message });
+      reject(err);
+    });
+  });
+}
+
+/** SIGTERM, then SIGKILL if the agent is still running after a grace period. */
+function stopRun(proc: ChildProcess): void {
+  proc.kill('SIGTERM');
+  setTimeout(() => {
+    if (proc.exitCode === null && proc.signalCode === null) proc.kill('SIGKILL');
+  }, KILL_GRACE_MS).unref();
+}
+
+export function cancelHeadless(runId: string): void {
+  const proc = activeRuns.get(runId);
+  if (proc) stopRun(proc);
+}
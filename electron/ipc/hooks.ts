@@ -0,0 +1,134 @@
+import { spawn } from 'child_process';
+import type { MergeStrategy } from './git.js';
+
+/** Outcome of a single user-configured hook command. */
+export interface HookResult {
+  command: string;
+  exit_code: number | null;
+  output: string;
+}
+
+const HOOK_TIMEOUT = 5 * 60_000; // 5 min
+const CHECK_TIMEOUT = 30 * 60_000; // test suites can take a while
+const HOOK_OUTPUT_CAP = 8 * 1024; // keep only the tail of hook output
+
+/**
+ * Run a single command through the user's shell. Output (stdout + stderr) is
+ * captured and capped to the last HOOK_OUTPUT_CAP bytes, and also passed
+ * uncapped to `onOutput` as it arrives. Never rejects — a spawn failure or
+ * timeout is reported as a null exit code.
+ */
+export function runHookCommand(
+  command: string,
+  cwd: string,
+  env: Record<string, string>,
+  timeoutMs = HOOK_TIMEOUT,
+  onOutput?: (text: string) => void,
+): Promise<HookResult> {
+  return new Promise((resolve) => {
+    const shell = process.env.SHELL || '/bin/sh';
+    const proc = spawn(shell, ['-c', command], {
+      cwd,
+      env: { ...process.env, ...env },
+      stdio: ['ignore', 'pipe', 'pipe'],
+      // Own process group so a timeout also stops what the command started
+      detached: process.platform !== 'win32',
+    });
+
+    let output = '';
+    const append = (chunk: Buffer) => {
+      const text = chunk.toString('utf8');
+      onOutput?.(text);
+      output += text;
+      if (output.length > HOOK_OUTPUT_CAP) output = output.slice(-HOOK_OUTPUT_CAP);
+    };
+    proc.stdout?.on('data', append);
+    proc.stderr?.on('data', append);
+
+    let settled = false;
+    const finish = (exitCode: number | null, extra?: string) => {
+      if (settled) return;
+      settled = true;
+      clearTimeout(timer);
+      if (extra) onOutput?.(extra);
+      resolve({ command, exit_code: exitCode, output: extra ? output + extra : output });
+    };
+
+    const timer = setTimeout(() => {
+      try {
+        if (process.platform === 'win32' || proc.pid === undefined) proc.kill('SIGKILL');
+        else process.kill(-proc.pid, 'SIGKILL');
+      } catch {
+        /* already exited */
+      }
+      finish(null, `\nTimed out after ${Math.round(timeoutMs / 1000)}s`);
+    }, timeoutMs);
+
+    proc.on('error', (err) => finish(null, `\nFailed to run: ${err.message}`));
+    proc.on('close', (code) => finish(code));
+  });
+}
+
+/**
+ * Run pre-merge checks (tests, lint) sequentially in a checkout of the task's
+ * branch, streaming their output. Stops at the first failing command.
+ */
+export async function runPreMergeChecks(
+  commands: string[],
+  worktreePath: string,
+  branchName: string,
+  onOutput?: (text: string) => void,
+): Promise<HookResult[]> {
+  const env = { PARALLEL_CODE_BRANCH: branchName, PARALLEL_CODE_WORKTREE: worktreePath };
+  const results: HookResult[] = [];
+  for (const raw of commands) {
+    const command = raw.trim();
+    if (!command) continue;
+    onOutput?.(`$ ${command}\n`);
+    const result = await runHookCommand(command, worktreePath, env, CHECK_TIMEOUT, onOutput);
+    results.push(result);
+    if (result.exit_code !== 0) break;
+  }
+  return results;
+}
+
+/** Details about a completed merge, exposed to post-merge hooks as env vars. */
+export interface MergeHookInfo {
+  projectRoot: string;
+  branchName: string;
+  mainBranch: string;
+  mergeCommit: string;
+  strategy: MergeStrategy;
+  linesAdded: number;
+  linesRemoved: number;
+}
+
+/**
+ * Run post-merge commands sequentially in the project root. Stops at the first
+ * failing command so later steps (e.g. a deploy) don't run on a broken state.
+ */
+export async function runPostMergeHooks(
+  commands: string[],
+  info: MergeHookInfo,
+): Promise<HookResult[]> {
+  const env: Record<string, string> = {
+    PARALLEL_CODE_PROJECT_ROOT: info.projectRoot,
+    PARALLEL_CODE_BRANCH: info.branchName,
+    PARALLEL_CODE_MAIN_BRANCH: info.mainBranch,
+    PARALLEL_CODE_MERGE_COMMIT: info.mergeCommit,
+    PARALLEL_CODE_SQUASH: info.strategy === 'squash' ? '1' : '0',
+    PARALLEL_CODE_MERGE_STRATEGY: info.strategy,
+    PARALLEL_CODE_LINES_ADDED: String(info.linesAdded),
+    PARALLEL_CODE_LINES_REMOVED: String(info.linesRemoved),
+  };
+
+  const results: HookResult[] = [];
+  for (const raw of commands) {
+    const command = raw.trim();
+    if (!command) continue;
+    const result = await runHookCommand(command, info.projectRoot, env);
+    results.push(result);
+    if (result.exit_code !== 0) break;
+  }
+  return results;
+}
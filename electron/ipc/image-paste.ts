@@ -0,0 +1,44 @@
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { randomUUID } from 'crypto';
+import { pasteToAgent } from './pty.js';
+
+const IMAGE_EXTENSIONS: Record<string, string> = {
+  'image/png': 'png',
+  'image/jpeg': 'jpg',
+  'image/gif': 'gif',
+  'image/webp': 'webp',
+};
+
+const MAX_IMAGE_BYTES = 20 * 1024 * 1024;
+
+/** Scratch dir for pasted images, outside worktrees so they never show up in diffs. */
+function pasteDir(): string {
+  return path.join(os.tmpdir(), 'parallel-code-pastes');
+}
+
+/**
+ * Write a pasted image to a temp file and paste its path into the agent's
+ * terminal, the way a real terminal does on image paste/drop. Claude Code
+ * recognises a pasted image path and attaches the file.
+ */
+export async function pasteImageToAgent(
+  agentId: string,
+  base64: string,
+  mimeType: string,
+): Promise<string> {
+  const ext = IMAGE_EXTENSIONS[mimeType];
+  if (!ext) throw new Error(`Unsupported image type: ${mimeType}`);
+  const bytes = Buffer.from(base64, 'base64');
+  if (bytes.length === 0) throw new Error('Pasted image is empty');
+  if (bytes.length > MAX_IMAGE_BYTES) throw new Error('Pasted image is larger than 20 MB');
+
+  const dir = pasteDir();
+  fs.mkdirSync(dir, { recursive: true, mode: 0o700 });
+  const file = path.join(dir, `paste-${Date.now()}-${randomUUID().slice(0, 8)}.${ext}`);
+  fs.writeFileSync(file, bytes, { mode: 0o600 });
+
+  await pasteToAgent(agentId, file);
+  return file;
+}
@@ -0,0 +1,226 @@
+import { spawn, type ChildProcess } from 'child_process';
+import { randomUUID } from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import { IPC } from './channels.js';
+import type { EventSink } from './pty.js';
+import {
+  allocatePort,
+  detectServerUrl,
+  portEnv,
+  releaseAllPorts,
+  releasePort,
+  withPortFlag,
+} from './ports.js';
+
+export type JobStatus = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+/** A script run for a task, outside the agent's terminal. */
+export interface JobInfo {
+  id: string;
+  task_id: string;
+  script_id: string;
+  name: string;
+  command: string;
+  status: JobStatus;
+  exit_code: number | null;
+  started_at: number;
+  finished_at: number | null;
+  /** Port assigned to a dev-server job, null for plain scripts. */
+  port: number | null;
+  /** URL the dev server printed, once seen in its output. */
+  url: string | null;
+}
+
+interface Job {
+  info: JobInfo;
+  proc: ChildProcess | null;
+  /** Tail of the combined output, replayed when a view opens late. */
+  output: string;
+}
+
+interface RunScriptRequest {
+  taskId: string;
+  scriptId: string;
+  name: string;
+  command: string;
+  cwd: string;
+  env?: Record<string, string>;
+  /** Give the job the task's reserved port and watch its output for the URL. */
+  devServer?: boolean;
+}
+
+const OUTPUT_CAP = 256 * 1024;
+/** Finished jobs kept per task for their status and output. */
+const MAX_FINISHED_PER_TASK = 10;
+const KILL_GRACE_MS = 5_000;
+/** Output tail searched for the dev server's URL, enough for a split chunk. */
+const URL_SCAN_CHARS = 4096;
+
+const jobs = new Map<string, Job>();
+
+function emitChanged(win: EventSink, job: Job): void {
+  if (!win.isDestroyed()) win.webContents.send(IPC.JobChanged, { ...job.info });
+}
+
+function pruneFinished(taskId: string): void {
+  const finished = [...jobs.values()]
+    .filter((j) => j.info.task_id === taskId && j.info.status !== 'running')
+    .sort((a, b) => b.info.started_at - a.info.started_at);
+  for (const job of finished.slice(MAX_FINISHED_PER_TASK)) jobs.delete(job.info.id);
+}
+
+/** Signal the job's process group, so children like a dev server go too. */
+function signalJob(proc: ChildProcess, signal: NodeJS.Signals): void {
+  if (proc.pid === undefined) return;
+  try {
+    if (process.platform === 'win32') proc.kill(signal);
+    else process.kill(-proc.pid, signal);
+  } catch {
+    /* already exited */
+  }
+}
+
+function runningDevServer(taskId: string): Job | undefined {
+  return [...jobs.values()].find(
+    (j) => j.info.task_id === taskId && j.info.port !== null && j.info.status === 'running',
+  );
+}
+
+/** The `scripts` of the package.json in `cwd`, if there is one. */
+function packageScripts(cwd: string): Record<string, unknown> {
+  try {
+    const pkg = JSON.parse(fs.readFileSync(path.join(cwd, 'package.json'), 'utf8'));
+    return pkg && typeof pkg.scripts === 'object' && pkg.scripts ? pkg.scripts : {};
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Run a script for a task through the user's shell. Output is pushed as
+ * `JobOutput` events and status changes as `JobChanged` events. Dev-server
+ * jobs get the task's port in `PORT` and friends (and as `--port` for Vite),
+ * so worktrees don't fight over the same one.
+ */
+export async function runScript(win: EventSink, req: RunScriptRequest): Promise<JobInfo> {
+  let port: number | null = null;
+  if (req.devServer) {
+    if (runningDevServer(req.taskId)) throw new Error('A dev server is already running');
+    port = await allocatePort(req.taskId);
+  }
+  const command =
+    port !== null ? withPortFlag(req.command, port, packageScripts(req.cwd)) : req.command;
+  const shell = process.env.SHELL || '/bin/sh';
+  const proc = spawn(shell, ['-c', command], {
+    cwd: req.cwd,
+    env: { ...process.env, ...req.env, ...(port !== null ? portEnv(port) : {}) },
+    stdio: ['ignore', 'pipe', 'pipe'],
+    // Own process group so cancelling reaches everything the script started
+    detached: process.platform !== 'win32',
+  });
+
+  const job: Job = {
+    info: {
+      id: randomUUID(),
+      task_id: req.taskId,
+      script_id: req.scriptId,
+      name: req.name,
+      command: req.command,
+      status: 'running',
+      exit_code: null,
+      started_at: Date.now(),
+      finished_at: null,
+      port,
+      url: null,
+    },
+    proc,
+    output: '',
+  };
+  jobs.set(job.info.id, job);
+
+  const append = (chunk: Buffer) => {
+    const text = chunk.toString('utf8');
+    job.output += text;
+    if (job.output.length > OUTPUT_CAP) job.output = job.output.slice(-OUTPUT_CAP);
+    if (!win.isDestroyed()) win.webContents.send(IPC.JobOutput, { jobId: job.info.id, text });
+    if (job.info.port !== null && job.info.url === null) {
+      const url = detectServerUrl(job.output.slice(-URL_SCAN_CHARS), job.info.port);
+      if (url) {
+        job.info.url = url;
+        emitChanged(win, job);
+      }
+    }
+  };
+  proc.stdout?.on('data', append);
+  proc.stderr?.on('data', append);
+
+  const finish = (exitCode: number | null, extra?: string) => {
+    if (job.info.finished_at !== null) return;
+    if (extra) append(Buffer.from(extra));
+    job.proc = null;
+    job.info.exit_code = exitCode;
+    job.info.finished_at = Date.now();
+    if (job.info.status === 'running') job.info.status = exitCode === 0 ? 'succeeded' : 'failed';
+    emitChanged(win, job);
+    pruneFinished(req.taskId);
+  };
+  proc.on('error', (err) => finish(null, `\nFailed to run: ${err.message}\n`));
+  proc.on('close', (code) => finish(code));
+
+  emitChanged(win, job);
+  return { ...job.info };
+}
+
+/** Stop a running job: SIGTERM to its process group, SIGKILL if it lingers. */
+export function cancelJob(jobId: string): void {
+  const job = jobs.get(jobId);
+  if (!job?.proc || job.info.status !== 'running') return;
+  const proc = job.proc;
+  job.info.status = 'cancelled';
+  signalJob(proc, 'SIGTERM');
+  setTimeout(() => {
+    if (job.proc === proc) signalJob(proc, 'SIGKILL');
+  }, KILL_GRACE_MS).unref();
+}
+
+/** Jobs of one task, or of all tasks, newest first. */
+export function listJobs(taskId?: string): JobInfo[] {
+  return [...jobs.values()]
+    .filter((j) => !taskId || j.info.task_id === taskId)
+    .map((j) => ({ ...j.info }))
+    .sort((a, b) => b.started_at - a.started_at);
+}
+
+/** The retained output tail of a job; empty for unknown jobs. */
+export function getJobOutput(jobId: string): string {
+  return jobs.get(jobId)?.output ?? '';
+}
+
+/**
+ * Where to preview the task's running dev server: the URL it printed, or its
+ * assigned port on localhost until it prints one. Null when none is running.
+ */
+export function getPreviewUrl(taskId: string): string | null {
+  const job = runningDevServer(taskId);
+  if (!job) return null;
+  return job.info.url ?? `http://localhost:${job.info.port}`;
+}
+
+/** Cancel a closed task's jobs, forget them and free its port. */
+export function removeTaskJobs(taskId: string): void {
+  for (const job of [...jobs.values()]) {
+    if (job.info.task_id !== taskId) continue;
+    cancelJob(job.info.id);
+    jobs.delete(job.info.id);
+  }
+  releasePort(taskId);
+}
+
+export function killAllJobs(): void {
+  for (const job of jobs.values()) {
+    if (job.proc) signalJob(job.proc, 'SIGKILL');
+  }
+  jobs.clear();
+  releaseAllPorts();
+}
@@ -0,0 +1,71 @@
+import { describe, it, expect } from 'vitest';
+import { parseLfsPointer, collapseLfsPointerDiffs } from './lfs.js';
+
+const OID_A = 'a'.repeat(64);
+const OID_B = 'b'.repeat(64);
+
+function pointer(oid: string, size: number): string {
+  return `version https://git-lfs.github.com/spec/v1\noid sha256:${oid}\nsize ${size}\n`;
+}
+
+describe('parseLfsPointer', () => {
+  it('reads the oid and size of a pointer file', () => {
+    expect(parseLfsPointer(pointer(OID_A, 1234))).toEqual({ oid: `sha256:${OID_A}`, size: 1234 });
+  });
+
+  it('rejects ordinary text and malformed pointers', () => {
+    expect(parseLfsPointer('hello\n')).toBeNull();
+    expect(parseLfsPointer('')).toBeNull();
+    expect(parseLfsPointer(pointer('xyz', 10))).toBeNull();
+    expect(
+      parseLfsPointer(`version https://git-lfs.github.com/spec/v1\noid sha256:${OID_A}\n`),
+    ).toBeNull();
+    expect(parseLfsPointer(pointer(OID_A, 1) + 'x'.repeat(2000))).toBeNull();
+  });
+});
+
+describe('collapseLfsPointerDiffs', () => {
+  it('turns a pointer change into a binary notice', () => {
+    const diff = [
+      'diff --git a/art.psd b/art.psd',
+      'index 111..222 100644',
+      '--- a/art.psd',
+      '+++ b/art.psd',
+      '@@ -1,3 +1,3 @@',
+      ' version https://git-lfs.github.com/spec/v1',
+      `-oid sha256:${OID_A}`,
+      '-size 100',
+      `+oid sha256:${OID_B}`,
+      '+size 200',
+      '',
+    ].join('\n');
+    expect(collapseLfsPointerDiffs(diff)).toBe(
+      [
+        'diff --git a/art.psd b/art.psd',
+        'index 111..222 100644',
+        'Binary files a/art.psd and b/art.psd differ',
+        '',
+      ].join('\n'),
+    );
+  });
+
+  it('handles added pointers and leaves other files alone', () => {
+    const text = 'diff --git a/x.txt b/x.txt\n--- a/x.txt\n+++ b/x.txt\n@@ -1 +1 @@\n-a\n+b\n';
+    const added = [
+      'diff --git a/big.bin b/big.bin',
+      'new file mode 100644',
+      '--- /dev/null',
+      '+++ b/big.bin',
+      '@@ -0,0 +1,3 @@',
+      '+version https://git-lfs.github.com/spec/v1',
+      `+oid sha256:${OID_A}`,
+      '+size 5',
+      '',
+    ].join('\n');
+    expect(collapseLfsPointerDiffs(text + added)).toBe(
+      text +
+        'diff --git a/big.bin b/big.bin\nnew file mode 100644\n' +
+        'Binary files /dev/null and b/big.bin differ\n',
+    );
+  });
+});
@@ -0,0 +1,61 @@
+// Git LFS pointer files: the small text stubs git stores in place of
+// LFS-tracked content. Diffs of them are noise, so they are shown as binary.
+
+export interface LfsPointer {
+  oid: string;
+  size: number;
+}
+
+/** Pointer files are capped at 1024 bytes by the LFS spec. */
+const MAX_POINTER_BYTES = 1024;
+
+/** The object a pointer file refers to, or null if `text` is not a pointer. */
+export function parseLfsPointer(text: string): LfsPointer | null {
+  if (!text || text.length > MAX_POINTER_BYTES) return null;
+  const lines = text.split('\n').filter((l) => l !== '');
+  if (!lines[0]?.startsWith('version https://git-lfs.github.com/spec/')) return null;
+  let oid = '';
+  let size = -1;
+  for (const line of lines.slice(1)) {
+    const m = /^(\S+) (.+)$/.exec(line);
+    if (!m) return null;
+    if (m[1] === 'oid') oid = m[2];
+    else if (m[1] === 'size' && /^\d+$/.test(m[2])) size = Number(m[2]);
+  }
+  if (!/^sha256:[0-9a-f]{64}$/.test(oid) || size < 0) return null;
+  return { oid, size };
+}
+
+/**
+ * Rewrite file sections of a unified diff that only change an LFS pointer
+ * into git's "Binary files ... differ" form, keeping the section headers.
+ */
+export function collapseLfsPointerDiffs(diff: string): string {
+  const sections = diff.split(/^(?=diff --git )/m);
+  return sections.map(collapseSection).join('');
+}
+
+function collapseSection(section: string): string {
+  const lines = section.split('\n');
+  const hunkStart = lines.findIndex((l) => l.startsWith('@@'));
+  if (hunkStart < 0) return section;
+  const oldSide: string[] = [];
+  const newSide: string[] = [];
+  for (const line of lines.slice(hunkStart)) {
+    if (line.startsWith('@@') || line.startsWith('\\') || line === '') continue;
+    if (line[0] !== '+') oldSide.push(line.slice(1));
+    if (line[0] !== '-') newSide.push(line.slice(1));
+  }
+  const oldText = oldSide.join('\n');
+  const newText = newSide.join('\n');
+  const oldPointer = parseLfsPointer(oldText);
+  const newPointer = parseLfsPointer(newText);
+  if (!oldPointer && !newPointer) return section;
+  if ((!oldPointer && oldText) || (!newPointer && newText)) return section;
+
+  const header = lines.slice(0, hunkStart);
+  const from = header.find((l) => l.startsWith('--- '))?.slice(4) ?? '/dev/null';
+  const to = header.find((l) => l.startsWith('+++ '))?.slice(4) ?? '/dev/null';
+  const kept = header.filter((l) => !l.startsWith('--- ') && !l.startsWith('+++ '));
+  return [...kept, `Binary files ${from} and ${to} differ`, ''].join('\n');
+}
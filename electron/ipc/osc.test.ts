@@ -0,0 +1,60 @@
+import { describe, it, expect } from 'vitest';
+import { scanOsc, windowTitle, clipboardWrite } from './osc.js';
+
+describe('scanOsc', () => {
+  it('extracts BEL- and ST-terminated sequences', () => {
+    const { sequences, carry } = scanOsc('', 'a\x1b]0;✳ Brewing…\x07b\x1b]2;Waiting\x1b\\c');
+    expect(sequences).toEqual([
+      { code: 0, payload: '✳ Brewing…' },
+      { code: 2, payload: 'Waiting' },
+    ]);
+    expect(carry).toBe('');
+  });
+
+  it('carries an unterminated sequence into the next chunk', () => {
+    const first = scanOsc('', 'out\x1b]2;Half');
+    expect(first.sequences).toEqual([]);
+    const second = scanOsc(first.carry, ' done\x07');
+    expect(second.sequences).toEqual([{ code: 2, payload: 'Half done' }]);
+  });
+
+  it('carries a trailing ESC that may start a sequence', () => {
+    const first = scanOsc('', 'text\x1b');
+    expect(first.carry).toBe('\x1b');
+    expect(scanOsc(first.carry, ']0;t\x07').sequences).toEqual([{ code: 0, payload: 't' }]);
+  });
+
+  it('counts bells outside sequences but not BEL terminators', () => {
+    expect(scanOsc('', 'a\x07b\x1b]0;t\x07c\x07').bells).toBe(2);
+    expect(scanOsc('', '\x1b]0;t\x1b\\').bells).toBe(0);
+  });
+
+  it('ignores sequences without a numeric code', () => {
+    expect(scanOsc('', '\x1b]x;y\x07').sequences).toEqual([]);
+  });
+});
+
+describe('windowTitle', () => {
+  it('returns the payload of OSC 0 and 2 only', () => {
+    expect(windowTitle({ code: 0, payload: 'a' })).toBe('a');
+    expect(windowTitle({ code: 2, payload: 'b' })).toBe('b');
+    expect(windowTitle({ code: 8, payload: ';https://x' })).toBeNull();
+  });
+});
+
+describe('clipboardWrite', () => {
+  it('decodes the base64 payload of an OSC 52 write', () => {
+    const data = Buffer.from('copied ✓').toString('base64');
+    expect(clipboardWrite({ code: 52, payload: `c;${data}` })).toBe('copied ✓');
+  });
+
+  it('ignores clipboard queries and malformed payloads', () => {
+    expect(clipboardWrite({ code: 52, payload: 'c;?' })).toBeNull();
+    expect(clipboardWrite({ code: 52, payload: 'nodata' })).toBeNull();
+    expect(clipboardWrite({ code: 52, payload: 'c;not base64!' })).toBeNull();
+  });
+
+  it('ignores other OSC codes', () => {
+    expect(clipboardWrite({ code: 2, payload: 'c;YQ==' })).toBeNull();
+  });
+});
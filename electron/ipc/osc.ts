@@ -0,0 +1,77 @@
+// Operating System Command sequences (ESC ] <code> ; <payload> BEL|ST) pulled
+// out of the raw PTY stream. Agents use them for window titles and clipboard
+// writes; xterm.js still receives the bytes untouched.
+
+/**
+ * Unterminated sequences longer than this are dropped instead of carried
+ * over. OSC 52 clipboard payloads are by far the largest.
+ */
+const MAX_OSC_LENGTH = 256 * 1024;
+
+export interface OscSequence {
+  code: number;
+  payload: string;
+}
+
+export interface OscScan {
+  sequences: OscSequence[];
+  /** BEL characters outside OSC sequences, i.e. the terminal bell rung. */
+  bells: number;
+  /** An unterminated sequence to prepend to the next chunk. */
+  carry: string;
+}
+
+function countBells(text: string, from: number, to: number): number {
+  let count = 0;
+  for (let i = text.indexOf('\x07', from); i !== -1 && i < to; i = text.indexOf('\x07', i + 1)) {
+    count++;
+  }
+  return count;
+}
+
+/** Extract complete OSC sequences and count bells in `carry + data`. */
+export function scanOsc(carry: string, data: string): OscScan {
+  const text = carry + data;
+  const sequences: OscSequence[] = [];
+  let bells = 0;
+  let pos = 0;
+  for (;;) {
+    const start = text.indexOf('\x1b]', pos);
+    bells += countBells(text, pos, start === -1 ? text.length : start);
+    if (start === -1) {
+      return { sequences, bells, carry: text.endsWith('\x1b') ? '\x1b' : '' };
+    }
+    const bel = text.indexOf('\x07', start + 2);
+    const st = text.indexOf('\x1b\\', start + 2);
+    const end = bel === -1 ? st : st === -1 ? bel : Math.min(bel, st);
+    if (end === -1) {
+      const rest = text.slice(start);
+      return { sequences, bells, carry: rest.length > MAX_OSC_LENGTH ? '' : rest };
+    }
+    const body = text.slice(start + 2, end);
+    const semi = body.indexOf(';');
+    const code = semi === -1 ? body : body.slice(0, semi);
+    if (/^\d+$/.test(code)) {
+      sequences.push({ code: Number(code), payload: semi === -1 ? '' : body.slice(semi + 1) });
+    }
+    pos = end === bel ? end + 1 : end + 2;
+  }
+}
+
+/**
+ * Text of an OSC 52 clipboard write (`52;<selection>;<base64>`). Queries
+ * (`?`) return null: reading the clipboard back into the PTY isn't supported.
+ */
+export function clipboardWrite(seq: OscSequence): string | null {
+  if (seq.code !== 52) return null;
+  const semi = seq.payload.indexOf(';');
+  if (semi === -1) return null;
+  const data = seq.payload.slice(semi + 1);
+  if (data === '?' || !/^[A-Za-z0-9+/]*=*$/.test(data)) return null;
+  return Buffer.from(data, 'base64').toString('utf8');
+}
+
+/** The window title set by OSC 0 (icon + title) or OSC 2 (title). */
+export function windowTitle(seq: OscSequence): string | null {
+  return seq.code === 0 || seq.code === 2 ? seq.payload : null;
+}
@@ -0,0 +1,60 @@
+import { describe, it, expect } from 'vitest';
+import { safeFlushBoundary, alignToCodepoint } from './output-boundary.js';
+
+const buf = (s: string) => Buffer.from(s, 'utf8');
+
+describe('safeFlushBoundary', () => {
+  it('flushes complete text in full', () => {
+    const b = buf('hello \x1b[1mworld\x1b[0m ✓');
+    expect(safeFlushBoundary(b)).toBe(b.length);
+  });
+
+  it('holds back a truncated multi-byte code point', () => {
+    const full = buf('ok ✓');
+    const cut = full.subarray(0, full.length - 1);
+    expect(safeFlushBoundary(cut)).toBe(3);
+  });
+
+  it('holds back a truncated 4-byte code point', () => {
+    const full = buf('a🙂');
+    expect(safeFlushBoundary(full.subarray(0, 3))).toBe(1);
+    expect(safeFlushBoundary(full)).toBe(full.length);
+  });
+
+  it('holds back a lone trailing ESC', () => {
+    expect(safeFlushBoundary(buf('abc\x1b'))).toBe(3);
+  });
+
+  it('holds back an unterminated CSI sequence', () => {
+    expect(safeFlushBoundary(buf('abc\x1b[38;5;2'))).toBe(3);
+    expect(safeFlushBoundary(buf('abc\x1b[38;5;2m'))).toBe(12);
+  });
+
+  it('holds back an OSC sequence until BEL or ST', () => {
+    expect(safeFlushBoundary(buf('x\x1b]0;title'))).toBe(1);
+    expect(safeFlushBoundary(buf('x\x1b]0;title\x07'))).toBe(11);
+    expect(safeFlushBoundary(buf('x\x1b]0;title\x1b\\'))).toBe(12);
+  });
+
+  it('holds back an escape with an intermediate but no final byte', () => {
+    expect(safeFlushBoundary(buf('x\x1b('))).toBe(1);
+    expect(safeFlushBoundary(buf('x\x1b(B'))).toBe(4);
+  });
+
+  it('does not hold back very long unterminated sequences', () => {
+    const b = buf('\x1b]52;c;' + 'A'.repeat(8192));
+    expect(safeFlushBoundary(b)).toBe(b.length);
+  });
+});
+
+describe('alignToCodepoint', () => {
+  it('drops leading continuation bytes', () => {
+    const b = buf('✓ok').subarray(1);
+    expect(alignToCodepoint(b).toString('utf8')).toBe('ok');
+  });
+
+  it('leaves aligned buffers untouched', () => {
+    const b = buf('ok');
+    expect(alignToCodepoint(b)).toBe(b);
+  });
+});
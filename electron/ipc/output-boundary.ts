@@ -0,0 +1,62 @@
+// Flush boundaries for PTY output: a chunk handed to the renderer should never
+// end inside a UTF-8 code point or an escape sequence, or xterm.js can render
+// mojibake / stray escape bytes when the chunks are decoded independently.
+
+const ESC = 0x1b;
+const BEL = 0x07;
+
+/** Sequences longer than this are flushed as-is rather than held back. */
+const MAX_HELD_BYTES = 4096;
+
+/** Start of a truncated trailing UTF-8 code point, or buf.length if none. */
+function utf8Boundary(buf: Buffer): number {
+  const end = buf.length;
+  for (let i = end - 1; i >= Math.max(0, end - 4); i--) {
+    const b = buf[i];
+    if ((b & 0xc0) === 0x80) continue; // continuation byte
+    const need = b >= 0xf0 ? 4 : b >= 0xe0 ? 3 : b >= 0xc0 ? 2 : 1;
+    return end - i < need ? i : end;
+  }
+  return end;
+}
+
+/** Start of an unterminated trailing escape sequence, or buf.length if none. */
+function escapeBoundary(buf: Buffer): number {
+  const esc = buf.lastIndexOf(ESC);
+  if (esc === -1 || buf.length - esc > MAX_HELD_BYTES) return buf.length;
+  if (esc === buf.length - 1) return esc;
+
+  const kind = buf[esc + 1];
+  if (kind === 0x5b) {
+    // CSI: parameter and intermediate bytes until a final byte in 0x40–0x7e
+    for (let i = esc + 2; i < buf.length; i++) {
+      if (buf[i] >= 0x40 && buf[i] <= 0x7e) return buf.length;
+    }
+    return esc;
+  }
+  if (kind === 0x5d || kind === 0x50 || kind === 0x5f || kind === 0x5e || kind === 0x58) {
+    // OSC/DCS/APC/PM/SOS end with BEL or ST (ESC \). An ST would itself be
+    // the last ESC, so only BEL can terminate the sequence here.
+    return buf.indexOf(BEL, esc + 2) === -1 ? esc : buf.length;
+  }
+  // Other escapes take optional intermediates (0x20–0x2f) and one final byte
+  for (let i = esc + 1; i < buf.length; i++) {
+    if (buf[i] >= 0x30) return buf.length;
+  }
+  return esc;
+}
+
+/**
+ * Offset up to which `buf` can be emitted without splitting a code point or an
+ * escape sequence. Bytes from there on should be held for the next flush.
+ */
+export function safeFlushBoundary(buf: Buffer): number {
+  return Math.min(utf8Boundary(buf), escapeBoundary(buf));
+}
+
+/** Drop leading continuation bytes left over from cutting a buffer mid code point. */
+export function alignToCodepoint(buf: Buffer): Buffer {
+  let start = 0;
+  while (start < buf.length && start < 3 && (buf[start] & 0xc0) === 0x80) start++;
+  return start === 0 ? buf : buf.subarray(start);
+}
@@ -0,0 +1,70 @@
+import { describe, it, expect } from 'vitest';
+import { splitPatch, selectHunks } from './patch-hunks.js';
+
+const PATCH = [
+  'diff --git a/f b/f',
+  'index e8823e1..6616946 100644',
+  '--- a/f',
+  '+++ b/f',
+  '@@ -1,2 +1,3 @@',
+  ' 1',
+  '+1.5',
+  ' 2',
+  '@@ -10,3 +11,3 @@ fn main()',
+  ' 10',
+  '-11',
+  '+eleven',
+  ' 12',
+  '',
+].join('\n');
+
+describe('splitPatch', () => {
+  it('separates the file header from the hunks', () => {
+    const patch = splitPatch(PATCH);
+    expect(patch.header).toBe(
+      'diff --git a/f b/f\nindex e8823e1..6616946 100644\n--- a/f\n+++ b/f\n',
+    );
+    expect(patch.hunks.map((h) => h.header)).toEqual([
+      '@@ -1,2 +1,3 @@',
+      '@@ -10,3 +11,3 @@ fn main()',
+    ]);
+    expect(patch.hunks[1].body).toBe(' 10\n-11\n+eleven\n 12\n');
+  });
+
+  it('keeps hunk ids stable when line numbers shift', () => {
+    const shifted = PATCH.replace('@@ -10,3 +11,3 @@', '@@ -10,3 +10,3 @@');
+    expect(splitPatch(shifted).hunks[1].id).toBe(splitPatch(PATCH).hunks[1].id);
+  });
+
+  it('gives identical hunks distinct ids', () => {
+    const twice = PATCH + '@@ -20,3 +21,3 @@ fn main()\n 10\n-11\n+eleven\n 12\n';
+    const ids = splitPatch(twice).hunks.map((h) => h.id);
+    expect(new Set(ids).size).toBe(3);
+  });
+
+  it('returns no hunks for binary diffs', () => {
+    const patch = splitPatch('diff --git a/x b/x\nBinary files a/x and b/x differ\n');
+    expect(patch.hunks).toEqual([]);
+  });
+});
+
+describe('selectHunks', () => {
+  it('shifts new-side start lines past skipped hunks', () => {
+    const patch = splitPatch(PATCH);
+    const out = selectHunks(patch, [patch.hunks[1].id]);
+    expect(out).toBe(patch.header + '@@ -10,3 +10,3 @@ fn main()\n 10\n-11\n+eleven\n 12\n');
+  });
+
+  it('keeps selected hunks in order', () => {
+    const patch = splitPatch(PATCH);
+    const out = selectHunks(patch, [patch.hunks[1].id, patch.hunks[0].id]);
+    expect(out).toBe(PATCH);
+  });
+
+  it('handles pure deletions', () => {
+    const patch = splitPatch('--- a/f\n+++ b/f\n@@ -3 +2,0 @@\n-3\n');
+    expect(selectHunks(patch, [patch.hunks[0].id])).toBe(
+      '--- a/f\n+++ b/f\n@@ -3,1 +2,0 @@\n-3\n',
+    );
+  });
+});
@@ -0,0 +1,85 @@
+// Splits a single-file unified diff into hunks with stable identifiers and
+// reassembles a patch from a subset of them, for partial staging via
+// `git apply --cached`.
+
+import { createHash } from 'crypto';
+
+export interface PatchHunk {
+  /**
+   * Derived from the hunk's content (not its line numbers), so it survives
+   * re-diffing after other hunks of the file were staged.
+   */
+  id: string;
+  /** The `@@ -a,b +c,d @@` line. */
+  header: string;
+  /** Hunk body lines (` `, `+`, `-` and `\` prefixed), newline-terminated. */
+  body: string;
+}
+
+export interface FilePatch {
+  /** Everything before the first hunk (`diff --git`, `---`, `+++`, mode lines). */
+  header: string;
+  hunks: PatchHunk[];
+}
+
+const HUNK_HEADER = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@(.*)$/;
+
+function hunkId(body: string, occurrence: number): string {
+  return createHash('sha1').update(`${occurrence}\0${body}`).digest('hex').slice(0, 12);
+}
+
+/** Parse a unified diff of one file. Hunkless diffs (binary, mode-only) yield no hunks. */
+export function splitPatch(patch: string): FilePatch {
+  const lines = patch.split('\n');
+  if (lines[lines.length - 1] === '') lines.pop();
+  const headerLines: string[] = [];
+  const hunks: PatchHunk[] = [];
+  const seen = new Map<string, number>();
+  let current: { header: string; body: string[] } | null = null;
+
+  const finish = () => {
+    if (!current) return;
+    const body = current.body.map((l) => l + '\n').join('');
+    const occurrence = seen.get(body) ?? 0;
+    seen.set(body, occurrence + 1);
+    hunks.push({ id: hunkId(body, occurrence), header: current.header, body });
+  };
+
+  for (const line of lines) {
+    if (HUNK_HEADER.test(line)) {
+      finish();
+      current = { header: line, body: [] };
+    } else if (current) {
+      current.body.push(line);
+    } else {
+      headerLines.push(line);
+    }
+  }
+  finish();
+  return { header: headerLines.map((l) => l + '\n').join(''), hunks };
+}
+
+/**
+ * Rebuild a patch containing only the hunks with the given ids. New-side
+ * start lines are shifted to account for the hunks left out.
+ */
+export function selectHunks(patch: FilePatch, ids: readonly string[]): string {
+  const wanted = new Set(ids);
+  let out = patch.header;
+  let delta = 0;
+  for (const hunk of patch.hunks) {
+    if (!wanted.has(hunk.id)) continue;
+    const m = HUNK_HEADER.exec(hunk.header);
+    if (!m) continue;
+    const oldStart = Number(m[1]);
+    const oldCount = m[2] === undefined ? 1 : Number(m[2]);
+    const newCount = m[4] === undefined ? 1 : Number(m[4]);
+    // An empty side's start names the line before the change rather than its first line
+    let newStart = oldStart + delta;
+    if (oldCount === 0 && newCount > 0) newStart++;
+    else if (newCount === 0 && oldCount > 0) newStart--;
+    out += `@@ -${oldStart},${oldCount} +${newStart},${newCount} @@${m[5]}\n${hunk.body}`;
+    delta += newCount - oldCount;
+  }
+  return out;
+}
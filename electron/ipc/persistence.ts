@@ -1,8 +1,17 @@
 import { app } from 'electron';
 import fs from 'fs';
 import path from 'path';
+import {
+  migrateState,
+  stateVersion,
+  STATE_SCHEMA_VERSION,
+  type MigrationFailure,
+} from './state-migrations.js';
+import { acquireFileLock, LOCK_HEARTBEAT_MS, type FileLock } from './state-lock.js';
+import { IPC } from './channels.js';
+import type { EventSink } from './pty.js';
 
-function getStateDir(): string {
+export function getStateDir(): string {
   let dir = app.getPath('userData');
   // Use separate dir for dev mode
   if (!app.isPackaged) {
@@ -12,34 +21,51 @@ function getStateDir(): string {
   return dir;
 }
 
-function getStatePath(): string {
+// Saved state is split by key so editing one task doesn't rewrite everything:
+// state/settings.json holds app settings plus project and task order,
+// state/projects/<id>.json and state/tasks/<id>.json one record each. The
+// single state.json of older versions is only read, until the first save.
+
+/** Writes to the same file within this window are coalesced into the last one. */
+const WRITE_DEBOUNCE_MS = 250;
+const SAFE_ID = /^[A-Za-z0-9_-]+$/;
+
+function getLegacyStatePath(): string {
   return path.join(getStateDir(), 'state.json');
 }
 
-export function saveAppState(json: string): void {
-  const statePath = getStatePath();
-  const dir = path.dirname(statePath);
-  fs.mkdirSync(dir, { recursive: true });
+function getStateRoot(): string {
+  return path.join(getStateDir(), 'state');
+}
+
+function settingsPath(): string {
+  return path.join(getStateRoot(), 'settings.json');
+}
+
+function recordPath(kind: 'projects' | 'tasks', id: string): string {
+  if (!SAFE_ID.test(id)) throw new Error(`Invalid ${kind} id: ${id}`);
+  return path.join(getStateRoot(), kind, `${id}.json`);
+}
 
-  // Validate JSON before writing
-  JSON.parse(json);
+/** Write `json` to `filePath` atomically, keeping the previous version as `.bak`. */
+function writeAtomic(filePath: string, json: string): void {
+  fs.mkdirSync(path.dirname(filePath), { recursive: true });
 
   // Atomic write: write to temp, then rename
-  const tmpPath = statePath + '.tmp';
+  const tmpPath = filePath + '.tmp';
   try {
     fs.writeFileSync(tmpPath, json, 'utf8');
 
-    // Keep one backup (copy so statePath is never missing during the operation)
-    if (fs.existsSync(statePath)) {
-      const bakPath = statePath + '.bak';
+    // Keep one backup (copy so filePath is never missing during the operation)
+    if (fs.existsSync(filePath)) {
       try {
-        fs.copyFileSync(statePath, bakPath);
+        fs.copyFileSync(filePath, filePath + '.bak');
       } catch {
         /* ignore */
       }
     }
 
-    fs.renameSync(tmpPath, statePath);
+    fs.renameSync(tmpPath, filePath);
   } catch (err) {
     // Clean up orphaned temp file on failure
     try {
@@ -51,33 +77,380 @@ export function saveAppState(json: string): void {
   }
 }
 
-export function loadAppState(): string | null {
-  const statePath = getStatePath();
-  const bakPath = statePath + '.bak';
+function getLockPath(): string {
+  return path.join(getStateDir(), 'state.lock');
+}
+
+let stateLock: FileLock | null = null;
+let lockRetryTimer: ReturnType<typeof setInterval> | null = null;
+/** Why this instance may not save right now; null while it may. Saves wait meanwhile. */
+let readOnlyReason: string | null = null;
+/** Why the state lock keeps this instance from saving. */
+let lockReason: string | null = null;
+/** Why the loaded state must not be saved over: it's newer, or failed to migrate. */
+let schemaReason: string | null = null;
+/** The schema version the loaded state was brought to, stamped on saved settings. */
+let loadedSchemaVersion = STATE_SCHEMA_VERSION;
+let stateEventSink: EventSink | null = null;
 
+/** Where to report state files changed behind this instance's back and lost locks. */
+export function setStateEventSink(win: EventSink): void {
+  stateEventSink = win;
+}
+
+function sendStateEvent(channel: IPC, payload: unknown): void {
+  if (stateEventSink && !stateEventSink.isDestroyed()) {
+    stateEventSink.webContents.send(channel, payload);
+  }
+}
+
+function setReadOnly(reason: string | null): void {
+  lockReason = reason;
+  const effective = schemaReason ?? lockReason;
+  if (effective === readOnlyReason) return;
+  readOnlyReason = effective;
+  if (effective) console.warn(`Not saving state: ${effective}`);
+  sendStateEvent(IPC.StateReadOnlyChanged, { reason: effective });
+}
+
+/** Hold saves for the state just loaded; loadAppState reports the reason itself. */
+function setSchemaReadOnly(reason: string | null): void {
+  schemaReason = reason;
+  readOnlyReason = schemaReason ?? lockReason;
+  if (reason) console.warn(`Not saving state: ${reason}`);
+}
+
+function tryAcquireStateLock(): boolean {
   try {
-    if (fs.existsSync(statePath)) {
-      const content = fs.readFileSync(statePath, 'utf8');
-      if (content.trim()) {
-        JSON.parse(content); // validate JSON; falls through to backup on invalid
-        return content;
+    fs.mkdirSync(getStateDir(), { recursive: true });
+    stateLock = acquireFileLock(getLockPath(), () => {
+      stateLock = null;
+      setReadOnly('Another Parallel Code instance took over the saved state');
+      waitForStateLock();
+    });
+  } catch (err) {
+    console.warn('Failed to take the state lock:', err);
+  }
+  return stateLock !== null;
+}
+
+/** Keep trying for the lock; once free, saving resumes with what was held back. */
+function waitForStateLock(): void {
+  if (lockRetryTimer) return;
+  lockRetryTimer = setInterval(() => {
+    if (!tryAcquireStateLock()) return;
+    if (lockRetryTimer) clearInterval(lockRetryTimer);
+    lockRetryTimer = null;
+    setReadOnly(null);
+    flushStateWrites();
+  }, LOCK_HEARTBEAT_MS);
+  lockRetryTimer.unref?.();
+}
+
+/**
+ * Claim the saved state for this process. Returns false when another running
+ * instance holds it: saves are then held back (and the user told) until that
+ * instance lets go.
+ */
+export function acquireStateLock(): boolean {
+  if (tryAcquireStateLock()) return true;
+  setReadOnly('Another Parallel Code instance is using the saved state');
+  waitForStateLock();
+  return false;
+}
+
+export function releaseStateLock(): void {
+  if (lockRetryTimer) clearInterval(lockRetryTimer);
+  lockRetryTimer = null;
+  stateLock?.release();
+  stateLock = null;
+}
+
+// Each file's version (mtime and size) as last read or written here. A file
+// whose version changed since was written by someone else, so it isn't saved
+// over: this instance's content goes to `<file>.conflict` until the user picks
+// whose copy to keep (resolveStateConflicts).
+const knownVersions = new Map<string, string>();
+/** This instance's latest content for each conflicting file; null removes it. */
+const conflicted = new Map<string, string | null>();
+/** The user chose to leave conflicting files as the other instance wrote them. */
+let ignoreConflicts = false;
+/** The user chose the other instance's state; saves are dropped until it's reloaded. */
+let awaitingReload = false;
+
+function diskVersion(filePath: string): string | null {
+  try {
+    const stat = fs.statSync(filePath);
+    return `${stat.mtimeMs}:${stat.size}`;
+  } catch {
+    return null;
+  }
+}
+
+function rememberVersion(filePath: string): void {
+  const version = diskVersion(filePath);
+  if (version) knownVersions.set(filePath, version);
+  else knownVersions.delete(filePath);
+}
+
+/** Whether the file on disk is no longer the one this instance last read or wrote. */
+function changedOnDisk(filePath: string): boolean {
+  const current = diskVersion(filePath);
+  return current !== null && current !== (knownVersions.get(filePath) ?? null);
+}
+
+function holdConflict(filePath: string, json: string | null): void {
+  const isNew = !conflicted.has(filePath);
+  conflicted.set(filePath, json);
+  if (json !== null) writeAtomic(filePath + '.conflict', json);
+  if (!isNew) return;
+  console.warn(`${filePath} was changed by another instance; not saving over it`);
+  if (!ignoreConflicts) sendStateEvent(IPC.StateSaveConflict, { paths: [...conflicted.keys()] });
+}
+
+export type StateConflictChoice = 'keep_mine' | 'reload' | 'stop';
+
+/**
+ * Settle the files another instance changed: `keep_mine` saves this
+ * instance's copy over theirs, `reload` keeps theirs and drops saves until the
+ * renderer has loaded it, `stop` keeps theirs on disk and this instance's copy
+ * in `.conflict` only, for the rest of the session.
+ */
+export function resolveStateConflicts(choice: StateConflictChoice): void {
+  if (choice === 'stop') {
+    ignoreConflicts = true;
+    return;
+  }
+  for (const [filePath, json] of conflicted) {
+    try {
+      if (choice === 'keep_mine') {
+        if (json === null) {
+          fs.rmSync(filePath, { force: true });
+          fs.rmSync(filePath + '.bak', { force: true });
+        } else {
+          writeAtomic(filePath, json);
+        }
+        fs.rmSync(filePath + '.conflict', { force: true });
       }
+      rememberVersion(filePath);
+      conflicted.delete(filePath);
+    } catch (err) {
+      console.warn(`Failed to resolve the conflict on ${filePath}:`, err);
     }
-  } catch {
-    // Primary state file unreadable or invalid JSON — try backup
   }
+  if (choice === 'reload') {
+    pendingWrites.clear();
+    awaitingReload = true;
+  }
+}
 
-  try {
-    if (fs.existsSync(bakPath)) {
-      const content = fs.readFileSync(bakPath, 'utf8');
-      if (content.trim()) {
-        JSON.parse(content); // validate JSON
-        return content;
+/** Pending content per file; null removes the file. */
+const pendingWrites = new Map<string, string | null>();
+let flushTimer: ReturnType<typeof setTimeout> | null = null;
+
+function scheduleWrite(filePath: string, json: string | null): void {
+  // Validate JSON before accepting it
+  if (json !== null) JSON.parse(json);
+  if (awaitingReload) return;
+  pendingWrites.set(filePath, json);
+  if (!flushTimer) flushTimer = setTimeout(flushStateWrites, WRITE_DEBOUNCE_MS);
+}
+
+/** Write everything still pending right away, e.g. before quitting. */
+export function flushStateWrites(): void {
+  if (flushTimer) clearTimeout(flushTimer);
+  flushTimer = null;
+  if (readOnlyReason) return;
+  const writes = [...pendingWrites];
+  pendingWrites.clear();
+  for (const [filePath, json] of writes) {
+    try {
+      if (conflicted.has(filePath) || changedOnDisk(filePath)) {
+        holdConflict(filePath, json);
+        continue;
       }
+      if (json === null) {
+        fs.rmSync(filePath, { force: true });
+        fs.rmSync(filePath + '.bak', { force: true });
+      } else {
+        writeAtomic(filePath, json);
+      }
+      rememberVersion(filePath);
+    } catch (err) {
+      console.warn(`Failed to save ${filePath}:`, err);
+    }
+  }
+}
+
+/**
+ * App settings, including `projectIds`, `taskOrder` and `collapsedTaskOrder`.
+ * Stamped with the schema version the loaded state was migrated to.
+ */
+export function saveSettings(json: string): void {
+  const settings = JSON.parse(json) as Record<string, unknown>;
+  scheduleWrite(
+    settingsPath(),
+    JSON.stringify({ ...settings, schema_version: loadedSchemaVersion }),
+  );
+}
+
+/** One project's record; null removes it. */
+export function saveProject(projectId: string, json: string | null): void {
+  scheduleWrite(recordPath('projects', projectId), json);
+}
+
+/** One task's record; null removes it. */
+export function saveTask(taskId: string, json: string | null): void {
+  scheduleWrite(recordPath('tasks', taskId), json);
+}
+
+/** A file's parsed content, falling back to its `.bak` when unreadable. */
+function readJson(filePath: string): unknown {
+  rememberVersion(filePath);
+  for (const candidate of [filePath, filePath + '.bak']) {
+    try {
+      if (!fs.existsSync(candidate)) continue;
+      const content = fs.readFileSync(candidate, 'utf8');
+      if (content.trim()) return JSON.parse(content);
+    } catch {
+      // Unreadable or invalid JSON — try the backup
     }
+  }
+  return undefined;
+}
+
+function readRecords(kind: 'projects' | 'tasks'): Map<string, unknown> {
+  const dir = path.join(getStateRoot(), kind);
+  const records = new Map<string, unknown>();
+  let names: string[] = [];
+  try {
+    names = fs.readdirSync(dir);
   } catch {
-    // Backup also unreadable or invalid JSON
+    return records;
+  }
+  for (const name of names) {
+    if (!name.endsWith('.json')) continue;
+    const id = name.slice(0, -'.json'.length);
+    const record = readJson(path.join(dir, name));
+    if (SAFE_ID.test(id) && record !== undefined) records.set(id, record);
+  }
+  return records;
+}
+
+/**
+ * The saved state as one document: settings with `projects` (in `projectIds`
+ * order) and `tasks` filled in from their own files, or an older version's
+ * state.json.
+ */
+function readAppState(): Record<string, unknown> | null {
+  awaitingReload = false;
+  // Anything still pending is newer than what's on disk
+  flushStateWrites();
+  const settings = readJson(settingsPath());
+  if (settings && typeof settings === 'object') {
+    const { projectIds, ...rest } = settings as { projectIds?: unknown };
+    const projects = readRecords('projects');
+    const order = Array.isArray(projectIds) ? projectIds : [...projects.keys()];
+    return {
+      ...rest,
+      projects: order.filter((id) => projects.has(id)).map((id) => projects.get(id)),
+      tasks: Object.fromEntries(readRecords('tasks')),
+    };
+  }
+
+  const legacy = readJson(getLegacyStatePath());
+  return legacy && typeof legacy === 'object' ? (legacy as Record<string, unknown>) : null;
+}
+
+/** Why the saved state couldn't be fully brought to the current schema. */
+export interface StateMigrationError {
+  from_version: number;
+  to_version: number;
+  failed: MigrationFailure[];
+  /** Copy of the state as it was on disk before loading. */
+  backup_path: string | null;
+}
+
+export interface LoadedAppState {
+  json: string | null;
+  migration_error: StateMigrationError | null;
+  /** Why nothing is being saved, e.g. another instance holds the state lock. */
+  read_only_reason: string | null;
+}
+
+/** Keep the state as found on disk before it's migrated (or saved over by an older version). */
+function backUpState(state: Record<string, unknown>, version: number): string | null {
+  const bakPath = path.join(getStateDir(), `state-v${version}.json.bak`);
+  try {
+    writeAtomic(bakPath, JSON.stringify(state));
+    return bakPath;
+  } catch (err) {
+    console.warn('Failed to back up state before migrating:', err);
+    return null;
   }
+}
+
+/**
+ * Load the saved state and migrate it to the current schema. A state from a
+ * newer version, or one whose migration failed, is still returned (the
+ * renderer keeps what it understands) along with an error listing what
+ * couldn't be migrated and where the untouched original was backed up.
+ * Nothing is saved over such a state, since that would drop what this
+ * version doesn't understand.
+ */
+export function loadAppState(): LoadedAppState {
+  const state = readAppState();
+  loadedSchemaVersion = STATE_SCHEMA_VERSION;
+  setSchemaReadOnly(null);
+  if (!state) return { json: null, migration_error: null, read_only_reason: readOnlyReason };
 
-  return null;
+  const version = stateVersion(state);
+  if (version === STATE_SCHEMA_VERSION) {
+    return {
+      json: JSON.stringify(state),
+      migration_error: null,
+      read_only_reason: readOnlyReason,
+    };
+  }
+
+  const backupPath = backUpState(state, version);
+  if (version > STATE_SCHEMA_VERSION) {
+    loadedSchemaVersion = version;
+    setSchemaReadOnly('The saved state is from a newer version of Parallel Code');
+    return {
+      json: JSON.stringify(state),
+      migration_error: {
+        from_version: version,
+        to_version: version,
+        failed: [
+          {
+            from: version,
+            description: 'Saved by a newer version of the app',
+            error: 'Nothing is saved, so this version cannot drop what it does not know about',
+          },
+        ],
+        backup_path: backupPath,
+      },
+      read_only_reason: readOnlyReason,
+    };
+  }
+
+  const outcome = migrateState(state);
+  loadedSchemaVersion = outcome.to_version;
+  if (outcome.failed.length > 0) {
+    setSchemaReadOnly('The saved state could not be fully migrated to this version');
+  }
+  return {
+    json: JSON.stringify(outcome.state),
+    migration_error:
+      outcome.failed.length > 0
+        ? {
+            from_version: outcome.from_version,
+            to_version: outcome.to_version,
+            failed: outcome.failed,
+            backup_path: backupPath,
+          }
+        : null,
+    read_only_reason: readOnlyReason,
+  };
 }
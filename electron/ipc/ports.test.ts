@@ -0,0 +1,58 @@
+import { describe, it, expect } from 'vitest';
+import { detectServerUrl, isLocalPreviewUrl, portEnv, withPortFlag } from './ports.js';
+
+describe('detectServerUrl', () => {
+  it('finds the URL a dev server prints, through ANSI colors', () => {
+    const out = '\x1b[32m  ➜  Local:   \x1b[1mhttp://localhost:4100/\x1b[22m\x1b[39m\n';
+    expect(detectServerUrl(out, 4100)).toBe('http://localhost:4100/');
+  });
+
+  it('prefers the URL on the assigned port', () => {
+    const out = 'Docs http://127.0.0.1:9000\nready http://localhost:4101';
+    expect(detectServerUrl(out, 4101)).toBe('http://localhost:4101');
+  });
+
+  it('falls back to the first URL and rewrites wildcard hosts', () => {
+    expect(detectServerUrl('Listening on http://0.0.0.0:8000/app', 4100)).toBe(
+      'http://localhost:8000/app',
+    );
+    expect(detectServerUrl('serving at http://[::]:4102', null)).toBe('http://localhost:4102');
+  });
+
+  it('returns null when no URL was printed yet', () => {
+    expect(detectServerUrl('compiling...', 4100)).toBeNull();
+  });
+});
+
+describe('portEnv', () => {
+  it('sets the common port variables', () => {
+    expect(portEnv(4100)).toMatchObject({ PORT: '4100', VITE_PORT: '4100' });
+  });
+});
+
+describe('withPortFlag', () => {
+  it('passes --port to vite, directly or through a package script', () => {
+    expect(withPortFlag('npx vite', 4100)).toBe('npx vite --port 4100 --strictPort');
+    const scripts = { dev: 'vite --host', start: 'node server.js' };
+    expect(withPortFlag('npm run dev', 4100, scripts)).toBe(
+      'npm run dev -- --port 4100 --strictPort',
+    );
+    expect(withPortFlag('pnpm dev', 4100, scripts)).toBe('pnpm dev --port 4100 --strictPort');
+  });
+
+  it('leaves other servers and explicit ports alone', () => {
+    expect(withPortFlag('npm start', 4100, { start: 'node server.js' })).toBe('npm start');
+    expect(withPortFlag('vite --port 3000', 4100)).toBe('vite --port 3000');
+  });
+});
+
+describe('isLocalPreviewUrl', () => {
+  it('allows only http(s) on the loopback hosts', () => {
+    expect(isLocalPreviewUrl('http://localhost:4100/')).toBe(true);
+    expect(isLocalPreviewUrl('https://127.0.0.1:4100')).toBe(true);
+    expect(isLocalPreviewUrl('http://[::1]:4100')).toBe(true);
+    expect(isLocalPreviewUrl('http://example.com:4100')).toBe(false);
+    expect(isLocalPreviewUrl('file:///etc/passwd')).toBe(false);
+    expect(isLocalPreviewUrl('not a url')).toBe(false);
+  });
+});
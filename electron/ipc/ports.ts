@@ -0,0 +1,110 @@
+import net from 'net';
+import { stripAnsi } from './prompt-detect.js';
+
+/** Ports handed to dev servers start here, clear of the usual 3000/5173/8080. */
+const PORT_BASE = 4100;
+const PORT_SPAN = 900;
+
+/** Port reserved for each task's dev server, kept until the task is closed. */
+const taskPorts = new Map<string, number>();
+
+function isReserved(port: number): boolean {
+  return [...taskPorts.values()].includes(port);
+}
+
+function isPortFree(port: number): Promise<boolean> {
+  return new Promise((resolve) => {
+    const server = net.createServer();
+    server.once('error', () => resolve(false));
+    server.listen(port, '127.0.0.1', () => server.close(() => resolve(true)));
+  });
+}
+
+/**
+ * The task's reserved port, or a newly reserved one that no other task holds
+ * and nothing is listening on.
+ */
+export async function allocatePort(taskId: string): Promise<number> {
+  const existing = taskPorts.get(taskId);
+  if (existing !== undefined) return existing;
+  for (let port = PORT_BASE; port < PORT_BASE + PORT_SPAN; port++) {
+    if (isReserved(port) || !(await isPortFree(port))) continue;
+    // Re-check: another allocation may have finished while we were probing
+    const raced = taskPorts.get(taskId);
+    if (raced !== undefined) return raced;
+    if (isReserved(port)) continue;
+    taskPorts.set(taskId, port);
+    return port;
+  }
+  throw new Error(`No free port in ${PORT_BASE}-${PORT_BASE + PORT_SPAN - 1}`);
+}
+
+export function releasePort(taskId: string): void {
+  taskPorts.delete(taskId);
+}
+
+export function releaseAllPorts(): void {
+  taskPorts.clear();
+}
+
+/** Env vars common dev servers read their port from. */
+export function portEnv(port: number): Record<string, string> {
+  const value = String(port);
+  return { PORT: value, VITE_PORT: value, DEV_SERVER_PORT: value };
+}
+
+const VITE_RE = /^(?:[A-Z_][A-Z0-9_]*=\S*\s+)*(?:(?:npx|bunx|pnpm exec|yarn)\s+)?vite(?:\s|$)/;
+const SCRIPT_RE = /^(npm|pnpm|yarn|bun)\s+(?:run\s+)?([\w:.-]+)(?:\s|$)/;
+
+/**
+ * The dev-server command with the port passed as a flag to servers that
+ * ignore PORT, which Vite does. `npm run dev` and the like are resolved
+ * through package.json's `scripts`. Commands that set a port are kept.
+ */
+export function withPortFlag(
+  command: string,
+  port: number,
+  scripts: Record<string, unknown> = {},
+): string {
+  const trimmed = command.trim();
+  if (/--port\b/.test(trimmed)) return command;
+  const flags = `--port ${port} --strictPort`;
+  if (VITE_RE.test(trimmed)) return `${trimmed} ${flags}`;
+  const run = SCRIPT_RE.exec(trimmed);
+  const script = run ? scripts[run[2]] : undefined;
+  if (!run || typeof script !== 'string' || !VITE_RE.test(script.trim())) return command;
+  // npm hands arguments after `--` to the script; the others pass them on directly
+  const separator = run[1] === 'npm' && !/\s--(?:\s|$)/.test(trimmed) ? '-- ' : '';
+  return `${trimmed} ${separator}${flags}`;
+}
+
+/** Whether `url` is a dev server on this machine, which is all a preview may open. */
+export function isLocalPreviewUrl(url: string): boolean {
+  try {
+    const parsed = new URL(url);
+    return (
+      (parsed.protocol === 'http:' || parsed.protocol === 'https:') &&
+      ['localhost', '127.0.0.1', '[::1]'].includes(parsed.hostname)
+    );
+  } catch {
+    return false;
+  }
+}
+
+const URL_RE = /https?:\/\/(?:[\w.-]+|\[[\da-f:]*\]):(\d+)[^\s'"<>)]*/g;
+
+/**
+ * Find the URL a dev server printed in its output. A URL on the assigned port
+ * wins over others (HMR sockets, network addresses); wildcard hosts are
+ * rewritten to localhost so the URL opens in a browser.
+ */
+export function detectServerUrl(output: string, port: number | null): string | null {
+  const text = stripAnsi(output);
+  let first: string | null = null;
+  for (const m of text.matchAll(URL_RE)) {
+    const url = m[0].replace(/\/\/(?:0\.0\.0\.0|\[::\])/, '//localhost');
+    if (port !== null && Number(m[1]) === port) return url;
+    first ??= url;
+  }
+  return first;
+}
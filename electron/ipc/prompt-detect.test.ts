@@ -0,0 +1,62 @@
+import { describe, it, expect } from 'vitest';
+import {
+  compileAutoResponseRules,
+  matchAutoResponse,
+  matchAwaitingInputPrompt,
+} from './prompt-detect.js';
+
+describe('matchAwaitingInputPrompt', () => {
+  it('matches a Claude Code confirmation', () => {
+    const out =
+      '\x1b[1mBash command\x1b[0m\r\n  rm -rf dist\r\nDo you want to proceed?\r\n❯ 1. Yes';
+    expect(matchAwaitingInputPrompt(out)).toBe('Do you want to proceed?');
+  });
+
+  it('matches TUI-garbled text without spaces', () => {
+    expect(matchAwaitingInputPrompt('Doyouwanttomakethisedittofoo.ts?')).not.toBeNull();
+  });
+
+  it('matches a Codex approval prompt', () => {
+    expect(matchAwaitingInputPrompt('Would you like to run the following command?\n')).toBe(
+      'Would you like to run the following command?',
+    );
+  });
+
+  it('matches a trailing y/N suffix', () => {
+    expect(matchAwaitingInputPrompt('Overwrite config? (y/N) ')).toBe('(y/N)');
+  });
+
+  it('ignores y/N text that is not at the end of the output', () => {
+    expect(matchAwaitingInputPrompt('Overwrite config? (y/N) y\r\nDone.\r\n')).toBeNull();
+  });
+
+  it('returns null for ordinary output', () => {
+    expect(matchAwaitingInputPrompt('Compiling crate v0.1.0\r\nFinished\r\n')).toBeNull();
+  });
+});
+
+describe('matchAutoResponse', () => {
+  const rules = compileAutoResponseRules([{ pattern: '\\(y/N\\)\\s*$', response: 'y\\n' }]);
+
+  it('returns the rule with escapes expanded', () => {
+    const hit = matchAutoResponse('\x1b[33mContinue? (y/N) \x1b[0m', rules);
+    expect(hit?.matched).toBe('(y/N)');
+    expect(hit?.rule.response).toBe('y\n');
+  });
+
+  it('returns null when nothing matches', () => {
+    expect(matchAutoResponse('Working...\r\n', rules)).toBeNull();
+  });
+
+  it('only answers a prompt still at the end of the output', () => {
+    const unanchored = compileAutoResponseRules([{ pattern: 'Continue\\?', response: 'y' }]);
+    expect(matchAutoResponse('Continue? \r\n', unanchored)?.matched).toBe('Continue?');
+    expect(matchAutoResponse('Continue? y\r\nBuilding...\r\n', unanchored)).toBeNull();
+  });
+
+  it('rejects invalid patterns at compile time', () => {
+    expect(() => compileAutoResponseRules([{ pattern: '(', response: 'y' }])).toThrow(
+      /Invalid auto-response pattern/,
+    );
+  });
+});
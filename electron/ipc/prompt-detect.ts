@@ -0,0 +1,92 @@
+// Detection of agent confirmation prompts in raw PTY output, so the backend
+// can report "waiting for user input" even when no window is watching.
+
+/** Strip ANSI escape sequences (CSI, OSC, and single-char escapes). */
+export function stripAnsi(text: string): string {
+  return text.replace(
+    // eslint-disable-next-line no-control-regex
+    /[\u001b\u009b][[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-ORZcf-nq-uy=><~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)?/g,
+    '',
+  );
+}
+
+/**
+ * Confirmation prompts, tested against the tail of stripped output. TUI
+ * agents lay text out with cursor moves, so after stripping, words may run
+ * together — patterns avoid relying on spaces where the agent omits them.
+ */
+const AWAITING_INPUT_PATTERNS: RegExp[] = [
+  // Claude Code
+  /Do\s*you\s*want\s*to\s*(?:proceed|continue|make\s*this\s*edit|create|run)[^?\n]*\?/i,
+  /❯\s*1\.\s*Yes/, // Claude Code selection menu
+  /Would\s*you\s*like\s*to\s*(?:run|make|apply)\s*the\s*following[^?\n]*\?/i, // Codex approval
+  /›\s*1\.\s*Yes/, // Codex selection menu
+  /Allow\s*(?:this\s*)?(?:command|action|edit)\s*\?/i, // Codex / Gemini approval
+  /[([]\s*y\s*\/\s*n\s*[)\]]\s*[:?]?\s*$/i, // (y/N), [Y/n]
+];
+
+/** Only the end of the output is considered; older prompts have scrolled past. */
+const DETECT_TAIL_CHARS = 600;
+
+/** Returns the matched prompt text if `rawTail` ends in a confirmation prompt. */
+export function matchAwaitingInputPrompt(rawTail: string): string | null {
+  const text = stripAnsi(rawTail).slice(-DETECT_TAIL_CHARS);
+  for (const re of AWAITING_INPUT_PATTERNS) {
+    const m = re.exec(text);
+    if (m) return m[0].trim();
+  }
+  return null;
+}
+
+/**
+ * Auto-responses only see this much of the stripped tail, which also bounds
+ * how long a user's pattern can take to run.
+ */
+const AUTO_RESPONSE_TAIL_CHARS = 300;
+
+export interface AutoResponseRule {
+  /** Regex source, matched against the end of the stripped output tail. */
+  pattern: string;
+  /** Text written to the PTY; `\n`, `\r` and `\t` escapes are expanded. */
+  response: string;
+}
+
+export interface CompiledAutoResponseRule {
+  re: RegExp;
+  pattern: string;
+  response: string;
+}
+
+function unescapeResponse(response: string): string {
+  return response.replace(/\\([nrt\\])/g, (_, c: string) =>
+    c === 'n' ? '\n' : c === 'r' ? '\r' : c === 't' ? '\t' : '\\',
+  );
+}
+
+/** Compile rules up front so an invalid pattern fails the spawn, not the reader. */
+export function compileAutoResponseRules(rules: AutoResponseRule[]): CompiledAutoResponseRule[] {
+  return rules.map((rule) => {
+    let re: RegExp;
+    try {
+      // Anchored, so text the agent has since printed past doesn't match
+      re = new RegExp(`(?:${rule.pattern})\\s*$`, 'i');
+    } catch (err) {
+      throw new Error(`Invalid auto-response pattern '${rule.pattern}': ${String(err)}`);
+    }
+    return { re, pattern: rule.pattern, response: unescapeResponse(rule.response) };
+  });
+}
+
+/** First rule whose pattern matches the end of `rawTail`, with the matched text. */
+export function matchAutoResponse(
+  rawTail: string,
+  rules: CompiledAutoResponseRule[],
+): { rule: CompiledAutoResponseRule; matched: string } | null {
+  if (rules.length === 0) return null;
+  const text = stripAnsi(rawTail).slice(-AUTO_RESPONSE_TAIL_CHARS);
+  for (const rule of rules) {
+    const m = rule.re.exec(text);
+    if (m) return { rule, matched: m[0].trim() };
+  }
+  return null;
+}
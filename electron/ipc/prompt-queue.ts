@@ -0,0 +1,166 @@
+import { randomUUID } from 'crypto';
+import { IPC } from './channels.js';
+import { writeToAgent, pasteToAgent, getAgentLastOutputAt, type EventSink } from './pty.js';
+import { agentStoppedAt } from './agent-stop.js';
+
+/** A follow-up prompt waiting to be written to an agent. */
+export interface QueuedPrompt {
+  id: string;
+  text: string;
+  queued_at: number;
+}
+
+interface PromptQueue {
+  win: EventSink;
+  prompts: QueuedPrompt[];
+  lastDeliveredAt: number;
+  delivering: boolean;
+}
+
+const queues = new Map<string, PromptQueue>();
+
+/** How long an agent must be silent before it is considered idle. */
+const QUEUE_IDLE_MS = 5_000;
+/** How often to check queued agents for idleness. */
+const QUEUE_POLL_MS = 1_000;
+/** Delay between writing the prompt text and the Enter key (matches sendPrompt). */
+const ENTER_DELAY_MS = 50;
+
+let pollTimer: ReturnType<typeof setInterval> | null = null;
+
+function sendQueueChanged(agentId: string, queue: PromptQueue | undefined): void {
+  const win = queue?.win;
+  if (!win || win.isDestroyed()) return;
+  win.webContents.send(IPC.PromptQueueChanged, {
+    agentId,
+    prompts: queue.prompts.map((p) => ({ ...p })),
+  });
+}
+
+function ensurePolling(): void {
+  if (pollTimer) return;
+  pollTimer = setInterval(pollQueues, QUEUE_POLL_MS);
+  pollTimer.unref?.();
+}
+
+function stopPollingIfEmpty(): void {
+  for (const q of queues.values()) {
+    if (q.prompts.length > 0) return;
+  }
+  if (pollTimer) {
+    clearInterval(pollTimer);
+    pollTimer = null;
+  }
+}
+
+/** Whether the agent finished its turn (Stop hook) or has been quiet long enough. */
+function isAgentIdle(agentId: string, queue: PromptQueue, now: number): boolean {
+  const lastOutputAt = getAgentLastOutputAt(agentId);
+  if (lastOutputAt === null) return false; // agent not running (yet)
+  const stoppedAt = agentStoppedAt(agentId);
+  if (stoppedAt !== null && stoppedAt > queue.lastDeliveredAt) return true;
+  return now - lastOutputAt >= QUEUE_IDLE_MS && now - queue.lastDeliveredAt >= QUEUE_IDLE_MS;
+}
+
+async function deliverNext(agentId: string, queue: PromptQueue): Promise<boolean> {
+  const next = queue.prompts[0];
+  if (!next || queue.delivering) return false;
+  queue.delivering = true;
+  try {
+    // Paste the text, then send Enter separately so TUI apps don't treat
+    // the \r as part of the pasted block.
+    await pasteToAgent(agentId, next.text);
+    await new Promise((r) => setTimeout(r, ENTER_DELAY_MS));
+    writeToAgent(agentId, '\r');
+    queue.prompts = queue.prompts.filter((p) => p.id !== next.id);
+    queue.lastDeliveredAt = Date.now();
+    sendQueueChanged(agentId, queue);
+    return true;
+  } catch (err) {
+    console.warn(`Failed to deliver queued prompt to ${agentId}:`, err);
+    return false;
+  } finally {
+    queue.delivering = false;
+  }
+}
+
+function pollQueues(): void {
+  const now = Date.now();
+  for (const [agentId, queue] of queues) {
+    if (queue.prompts.length === 0 || queue.delivering) continue;
+    if (!isAgentIdle(agentId, queue, now)) continue;
+    void deliverNext(agentId, queue).then(stopPollingIfEmpty);
+  }
+}
+
+function getQueue(agentId: string): PromptQueue {
+  const queue = queues.get(agentId);
+  if (!queue) throw new Error(`No queued prompts for agent: ${agentId}`);
+  return queue;
+}
+
+/** Append a prompt to the agent's queue. It is written once the agent goes idle. */
+export function queuePrompt(win: EventSink, agentId: string, text: string): QueuedPrompt[] {
+  if (!text.trim()) throw new Error('Prompt must not be empty');
+  let queue = queues.get(agentId);
+  if (!queue) {
+    // Count the time of queueing as the last "delivery" so a prompt added
+    // to an agent that is mid-task isn't sent during a short output pause.
+    queue = { win, prompts: [], lastDeliveredAt: Date.now(), delivering: false };
+    queues.set(agentId, queue);
+  }
+  queue.win = win;
+  queue.prompts.push({ id: randomUUID(), text, queued_at: Date.now() });
+  sendQueueChanged(agentId, queue);
+  ensurePolling();
+  return queue.prompts;
+}
+
+/** Return the prompts currently queued for an agent. */
+export function listQueuedPrompts(agentId: string): QueuedPrompt[] {
+  return queues.get(agentId)?.prompts ?? [];
+}
+
+/** Cancel a single queued prompt. */
+export function removeQueuedPrompt(agentId: string, promptId: string): QueuedPrompt[] {
+  const queue = getQueue(agentId);
+  queue.prompts = queue.prompts.filter((p) => p.id !== promptId);
+  sendQueueChanged(agentId, queue);
+  stopPollingIfEmpty();
+  return queue.prompts;
+}
+
+/** Move a queued prompt from one position to another. */
+export function reorderQueuedPrompt(
+  agentId: string,
+  fromIndex: number,
+  toIndex: number,
+): QueuedPrompt[] {
+  const queue = getQueue(agentId);
+  const len = queue.prompts.length;
+  if (fromIndex < 0 || fromIndex >= len || toIndex < 0 || toIndex >= len) {
+    throw new Error('Queue index out of range');
+  }
+  const [moved] = queue.prompts.splice(fromIndex, 1);
+  queue.prompts.splice(toIndex, 0, moved);
+  sendQueueChanged(agentId, queue);
+  return queue.prompts;
+}
+
+/** Deliver the next queued prompt immediately, without waiting for idle. */
+export async function sendNextQueuedPrompt(agentId: string): Promise<boolean> {
+  const queue = getQueue(agentId);
+  const delivered = await deliverNext(agentId, queue);
+  stopPollingIfEmpty();
+  return delivered;
+}
+
+/** Drop every queued prompt for an agent (e.g. when it exits or its task is deleted). */
+export function clearPromptQueue(agentId: string): void {
+  const queue = queues.get(agentId);
+  if (!queue) return;
+  queues.delete(agentId);
+  queue.prompts = [];
+  sendQueueChanged(agentId, queue);
+  stopPollingIfEmpty();
+}
@@ -1,5 +1,10 @@
 import { describe, it, expect } from 'vitest';
-import { validateCommand } from './pty.js';
+import {
+  validateCommand,
+  collectDescendantPids,
+  parseProcessTable,
+  liveProcessTree,
+} from './pty.js';
 
 describe('validateCommand', () => {
   it('does not throw for a command found in PATH', () => {
@@ -33,3 +38,47 @@ describe('validateCommand', () => {
     expect(() => validateCommand('   ')).toThrow(/must not be empty/);
   });
 });
+
+describe('collectDescendantPids', () => {
+  it('collects children and grandchildren but not unrelated processes', () => {
+    const table: Array<[number, number]> = [
+      [10, 1],
+      [11, 10],
+      [12, 11],
+      [13, 10],
+      [20, 1],
+    ];
+    expect(collectDescendantPids(table, 10).sort()).toEqual([11, 12, 13]);
+  });
+
+  it('returns an empty list for a process without children', () => {
+    expect(collectDescendantPids([[10, 1]], 10)).toEqual([]);
+  });
+});
+
+describe('parseProcessTable', () => {
+  it('keeps the whole start time, spaces included', () => {
+    expect(parseProcessTable('  10     1    10 Sat Oct 17 10:00:00 2026\n')).toEqual([
+      { pid: 10, ppid: 1, pgid: 10, started: 'Sat Oct 17 10:00:00 2026' },
+    ]);
+  });
+});
+
+describe('liveProcessTree', () => {
+  const row = (pid: number, ppid: number, started = 'T1') => ({ pid, ppid, pgid: 10, started });
+
+  it('takes the leader and its descendants while the leader is alive', () => {
+    const tree = liveProcessTree([row(10, 1), row(11, 10), row(20, 1)], 10, new Map(), true);
+    expect([...tree.keys()].sort()).toEqual([10, 11]);
+  });
+
+  it('skips pids that were reused by a different process', () => {
+    const known = new Map([
+      [10, 'T1'],
+      [11, 'T1'],
+      [12, 'T1'],
+    ]);
+    const table = [row(10, 1, 'T2'), row(11, 1), row(13, 11), row(12, 1, 'T2')];
+    expect([...liveProcessTree(table, 10, known, false).keys()].sort()).toEqual([11, 13]);
+  });
+});
@@ -1,32 +1,115 @@
 import * as pty from 'node-pty';
 import { execFileSync, execFile, spawn as cpSpawn } from 'child_process';
 import fs from 'fs';
+import os from 'os';
 import path from 'path';
 import { fileURLToPath } from 'url';
+import { promisify } from 'util';
 import type { BrowserWindow } from 'electron';
 import { RingBuffer } from '../remote/ring-buffer.js';
+import { IPC } from './channels.js';
+import { openSessionLog } from './session-log.js';
+import { safeFlushBoundary, alignToCodepoint } from './output-boundary.js';
+import { scanOsc, windowTitle, clipboardWrite } from './osc.js';
+import { startRecording, type SessionRecorder } from './recording.js';
+import { agentStopFile, clearAgentStop, stopHookArgs, AGENT_STOP_FILE_ENV } from './agent-stop.js';
+import {
+  compileAutoResponseRules,
+  matchAutoResponse,
+  matchAwaitingInputPrompt,
+  type AutoResponseRule,
+} from './prompt-detect.js';
+import { parseUsageLimitReset } from './usage-limit.js';
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
 
+/** Agents are the task's AI CLI; shells are auxiliary terminals beside it. */
+export type SessionKind = 'agent' | 'shell';
+
 interface PtySession {
   proc: pty.IPty;
   channelId: string;
   taskId: string;
   agentId: string;
-  isShell: boolean;
+  kind: SessionKind;
+  /** Directory the session was started in. */
+  cwd: string;
   flushTimer: ReturnType<typeof setTimeout> | null;
   subscribers: Set<(encoded: string) => void>;
   scrollback: RingBuffer;
   /** Assigned container name when running in Docker mode, null otherwise. */
   containerName: string | null;
+  /** Timestamp (ms) of the most recent PTY output, used for idle detection. */
+  lastOutputAt: number;
+  /** Timestamp (ms) of the most recent write to the PTY. */
+  lastInputAt: number;
+  /** Total bytes of output produced, for the exit summary. */
+  outputBytes: number;
+  /** Set when the exit was requested (kill), so supervision doesn't restart it. */
+  killed: boolean;
+  /** Fires the Idle transition after the quiet period; null once idle or exited. */
+  idleTimer: ReturnType<typeof setTimeout> | null;
+  idle: boolean;
+  /** Recent raw output scanned for confirmation prompts. */
+  promptTail: string;
+  /** Scans promptTail once output has paused, so only a prompt still showing counts. */
+  promptCheckTimer: ReturnType<typeof setTimeout> | null;
+  /** The prompt the agent is blocked on, until the user answers it. */
+  awaitingInput: string | null;
+  /** When the last auto-response was written, to rate-limit redraw loops. */
+  lastAutoResponseAt: number;
+  /** Whether the program enabled bracketed paste mode (DECSET 2004). */
+  bracketedPaste: boolean;
+  /** Asciicast recorder when the spawn opted into recording. */
+  recorder: SessionRecorder | null;
+  /** Current CPU/IO priority, carried over to supervised restarts. */
+  priority: AgentPriority;
+  /** Unterminated OSC sequence from the previous chunk. */
+  oscCarry: string;
+  /** Last window title the program set via OSC 0/2. */
+  title: string | null;
+  /** When the last Bell event was sent, so bursts of BEL collapse into one. */
+  lastBellAt: number;
+  /** Stops or suspends the agent after a stretch without output or input. */
+  idleTimeoutTimer: ReturnType<typeof setTimeout> | null;
+  /** Restart the idle-timeout countdown; null when no timeout is configured. */
+  resetIdleTimeout: (() => void) | null;
+  /** Reset time of the last usage-limit notice seen, so redraws don't re-report it. */
+  usageLimitResetAt: number | null;
+  /** Set once the pty reported its child's exit; its pid may be reused after that. */
+  exited: boolean;
 }
 
+const exec = promisify(execFile);
+
 const sessions = new Map<string, PtySession>();
 
+/**
+ * The slice of BrowserWindow the backend uses to push events. Headless mode
+ * passes a stand-in so the same orchestration runs without a window.
+ */
+export type EventSink = Pick<BrowserWindow, 'isDestroyed'> & {
+  webContents: Pick<BrowserWindow['webContents'], 'send'>;
+};
+
+/** Pending supervised restarts, keyed by agentId, so a kill can cancel them. */
+const pendingRestarts = new Map<string, ReturnType<typeof setTimeout>>();
+
 // --- PTY event bus for spawn/exit notifications ---
 
-type PtyEventType = 'spawn' | 'exit' | 'list-changed';
+type PtyEventType =
+  | 'spawn'
+  | 'exit'
+  | 'list-changed'
+  | 'idle'
+  | 'active'
+  | 'awaiting-input'
+  | 'auto-response'
+  | 'clipboard'
+  | 'bell'
+  | 'idle-timeout'
+  | 'usage-limit';
 type PtyEventListener = (agentId: string, data?: unknown) => void;
 const eventListeners = new Map<PtyEventType, Set<PtyEventListener>>();
 
@@ -52,10 +135,42 @@ export function notifyAgentListChanged(): void {
   emitPtyEvent('list-changed', '');
 }
 
+// Supervision: restart crashed agents with exponential backoff. A run longer
+// than SUPERVISE_STABLE_MS counts as healthy and resets the attempt counter.
+const SUPERVISE_DEFAULT_MAX_RESTARTS = 3;
+const SUPERVISE_BASE_DELAY_MS = 2_000;
+const SUPERVISE_MAX_DELAY_MS = 30_000;
+const SUPERVISE_STABLE_MS = 60_000;
+
+export interface SuperviseOptions {
+  /** Args used for respawns (the agent's resume_args), replacing the original args. */
+  resumeArgs: string[];
+  maxRestarts?: number;
+  /** Restarts already attempted in the current crash streak (internal). */
+  attempt?: number;
+}
+
+/** Default quiet period before an agent is reported Idle (matches the renderer heuristic). */
+const DEFAULT_IDLE_AFTER_MS = 15_000;
+
 const BATCH_MAX = 64 * 1024;
 const BATCH_INTERVAL = 8; // ms
 const TAIL_CAP = 8 * 1024;
 const MAX_LINES = 50;
+const PROMPT_TAIL_CAP = 2 * 1024;
+const AUTO_RESPONSE_COOLDOWN_MS = 1_000;
+/** Output must pause this long before the tail is checked for prompts. */
+const PROMPT_QUIET_MS = 200;
+const BELL_COOLDOWN_MS = 1_000;
+const USAGE_LIMIT_JITTER_MS = 60_000;
+// Backpressure: at most OUTPUT_BUDGET_BYTES per OUTPUT_WINDOW_MS reach the
+// renderer. Past that, output is coalesced down to its last
+// SUPPRESSED_TAIL_BYTES and released with an OutputSuppressed marker when the
+// window rolls over, so an agent cat-ing a huge file can't freeze the webview.
+const OUTPUT_BUDGET_BYTES = 2 * 1024 * 1024;
+const OUTPUT_WINDOW_MS = 1_000;
+const SUPPRESSED_TAIL_BYTES = 64 * 1024;
+const HELD_BYTES_TIMEOUT_MS = 50;
 
 /** Verify that a command exists in PATH. Throws a descriptive error if not found. */
 export function validateCommand(command: string): void {
@@ -84,12 +199,14 @@ export function validateCommand(command: string): void {
 }
 
 export function spawnAgent(
-  win: BrowserWindow,
+  win: EventSink,
   args: {
     taskId: string;
     agentId: string;
     command: string;
     args: string[];
+    /** User-configured per-agent args, appended after `args` (kept across restarts). */
+    extraArgs?: string[];
     cwd: string;
     env: Record<string, string>;
     cols: number;
@@ -97,6 +214,19 @@ export function spawnAgent(
     isShell?: boolean;
     dockerMode?: boolean;
     dockerImage?: string;
+    supervise?: SuperviseOptions;
+    /** Quiet period before an Idle event is sent on the output channel. */
+    idleAfterMs?: number;
+    /** Regex→response rules answered automatically from the reader. */
+    autoRespond?: AutoResponseRule[];
+    /** Record the session as an asciicast file. */
+    record?: boolean;
+    /** Forward OSC 52 clipboard writes as 'clipboard' events. */
+    clipboard?: boolean;
+    /** Stop or suspend the agent after this long without output or input. */
+    idleTimeout?: { minutes: number; action: IdleTimeoutAction };
+    /** CPU/IO priority for the agent process; defaults to 'normal'. */
+    priority?: AgentPriority;
     onOutput: { __CHANNEL_ID__: string };
   },
 ): void {
@@ -118,12 +248,25 @@ export function spawnAgent(
     validateCommand('docker');
   }
 
+  const autoResponseRules = compileAutoResponseRules(args.autoRespond ?? []);
+  const agentArgs = [...args.args, ...(args.extraArgs ?? [])];
+
+  // A fresh spawn supersedes any supervised restart still waiting on its backoff
+  const pendingRestart = pendingRestarts.get(args.agentId);
+  if (pendingRestart) {
+    clearTimeout(pendingRestart);
+    pendingRestarts.delete(args.agentId);
+  }
+
   // Kill any existing session with the same agentId to prevent PTY leaks
   const existing = sessions.get(args.agentId);
   if (existing) {
     if (existing.flushTimer) clearTimeout(existing.flushTimer);
+    if (existing.idleTimer) clearTimeout(existing.idleTimer);
+    if (existing.promptCheckTimer) clearTimeout(existing.promptCheckTimer);
     existing.subscribers.clear();
-    existing.proc.kill();
+    existing.killed = true;
+    killProcessTree(existing);
     sessions.delete(args.agentId);
   }
 
@@ -206,11 +349,17 @@ export function spawnAgent(
       ...buildDockerCredentialMounts(),
       image,
       command,
-      ...args.args,
+      ...agentArgs,
     ];
   } else {
     spawnCommand = command;
-    spawnArgs = args.args;
+    spawnArgs = agentArgs;
+    // Let the agent tell the prompt queue when its turn ends
+    const stopFile = args.isShell ? null : agentStopFile(args.agentId);
+    if (stopFile) {
+      spawnEnv[AGENT_STOP_FILE_ENV] = stopFile;
+      spawnArgs = [...agentArgs, ...stopHookArgs(command)];
+    }
   }
 
   const proc = pty.spawn(spawnCommand, spawnArgs, {
@@ -226,13 +375,38 @@ export function spawnAgent(
     channelId,
     taskId: args.taskId,
     agentId: args.agentId,
-    isShell: args.isShell ?? false,
+    kind: args.isShell ? 'shell' : 'agent',
+    cwd,
     flushTimer: null,
     subscribers: new Set(),
     scrollback: new RingBuffer(),
     containerName,
+    lastOutputAt: Date.now(),
+    lastInputAt: 0,
+    outputBytes: 0,
+    killed: false,
+    idleTimer: null,
+    idle: false,
+    promptTail: '',
+    promptCheckTimer: null,
+    awaitingInput: null,
+    lastAutoResponseAt: 0,
+    bracketedPaste: false,
+    priority: args.priority ?? 'normal',
+    oscCarry: '',
+    title: null,
+    lastBellAt: 0,
+    idleTimeoutTimer: null,
+    resetIdleTimeout: null,
+    usageLimitResetAt: null,
+    exited: false,
+    recorder: args.record
+      ? startRecording(args.agentId, args.cols, args.rows, [command, ...agentArgs].join(' '))
+      : null,
   };
   sessions.set(args.agentId, session);
+  const sessionLog = openSessionLog(args.taskId, args.agentId);
+  const spawnedAt = Date.now();
 
   // Batching strategy matching the Rust implementation
   let batchChunks: Buffer[] = [];
@@ -246,25 +420,209 @@ export function spawnAgent(
     }
   };
 
-  const flush = () => {
-    if (batchSize === 0) return;
-    const batch = Buffer.concat(batchChunks);
+  let windowStart = 0;
+  let windowBytes = 0;
+  let suppressedBytes = 0;
+  let suppressedTail: Buffer | null = null;
+  let releaseTimer: ReturnType<typeof setTimeout> | null = null;
+
+  const emitData = (batch: Buffer) => {
     const encoded = batch.toString('base64');
     send({ type: 'Data', data: encoded });
-    session.scrollback.write(batch);
     for (const sub of session.subscribers) {
       sub(encoded);
     }
+  };
+
+  // Send the marker and the newest suppressed output, opening a fresh window.
+  const releaseSuppressed = () => {
+    if (releaseTimer) {
+      clearTimeout(releaseTimer);
+      releaseTimer = null;
+    }
+    if (!suppressedTail) return;
+    const tail = suppressedTail;
+    send({ type: 'OutputSuppressed', data: { bytes: suppressedBytes } });
+    suppressedBytes = 0;
+    suppressedTail = null;
+    windowStart = Date.now();
+    windowBytes = tail.length;
+    emitData(tail);
+  };
+
+  // Bytes of a split code point or escape sequence held back from the last flush
+  let heldBytes: Buffer | null = null;
+
+  const flush = (final = false) => {
+    if (batchSize === 0 && !(final && heldBytes)) return;
+    if (heldBytes) batchChunks.unshift(heldBytes);
+    let batch = Buffer.concat(batchChunks);
     batchChunks = [];
     batchSize = 0;
+    heldBytes = null;
     if (session.flushTimer) {
       clearTimeout(session.flushTimer);
       session.flushTimer = null;
     }
+    if (!final) {
+      const boundary = safeFlushBoundary(batch);
+      if (boundary < batch.length) {
+        heldBytes = Buffer.from(batch.subarray(boundary));
+        batch = batch.subarray(0, boundary);
+        // Don't sit on a partial sequence forever if the agent goes quiet
+        session.flushTimer = setTimeout(() => flush(true), HELD_BYTES_TIMEOUT_MS);
+      }
+      if (batch.length === 0) return;
+    }
+    session.scrollback.write(batch);
+
+    const now = Date.now();
+    if (!suppressedTail && now - windowStart >= OUTPUT_WINDOW_MS) {
+      windowStart = now;
+      windowBytes = 0;
+    }
+    if (!suppressedTail && windowBytes + batch.length <= OUTPUT_BUDGET_BYTES) {
+      windowBytes += batch.length;
+      emitData(batch);
+      return;
+    }
+
+    const combined = suppressedTail ? Buffer.concat([suppressedTail, batch]) : batch;
+    const kept =
+      combined.length > SUPPRESSED_TAIL_BYTES
+        ? alignToCodepoint(combined.subarray(combined.length - SUPPRESSED_TAIL_BYTES))
+        : combined;
+    suppressedBytes += combined.length - kept.length;
+    suppressedTail = Buffer.from(kept);
+    releaseTimer ??= setTimeout(
+      releaseSuppressed,
+      Math.max(0, windowStart + OUTPUT_WINDOW_MS - now),
+    );
+  };
+
+  const idleAfterMs = args.idleAfterMs ?? DEFAULT_IDLE_AFTER_MS;
+  const armIdleTimer = () => {
+    if (session.idleTimer) clearTimeout(session.idleTimer);
+    session.idleTimer = setTimeout(() => {
+      session.idleTimer = null;
+      session.idle = true;
+      send({ type: 'Idle', data: { idle_secs: Math.round(idleAfterMs / 1000) } });
+      emitPtyEvent('idle', args.agentId);
+    }, idleAfterMs);
+  };
+  armIdleTimer();
+
+  const idleTimeout = args.idleTimeout;
+  if (idleTimeout) {
+    session.resetIdleTimeout = () => {
+      if (session.idleTimeoutTimer) clearTimeout(session.idleTimeoutTimer);
+      session.idleTimeoutTimer = setTimeout(() => {
+        session.idleTimeoutTimer = null;
+        if (sessions.get(args.agentId) !== session) return;
+        const { minutes, action } = idleTimeout;
+        const notice =
+          `\r\n\x1b[33m[parallel-code] No activity for ${minutes} min; ` +
+          `${action === 'kill' ? 'stopping' : 'suspending'} the agent\x1b[0m\r\n`;
+        send({ type: 'Data', data: Buffer.from(notice).toString('base64') });
+        send({ type: 'IdleTimeout', data: { idle_mins: minutes, action } });
+        emitPtyEvent('idle-timeout', args.agentId, { idle_mins: minutes, action });
+        if (action === 'kill') {
+          void terminateAgent(args.agentId);
+        } else {
+          sendSignal(args.agentId, 'SIGTSTP');
+        }
+      }, idleTimeout.minutes * 60_000);
+    };
+    session.resetIdleTimeout();
+  }
+
+  // Runs once output pauses: a prompt that was redrawn over or scrolled away
+  // by then is no longer showing and mustn't be answered.
+  const checkPrompts = () => {
+    session.promptCheckTimer = null;
+    const now = Date.now();
+    const hit =
+      session.lastAutoResponseAt + AUTO_RESPONSE_COOLDOWN_MS < now
+        ? matchAutoResponse(session.promptTail, autoResponseRules)
+        : null;
+    if (hit) {
+      session.promptTail = '';
+      session.awaitingInput = null;
+      session.lastAutoResponseAt = now;
+      proc.write(hit.rule.response);
+      const entry = {
+        at: now,
+        task_id: args.taskId,
+        agent_id: args.agentId,
+        pattern: hit.rule.pattern,
+        matched: hit.matched,
+        response: hit.rule.response,
+      };
+      send({ type: 'AutoResponded', data: entry });
+      emitPtyEvent('auto-response', args.agentId, entry);
+    } else if (session.awaitingInput === null) {
+      const matched = matchAwaitingInputPrompt(session.promptTail);
+      if (matched) {
+        session.awaitingInput = matched;
+        send({ type: 'AwaitingInput', data: { matched_prompt: matched } });
+        emitPtyEvent('awaiting-input', args.agentId, { matched_prompt: matched });
+      }
+    }
   };
 
   proc.onData((data: string) => {
     const chunk = Buffer.from(data, 'utf8');
+    session.lastOutputAt = Date.now();
+    session.outputBytes += chunk.length;
+    if (session.idle) {
+      session.idle = false;
+      send({ type: 'Active' });
+      emitPtyEvent('active', args.agentId);
+    }
+    armIdleTimer();
+    session.resetIdleTimeout?.();
+
+    const osc = scanOsc(session.oscCarry, data);
+    session.oscCarry = osc.carry;
+    for (const seq of osc.sequences) {
+      const title = windowTitle(seq);
+      if (title !== null && title !== session.title) {
+        session.title = title;
+        send({ type: 'Title', data: title });
+      }
+      const copied = args.clipboard ? clipboardWrite(seq) : null;
+      if (copied !== null) emitPtyEvent('clipboard', args.agentId, copied);
+    }
+    if (osc.bells > 0 && session.lastOutputAt - session.lastBellAt >= BELL_COOLDOWN_MS) {
+      session.lastBellAt = session.lastOutputAt;
+      send({ type: 'Bell' });
+      emitPtyEvent('bell', args.agentId);
+    }
+
+    const pasteOn = data.lastIndexOf('\x1b[?2004h');
+    const pasteOff = data.lastIndexOf('\x1b[?2004l');
+    if (pasteOn !== pasteOff) session.bracketedPaste = pasteOn > pasteOff;
+
+    const combined = session.promptTail + data;
+    session.promptTail =
+      combined.length > PROMPT_TAIL_CAP ? combined.slice(-PROMPT_TAIL_CAP) : combined;
+    if (session.promptCheckTimer) clearTimeout(session.promptCheckTimer);
+    session.promptCheckTimer = setTimeout(checkPrompts, PROMPT_QUIET_MS);
+
+    if (/limit/i.test(data)) {
+      const resetsAt = parseUsageLimitReset(session.promptTail, new Date());
+      // Relative waits ("try again in 2 hours") drift with each redraw; only a real change counts
+      if (
+        resetsAt !== null &&
+        Math.abs(resetsAt - (session.usageLimitResetAt ?? 0)) > USAGE_LIMIT_JITTER_MS
+      ) {
+        session.usageLimitResetAt = resetsAt;
+        emitPtyEvent('usage-limit', args.agentId, { resets_at: resetsAt });
+      }
+    }
+
+    sessionLog?.write(chunk);
+    session.recorder?.output(data);
 
     // Maintain tail buffer for exit diagnostics
     tailChunks.push(chunk);
@@ -298,12 +656,87 @@ export function spawnAgent(
   });
 
   proc.onExit(({ exitCode, signal }) => {
+    session.exited = true;
+    sessionLog?.close();
+    session.recorder?.close();
     // If this session was replaced by a new spawn with the same agentId,
     // skip cleanup — the new session owns the map entry now.
     if (sessions.get(args.agentId) !== session) return;
+    clearAgentStop(args.agentId);
 
     // Flush any remaining buffered data
-    flush();
+    flush(true);
+    releaseSuppressed();
+    if (session.idleTimeoutTimer) {
+      clearTimeout(session.idleTimeoutTimer);
+      session.idleTimeoutTimer = null;
+    }
+    if (session.idleTimer) {
+      clearTimeout(session.idleTimer);
+      session.idleTimer = null;
+    }
+    if (session.promptCheckTimer) {
+      clearTimeout(session.promptCheckTimer);
+      session.promptCheckTimer = null;
+    }
+
+    if (args.supervise && !session.killed && (exitCode !== 0 || signal)) {
+      const sup = args.supervise;
+      const attempt = Date.now() - spawnedAt > SUPERVISE_STABLE_MS ? 0 : (sup.attempt ?? 0);
+      const maxRestarts = sup.maxRestarts ?? SUPERVISE_DEFAULT_MAX_RESTARTS;
+      const event = {
+        agentId: args.agentId,
+        taskId: args.taskId,
+        attempt: attempt + 1,
+        max_attempts: maxRestarts,
+        exit_code: exitCode,
+        signal: signal !== undefined ? String(signal) : null,
+      };
+      if (attempt < maxRestarts) {
+        const delayMs = Math.min(SUPERVISE_BASE_DELAY_MS * 2 ** attempt, SUPERVISE_MAX_DELAY_MS);
+        const notice =
+          `\r\n\x1b[33m[parallel-code] Agent exited unexpectedly (code ${exitCode}); ` +
+          `restarting in ${Math.round(delayMs / 1000)}s ` +
+          `(attempt ${attempt + 1}/${maxRestarts})\x1b[0m\r\n`;
+        send({ type: 'Data', data: Buffer.from(notice).toString('base64') });
+        if (!win.isDestroyed()) {
+          win.webContents.send(IPC.AgentSupervision, {
+            ...event,
+            status: 'restarting',
+            delay_ms: delayMs,
+          });
+        }
+        sessions.delete(args.agentId);
+        emitPtyEvent('exit', args.agentId, { exitCode, signal });
+        pendingRestarts.set(
+          args.agentId,
+          setTimeout(() => {
+            pendingRestarts.delete(args.agentId);
+            try {
+              spawnAgent(win, {
+                ...args,
+                args: sup.resumeArgs,
+                priority: session.priority,
+                supervise: { ...sup, attempt: attempt + 1 },
+              });
+            } catch (err) {
+              console.warn(`Supervised restart of agent ${args.agentId} failed:`, err);
+              send({
+                type: 'Exit',
+                data: { exit_code: exitCode, signal: 'restart_failed', last_output: [String(err)] },
+              });
+              if (!win.isDestroyed()) {
+                win.webContents.send(IPC.AgentSupervision, { ...event, status: 'gave_up' });
+              }
+            }
+          }, delayMs),
+        );
+        return;
+      }
+      if (!win.isDestroyed()) {
+        win.webContents.send(IPC.AgentSupervision, { ...event, status: 'gave_up' });
+      }
+    }
 
     // Parse tail buffer into last N lines for exit diagnostics
     const tailBuf = Buffer.concat(tailChunks);
@@ -320,6 +753,9 @@ export function spawnAgent(
         exit_code: exitCode,
         signal: signal !== undefined ? String(signal) : null,
         last_output: lines,
+        duration_ms: Date.now() - spawnedAt,
+        output_bytes: session.outputBytes,
+        last_activity_at: Math.max(session.lastOutputAt, session.lastInputAt),
       },
     });
 
@@ -327,19 +763,100 @@ export function spawnAgent(
     sessions.delete(args.agentId);
   });
 
+  if (args.priority && args.priority !== 'normal') {
+    try {
+      applyPriority([proc.pid], args.priority);
+    } catch (err) {
+      console.warn(`Failed to set priority for agent ${args.agentId}:`, err);
+    }
+  }
+
   emitPtyEvent('spawn', args.agentId);
 }
 
+/**
+ * Spawn an auxiliary login shell for a task, rooted in its worktree. Shares
+ * the PTY plumbing with agents but skips agent-only setup (plans, supervision).
+ */
+export function spawnShell(
+  win: EventSink,
+  args: {
+    taskId: string;
+    shellId: string;
+    cwd: string;
+    env?: Record<string, string>;
+    cols: number;
+    rows: number;
+    dockerMode?: boolean;
+    dockerImage?: string;
+    onOutput: { __CHANNEL_ID__: string };
+  },
+): void {
+  spawnAgent(win, {
+    taskId: args.taskId,
+    agentId: args.shellId,
+    command: '',
+    args: ['-l'],
+    cwd: args.cwd,
+    env: args.env ?? {},
+    cols: args.cols,
+    rows: args.rows,
+    isShell: true,
+    dockerMode: args.dockerMode,
+    dockerImage: args.dockerImage,
+    onOutput: args.onOutput,
+  });
+}
+
 export function writeToAgent(agentId: string, data: string): void {
   const session = sessions.get(agentId);
   if (!session) throw new Error(`Agent not found: ${agentId}`);
+  // Any keystroke other than an escape sequence (menu navigation) answers
+  // the prompt; start scanning fresh output for the next one.
+  if (session.awaitingInput !== null && !data.startsWith('\x1b')) {
+    session.awaitingInput = null;
+    session.promptTail = '';
+  }
+  session.resetIdleTimeout?.();
+  session.lastInputAt = Date.now();
   session.proc.write(data);
 }
 
+const PASTE_START = '\x1b[200~';
+const PASTE_END = '\x1b[201~';
+const PASTE_CHUNK_SIZE = 4096;
+/** Pause between paste chunks so line-buffered CLIs don't drop input. */
+const PASTE_CHUNK_DELAY_MS = 5;
+
+/**
+ * Paste text into an agent as a terminal would: wrapped in bracketed-paste
+ * markers when the program asked for them, so multi-line text arrives as one
+ * block instead of being submitted line by line. Large pastes are chunked.
+ */
+export async function pasteToAgent(agentId: string, text: string): Promise<void> {
+  const session = sessions.get(agentId);
+  if (!session) throw new Error(`Agent not found: ${agentId}`);
+  // Embedded end markers would let pasted text break out of the paste.
+  const body = text.split(PASTE_START).join('').split(PASTE_END).join('');
+  const payload = session.bracketedPaste
+    ? PASTE_START + body + PASTE_END
+    : body.replace(/\r?\n/g, '\r');
+  for (let i = 0; i < payload.length; ) {
+    if (i > 0) await new Promise((r) => setTimeout(r, PASTE_CHUNK_DELAY_MS));
+    let end = Math.min(i + PASTE_CHUNK_SIZE, payload.length);
+    // Keep surrogate pairs in one chunk; a lone half would be written as U+FFFD
+    const last = payload.charCodeAt(end - 1);
+    if (end < payload.length && last >= 0xd800 && last <= 0xdbff) end--;
+    writeToAgent(agentId, payload.slice(i, end));
+    i = end;
+  }
+}
+
 export function resizeAgent(agentId: string, cols: number, rows: number): void {
   const session = sessions.get(agentId);
   if (!session) throw new Error(`Agent not found: ${agentId}`);
   session.proc.resize(cols, rows);
+  session.recorder?.resize(cols, rows);
 }
 
 export function pauseAgent(agentId: string): void {
@@ -354,6 +871,139 @@ export function resumeAgent(agentId: string): void {
   session.proc.resume();
 }
 
+// --- Process tree termination ---
+
+/** How long processes in a killed agent's tree get to exit on SIGHUP before SIGKILL. */
+const KILL_ESCALATION_MS = 2_000;
+
+/** Collect all transitive children of `rootPid` from a `[pid, ppid]` table. */
+export function collectDescendantPids(table: Array<[number, number]>, rootPid: number): number[] {
+  const children = new Map<number, number[]>();
+  for (const [pid, ppid] of table) {
+    const list = children.get(ppid);
+    if (list) list.push(pid);
+    else children.set(ppid, [pid]);
+  }
+  const result: number[] = [];
+  const stack = [...(children.get(rootPid) ?? [])];
+  while (stack.length > 0) {
+    const pid = stack.pop() as number;
+    if (result.includes(pid)) continue;
+    result.push(pid);
+    stack.push(...(children.get(pid) ?? []));
+  }
+  return result;
+}
+
+/** One row of the process table; the start time tells a reused pid apart. */
+export interface ProcessEntry {
+  pid: number;
+  ppid: number;
+  pgid: number;
+  started: string;
+}
+
+/** Processes of an agent's tree, by pid, with their start time. */
+type ProcessTree = Map<number, string>;
+
+/** Parse `ps -o pid=,ppid=,pgid=,lstart=` output; lstart contains spaces, so it comes last. */
+export function parseProcessTable(out: string): ProcessEntry[] {
+  const entries: ProcessEntry[] = [];
+  for (const line of out.split('\n')) {
+    const m = /^\s*(\d+)\s+(\d+)\s+(\d+)\s+(\S.*?)\s*$/.exec(line);
+    if (!m) continue;
+    entries.push({ pid: Number(m[1]), ppid: Number(m[2]), pgid: Number(m[3]), started: m[4] });
+  }
+  return entries;
+}
+
+/** Snapshot the process table via `ps`. Empty if `ps` is unavailable. */
+async function readProcessTable(): Promise<ProcessEntry[]> {
+  try {
+    const { stdout } = await exec('ps', ['-A', '-o', 'pid=,ppid=,pgid=,lstart='], {
+      encoding: 'utf8',
+      timeout: 3000,
+    });
+    return parseProcessTable(stdout);
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * The processes of `known` that are still the same processes (same pid and
+ * start time), plus their current descendants. `rootPid` counts as ours while
+ * `leaderAlive`, i.e. until its pty reported the exit.
+ */
+export function liveProcessTree(
+  table: ProcessEntry[],
+  rootPid: number,
+  known: ProcessTree,
+  leaderAlive: boolean,
+): ProcessTree {
+  const tree: ProcessTree = new Map();
+  for (const p of table) {
+    if (known.get(p.pid) === p.started || (leaderAlive && p.pid === rootPid)) {
+      tree.set(p.pid, p.started);
+    }
+  }
+  const started = new Map(table.map((p) => [p.pid, p.started]));
+  const pairs = table.map((p): [number, number] => [p.pid, p.ppid]);
+  for (const pid of [...tree.keys()]) {
+    for (const child of collectDescendantPids(pairs, pid)) {
+      tree.set(child, started.get(child) as string);
+    }
+  }
+  return tree;
+}
+
+/**
+ * Signal what is left of an agent's process tree: its process group (node-pty
+ * starts the child with setsid, so its pid is the pgid) while the group is
+ * still ours, plus descendants that moved to their own group or session, e.g.
+ * dev servers. The table is re-read first so pids that exited and were reused
+ * meanwhile are left alone. Resolves to the tree it signalled.
+ */
+async function signalProcessTree(
+  rootPid: number,
+  known: ProcessTree,
+  leaderAlive: () => boolean,
+  signal: NodeJS.Signals,
+): Promise<ProcessTree> {
+  const table = await readProcessTable();
+  const alive = leaderAlive();
+  const tree = liveProcessTree(table, rootPid, known, alive);
+  // A pgid isn't reused while any process is in the group, so one of ours in it vouches for it
+  if (alive || table.some((p) => p.pgid === rootPid && tree.has(p.pid))) {
+    try {
+      process.kill(-rootPid, signal);
+    } catch {
+      /* group already gone */
+    }
+  }
+  for (const pid of tree.keys()) {
+    try {
+      process.kill(pid, signal);
+    } catch {
+      /* already exited */
+    }
+  }
+  return tree;
+}
+
+/** SIGHUP the agent's process tree, then SIGKILL whatever is still alive after a grace period. */
+function killProcessTree(session: PtySession): void {
+  const pid = session.proc.pid;
+  const leaderAlive = () => !session.exited;
+  void signalProcessTree(pid, new Map(), leaderAlive, 'SIGHUP').then((tree) => {
+    const timer = setTimeout(
+      () => void signalProcessTree(pid, tree, leaderAlive, 'SIGKILL'),
+      KILL_ESCALATION_MS,
+    );
+    timer.unref?.();
+  });
+}
+
 export function killAgent(agentId: string): void {
   const session = sessions.get(agentId);
   if (session) {
@@ -371,14 +1021,149 @@ export function killAgent(agentId: string): void {
     if (session.containerName) {
       stopDockerContainer(session.containerName);
     }
-    session.proc.kill();
+    session.killed = true;
+    killProcessTree(session);
+  }
+  const pendingRestart = pendingRestarts.get(agentId);
+  if (pendingRestart) {
+    clearTimeout(pendingRestart);
+    pendingRestarts.delete(agentId);
+  }
+}
+
+export type AgentPriority = 'normal' | 'below_normal' | 'low';
+
+export type IdleTimeoutAction = 'kill' | 'suspend';
+
+// os.setPriority maps these to nice values on Unix and priority classes on Windows.
+const CPU_PRIORITY: Record<AgentPriority, number> = {
+  normal: os.constants.priority.PRIORITY_NORMAL,
+  below_normal: os.constants.priority.PRIORITY_BELOW_NORMAL,
+  low: os.constants.priority.PRIORITY_LOW,
+};
+
+// ionice class/level on Linux: best-effort 4 is the default, class 3 is idle.
+const IO_PRIORITY: Record<AgentPriority, string[]> = {
+  normal: ['-c', '2', '-n', '4'],
+  below_normal: ['-c', '2', '-n', '7'],
+  low: ['-c', '3'],
+};
+
+export function isAgentPriority(value: unknown): value is AgentPriority {
+  return typeof value === 'string' && Object.prototype.hasOwnProperty.call(CPU_PRIORITY, value);
+}
+
+function applyPriority(pids: number[], priority: AgentPriority): void {
+  for (const pid of pids) {
+    try {
+      os.setPriority(pid, CPU_PRIORITY[priority]);
+    } catch (err) {
+      // Lowering the nice value back is privileged on Unix.
+      if ((err as NodeJS.ErrnoException).code === 'EACCES') {
+        throw new Error('Raising an agent back to a higher priority needs elevated privileges');
+      }
+      // ESRCH: the process exited meanwhile
+    }
+  }
+  if (process.platform === 'linux') {
+    for (const pid of pids) {
+      execFile('ionice', [...IO_PRIORITY[priority], '-p', String(pid)], () => {
+        /* ionice missing or process gone */
+      });
+    }
+  }
+}
+
+/** Change the CPU/IO priority of a running agent and all its descendants. */
+export async function setAgentPriority(agentId: string, priority: AgentPriority): Promise<void> {
+  const session = sessions.get(agentId);
+  if (!session) throw new Error(`Agent not found: ${agentId}`);
+  const table = await readProcessTable();
+  const tree = liveProcessTree(table, session.proc.pid, new Map(), !session.exited);
+  applyPriority(tree.size > 0 ? [...tree.keys()] : [session.proc.pid], priority);
+  session.priority = priority;
+}
+
+/** Signals the renderer may deliver without ending the session. */
+const SENDABLE_SIGNALS = new Set<string>(['SIGINT', 'SIGTSTP', 'SIGCONT', 'SIGTERM']);
+
+/**
+ * Deliver a job-control signal (interrupt, suspend, continue) to the agent's
+ * process group, like pressing Ctrl+C / Ctrl+Z in a terminal.
+ */
+export function sendSignal(agentId: string, signal: string): void {
+  const session = sessions.get(agentId);
+  if (!session) throw new Error(`Agent not found: ${agentId}`);
+  if (!SENDABLE_SIGNALS.has(signal)) throw new Error(`Unsupported signal: ${signal}`);
+  if (session.containerName) {
+    // The docker CLI doesn't proxy signals for TTY sessions; signal the container directly
+    execFile('docker', ['kill', '--signal', signal, session.containerName], () => {});
+    return;
+  }
+  try {
+    process.kill(-session.proc.pid, signal);
+  } catch {
+    session.proc.kill(signal);
+  }
+}
+
+/** Default time an agent gets to shut down cleanly before being hard-killed. */
+export const DEFAULT_TERMINATE_GRACE_MS = 3_000;
+
+/**
+ * Gracefully stop an agent: SIGTERM its process tree so it can persist session
+ * state (e.g. for `--continue`), then SIGKILL whatever is left if the agent's
+ * own process hasn't exited after `graceMs`. Resolves once the agent has
+ * exited or been hard-killed.
+ */
+export function terminateAgent(
+  agentId: string,
+  graceMs = DEFAULT_TERMINATE_GRACE_MS,
+): Promise<void> {
+  const pendingRestart = pendingRestarts.get(agentId);
+  if (pendingRestart) {
+    clearTimeout(pendingRestart);
+    pendingRestarts.delete(agentId);
   }
+  const session = sessions.get(agentId);
+  if (!session || session.exited) return Promise.resolve();
+
+  session.killed = true;
+  if (session.containerName) stopDockerContainer(session.containerName);
+  const pid = session.proc.pid;
+  const leaderAlive = () => !session.exited;
+  const signalled = signalProcessTree(pid, new Map(), leaderAlive, 'SIGTERM');
+
+  return new Promise((resolve) => {
+    const finish = () => {
+      clearTimeout(timer);
+      exitListener.dispose();
+      resolve();
+    };
+    // The pty's own exit, not the session map: a replaced session never reports one there
+    const exitListener = session.proc.onExit(finish);
+    const timer = setTimeout(() => {
+      if (session.exited) return finish();
+      void signalled
+        .then((tree) => signalProcessTree(pid, tree, leaderAlive, 'SIGKILL'))
+        .then(finish);
+    }, graceMs);
+  });
 }
 
 export function countRunningAgents(): number {
   return sessions.size;
 }
 
+/** Agents holding a concurrency slot: running ones plus those waiting on a supervised restart. */
+export function countAgentSlotsInUse(): number {
+  let count = pendingRestarts.size;
+  for (const session of sessions.values()) {
+    if (session.kind === 'agent') count++;
+  }
+  return count;
+}
+
 export function killAllAgents(): void {
   for (const [, session] of sessions) {
     if (session.flushTimer) clearTimeout(session.flushTimer);
@@ -393,8 +1178,20 @@ export function killAllAgents(): void {
         // Intentionally ignore: container may not exist or may have already stopped.
       }
     }
-    session.proc.kill();
+    session.killed = true;
+    // The app is exiting, so there is no time to escalate to SIGKILL: the group
+    // goes now, descendants in other groups once `ps` answers
+    if (!session.exited) {
+      try {
+        process.kill(-session.proc.pid, 'SIGHUP');
+      } catch {
+        /* group already gone */
+      }
+    }
+    void signalProcessTree(session.proc.pid, new Map(), () => !session.exited, 'SIGHUP');
   }
+  for (const timer of pendingRestarts.values()) clearTimeout(timer);
+  pendingRestarts.clear();
   // Let onExit handlers clean up sessions individually
 }
 
@@ -426,9 +1223,25 @@ export function getActiveAgentIds(): string[] {
 /** Return metadata for a specific agent, or null if not found. */
 export function getAgentMeta(
   agentId: string,
-): { taskId: string; agentId: string; isShell: boolean } | null {
+): { taskId: string; agentId: string; kind: SessionKind } | null {
   const s = sessions.get(agentId);
-  return s ? { taskId: s.taskId, agentId: s.agentId, isShell: s.isShell } : null;
+  return s ? { taskId: s.taskId, agentId: s.agentId, kind: s.kind } : null;
+}
+
+/** Whether a running agent or shell was started in `dir` or a folder inside it. */
+export function isDirInUse(dir: string): boolean {
+  const root = path.resolve(dir);
+  for (const s of sessions.values()) {
+    if (s.exited) continue;
+    const cwd = path.resolve(s.cwd);
+    if (cwd === root || cwd.startsWith(root + path.sep)) return true;
+  }
+  return false;
+}
+
+/** Return the timestamp (ms) of the agent's most recent output, or null if not found. */
+export function getAgentLastOutputAt(agentId: string): number | null {
+  return sessions.get(agentId)?.lastOutputAt ?? null;
 }
 
 /** Return the current column width of an agent's PTY. */
@@ -588,7 +1401,7 @@ let activeBuild: Promise<{ ok: boolean; error?: string }> | null = null;
  * Concurrent calls return the same in-flight promise.
  */
 export function buildDockerImage(
-  win: BrowserWindow,
+  win: EventSink,
   onOutputChannel: string,
 ): Promise<{ ok: boolean; error?: string }> {
   if (activeBuild !== null) {
@@ -0,0 +1,100 @@
+import fs from 'fs';
+import path from 'path';
+
+// Opt-in asciinema v2 recordings of agent sessions, one .cast file per spawn
+// under recordings/<agentId>-<startedAt>.cast in the app data dir.
+
+const SAFE_ID = /^[A-Za-z0-9_-]+$/;
+
+let recordingRoot: string | null = null;
+
+export function setRecordingRoot(dir: string): void {
+  recordingRoot = path.join(dir, 'recordings');
+}
+
+export interface SessionRecorder {
+  output(data: string): void;
+  resize(cols: number, rows: number): void;
+  close(): void;
+}
+
+/** Start an asciicast v2 recording; returns null when recording isn't possible. */
+export function startRecording(
+  agentId: string,
+  cols: number,
+  rows: number,
+  title: string,
+): SessionRecorder | null {
+  if (!recordingRoot || !SAFE_ID.test(agentId)) return null;
+  const startedAt = Date.now();
+  const file = path.join(recordingRoot, `${agentId}-${startedAt}.cast`);
+  let stream: fs.WriteStream | null;
+  try {
+    fs.mkdirSync(recordingRoot, { recursive: true });
+    stream = fs.createWriteStream(file, { flags: 'w' });
+  } catch (err) {
+    console.warn('Failed to start session recording:', err);
+    return null;
+  }
+  stream.on('error', (err) => {
+    console.warn('Session recording write failed:', err);
+    stream = null;
+  });
+
+  const header = {
+    version: 2,
+    width: cols,
+    height: rows,
+    timestamp: Math.floor(startedAt / 1000),
+    title,
+    env: { TERM: 'xterm-256color', SHELL: process.env.SHELL ?? '/bin/sh' },
+  };
+  stream.write(JSON.stringify(header) + '\n');
+
+  const event = (type: 'o' | 'r', data: string) => {
+    const elapsed = (Date.now() - startedAt) / 1000;
+    stream?.write(JSON.stringify([Number(elapsed.toFixed(6)), type, data]) + '\n');
+  };
+
+  return {
+    output: (data) => event('o', data),
+    resize: (c, r) => event('r', `${c}x${r}`),
+    close() {
+      stream?.end();
+      stream = null;
+    },
+  };
+}
+
+function recordingNames(agentId: string): string[] {
+  if (!recordingRoot || !SAFE_ID.test(agentId)) return [];
+  try {
+    return fs
+      .readdirSync(recordingRoot)
+      .filter((n) => n.startsWith(`${agentId}-`) && n.endsWith('.cast'));
+  } catch {
+    return [];
+  }
+}
+
+/** Path of the most recent recording for an agent, or null if none exists. */
+export function latestRecording(agentId: string): string | null {
+  const latest = recordingNames(agentId).sort().pop();
+  return recordingRoot && latest ? path.join(recordingRoot, latest) : null;
+}
+
+/** Copy an agent's recordings into `dest`, e.g. when its task is archived. */
+export function copyRecordings(agentId: string, dest: string): void {
+  const names = recordingNames(agentId);
+  if (!recordingRoot || names.length === 0) return;
+  fs.mkdirSync(dest, { recursive: true });
+  for (const name of names) fs.copyFileSync(path.join(recordingRoot, name), path.join(dest, name));
+}
+
+/** Drop every recording of an agent (called when its task is deleted). */
+export function removeRecordings(agentId: string): void {
+  if (!recordingRoot) return;
+  for (const name of recordingNames(agentId)) {
+    fs.rmSync(path.join(recordingRoot, name), { force: true });
+  }
+}
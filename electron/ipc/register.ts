@@ -1,53 +1,181 @@
-import { ipcMain, dialog, shell, app, BrowserWindow, Notification } from 'electron';
+import { ipcMain, dialog, shell, app, clipboard, BrowserWindow, Notification } from 'electron';
 import fs from 'fs';
 import { fileURLToPath } from 'url';
 import { IPC } from './channels.js';
 import {
   spawnAgent,
+  spawnShell,
   writeToAgent,
+  pasteToAgent,
   resizeAgent,
   pauseAgent,
   resumeAgent,
   killAgent,
+  terminateAgent,
+  sendSignal,
+  setAgentPriority,
+  isAgentPriority,
   countRunningAgents,
   killAllAgents,
   getAgentMeta,
   isDockerAvailable,
   dockerImageExists,
   buildDockerImage,
+  onPtyEvent,
 } from './pty.js';
+import {
+  appendAutoResponseAudit,
+  readAutoResponseLog,
+  type AutoResponseAuditEntry,
+} from './auto-response-log.js';
 import {
   ensurePlansDirectory,
   startPlanWatcher,
   stopPlanWatcher,
   readPlanForWorktree,
 } from './plans.js';
+import { startAutoFetch, stopAutoFetch } from './auto-fetch.js';
+import {
+  requestAgentSpawn,
+  cancelQueuedSpawn,
+  startQueuedSpawnNow,
+  drainAgentQueue,
+  setAgentConcurrencyLimit,
+} from './agent-queue.js';
+import { scheduleTaskStart, cancelScheduledTaskStart } from './scheduler.js';
+import { watchWorktree, unwatchWorktree } from './worktree-watcher.js';
 import { startRemoteServer } from '../remote/server.js';
 import {
   getGitIgnoredDirs,
+  scanIgnoredEntries,
   getMainBranch,
   getCurrentBranch,
   getChangedFiles,
   getChangedFilesFromBranch,
   getAllFileDiffs,
   getAllFileDiffsFromBranch,
+  getMergePreview,
   getFileDiff,
+  getFileDiffRange,
+  truncateLargeFileDiffs,
+  getFileAtRevision,
+  getFileDiffStructured,
+  getAllFileDiffsStructured,
   getFileDiffFromBranch,
   getWorktreeStatus,
+  getAllWorktreeStatuses,
   commitAll,
+  commitWorktree,
+  stageFile,
+  unstageFile,
+  discardFileChanges,
+  getUnstagedHunks,
+  stageHunks,
   discardUncommitted,
+  stashChanges,
+  listStashes,
+  popStash,
+  transferChanges,
+  applyPatch,
   checkMergeStatus,
+  createIntegrationBranch,
+  checkCrossTaskConflicts,
+  compareWorktrees,
+  getWorktreeCrossDiff,
+  getConflictDetails,
+  resolveConflict,
+  continueMerge,
+  abortMerge,
+  continueRebase,
+  abortRebase,
+  verifyConflictResolution,
   mergeTask,
   getBranchLog,
+  exportBranch,
+  listBranches,
+  discoverWorktrees,
+  repairWorktrees,
+  removeWorktreeOrphans,
+  findTaskLeftovers,
+  removeTaskLeftovers,
+  getCommitList,
+  getCommitDiff,
+  getBlame,
+  createSnapshot,
+  listSnapshots,
+  restoreSnapshot,
+  deleteSnapshot,
+  getUndoableOperation,
+  undoLastOperation,
   pushTask,
+  deleteRemoteBranch,
   rebaseTask,
+  pullMain,
   createWorktree,
   removeWorktree,
   isGitRepo,
+  type DiffOptions,
 } from './git.js';
-import { createTask, deleteTask } from './tasks.js';
-import { listAgents } from './agents.js';
-import { saveAppState, loadAppState } from './persistence.js';
+import {
+  createTask,
+  createTaskBatch,
+  deleteTask,
+  duplicateTask,
+  generateCommitMessage,
+  previewDeleteTask,
+  type LinkedBranch,
+  type LinkedRepoSpec,
+} from './tasks.js';
+import { pasteImageToAgent } from './image-paste.js';
+import { runHeadless, cancelHeadless, hasHeadlessMode } from './headless-exec.js';
+import { setSessionLogRoot, readSessionLog } from './session-log.js';
+import { setAgentStopRoot } from './agent-stop.js';
+import { setRecordingRoot, latestRecording } from './recording.js';
+import { setArchiveRoot, archiveTask, listArchived, restoreArchivedTask } from './archive.js';
+import {
+  listAgents,
+  addAgent,
+  updateAgent,
+  removeAgent,
+  listCustomAgents,
+  importAgents,
+  checkImportedAgents,
+} from './agents.js';
+import { buildStateExport, parseStateExport } from './state-export.js';
+import { detectSetupCommand } from './setup.js';
+import { createPullRequest, detectForge, getCiStatus, listRemotes } from './forge.js';
+import {
+  setForgeTokenRoot,
+  listForgeTokenHosts,
+  setForgeToken,
+  getForgeToken,
+} from './forge-tokens.js';
+import {
+  runScript,
+  cancelJob,
+  listJobs,
+  getJobOutput,
+  removeTaskJobs,
+  getPreviewUrl,
+} from './jobs.js';
+import { isLocalPreviewUrl } from './ports.js';
+import {
+  queuePrompt,
+  listQueuedPrompts,
+  removeQueuedPrompt,
+  reorderQueuedPrompt,
+  sendNextQueuedPrompt,
+  clearPromptQueue,
+} from './prompt-queue.js';
+import {
+  saveSettings,
+  saveProject,
+  saveTask,
+  loadAppState,
+  getStateDir,
+  setStateEventSink,
+  resolveStateConflicts,
+} from './persistence.js';
 import { spawn } from 'child_process';
 import { askAboutCode, cancelAskAboutCode } from './ask-code.js';
 import path from 'path';
@@ -80,6 +208,63 @@ function validateBranchName(name: unknown, label: string): void {
   if (name.startsWith('-')) throw new Error(`${label} must not start with "-"`);
 }
 
+/** Optional remote name; the pattern also keeps it from reading as a git flag. */
+function validateRemoteName(name: unknown, label: string): void {
+  if (name === undefined) return;
+  if (typeof name !== 'string' || !/^[A-Za-z0-9][\w.-]*$/.test(name)) {
+    throw new Error(`${label} must be a remote name`);
+  }
+}
+
+/** Validate the other repositories a multi-repo task gets worktrees in. */
+function validateLinkedRepos(v: unknown): LinkedRepoSpec[] {
+  if (v === undefined) return [];
+  if (!Array.isArray(v)) throw new Error('linkedRepos must be an array');
+  return v.map((repo) => {
+    validatePath(repo?.projectRoot, 'linkedRepos.projectRoot');
+    assertStringArray(repo.symlinkDirs, 'linkedRepos.symlinkDirs');
+    assertStringArray(repo.copyPaths, 'linkedRepos.copyPaths');
+    assertStringArray(repo.clonePaths, 'linkedRepos.clonePaths');
+    assertStringArray(repo.hardlinkPaths, 'linkedRepos.hardlinkPaths');
+    assertOptionalString(repo.worktreeBaseDir, 'linkedRepos.worktreeBaseDir');
+    return { ...repo, worktreeBaseDir: repo.worktreeBaseDir || null };
+  });
+}
+
+/** Validate the other repositories' branches of a multi-repo task being closed. */
+function validateLinkedBranches(v: unknown): LinkedBranch[] | undefined {
+  if (v === undefined) return undefined;
+  if (!Array.isArray(v)) throw new Error('linked must be an array');
+  return v.map((l) => {
+    validatePath(l?.projectRoot, 'linked.projectRoot');
+    validateBranchName(l.branchName, 'linked.branchName');
+    return { projectRoot: l.projectRoot, branchName: l.branchName };
+  });
+}
+
+/** Validate the optional diff options object passed to the diff commands. */
+function validateDiffOptions(v: unknown): DiffOptions | undefined {
+  if (v === undefined || v === null) return undefined;
+  if (typeof v !== 'object') throw new Error('options must be an object');
+  const o = v as Record<string, unknown>;
+  const ws = o.ignore_whitespace;
+  if (ws !== undefined && ws !== 'all' && ws !== 'change') {
+    throw new Error('options.ignore_whitespace must be "all" or "change"');
+  }
+  if (o.context_lines !== undefined) {
+    assertInt(o.context_lines, 'options.context_lines');
+    if (o.context_lines < 0 || o.context_lines > 1000) {
+      throw new Error('options.context_lines must be between 0 and 1000');
+    }
+  }
+  assertOptionalBoolean(o.detect_renames, 'options.detect_renames');
+  return {
+    ignore_whitespace: ws as DiffOptions['ignore_whitespace'],
+    context_lines: o.context_lines as number | undefined,
+    detect_renames: o.detect_renames as boolean | undefined,
+  };
+}
+
 /**
  * Create a leading+trailing throttled event forwarder.
  * Fires immediately, suppresses for `intervalMs`, then fires once more
@@ -115,39 +300,106 @@ export function registerAllHandlers(win: BrowserWindow): void {
   let remoteServer: ReturnType<typeof startRemoteServer> | null = null;
   const taskNames = new Map<string, string>();
 
+  setSessionLogRoot(getStateDir());
+  setAgentStopRoot(getStateDir());
+  setRecordingRoot(getStateDir());
+  setArchiveRoot(getStateDir());
+  setForgeTokenRoot(getStateDir());
+  setStateEventSink(win);
+
   // --- PTY commands ---
   ipcMain.handle(IPC.SpawnAgent, (_e, args) => {
     assertString(args.command, 'command');
     assertStringArray(args.args, 'args');
+    if (args.extraArgs != null) assertStringArray(args.extraArgs, 'extraArgs');
     assertString(args.taskId, 'taskId');
     assertString(args.agentId, 'agentId');
     assertInt(args.cols, 'cols');
     assertInt(args.rows, 'rows');
     assertOptionalBoolean(args.dockerMode, 'dockerMode');
     assertOptionalString(args.dockerImage, 'dockerImage');
+    assertOptionalBoolean(args.record, 'record');
+    assertOptionalBoolean(args.clipboard, 'clipboard');
+    if (args.idleTimeout != null) {
+      assertInt(args.idleTimeout.minutes, 'idleTimeout.minutes');
+      if (args.idleTimeout.minutes < 1) throw new Error('idleTimeout.minutes must be at least 1');
+      if (args.idleTimeout.action !== 'kill' && args.idleTimeout.action !== 'suspend') {
+        throw new Error(`Invalid idleTimeout.action: ${args.idleTimeout.action}`);
+      }
+    }
+    if (args.priority != null && !isAgentPriority(args.priority)) {
+      throw new Error(`Invalid priority: ${args.priority}`);
+    }
+    if (args.supervise != null) {
+      assertStringArray(args.supervise.resumeArgs, 'supervise.resumeArgs');
+      if (args.supervise.maxRestarts !== undefined)
+        assertInt(args.supervise.maxRestarts, 'supervise.maxRestarts');
+      // attempt is internal bookkeeping; never trust it from the renderer
+      delete args.supervise.attempt;
+    }
+    if (args.autoRespond != null) {
+      if (!Array.isArray(args.autoRespond)) throw new Error('autoRespond must be an array');
+      for (const rule of args.autoRespond) {
+        assertString(rule?.pattern, 'autoRespond.pattern');
+        assertString(rule?.response, 'autoRespond.response');
+      }
+    }
     if (args.cwd) validatePath(args.cwd, 'cwd');
-    if (!args.isShell && args.cwd) {
-      try {
-        ensurePlansDirectory(args.cwd);
-      } catch (err) {
-        console.warn('Failed to set up plans directory:', err);
+    const start = () => {
+      if (!args.isShell && args.cwd) {
+        try {
+          ensurePlansDirectory(args.cwd);
+        } catch (err) {
+          console.warn('Failed to set up plans directory:', err);
+        }
       }
+      spawnAgent(win, args);
+      if (!args.isShell && args.cwd) {
+        try {
+          startPlanWatcher(win, args.taskId, args.cwd);
+        } catch (err) {
+          console.warn('Failed to start plan watcher:', err);
+        }
+      }
+    };
+    if (args.isShell) {
+      start();
+      return 0;
     }
-    const result = spawnAgent(win, args);
-    if (!args.isShell && args.cwd) {
+    // Agents over the concurrency limit wait; a failed deferred start is
+    // reported on the output channel since the invoke has long resolved.
+    return requestAgentSpawn(win, args.agentId, args.taskId, () => {
       try {
-        startPlanWatcher(win, args.taskId, args.cwd);
+        start();
       } catch (err) {
-        console.warn('Failed to start plan watcher:', err);
+        if (win.isDestroyed()) return;
+        win.webContents.send(`channel:${args.onOutput.__CHANNEL_ID__}`, {
+          type: 'Exit',
+          data: { exit_code: null, signal: 'spawn_failed', last_output: [String(err)] },
+        });
       }
-    }
-    return result;
+    });
+  });
+  ipcMain.handle(IPC.SpawnShell, (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    assertString(args.shellId, 'shellId');
+    assertInt(args.cols, 'cols');
+    assertInt(args.rows, 'rows');
+    assertOptionalBoolean(args.dockerMode, 'dockerMode');
+    assertOptionalString(args.dockerImage, 'dockerImage');
+    if (args.cwd) validatePath(args.cwd, 'cwd');
+    return spawnShell(win, args);
   });
   ipcMain.handle(IPC.WriteToAgent, (_e, args) => {
     assertString(args.agentId, 'agentId');
     assertString(args.data, 'data');
     return writeToAgent(args.agentId, args.data);
   });
+  ipcMain.handle(IPC.PasteToAgent, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertString(args.data, 'data');
+    return pasteToAgent(args.agentId, args.data);
+  });
   ipcMain.handle(IPC.ResizeAgent, (_e, args) => {
     assertString(args.agentId, 'agentId');
     assertInt(args.cols, 'cols');
@@ -164,13 +416,157 @@ export function registerAllHandlers(win: BrowserWindow): void {
   });
   ipcMain.handle(IPC.KillAgent, (_e, args) => {
     assertString(args.agentId, 'agentId');
-    return killAgent(args.agentId);
+    if (cancelQueuedSpawn(args.agentId)) return;
+    killAgent(args.agentId);
+    // A kill can cancel a pending supervised restart without an exit event
+    drainAgentQueue();
+  });
+  ipcMain.handle(IPC.TerminateAgent, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    if (args.graceMs !== undefined) {
+      assertInt(args.graceMs, 'graceMs');
+      if (args.graceMs < 0 || args.graceMs > 60_000) throw new Error('graceMs must be 0-60000');
+    }
+    return terminateAgent(args.agentId, args.graceMs);
+  });
+  ipcMain.handle(IPC.SendSignal, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertString(args.signal, 'signal');
+    return sendSignal(args.agentId, args.signal);
+  });
+  ipcMain.handle(IPC.PasteImage, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertString(args.data, 'data');
+    assertString(args.mimeType, 'mimeType');
+    return pasteImageToAgent(args.agentId, args.data, args.mimeType);
+  });
+  ipcMain.handle(IPC.GetSessionLog, (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    assertString(args.agentId, 'agentId');
+    const offset = args.offset ?? -64 * 1024;
+    const length = args.length ?? 64 * 1024;
+    assertInt(offset, 'offset');
+    assertInt(length, 'length');
+    return readSessionLog(args.taskId, args.agentId, offset, length);
+  });
+  ipcMain.handle(IPC.ExportSessionRecording, async (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    const source = latestRecording(args.agentId);
+    if (!source) throw new Error('No recording found for this agent');
+    const result = await dialog.showSaveDialog(win, {
+      defaultPath: path.join(app.getPath('downloads'), path.basename(source)),
+      filters: [{ name: 'Asciicast', extensions: ['cast'] }],
+    });
+    if (result.canceled || !result.filePath) return null;
+    await fs.promises.copyFile(source, result.filePath);
+    return result.filePath;
+  });
+  ipcMain.handle(IPC.RunHeadless, async (_e, args) => {
+    assertString(args.runId, 'runId');
+    assertString(args.agentDefId, 'agentDefId');
+    assertString(args.prompt, 'prompt');
+    validatePath(args.cwd, 'cwd');
+    if (args.extraArgs != null) assertStringArray(args.extraArgs, 'extraArgs');
+    const agentDef = (await listAgents()).find((a) => a.id === args.agentDefId);
+    if (!agentDef) throw new Error(`Unknown agent: ${args.agentDefId}`);
+    return runHeadless(win, {
+      runId: args.runId,
+      agentDefId: agentDef.id,
+      command: agentDef.command,
+      prompt: args.prompt,
+      cwd: args.cwd,
+      extraArgs: args.extraArgs,
+      env: agentDef.env,
+      channelId: args.onOutput?.__CHANNEL_ID__,
+    });
+  });
+  ipcMain.handle(IPC.CancelHeadless, (_e, args) => {
+    assertString(args.runId, 'runId');
+    cancelHeadless(args.runId);
+  });
+  ipcMain.handle(IPC.GetAutoResponseLog, (_e, args) => {
+    const limit = args?.limit ?? 100;
+    assertInt(limit, 'limit');
+    return readAutoResponseLog(Math.min(Math.max(limit, 1), 1000));
+  });
+  onPtyEvent('auto-response', (_agentId, entry) =>
+    appendAutoResponseAudit(entry as AutoResponseAuditEntry),
+  );
+  onPtyEvent('clipboard', (_agentId, text) => clipboard.writeText(text as string));
+  ipcMain.handle(IPC.SetAgentPriority, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    if (!isAgentPriority(args.priority)) throw new Error(`Invalid priority: ${args.priority}`);
+    return setAgentPriority(args.agentId, args.priority);
   });
   ipcMain.handle(IPC.CountRunningAgents, () => countRunningAgents());
+  ipcMain.handle(IPC.SetAgentConcurrencyLimit, (_e, args) => {
+    assertInt(args.limit, 'limit');
+    if (args.limit < 0) throw new Error('limit must not be negative');
+    setAgentConcurrencyLimit(win, args.limit);
+  });
+  ipcMain.handle(IPC.StartQueuedAgent, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    return startQueuedSpawnNow(args.agentId);
+  });
+  ipcMain.handle(IPC.ScheduleTaskStart, (_e, args) => {
+    assertString(args.id, 'id');
+    assertInt(args.at, 'at');
+    scheduleTaskStart(win, args.id, args.at);
+  });
+  ipcMain.handle(IPC.CancelScheduledTaskStart, (_e, args) => {
+    assertString(args.id, 'id');
+    cancelScheduledTaskStart(args.id);
+  });
+  onPtyEvent('usage-limit', (agentId, data) => {
+    if (!win.isDestroyed()) {
+      win.webContents.send(IPC.UsageLimitDetected, {
+        agentId,
+        ...(data as { resets_at: number }),
+      });
+    }
+  });
+  // Deferred: the exiting session is still counted while exit listeners run
+  onPtyEvent('exit', () => setImmediate(drainAgentQueue));
+  onPtyEvent('exit', (agentId) => clearPromptQueue(agentId));
   ipcMain.handle(IPC.KillAllAgents, () => killAllAgents());
 
+  // --- Prompt queue ---
+  ipcMain.handle(IPC.QueuePrompt, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertString(args.text, 'text');
+    return queuePrompt(win, args.agentId, args.text);
+  });
+  ipcMain.handle(IPC.ListQueuedPrompts, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    return listQueuedPrompts(args.agentId);
+  });
+  ipcMain.handle(IPC.RemoveQueuedPrompt, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertString(args.promptId, 'promptId');
+    return removeQueuedPrompt(args.agentId, args.promptId);
+  });
+  ipcMain.handle(IPC.ReorderQueuedPrompt, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    assertInt(args.fromIndex, 'fromIndex');
+    assertInt(args.toIndex, 'toIndex');
+    return reorderQueuedPrompt(args.agentId, args.fromIndex, args.toIndex);
+  });
+  ipcMain.handle(IPC.SendNextQueuedPrompt, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    return sendNextQueuedPrompt(args.agentId);
+  });
+
   // --- Agent commands ---
   ipcMain.handle(IPC.ListAgents, () => listAgents());
+  ipcMain.handle(IPC.AddAgent, (_e, args) => addAgent(args?.agent));
+  ipcMain.handle(IPC.UpdateAgent, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    return updateAgent(args.agentId, args.agent);
+  });
+  ipcMain.handle(IPC.RemoveAgent, (_e, args) => {
+    assertString(args.agentId, 'agentId');
+    return removeAgent(args.agentId);
+  });
   ipcMain.handle(IPC.CheckDockerAvailable, () => isDockerAvailable());
   ipcMain.handle(IPC.CheckDockerImageExists, (_e, args) => {
     assertString(args.image, 'image');
@@ -187,108 +583,764 @@ export function registerAllHandlers(win: BrowserWindow): void {
     validatePath(args.projectRoot, 'projectRoot');
     assertStringArray(args.symlinkDirs, 'symlinkDirs');
     assertOptionalString(args.branchPrefix, 'branchPrefix');
+    if (args.copyPaths !== undefined) assertStringArray(args.copyPaths, 'copyPaths');
+    if (args.clonePaths !== undefined) assertStringArray(args.clonePaths, 'clonePaths');
+    if (args.hardlinkPaths !== undefined) assertStringArray(args.hardlinkPaths, 'hardlinkPaths');
+    assertOptionalBoolean(args.carryRootChanges, 'carryRootChanges');
+    if (args.fromBranch != null) validateBranchName(args.fromBranch, 'fromBranch');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    const linkedRepos = validateLinkedRepos(args.linkedRepos);
     const result = createTask(
       args.name,
       args.projectRoot,
       args.symlinkDirs,
       args.branchPrefix ?? 'task',
+      args.copyPaths ?? [],
+      args.clonePaths ?? [],
+      args.carryRootChanges ?? false,
+      args.fromBranch || null,
+      args.worktreeBaseDir || null,
+      args.hardlinkPaths ?? [],
+      linkedRepos,
     );
     result.then((r: { id: string }) => taskNames.set(r.id, args.name)).catch(() => {});
     return result;
   });
+  ipcMain.handle(IPC.CreateTaskBatch, async (_e, args) => {
+    assertStringArray(args.names, 'names');
+    validatePath(args.projectRoot, 'projectRoot');
+    assertStringArray(args.symlinkDirs, 'symlinkDirs');
+    assertOptionalString(args.branchPrefix, 'branchPrefix');
+    if (args.copyPaths !== undefined) assertStringArray(args.copyPaths, 'copyPaths');
+    if (args.clonePaths !== undefined) assertStringArray(args.clonePaths, 'clonePaths');
+    if (args.hardlinkPaths !== undefined) assertStringArray(args.hardlinkPaths, 'hardlinkPaths');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    const results = await createTaskBatch(
+      args.names,
+      args.projectRoot,
+      args.symlinkDirs,
+      args.branchPrefix ?? 'task',
+      args.copyPaths ?? [],
+      args.clonePaths ?? [],
+      args.worktreeBaseDir || null,
+      args.hardlinkPaths ?? [],
+      validateLinkedRepos(args.linkedRepos),
+    );
+    for (const r of results) if (r.task) taskNames.set(r.task.id, r.name);
+    return results;
+  });
+  ipcMain.handle(IPC.DuplicateTask, async (_e, args) => {
+    assertString(args.name, 'name');
+    validatePath(args.projectRoot, 'projectRoot');
+    validatePath(args.sourcePath, 'sourcePath');
+    assertStringArray(args.symlinkDirs, 'symlinkDirs');
+    assertOptionalString(args.branchPrefix, 'branchPrefix');
+    if (args.copyPaths !== undefined) assertStringArray(args.copyPaths, 'copyPaths');
+    if (args.clonePaths !== undefined) assertStringArray(args.clonePaths, 'clonePaths');
+    if (args.hardlinkPaths !== undefined) assertStringArray(args.hardlinkPaths, 'hardlinkPaths');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    assertOptionalBoolean(args.fromMain, 'fromMain');
+    assertOptionalBoolean(args.copyUncommitted, 'copyUncommitted');
+    const result = await duplicateTask({
+      name: args.name,
+      projectRoot: args.projectRoot,
+      sourcePath: args.sourcePath,
+      symlinkDirs: args.symlinkDirs,
+      branchPrefix: args.branchPrefix ?? 'task',
+      copyPaths: args.copyPaths ?? [],
+      clonePaths: args.clonePaths ?? [],
+      hardlinkPaths: args.hardlinkPaths ?? [],
+      worktreeBaseDir: args.worktreeBaseDir || null,
+      fromMain: args.fromMain ?? false,
+      copyUncommitted: args.copyUncommitted ?? false,
+    });
+    taskNames.set(result.id, args.name);
+    return result;
+  });
+  ipcMain.handle(IPC.ArchiveTask, async (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    assertString(args.name, 'name');
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    validatePath(args.worktreePath, 'worktreePath');
+    assertStringArray(args.agentIds, 'agentIds');
+    assertBoolean(args.keepBranch, 'keepBranch');
+    assertOptionalBoolean(args.permanent, 'permanent');
+    removeTaskJobs(args.taskId);
+    return archiveTask({
+      taskId: args.taskId,
+      name: args.name,
+      projectRoot: args.projectRoot,
+      branchName: args.branchName,
+      worktreePath: args.worktreePath,
+      agentIds: args.agentIds,
+      keepBranch: args.keepBranch,
+      permanent: args.permanent ?? false,
+      task: args.task ?? null,
+    });
+  });
+  ipcMain.handle(IPC.ListArchivedTasks, (_e, args) => {
+    if (args?.projectRoot != null) validatePath(args.projectRoot, 'projectRoot');
+    return listArchived(args?.projectRoot ?? undefined);
+  });
+  ipcMain.handle(IPC.RestoreArchivedTask, async (_e, args) => {
+    assertString(args.id, 'id');
+    assertStringArray(args.symlinkDirs, 'symlinkDirs');
+    assertOptionalString(args.branchPrefix, 'branchPrefix');
+    if (args.copyPaths !== undefined) assertStringArray(args.copyPaths, 'copyPaths');
+    if (args.clonePaths !== undefined) assertStringArray(args.clonePaths, 'clonePaths');
+    if (args.hardlinkPaths !== undefined) assertStringArray(args.hardlinkPaths, 'hardlinkPaths');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    const result = await restoreArchivedTask({
+      id: args.id,
+      symlinkDirs: args.symlinkDirs,
+      branchPrefix: args.branchPrefix ?? 'task',
+      copyPaths: args.copyPaths ?? [],
+      clonePaths: args.clonePaths ?? [],
+      hardlinkPaths: args.hardlinkPaths ?? [],
+      worktreeBaseDir: args.worktreeBaseDir || null,
+    });
+    taskNames.set(result.task.id, result.archived.name);
+    return result;
+  });
   ipcMain.handle(IPC.DeleteTask, (_e, args) => {
     assertStringArray(args.agentIds, 'agentIds');
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
     assertBoolean(args.deleteBranch, 'deleteBranch');
     assertOptionalString(args.taskId, 'taskId');
+    assertOptionalBoolean(args.permanent, 'permanent');
+    assertOptionalBoolean(args.force, 'force');
+    const linked = validateLinkedBranches(args.linked);
+    if (args.taskId) removeTaskJobs(args.taskId);
     return deleteTask({
       taskId: args.taskId,
       agentIds: args.agentIds,
       branchName: args.branchName,
       deleteBranch: args.deleteBranch,
       projectRoot: args.projectRoot,
+      linked,
+      permanent: args.permanent,
+      force: args.force,
+    });
+  });
+  ipcMain.handle(IPC.DetectSetupCommand, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return detectSetupCommand(args.worktreePath);
+  });
+
+  // --- Jobs ---
+  ipcMain.handle(IPC.RunScript, (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    assertString(args.scriptId, 'scriptId');
+    assertString(args.name, 'name');
+    assertString(args.command, 'command');
+    validatePath(args.cwd, 'cwd');
+    assertOptionalBoolean(args.devServer, 'devServer');
+    return runScript(win, {
+      taskId: args.taskId,
+      scriptId: args.scriptId,
+      name: args.name,
+      command: args.command,
+      cwd: args.cwd,
+      devServer: args.devServer,
+    });
+  });
+  ipcMain.handle(IPC.CancelJob, (_e, args) => {
+    assertString(args.jobId, 'jobId');
+    cancelJob(args.jobId);
+  });
+  ipcMain.handle(IPC.ListJobs, (_e, args) => {
+    assertOptionalString(args?.taskId, 'taskId');
+    return listJobs(args?.taskId);
+  });
+  ipcMain.handle(IPC.GetJobOutput, (_e, args) => {
+    assertString(args.jobId, 'jobId');
+    return getJobOutput(args.jobId);
+  });
+  ipcMain.handle(IPC.RemoveTaskJobs, (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    removeTaskJobs(args.taskId);
+  });
+  ipcMain.handle(IPC.OpenPreview, async (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    const url = getPreviewUrl(args.taskId);
+    if (!url) throw new Error('No dev server is running for this task');
+    // The URL comes from the script's output, so only a local server may be opened
+    if (!isLocalPreviewUrl(url)) throw new Error(`Not a local dev server URL: ${url}`);
+    await shell.openExternal(url);
+    return url;
+  });
+  ipcMain.handle(IPC.PreviewDeleteTask, (_e, args) => {
+    assertStringArray(args.agentIds, 'agentIds');
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    assertBoolean(args.deleteBranch, 'deleteBranch');
+    const linked = validateLinkedBranches(args.linked);
+    return previewDeleteTask({
+      agentIds: args.agentIds,
+      branchName: args.branchName,
+      deleteBranch: args.deleteBranch,
+      projectRoot: args.projectRoot,
+      linked,
     });
   });
 
   // --- Git commands ---
   ipcMain.handle(IPC.GetChangedFiles, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
-    return getChangedFiles(args.worktreePath);
+    if (args.generatedPatterns !== undefined) {
+      assertStringArray(args.generatedPatterns, 'generatedPatterns');
+    }
+    return getChangedFiles(
+      args.worktreePath,
+      validateDiffOptions(args.options),
+      args.generatedPatterns,
+    );
   });
   ipcMain.handle(IPC.GetChangedFilesFromBranch, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
-    return getChangedFilesFromBranch(args.projectRoot, args.branchName);
+    if (args.generatedPatterns !== undefined) {
+      assertStringArray(args.generatedPatterns, 'generatedPatterns');
+    }
+    return getChangedFilesFromBranch(
+      args.projectRoot,
+      args.branchName,
+      validateDiffOptions(args.options),
+      args.generatedPatterns,
+    );
   });
   ipcMain.handle(IPC.GetAllFileDiffs, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
-    return getAllFileDiffs(args.worktreePath);
+    return getAllFileDiffs(args.worktreePath, validateDiffOptions(args.options)).then(
+      truncateLargeFileDiffs,
+    );
   });
   ipcMain.handle(IPC.GetAllFileDiffsFromBranch, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
-    return getAllFileDiffsFromBranch(args.projectRoot, args.branchName);
+    return getAllFileDiffsFromBranch(
+      args.projectRoot,
+      args.branchName,
+      validateDiffOptions(args.options),
+    );
+  });
+  ipcMain.handle(IPC.GetMergePreview, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    return getMergePreview(args.projectRoot, args.branchName, validateDiffOptions(args.options));
   });
   ipcMain.handle(IPC.GetFileDiff, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     validateRelativePath(args.filePath, 'filePath');
-    return getFileDiff(args.worktreePath, args.filePath);
+    return getFileDiff(args.worktreePath, args.filePath, validateDiffOptions(args.options));
+  });
+  ipcMain.handle(IPC.GetFileDiffRange, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    assertInt(args.offset, 'offset');
+    if (args.offset < 0) throw new Error('offset must not be negative');
+    assertInt(args.limit, 'limit');
+    return getFileDiffRange(
+      args.worktreePath,
+      args.filePath,
+      args.offset,
+      args.limit,
+      validateDiffOptions(args.options),
+    );
+  });
+  ipcMain.handle(IPC.GetFileAtRevision, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    if (args.rev !== undefined) validateBranchName(args.rev, 'rev');
+    return getFileAtRevision(args.worktreePath, args.filePath, args.rev);
+  });
+  ipcMain.handle(IPC.GetFileDiffStructured, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    return getFileDiffStructured(
+      args.worktreePath,
+      args.filePath,
+      validateDiffOptions(args.options),
+    );
+  });
+  ipcMain.handle(IPC.GetAllFileDiffsStructured, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return getAllFileDiffsStructured(args.worktreePath, validateDiffOptions(args.options));
   });
   ipcMain.handle(IPC.GetFileDiffFromBranch, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
     validateRelativePath(args.filePath, 'filePath');
-    return getFileDiffFromBranch(args.projectRoot, args.branchName, args.filePath);
+    return getFileDiffFromBranch(
+      args.projectRoot,
+      args.branchName,
+      args.filePath,
+      validateDiffOptions(args.options),
+    );
   });
   ipcMain.handle(IPC.GetGitignoredDirs, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
-    return getGitIgnoredDirs(args.projectRoot);
+    const extra: unknown[] = args.extraCandidates ?? [];
+    const excluded: unknown[] = args.excludedCandidates ?? [];
+    assertStringArray(extra, 'extraCandidates');
+    assertStringArray(excluded, 'excludedCandidates');
+    for (const p of extra) validateRelativePath(p, 'extraCandidates');
+    return getGitIgnoredDirs(args.projectRoot, extra, excluded);
+  });
+  ipcMain.handle(IPC.ScanIgnoredEntries, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    return scanIgnoredEntries(args.projectRoot, args.worktreeBaseDir || null);
   });
   ipcMain.handle(IPC.GetWorktreeStatus, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     return getWorktreeStatus(args.worktreePath);
   });
+  ipcMain.handle(IPC.GetAllWorktreeStatuses, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertStringArray(args.worktreePaths, 'worktreePaths');
+    for (const p of args.worktreePaths) validatePath(p, 'worktreePaths');
+    return getAllWorktreeStatuses(args.projectRoot, args.worktreePaths);
+  });
   ipcMain.handle(IPC.CommitAll, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     assertString(args.message, 'message');
-    return commitAll(args.worktreePath, args.message);
+    assertOptionalBoolean(args.sign, 'sign');
+    assertOptionalBoolean(args.skipHooks, 'skipHooks');
+    return commitAll(args.worktreePath, args.message, {
+      sign: args.sign,
+      skipHooks: args.skipHooks,
+    });
+  });
+  ipcMain.handle(IPC.CommitWorktree, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    assertString(args.message, 'message');
+    if (args.paths !== undefined) {
+      assertStringArray(args.paths, 'paths');
+      for (const p of args.paths) validateRelativePath(p, 'paths');
+    }
+    assertOptionalBoolean(args.sign, 'sign');
+    assertOptionalBoolean(args.skipHooks, 'skipHooks');
+    return commitWorktree(args.worktreePath, args.message, args.paths, {
+      sign: args.sign,
+      skipHooks: args.skipHooks,
+    });
+  });
+  ipcMain.handle(IPC.StageFile, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    return stageFile(args.worktreePath, args.filePath);
+  });
+  ipcMain.handle(IPC.UnstageFile, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    return unstageFile(args.worktreePath, args.filePath);
+  });
+  ipcMain.handle(IPC.DiscardFileChanges, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    return discardFileChanges(args.worktreePath, args.filePath);
+  });
+  ipcMain.handle(IPC.GetUnstagedHunks, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    return getUnstagedHunks(args.worktreePath, args.filePath);
+  });
+  ipcMain.handle(IPC.StageHunks, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    assertStringArray(args.hunkIds, 'hunkIds');
+    return stageHunks(args.worktreePath, args.filePath, args.hunkIds);
   });
   ipcMain.handle(IPC.DiscardUncommitted, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     return discardUncommitted(args.worktreePath);
   });
+  ipcMain.handle(IPC.StashChanges, (_e, args) => {
+    validatePath(args.path, 'path');
+    assertOptionalString(args.message, 'message');
+    return stashChanges(args.path, args.message);
+  });
+  ipcMain.handle(IPC.ListStashes, (_e, args) => {
+    validatePath(args.path, 'path');
+    return listStashes(args.path);
+  });
+  ipcMain.handle(IPC.PopStash, (_e, args) => {
+    validatePath(args.path, 'path');
+    assertOptionalString(args.ref, 'ref');
+    return popStash(args.path, args.ref);
+  });
+  ipcMain.handle(IPC.TransferChanges, (_e, args) => {
+    validatePath(args.sourcePath, 'sourcePath');
+    validatePath(args.targetPath, 'targetPath');
+    if (args.files !== undefined && args.files !== null) {
+      assertStringArray(args.files, 'files');
+      for (const f of args.files) validateRelativePath(f, 'files');
+    }
+    return transferChanges(args.sourcePath, args.targetPath, args.files ?? null);
+  });
+  ipcMain.handle(IPC.ApplyPatch, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    assertString(args.patch, 'patch');
+    assertOptionalBoolean(args.threeWay, 'threeWay');
+    return applyPatch(args.worktreePath, args.patch, args.threeWay ?? false);
+  });
   ipcMain.handle(IPC.CheckMergeStatus, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     return checkMergeStatus(args.worktreePath);
   });
-  ipcMain.handle(IPC.MergeTask, (_e, args) => {
+  ipcMain.handle(IPC.CheckCrossTaskConflicts, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertStringArray(args.branches, 'branches');
+    for (const branch of args.branches) validateBranchName(branch, 'branches');
+    return checkCrossTaskConflicts(args.projectRoot, args.branches);
+  });
+  ipcMain.handle(IPC.CompareWorktrees, (_e, args) => {
+    assertStringArray(args.worktreePaths, 'worktreePaths');
+    for (const p of args.worktreePaths) validatePath(p, 'worktreePaths');
+    return compareWorktrees(args.worktreePaths);
+  });
+  ipcMain.handle(IPC.GetWorktreeCrossDiff, (_e, args) => {
+    validatePath(args.fromWorktree, 'fromWorktree');
+    validatePath(args.toWorktree, 'toWorktree');
+    return getWorktreeCrossDiff(
+      args.fromWorktree,
+      args.toWorktree,
+      validateDiffOptions(args.options),
+    );
+  });
+  ipcMain.handle(IPC.CreateIntegrationBranch, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertStringArray(args.taskBranches, 'taskBranches');
+    for (const branch of args.taskBranches) validateBranchName(branch, 'taskBranches');
+    validateBranchName(args.name, 'name');
+    assertOptionalBoolean(args.replace, 'replace');
+    return createIntegrationBranch(
+      args.projectRoot,
+      args.taskBranches,
+      args.name,
+      args.replace ?? false,
+    );
+  });
+  ipcMain.handle(IPC.GetConflictDetails, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return getConflictDetails(args.worktreePath);
+  });
+  ipcMain.handle(IPC.ResolveConflict, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    const resolution = args.resolution;
+    if (resolution !== 'ours' && resolution !== 'theirs') {
+      assertString(resolution?.content, 'resolution.content');
+    }
+    return resolveConflict(args.worktreePath, args.filePath, resolution);
+  });
+  ipcMain.handle(IPC.ContinueMerge, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return continueMerge(args.worktreePath);
+  });
+  ipcMain.handle(IPC.AbortMerge, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return abortMerge(args.worktreePath);
+  });
+  ipcMain.handle(IPC.ContinueRebase, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return continueRebase(args.worktreePath);
+  });
+  ipcMain.handle(IPC.AbortRebase, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return abortRebase(args.worktreePath);
+  });
+  ipcMain.handle(IPC.VerifyConflictResolution, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    assertStringArray(args.files, 'files');
+    for (const f of args.files) validateRelativePath(f, 'files');
+    assertOptionalString(args.verifyCommand, 'verifyCommand');
+    return verifyConflictResolution(args.worktreePath, args.files, args.verifyCommand ?? null);
+  });
+  ipcMain.handle(IPC.MergeTask, async (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
-    assertBoolean(args.squash, 'squash');
+    if (!['merge', 'squash', 'rebase'].includes(args.strategy))
+      throw new Error('strategy must be "merge", "squash" or "rebase"');
     assertOptionalString(args.message, 'message');
     assertOptionalBoolean(args.cleanup, 'cleanup');
+    assertOptionalBoolean(args.keepBranch, 'keepBranch');
+    assertOptionalBoolean(args.stashRootChanges, 'stashRootChanges');
+    assertOptionalBoolean(args.blockOnRedCi, 'blockOnRedCi');
+    assertOptionalBoolean(args.sign, 'sign');
+    assertOptionalBoolean(args.skipHooks, 'skipHooks');
+    validateRemoteName(args.remote, 'remote');
+    if (args.postMergeCommands !== undefined)
+      assertStringArray(args.postMergeCommands, 'postMergeCommands');
+    if (args.preMergeCommands !== undefined)
+      assertStringArray(args.preMergeCommands, 'preMergeCommands');
+    if (args.preMergeGate !== undefined && !['block', 'warn'].includes(args.preMergeGate))
+      throw new Error('preMergeGate must be "block" or "warn"');
+    const channelId: unknown = args.onCheckOutput?.__CHANNEL_ID__;
+    if (channelId !== undefined) assertString(channelId, 'channelId');
+    if (args.blockOnRedCi) {
+      const ci = await getCiStatus(
+        args.projectRoot,
+        args.branchName,
+        getForgeToken,
+        true,
+        args.remote,
+      );
+      if (ci.state === 'failure') {
+        throw new Error(`CI is failing for ${args.branchName} (${ci.failed} failed)`);
+      }
+    }
     return mergeTask(
       args.projectRoot,
       args.branchName,
-      args.squash,
+      args.strategy,
       args.message ?? null,
       args.cleanup ?? false,
+      args.postMergeCommands ?? [],
+      args.stashRootChanges ?? false,
+      args.preMergeCommands ?? [],
+      args.preMergeGate ?? 'block',
+      channelId
+        ? (text) => {
+            if (!win.isDestroyed()) win.webContents.send(`channel:${channelId}`, text);
+          }
+        : undefined,
+      { sign: args.sign, skipHooks: args.skipHooks },
+      args.keepBranch ?? false,
+    );
+  });
+  ipcMain.handle(IPC.ExportTask, async (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    if (args.format !== 'patch' && args.format !== 'bundle') {
+      throw new Error('format must be "patch" or "bundle"');
+    }
+    const extension = args.format === 'bundle' ? 'bundle' : 'patch';
+    const result = await dialog.showSaveDialog(win, {
+      defaultPath: path.join(
+        app.getPath('downloads'),
+        `${args.branchName.replace(/\//g, '-')}.${extension}`,
+      ),
+      filters: [
+        args.format === 'bundle'
+          ? { name: 'Git bundle', extensions: ['bundle'] }
+          : { name: 'Patch series', extensions: ['patch', 'mbox'] },
+      ],
+    });
+    if (result.canceled || !result.filePath) return null;
+    await exportBranch(args.projectRoot, args.branchName, args.format, result.filePath);
+    return result.filePath;
+  });
+  ipcMain.handle(IPC.ListBranches, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return listBranches(args.projectRoot);
+  });
+  ipcMain.handle(IPC.DiscoverWorktrees, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return discoverWorktrees(args.projectRoot);
+  });
+  ipcMain.handle(IPC.RepairWorktrees, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    validateBranchName(args.branchPrefix, 'branchPrefix');
+    return repairWorktrees(args.projectRoot, args.worktreeBaseDir || null, args.branchPrefix);
+  });
+  ipcMain.handle(IPC.RemoveWorktreeOrphans, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    assertStringArray(args.dirs, 'dirs');
+    assertStringArray(args.branches, 'branches');
+    for (const dir of args.dirs) validatePath(dir, 'dirs');
+    for (const branch of args.branches) validateBranchName(branch, 'branches');
+    return removeWorktreeOrphans(
+      args.projectRoot,
+      args.worktreeBaseDir || null,
+      args.dirs,
+      args.branches,
+    );
+  });
+  ipcMain.handle(IPC.FindTaskLeftovers, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    validateBranchName(args.branchPrefix, 'branchPrefix');
+    assertStringArray(args.knownBranches, 'knownBranches');
+    return findTaskLeftovers(
+      args.projectRoot,
+      args.worktreeBaseDir || null,
+      args.branchPrefix,
+      args.knownBranches,
     );
   });
+  ipcMain.handle(IPC.RemoveTaskLeftovers, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    assertOptionalString(args.worktreeBaseDir, 'worktreeBaseDir');
+    if (!Array.isArray(args.leftovers)) throw new Error('leftovers must be an array');
+    const leftovers = args.leftovers.map((l: { branch?: unknown; path?: unknown }) => {
+      if (l.branch !== null) validateBranchName(l.branch, 'leftovers.branch');
+      if (l.path !== null) validatePath(l.path, 'leftovers.path');
+      return { branch: l.branch as string | null, path: l.path as string | null };
+    });
+    return removeTaskLeftovers(args.projectRoot, args.worktreeBaseDir || null, leftovers);
+  });
   ipcMain.handle(IPC.GetBranchLog, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
     return getBranchLog(args.worktreePath);
   });
+  ipcMain.handle(IPC.GenerateCommitMessage, async (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    assertOptionalString(args.agentDefId, 'agentDefId');
+    // The task's own agent when it can run headless, else the first one that can
+    const agents = (await listAgents()).filter((a) => hasHeadlessMode(a.id));
+    const agent =
+      agents.find((a) => a.id === args.agentDefId) ?? agents.find((a) => a.available !== false);
+    if (!agent) throw new Error('No installed agent can run headless');
+    return generateCommitMessage(win, args.projectRoot, args.branchName, agent);
+  });
+  ipcMain.handle(IPC.GetCommitList, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    if (args.range !== undefined && args.range !== null) validateBranchName(args.range, 'range');
+    if (args.limit !== undefined) assertInt(args.limit, 'limit');
+    if (args.offset !== undefined) assertInt(args.offset, 'offset');
+    return getCommitList(args.worktreePath, args.range ?? null, args.limit, args.offset);
+  });
+  ipcMain.handle(IPC.GetBlame, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateRelativePath(args.filePath, 'filePath');
+    assertInt(args.startLine, 'startLine');
+    assertInt(args.endLine, 'endLine');
+    return getBlame(args.worktreePath, args.filePath, args.startLine, args.endLine);
+  });
+  ipcMain.handle(IPC.CreateSnapshot, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    assertString(args.label, 'label');
+    return createSnapshot(args.worktreePath, args.label);
+  });
+  ipcMain.handle(IPC.ListSnapshots, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return listSnapshots(args.worktreePath);
+  });
+  ipcMain.handle(IPC.RestoreSnapshot, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateBranchName(args.id, 'id');
+    return restoreSnapshot(args.worktreePath, args.id);
+  });
+  ipcMain.handle(IPC.DeleteSnapshot, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    validateBranchName(args.id, 'id');
+    return deleteSnapshot(args.worktreePath, args.id);
+  });
+  ipcMain.handle(IPC.GetUndoableOperation, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return getUndoableOperation(args.projectRoot);
+  });
+  ipcMain.handle(IPC.UndoLastOperation, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return undoLastOperation(args.projectRoot);
+  });
+  ipcMain.handle(IPC.GetCommitDiff, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    assertString(args.hash, 'hash');
+    if (!/^[0-9a-f]{4,64}$/i.test(args.hash)) throw new Error('hash must be a commit hash');
+    return getCommitDiff(args.worktreePath, args.hash, validateDiffOptions(args.options));
+  });
+  ipcMain.handle(IPC.CreatePullRequest, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branch, 'branch');
+    assertString(args.title, 'title');
+    assertString(args.body, 'body');
+    assertOptionalBoolean(args.draft, 'draft');
+    validateRemoteName(args.remote, 'remote');
+    return createPullRequest({
+      projectRoot: args.projectRoot,
+      branch: args.branch,
+      remote: args.remote,
+      title: args.title,
+      body: args.body,
+      draft: args.draft ?? false,
+      tokens: getForgeToken,
+    });
+  });
+  ipcMain.handle(IPC.GetCiStatus, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    assertOptionalBoolean(args.force, 'force');
+    validateRemoteName(args.remote, 'remote');
+    return getCiStatus(
+      args.projectRoot,
+      args.branchName,
+      getForgeToken,
+      args.force ?? false,
+      args.remote,
+    );
+  });
+  ipcMain.handle(IPC.DetectForge, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return detectForge(args.projectRoot);
+  });
+  ipcMain.handle(IPC.ListForgeTokenHosts, () => listForgeTokenHosts());
+  ipcMain.handle(IPC.SetForgeToken, (_e, args) => {
+    assertString(args.host, 'host');
+    assertString(args.token, 'token');
+    setForgeToken(args.host, args.token);
+    return listForgeTokenHosts();
+  });
   ipcMain.handle(IPC.PushTask, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
     validateBranchName(args.branchName, 'branchName');
     assertString(args.onOutput?.__CHANNEL_ID__, 'channelId');
-    return pushTask(win, args.projectRoot, args.branchName, args.onOutput.__CHANNEL_ID__);
+    assertOptionalBoolean(args.forceWithLease, 'forceWithLease');
+    validateRemoteName(args.remote, 'remote');
+    return pushTask(
+      win,
+      args.projectRoot,
+      args.branchName,
+      args.onOutput.__CHANNEL_ID__,
+      args.forceWithLease ?? false,
+      args.remote,
+    );
+  });
+  ipcMain.handle(IPC.DeleteRemoteBranch, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    validateBranchName(args.branchName, 'branchName');
+    validateRemoteName(args.remote, 'remote');
+    return deleteRemoteBranch(args.projectRoot, args.branchName, args.remote);
+  });
+  ipcMain.handle(IPC.ListRemotes, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return listRemotes(args.projectRoot);
   });
   ipcMain.handle(IPC.RebaseTask, (_e, args) => {
     validatePath(args.worktreePath, 'worktreePath');
-    return rebaseTask(args.worktreePath);
+    assertOptionalBoolean(args.pauseOnConflict, 'pauseOnConflict');
+    return rebaseTask(args.worktreePath, args.pauseOnConflict ?? false);
+  });
+  ipcMain.handle(IPC.PullMain, (_e, args) => {
+    validatePath(args.projectRoot, 'projectRoot');
+    return pullMain(args.projectRoot);
+  });
+  ipcMain.handle(IPC.StartAutoFetch, (_e, args) => {
+    assertString(args.projectId, 'projectId');
+    validatePath(args.projectRoot, 'projectRoot');
+    assertInt(args.intervalMinutes, 'intervalMinutes');
+    assertStringArray(args.worktreePaths, 'worktreePaths');
+    for (const p of args.worktreePaths) validatePath(p, 'worktreePaths');
+    startAutoFetch(win, args.projectId, args.projectRoot, args.intervalMinutes, args.worktreePaths);
+  });
+  ipcMain.handle(IPC.StopAutoFetch, (_e, args) => {
+    assertString(args.projectId, 'projectId');
+    stopAutoFetch(args.projectId);
+  });
+  ipcMain.handle(IPC.WatchWorktree, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    return watchWorktree(win, args.worktreePath);
+  });
+  ipcMain.handle(IPC.UnwatchWorktree, (_e, args) => {
+    validatePath(args.worktreePath, 'worktreePath');
+    unwatchWorktree(args.worktreePath);
   });
   ipcMain.handle(IPC.GetMainBranch, (_e, args) => {
     validatePath(args.projectRoot, 'projectRoot');
@@ -318,15 +1370,68 @@ export function registerAllHandlers(win: BrowserWindow): void {
       console.warn('Ignoring malformed saved state:', e);
     }
   }
-  ipcMain.handle(IPC.SaveAppState, (_e, args) => {
+  ipcMain.handle(IPC.SaveSettings, (_e, args) => {
     assertString(args.json, 'json');
-    syncTaskNamesFromJson(args.json);
-    return saveAppState(args.json);
+    return saveSettings(args.json);
+  });
+  ipcMain.handle(IPC.SaveProject, (_e, args) => {
+    assertString(args.projectId, 'projectId');
+    if (args.json !== null) assertString(args.json, 'json');
+    return saveProject(args.projectId, args.json);
+  });
+  ipcMain.handle(IPC.SaveTask, (_e, args) => {
+    assertString(args.taskId, 'taskId');
+    if (args.json !== null) assertString(args.json, 'json');
+    saveTask(args.taskId, args.json);
+    if (args.json !== null) {
+      const task = JSON.parse(args.json) as { name?: string };
+      if (task.name) taskNames.set(args.taskId, task.name);
+    }
   });
   ipcMain.handle(IPC.LoadAppState, () => {
-    const json = loadAppState();
-    if (json) syncTaskNamesFromJson(json);
-    return json;
+    const loaded = loadAppState();
+    if (loaded.json) syncTaskNamesFromJson(loaded.json);
+    return loaded;
+  });
+  ipcMain.handle(IPC.ResolveStateConflict, (_e, args) => {
+    if (!['keep_mine', 'reload', 'stop'].includes(args.choice))
+      throw new Error('choice must be "keep_mine", "reload" or "stop"');
+    resolveStateConflicts(args.choice);
+  });
+  ipcMain.handle(IPC.ExportState, async (_e, args) => {
+    assertString(args.json, 'json');
+    const { settings, projects } = JSON.parse(args.json) as {
+      settings?: Record<string, unknown>;
+      projects?: Record<string, unknown>[];
+    };
+    const result = await dialog.showSaveDialog(win, {
+      defaultPath: path.join(app.getPath('downloads'), 'parallel-code-config.json'),
+      filters: [{ name: 'Parallel Code config', extensions: ['json'] }],
+    });
+    if (result.canceled || !result.filePath) return null;
+    const exported = buildStateExport(settings ?? {}, projects ?? [], listCustomAgents());
+    await fs.promises.writeFile(result.filePath, JSON.stringify(exported, null, 2), 'utf8');
+    return result.filePath;
+  });
+  ipcMain.handle(IPC.ImportState, async () => {
+    const result = await dialog.showOpenDialog(win, {
+      properties: ['openFile'],
+      filters: [{ name: 'Parallel Code config', extensions: ['json'] }],
+    });
+    if (result.canceled || !result.filePaths[0]) return null;
+    // Nothing is applied here: the renderer shows what the file would change first
+    const imported = parseStateExport(await fs.promises.readFile(result.filePaths[0], 'utf8'));
+    const agents = checkImportedAgents(imported.custom_agents);
+    return {
+      settings: imported.settings,
+      projects: imported.projects,
+      custom_agents: agents.agents,
+      refused_agents: agents.refused,
+    };
+  });
+  ipcMain.handle(IPC.ImportAgents, (_e, args) => {
+    if (!Array.isArray(args.agents)) throw new Error('agents must be an array');
+    return importAgents(args.agents);
   });
 
   // --- Arena persistence ---
@@ -0,0 +1,42 @@
+import { IPC } from './channels.js';
+import type { EventSink } from './pty.js';
+
+/**
+ * Wall-clock polling rather than one long timeout: timers stall while the
+ * machine sleeps, and a start due during sleep should fire right after wake.
+ */
+const SCHEDULE_POLL_MS = 15_000;
+
+/** Start time (epoch ms) per pending task; the renderer creates the task when it fires. */
+const scheduled = new Map<string, number>();
+let sink: EventSink | null = null;
+let pollTimer: ReturnType<typeof setInterval> | null = null;
+
+function fireDue(): void {
+  const now = Date.now();
+  for (const [id, at] of scheduled) {
+    if (at > now) continue;
+    if (!sink || sink.isDestroyed()) return; // keep it until a window can hear it
+    scheduled.delete(id);
+    sink.webContents.send(IPC.ScheduledTaskStartFired, { id, at });
+  }
+  if (scheduled.size === 0 && pollTimer) {
+    clearInterval(pollTimer);
+    pollTimer = null;
+  }
+}
+
+/** Fire `id` at `at` (epoch ms). A start already due fires on the next tick. */
+export function scheduleTaskStart(win: EventSink, id: string, at: number): void {
+  sink = win;
+  scheduled.set(id, at);
+  if (!pollTimer) {
+    pollTimer = setInterval(fireDue, SCHEDULE_POLL_MS);
+    pollTimer.unref?.();
+  }
+  if (at <= Date.now()) setImmediate(fireDue);
+}
+
+export function cancelScheduledTaskStart(id: string): void {
+  scheduled.delete(id);
+}
@@ -0,0 +1,125 @@
+import fs from 'fs';
+import path from 'path';
+
+// Raw PTY output of every session, streamed to logs/<taskId>/<agentId>.log
+// under the app data dir. Files rotate at MAX_LOG_BYTES, keeping
+// MAX_GENERATIONS older files (<agentId>.log.1 is the most recent).
+
+const MAX_LOG_BYTES = 5 * 1024 * 1024;
+const MAX_GENERATIONS = 2;
+const MAX_READ_BYTES = 1024 * 1024;
+const SAFE_ID = /^[A-Za-z0-9_-]+$/;
+
+let logRoot: string | null = null;
+
+/** Enable session logging under `dir`. Until called, sessions aren't logged. */
+export function setSessionLogRoot(dir: string): void {
+  logRoot = path.join(dir, 'logs');
+}
+
+function logPath(taskId: string, agentId: string): string | null {
+  if (!logRoot || !SAFE_ID.test(taskId) || !SAFE_ID.test(agentId)) return null;
+  return path.join(logRoot, taskId, `${agentId}.log`);
+}
+
+export interface SessionLogWriter {
+  write(chunk: Buffer): void;
+  close(): void;
+}
+
+export function openSessionLog(taskId: string, agentId: string): SessionLogWriter | null {
+  const file = logPath(taskId, agentId);
+  if (!file) return null;
+  try {
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+  } catch (err) {
+    console.warn('Failed to create session log dir:', err);
+    return null;
+  }
+
+  let size = 0;
+  try {
+    size = fs.statSync(file).size;
+  } catch {
+    /* new file */
+  }
+  let stream: fs.WriteStream | null = null;
+  const open = () => {
+    stream = fs.createWriteStream(file, { flags: 'a' });
+    stream.on('error', (err) => {
+      console.warn('Session log write failed:', err);
+      stream = null;
+    });
+  };
+
+  const rotate = () => {
+    stream?.end();
+    for (let gen = MAX_GENERATIONS; gen >= 1; gen--) {
+      const from = gen === 1 ? file : `${file}.${gen - 1}`;
+      try {
+        fs.renameSync(from, `${file}.${gen}`);
+      } catch {
+        /* generation doesn't exist yet */
+      }
+    }
+    size = 0;
+    open();
+  };
+
+  open();
+  return {
+    write(chunk) {
+      if (!stream) return;
+      if (size + chunk.length > MAX_LOG_BYTES) rotate();
+      stream?.write(chunk);
+      size += chunk.length;
+    },
+    close() {
+      stream?.end();
+      stream = null;
+    },
+  };
+}
+
+/**
+ * Read a slice of a session's current log file. A negative `offset` counts
+ * back from the end, so `offset: -65536` returns the last 64 KB.
+ */
+export function readSessionLog(
+  taskId: string,
+  agentId: string,
+  offset: number,
+  length: number,
+): { content: string; offset: number; size: number } {
+  const file = logPath(taskId, agentId);
+  if (!file) throw new Error('Session logging is not available for this session');
+  let fd: number;
+  try {
+    fd = fs.openSync(file, 'r');
+  } catch {
+    return { content: '', offset: 0, size: 0 };
+  }
+  try {
+    const size = fs.fstatSync(fd).size;
+    const start = Math.max(0, offset < 0 ? size + offset : Math.min(offset, size));
+    const count = Math.min(length, MAX_READ_BYTES, size - start);
+    const buf = Buffer.alloc(count);
+    fs.readSync(fd, buf, 0, count, start);
+    return { content: buf.toString('utf8'), offset: start, size };
+  } finally {
+    fs.closeSync(fd);
+  }
+}
+
+/** Copy all logs for a task into `dest`, e.g. when the task is archived. */
+export function copySessionLogs(taskId: string, dest: string): void {
+  if (!logRoot || !SAFE_ID.test(taskId)) return;
+  const dir = path.join(logRoot, taskId);
+  if (fs.existsSync(dir)) fs.cpSync(dir, dest, { recursive: true });
+}
+
+/** Drop all logs for a task (called when the task is deleted). */
+export function removeSessionLogs(taskId: string): void {
+  if (!logRoot || !SAFE_ID.test(taskId)) return;
+  fs.rmSync(path.join(logRoot, taskId), { recursive: true, force: true });
+}
@@ -0,0 +1,52 @@
+import fs from 'fs';
+import path from 'path';
+
+export interface SetupCommand {
+  manager: string;
+  command: string;
+}
+
+interface SetupRule {
+  /** Lockfile or manifest whose presence selects this rule. */
+  file: string;
+  manager: string;
+  command: string;
+  /** Install target that, when symlinked from the main checkout, must not be written to. */
+  installDir?: string;
+}
+
+// Checked in order; the first rule whose file exists wins. Lockfiles come
+// before manifests so e.g. a pnpm repo isn't installed with npm.
+const SETUP_RULES: SetupRule[] = [
+  { file: 'pnpm-lock.yaml', manager: 'pnpm', command: 'pnpm install', installDir: 'node_modules' },
+  { file: 'yarn.lock', manager: 'yarn', command: 'yarn install', installDir: 'node_modules' },
+  { file: 'bun.lock', manager: 'bun', command: 'bun install', installDir: 'node_modules' },
+  { file: 'bun.lockb', manager: 'bun', command: 'bun install', installDir: 'node_modules' },
+  { file: 'package-lock.json', manager: 'npm', command: 'npm install', installDir: 'node_modules' },
+  { file: 'poetry.lock', manager: 'poetry', command: 'poetry install', installDir: '.venv' },
+  { file: 'uv.lock', manager: 'uv', command: 'uv sync', installDir: '.venv' },
+  { file: 'Cargo.toml', manager: 'cargo', command: 'cargo fetch' },
+  { file: 'go.mod', manager: 'go', command: 'go mod download' },
+];
+
+function isSymlink(p: string): boolean {
+  try {
+    return fs.lstatSync(p).isSymbolicLink();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Detect the dependency install command for a fresh worktree. Returns null if
+ * nothing is recognised, or if the install target is a symlink shared with the
+ * main checkout (installing would mutate the shared directory).
+ */
+export function detectSetupCommand(worktreePath: string): SetupCommand | null {
+  for (const rule of SETUP_RULES) {
+    if (!fs.existsSync(path.join(worktreePath, rule.file))) continue;
+    if (rule.installDir && isSymlink(path.join(worktreePath, rule.installDir))) return null;
+    return { manager: rule.manager, command: rule.command };
+  }
+  return null;
+}
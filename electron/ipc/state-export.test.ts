@@ -0,0 +1,35 @@
+import { describe, it, expect } from 'vitest';
+import { buildStateExport, parseStateExport } from './state-export.js';
+
+describe('parseStateExport', () => {
+  it('reads back what buildStateExport wrote', () => {
+    const exported = buildStateExport(
+      { themePreset: 'minimal' },
+      [{ id: 'p1', name: 'repo', path: '/src/repo' }],
+      [{ id: 'aider', name: 'Aider', command: 'aider' }],
+      new Date('2026-01-01T00:00:00Z'),
+    );
+    expect(parseStateExport(JSON.stringify(exported))).toEqual(exported);
+  });
+
+  it('drops malformed projects and defaults missing sections', () => {
+    const parsed = parseStateExport(
+      JSON.stringify({
+        format: 'parallel-code-config',
+        version: 1,
+        projects: [{ id: 'p1', name: 'repo' }, 'nope', { id: 'p2', name: 'b', path: '/b' }],
+      }),
+    );
+    expect(parsed.projects).toEqual([{ id: 'p2', name: 'b', path: '/b' }]);
+    expect(parsed.settings).toEqual({});
+    expect(parsed.custom_agents).toEqual([]);
+  });
+
+  it('rejects other files and newer formats', () => {
+    expect(() => parseStateExport('not json')).toThrow(/invalid JSON/);
+    expect(() => parseStateExport(JSON.stringify({ tasks: {} }))).toThrow(/Not a Parallel/);
+    expect(() =>
+      parseStateExport(JSON.stringify({ format: 'parallel-code-config', version: 99 })),
+    ).toThrow(/not supported/);
+  });
+});
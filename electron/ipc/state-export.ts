@@ -0,0 +1,71 @@
+// A portable copy of the user's setup (settings, projects and custom agents)
+// that can be moved to another machine or shared as a team baseline. Tasks,
+// window layout and forge tokens are machine- or person-specific and left out.
+
+const EXPORT_FORMAT = 'parallel-code-config';
+const EXPORT_VERSION = 1;
+
+export interface StateExport {
+  format: typeof EXPORT_FORMAT;
+  version: number;
+  exported_at: string;
+  settings: Record<string, unknown>;
+  projects: Record<string, unknown>[];
+  /** Custom agents as stored in agents.json. */
+  custom_agents: unknown[];
+}
+
+function isRecord(v: unknown): v is Record<string, unknown> {
+  return typeof v === 'object' && v !== null && !Array.isArray(v);
+}
+
+export function buildStateExport(
+  settings: Record<string, unknown>,
+  projects: Record<string, unknown>[],
+  customAgents: unknown[],
+  now: Date = new Date(),
+): StateExport {
+  return {
+    format: EXPORT_FORMAT,
+    version: EXPORT_VERSION,
+    exported_at: now.toISOString(),
+    settings,
+    projects,
+    custom_agents: customAgents,
+  };
+}
+
+/**
+ * Read an exported config, rejecting files that aren't one or come from a
+ * newer format. Projects without an id, name and path are dropped.
+ */
+export function parseStateExport(json: string): StateExport {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(json);
+  } catch {
+    throw new Error('Not a Parallel Code config export: invalid JSON');
+  }
+  if (!isRecord(parsed) || parsed.format !== EXPORT_FORMAT) {
+    throw new Error('Not a Parallel Code config export');
+  }
+  const version = parsed.version;
+  if (typeof version !== 'number' || version > EXPORT_VERSION) {
+    throw new Error(`Config export version ${String(version)} is not supported by this version`);
+  }
+  const projects = Array.isArray(parsed.projects) ? parsed.projects : [];
+  return {
+    format: EXPORT_FORMAT,
+    version,
+    exported_at: typeof parsed.exported_at === 'string' ? parsed.exported_at : '',
+    settings: isRecord(parsed.settings) ? parsed.settings : {},
+    projects: projects.filter(
+      (p): p is Record<string, unknown> =>
+        isRecord(p) &&
+        typeof p.id === 'string' &&
+        typeof p.name === 'string' &&
+        typeof p.path === 'string',
+    ),
+    custom_agents: Array.isArray(parsed.custom_agents) ? parsed.custom_agents : [],
+  };
+}
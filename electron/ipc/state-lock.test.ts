@@ -0,0 +1,16 @@
+import { describe, it, expect } from 'vitest';
+import { isLockStale, LOCK_STALE_MS } from './state-lock.js';
+
+describe('isLockStale', () => {
+  const now = 1_000_000;
+
+  it('is fresh while the holder keeps touching it', () => {
+    expect(isLockStale(now, now)).toBe(false);
+    expect(isLockStale(now - LOCK_STALE_MS, now)).toBe(false);
+  });
+
+  it('is stale once the holder stopped touching it', () => {
+    expect(isLockStale(now - LOCK_STALE_MS - 1, now)).toBe(true);
+    expect(isLockStale(now - 11, now, 10)).toBe(true);
+  });
+});
@@ -0,0 +1,80 @@
+import fs from 'fs';
+import { randomUUID } from 'crypto';
+
+// A lock file next to the saved state naming the instance allowed to write it.
+// The holder touches it every LOCK_HEARTBEAT_MS; a lock left untouched for
+// LOCK_STALE_MS belongs to an instance that crashed or was killed and is taken
+// over. Staleness goes by the file's age, not a pid: after a crash or reboot
+// the OS may have handed the old pid to an unrelated process.
+
+export const LOCK_HEARTBEAT_MS = 5_000;
+export const LOCK_STALE_MS = 30_000;
+
+/** Whether a lock last touched at `mtimeMs` has been abandoned by its holder. */
+export function isLockStale(mtimeMs: number, now: number, staleMs = LOCK_STALE_MS): boolean {
+  return now - mtimeMs > staleMs;
+}
+
+export interface FileLock {
+  /** Stop the heartbeat and remove the lock file if it is still ours. */
+  release(): void;
+}
+
+function tryTake(lockPath: string, token: string): boolean {
+  for (let attempt = 0; attempt < 2; attempt++) {
+    try {
+      fs.writeFileSync(lockPath, token, { flag: 'wx' });
+      return true;
+    } catch (e) {
+      if ((e as NodeJS.ErrnoException).code !== 'EEXIST') throw e;
+    }
+    let mtimeMs: number;
+    try {
+      mtimeMs = fs.statSync(lockPath).mtimeMs;
+    } catch {
+      continue; // removed in the meantime
+    }
+    if (!isLockStale(mtimeMs, Date.now())) return false;
+    fs.rmSync(lockPath, { force: true });
+  }
+  return false;
+}
+
+/**
+ * Take the lock at `lockPath` and keep it fresh, or return null while another
+ * instance holds it. `onLost` runs if the lock is later taken from under us,
+ * e.g. after this process was suspended for longer than LOCK_STALE_MS.
+ */
+export function acquireFileLock(lockPath: string, onLost: () => void): FileLock | null {
+  const token = `${process.pid}:${randomUUID()}`;
+  if (!tryTake(lockPath, token)) return null;
+
+  const held = () => {
+    try {
+      return fs.readFileSync(lockPath, 'utf8') === token;
+    } catch {
+      return false;
+    }
+  };
+  const heartbeat = setInterval(() => {
+    if (!held()) {
+      clearInterval(heartbeat);
+      onLost();
+      return;
+    }
+    const now = new Date();
+    try {
+      fs.utimesSync(lockPath, now, now);
+    } catch (err) {
+      console.warn('Failed to refresh the state lock:', err);
+    }
+  }, LOCK_HEARTBEAT_MS);
+  heartbeat.unref?.();
+
+  return {
+    release() {
+      clearInterval(heartbeat);
+      if (held()) fs.rmSync(lockPath, { force: true });
+    },
+  };
+}
@@ -0,0 +1,55 @@
+import { describe, it, expect } from 'vitest';
+import { migrateState, STATE_SCHEMA_VERSION, type StateMigration } from './state-migrations.js';
+
+describe('migrateState', () => {
+  it('moves a pre-project state onto a project', () => {
+    const { state, from_version, to_version, failed } = migrateState({
+      projectRoot: '/home/me/repo',
+      taskOrder: ['t1'],
+      tasks: { t1: { id: 't1' } },
+    });
+    expect([from_version, to_version, failed]).toEqual([1, STATE_SCHEMA_VERSION, []]);
+    const projects = state.projects as { id: string; name: string; path: string }[];
+    expect(projects).toHaveLength(1);
+    expect(projects[0]).toMatchObject({ name: 'repo', path: '/home/me/repo' });
+    expect(state.lastProjectId).toBe(projects[0].id);
+    expect((state.tasks as Record<string, { projectId: string }>).t1.projectId).toBe(
+      projects[0].id,
+    );
+    expect(state).not.toHaveProperty('projectRoot');
+    expect(state.schema_version).toBe(STATE_SCHEMA_VERSION);
+  });
+
+  it('leaves current and newer states alone', () => {
+    const current = { schema_version: STATE_SCHEMA_VERSION, projects: [] };
+    expect(migrateState(current).state).toEqual(current);
+    const newer = { schema_version: STATE_SCHEMA_VERSION + 1, somethingNew: true };
+    const outcome = migrateState(newer);
+    expect(outcome.state).toEqual(newer);
+    expect(outcome.failed).toEqual([]);
+  });
+
+  it('stops at a failing step and lists it and the steps after it', () => {
+    const migrations: StateMigration[] = [
+      { from: 1, description: 'one', migrate: (s) => void (s.one = true) },
+      {
+        from: 2,
+        description: 'two',
+        migrate: (s) => {
+          s.two = true;
+          throw new Error('bad shape');
+        },
+      },
+      { from: 3, description: 'three', migrate: () => {} },
+    ];
+    const original = { keep: 1 };
+    const outcome = migrateState(original, migrations, 4);
+    expect(outcome.to_version).toBe(2);
+    expect(outcome.state).toEqual({ keep: 1, one: true, schema_version: 2 });
+    expect(outcome.failed).toEqual([
+      { from: 2, description: 'two', error: 'bad shape' },
+      { from: 3, description: 'three', error: 'Not run' },
+    ]);
+    expect(original).toEqual({ keep: 1 });
+  });
+});
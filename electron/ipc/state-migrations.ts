@@ -0,0 +1,99 @@
+// Upgrades of the saved state's shape, applied on load. Each migration takes
+// the state from `from` to `from + 1`; state without a `schema_version` is
+// version 1, from before versions were recorded.
+
+import { randomUUID } from 'crypto';
+
+export const STATE_SCHEMA_VERSION = 2;
+
+type State = Record<string, unknown>;
+
+export interface StateMigration {
+  from: number;
+  description: string;
+  migrate: (state: State) => void;
+}
+
+export const STATE_MIGRATIONS: StateMigration[] = [
+  {
+    from: 1,
+    description: 'Move the single project root into the project list',
+    migrate: (state) => {
+      const root = state.projectRoot;
+      delete state.projectRoot;
+      const projects = Array.isArray(state.projects) ? state.projects : [];
+      if (projects.length > 0 || typeof root !== 'string' || !root) return;
+      const id = randomUUID();
+      state.projects = [{ id, name: root.split('/').pop() || root, path: root }];
+      state.lastProjectId = id;
+      const tasks = (state.tasks ?? {}) as Record<string, { projectId?: string } | null>;
+      for (const task of Object.values(tasks)) {
+        if (task && !task.projectId) task.projectId = id;
+      }
+    },
+  },
+];
+
+export interface MigrationFailure {
+  from: number;
+  description: string;
+  error: string;
+}
+
+export interface MigrationOutcome {
+  state: State;
+  /** The version the state was saved with. */
+  from_version: number;
+  /** The version it is at now; below the current one if a step failed. */
+  to_version: number;
+  /** The step that failed, then the ones that could not run after it. */
+  failed: MigrationFailure[];
+}
+
+/** The version a saved state was written with. */
+export function stateVersion(state: State): number {
+  const v = state.schema_version;
+  return typeof v === 'number' && Number.isInteger(v) && v >= 1 ? v : 1;
+}
+
+/**
+ * Bring a copy of `state` up to STATE_SCHEMA_VERSION. Each step runs on its
+ * own copy, so a step that throws leaves the state as the previous step left
+ * it; migration stops there, since later steps assume the shape it produces.
+ * State from a newer version is returned unchanged.
+ */
+export function migrateState(
+  state: State,
+  migrations: StateMigration[] = STATE_MIGRATIONS,
+  target = STATE_SCHEMA_VERSION,
+): MigrationOutcome {
+  const from = stateVersion(state);
+  let current = structuredClone(state);
+  let version = from;
+  const failed: MigrationFailure[] = [];
+  while (version < target) {
+    const step = migrations.find((m) => m.from === version);
+    if (!step) {
+      failed.push({ from: version, description: '', error: 'No migration for this version' });
+      break;
+    }
+    try {
+      const draft = structuredClone(current);
+      step.migrate(draft);
+      current = draft;
+    } catch (e) {
+      failed.push({
+        from: version,
+        description: step.description,
+        error: e instanceof Error ? e.message : String(e),
+      });
+      for (const later of migrations.filter((m) => m.from > version && m.from < target)) {
+        failed.push({ from: later.from, description: later.description, error: 'Not run' });
+      }
+      break;
+    }
+    version++;
+  }
+  if (version > from) current.schema_version = version;
+  return { state: current, from_version: from, to_version: version, failed };
+}
@@ -1,7 +1,25 @@
 import { randomUUID } from 'crypto';
-import { createWorktree, removeWorktree } from './git.js';
-import { killAgent, notifyAgentListChanged } from './pty.js';
+import {
+  copyUncommittedChanges,
+  countWorktreeChanges,
+  createWorktree,
+  getAllFileDiffsFromBranch,
+  getBranchLog,
+  getHeadCommit,
+  getMainBranch,
+  moveUncommittedChanges,
+  removeWorktree,
+  resolveTaskBranch,
+  taskWorktreePath,
+} from './git.js';
+import { getAgentMeta, killAgent, notifyAgentListChanged, type EventSink } from './pty.js';
+import { cancelQueuedSpawn } from './agent-queue.js';
+import { runHeadless } from './headless-exec.js';
+import { buildCommitMessagePrompt, cleanCommitMessage } from './commit-message.js';
 import { stopPlanWatcher } from './plans.js';
+import { clearPromptQueue } from './prompt-queue.js';
+import { removeSessionLogs } from './session-log.js';
+import { removeRecordings } from './recording.js';
 
 const MAX_SLUG_LEN = 72;
 
@@ -29,40 +47,350 @@ function sanitizeBranchPrefix(prefix: string): string {
   return parts.length === 0 ? 'task' : parts.join('/');
 }
 
+/** Another repository a multi-repo task gets a worktree in, with that project's settings. */
+export interface LinkedRepoSpec {
+  projectRoot: string;
+  symlinkDirs: string[];
+  copyPaths: string[];
+  clonePaths: string[];
+  hardlinkPaths: string[];
+  worktreeBaseDir: string | null;
+}
+
+/** A worktree a multi-repo task got in one of its other repositories. */
+export interface LinkedWorktreeResult {
+  project_root: string;
+  path: string;
+  branch_name: string;
+}
+
 export async function createTask(
   name: string,
   projectRoot: string,
   symlinkDirs: string[],
   branchPrefix: string,
-): Promise<{ id: string; branch_name: string; worktree_path: string }> {
+  copyPaths: string[] = [],
+  clonePaths: string[] = [],
+  /** Move the project root's uncommitted changes into the new worktree. */
+  carryRootChanges = false,
+  /** Existing local/remote branch or fetchable ref to check out instead of branching from HEAD. */
+  fromBranch: string | null = null,
+  /** Project's worktree directory setting; null keeps `<repo>/.worktrees`. */
+  worktreeBaseDir: string | null = null,
+  hardlinkPaths: string[] = [],
+  /** Further repositories to create a worktree in, on the same branch name where it is free. */
+  linkedRepos: LinkedRepoSpec[] = [],
+): Promise<{
+  id: string;
+  branch_name: string;
+  worktree_path: string;
+  /** Worktrees in `linkedRepos`, in the same order. */
+  linked_worktrees: LinkedWorktreeResult[];
+  /**
+   * False when the task checked out an existing branch (adopted, or started
+   * from a branch or PR), which closing or merging it must not delete.
+   */
+  owns_branch: boolean;
+}> {
   const id = randomUUID();
   const prefix = sanitizeBranchPrefix(branchPrefix);
-  const branchName = `${prefix}/${slug(name)}-${id.slice(0, 6)}`;
-  const worktree = await createWorktree(projectRoot, branchName, symlinkDirs);
+  let branchName = `${prefix}/${slug(name)}-${id.slice(0, 6)}`;
+  if (fromBranch) branchName = await resolveTaskBranch(projectRoot, fromBranch, branchName);
+  const worktree = await createWorktree(
+    projectRoot,
+    branchName,
+    symlinkDirs,
+    false,
+    copyPaths,
+    clonePaths,
+    fromBranch !== null,
+    worktreeBaseDir,
+    hardlinkPaths,
+  );
+  if (carryRootChanges) {
+    try {
+      await moveUncommittedChanges(projectRoot, worktree.path);
+    } catch (e) {
+      // Never delete a branch the task didn't create
+      await removeWorktree(projectRoot, worktree.branch, !fromBranch).catch((removeErr) =>
+        console.warn('Failed to remove worktree after a failed carry:', removeErr),
+      );
+      throw e;
+    }
+  }
+
+  const linkedWorktrees: LinkedWorktreeResult[] = [];
+  for (const repo of linkedRepos) {
+    try {
+      const linked = await createWorktree(
+        repo.projectRoot,
+        worktree.branch,
+        repo.symlinkDirs,
+        false,
+        repo.copyPaths,
+        repo.clonePaths,
+        false,
+        repo.worktreeBaseDir,
+        repo.hardlinkPaths,
+      );
+      // The name may be taken in that repository, in which case it got a suffixed one
+      linkedWorktrees.push({
+        project_root: repo.projectRoot,
+        path: linked.path,
+        branch_name: linked.branch,
+      });
+    } catch (e) {
+      // All or nothing: a half-created multi-repo task has no owner to clean it up
+      for (const created of linkedWorktrees) {
+        await removeWorktree(created.project_root, created.branch_name, true).catch(console.warn);
+      }
+      await removeWorktree(projectRoot, worktree.branch, !fromBranch).catch(console.warn);
+      throw new Error(`Creating the worktree in ${repo.projectRoot} failed: ${e}`);
+    }
+  }
+
+  return {
+    id,
+    branch_name: worktree.branch,
+    worktree_path: worktree.path,
+    linked_worktrees: linkedWorktrees,
+    owns_branch: !fromBranch,
+  };
+}
+
+export interface DuplicateTaskOpts {
+  name: string;
+  projectRoot: string;
+  /** Worktree of the task being duplicated. */
+  sourcePath: string;
+  symlinkDirs: string[];
+  branchPrefix: string;
+  copyPaths: string[];
+  clonePaths: string[];
+  hardlinkPaths: string[];
+  worktreeBaseDir: string | null;
+  /** Start from the main branch instead of the source task's current HEAD. */
+  fromMain: boolean;
+  /** Copy the source's uncommitted changes into the new worktree. */
+  copyUncommitted: boolean;
+}
+
+/**
+ * Create a task on a new branch from another task's current HEAD (or from
+ * main), optionally with a copy of its uncommitted changes. The source
+ * worktree and its agents are not touched.
+ */
+export async function duplicateTask(opts: DuplicateTaskOpts): Promise<{
+  id: string;
+  branch_name: string;
+  worktree_path: string;
+  linked_worktrees: LinkedWorktreeResult[];
+  owns_branch: boolean;
+  /** Copied files left with conflict markers, e.g. when starting from main. */
+  conflicted_files: string[];
+}> {
+  const id = randomUUID();
+  const prefix = sanitizeBranchPrefix(opts.branchPrefix);
+  const startPoint = opts.fromMain
+    ? await getMainBranch(opts.projectRoot)
+    : await getHeadCommit(opts.sourcePath);
+  const worktree = await createWorktree(
+    opts.projectRoot,
+    `${prefix}/${slug(opts.name)}-${id.slice(0, 6)}`,
+    opts.symlinkDirs,
+    false,
+    opts.copyPaths,
+    opts.clonePaths,
+    false,
+    opts.worktreeBaseDir,
+    opts.hardlinkPaths,
+    startPoint,
+  );
+  let conflicted: string[] = [];
+  if (opts.copyUncommitted) {
+    try {
+      conflicted = (await copyUncommittedChanges(opts.sourcePath, worktree.path))
+        .conflicted_files;
+    } catch (e) {
+      await removeWorktree(opts.projectRoot, worktree.branch, true).catch(console.warn);
+      throw e;
+    }
+  }
   return {
     id,
     branch_name: worktree.branch,
     worktree_path: worktree.path,
+    linked_worktrees: [],
+    owns_branch: true,
+    conflicted_files: conflicted,
   };
 }
 
+export interface BatchTaskResult {
+  name: string;
+  task: Awaited<ReturnType<typeof createTask>> | null;
+  error: string | null;
+}
+
+/**
+ * Create one task per name with shared worktree settings. Worktrees are made
+ * one at a time (each behind the repo's worktree lock); a failed item is
+ * reported and the rest still get created.
+ */
+export async function createTaskBatch(
+  names: string[],
+  projectRoot: string,
+  symlinkDirs: string[],
+  branchPrefix: string,
+  copyPaths: string[] = [],
+  clonePaths: string[] = [],
+  worktreeBaseDir: string | null = null,
+  hardlinkPaths: string[] = [],
+  linkedRepos: LinkedRepoSpec[] = [],
+): Promise<BatchTaskResult[]> {
+  const results: BatchTaskResult[] = [];
+  for (const name of names) {
+    try {
+      const task = await createTask(
+        name,
+        projectRoot,
+        symlinkDirs,
+        branchPrefix,
+        copyPaths,
+        clonePaths,
+        false,
+        null,
+        worktreeBaseDir,
+        hardlinkPaths,
+        linkedRepos,
+      );
+      results.push({ name, task, error: null });
+    } catch (e) {
+      results.push({ name, task: null, error: e instanceof Error ? e.message : String(e) });
+    }
+  }
+  return results;
+}
+
+/** Another repository a multi-repo task has a worktree in, and the branch it got there. */
+export interface LinkedBranch {
+  projectRoot: string;
+  branchName: string;
+}
+
 interface DeleteTaskOpts {
   taskId?: string;
   agentIds: string[];
   branchName: string;
   deleteBranch: boolean;
   projectRoot: string;
+  linked?: LinkedBranch[];
+  permanent?: boolean;
+  force?: boolean;
+}
+
+export interface DeleteTaskPreview {
+  unmerged_commits: number; // only counted when the branch would be deleted
+  uncommitted_files: number;
+  running_agents: string[];
 }
 
-export async function deleteTask(opts: DeleteTaskOpts): Promise<void> {
+/** Report what deleting a task would destroy, without touching anything. */
+export async function previewDeleteTask(
+  opts: Pick<
+    DeleteTaskOpts,
+    'agentIds' | 'branchName' | 'deleteBranch' | 'projectRoot' | 'linked'
+  >,
+): Promise<DeleteTaskPreview> {
+  const branches = [
+    { projectRoot: opts.projectRoot, branchName: opts.branchName },
+    ...(opts.linked ?? []),
+  ];
+  const changes = await Promise.all(
+    branches.map(async (b) =>
+      countWorktreeChanges(await taskWorktreePath(b.projectRoot, b.branchName)),
+    ),
+  );
+  return {
+    unmerged_commits: opts.deleteBranch
+      ? changes.reduce((sum, c) => sum + c.unmerged_commits, 0)
+      : 0,
+    uncommitted_files: changes.reduce((sum, c) => sum + c.uncommitted_files, 0),
+    running_agents: opts.agentIds.filter((id) => getAgentMeta(id) !== null),
+  };
+}
+
+export async function deleteTask(opts: DeleteTaskOpts): Promise<{ trashed_path: string | null }> {
+  if (!opts.force) {
+    const preview = await previewDeleteTask(opts);
+    const reasons: string[] = [];
+    if (preview.unmerged_commits > 0)
+      reasons.push(`${preview.unmerged_commits} unmerged commit(s)`);
+    if (preview.uncommitted_files > 0)
+      reasons.push(`${preview.uncommitted_files} uncommitted file(s)`);
+    if (preview.running_agents.length > 0)
+      reasons.push(`${preview.running_agents.length} running agent(s)`);
+    if (reasons.length > 0) {
+      throw new Error(`Refusing to delete task with ${reasons.join(', ')}; confirm to force`);
+    }
+  }
   if (opts.taskId) stopPlanWatcher(opts.taskId);
   for (const agentId of opts.agentIds) {
+    clearPromptQueue(agentId);
+    cancelQueuedSpawn(agentId);
     try {
       killAgent(agentId);
     } catch {
       /* already dead */
     }
   }
-  await removeWorktree(opts.projectRoot, opts.branchName, opts.deleteBranch);
+  const result = await removeWorktree(
+    opts.projectRoot,
+    opts.branchName,
+    opts.deleteBranch,
+    !opts.permanent,
+  );
+  for (const l of opts.linked ?? []) {
+    await removeWorktree(l.projectRoot, l.branchName, opts.deleteBranch, !opts.permanent);
+  }
+  if (opts.taskId) removeSessionLogs(opts.taskId);
+  for (const agentId of opts.agentIds) removeRecordings(agentId);
   notifyAgentListChanged();
+  return result;
+}
+
+const COMMIT_MESSAGE_TIMEOUT_MS = 3 * 60_000;
+
+/**
+ * Ask an agent, run headless in the task's worktree, for a conventional
+ * commit message covering the branch's changes since main.
+ */
+export async function generateCommitMessage(
+  win: EventSink,
+  projectRoot: string,
+  branchName: string,
+  agent: { id: string; command: string; env?: Record<string, string> },
+): Promise<string> {
+  const worktreePath = await taskWorktreePath(projectRoot, branchName);
+  const [log, diff] = await Promise.all([
+    getBranchLog(worktreePath),
+    getAllFileDiffsFromBranch(projectRoot, branchName),
+  ]);
+  if (!diff.trim()) throw new Error('The branch has no committed changes to describe');
+
+  const run = await runHeadless(win, {
+    runId: randomUUID(),
+    agentDefId: agent.id,
+    command: agent.command,
+    prompt: buildCommitMessagePrompt(log, diff),
+    cwd: worktreePath,
+    env: agent.env,
+    timeoutMs: COMMIT_MESSAGE_TIMEOUT_MS,
+  });
+  const message = cleanCommitMessage(run.result);
+  if (run.is_error || !message) {
+    const detail = run.stderr.trim().split('\n').slice(-3).join('\n');
+    throw new Error(`The agent did not return a commit message${detail ? `: ${detail}` : ''}`);
+  }
+  return message;
 }
@@ -0,0 +1,31 @@
+import { describe, it, expect } from 'vitest';
+import { parseUsageLimitReset } from './usage-limit.js';
+
+// Local time, so the expectations hold in any timezone
+const now = new Date(2026, 0, 10, 22, 30);
+
+describe('parseUsageLimitReset', () => {
+  it('reads a Claude Code reset time as its next occurrence', () => {
+    expect(parseUsageLimitReset('5-hour limit reached ∙ resets 2am', now)).toBe(
+      new Date(2026, 0, 11, 2, 0).getTime(),
+    );
+    expect(
+      parseUsageLimitReset(
+        'Claude usage limit reached. Your limit will reset at 11:15pm (Europe/Berlin).',
+        now,
+      ),
+    ).toBe(new Date(2026, 0, 10, 23, 15).getTime());
+  });
+
+  it('reads a relative wait', () => {
+    expect(
+      parseUsageLimitReset("You've hit your usage limit. Try again in 2 hours 5 minutes.", now),
+    ).toBe(now.getTime() + 125 * 60_000);
+  });
+
+  it('ignores output that is not a limit notice or has no usable time', () => {
+    expect(parseUsageLimitReset('The cache resets at 2am', now)).toBeNull();
+    expect(parseUsageLimitReset('Rate limit reached, resets 3', now)).toBeNull();
+    expect(parseUsageLimitReset('\x1b[1mlimit reached\x1b[0m resets 13pm', now)).toBeNull();
+  });
+});
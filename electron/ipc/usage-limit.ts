@@ -0,0 +1,49 @@
+// Detection of "usage limit reached" notices in agent output, so a task can
+// be scheduled to start once the limit window resets.
+
+import { stripAnsi } from './prompt-detect.js';
+
+/** Only the end of the output is considered, like prompt detection. */
+const DETECT_TAIL_CHARS = 600;
+
+// Words may run together after stripping TUI cursor moves, so spaces are optional.
+const LIMIT_RE = /limit\s*(?:reached|hit)|hit\s*your\s*(?:usage\s*)?limit/i;
+/** Claude Code: "resets 2am", "Your limit will reset at 5pm (Europe/Berlin)". */
+const RESET_AT_RE = /\bresets?\s*(?:at\s*)?(\d{1,2})(?::(\d{2}))?\s*(am|pm)?/i;
+/** Codex: "Try again in 2 hours 15 minutes". */
+const TRY_AGAIN_RE =
+  /try\s*again\s*in\s*(?:(\d+)\s*h(?:ours?|rs?)?)?[\s,]*(?:(\d+)\s*m(?:in(?:ute)?s?)?)?/i;
+
+/**
+ * When the usage limit reported at the end of `rawTail` resets, as epoch ms.
+ * Clock times are read as local time and resolve to their next occurrence
+ * after `now`; the timezone an agent prints in parentheses is ignored.
+ */
+export function parseUsageLimitReset(rawTail: string, now: Date): number | null {
+  const text = stripAnsi(rawTail).slice(-DETECT_TAIL_CHARS);
+  if (!LIMIT_RE.test(text)) return null;
+
+  const at = RESET_AT_RE.exec(text);
+  // A bare number without minutes or am/pm is too ambiguous to schedule on
+  if (at && (at[2] !== undefined || at[3] !== undefined)) {
+    let hour = Number(at[1]);
+    const minute = at[2] !== undefined ? Number(at[2]) : 0;
+    const meridiem = at[3]?.toLowerCase();
+    if (meridiem) {
+      if (hour < 1 || hour > 12) return null;
+      hour = (hour % 12) + (meridiem === 'pm' ? 12 : 0);
+    }
+    if (hour > 23 || minute > 59) return null;
+    const reset = new Date(now);
+    reset.setHours(hour, minute, 0, 0);
+    if (reset.getTime() <= now.getTime()) reset.setDate(reset.getDate() + 1);
+    return reset.getTime();
+  }
+
+  const wait = TRY_AGAIN_RE.exec(text);
+  if (wait && (wait[1] !== undefined || wait[2] !== undefined)) {
+    const minutes = Number(wait[1] ?? 0) * 60 + Number(wait[2] ?? 0);
+    return now.getTime() + minutes * 60_000;
+  }
+  return null;
+}
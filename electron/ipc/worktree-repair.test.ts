@@ -0,0 +1,62 @@
+import { describe, it, expect } from 'vitest';
+import { findLeftovers, isInsideDir, parsePrunableWorktrees } from './worktree-repair.js';
+
+describe('parsePrunableWorktrees', () => {
+  it('returns only worktrees git marks prunable', () => {
+    const porcelain = [
+      'worktree /repo',
+      'HEAD 1111111111111111111111111111111111111111',
+      'branch refs/heads/main',
+      '',
+      'worktree /repo/.worktrees/task/a-123',
+      'HEAD 2222222222222222222222222222222222222222',
+      'branch refs/heads/task/a-123',
+      'prunable gitdir file points to non-existent location',
+      '',
+      'worktree /repo/.worktrees/task/b-456',
+      'HEAD 3333333333333333333333333333333333333333',
+      'branch refs/heads/task/b-456',
+      '',
+      'worktree /tmp/detached',
+      'HEAD 4444444444444444444444444444444444444444',
+      'detached',
+      'prunable',
+      '',
+    ].join('\n');
+    expect(parsePrunableWorktrees(porcelain)).toEqual([
+      { path: '/repo/.worktrees/task/a-123', branch: 'task/a-123' },
+      { path: '/tmp/detached', branch: null },
+    ]);
+  });
+});
+
+describe('isInsideDir', () => {
+  it('accepts strict descendants only', () => {
+    expect(isInsideDir('/repo/.worktrees/task/a', '/repo/.worktrees')).toBe(true);
+    expect(isInsideDir('/repo/.worktrees', '/repo/.worktrees')).toBe(false);
+    expect(isInsideDir('/repo/.worktrees-old/a', '/repo/.worktrees')).toBe(false);
+    expect(isInsideDir('/repo/.worktrees/../src', '/repo/.worktrees')).toBe(false);
+  });
+});
+
+describe('findLeftovers', () => {
+  it('pairs unknown task branches with their worktree and adds orphaned dirs', () => {
+    const worktrees = [
+      { path: '/repo/.worktrees/task/a', branch: 'task/a' },
+      { path: '/repo/.worktrees/task/b', branch: 'task/b' },
+      { path: '/repo/.worktrees/scratch', branch: 'scratch' },
+    ];
+    expect(
+      findLeftovers(
+        ['task/a', 'task/b', 'task/old'],
+        worktrees,
+        ['/repo/.worktrees/task/crashed'],
+        new Set(['task/a']),
+      ),
+    ).toEqual([
+      { branch: 'task/b', path: '/repo/.worktrees/task/b' },
+      { branch: 'task/old', path: null },
+      { branch: null, path: '/repo/.worktrees/task/crashed' },
+    ]);
+  });
+});
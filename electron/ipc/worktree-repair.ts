@@ -0,0 +1,115 @@
+import fs from 'fs';
+import path from 'path';
+
+// Branch names nest (`task/x/y`), so worktrees sit a few levels deep
+const MAX_SCAN_DEPTH = 6;
+
+export interface PrunableWorktree {
+  path: string;
+  branch: string | null;
+}
+
+/** Worktrees `git worktree list --porcelain` marks prunable: their directory is gone. */
+export function parsePrunableWorktrees(porcelain: string): PrunableWorktree[] {
+  const result: PrunableWorktree[] = [];
+  for (const block of porcelain.split('\n\n')) {
+    const lines = block.split('\n');
+    const wtPath = lines.find((l) => l.startsWith('worktree '))?.slice('worktree '.length);
+    if (!wtPath || !lines.some((l) => l === 'prunable' || l.startsWith('prunable '))) continue;
+    const ref = lines.find((l) => l.startsWith('branch '))?.slice('branch '.length);
+    result.push({
+      path: wtPath,
+      branch: ref?.startsWith('refs/heads/') ? ref.slice('refs/heads/'.length) : null,
+    });
+  }
+  return result;
+}
+
+export function isInsideDir(child: string, parent: string): boolean {
+  const relative = path.relative(parent, child);
+  return relative !== '' && !relative.startsWith('..') && !path.isAbsolute(relative);
+}
+
+/** Where a worktree's `.git` file points: its admin dir under `<common dir>/worktrees/`. */
+function readGitdirLink(dir: string): string | null {
+  try {
+    const m = /^gitdir: (.+)$/m.exec(fs.readFileSync(path.join(dir, '.git'), 'utf8'));
+    return m ? path.resolve(dir, m[1].trim()) : null;
+  } catch {
+    return null; // a .git directory (a clone), or no .git at all
+  }
+}
+
+export interface WorktreeFolderScan {
+  /** Directories whose `.git` file links to this repo, registered or not. */
+  linked: string[];
+  /** Directories with no worktree anywhere inside, e.g. left behind by a crash. */
+  strays: string[];
+}
+
+/**
+ * Walk a worktree folder. Anything with a `.git` entry is a worktree (of this
+ * repo when it links into `adminDir`) and is not descended into; any other
+ * subtree without a worktree in it is a stray.
+ */
+export async function scanWorktreeFolder(
+  base: string,
+  adminDir: string,
+): Promise<WorktreeFolderScan> {
+  const linked: string[] = [];
+  const strays: string[] = [];
+
+  // Whether `dir` holds a worktree; strays are reported as the largest empty subtree
+  const walk = async (dir: string, depth: number): Promise<boolean> => {
+    let entries: fs.Dirent[];
+    try {
+      entries = await fs.promises.readdir(dir, { withFileTypes: true });
+    } catch {
+      return true; // unreadable: leave it alone
+    }
+    if (entries.some((e) => e.name === '.git')) {
+      const link = readGitdirLink(dir);
+      if (link && isInsideDir(link, adminDir)) linked.push(dir);
+      return true;
+    }
+    if (depth >= MAX_SCAN_DEPTH) return true;
+    const subdirs = entries.filter((e) => e.isDirectory()).map((e) => path.join(dir, e.name));
+    const holds = await Promise.all(subdirs.map((d) => walk(d, depth + 1)));
+    if (depth > 0 && !holds.some(Boolean)) return false;
+    subdirs.forEach((d, i) => {
+      if (!holds[i]) strays.push(d);
+    });
+    return true;
+  };
+
+  if (fs.existsSync(base)) await walk(base, 0);
+  return { linked, strays };
+}
+
+export interface TaskLeftover {
+  /** Task branch, or null for a directory that holds no branch. */
+  branch: string | null;
+  /** Worktree the branch is checked out in, or a stray directory; null for a bare branch. */
+  path: string | null;
+}
+
+/**
+ * Task branches and worktree directories no known task accounts for. A branch
+ * and the worktree it's checked out in count as one leftover; worktrees on
+ * branches outside the task prefix are left alone.
+ */
+export function findLeftovers(
+  taskBranches: string[],
+  worktrees: { path: string; branch: string | null }[],
+  orphanedDirs: string[],
+  knownBranches: Set<string>,
+): TaskLeftover[] {
+  const leftovers: TaskLeftover[] = taskBranches
+    .filter((b) => !knownBranches.has(b))
+    .map((branch) => ({
+      branch,
+      path: worktrees.find((w) => w.branch === branch)?.path ?? null,
+    }));
+  for (const dir of orphanedDirs) leftovers.push({ branch: null, path: dir });
+  return leftovers;
+}
@@ -0,0 +1,175 @@
+import { execFile } from 'child_process';
+import fs from 'fs';
+import path from 'path';
+import { IPC } from './channels.js';
+import type { EventSink } from './pty.js';
+
+interface WorktreeWatcher {
+  watcher: fs.FSWatcher;
+  /** Non-recursive watcher on the worktree's git directory, once it is known. */
+  gitDirWatcher: fs.FSWatcher | null;
+  /** Renderer subscriptions; the watcher closes when the last one goes away. */
+  refs: number;
+  timeout: ReturnType<typeof setTimeout> | null;
+  /** When the first event of the current burst arrived. */
+  burstStart: number;
+  /** Changed paths since the last report, relative to the worktree. */
+  pending: Set<string>;
+  /** An event came without a filename, so the change can't be ruled out. */
+  unknownChange: boolean;
+}
+
+const watchers = new Map<string, WorktreeWatcher>();
+
+/** Quiet period after the last file event before the change is reported (ms). */
+const CHANGE_DEBOUNCE_MS = 300;
+/** Longest a burst of events (e.g. a build) delays the report (ms). */
+const CHANGE_MAX_WAIT_MS = 2_000;
+
+/** Top-level directories whose churn doesn't affect the diff. */
+const IGNORED_ROOTS = new Set(['.git', 'node_modules']);
+
+function isIgnored(filename: string): boolean {
+  const root = filename.split(/[\\/]/, 1)[0];
+  return IGNORED_ROOTS.has(root);
+}
+
+/** The paths git doesn't ignore; all of them when git can't tell. */
+function unignoredPaths(worktreePath: string, paths: string[]): Promise<string[]> {
+  return new Promise((resolve) => {
+    const proc = execFile(
+      'git',
+      ['check-ignore', '--stdin', '-z'],
+      { cwd: worktreePath, timeout: 5_000, maxBuffer: 10 * 1024 * 1024 },
+      (err, stdout) => {
+        // Exit code 1 means none of them is ignored
+        if (err && (err as { code?: unknown }).code !== 1) return resolve(paths);
+        const ignored = new Set(stdout.split('\0'));
+        resolve(paths.filter((p) => !ignored.has(p)));
+      },
+    );
+    proc.stdin?.end(paths.join('\0'));
+  });
+}
+
+async function reportChange(
+  win: EventSink,
+  worktreePath: string,
+  entry: WorktreeWatcher,
+): Promise<void> {
+  entry.timeout = null;
+  const paths = [...entry.pending];
+  const unknownChange = entry.unknownChange;
+  entry.pending.clear();
+  entry.unknownChange = false;
+  if (!unknownChange && (await unignoredPaths(worktreePath, paths)).length === 0) return;
+  if (watchers.get(worktreePath) !== entry || win.isDestroyed()) return;
+  win.webContents.send(IPC.WorktreeChanged, { worktreePath });
+}
+
+/** Note a changed path (null when unknown) and (re)schedule the report. */
+function noteChange(win: EventSink, worktreePath: string, filename: string | null): void {
+  const entry = watchers.get(worktreePath);
+  if (!entry) return;
+  if (filename) entry.pending.add(filename);
+  else entry.unknownChange = true;
+  if (entry.timeout) clearTimeout(entry.timeout);
+  else entry.burstStart = Date.now();
+  const maxWait = entry.burstStart + CHANGE_MAX_WAIT_MS - Date.now();
+  entry.timeout = setTimeout(
+    () => void reportChange(win, worktreePath, entry),
+    Math.max(0, Math.min(CHANGE_DEBOUNCE_MS, maxWait)),
+  );
+}
+
+/**
+ * Watch the worktree's git directory too, non-recursively: commits, checkouts
+ * and resets rewrite HEAD and the index there without touching the files.
+ */
+function watchGitDir(win: EventSink, worktreePath: string, entry: WorktreeWatcher): void {
+  execFile(
+    'git',
+    ['rev-parse', '--absolute-git-dir'],
+    { cwd: worktreePath, timeout: 5_000 },
+    (err, stdout) => {
+      if (err || watchers.get(worktreePath) !== entry) return;
+      const gitDir = stdout.trim();
+      try {
+        entry.gitDirWatcher = fs.watch(gitDir, (_event, filename) => {
+          if (filename?.endsWith('.lock')) return;
+          noteChange(win, worktreePath, null);
+        });
+      } catch (watchErr) {
+        console.warn(`Failed to watch git directory ${gitDir}:`, watchErr);
+        return;
+      }
+      entry.gitDirWatcher.on('error', (watchErr) => {
+        console.warn(`Git directory watcher error for ${worktreePath}:`, watchErr);
+        entry.gitDirWatcher?.close();
+        entry.gitDirWatcher = null;
+      });
+    },
+  );
+}
+
+/**
+ * Watch a worktree recursively and send `WorktreeChanged` once file events
+ * settle, unless git ignores everything that changed (build output, caches).
+ * Changes in its git directory (HEAD, index) are always reported.
+ * Returns false when the platform can't watch it, so the caller keeps
+ * polling; a watcher that fails later sends `WorktreeWatchFailed`.
+ */
+export function watchWorktree(win: EventSink, worktreePath: string): boolean {
+  const existing = watchers.get(worktreePath);
+  if (existing) {
+    existing.refs++;
+    return true;
+  }
+
+  let watcher: fs.FSWatcher;
+  try {
+    watcher = fs.watch(worktreePath, { recursive: true }, (_event, filename) => {
+      if (filename && isIgnored(filename)) return;
+      noteChange(win, worktreePath, filename ? filename.split(path.sep).join('/') : null);
+    });
+  } catch (err) {
+    console.warn(`Failed to watch worktree ${worktreePath}:`, err);
+    return false;
+  }
+  watcher.on('error', (err) => {
+    console.warn(`Worktree watcher error for ${worktreePath}:`, err);
+    closeWatcher(worktreePath);
+    if (!win.isDestroyed()) win.webContents.send(IPC.WorktreeWatchFailed, { worktreePath });
+  });
+  const entry: WorktreeWatcher = {
+    watcher,
+    gitDirWatcher: null,
+    refs: 1,
+    timeout: null,
+    burstStart: 0,
+    pending: new Set(),
+    unknownChange: false,
+  };
+  watchers.set(worktreePath, entry);
+  watchGitDir(win, worktreePath, entry);
+  return true;
+}
+
+function closeWatcher(worktreePath: string): void {
+  const entry = watchers.get(worktreePath);
+  if (!entry) return;
+  if (entry.timeout) clearTimeout(entry.timeout);
+  entry.watcher.close();
+  entry.gitDirWatcher?.close();
+  watchers.delete(worktreePath);
+}
+
+export function unwatchWorktree(worktreePath: string): void {
+  const entry = watchers.get(worktreePath);
+  if (!entry) return;
+  if (--entry.refs <= 0) closeWatcher(worktreePath);
+}
+
+export function stopAllWorktreeWatchers(): void {
+  for (const worktreePath of [...watchers.keys()]) closeWatcher(worktreePath);
+}
@@ -6,7 +6,12 @@ import { execFileSync } from 'child_process';
 import { registerAllHandlers } from './ipc/register.js';
 import { killAllAgents } from './ipc/pty.js';
 import { stopAllPlanWatchers } from './ipc/plans.js';
+import { stopAllAutoFetchers } from './ipc/auto-fetch.js';
+import { stopAllWorktreeWatchers } from './ipc/worktree-watcher.js';
+import { killAllJobs } from './ipc/jobs.js';
+import { flushStateWrites, acquireStateLock, releaseStateLock } from './ipc/persistence.js';
 import { IPC } from './ipc/channels.js';
+import { startHeadless } from './headless.js';
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
@@ -63,8 +68,32 @@ function verifyPreloadAllowlist(): void {
 
 if (!app.isPackaged) verifyPreloadAllowlist();
 
+// Headless mode: `--headless [--port=7777] [--host=0.0.0.0]`, token from
+// PARALLEL_CODE_TOKEN (random if unset). No window is created.
+function getFlag(name: string): string | undefined {
+  const prefix = `--${name}=`;
+  return process.argv.find((a) => a.startsWith(prefix))?.slice(prefix.length);
+}
+
+const headless = process.argv.includes('--headless') || process.env.PARALLEL_CODE_HEADLESS === '1';
+if (headless) app.disableHardwareAcceleration();
+
 let mainWindow: BrowserWindow | null = null;
 
+// One window instance at a time: a second launch focuses the running one
+// instead of loading the same saved state and saving over it. Headless mode
+// doesn't touch the saved state and can run next to a window.
+const isDuplicateInstance = !headless && !app.requestSingleInstanceLock();
+if (isDuplicateInstance) {
+  app.quit();
+} else if (!headless) {
+  app.on('second-instance', () => {
+    if (!mainWindow) return;
+    if (mainWindow.isMinimized()) mainWindow.restore();
+    mainWindow.focus();
+  });
+}
+
 function getIconPath(): string | undefined {
   if (process.platform !== 'linux') return undefined;
   if (app.isPackaged) {
@@ -138,11 +167,31 @@ function createWindow() {
   });
 }
 
-app.whenReady().then(createWindow);
+app.whenReady().then(() => {
+  if (isDuplicateInstance) return;
+  if (headless) {
+    startHeadless({
+      port: parseInt(getFlag('port') ?? '', 10) || 7777,
+      host: getFlag('host'),
+      token: process.env.PARALLEL_CODE_TOKEN,
+    });
+  } else {
+    // Another build sharing the state dir (e.g. a portable copy) isn't covered
+    // by the single-instance lock; it loads the state but holds its saves back
+    // until the other instance lets go.
+    acquireStateLock();
+    createWindow();
+  }
+});
 
 app.on('before-quit', () => {
   killAllAgents();
   stopAllPlanWatchers();
+  stopAllAutoFetchers();
+  stopAllWorktreeWatchers();
+  killAllJobs();
+  flushStateWrites();
+  releaseStateLock();
 });
 
 app.on('window-all-closed', () => {
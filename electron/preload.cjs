@@ -6,38 +6,152 @@ const { contextBridge, ipcRenderer } = require('electron');
 const ALLOWED_CHANNELS = new Set([
   // Agent/PTY
   'spawn_agent',
+  'spawn_shell',
   'write_to_agent',
+  'paste_to_agent',
   'resize_agent',
   'pause_agent',
   'resume_agent',
   'kill_agent',
+  'terminate_agent',
+  'send_signal',
+  'set_agent_priority',
+  'paste_image',
+  'get_session_log',
+  'export_session_recording',
+  'run_headless',
+  'cancel_headless',
   'count_running_agents',
   'kill_all_agents',
   'list_agents',
+  'add_agent',
+  'update_agent',
+  'remove_agent',
+  'agent_supervision',
+  'get_auto_response_log',
+  // Prompt queue
+  'queue_prompt',
+  'list_queued_prompts',
+  'remove_queued_prompt',
+  'reorder_queued_prompt',
+  'send_next_queued_prompt',
+  'prompt_queue_changed',
+  'set_agent_concurrency_limit',
+  'start_queued_agent',
+  'agent_queue_changed',
+  'schedule_task_start',
+  'cancel_scheduled_task_start',
+  'scheduled_task_start_fired',
+  'usage_limit_detected',
   // Task
   'create_task',
+  'create_task_batch',
+  'duplicate_task',
+  'archive_task',
+  'list_archived_tasks',
+  'restore_archived_task',
   'delete_task',
+  'preview_delete_task',
+  'detect_setup_command',
+  // Jobs
+  'run_script',
+  'cancel_job',
+  'list_jobs',
+  'get_job_output',
+  'remove_task_jobs',
+  'open_preview',
+  'job_changed',
+  'job_output',
   // Git
   'get_changed_files',
   'get_changed_files_from_branch',
   'get_file_diff',
+  'get_file_diff_range',
+  'get_file_at_revision',
+  'get_file_diff_structured',
+  'get_all_file_diffs_structured',
   'get_file_diff_from_branch',
   'get_all_file_diffs',
   'get_all_file_diffs_from_branch',
+  'get_merge_preview',
   'get_gitignored_dirs',
+  'scan_ignored_entries',
   'get_worktree_status',
+  'get_all_worktree_statuses',
   'commit_all',
+  'commit_worktree',
+  'stage_file',
+  'unstage_file',
+  'discard_file_changes',
+  'get_unstaged_hunks',
+  'stage_hunks',
   'discard_uncommitted',
+  'stash_changes',
+  'list_stashes',
+  'pop_stash',
+  'transfer_changes',
+  'apply_patch',
   'check_merge_status',
+  'create_integration_branch',
+  'check_cross_task_conflicts',
+  'compare_worktrees',
+  'get_worktree_cross_diff',
+  'get_conflict_details',
+  'resolve_conflict',
+  'continue_merge',
+  'abort_merge',
+  'continue_rebase',
+  'abort_rebase',
+  'verify_conflict_resolution',
   'merge_task',
+  'list_branches',
+  'discover_worktrees',
+  'repair_worktrees',
+  'remove_worktree_orphans',
+  'find_task_leftovers',
+  'remove_task_leftovers',
   'get_branch_log',
+  'generate_commit_message',
+  'get_commit_list',
+  'get_commit_diff',
+  'get_blame',
+  'create_snapshot',
+  'list_snapshots',
+  'restore_snapshot',
+  'delete_snapshot',
+  'get_undoable_operation',
+  'undo_last_operation',
+  'export_task',
   'push_task',
+  'delete_remote_branch',
+  'list_remotes',
+  'create_pull_request',
+  'detect_forge',
+  'list_forge_token_hosts',
+  'set_forge_token',
+  'get_ci_status',
   'rebase_task',
+  'pull_main',
+  'start_auto_fetch',
+  'stop_auto_fetch',
+  'worktree_status_changed',
+  'watch_worktree',
+  'unwatch_worktree',
+  'worktree_changed',
+  'worktree_watch_failed',
   'get_main_branch',
   'get_current_branch',
   // Persistence
-  'save_app_state',
+  'save_settings',
+  'save_project',
+  'save_task',
   'load_app_state',
+  'state_save_conflict',
+  'resolve_state_conflict',
+  'state_read_only_changed',
+  'export_state',
+  'import_state',
+  'import_agents',
   // Window
   '__window_is_focused',
   '__window_is_maximized',
@@ -74,7 +74,7 @@ function buildAgentList(
     const meta = getAgentMeta(agentId);
     if (!meta) continue;
     // Skip shell/sub-terminals — mobile should only show the main agent
-    if (meta.isShell) continue;
+    if (meta.kind === 'shell') continue;
     const info = getAgentStatus(agentId);
     const agent: RemoteAgent = {
       agentId,
@@ -93,9 +93,48 @@ function buildAgentList(
   return Array.from(byTask.values());
 }
 
+/** Task orchestration exposed over HTTP in headless mode (absent for the desktop app). */
+export interface RemoteTaskApi {
+  createTask: (body: unknown) => Promise<unknown>;
+  deleteTask: (taskId: string, force: boolean) => Promise<unknown>;
+  /** Run the task's agent once in print/exec mode and return its result. */
+  execTask?: (taskId: string, body: unknown) => Promise<unknown>;
+}
+
+const MAX_BODY_BYTES = 64 * 1024;
+
+function readJsonBody(req: IncomingMessage): Promise<unknown> {
+  return new Promise((resolve, reject) => {
+    let size = 0;
+    const chunks: Buffer[] = [];
+    req.on('data', (chunk: Buffer) => {
+      size += chunk.length;
+      if (size > MAX_BODY_BYTES) {
+        reject(new Error('request body too large'));
+        req.destroy();
+        return;
+      }
+      chunks.push(chunk);
+    });
+    req.on('end', () => {
+      try {
+        resolve(chunks.length ? JSON.parse(Buffer.concat(chunks).toString('utf8')) : {});
+      } catch {
+        reject(new Error('invalid JSON body'));
+      }
+    });
+    req.on('error', reject);
+  });
+}
+
 export function startRemoteServer(opts: {
   port: number;
   staticDir: string;
+  /** Fixed token (headless mode); a random one is generated otherwise. */
+  token?: string;
+  /** Interface to bind; defaults to all interfaces. */
+  host?: string;
+  tasks?: RemoteTaskApi;
   getTaskName: (taskId: string) => string;
   getAgentStatus: (agentId: string) => {
     status: 'running' | 'exited';
@@ -103,7 +142,7 @@ export function startRemoteServer(opts: {
     lastLine: string;
   };
 }): RemoteServer {
-  const token = randomBytes(24).toString('base64url');
+  const token = opts.token || randomBytes(24).toString('base64url');
   const ips = getNetworkIps();
 
   const tokenBuf = Buffer.from(token);
@@ -169,6 +208,39 @@ export function startRemoteServer(opts: {
         return;
       }
 
+      const tasks = opts.tasks;
+      if (tasks) {
+        const respond = (promise: Promise<unknown>, status: number) => {
+          promise
+            .then((result) => {
+              res.writeHead(status, { ...SECURITY_HEADERS, 'Content-Type': 'application/json' });
+              res.end(JSON.stringify(result ?? {}));
+            })
+            .catch((err: unknown) => {
+              res.writeHead(400, { ...SECURITY_HEADERS, 'Content-Type': 'application/json' });
+              res.end(JSON.stringify({ error: err instanceof Error ? err.message : String(err) }));
+            });
+        };
+
+        if (url.pathname === '/api/tasks' && req.method === 'POST') {
+          respond(readJsonBody(req).then((body) => tasks.createTask(body)), 201);
+          return;
+        }
+
+        const taskMatch = url.pathname.match(/^\/api\/tasks\/([^/]+)$/);
+        if (taskMatch && req.method === 'DELETE') {
+          respond(tasks.deleteTask(taskMatch[1], url.searchParams.get('force') === 'true'), 200);
+          return;
+        }
+
+        const execMatch = url.pathname.match(/^\/api\/tasks\/([^/]+)\/exec$/);
+        if (execMatch && req.method === 'POST' && tasks.execTask) {
+          const execTask = tasks.execTask;
+          respond(readJsonBody(req).then((body) => execTask(execMatch[1], body)), 200);
+          return;
+        }
+      }
+
       res.writeHead(404, { ...SECURITY_HEADERS, 'Content-Type': 'application/json' });
       res.end(JSON.stringify({ error: 'not found' }));
       return;
@@ -392,7 +464,7 @@ export function startRemoteServer(opts: {
   server.on('error', (err) => {
     console.error('[remote] Server error:', err.message);
   });
-  server.listen(opts.port, '0.0.0.0', () => {
+  server.listen(opts.port, opts.host ?? '0.0.0.0', () => {
     /* bind confirmed */
   });
 
@@ -8,8 +8,10 @@ import { confirm } from './lib/dialog';
 import { Sidebar } from './components/Sidebar';
 import { TilingLayout } from './components/TilingLayout';
 import { NewTaskDialog } from './components/NewTaskDialog';
+import { AdoptWorktreesDialog } from './components/AdoptWorktreesDialog';
 import { HelpDialog } from './components/HelpDialog';
 import { SettingsDialog } from './components/SettingsDialog';
+import { StateConflictDialog } from './components/StateConflictDialog';
 import { WindowTitleBar } from './components/WindowTitleBar';
 import { WindowResizeHandles } from './components/WindowResizeHandles';
 import { theme } from './lib/theme';
@@ -17,6 +19,7 @@ import {
   store,
   loadAgents,
   loadState,
+  startStateEventListener,
   saveState,
   toggleNewTaskDialog,
   toggleSidebar,
@@ -53,6 +56,14 @@ import { isMac, mod } from './lib/platform';
 import { createCtrlWheelZoomHandler } from './lib/wheelZoom';
 import { ArenaOverlay } from './arena/ArenaOverlay';
 import { startDesktopNotificationWatcher } from './store/desktopNotifications';
+import { startPromptQueueListener } from './store/promptQueue';
+import { startAgentQueueListener } from './store/agentQueue';
+import { startTaskScheduler } from './store/dependencies';
+import { startJobListener } from './store/jobs';
+import { startCiPolling } from './store/ci';
+import { startLeftoverCheck } from './store/leftovers';
+import { startAutoFetchSync } from './store/autoFetch';
+import { startAgentSupervisionListener } from './store/agents';
 
 const MIN_WINDOW_DIMENSION = 100;
 
@@ -316,6 +327,15 @@ function App() {
     setupAutosave();
     startTaskStatusPolling();
     const stopNotificationWatcher = startDesktopNotificationWatcher(windowFocused);
+    const stopPromptQueueListener = startPromptQueueListener();
+    const stopAgentQueueListener = startAgentQueueListener();
+    const stopTaskScheduler = startTaskScheduler();
+    const stopJobListener = startJobListener();
+    const stopCiPolling = startCiPolling();
+    const stopLeftoverCheck = startLeftoverCheck();
+    const stopStateEventListener = startStateEventListener();
+    const stopAutoFetchSync = startAutoFetchSync();
+    const stopSupervisionListener = startAgentSupervisionListener();
 
     // Listen for plan content pushed from backend plan watcher
     const offPlanContent = window.electron.ipcRenderer.on(IPC.PlanContent, (data: unknown) => {
@@ -582,6 +602,15 @@ function App() {
       cleanupShortcuts();
       stopTaskStatusPolling();
       stopNotificationWatcher();
+      stopPromptQueueListener();
+      stopAgentQueueListener();
+      stopTaskScheduler();
+      stopJobListener();
+      stopCiPolling();
+      stopLeftoverCheck();
+      stopStateEventListener();
+      stopAutoFetchSync();
+      stopSupervisionListener();
       offPlanContent();
       unlistenFocusChanged?.();
       unlistenResized?.();
@@ -708,6 +737,7 @@ function App() {
             open={store.showNewTaskDialog}
             onClose={() => toggleNewTaskDialog(false)}
           />
+          <AdoptWorktreesDialog />
         </main>
         <Show when={!isMac}>
           <WindowResizeHandles />
@@ -717,6 +747,7 @@ function App() {
           open={store.showSettingsDialog}
           onClose={() => toggleSettingsDialog(false)}
         />
+        <StateConflictDialog />
         <Show when={store.showArena}>
           <ArenaOverlay onClose={() => toggleArena(false)} />
         </Show>
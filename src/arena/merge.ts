@@ -101,7 +101,7 @@ export function createMergeWorkflow() {
       await invoke(IPC.MergeTask, {
         projectRoot: arenaStore.cwd,
         branchName: competitor.branchName,
-        squash: true,
+        strategy: 'squash',
         message: `arena: merge ${competitor.name} — ${promptSnippet}`,
         cleanup: true,
       });
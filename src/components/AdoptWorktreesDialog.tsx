@@ -0,0 +1,159 @@
+import { For, Show, createEffect, createResource, createSignal } from 'solid-js';
+import {
+  store,
+  getProject,
+  createTask,
+  loadAgents,
+  findAdoptableBranches,
+  showAdoptWorktrees,
+} from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+
+export function AdoptWorktreesDialog() {
+  const [selected, setSelected] = createSignal<Set<string>>(new Set());
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+
+  const projectId = () => store.adoptWorktreesProjectId;
+  const [found] = createResource(projectId, (id) => findAdoptableBranches(id));
+
+  // Worktrees are preselected; bare branches are opt-in
+  createEffect(() => {
+    const list = found();
+    setSelected(new Set((list ?? []).filter((b) => b.worktree_path).map((b) => b.branch)));
+    setError('');
+  });
+
+  function toggle(branch: string, checked: boolean) {
+    const next = new Set(selected());
+    if (checked) next.add(branch);
+    else next.delete(branch);
+    setSelected(next);
+  }
+
+  async function adopt() {
+    const id = projectId();
+    if (!id) return;
+    setBusy(true);
+    setError('');
+    try {
+      if (store.availableAgents.length === 0) await loadAgents();
+      const agentDef =
+        store.availableAgents.find((a) => a.id === store.lastAgentId) ?? store.availableAgents[0];
+      if (!agentDef) throw new Error('No agent available');
+      for (const branch of selected()) {
+        await createTask({ name: branch, agentDef, projectId: id, fromBranch: branch });
+      }
+      showAdoptWorktrees(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={projectId() !== null} onClose={() => showAdoptWorktrees(null)} width="520px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Existing branches
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        These branches of {getProject(projectId() ?? '')?.name ?? 'the project'} don't belong to a
+        task. Adopting one opens it as a task; branches without a worktree get a new one. The
+        branches stay yours: closing an adopted task never deletes its branch.
+      </div>
+
+      <div
+        style={{
+          display: 'flex',
+          'flex-direction': 'column',
+          gap: '4px',
+          'max-height': '320px',
+          'overflow-y': 'auto',
+          'font-size': '12px',
+        }}
+      >
+        <Show
+          when={(found() ?? []).length > 0}
+          fallback={
+            <div style={{ color: theme.fgSubtle }}>
+              {found.loading ? 'Looking for branches...' : 'Nothing to adopt.'}
+            </div>
+          }
+        >
+          <For each={found()}>
+            {(item) => (
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  color: theme.fg,
+                  cursor: 'pointer',
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={selected().has(item.branch)}
+                  onChange={(e) => toggle(item.branch, e.currentTarget.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                <span style={{ flex: '1', 'font-family': "'JetBrains Mono', monospace" }}>
+                  {item.branch}
+                </span>
+                <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                  {item.worktree_path ? 'worktree' : 'branch'} &middot;{' '}
+                  {new Date(item.committed_at * 1000).toLocaleDateString()}
+                </span>
+              </label>
+            )}
+          </For>
+        </Show>
+      </div>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => showAdoptWorktrees(null)}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Skip
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy() || selected().size === 0}
+          onClick={() => void adopt()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Adopting...' : `Adopt ${selected().size}`}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
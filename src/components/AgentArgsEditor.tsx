@@ -0,0 +1,81 @@
+import { For, Show } from 'solid-js';
+import { store, setAgentExtraArgs } from '../store/store';
+import { theme } from '../lib/theme';
+
+/** Common non-interactive flags, offered as one-click presets per agent. */
+const ARG_PRESETS: Record<string, string[]> = {
+  'claude-code': ['--permission-mode acceptEdits', '--dangerously-skip-permissions'],
+  codex: ['--full-auto'],
+  gemini: ['--yolo'],
+};
+
+function splitArgs(text: string): string[] {
+  const trimmed = text.trim();
+  return trimmed ? trimmed.split(/\s+/) : [];
+}
+
+export function AgentArgsEditor() {
+  function togglePreset(agentDefId: string, preset: string) {
+    const current = store.agentExtraArgs[agentDefId] ?? [];
+    const text = current.join(' ');
+    const next = text.includes(preset) ? text.replace(preset, '') : `${text} ${preset}`;
+    setAgentExtraArgs(agentDefId, splitArgs(next));
+  }
+
+  return (
+    <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+      <For each={store.availableAgents}>
+        {(agent) => (
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '4px' }}>
+            <span style={{ 'font-size': '12px', color: theme.fg }}>{agent.name}</span>
+            <input
+              type="text"
+              placeholder="Extra args (space-separated)"
+              value={(store.agentExtraArgs[agent.id] ?? []).join(' ')}
+              onChange={(e) => setAgentExtraArgs(agent.id, splitArgs(e.currentTarget.value))}
+              style={{
+                padding: '6px 10px',
+                background: theme.bgInput,
+                border: `1px solid ${theme.border}`,
+                'border-radius': '6px',
+                color: theme.fg,
+                'font-size': '12px',
+                'font-family': "'JetBrains Mono', monospace",
+              }}
+            />
+            <Show when={ARG_PRESETS[agent.id]}>
+              {(presets) => (
+                <div style={{ display: 'flex', gap: '6px', 'flex-wrap': 'wrap' }}>
+                  <For each={presets()}>
+                    {(preset) => (
+                      <button
+                        type="button"
+                        onClick={() => togglePreset(agent.id, preset)}
+                        style={{
+                          padding: '2px 8px',
+                          background: (store.agentExtraArgs[agent.id] ?? [])
+                            .join(' ')
+                            .includes(preset)
+                            ? theme.bgSelected
+                            : 'transparent',
+                          border: `1px solid ${theme.border}`,
+                          'border-radius': '10px',
+                          color: theme.fgMuted,
+                          cursor: 'pointer',
+                          'font-size': '11px',
+                          'font-family': "'JetBrains Mono', monospace",
+                        }}
+                      >
+                        {preset}
+                      </button>
+                    )}
+                  </For>
+                </div>
+              )}
+            </Show>
+          </div>
+        )}
+      </For>
+    </div>
+  );
+}
@@ -0,0 +1,147 @@
+import { Show, createEffect, createSignal } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { Task } from '../store/types';
+import type { PatchApplyResult } from '../ipc/types';
+
+interface ApplyPatchDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+export function ApplyPatchDialog(props: ApplyPatchDialogProps) {
+  const [patch, setPatch] = createSignal('');
+  const [threeWay, setThreeWay] = createSignal(true);
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [message, setMessage] = createSignal('');
+
+  createEffect(() => {
+    if (props.open) {
+      setPatch('');
+      setError('');
+      setMessage('');
+    }
+  });
+
+  async function apply() {
+    setBusy(true);
+    setError('');
+    setMessage('');
+    try {
+      const result = await invoke<PatchApplyResult>(IPC.ApplyPatch, {
+        worktreePath: props.task.worktreePath,
+        patch: patch(),
+        threeWay: threeWay(),
+      });
+      const n = result.files.length;
+      const conflicts = result.conflicted_files.length;
+      const summary = `Applied to ${n} file${n === 1 ? '' : 's'}`;
+      setMessage(conflicts > 0 ? `${summary}; ${conflicts} left with conflict markers` : summary);
+      setPatch('');
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="620px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Apply patch
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Paste a diff or a format-patch mail. It is applied to the worktree as uncommitted changes.
+      </div>
+
+      <textarea
+        value={patch()}
+        onInput={(e) => setPatch(e.currentTarget.value)}
+        placeholder="diff --git a/... b/..."
+        rows={14}
+        spellcheck={false}
+        style={{
+          width: '100%',
+          background: theme.bgInput,
+          border: `1px solid ${theme.border}`,
+          'border-radius': '8px',
+          padding: '8px 10px',
+          color: theme.fg,
+          'font-size': '12px',
+          'font-family': "'JetBrains Mono', monospace",
+          resize: 'vertical',
+          outline: 'none',
+          'box-sizing': 'border-box',
+        }}
+      />
+      <label
+        style={{
+          display: 'flex',
+          'align-items': 'center',
+          gap: '8px',
+          cursor: 'pointer',
+          'font-size': '13px',
+          color: theme.fg,
+        }}
+      >
+        <input
+          type="checkbox"
+          checked={threeWay()}
+          onChange={(e) => setThreeWay(e.currentTarget.checked)}
+          style={{ cursor: 'pointer' }}
+        />
+        Three-way merge hunks that don't apply cleanly (stages the result)
+      </label>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+      <Show when={message()}>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted }}>{message()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy() || !patch().trim()}
+          onClick={() => void apply()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Applying...' : 'Apply'}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
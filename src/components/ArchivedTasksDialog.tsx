@@ -0,0 +1,167 @@
+import { For, Show, createEffect, createSignal, untrack } from 'solid-js';
+import { listArchivedTasks, restoreArchivedTask } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import { formatBytes } from '../lib/format-size';
+import type { ArchivedTask } from '../ipc/types';
+
+interface ArchivedTasksDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+export function ArchivedTasksDialog(props: ArchivedTasksDialogProps) {
+  const [archived, setArchived] = createSignal<ArchivedTask[] | null>(null);
+  const [restoring, setRestoring] = createSignal<string | null>(null);
+  const [error, setError] = createSignal('');
+
+  async function load() {
+    try {
+      setArchived(await listArchivedTasks(props.projectId));
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  createEffect(() => {
+    if (props.open) {
+      setArchived(null);
+      setError('');
+      untrack(() => void load());
+    }
+  });
+
+  async function restore(entry: ArchivedTask) {
+    setRestoring(entry.id);
+    setError('');
+    try {
+      await restoreArchivedTask(entry.id, props.projectId);
+      props.onClose();
+    } catch (err) {
+      setError(String(err));
+      await load();
+    } finally {
+      setRestoring(null);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="640px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Archived tasks
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Closed tasks whose changes, commit log and agent transcripts were kept. Restoring one gives
+        it a new worktree, on its branch if that was kept.
+      </div>
+
+      <Show when={archived()}>
+        {(list) => (
+          <Show
+            when={list().length > 0}
+            fallback={
+              <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+                No archived tasks in this project.
+              </div>
+            }
+          >
+            <div
+              style={{
+                display: 'flex',
+                'flex-direction': 'column',
+                gap: '6px',
+                'max-height': '320px',
+                'overflow-y': 'auto',
+              }}
+            >
+              <For each={list()}>
+                {(entry) => (
+                  <div
+                    style={{
+                      display: 'flex',
+                      'align-items': 'center',
+                      gap: '8px',
+                      'font-size': '12px',
+                    }}
+                  >
+                    <span
+                      style={{
+                        flex: '1',
+                        display: 'flex',
+                        'flex-direction': 'column',
+                        'min-width': '0',
+                      }}
+                    >
+                      <span style={{ color: theme.fg }}>{entry.name}</span>
+                      <span
+                        style={{
+                          color: theme.fgSubtle,
+                          'font-size': '11px',
+                          'font-family': "'JetBrains Mono', monospace",
+                          overflow: 'hidden',
+                          'text-overflow': 'ellipsis',
+                          'white-space': 'nowrap',
+                        }}
+                      >
+                        {new Date(entry.archived_at).toLocaleString()} · {entry.branch_name}
+                        {entry.branch_kept ? '' : ' (deleted)'}
+                      </span>
+                    </span>
+                    <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                      {entry.patch_bytes > 0 ? formatBytes(entry.patch_bytes) : 'no changes'}
+                    </span>
+                    <button
+                      type="button"
+                      class="btn-secondary"
+                      disabled={restoring() !== null}
+                      onClick={() => void restore(entry)}
+                      style={{
+                        padding: '3px 10px',
+                        background: theme.bgInput,
+                        border: `1px solid ${theme.border}`,
+                        'border-radius': '6px',
+                        color: theme.fgMuted,
+                        cursor: 'pointer',
+                        'font-size': '12px',
+                      }}
+                    >
+                      {restoring() === entry.id ? 'Restoring...' : 'Restore'}
+                    </button>
+                  </div>
+                )}
+              </For>
+            </div>
+          </Show>
+        )}
+      </Show>
+
+      <Show when={error()}>
+        <div
+          style={{ ...bannerStyle(theme.error), 'font-size': '12px', 'white-space': 'pre-wrap' }}
+        >
+          {error()}
+        </div>
+      </Show>
+
+      <div style={{ display: 'flex', 'justify-content': 'flex-end', 'padding-top': '4px' }}>
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+      </div>
+    </Dialog>
+  );
+}
@@ -0,0 +1,219 @@
+import { For, Show, createResource, createSignal } from 'solid-js';
+import { store, addAutoResponseRule, removeAutoResponseRule } from '../store/store';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { theme } from '../lib/theme';
+import type { AutoResponseAuditEntry } from '../ipc/types';
+
+export function AutoResponseEditor() {
+  const [showForm, setShowForm] = createSignal(false);
+  const [agentDefId, setAgentDefId] = createSignal(store.availableAgents[0]?.id ?? '');
+  const [pattern, setPattern] = createSignal('');
+  const [response, setResponse] = createSignal('');
+  const [error, setError] = createSignal('');
+  const [showLog, setShowLog] = createSignal(false);
+  const [log] = createResource(showLog, () =>
+    invoke<AutoResponseAuditEntry[]>(IPC.GetAutoResponseLog, { limit: 20 }),
+  );
+
+  function agentName(id: string): string {
+    return store.availableAgents.find((a) => a.id === id)?.name ?? id;
+  }
+
+  function handleAdd() {
+    const p = pattern().trim();
+    if (!agentDefId() || !p || !response()) return;
+    try {
+      new RegExp(p, 'i');
+    } catch {
+      setError('Invalid regular expression');
+      return;
+    }
+    addAutoResponseRule({ agentDefId: agentDefId(), pattern: p, response: response() });
+    setPattern('');
+    setResponse('');
+    setError('');
+    setShowForm(false);
+  }
+
+  const inputStyle = () => ({
+    padding: '8px 10px',
+    background: theme.bgInput,
+    border: `1px solid ${theme.border}`,
+    'border-radius': '6px',
+    color: theme.fg,
+    'font-size': '12px',
+    width: '100%',
+    'box-sizing': 'border-box' as const,
+  });
+
+  const monoStyle = {
+    'font-size': '11px',
+    color: theme.fgSubtle,
+    'font-family': "'JetBrains Mono', monospace",
+  };
+
+  return (
+    <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+      <For each={store.autoResponseRules}>
+        {(rule) => (
+          <div
+            style={{
+              display: 'flex',
+              'align-items': 'center',
+              'justify-content': 'space-between',
+              padding: '8px 12px',
+              'border-radius': '8px',
+              background: theme.bgInput,
+              border: `1px solid ${theme.border}`,
+            }}
+          >
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px' }}>
+              <span style={{ 'font-size': '13px', color: theme.fg }}>
+                {agentName(rule.agentDefId)}
+              </span>
+              <span style={monoStyle}>/{rule.pattern}/ &rarr; {rule.response}</span>
+            </div>
+            <button
+              type="button"
+              onClick={() => removeAutoResponseRule(rule.id)}
+              style={{
+                background: 'transparent',
+                border: 'none',
+                color: theme.fgMuted,
+                cursor: 'pointer',
+                'font-size': '16px',
+                padding: '0 4px',
+              }}
+            >
+              &times;
+            </button>
+          </div>
+        )}
+      </For>
+
+      <Show when={!showForm()}>
+        <button
+          type="button"
+          onClick={() => setShowForm(true)}
+          style={{
+            padding: '8px 12px',
+            background: 'transparent',
+            border: `1px dashed ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '12px',
+          }}
+        >
+          + Add auto-response rule
+        </button>
+      </Show>
+
+      <Show when={showForm()}>
+        <div
+          style={{
+            display: 'flex',
+            'flex-direction': 'column',
+            gap: '8px',
+            padding: '12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <select
+            value={agentDefId()}
+            onChange={(e) => setAgentDefId(e.currentTarget.value)}
+            style={inputStyle()}
+          >
+            <For each={store.availableAgents}>
+              {(agent) => <option value={agent.id}>{agent.name}</option>}
+            </For>
+          </select>
+          <input
+            type="text"
+            placeholder="Pattern (regex, e.g. \(y/N\)\s*$)"
+            value={pattern()}
+            onInput={(e) => setPattern(e.currentTarget.value)}
+            style={inputStyle()}
+          />
+          <input
+            type="text"
+            placeholder="Response (e.g. y\r — \r is Enter)"
+            value={response()}
+            onInput={(e) => setResponse(e.currentTarget.value)}
+            style={inputStyle()}
+          />
+          <Show when={error()}>
+            <span style={{ 'font-size': '11px', color: theme.error }}>{error()}</span>
+          </Show>
+          <div style={{ display: 'flex', gap: '8px', 'justify-content': 'flex-end' }}>
+            <button
+              type="button"
+              onClick={() => setShowForm(false)}
+              style={{
+                padding: '6px 14px',
+                background: 'transparent',
+                border: `1px solid ${theme.border}`,
+                'border-radius': '6px',
+                color: theme.fgMuted,
+                cursor: 'pointer',
+                'font-size': '12px',
+              }}
+            >
+              Cancel
+            </button>
+            <button
+              type="button"
+              onClick={handleAdd}
+              style={{
+                padding: '6px 14px',
+                background: theme.accent,
+                border: 'none',
+                'border-radius': '6px',
+                color: theme.accentText,
+                cursor: 'pointer',
+                'font-size': '12px',
+                opacity: pattern().trim() && response() ? 1 : 0.5,
+              }}
+            >
+              Add Rule
+            </button>
+          </div>
+        </div>
+      </Show>
+
+      <button
+        type="button"
+        onClick={() => setShowLog((v) => !v)}
+        style={{
+          background: 'transparent',
+          border: 'none',
+          color: theme.fgMuted,
+          cursor: 'pointer',
+          'font-size': '11px',
+          'text-align': 'left',
+          padding: '0',
+        }}
+      >
+        {showLog() ? 'Hide' : 'Show'} recent auto-responses
+      </button>
+      <Show when={showLog()}>
+        <Show
+          when={(log() ?? []).length > 0}
+          fallback={<span style={monoStyle}>No auto-responses sent yet</span>}
+        >
+          <For each={log()}>
+            {(entry) => (
+              <span style={monoStyle}>
+                {new Date(entry.at).toLocaleString()} &middot; {entry.matched} &rarr;{' '}
+                {JSON.stringify(entry.response)}
+              </span>
+            )}
+          </For>
+        </Show>
+      </Show>
+    </div>
+  );
+}
@@ -5,10 +5,18 @@ interface BranchPrefixFieldProps {
   branchPrefix: string;
   branchPreview: string;
   projectPath: string | undefined;
+  /** Project's worktree directory setting, if any. */
+  worktreeDir?: string;
   onPrefixChange: (prefix: string) => void;
 }
 
 export function BranchPrefixField(props: BranchPrefixFieldProps) {
+  const worktreeBase = () => {
+    const dir = props.worktreeDir?.trim();
+    if (!dir) return `${props.projectPath}/.worktrees`;
+    return dir.startsWith('/') || dir.startsWith('~') ? dir : `${props.projectPath}/${dir}`;
+  };
+
   return (
     <div
       data-nav-field="branch-prefix"
@@ -71,7 +79,7 @@ export function BranchPrefixField(props: BranchPrefixFieldProps) {
             >
               <path d="M1.75 1A1.75 1.75 0 0 0 0 2.75v10.5C0 14.216.784 15 1.75 15h12.5A1.75 1.75 0 0 0 16 13.25v-8.5A1.75 1.75 0 0 0 14.25 3H7.5a.25.25 0 0 1-.2-.1l-.9-1.2C6.07 1.26 5.55 1 5 1H1.75Z" />
             </svg>
-            {props.projectPath}/.worktrees/{props.branchPreview}
+            {worktreeBase()}/{props.branchPreview}
           </span>
         </div>
       </Show>
@@ -1,10 +1,11 @@
 import { createSignal, createMemo, createEffect, onCleanup, For, Show } from 'solid-js';
-import { invoke } from '../lib/ipc';
+import { invoke, fireAndForget } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import { theme } from '../lib/theme';
 import { sf } from '../lib/fontScale';
 import { getStatusColor } from '../lib/status-colors';
-import type { ChangedFile } from '../ipc/types';
+import { ConfirmDialog } from './ConfirmDialog';
+import type { ChangedFile, CommitOptions } from '../ipc/types';
 
 interface ChangedFilesListProps {
   worktreePath: string;
@@ -15,12 +16,24 @@ interface ChangedFilesListProps {
   projectRoot?: string;
   /** Branch name for branch-based fallback when worktree doesn't exist */
   branchName?: string | null;
+  /** Stage, unstage, discard and commit uncommitted files in place */
+  canCommit?: boolean;
+  /** Project globs for generated files, left out of the line totals */
+  generatedPatterns?: string[];
+  /** Signing and hook overrides for commits; unset ones follow the repo's git config */
+  commitOptions?: CommitOptions;
 }
 
 export function ChangedFilesList(props: ChangedFilesListProps) {
   const [files, setFiles] = createSignal<ChangedFile[]>([]);
   const [selectedIndex, setSelectedIndex] = createSignal(-1);
   const rowRefs: HTMLDivElement[] = [];
+  // Uncommitted files the user unchecked; new files default to being committed
+  const [excluded, setExcluded] = createSignal<ReadonlySet<string>>(new Set());
+  const [commitMessage, setCommitMessage] = createSignal('');
+  const [committing, setCommitting] = createSignal(false);
+  const [commitError, setCommitError] = createSignal('');
+  const [pendingDiscard, setPendingDiscard] = createSignal<ChangedFile | null>(null);
 
   // Scroll selected item into view reactively
   createEffect(() => {
@@ -54,12 +67,14 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
     }
   }
 
-  // Poll every 5s, matching the git status polling interval.
+  // Refresh when the worktree watcher reports a change; poll every 5s only when
+  // the worktree can't be watched, and every 30s otherwise as a safety net.
   // Falls back to branch-based diff when worktree path doesn't exist.
   createEffect(() => {
     const path = props.worktreePath;
     const projectRoot = props.projectRoot;
     const branchName = props.branchName;
+    const generatedPatterns = props.generatedPatterns;
     if (!props.isActive) return;
     let cancelled = false;
     let inFlight = false;
@@ -74,6 +89,7 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
           try {
             const result = await invoke<ChangedFile[]>(IPC.GetChangedFiles, {
               worktreePath: path,
+              generatedPatterns,
             });
             if (!cancelled) setFiles(result);
             return;
@@ -89,6 +105,7 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
             const result = await invoke<ChangedFile[]>(IPC.GetChangedFilesFromBranch, {
               projectRoot,
               branchName,
+              generatedPatterns,
             });
             if (!cancelled) setFiles(result);
           } catch {
@@ -101,19 +118,111 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
     }
 
     void refresh();
-    const timer = setInterval(() => {
-      if (!usingBranchFallback) void refresh();
-    }, 5000);
+    const poll = (ms: number) =>
+      setInterval(() => {
+        if (!usingBranchFallback) void refresh();
+      }, ms);
+    let timer = poll(5000);
+    let watching = false;
+    const offChanged = window.electron.ipcRenderer.on(IPC.WorktreeChanged, (data: unknown) => {
+      const msg = data as { worktreePath?: string } | null;
+      if (msg?.worktreePath === path && !usingBranchFallback) void refresh();
+    });
+    // The watcher died (e.g. too many files for the OS limit): back to polling
+    const offFailed = window.electron.ipcRenderer.on(IPC.WorktreeWatchFailed, (data: unknown) => {
+      const msg = data as { worktreePath?: string } | null;
+      if (msg?.worktreePath !== path || !watching) return;
+      watching = false;
+      clearInterval(timer);
+      timer = poll(5000);
+    });
+    if (path) {
+      invoke<boolean>(IPC.WatchWorktree, { worktreePath: path })
+        .then((ok) => {
+          watching = ok;
+          if (cancelled) {
+            if (ok) fireAndForget(IPC.UnwatchWorktree, { worktreePath: path });
+            return;
+          }
+          if (!ok) return;
+          clearInterval(timer);
+          timer = poll(30_000);
+        })
+        .catch(() => {
+          // Keep polling
+        });
+    }
     onCleanup(() => {
       cancelled = true;
       clearInterval(timer);
+      offChanged();
+      offFailed();
+      if (watching) fireAndForget(IPC.UnwatchWorktree, { worktreePath: path });
     });
   });
 
-  const totalAdded = createMemo(() => files().reduce((s, f) => s + f.lines_added, 0));
-  const totalRemoved = createMemo(() => files().reduce((s, f) => s + f.lines_removed, 0));
+  const reviewed = createMemo(() => files().filter((f) => !f.generated));
+  const totalAdded = createMemo(() => reviewed().reduce((s, f) => s + f.lines_added, 0));
+  const totalRemoved = createMemo(() => reviewed().reduce((s, f) => s + f.lines_removed, 0));
   const uncommittedCount = createMemo(() => files().filter((f) => !f.committed).length);
 
+  const commitPaths = createMemo(() =>
+    files()
+      .filter((f) => !f.committed && !excluded().has(f.path))
+      .map((f) => f.path),
+  );
+
+  function toggleExcluded(path: string) {
+    setExcluded((prev) => {
+      const next = new Set(prev);
+      if (!next.delete(path)) next.add(path);
+      return next;
+    });
+  }
+
+  async function commit() {
+    const message = commitMessage().trim();
+    const paths = commitPaths();
+    if (!message || paths.length === 0 || committing()) return;
+    setCommitting(true);
+    setCommitError('');
+    try {
+      await invoke(IPC.CommitWorktree, {
+        worktreePath: props.worktreePath,
+        message,
+        // Without an explicit selection, also pick up files that appeared since the last poll
+        paths: excluded().size > 0 ? paths : undefined,
+        ...props.commitOptions,
+      });
+      setCommitMessage('');
+      setExcluded(new Set<string>());
+      await reloadFiles();
+    } catch (err) {
+      setCommitError(String(err));
+    } finally {
+      setCommitting(false);
+    }
+  }
+
+  async function reloadFiles() {
+    setFiles(
+      await invoke<ChangedFile[]>(IPC.GetChangedFiles, {
+        worktreePath: props.worktreePath,
+        generatedPatterns: props.generatedPatterns,
+      }),
+    );
+  }
+
+  async function runFileAction(channel: IPC, file: ChangedFile) {
+    setCommitError('');
+    try {
+      await invoke(channel, { worktreePath: props.worktreePath, filePath: file.path });
+      await reloadFiles();
+    } catch (err) {
+      setCommitError(String(err));
+    }
+  }
+
   /** For each file, compute the display filename and an optional disambiguating directory. */
   const fileDisplays = createMemo(() => {
     const list = files();
@@ -191,6 +300,16 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
                 props.onFileClick?.(file);
               }}
             >
+              <Show when={props.canCommit && !file.committed}>
+                <input
+                  type="checkbox"
+                  checked={!excluded().has(file.path)}
+                  onClick={(e) => e.stopPropagation()}
+                  onChange={() => toggleExcluded(file.path)}
+                  title="Include in commit"
+                  style={{ margin: '0', cursor: 'pointer', 'accent-color': theme.accent }}
+                />
+              </Show>
               <span
                 style={{
                   color: getStatusColor(file.status),
@@ -211,7 +330,7 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
                   gap: '4px',
                   'align-items': 'baseline',
                 }}
-                title={file.path}
+                title={file.old_path ? `${file.old_path} \u2192 ${file.path}` : file.path}
               >
                 <span style={{ color: theme.fg }}>{fileDisplays()[i()].name}</span>
                 <Show when={fileDisplays()[i()].disambig}>
@@ -220,7 +339,49 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
                   </span>
                 </Show>
               </span>
-              <Show when={file.lines_added > 0 || file.lines_removed > 0}>
+              <Show when={file.staged}>
+                <span
+                  style={{ color: theme.success, 'font-size': sf(10), 'flex-shrink': '0' }}
+                  title="Staged"
+                >
+                  S
+                </span>
+              </Show>
+              <Show when={props.canCommit && !file.committed}>
+                <span class="file-row-actions" style={{ gap: '2px', 'flex-shrink': '0' }}>
+                  <button
+                    type="button"
+                    class="file-row-action"
+                    title={file.staged ? 'Unstage' : 'Stage'}
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      void runFileAction(file.staged ? IPC.UnstageFile : IPC.StageFile, file);
+                    }}
+                  >
+                    {file.staged ? '\u2212' : '+'}
+                  </button>
+                  <button
+                    type="button"
+                    class="file-row-action"
+                    title="Discard changes"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      setPendingDiscard(file);
+                    }}
+                  >
+                    {'\u21ba'}
+                  </button>
+                </span>
+              </Show>
+              <Show when={file.generated}>
+                <span
+                  style={{ color: theme.fgSubtle, 'font-size': sf(10), 'flex-shrink': '0' }}
+                  title="Generated file, not counted in the totals"
+                >
+                  gen
+                </span>
+              </Show>
+              <Show when={!file.generated && (file.lines_added > 0 || file.lines_removed > 0)}>
                 <span style={{ color: theme.success, 'flex-shrink': '0' }}>
                   +{file.lines_added}
                 </span>
@@ -249,6 +410,71 @@ export function ChangedFilesList(props: ChangedFilesListProps) {
           </Show>
         </div>
       </Show>
+      <Show when={props.canCommit && uncommittedCount() > 0}>
+        <div
+          style={{
+            display: 'flex',
+            gap: '4px',
+            padding: '4px 8px',
+            'border-top': `1px solid ${theme.border}`,
+            'flex-shrink': '0',
+          }}
+        >
+          <input
+            class="input-field"
+            type="text"
+            placeholder="Commit message"
+            value={commitMessage()}
+            onInput={(e) => setCommitMessage(e.currentTarget.value)}
+            onKeyDown={(e) => {
+              e.stopPropagation();
+              if (e.key === 'Enter') void commit();
+            }}
+            style={{ flex: '1', 'min-width': '0', 'font-size': sf(11) }}
+          />
+          <button
+            type="button"
+            disabled={committing() || !commitMessage().trim() || commitPaths().length === 0}
+            onClick={() => void commit()}
+            title={`Commit ${commitPaths().length} file${commitPaths().length === 1 ? '' : 's'}`}
+            style={{
+              padding: '2px 10px',
+              background: theme.accent,
+              border: 'none',
+              'border-radius': '4px',
+              color: theme.accentText,
+              cursor: 'pointer',
+              'font-size': sf(11),
+              opacity:
+                committing() || !commitMessage().trim() || commitPaths().length === 0
+                  ? '0.5'
+                  : '1',
+            }}
+          >
+            {committing() ? 'Committing...' : `Commit (${commitPaths().length})`}
+          </button>
+        </div>
+      </Show>
+      <Show when={commitError()}>
+        <div style={{ padding: '0 8px 4px', color: theme.error, 'font-size': sf(10) }}>
+          {commitError()}
+        </div>
+      </Show>
+      <ConfirmDialog
+        open={pendingDiscard() !== null}
+        title="Discard Changes"
+        message={`Discard uncommitted changes to ${pendingDiscard()?.path ?? ''}? ${
+          pendingDiscard()?.status === '?' ? 'The file will be deleted.' : 'This cannot be undone.'
+        }`}
+        confirmLabel="Discard"
+        danger
+        onConfirm={() => {
+          const file = pendingDiscard();
+          setPendingDiscard(null);
+          if (file) void runFileAction(IPC.DiscardFileChanges, file);
+        }}
+        onCancel={() => setPendingDiscard(null)}
+      />
     </div>
   );
 }
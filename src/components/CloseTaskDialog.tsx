@@ -1,11 +1,18 @@
-import { Show, createResource } from 'solid-js';
+import { For, Show, createResource } from 'solid-js';
 import { invoke } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
-import { closeTask, getProject } from '../store/store';
+import {
+  archiveTask,
+  closeTask,
+  exportTask,
+  getLinkedBranches,
+  willDeleteTaskBranch,
+  getProjectPath,
+} from '../store/store';
 import { ConfirmDialog } from './ConfirmDialog';
 import { theme, bannerStyle } from '../lib/theme';
 import type { Task } from '../store/types';
-import type { WorktreeStatus } from '../ipc/types';
+import type { DeleteTaskPreview } from '../ipc/types';
 
 interface CloseTaskDialogProps {
   open: boolean;
@@ -14,11 +21,28 @@ interface CloseTaskDialogProps {
 }
 
 export function CloseTaskDialog(props: CloseTaskDialogProps) {
-  const [worktreeStatus] = createResource(
-    () => (props.open && !props.task.directMode ? props.task.worktreePath : null),
-    (path) => invoke<WorktreeStatus>(IPC.GetWorktreeStatus, { worktreePath: path }),
+  const [preview] = createResource(
+    () => (props.open && !props.task.directMode ? props.task : null),
+    (task) =>
+      invoke<DeleteTaskPreview>(IPC.PreviewDeleteTask, {
+        agentIds: [...task.agentIds, ...task.shellAgentIds],
+        branchName: task.branchName,
+        deleteBranch: willDeleteTaskBranch(task),
+        projectRoot: getProjectPath(task.projectId) ?? '',
+        linked: getLinkedBranches(task),
+      }),
   );
 
+  const exportButtonStyle = {
+    padding: '3px 8px',
+    background: theme.bgInput,
+    border: `1px solid ${theme.border}`,
+    'border-radius': '6px',
+    color: theme.fg,
+    cursor: 'pointer',
+    'font-size': '12px',
+  };
+
   return (
     <ConfirmDialog
       open={props.open}
@@ -34,7 +58,7 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
           <Show when={!props.task.directMode}>
             <Show
               when={
-                worktreeStatus()?.has_uncommitted_changes || worktreeStatus()?.has_committed_changes
+                (preview()?.uncommitted_files ?? 0) > 0 || (preview()?.unmerged_commits ?? 0) > 0
               }
             >
               <div
@@ -45,7 +69,7 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
                   gap: '8px',
                 }}
               >
-                <Show when={worktreeStatus()?.has_uncommitted_changes}>
+                <Show when={(preview()?.uncommitted_files ?? 0) > 0}>
                   <div
                     style={{
                       ...bannerStyle(theme.warning),
@@ -53,10 +77,10 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
                       'font-weight': '600',
                     }}
                   >
-                    Warning: There are uncommitted changes that will be permanently lost.
+                    Warning: {preview()?.uncommitted_files} uncommitted file(s) will be lost.
                   </div>
                 </Show>
-                <Show when={worktreeStatus()?.has_committed_changes}>
+                <Show when={(preview()?.unmerged_commits ?? 0) > 0}>
                   <div
                     style={{
                       ...bannerStyle(theme.warning),
@@ -64,14 +88,38 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
                       'font-weight': '600',
                     }}
                   >
-                    Warning: This branch has commits that have not been merged into main.
+                    Warning: {preview()?.unmerged_commits} commit(s) have not been merged into main.
+                  </div>
+                  <div
+                    style={{
+                      display: 'flex',
+                      'align-items': 'center',
+                      gap: '8px',
+                      'font-size': '12px',
+                      color: theme.fgMuted,
+                    }}
+                  >
+                    Keep a copy first:
+                    <button
+                      type="button"
+                      onClick={() => void exportTask(props.task.id, 'patch')}
+                      style={exportButtonStyle}
+                    >
+                      Export patches
+                    </button>
+                    <button
+                      type="button"
+                      onClick={() => void exportTask(props.task.id, 'bundle')}
+                      style={exportButtonStyle}
+                    >
+                      Export bundle
+                    </button>
                   </div>
                 </Show>
               </div>
             </Show>
             {(() => {
-              const project = getProject(props.task.projectId);
-              const willDeleteBranch = project?.deleteBranchOnClose ?? true;
+              const willDeleteBranch = willDeleteTaskBranch(props.task);
               return (
                 <>
                   <p style={{ margin: '0 0 8px' }}>
@@ -96,12 +144,49 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
                     <li>
                       Worktree at <strong>{props.task.worktreePath}</strong>
                     </li>
+                    <For each={props.task.linkedWorktrees ?? []}>
+                      {(linked) => (
+                        <li>
+                          Worktree at <strong>{linked.worktreePath}</strong>
+                        </li>
+                      )}
+                    </For>
                     <Show when={!willDeleteBranch}>
                       <li style={{ color: theme.fgMuted }}>
                         Branch <strong>{props.task.branchName}</strong> will be kept
                       </li>
                     </Show>
+                    <Show when={(preview()?.running_agents.length ?? 0) > 0}>
+                      <li style={{ color: theme.fgMuted }}>
+                        {preview()?.running_agents.length} running agent(s) and shell(s) will be
+                        stopped
+                      </li>
+                    </Show>
                   </ul>
+                  <Show when={!props.task.linkedWorktrees?.length}>
+                    <div
+                      style={{
+                        display: 'flex',
+                        'align-items': 'center',
+                        gap: '8px',
+                        'margin-top': '12px',
+                        'font-size': '12px',
+                        color: theme.fgMuted,
+                      }}
+                    >
+                      Or keep the diff, commit log and agent transcript to restore it later:
+                      <button
+                        type="button"
+                        onClick={() => {
+                          props.onDone();
+                          void archiveTask(props.task.id);
+                        }}
+                        style={exportButtonStyle}
+                      >
+                        Archive
+                      </button>
+                    </div>
+                  </Show>
                 </>
               );
             })()}
@@ -112,7 +197,7 @@ export function CloseTaskDialog(props: CloseTaskDialogProps) {
       danger={!props.task.directMode}
       onConfirm={() => {
         props.onDone();
-        closeTask(props.task.id);
+        closeTask(props.task.id, true);
       }}
       onCancel={() => props.onDone()}
     />
@@ -0,0 +1,166 @@
+import { For, Show, createResource, createSignal } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, getProject } from '../store/store';
+import { Dialog } from './Dialog';
+import { DiffViewerDialog } from './DiffViewerDialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { Task } from '../store/types';
+import type { WorktreeDiffStats } from '../ipc/types';
+
+interface CompareTasksDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+const cellStyle = { padding: '4px 8px', 'text-align': 'right' as const };
+
+export function CompareTasksDialog(props: CompareTasksDialogProps) {
+  const [diffAgainst, setDiffAgainst] = createSignal<Task | null>(null);
+
+  // The task's fan-out siblings when it has any, otherwise every worktree task in the project
+  const tasks = () => {
+    const inProject = [...store.taskOrder, ...store.collapsedTaskOrder]
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.projectId === props.task.projectId && !t.directMode);
+    const group = props.task.fanoutGroupId;
+    const siblings = group ? inProject.filter((t) => t.fanoutGroupId === group) : [];
+    return siblings.length > 1 ? siblings : inProject;
+  };
+
+  const [stats] = createResource(
+    () => (props.open ? tasks().map((t) => t.worktreePath) : null),
+    (worktreePaths) => invoke<WorktreeDiffStats[]>(IPC.CompareWorktrees, { worktreePaths }),
+  );
+  const statsFor = (task: Task) => stats()?.find((s) => s.worktree_path === task.worktreePath);
+  const agentName = (task: Task) =>
+    store.agents[task.agentIds[0]]?.def.name ?? task.savedAgentDef?.name ?? '';
+
+  return (
+    <>
+      <Dialog open={props.open} onClose={props.onClose} width="640px">
+        <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+          Compare tasks
+        </h2>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+          What each task changed since it branched from main, committed or not. Diff shows how
+          this task's worktree differs from the other one.
+        </div>
+
+        <Show when={stats.error}>
+          <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>
+            {String(stats.error)}
+          </div>
+        </Show>
+
+        <table style={{ 'border-collapse': 'collapse', 'font-size': '12px', color: theme.fg }}>
+          <thead>
+            <tr style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+              <th style={{ ...cellStyle, 'text-align': 'left' }}>Task</th>
+              <th style={cellStyle}>Commits</th>
+              <th style={cellStyle}>Files</th>
+              <th style={cellStyle}>Lines</th>
+              <th style={cellStyle} />
+            </tr>
+          </thead>
+          <tbody>
+            <For each={tasks()}>
+              {(task) => (
+                <tr style={{ 'border-top': `1px solid ${theme.borderSubtle}` }}>
+                  <td style={{ ...cellStyle, 'text-align': 'left' }}>
+                    <span style={{ 'font-weight': task.id === props.task.id ? '600' : '400' }}>
+                      {task.name}
+                    </span>
+                    <Show when={agentName(task)}>
+                      <span style={{ color: theme.fgSubtle }}> · {agentName(task)}</span>
+                    </Show>
+                  </td>
+                  <Show
+                    when={statsFor(task)}
+                    fallback={
+                      <td colSpan={3} style={{ ...cellStyle, color: theme.fgSubtle }}>
+                        {stats.loading ? '...' : ''}
+                      </td>
+                    }
+                  >
+                    {(s) => (
+                      <Show
+                        when={!s().error}
+                        fallback={
+                          <td
+                            colSpan={3}
+                            title={s().error}
+                            style={{ ...cellStyle, color: theme.error }}
+                          >
+                            Couldn't compare
+                          </td>
+                        }
+                      >
+                        <td style={cellStyle}>{s().commits}</td>
+                        <td style={cellStyle}>{s().files_changed}</td>
+                        <td style={{ ...cellStyle, 'font-family': "'JetBrains Mono', monospace" }}>
+                          <span style={{ color: theme.success }}>+{s().lines_added}</span>{' '}
+                          <span style={{ color: theme.error }}>-{s().lines_removed}</span>
+                        </td>
+                      </Show>
+                    )}
+                  </Show>
+                  <td style={cellStyle}>
+                    <Show when={task.id !== props.task.id}>
+                      <button
+                        type="button"
+                        class="btn-secondary"
+                        onClick={() => setDiffAgainst(task)}
+                        style={{
+                          padding: '3px 10px',
+                          background: theme.bgInput,
+                          border: `1px solid ${theme.border}`,
+                          'border-radius': '6px',
+                          color: theme.fgMuted,
+                          cursor: 'pointer',
+                          'font-size': '12px',
+                        }}
+                      >
+                        Diff
+                      </button>
+                    </Show>
+                  </td>
+                </tr>
+              )}
+            </For>
+          </tbody>
+        </table>
+
+        <div style={{ display: 'flex', 'justify-content': 'flex-end', 'padding-top': '4px' }}>
+          <button
+            type="button"
+            class="btn-secondary"
+            onClick={() => props.onClose()}
+            style={{
+              padding: '9px 18px',
+              background: theme.bgInput,
+              border: `1px solid ${theme.border}`,
+              'border-radius': '8px',
+              color: theme.fgMuted,
+              cursor: 'pointer',
+              'font-size': '13px',
+            }}
+          >
+            Close
+          </button>
+        </div>
+      </Dialog>
+      <DiffViewerDialog
+        scrollToFile={diffAgainst() ? '' : null}
+        worktreePath={props.task.worktreePath}
+        compareWorktreePath={diffAgainst()?.worktreePath}
+        compareLabel={diffAgainst()?.name}
+        generatedPatterns={getProject(props.task.projectId)?.generatedFiles}
+        onClose={() => setDiffAgainst(null)}
+        taskId={props.task.id}
+        agentId={props.task.agentIds[0]}
+      />
+    </>
+  );
+}
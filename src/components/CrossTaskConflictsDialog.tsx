@@ -0,0 +1,187 @@
+import { For, Show, createResource } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, getProjectPath } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import { conflictCounts, conflictLookup } from '../lib/conflict-matrix';
+import type { BranchConflict } from '../ipc/types';
+
+interface CrossTaskConflictsDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+const cellStyle = {
+  padding: '4px 6px',
+  'text-align': 'center',
+  border: `1px solid ${theme.border}`,
+  'min-width': '24px',
+};
+
+export function CrossTaskConflictsDialog(props: CrossTaskConflictsDialogProps) {
+  const tasks = () =>
+    [...store.taskOrder, ...store.collapsedTaskOrder]
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.projectId === props.projectId && !t.directMode);
+
+  const [conflicts, { refetch }] = createResource(
+    () => (props.open ? tasks().map((t) => t.branchName) : null),
+    (branches) =>
+      invoke<BranchConflict[]>(IPC.CheckCrossTaskConflicts, {
+        projectRoot: getProjectPath(props.projectId),
+        branches,
+      }),
+  );
+
+  const lookup = () => conflictLookup(conflicts() ?? []);
+  const counts = () => conflictCounts(conflicts() ?? []);
+  const taskName = (branch: string) => tasks().find((t) => t.branchName === branch)?.name ?? branch;
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="640px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Cross-task conflicts
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Which of this project's task branches would conflict with each other if both were merged,
+        as committed so far. Uncommitted changes are not included.
+      </div>
+
+      <Show when={conflicts.error}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>
+          {String(conflicts.error)}
+        </div>
+      </Show>
+
+      <Show
+        when={tasks().length > 1}
+        fallback={
+          <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+            Needs at least two tasks with a worktree.
+          </div>
+        }
+      >
+        <Show
+          when={!conflicts.loading}
+          fallback={<div style={{ 'font-size': '12px', color: theme.fgSubtle }}>Checking...</div>}
+        >
+          <div style={{ overflow: 'auto', 'max-height': '320px' }}>
+            <table style={{ 'border-collapse': 'collapse', 'font-size': '12px', color: theme.fg }}>
+              <thead>
+                <tr>
+                  <th />
+                  <For each={tasks()}>
+                    {(_t, i) => (
+                      <th style={{ ...cellStyle, color: theme.fgMuted, 'font-weight': '500' }}>
+                        {i() + 1}
+                      </th>
+                    )}
+                  </For>
+                </tr>
+              </thead>
+              <tbody>
+                <For each={tasks()}>
+                  {(row, i) => (
+                    <tr>
+                      <td style={{ ...cellStyle, 'text-align': 'left', 'white-space': 'nowrap' }}>
+                        {i() + 1}. {row.name}
+                        <Show when={counts().get(row.branchName)}>
+                          {(n) => <span style={{ color: theme.fgSubtle }}> ({n()})</span>}
+                        </Show>
+                      </td>
+                      <For each={tasks()}>
+                        {(col) => {
+                          const files = () => lookup()(row.branchName, col.branchName);
+                          return (
+                            <td
+                              title={files().join('\n')}
+                              style={{
+                                ...cellStyle,
+                                background:
+                                  files().length > 0
+                                    ? `color-mix(in srgb, ${theme.error} 25%, transparent)`
+                                    : 'transparent',
+                                color: row.id === col.id ? theme.fgSubtle : theme.fg,
+                              }}
+                            >
+                              {row.id === col.id ? '–' : files().length || ''}
+                            </td>
+                          );
+                        }}
+                      </For>
+                    </tr>
+                  )}
+                </For>
+              </tbody>
+            </table>
+          </div>
+
+          <Show
+            when={(conflicts() ?? []).length > 0}
+            fallback={
+              <div style={{ 'font-size': '12px', color: theme.success }}>
+                No two tasks conflict.
+              </div>
+            }
+          >
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '4px' }}>
+              <For each={conflicts()}>
+                {(c) => (
+                  <div style={{ 'font-size': '12px', color: theme.fgMuted }}>
+                    <span style={{ color: theme.fg }}>
+                      {taskName(c.branch_a)} ↔ {taskName(c.branch_b)}:
+                    </span>{' '}
+                    <span style={{ 'font-family': "'JetBrains Mono', monospace" }}>
+                      {c.files.join(', ')}
+                    </span>
+                  </div>
+                )}
+              </For>
+            </div>
+          </Show>
+        </Show>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          disabled={conflicts.loading}
+          onClick={() => void refetch()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Re-check
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          Done
+        </button>
+      </div>
+    </Dialog>
+  );
+}
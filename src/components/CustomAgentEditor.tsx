@@ -1,5 +1,5 @@
 import { For, Show, createSignal } from 'solid-js';
-import { store, addCustomAgent, removeCustomAgent } from '../store/store';
+import { store, addCustomAgent, removeCustomAgent, showNotification } from '../store/store';
 import { theme } from '../lib/theme';
 import type { AgentDef } from '../ipc/types';
 
@@ -9,6 +9,16 @@ export function CustomAgentEditor() {
   const [command, setCommand] = createSignal('');
   const [resumeArgs, setResumeArgs] = createSignal('');
   const [skipArgs, setSkipArgs] = createSignal('');
+  const [env, setEnv] = createSignal('');
+
+  function parseEnv(text: string): Record<string, string> {
+    const result: Record<string, string> = {};
+    for (const pair of text.trim().split(/\s+/)) {
+      const eq = pair.indexOf('=');
+      if (eq > 0) result[pair.slice(0, eq)] = pair.slice(eq + 1);
+    }
+    return result;
+  }
 
   function handleAdd() {
     const n = name().trim();
@@ -27,13 +37,19 @@ export function CustomAgentEditor() {
       resume_args: resumeArgs().trim() ? resumeArgs().trim().split(/\s+/) : [],
       skip_permissions_args: skipArgs().trim() ? skipArgs().trim().split(/\s+/) : [],
       description: `Custom agent: ${n}`,
+      env: parseEnv(env()),
     };
-    addCustomAgent(agent);
-    setName('');
-    setCommand('');
-    setResumeArgs('');
-    setSkipArgs('');
-    setShowForm(false);
+    addCustomAgent(agent).then(
+      () => {
+        setName('');
+        setCommand('');
+        setResumeArgs('');
+        setSkipArgs('');
+        setEnv('');
+        setShowForm(false);
+      },
+      (err) => showNotification(`Failed to add agent: ${String(err)}`),
+    );
   }
 
   const inputStyle = () => ({
@@ -76,7 +92,11 @@ export function CustomAgentEditor() {
             </div>
             <button
               type="button"
-              onClick={() => removeCustomAgent(agent.id)}
+              onClick={() =>
+                removeCustomAgent(agent.id).catch((err) =>
+                  showNotification(`Failed to remove agent: ${String(err)}`),
+                )
+              }
               style={{
                 background: 'transparent',
                 border: 'none',
@@ -150,6 +170,13 @@ export function CustomAgentEditor() {
             onInput={(e) => setSkipArgs(e.currentTarget.value)}
             style={inputStyle()}
           />
+          <input
+            type="text"
+            placeholder="Environment (optional, KEY=value space-separated)"
+            value={env()}
+            onInput={(e) => setEnv(e.currentTarget.value)}
+            style={inputStyle()}
+          />
           <div style={{ display: 'flex', gap: '8px', 'justify-content': 'flex-end' }}>
             <button
               type="button"
@@ -5,14 +5,19 @@ import { IPC } from '../../electron/ipc/channels';
 import { createDialogScroll } from '../lib/dialog-scroll';
 import { theme } from '../lib/theme';
 import { sf } from '../lib/fontScale';
-import { parseUnifiedDiff } from '../lib/unified-diff-parser';
+import { appendDiffLines, parseUnifiedDiff } from '../lib/unified-diff-parser';
+import { matchesAnyGlob } from '../../electron/ipc/glob';
 import { evictStaleAnnotations } from '../lib/review-eviction';
 import { ScrollingDiffView } from './ScrollingDiffView';
 import { ReviewCommentsButton, ReviewSidebarPanel } from './ReviewSidebarPanel';
 import { ReviewProvider, useReview } from './ReviewProvider';
 import type { FileDiff } from '../lib/unified-diff-parser';
+import type { DiffOptions, FileDiffRange, MergePreview } from '../ipc/types';
 import type { ReviewAnnotation } from './review-types';
 
+/** Diff lines fetched per "load more" on a truncated file. */
+const DIFF_PAGE_LINES = 5000;
+
 interface DiffViewerDialogProps {
   /** Which file to auto-scroll to (the one the user clicked). Null = closed. */
   scrollToFile: string | null;
@@ -22,6 +27,14 @@ interface DiffViewerDialogProps {
   projectRoot?: string;
   /** Branch name for branch-based fallback when worktree doesn't exist */
   branchName?: string | null;
+  /** Project globs for generated files: collapsed and left out of the totals */
+  generatedPatterns?: string[];
+  /** Show main after merging `branchName` (diffed against main) instead of the task's changes */
+  mergePreview?: boolean;
+  /** Show how this worktree differs from another one's current state instead */
+  compareWorktreePath?: string;
+  /** What `compareWorktreePath` is called in the header, e.g. the other task's name */
+  compareLabel?: string;
   taskId?: string;
   agentId?: string;
 }
@@ -67,6 +80,10 @@ export function DiffViewerDialog(props: DiffViewerDialogProps) {
             onClose={props.onClose}
             projectRoot={props.projectRoot}
             branchName={props.branchName}
+            generatedPatterns={props.generatedPatterns}
+            mergePreview={props.mergePreview}
+            compareWorktreePath={props.compareWorktreePath}
+            compareLabel={props.compareLabel}
             taskId={props.taskId}
             agentId={props.agentId}
           />
@@ -84,6 +101,8 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
   const [loading, setLoading] = createSignal(false);
   const [error, setError] = createSignal('');
   const [searchQuery, setSearchQuery] = createSignal('');
+  const [ignoreWhitespace, setIgnoreWhitespace] = createSignal(false);
+  const [preview, setPreview] = createSignal<MergePreview | null>(null);
 
   let fetchGeneration = 0;
   let searchInputRef: HTMLInputElement | undefined;
@@ -108,34 +127,66 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
   });
 
   createEffect(() => {
+    // An empty target opens the dialog at the top
     const scrollTarget = props.scrollToFile;
-    if (!scrollTarget) return;
+    if (scrollTarget === null) return;
 
     const worktreePath = props.worktreePath;
     const projectRoot = props.projectRoot;
     const branchName = props.branchName;
+    const options: DiffOptions = ignoreWhitespace() ? { ignore_whitespace: 'all' } : {};
     const thisGen = ++fetchGeneration;
 
     setSearchQuery('');
     setLoading(true);
     setError('');
     setParsedFiles([]);
+    setPreview(null);
 
-    const worktreePromise = worktreePath
-      ? invoke<string>(IPC.GetAllFileDiffs, { worktreePath })
-      : Promise.reject(new Error('no worktree'));
-
-    worktreePromise
-      .catch((err: unknown) => {
+    const loadTaskDiff = () =>
+      (worktreePath
+        ? invoke<string>(IPC.GetAllFileDiffs, { worktreePath, options })
+        : Promise.reject(new Error('no worktree'))
+      ).catch((err: unknown) => {
         if (projectRoot && branchName) {
           return invoke<string>(IPC.GetAllFileDiffsFromBranch, {
             projectRoot,
             branchName,
+            options,
           });
         }
         const msg = err instanceof Error ? err.message : String(err);
         throw new Error(`Could not load diffs: ${msg}`);
-      })
+      });
+
+    const loadMergePreview = (root: string, branch: string) =>
+      invoke<MergePreview>(IPC.GetMergePreview, { projectRoot: root, branchName: branch, options })
+        .then((result) => {
+          if (thisGen === fetchGeneration) setPreview(result);
+          return result.diff;
+        })
+        .catch((err: unknown) => {
+          const msg = err instanceof Error ? err.message : String(err);
+          throw new Error(`Could not preview the merge: ${msg}`);
+        });
+
+    const compareWith = props.compareWorktreePath;
+    const loadCrossDiff = (fromWorktree: string) =>
+      invoke<string>(IPC.GetWorktreeCrossDiff, {
+        fromWorktree,
+        toWorktree: worktreePath,
+        options,
+      }).catch((err: unknown) => {
+        const msg = err instanceof Error ? err.message : String(err);
+        throw new Error(`Could not compare the worktrees: ${msg}`);
+      });
+
+    (compareWith
+      ? loadCrossDiff(compareWith)
+      : props.mergePreview && projectRoot && branchName
+        ? loadMergePreview(projectRoot, branchName)
+        : loadTaskDiff()
+    )
       .then((rawDiff) => {
         if (thisGen !== fetchGeneration) return;
         const newFiles = parseUnifiedDiff(rawDiff);
@@ -151,15 +202,38 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
       });
   });
 
+  /** Page the rest of a file the backend truncated into the view. */
+  async function loadMoreLines(path: string) {
+    const file = parsedFiles().find((f) => f.path === path);
+    if (!file?.truncated || !props.worktreePath) return;
+    const thisGen = fetchGeneration;
+    const page = await invoke<FileDiffRange>(IPC.GetFileDiffRange, {
+      worktreePath: props.worktreePath,
+      filePath: path,
+      offset: file.truncated.shownLines,
+      limit: DIFF_PAGE_LINES,
+      options: ignoreWhitespace() ? { ignore_whitespace: 'all' } : {},
+    });
+    if (thisGen !== fetchGeneration) return;
+    setParsedFiles((files) =>
+      files.map((f) => (f.path === path ? appendDiffLines(f, page.lines) : f)),
+    );
+  }
+
+  const isGenerated = (path: string) =>
+    path !== props.scrollToFile && matchesAnyGlob(path, props.generatedPatterns ?? []);
+  const reviewedFiles = () =>
+    parsedFiles().filter((f) => !matchesAnyGlob(f.path, props.generatedPatterns ?? []));
+
   const totalAdded = () =>
-    parsedFiles().reduce(
+    reviewedFiles().reduce(
       (sum, f) =>
         sum + f.hunks.reduce((s, h) => s + h.lines.filter((l) => l.type === 'add').length, 0),
       0,
     );
 
   const totalRemoved = () =>
-    parsedFiles().reduce(
+    reviewedFiles().reduce(
       (sum, f) =>
         sum + f.hunks.reduce((s, h) => s + h.lines.filter((l) => l.type === 'remove').length, 0),
       0,
@@ -204,7 +278,16 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
             'font-weight': '600',
           }}
         >
-          {parsedFiles().length} files changed
+          <Show
+            when={preview()}
+            fallback={
+              props.compareWorktreePath
+                ? `${parsedFiles().length} files differ from ${props.compareLabel ?? 'the other'}`
+                : `${parsedFiles().length} files changed`
+            }
+          >
+            {(p) => `${parsedFiles().length} files differ from ${p().main_branch} after merging`}
+          </Show>
         </span>
         <span
           style={{
@@ -225,10 +308,50 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
           -{totalRemoved()}
         </span>
 
+        <Show when={preview()}>
+          {(p) => (
+            <span
+              style={{ 'font-size': sf(12), color: theme.fgMuted }}
+              title={[
+                ...p().auto_merged_files.map((f) => `auto-merged: ${f}`),
+                ...p().conflicting_files.map((f) => `conflict: ${f}`),
+              ].join('\n')}
+            >
+              {p().auto_merged_files.length} auto-merged
+              <Show when={p().conflicting_files.length > 0}>
+                <span style={{ color: theme.error }}>
+                  , {p().conflicting_files.length} conflicting
+                </span>
+              </Show>
+            </span>
+          )}
+        </Show>
+
         <ReviewCommentsButton />
 
         <span style={{ flex: '1' }} />
 
+        <label
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '4px',
+            'font-size': sf(12),
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'white-space': 'nowrap',
+          }}
+          title="Hide changes that only touch whitespace (git diff -w)"
+        >
+          <input
+            type="checkbox"
+            checked={ignoreWhitespace()}
+            onChange={(e) => setIgnoreWhitespace(e.currentTarget.checked)}
+            style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+          />
+          Ignore whitespace
+        </label>
+
         <input
           ref={searchInputRef}
           type="text"
@@ -313,6 +436,8 @@ function DiffViewerContent(props: DiffViewerDialogProps) {
               onAnnotationDismiss={review.dismissAnnotation}
               onAnnotationUpdate={review.updateAnnotation}
               scrollToAnnotation={review.scrollTarget()}
+              isGenerated={isGenerated}
+              onLoadMore={loadMoreLines}
               onScrollRef={(el) => {
                 diffScrollRef = el;
               }}
@@ -0,0 +1,154 @@
+import { Show, createEffect, createSignal } from 'solid-js';
+import { duplicateTask, LINKED_DUPLICATE_ERROR } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { Task } from '../store/types';
+
+interface DuplicateTaskDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+const optionStyle = {
+  display: 'flex',
+  'align-items': 'center',
+  gap: '8px',
+  cursor: 'pointer',
+  'font-size': '13px',
+  color: theme.fg,
+};
+
+export function DuplicateTaskDialog(props: DuplicateTaskDialogProps) {
+  const [fromMain, setFromMain] = createSignal(false);
+  const [copyUncommitted, setCopyUncommitted] = createSignal(true);
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [message, setMessage] = createSignal('');
+  const linked = () => (props.task.linkedWorktrees?.length ?? 0) > 0;
+
+  createEffect(() => {
+    if (props.open) {
+      setError('');
+      setMessage('');
+    }
+  });
+
+  async function duplicate() {
+    setBusy(true);
+    setError('');
+    setMessage('');
+    try {
+      const result = await duplicateTask(props.task.id, {
+        fromMain: fromMain(),
+        copyUncommitted: copyUncommitted(),
+      });
+      const conflicts = result.conflictedFiles.length;
+      if (conflicts === 0) {
+        props.onClose();
+        return;
+      }
+      setMessage(
+        `Task created; ${conflicts} copied file${conflicts === 1 ? ' was' : 's were'} ` +
+          'left with conflict markers',
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="480px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Duplicate task
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Start a new task with the same agent and settings on a branch of its own, to try another
+        approach. This task and its agent are left as they are.
+      </div>
+
+      <label style={optionStyle}>
+        <input
+          type="radio"
+          name="duplicate-start"
+          checked={!fromMain()}
+          onChange={() => setFromMain(false)}
+          style={{ cursor: 'pointer' }}
+        />
+        Start from the current commit of {props.task.branchName}
+      </label>
+      <label style={optionStyle}>
+        <input
+          type="radio"
+          name="duplicate-start"
+          checked={fromMain()}
+          onChange={() => setFromMain(true)}
+          style={{ cursor: 'pointer' }}
+        />
+        Start from the main branch
+      </label>
+      <label style={optionStyle}>
+        <input
+          type="checkbox"
+          checked={copyUncommitted()}
+          onChange={(e) => setCopyUncommitted(e.currentTarget.checked)}
+          style={{ cursor: 'pointer' }}
+        />
+        Copy uncommitted changes
+      </label>
+
+      <Show when={linked()}>
+        <div style={{ ...bannerStyle(theme.warning), 'font-size': '12px' }}>
+          {LINKED_DUPLICATE_ERROR}
+        </div>
+      </Show>
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+      <Show when={message()}>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted }}>{message()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy() || !!message() || linked()}
+          onClick={() => void duplicate()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Duplicating...' : 'Duplicate'}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
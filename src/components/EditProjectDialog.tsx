@@ -1,15 +1,26 @@
-import { createSignal, createEffect, For, Show } from 'solid-js';
+import { createSignal, createEffect, createResource, For, Show } from 'solid-js';
 import { Dialog } from './Dialog';
+import { WorktreeRepairDialog } from './WorktreeRepairDialog';
+import { TaskLeftoversDialog } from './TaskLeftoversDialog';
+import { ArchivedTasksDialog } from './ArchivedTasksDialog';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
 import {
   updateProject,
   PASTEL_HUES,
   isProjectMissing,
   relinkProject,
   removeProjectWithTasks,
+  showAdoptWorktrees,
 } from '../store/store';
 import { sanitizeBranchPrefix, toBranchName } from '../lib/branch-name';
+import { formatLinkRules, parseLinkRules } from '../lib/link-rules';
+import { formatBytes } from '../lib/format-size';
+import { formatScriptLines, parseScriptLines } from '../lib/task-scripts';
+import { DEFAULT_MERGE_MESSAGE_TEMPLATE, MERGE_STRATEGY_OPTIONS } from '../lib/merge-message';
 import { theme, sectionLabelStyle } from '../lib/theme';
 import type { Project, TerminalBookmark } from '../store/types';
+import type { IgnoredEntry, MergeStrategy, UndoableOperation } from '../ipc/types';
 
 interface EditProjectDialogProps {
   project: Project | null;
@@ -21,14 +32,62 @@ function hueFromColor(color: string): number {
   return match ? Number(match[1]) : 0;
 }
 
+/** Split a multi-line textarea value into trimmed, non-empty commands. */
+function parseCommandLines(text: string): string[] {
+  return text
+    .split('\n')
+    .map((l) => l.trim())
+    .filter((l) => l.length > 0);
+}
+
+const commandListStyle = {
+  background: theme.bgInput,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '8px',
+  padding: '8px 12px',
+  color: theme.fg,
+  'font-size': '12px',
+  'font-family': "'JetBrains Mono', monospace",
+  outline: 'none',
+  resize: 'vertical',
+} as const;
+
 export function EditProjectDialog(props: EditProjectDialogProps) {
   const [name, setName] = createSignal('');
   const [selectedHue, setSelectedHue] = createSignal(0);
   const [branchPrefix, setBranchPrefix] = createSignal('task');
   const [deleteBranchOnClose, setDeleteBranchOnClose] = createSignal(true);
   const [defaultDirectMode, setDefaultDirectMode] = createSignal(false);
+  const [autoInstallDeps, setAutoInstallDeps] = createSignal(false);
   const [bookmarks, setBookmarks] = createSignal<TerminalBookmark[]>([]);
   const [newCommand, setNewCommand] = createSignal('');
+  const [postMergeCommands, setPostMergeCommands] = createSignal('');
+  const [setupCommands, setSetupCommands] = createSignal('');
+  const [scripts, setScripts] = createSignal('');
+  const [preMergeCommands, setPreMergeCommands] = createSignal('');
+  const [preMergeWarnOnly, setPreMergeWarnOnly] = createSignal(false);
+  const [blockOnRedCi, setBlockOnRedCi] = createSignal(false);
+  const [verifyCommand, setVerifyCommand] = createSignal('');
+  const [generatedFiles, setGeneratedFiles] = createSignal('');
+  const [autoFetchMinutes, setAutoFetchMinutes] = createSignal(0);
+  const [commitSigning, setCommitSigning] = createSignal<'' | 'sign' | 'no-sign'>('');
+  const [skipCommitHooks, setSkipCommitHooks] = createSignal(false);
+  const [mergeStrategy, setMergeStrategy] = createSignal<MergeStrategy>('merge');
+  const [mergeMessageTemplate, setMergeMessageTemplate] = createSignal('');
+  const [worktreeDir, setWorktreeDir] = createSignal('');
+  const [linkRules, setLinkRules] = createSignal('');
+  const [ignoredEntries, setIgnoredEntries] = createSignal<IgnoredEntry[] | null>(null);
+  const [scanning, setScanning] = createSignal(false);
+  const [confirmUndo, setConfirmUndo] = createSignal(false);
+  const [showRepair, setShowRepair] = createSignal(false);
+  const [showLeftovers, setShowLeftovers] = createSignal(false);
+  const [showArchived, setShowArchived] = createSignal(false);
+  const [undoing, setUndoing] = createSignal(false);
+  const [undoMessage, setUndoMessage] = createSignal('');
+  const [undoable, { refetch: refetchUndoable }] = createResource(
+    () => (isProjectMissing(props.project?.id ?? '') ? null : props.project?.path),
+    (projectRoot) => invoke<UndoableOperation | null>(IPC.GetUndoableOperation, { projectRoot }),
+  );
   let nameRef!: HTMLInputElement;
 
   // Sync signals when project prop changes
@@ -40,8 +99,27 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
     setBranchPrefix(sanitizeBranchPrefix(p.branchPrefix ?? 'task'));
     setDeleteBranchOnClose(p.deleteBranchOnClose ?? true);
     setDefaultDirectMode(p.defaultDirectMode ?? false);
+    setAutoInstallDeps(p.autoInstallDeps ?? false);
     setBookmarks(p.terminalBookmarks ? [...p.terminalBookmarks] : []);
     setNewCommand('');
+    setPostMergeCommands((p.postMergeCommands ?? []).join('\n'));
+    setSetupCommands((p.setupCommands ?? []).join('\n'));
+    setScripts(formatScriptLines(p.scripts ?? []));
+    setPreMergeCommands((p.preMergeCommands ?? []).join('\n'));
+    setPreMergeWarnOnly(p.preMergeGate === 'warn');
+    setBlockOnRedCi(p.blockMergeOnRedCi ?? false);
+    setVerifyCommand(p.verifyCommand ?? '');
+    setGeneratedFiles((p.generatedFiles ?? []).join('\n'));
+    setAutoFetchMinutes(p.autoFetchMinutes ?? 0);
+    setCommitSigning(p.commitSigning ?? '');
+    setSkipCommitHooks(p.skipCommitHooks ?? false);
+    setMergeStrategy(p.mergeStrategy ?? 'merge');
+    setMergeMessageTemplate(p.mergeMessageTemplate ?? '');
+    setWorktreeDir(p.worktreeDir ?? '');
+    setLinkRules(formatLinkRules(p.linkRules ?? []));
+    setIgnoredEntries(null);
+    setConfirmUndo(false);
+    setUndoMessage('');
     requestAnimationFrame(() => nameRef?.focus());
   });
 
@@ -61,6 +139,47 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
     setBookmarks(bookmarks().filter((b) => b.id !== id));
   }
 
+  async function scanIgnored() {
+    if (!props.project) return;
+    setScanning(true);
+    try {
+      setIgnoredEntries(
+        await invoke<IgnoredEntry[]>(IPC.ScanIgnoredEntries, {
+          projectRoot: props.project.path,
+          worktreeBaseDir: worktreeDir().trim() || undefined,
+        }),
+      );
+    } catch {
+      setIgnoredEntries([]);
+    } finally {
+      setScanning(false);
+    }
+  }
+
+  const hasLinkRule = (path: string) => parseLinkRules(linkRules()).some((r) => r.path === path);
+
+  function addLinkRule(path: string) {
+    const current = linkRules().trimEnd();
+    setLinkRules(current ? `${current}\n${path}` : path);
+  }
+
+  async function undoLastOperation() {
+    if (!props.project) return;
+    setConfirmUndo(false);
+    setUndoing(true);
+    try {
+      const undone = await invoke<UndoableOperation>(IPC.UndoLastOperation, {
+        projectRoot: props.project.path,
+      });
+      setUndoMessage(`Undid the ${undone.operation} of ${undone.branch}`);
+    } catch (err) {
+      setUndoMessage(String(err));
+    } finally {
+      setUndoing(false);
+      refetchUndoable();
+    }
+  }
+
   const canSave = () => name().trim().length > 0;
 
   function handleSave() {
@@ -72,7 +191,23 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
       branchPrefix: sanitizedPrefix,
       deleteBranchOnClose: deleteBranchOnClose(),
       defaultDirectMode: defaultDirectMode(),
+      autoInstallDeps: autoInstallDeps(),
       terminalBookmarks: bookmarks(),
+      postMergeCommands: parseCommandLines(postMergeCommands()),
+      setupCommands: parseCommandLines(setupCommands()),
+      scripts: parseScriptLines(scripts()),
+      preMergeCommands: parseCommandLines(preMergeCommands()),
+      preMergeGate: preMergeWarnOnly() ? 'warn' : 'block',
+      blockMergeOnRedCi: blockOnRedCi(),
+      verifyCommand: verifyCommand().trim(),
+      generatedFiles: parseCommandLines(generatedFiles()),
+      autoFetchMinutes: autoFetchMinutes(),
+      commitSigning: commitSigning() || undefined,
+      skipCommitHooks: skipCommitHooks(),
+      mergeStrategy: mergeStrategy(),
+      mergeMessageTemplate: mergeMessageTemplate().trim(),
+      worktreeDir: worktreeDir().trim(),
+      linkRules: parseLinkRules(linkRules()),
     });
     props.onClose();
   }
@@ -339,6 +474,61 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
               Default to working directly on main branch
             </label>
 
+            {/* Dependency install preference */}
+            <label
+              style={{
+                display: 'flex',
+                'align-items': 'center',
+                gap: '8px',
+                cursor: 'pointer',
+                'font-size': '13px',
+                color: theme.fg,
+              }}
+            >
+              <input
+                type="checkbox"
+                checked={autoInstallDeps()}
+                onChange={(e) => setAutoInstallDeps(e.currentTarget.checked)}
+                style={{ cursor: 'pointer' }}
+              />
+              Install dependencies in new worktrees before sending the prompt
+            </label>
+
+            {/* Worktree setup */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Setup commands</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={setupCommands()}
+                onInput={(e) => setSetupCommands(e.currentTarget.value)}
+                placeholder={'pnpm install\ncp ../../.env .env\ndirenv allow'}
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Run in a shell in each new worktree, after the dependency install, before the
+                prompt is sent. Setup stops at the first failing command.
+              </div>
+            </div>
+
+            {/* Script presets */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Scripts</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={scripts()}
+                onInput={(e) => setScripts(e.currentTarget.value)}
+                placeholder={'Web [server]: pnpm dev\nTests: pnpm test --watch\npnpm lint'}
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                One per line as "Name: command". Each task can run these as background jobs from
+                its Scripts button, with their output kept apart from the agent. Mark dev servers
+                with "Name [server]: command" to give each task its own port in $PORT.
+              </div>
+            </div>
+
             {/* Command Bookmarks */}
             <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
               <label style={sectionLabelStyle}>Command Bookmarks</label>
@@ -438,6 +628,437 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
               </div>
             </div>
 
+            {/* Worktree link candidates */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Worktree files</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={linkRules()}
+                onInput={(e) => setLinkRules(e.currentTarget.value)}
+                placeholder={'.env.local\nnode_modules clone\n!.idea'}
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Gitignored files or directories offered when creating a task. Append "copy", "clone"
+                (copy-on-write, best effort: some macOS volumes make a full copy) or "hardlink" to
+                use that instead of a symlink; prefix with "!" to never offer a path. The mode can
+                also be changed per task.
+              </div>
+              <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+                <span style={{ flex: '1', 'font-size': '11px', color: theme.fgSubtle }}>
+                  List every gitignored entry in the project root with its size.
+                </span>
+                <button
+                  type="button"
+                  onClick={() => void scanIgnored()}
+                  disabled={scanning()}
+                  style={{
+                    padding: '6px 12px',
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '8px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                    'font-size': '12px',
+                  }}
+                >
+                  {scanning() ? 'Scanning...' : 'Scan ignored files'}
+                </button>
+              </div>
+              <Show when={ignoredEntries()}>
+                {(entries) => (
+                  <div
+                    style={{
+                      display: 'flex',
+                      'flex-direction': 'column',
+                      gap: '2px',
+                      'max-height': '160px',
+                      'overflow-y': 'auto',
+                      'font-size': '12px',
+                    }}
+                  >
+                    <Show when={entries().length === 0}>
+                      <span style={{ color: theme.fgSubtle }}>Nothing is gitignored.</span>
+                    </Show>
+                    <For each={entries()}>
+                      {(entry) => (
+                        <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+                          <span
+                            style={{
+                              flex: '1',
+                              color: theme.fg,
+                              'font-family': "'JetBrains Mono', monospace",
+                            }}
+                          >
+                            {entry.path}
+                            {entry.is_dir ? '/' : ''}
+                          </span>
+                          <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                            {entry.size_bytes === null ? '' : formatBytes(entry.size_bytes)}
+                          </span>
+                          <button
+                            type="button"
+                            onClick={() => addLinkRule(entry.path)}
+                            disabled={hasLinkRule(entry.path)}
+                            style={{
+                              padding: '2px 8px',
+                              background: theme.bgInput,
+                              border: `1px solid ${theme.border}`,
+                              'border-radius': '6px',
+                              color: hasLinkRule(entry.path) ? theme.fgSubtle : theme.fg,
+                              cursor: hasLinkRule(entry.path) ? 'default' : 'pointer',
+                              'font-size': '11px',
+                            }}
+                          >
+                            {hasLinkRule(entry.path) ? 'Added' : 'Add'}
+                          </button>
+                        </div>
+                      )}
+                    </For>
+                  </div>
+                )}
+              </Show>
+            </div>
+
+            {/* Post-merge hooks */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Post-merge commands</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={postMergeCommands()}
+                onInput={(e) => setPostMergeCommands(e.currentTarget.value)}
+                placeholder="One command per line, e.g. npm run deploy"
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Run in the project root after a successful merge. Details are available as
+                PARALLEL_CODE_* environment variables (branch, merge commit, line counts).
+              </div>
+            </div>
+
+            {/* Pre-merge checks */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Pre-merge checks</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={preMergeCommands()}
+                onInput={(e) => setPreMergeCommands(e.currentTarget.value)}
+                placeholder={'npm test\ncargo clippy -- -D warnings'}
+                style={commandListStyle}
+              />
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  cursor: 'pointer',
+                  'font-size': '12px',
+                  color: theme.fgMuted,
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={preMergeWarnOnly()}
+                  onChange={(e) => setPreMergeWarnOnly(e.currentTarget.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                Merge anyway when a check fails, with a warning
+              </label>
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  cursor: 'pointer',
+                  'font-size': '12px',
+                  color: theme.fgMuted,
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={blockOnRedCi()}
+                  onChange={(e) => setBlockOnRedCi(e.currentTarget.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                Refuse to merge while the pushed branch's CI is failing
+              </label>
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Run on a clean checkout of the task's committed work before merging, one after
+                another. A failing check refuses the merge.
+              </div>
+            </div>
+
+            {/* Merge strategy */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Merge strategy</label>
+              <select
+                value={mergeStrategy()}
+                onChange={(e) => setMergeStrategy(e.currentTarget.value as MergeStrategy)}
+                style={{
+                  background: theme.bgInput,
+                  border: `1px solid ${theme.border}`,
+                  'border-radius': '8px',
+                  padding: '8px 12px',
+                  color: theme.fg,
+                  'font-size': '12px',
+                  outline: 'none',
+                }}
+              >
+                <For each={MERGE_STRATEGY_OPTIONS}>
+                  {(o) => <option value={o.value}>{o.label}</option>}
+                </For>
+              </select>
+              <input
+                class="input-field"
+                type="text"
+                value={mergeMessageTemplate()}
+                onInput={(e) => setMergeMessageTemplate(e.currentTarget.value)}
+                placeholder={DEFAULT_MERGE_MESSAGE_TEMPLATE}
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Preselected when merging. The template is the merge commit message, with{' '}
+                {'{name}'} and {'{branch}'} standing for the task's name and branch. Squash
+                messages start from the branch's commits, with a conventional-commit prefix when
+                they use one.
+              </div>
+            </div>
+
+            {/* Conflict verification */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Verify command</label>
+              <input
+                class="input-field"
+                type="text"
+                value={verifyCommand()}
+                onInput={(e) => setVerifyCommand(e.currentTarget.value)}
+                placeholder="e.g. npm run build"
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Must pass in the worktree before a rebase continues after the agent resolved its
+                conflicts.
+              </div>
+            </div>
+
+            {/* Worktree directory */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Worktree directory</label>
+              <input
+                class="input-field"
+                type="text"
+                value={worktreeDir()}
+                onInput={(e) => setWorktreeDir(e.currentTarget.value)}
+                placeholder=".worktrees"
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Where new task worktrees are created, e.g. ~/worktrees/{props.project?.name}.
+                Relative paths start at the project root. Existing tasks stay where they are.
+              </div>
+              <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+                <span style={{ flex: '1', 'font-size': '11px', color: theme.fgSubtle }}>
+                  Fix worktrees that were moved or deleted outside the app, or free the space
+                  taken by branches and worktrees of tasks that are gone.
+                </span>
+                <button
+                  type="button"
+                  onClick={() => setShowRepair(true)}
+                  style={{
+                    padding: '6px 12px',
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '8px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                    'font-size': '12px',
+                  }}
+                >
+                  Repair worktrees
+                </button>
+                <button
+                  type="button"
+                  onClick={() => setShowLeftovers(true)}
+                  style={{
+                    padding: '6px 12px',
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '8px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                    'font-size': '12px',
+                  }}
+                >
+                  Clean up old tasks
+                </button>
+                <button
+                  type="button"
+                  onClick={() => setShowArchived(true)}
+                  style={{
+                    padding: '6px 12px',
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '8px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                    'font-size': '12px',
+                  }}
+                >
+                  Archived tasks
+                </button>
+              </div>
+            </div>
+
+            {/* Generated files */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Generated files</label>
+              <textarea
+                class="input-field"
+                rows={3}
+                value={generatedFiles()}
+                onInput={(e) => setGeneratedFiles(e.currentTarget.value)}
+                placeholder={'package-lock.json\ndist/**\n*.snap'}
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                One glob per line. Matching files are still listed in diffs but start collapsed and
+                don't count toward the line totals.
+              </div>
+            </div>
+
+            {/* Undo last merge/rebase */}
+            <Show when={undoable() || undoMessage()}>
+              <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+                <label style={sectionLabelStyle}>Undo last merge or rebase</label>
+                <Show when={undoable()}>
+                  {(op) => (
+                    <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+                      <span style={{ flex: '1', 'font-size': '12px', color: theme.fgMuted }}>
+                        {op().operation === 'merge' ? 'Merge' : 'Rebase'} of {op().branch},{' '}
+                        {new Date(op().created_at).toLocaleString()}
+                      </span>
+                      <button
+                        type="button"
+                        disabled={undoing()}
+                        onClick={() =>
+                          confirmUndo() ? void undoLastOperation() : setConfirmUndo(true)
+                        }
+                        style={{
+                          padding: '6px 12px',
+                          background: theme.bgInput,
+                          border: `1px solid ${theme.border}`,
+                          'border-radius': '8px',
+                          color: confirmUndo() ? theme.warning : theme.fg,
+                          cursor: undoing() ? 'not-allowed' : 'pointer',
+                          'font-size': '12px',
+                        }}
+                      >
+                        {undoing() ? 'Undoing...' : confirmUndo() ? 'Confirm undo' : 'Undo'}
+                      </button>
+                    </div>
+                  )}
+                </Show>
+                <Show when={undoMessage()}>
+                  <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>{undoMessage()}</div>
+                </Show>
+              </div>
+            </Show>
+
+            {/* Adopt existing branches */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Existing branches</label>
+              <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+                <span style={{ flex: '1', 'font-size': '11px', color: theme.fgSubtle }}>
+                  Open worktrees and branches created outside the app as tasks.
+                </span>
+                <button
+                  type="button"
+                  onClick={() => {
+                    const id = project().id;
+                    props.onClose();
+                    showAdoptWorktrees(id);
+                  }}
+                  style={{
+                    padding: '6px 12px',
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '8px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                    'font-size': '12px',
+                  }}
+                >
+                  Find branches
+                </button>
+              </div>
+            </div>
+
+            {/* Background fetch */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Auto-fetch interval (minutes)</label>
+              <input
+                class="input-field"
+                type="number"
+                min="0"
+                value={autoFetchMinutes()}
+                onInput={(e) =>
+                  setAutoFetchMinutes(Math.max(0, Math.floor(Number(e.currentTarget.value) || 0)))
+                }
+                style={commandListStyle}
+              />
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Fetch origin in the background and flag tasks that fall behind main. 0 turns it off.
+              </div>
+            </div>
+
+            {/* Signing and hooks for the app's commits */}
+            <div style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}>
+              <label style={sectionLabelStyle}>Commit signing and hooks</label>
+              <select
+                value={commitSigning()}
+                onChange={(e) => setCommitSigning(e.currentTarget.value as '' | 'sign' | 'no-sign')}
+                style={{
+                  background: theme.bgInput,
+                  border: `1px solid ${theme.border}`,
+                  'border-radius': '8px',
+                  padding: '8px 12px',
+                  color: theme.fg,
+                  'font-size': '12px',
+                  outline: 'none',
+                }}
+              >
+                <option value="">Follow git config (commit.gpgsign)</option>
+                <option value="sign">Always sign</option>
+                <option value="no-sign">Never sign</option>
+              </select>
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  cursor: 'pointer',
+                  'font-size': '12px',
+                  color: theme.fgMuted,
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={skipCommitHooks()}
+                  onChange={(e) => setSkipCommitHooks(e.currentTarget.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                Skip pre-commit and commit-msg hooks (--no-verify)
+              </label>
+              <div style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+                Applies to merge, squash and worktree commits made by the app. Signing uses your
+                gpg or ssh setup from git config and must work without a passphrase prompt. When
+                hooks run and fail, their output is shown with the error.
+              </div>
+            </div>
+
             {/* Buttons */}
             <div
               style={{
@@ -486,6 +1107,21 @@ export function EditProjectDialog(props: EditProjectDialogProps) {
           </>
         )}
       </Show>
+      <WorktreeRepairDialog
+        open={showRepair()}
+        projectId={props.project?.id ?? ''}
+        onClose={() => setShowRepair(false)}
+      />
+      <TaskLeftoversDialog
+        open={showLeftovers()}
+        projectId={props.project?.id ?? ''}
+        onClose={() => setShowLeftovers(false)}
+      />
+      <ArchivedTasksDialog
+        open={showArchived()}
+        projectId={props.project?.id ?? ''}
+        onClose={() => setShowArchived(false)}
+      />
     </Dialog>
   );
 }
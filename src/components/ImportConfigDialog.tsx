@@ -0,0 +1,167 @@
+import { For, Show, createEffect, createSignal } from 'solid-js';
+import { applyConfigImport, configImportChanges, type ConfigImport } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+
+interface ImportConfigDialogProps {
+  /** The config read from the picked file; the dialog is open while set. */
+  imported: ConfigImport | null;
+  onClose: () => void;
+  onImported: (message: string) => void;
+}
+
+/**
+ * What an imported config would change, with the commands it brings in listed
+ * for review: they are only applied when the user ticks the box.
+ */
+export function ImportConfigDialog(props: ImportConfigDialogProps) {
+  const [includeCommands, setIncludeCommands] = createSignal(false);
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+
+  const changes = () => (props.imported ? configImportChanges(props.imported) : []);
+
+  createEffect(() => {
+    if (props.imported) {
+      setIncludeCommands(false);
+      setError('');
+    }
+  });
+
+  async function apply() {
+    const imported = props.imported;
+    if (!imported) return;
+    setBusy(true);
+    setError('');
+    try {
+      const result = await applyConfigImport(imported, includeCommands());
+      props.onImported(
+        `Imported settings, ${result.agentsImported} agent(s) and ` +
+          `${result.projectsAdded} new project(s)` +
+          (changes().length > 0 && !includeCommands() ? ', without commands' : ''),
+      );
+      props.onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.imported !== null} onClose={props.onClose} width="600px" zIndex={1200}>
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Import config
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Settings are replaced and {props.imported?.projects.length ?? 0} new project(s) added.
+        Projects already open are left as they are.
+      </div>
+
+      <Show when={(props.imported?.refused_agents.length ?? 0) > 0}>
+        <div style={{ ...bannerStyle(theme.warning), 'font-size': '12px' }}>
+          Not importing agents that would replace built-in ones:{' '}
+          {props.imported?.refused_agents.join(', ')}
+        </div>
+      </Show>
+
+      <Show
+        when={changes().length > 0}
+        fallback={
+          <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+            The file brings in no commands.
+          </div>
+        }
+      >
+        <div style={{ 'font-size': '12px', color: theme.fg }}>
+          The file brings in commands that run on this machine. Only apply them if you trust
+          where it came from:
+        </div>
+        <div
+          style={{
+            display: 'flex',
+            'flex-direction': 'column',
+            gap: '4px',
+            'max-height': '240px',
+            'overflow-y': 'auto',
+            'font-size': '11px',
+            'font-family': "'JetBrains Mono', monospace",
+          }}
+        >
+          <For each={changes()}>
+            {(change) => (
+              <div>
+                <span style={{ color: theme.fg }}>{change.label}</span>
+                <span style={{ color: theme.fgSubtle, 'word-break': 'break-all' }}>
+                  {' '}
+                  {change.detail}
+                </span>
+              </div>
+            )}
+          </For>
+        </div>
+        <label
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '8px',
+            'font-size': '12px',
+            color: theme.fg,
+            cursor: 'pointer',
+          }}
+        >
+          <input
+            type="checkbox"
+            checked={includeCommands()}
+            onChange={(e) => setIncludeCommands(e.currentTarget.checked)}
+            style={{ cursor: 'pointer' }}
+          />
+          Apply these commands and custom agents too
+        </label>
+      </Show>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Cancel
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy()}
+          onClick={() => void apply()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Importing...' : 'Import'}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
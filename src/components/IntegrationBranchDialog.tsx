@@ -0,0 +1,216 @@
+import { For, Show, createEffect, createSignal, untrack } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, getProjectPath } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { IntegrationBranchResult } from '../ipc/types';
+
+interface IntegrationBranchDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+const checkboxLabelStyle = {
+  display: 'flex',
+  'align-items': 'center',
+  gap: '8px',
+  cursor: 'pointer',
+  'font-size': '13px',
+  color: theme.fg,
+};
+
+export function IntegrationBranchDialog(props: IntegrationBranchDialogProps) {
+  const [selected, setSelected] = createSignal<string[]>([]);
+  const [name, setName] = createSignal('preview/combined');
+  const [replace, setReplace] = createSignal(false);
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [result, setResult] = createSignal<IntegrationBranchResult | null>(null);
+
+  const candidates = () =>
+    store.taskOrder
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.projectId === props.projectId && !t.directMode);
+
+  createEffect(() => {
+    if (props.open) {
+      setSelected(untrack(() => candidates().map((t) => t.branchName)));
+      setError('');
+      setResult(null);
+    }
+  });
+
+  function toggle(branch: string, checked: boolean) {
+    const next = new Set(selected());
+    if (checked) next.add(branch);
+    else next.delete(branch);
+    setSelected(candidates().map((t) => t.branchName).filter((b) => next.has(b)));
+  }
+
+  async function create() {
+    const projectRoot = getProjectPath(props.projectId);
+    if (!projectRoot) return;
+    setBusy(true);
+    setError('');
+    setResult(null);
+    try {
+      setResult(
+        await invoke<IntegrationBranchResult>(IPC.CreateIntegrationBranch, {
+          projectRoot,
+          taskBranches: selected(),
+          name: name().trim(),
+          replace: replace(),
+        }),
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="560px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Combined preview branch
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Merges the selected task branches onto main into a throwaway branch, in sidebar order,
+        without touching any worktree. Branches that conflict with the ones before them are left
+        out.
+      </div>
+
+      <div
+        style={{
+          display: 'flex',
+          'flex-direction': 'column',
+          gap: '4px',
+          'max-height': '200px',
+          'overflow-y': 'auto',
+        }}
+      >
+        <For each={candidates()}>
+          {(task) => (
+            <label style={checkboxLabelStyle}>
+              <input
+                type="checkbox"
+                checked={selected().includes(task.branchName)}
+                onChange={(e) => toggle(task.branchName, e.currentTarget.checked)}
+                style={{ cursor: 'pointer' }}
+              />
+              {task.name}
+              <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>{task.branchName}</span>
+            </label>
+          )}
+        </For>
+      </div>
+
+      <div style={{ display: 'flex', 'align-items': 'center', gap: '8px' }}>
+        <input
+          class="input-field"
+          type="text"
+          value={name()}
+          onInput={(e) => setName(e.currentTarget.value)}
+          style={{
+            flex: '1',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '6px',
+            padding: '6px 8px',
+            color: theme.fg,
+            'font-size': '12px',
+            'font-family': "'JetBrains Mono', monospace",
+            outline: 'none',
+          }}
+        />
+        <label style={{ ...checkboxLabelStyle, 'font-size': '12px' }}>
+          <input
+            type="checkbox"
+            checked={replace()}
+            onChange={(e) => setReplace(e.currentTarget.checked)}
+            style={{ cursor: 'pointer' }}
+          />
+          Replace if it exists
+        </label>
+      </div>
+
+      <Show when={result()}>
+        {(r) => (
+          <div
+            style={{
+              display: 'flex',
+              'flex-direction': 'column',
+              gap: '4px',
+              'font-size': '12px',
+              color: theme.fgMuted,
+            }}
+          >
+            <div style={{ color: theme.fg }}>
+              {r().branch}: {r().merged.length} of {r().merged.length + r().skipped.length} merged
+            </div>
+            <For each={r().skipped}>
+              {(s) => (
+                <div style={{ color: theme.warning }}>
+                  Left out {s.branch}, conflicts in {s.conflicting_files.join(', ')}
+                </div>
+              )}
+            </For>
+            <For each={r().conflicts}>
+              {(c) => (
+                <div>
+                  {c.branch_a} ↔ {c.branch_b}: {c.files.join(', ')}
+                </div>
+              )}
+            </For>
+            <div>Start a task from this branch to run the combined changes.</div>
+          </div>
+        )}
+      </Show>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy() || selected().length === 0 || !name().trim()}
+          onClick={() => void create()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Creating...' : 'Create branch'}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
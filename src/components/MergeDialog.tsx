@@ -1,12 +1,38 @@
-import { Show, For, createSignal, createResource, createEffect } from 'solid-js';
-import { invoke } from '../lib/ipc';
+import {
+  Show,
+  For,
+  createSignal,
+  createResource,
+  createEffect,
+  onCleanup,
+  untrack,
+} from 'solid-js';
+import { invoke, Channel } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
-import { store, mergeTask, sendPrompt } from '../store/store';
+import {
+  store,
+  getProject,
+  getProjectPath,
+  mergeTask,
+  defaultMergeMessage,
+  generateCommitMessage,
+  sendPrompt,
+  resolveConflictsWithAgent,
+} from '../store/store';
 import { ConfirmDialog } from './ConfirmDialog';
 import { ChangedFilesList } from './ChangedFilesList';
 import { theme, bannerStyle } from '../lib/theme';
+import { MERGE_STRATEGY_OPTIONS } from '../lib/merge-message';
 import type { Task } from '../store/types';
-import type { ChangedFile, MergeStatus, WorktreeStatus } from '../ipc/types';
+import type {
+  ChangedFile,
+  ConflictDetails,
+  MergeStatus,
+  MergeStrategy,
+  PullMainResult,
+  RebaseResult,
+  WorktreeStatus,
+} from '../ipc/types';
 
 interface MergeDialogProps {
   open: boolean;
@@ -14,17 +40,34 @@ interface MergeDialogProps {
   initialCleanup: boolean;
   onDone: () => void;
   onDiffFileClick: (file: ChangedFile) => void;
+  /** Show main as it would look after the merge. */
+  onPreviewMerge: () => void;
 }
 
 export function MergeDialog(props: MergeDialogProps) {
   const [mergeError, setMergeError] = createSignal('');
   const [merging, setMerging] = createSignal(false);
-  const [squash, setSquash] = createSignal(false);
+  const [strategy, setStrategy] = createSignal<MergeStrategy>('merge');
   const [cleanupAfterMerge, setCleanupAfterMerge] = createSignal(false);
-  const [squashMessage, setSquashMessage] = createSignal('');
+  const [deleteRemote, setDeleteRemote] = createSignal(false);
+  const [mergeMessage, setMergeMessage] = createSignal('');
+  const [generating, setGenerating] = createSignal(false);
   const [rebasing, setRebasing] = createSignal(false);
   const [rebaseError, setRebaseError] = createSignal('');
   const [rebaseSuccess, setRebaseSuccess] = createSignal(false);
+  const [pauseOnConflict, setPauseOnConflict] = createSignal(false);
+  const [pausedRebase, setPausedRebase] = createSignal<RebaseResult | null>(null);
+  const [pulling, setPulling] = createSignal(false);
+  const [pullMessage, setPullMessage] = createSignal('');
+  const [pullError, setPullError] = createSignal('');
+  const [stashRootChanges, setStashRootChanges] = createSignal(true);
+  const [checkOutput, setCheckOutput] = createSignal('');
+  let checkChannel: Channel<string> | null = null;
+  let checkOutputRef: HTMLPreElement | undefined;
+
+  onCleanup(() => checkChannel?.dispose());
+
+  const preMergeCommands = () => getProject(props.task.projectId)?.preMergeCommands ?? [];
 
   const [branchLog, { refetch: refetchBranchLog }] = createResource(
     () => (props.open ? props.task.worktreePath : null),
@@ -34,30 +77,138 @@ export function MergeDialog(props: MergeDialogProps) {
     () => (props.open ? props.task.worktreePath : null),
     (path) => invoke<WorktreeStatus>(IPC.GetWorktreeStatus, { worktreePath: path }),
   );
+  const [rootStatus, { refetch: refetchRootStatus }] = createResource(
+    () => (props.open ? getProjectPath(props.task.projectId) : null),
+    (path) => invoke<WorktreeStatus>(IPC.GetWorktreeStatus, { worktreePath: path }),
+  );
   const [mergeStatus, { refetch: refetchMergeStatus }] = createResource(
     () => (props.open ? props.task.worktreePath : null),
     (path) => invoke<MergeStatus>(IPC.CheckMergeStatus, { worktreePath: path }),
   );
 
   const hasConflicts = () => (mergeStatus()?.conflicting_files.length ?? 0) > 0;
+  const agentRunning = () =>
+    props.task.agentIds.length > 0 && store.agents[props.task.agentIds[0]]?.status === 'running';
+
+  // Switching strategy starts over from that strategy's default message
+  function chooseStrategy(next: MergeStrategy) {
+    setStrategy(next);
+    setMergeMessage('');
+    defaultMergeMessage(props.task.id, next)
+      .then((msg) => {
+        if (strategy() === next) setMergeMessage(msg ?? '');
+      })
+      .catch(console.warn);
+  }
+
+  async function writeMessageWithAgent() {
+    setGenerating(true);
+    setMergeError('');
+    try {
+      const msg = await generateCommitMessage(props.task.id);
+      if (strategy() === 'squash') setMergeMessage(msg);
+    } catch (err) {
+      setMergeError(String(err));
+    } finally {
+      setGenerating(false);
+    }
+  }
+
+  function refreshAll() {
+    refetchMergeStatus();
+    refetchBranchLog();
+    refetchWorktreeStatus();
+    refetchRootStatus();
+  }
+
+  async function checkPausedRebase() {
+    try {
+      const details = await invoke<ConflictDetails>(IPC.GetConflictDetails, {
+        worktreePath: props.task.worktreePath,
+      });
+      setPausedRebase(
+        details.operation === 'rebase'
+          ? {
+              status: 'conflicted',
+              conflicting_files: details.files.map((f) => f.path),
+              step: null,
+            }
+          : null,
+      );
+    } catch {
+      setPausedRebase(null);
+    }
+  }
+
+  async function runRebaseStep(channel: IPC, args: Record<string, unknown> = {}) {
+    setRebasing(true);
+    setRebaseError('');
+    setRebaseSuccess(false);
+    try {
+      const result = await invoke<RebaseResult | undefined>(channel, {
+        worktreePath: props.task.worktreePath,
+        ...args,
+      });
+      if (result?.status === 'conflicted') {
+        setPausedRebase(result);
+      } else {
+        setPausedRebase(null);
+        if (channel !== IPC.AbortRebase) setRebaseSuccess(true);
+      }
+      refreshAll();
+    } catch (err) {
+      setRebaseError(String(err));
+    } finally {
+      setRebasing(false);
+    }
+  }
+  async function pullMain() {
+    const projectRoot = getProjectPath(props.task.projectId);
+    if (!projectRoot) return;
+    setPulling(true);
+    setPullMessage('');
+    setPullError('');
+    try {
+      const result = await invoke<PullMainResult>(IPC.PullMain, { projectRoot });
+      const n = result.pulled_commits;
+      setPullMessage(
+        n > 0
+          ? `Pulled ${n} commit${n > 1 ? 's' : ''} into ${result.main_branch}`
+          : `${result.main_branch} is up to date`,
+      );
+      refreshAll();
+    } catch (err) {
+      setPullError(String(err));
+    } finally {
+      setPulling(false);
+    }
+  }
+
   const hasCommittedChangesToMerge = () => worktreeStatus()?.has_committed_changes ?? false;
 
   createEffect(() => {
     if (props.open) {
       setCleanupAfterMerge(props.initialCleanup);
-      setSquash(false);
-      setSquashMessage('');
+      untrack(() => chooseStrategy(getProject(props.task.projectId)?.mergeStrategy ?? 'merge'));
       setMergeError('');
       setRebaseError('');
       setRebaseSuccess(false);
       setMerging(false);
       setRebasing(false);
+      setPausedRebase(null);
+      setPulling(false);
+      setPullMessage('');
+      setPullError('');
+      setStashRootChanges(true);
+      setCheckOutput('');
+      void checkPausedRebase();
       // Force fresh data on every open — covers edge cases where
       // createResource source tracking alone misses a refresh
       // (e.g. external rebase by AI agent while dialog was closed).
       refetchBranchLog();
       refetchMergeStatus();
       refetchWorktreeStatus();
+      refetchRootStatus();
     }
   });
 
@@ -93,6 +244,57 @@ export function MergeDialog(props: MergeDialogProps) {
               Nothing to merge: this branch has no committed changes compared to main/master.
             </div>
           </Show>
+          <div
+            style={{
+              'margin-bottom': '12px',
+              display: 'flex',
+              'align-items': 'center',
+              gap: '8px',
+            }}
+          >
+            <button
+              type="button"
+              disabled={pulling()}
+              onClick={() => void pullMain()}
+              title="Fetch origin and fast-forward the local main branch"
+              style={{
+                padding: '6px 14px',
+                background: theme.bgInput,
+                border: `1px solid ${theme.border}`,
+                'border-radius': '8px',
+                color: theme.fg,
+                cursor: pulling() ? 'not-allowed' : 'pointer',
+                'font-size': '12px',
+                opacity: pulling() ? '0.5' : '1',
+              }}
+            >
+              {pulling() ? 'Pulling...' : 'Pull main'}
+            </button>
+            <button
+              type="button"
+              disabled={!hasCommittedChangesToMerge()}
+              onClick={() => props.onPreviewMerge()}
+              title="Diff main against the result of the merge, including auto-merged files"
+              style={{
+                padding: '6px 14px',
+                background: theme.bgInput,
+                border: `1px solid ${theme.border}`,
+                'border-radius': '8px',
+                color: theme.fg,
+                cursor: hasCommittedChangesToMerge() ? 'pointer' : 'not-allowed',
+                'font-size': '12px',
+                opacity: hasCommittedChangesToMerge() ? '1' : '0.5',
+              }}
+            >
+              Preview result
+            </button>
+            <Show when={pullMessage()}>
+              <span style={{ 'font-size': '12px', color: theme.fgMuted }}>{pullMessage()}</span>
+            </Show>
+            <Show when={pullError()}>
+              <span style={{ 'font-size': '12px', color: theme.error }}>{pullError()}</span>
+            </Show>
+          </div>
           <Show when={mergeStatus.loading}>
             <div
               style={{
@@ -147,22 +349,9 @@ export function MergeDialog(props: MergeDialogProps) {
                   <button
                     type="button"
                     disabled={rebasing() || worktreeStatus()?.has_uncommitted_changes}
-                    onClick={async () => {
-                      setRebasing(true);
-                      setRebaseError('');
-                      setRebaseSuccess(false);
-                      try {
-                        await invoke(IPC.RebaseTask, { worktreePath: props.task.worktreePath });
-                        setRebaseSuccess(true);
-                        refetchMergeStatus();
-                        refetchBranchLog();
-                        refetchWorktreeStatus();
-                      } catch (err) {
-                        setRebaseError(String(err));
-                      } finally {
-                        setRebasing(false);
-                      }
-                    }}
+                    onClick={() =>
+                      void runRebaseStep(IPC.RebaseTask, { pauseOnConflict: pauseOnConflict() })
+                    }
                     title={
                       worktreeStatus()?.has_uncommitted_changes
                         ? 'Commit or stash changes before rebasing'
@@ -185,12 +374,7 @@ export function MergeDialog(props: MergeDialogProps) {
                   >
                     {rebasing() ? 'Rebasing...' : 'Rebase onto main'}
                   </button>
-                  <Show
-                    when={
-                      props.task.agentIds.length > 0 &&
-                      store.agents[props.task.agentIds[0]]?.status === 'running'
-                    }
-                  >
+                  <Show when={agentRunning()}>
                     <button
                       type="button"
                       onClick={() => {
@@ -214,6 +398,28 @@ export function MergeDialog(props: MergeDialogProps) {
                     >
                       Rebase with AI
                     </button>
+                    <Show when={hasConflicts() && !worktreeStatus()?.has_uncommitted_changes}>
+                      <button
+                        type="button"
+                        onClick={() => {
+                          props.onDone();
+                          void resolveConflictsWithAgent(props.task.id);
+                        }}
+                        title="Rebase and let the AI agent resolve and verify each conflict"
+                        style={{
+                          padding: '6px 14px',
+                          background: theme.accent,
+                          border: 'none',
+                          'border-radius': '8px',
+                          color: theme.accentText,
+                          cursor: 'pointer',
+                          'font-size': '12px',
+                          'font-weight': '600',
+                        }}
+                      >
+                        Rebase &amp; resolve with AI
+                      </button>
+                    </Show>
                   </Show>
                   <Show when={rebaseSuccess()}>
                     <span style={{ 'font-size': '12px', color: theme.success }}>
@@ -224,9 +430,106 @@ export function MergeDialog(props: MergeDialogProps) {
                     <span style={{ 'font-size': '12px', color: theme.error }}>{rebaseError()}</span>
                   </Show>
                 </div>
+                <label
+                  style={{
+                    display: 'flex',
+                    'align-items': 'center',
+                    gap: '8px',
+                    'margin-bottom': '12px',
+                    'font-size': '12px',
+                    color: theme.fgMuted,
+                    cursor: 'pointer',
+                  }}
+                >
+                  <input
+                    type="checkbox"
+                    checked={pauseOnConflict()}
+                    onChange={(e) => setPauseOnConflict(e.currentTarget.checked)}
+                    style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+                  />
+                  Pause on conflicts instead of aborting the rebase
+                </label>
               </Show>
             )}
           </Show>
+          <Show when={pausedRebase()}>
+            {(paused) => (
+              <div
+                style={{
+                  ...bannerStyle(theme.error),
+                  'margin-bottom': '12px',
+                  'font-size': '12px',
+                }}
+              >
+                <div style={{ 'font-weight': '600' }}>
+                  Rebase paused
+                  {paused().step
+                    ? ` at commit ${paused().step?.current}/${paused().step?.total}`
+                    : ''}
+                  {paused().conflicting_files.length ? ' with conflicts in:' : ''}
+                </div>
+                <ul style={{ margin: '4px 0 8px', 'padding-left': '20px' }}>
+                  <For each={paused().conflicting_files}>{(f) => <li>{f}</li>}</For>
+                </ul>
+                <div style={{ display: 'flex', gap: '8px', 'flex-wrap': 'wrap' }}>
+                  <Show when={agentRunning() && paused().conflicting_files.length > 0}>
+                    <button
+                      type="button"
+                      onClick={() => {
+                        props.onDone();
+                        void resolveConflictsWithAgent(props.task.id);
+                      }}
+                      title="Close dialog and let the AI agent resolve the conflicts"
+                      style={{
+                        padding: '4px 12px',
+                        background: theme.accent,
+                        border: 'none',
+                        'border-radius': '8px',
+                        color: theme.accentText,
+                        cursor: 'pointer',
+                        'font-size': '12px',
+                        'font-weight': '600',
+                      }}
+                    >
+                      Resolve with AI
+                    </button>
+                  </Show>
+                  <button
+                    type="button"
+                    disabled={rebasing()}
+                    onClick={() => void runRebaseStep(IPC.ContinueRebase)}
+                    style={{
+                      padding: '4px 12px',
+                      background: theme.bgInput,
+                      border: `1px solid ${theme.border}`,
+                      'border-radius': '8px',
+                      color: theme.fg,
+                      cursor: rebasing() ? 'not-allowed' : 'pointer',
+                      'font-size': '12px',
+                    }}
+                  >
+                    Continue rebase
+                  </button>
+                  <button
+                    type="button"
+                    disabled={rebasing()}
+                    onClick={() => void runRebaseStep(IPC.AbortRebase)}
+                    style={{
+                      padding: '4px 12px',
+                      background: theme.bgInput,
+                      border: `1px solid ${theme.border}`,
+                      'border-radius': '8px',
+                      color: theme.fg,
+                      cursor: rebasing() ? 'not-allowed' : 'pointer',
+                      'font-size': '12px',
+                    }}
+                  >
+                    Abort rebase
+                  </button>
+                </div>
+              </div>
+            )}
+          </Show>
           <p style={{ margin: '0 0 12px' }}>
             Merge <strong>{props.task.branchName}</strong> into main:
           </p>
@@ -324,6 +627,7 @@ export function MergeDialog(props: MergeDialogProps) {
             <ChangedFilesList
               worktreePath={props.task.worktreePath}
               isActive={props.open}
+              generatedPatterns={getProject(props.task.projectId)?.generatedFiles}
               onFileClick={props.onDiffFileClick}
             />
           </div>
@@ -359,27 +663,77 @@ export function MergeDialog(props: MergeDialogProps) {
           >
             <input
               type="checkbox"
-              checked={squash()}
-              onChange={(e) => {
-                const checked = e.currentTarget.checked;
-                setSquash(checked);
-                if (checked && !squashMessage()) {
-                  const log = branchLog() ?? '';
-                  const msgOnly = log
-                    .split('\n')
-                    .map((l) => l.replace(/^- [a-f0-9]+ /, '- '))
-                    .join('\n');
-                  setSquashMessage(msgOnly);
-                }
-              }}
+              checked={deleteRemote()}
+              onChange={(e) => setDeleteRemote(e.currentTarget.checked)}
               style={{ cursor: 'pointer' }}
             />
-            Squash commits
+            Delete the branch on origin after merge
+          </label>
+          <Show when={rootStatus()?.has_uncommitted_changes}>
+            <label
+              style={{
+                display: 'flex',
+                'align-items': 'center',
+                gap: '8px',
+                'margin-top': '8px',
+                cursor: 'pointer',
+                'font-size': '13px',
+                color: theme.warning,
+              }}
+            >
+              <input
+                type="checkbox"
+                checked={stashRootChanges()}
+                onChange={(e) => setStashRootChanges(e.currentTarget.checked)}
+                style={{ cursor: 'pointer' }}
+              />
+              Project root has uncommitted changes — stash them during the merge and restore
+              them afterwards
+            </label>
+          </Show>
+          <label
+            style={{
+              display: 'flex',
+              'align-items': 'center',
+              gap: '8px',
+              'margin-top': '8px',
+              'font-size': '13px',
+              color: theme.fg,
+            }}
+          >
+            Strategy
+            <select
+              value={strategy()}
+              onChange={(e) => chooseStrategy(e.currentTarget.value as MergeStrategy)}
+              style={{
+                flex: '1',
+                background: theme.bgInput,
+                border: `1px solid ${theme.border}`,
+                'border-radius': '6px',
+                padding: '4px 8px',
+                color: theme.fg,
+                'font-size': '12px',
+                outline: 'none',
+              }}
+            >
+              <For each={MERGE_STRATEGY_OPTIONS}>
+                {(o) => <option value={o.value}>{o.label}</option>}
+              </For>
+            </select>
           </label>
-          <Show when={squash()}>
+          <Show
+            when={strategy() !== 'rebase'}
+            fallback={
+              <div style={{ 'margin-top': '8px', 'font-size': '12px', color: theme.fgMuted }}>
+                The task's commits are rebased onto main in its worktree, which must have no
+                uncommitted changes, and main is fast-forwarded. A conflicting rebase is
+                aborted and nothing is merged.
+              </div>
+            }
+          >
             <textarea
-              value={squashMessage()}
-              onInput={(e) => setSquashMessage(e.currentTarget.value)}
+              value={mergeMessage()}
+              onInput={(e) => setMergeMessage(e.currentTarget.value)}
               placeholder="Commit message..."
               rows={6}
               style={{
@@ -397,6 +751,54 @@ export function MergeDialog(props: MergeDialogProps) {
                 'box-sizing': 'border-box',
               }}
             />
+            <Show when={strategy() === 'squash'}>
+              <button
+                type="button"
+                disabled={generating()}
+                onClick={() => void writeMessageWithAgent()}
+                title="Have an agent read the branch's diff and write a conventional commit message"
+                style={{
+                  'margin-top': '6px',
+                  padding: '6px 14px',
+                  background: theme.bgInput,
+                  border: `1px solid ${theme.border}`,
+                  'border-radius': '8px',
+                  color: theme.fg,
+                  cursor: generating() ? 'not-allowed' : 'pointer',
+                  'font-size': '12px',
+                  opacity: generating() ? '0.5' : '1',
+                }}
+              >
+                {generating() ? 'Writing message...' : 'Write message with agent'}
+              </button>
+            </Show>
+          </Show>
+          <Show when={preMergeCommands().length > 0}>
+            <div style={{ 'margin-top': '12px', 'font-size': '12px', color: theme.fgMuted }}>
+              Checks run first: {preMergeCommands().join(', ')}
+            </div>
+          </Show>
+          <Show when={checkOutput()}>
+            <pre
+              ref={checkOutputRef}
+              style={{
+                margin: '8px 0 0',
+                'font-family': "'JetBrains Mono', monospace",
+                'font-size': '11px',
+                'line-height': '1.5',
+                'white-space': 'pre-wrap',
+                'word-break': 'break-all',
+                padding: '8px 12px',
+                'max-height': '200px',
+                'overflow-y': 'auto',
+                background: theme.bgInput,
+                'border-radius': '8px',
+                border: `1px solid ${theme.border}`,
+                color: theme.fgMuted,
+              }}
+            >
+              {checkOutput()}
+            </pre>
           </Show>
           <Show when={mergeError()}>
             <div
@@ -411,18 +813,41 @@ export function MergeDialog(props: MergeDialogProps) {
           </Show>
         </div>
       }
-      confirmDisabled={merging() || hasConflicts() || !hasCommittedChangesToMerge()}
+      confirmDisabled={
+        merging() || hasConflicts() || pausedRebase() !== null || !hasCommittedChangesToMerge()
+      }
       confirmLoading={merging()}
-      confirmLabel={merging() ? 'Merging...' : squash() ? 'Squash Merge' : 'Merge'}
+      confirmLabel={
+        merging()
+          ? 'Merging...'
+          : strategy() === 'squash'
+            ? 'Squash Merge'
+            : strategy() === 'rebase'
+              ? 'Rebase and Merge'
+              : 'Merge'
+      }
       onConfirm={() => {
         const taskId = props.task.id;
         const onDone = props.onDone;
         setMergeError('');
         setMerging(true);
+        setCheckOutput('');
+        checkChannel?.dispose();
+        const channel = new Channel<string>();
+        checkChannel = channel;
+        channel.onmessage = (text) => {
+          setCheckOutput((prev) => prev + text);
+          requestAnimationFrame(() => {
+            if (checkOutputRef) checkOutputRef.scrollTop = checkOutputRef.scrollHeight;
+          });
+        };
         void mergeTask(taskId, {
-          squash: squash(),
-          message: squash() ? squashMessage() || undefined : undefined,
+          strategy: strategy(),
+          message: strategy() === 'rebase' ? undefined : mergeMessage() || undefined,
           cleanup: cleanupAfterMerge(),
+          deleteRemoteBranch: deleteRemote(),
+          stashRootChanges: (rootStatus()?.has_uncommitted_changes ?? false) && stashRootChanges(),
+          onCheckOutput: channel,
         })
           .then(() => {
             onDone();
@@ -432,6 +857,8 @@ export function MergeDialog(props: MergeDialogProps) {
           })
           .finally(() => {
             setMerging(false);
+            channel.dispose();
+            if (checkChannel === channel) checkChannel = null;
           });
       }}
       onCancel={() => props.onDone()}
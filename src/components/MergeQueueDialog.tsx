@@ -0,0 +1,259 @@
+import { For, Show, createEffect, createSignal, untrack } from 'solid-js';
+import { store, enqueueMerge, getProject } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import { MERGE_STRATEGY_OPTIONS } from '../lib/merge-message';
+import type { MergeQueueEntry, MergeQueueOutcome } from '../store/mergeQueue';
+import type { MergeStrategy } from '../ipc/types';
+
+interface MergeQueueDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+const OUTCOME_LABELS: Record<MergeQueueOutcome, string> = {
+  pending: 'queued',
+  running: 'merging...',
+  merged: 'merged',
+  skipped: 'skipped',
+  conflict: 'conflicts with main',
+  rebase_failed: 'rebase failed',
+  checks_failed: 'pre-merge check failed',
+  merge_failed: 'merge failed',
+  not_run: 'not run',
+};
+
+function outcomeColor(outcome: MergeQueueOutcome): string {
+  if (outcome === 'merged') return theme.success;
+  if (outcome === 'running') return theme.accent;
+  if (outcome === 'pending' || outcome === 'skipped' || outcome === 'not_run') {
+    return theme.fgSubtle;
+  }
+  return theme.error;
+}
+
+const checkboxLabelStyle = {
+  display: 'flex',
+  'align-items': 'center',
+  gap: '8px',
+  cursor: 'pointer',
+  'font-size': '13px',
+  color: theme.fg,
+};
+
+export function MergeQueueDialog(props: MergeQueueDialogProps) {
+  const [selected, setSelected] = createSignal<string[]>([]);
+  const [strategy, setStrategy] = createSignal<MergeStrategy>('merge');
+  const [cleanup, setCleanup] = createSignal(false);
+  const [entries, setEntries] = createSignal<MergeQueueEntry[]>([]);
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+
+  // Merge candidates in sidebar order; direct-mode tasks have nothing to merge
+  const candidates = () =>
+    store.taskOrder
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.projectId === props.projectId && !t.directMode && !t.closingStatus);
+
+  createEffect(() => {
+    if (props.open) {
+      setSelected(untrack(() => candidates().map((t) => t.id)));
+      setStrategy(untrack(() => getProject(props.projectId)?.mergeStrategy ?? 'merge'));
+      setEntries([]);
+      setError('');
+    }
+  });
+
+  function toggle(taskId: string, checked: boolean) {
+    const next = new Set(selected());
+    if (checked) next.add(taskId);
+    else next.delete(taskId);
+    // Keep the sidebar order, which is the merge order
+    setSelected(candidates().map((t) => t.id).filter((id) => next.has(id)));
+  }
+
+  async function run() {
+    setBusy(true);
+    setError('');
+    try {
+      await enqueueMerge(selected(), {
+        strategy: strategy(),
+        cleanup: cleanup(),
+        onProgress: setEntries,
+      });
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="560px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Merge queue
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Merges the selected tasks one after another in sidebar order. Each is rebased onto main
+        as left by the previous merge and runs the project's pre-merge checks. The queue stops at
+        the first task that conflicts or fails.
+      </div>
+
+      <Show
+        when={entries().length === 0}
+        fallback={
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '4px' }}>
+            <For each={entries()}>
+              {(entry) => (
+                <div
+                  style={{
+                    padding: '4px 8px',
+                    background: theme.bgInput,
+                    'border-radius': '6px',
+                    border: `1px solid ${theme.border}`,
+                    'font-size': '12px',
+                  }}
+                >
+                  <div style={{ display: 'flex', 'justify-content': 'space-between', gap: '8px' }}>
+                    <span style={{ color: theme.fg }}>{entry.task_name}</span>
+                    <span style={{ color: outcomeColor(entry.outcome) }}>
+                      {OUTCOME_LABELS[entry.outcome]}
+                    </span>
+                  </div>
+                  <Show when={entry.conflicting_files.length > 0}>
+                    <div
+                      style={{
+                        color: theme.fgMuted,
+                        'font-family': "'JetBrains Mono', monospace",
+                        'margin-top': '2px',
+                      }}
+                    >
+                      {entry.conflicting_files.join(', ')}
+                    </div>
+                  </Show>
+                  <Show when={entry.error}>
+                    <div style={{ color: theme.fgMuted, 'margin-top': '2px' }}>{entry.error}</div>
+                  </Show>
+                </div>
+              )}
+            </For>
+          </div>
+        }
+      >
+        <Show
+          when={candidates().length > 0}
+          fallback={
+            <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+              No tasks with a worktree in this project.
+            </div>
+          }
+        >
+          <div
+            style={{
+              display: 'flex',
+              'flex-direction': 'column',
+              gap: '4px',
+              'max-height': '240px',
+              'overflow-y': 'auto',
+            }}
+          >
+            <For each={candidates()}>
+              {(task) => (
+                <label style={checkboxLabelStyle}>
+                  <input
+                    type="checkbox"
+                    checked={selected().includes(task.id)}
+                    onChange={(e) => toggle(task.id, e.currentTarget.checked)}
+                    style={{ cursor: 'pointer' }}
+                  />
+                  {task.name}
+                  <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                    {task.branchName}
+                  </span>
+                </label>
+              )}
+            </For>
+          </div>
+        </Show>
+
+        <div style={{ height: '1px', background: theme.border }} />
+        <label style={{ ...checkboxLabelStyle, cursor: 'default' }}>
+          Strategy
+          <select
+            value={strategy()}
+            onChange={(e) => setStrategy(e.currentTarget.value as MergeStrategy)}
+            style={{
+              flex: '1',
+              background: theme.bgInput,
+              border: `1px solid ${theme.border}`,
+              'border-radius': '6px',
+              padding: '4px 8px',
+              color: theme.fg,
+              'font-size': '12px',
+              outline: 'none',
+            }}
+          >
+            <For each={MERGE_STRATEGY_OPTIONS}>
+              {(o) => <option value={o.value}>{o.label}</option>}
+            </For>
+          </select>
+        </label>
+        <label style={checkboxLabelStyle}>
+          <input
+            type="checkbox"
+            checked={cleanup()}
+            onChange={(e) => setCleanup(e.currentTarget.checked)}
+            style={{ cursor: 'pointer' }}
+          />
+          Close merged tasks (removes their worktrees)
+        </label>
+      </Show>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <Show when={entries().length === 0}>
+          <button
+            type="button"
+            class="btn-primary"
+            disabled={busy() || selected().length === 0}
+            onClick={() => void run()}
+            style={{
+              padding: '9px 20px',
+              background: theme.accent,
+              border: 'none',
+              'border-radius': '8px',
+              color: theme.accentText,
+              cursor: 'pointer',
+              'font-size': '13px',
+              'font-weight': '500',
+            }}
+          >
+            Merge {selected().length} task{selected().length === 1 ? '' : 's'}
+          </button>
+        </Show>
+      </div>
+    </Dialog>
+  );
+}
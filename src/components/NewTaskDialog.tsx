@@ -1,11 +1,14 @@
-import { createSignal, createEffect, Show, onCleanup } from 'solid-js';
+import { createSignal, createEffect, createResource, For, Show, onCleanup } from 'solid-js';
 import { Dialog } from './Dialog';
 import { invoke } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import {
   store,
   createTask,
+  createTaskBatch,
+  createFanoutTasks,
   createDirectTask,
+  addPendingTask,
   toggleNewTaskDialog,
   loadAgents,
   getProject,
@@ -19,14 +22,17 @@ import {
   setDockerImage,
 } from '../store/store';
 import { toBranchName, sanitizeBranchPrefix } from '../lib/branch-name';
-import { cleanTaskName } from '../lib/clean-task-name';
+import { taskNameFromText } from '../lib/clean-task-name';
+import { parseChecklist } from '../lib/checklist';
+import { formatStartTime, toDateTimeLocalValue } from '../lib/date';
 import { extractGitHubUrl } from '../lib/github-url';
+import { linkModeFor, partitionLinkPaths, type LinkMode } from '../lib/link-rules';
 import { theme, sectionLabelStyle, bannerStyle } from '../lib/theme';
 import { AgentSelector } from './AgentSelector';
 import { BranchPrefixField } from './BranchPrefixField';
 import { ProjectSelect } from './ProjectSelect';
 import { SymlinkDirPicker } from './SymlinkDirPicker';
-import type { AgentDef } from '../ipc/types';
+import type { AgentDef, WorktreeStatus } from '../ipc/types';
 
 interface NewTaskDialogProps {
   open: boolean;
@@ -44,12 +50,61 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
   const [selectedDirs, setSelectedDirs] = createSignal<Set<string>>(new Set());
   const [directMode, setDirectMode] = createSignal(false);
   const [skipPermissions, setSkipPermissions] = createSignal(false);
+  const [autoRestart, setAutoRestart] = createSignal(false);
+  const [lowPriority, setLowPriority] = createSignal(false);
   const [dockerMode, setDockerMode] = createSignal(false);
   const [dockerImageReady, setDockerImageReady] = createSignal<boolean | null>(null); // null = unknown
   const [dockerBuilding, setDockerBuilding] = createSignal(false);
   const [dockerBuildOutput, setDockerBuildOutput] = createSignal('');
   const [dockerBuildError, setDockerBuildError] = createSignal('');
   const [branchPrefix, setBranchPrefix] = createSignal('');
+  const [carryRootChanges, setCarryRootChanges] = createSignal(false);
+  const [fromBranch, setFromBranch] = createSignal('');
+  const [linkedProjectIds, setLinkedProjectIds] = createSignal<Set<string>>(new Set());
+  // '' starts now; otherwise 'at', 'limit' or 'after:<taskId>'
+  const [startWhen, setStartWhen] = createSignal('');
+  const [startTime, setStartTime] = createSignal('');
+  // Tasks in the selected project a new task can wait for
+  const dependencyCandidates = () =>
+    [...store.taskOrder, ...store.collapsedTaskOrder]
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.projectId === selectedProjectId() && !t.directMode);
+  const waitFor = () => dependencyCandidates().find((t) => `after:${t.id}` === startWhen());
+  const limitResetAt = () => {
+    const at = store.usageLimitResetAt;
+    return at !== null && at > Date.now() ? at : null;
+  };
+  const startAt = (): number | undefined => {
+    if (startWhen() === 'limit') return limitResetAt() ?? undefined;
+    if (startWhen() !== 'at') return undefined;
+    const at = new Date(startTime()).getTime();
+    return Number.isNaN(at) ? undefined : at;
+  };
+  const deferred = () => !directMode() && startWhen() !== '';
+  const [splitChecklist, setSplitChecklist] = createSignal(false);
+  const [sendItemPrompts, setSendItemPrompts] = createSignal(true);
+  const checklistItems = () => parseChecklist(prompt());
+  // A pasted TODO list with several open items can become one task per item
+  const batchMode = () =>
+    splitChecklist() && !directMode() && !startWhen() && checklistItems().length > 1;
+  // Extra agents that get the same prompt in their own task, to compare results
+  const [raceAgentIds, setRaceAgentIds] = createSignal<Set<string>>(new Set());
+  const raceCandidates = () =>
+    store.availableAgents.filter((a) => a.id !== selectedAgent()?.id && a.available !== false);
+  const raceAgents = () => raceCandidates().filter((a) => raceAgentIds().has(a.id));
+  const raceMode = () =>
+    raceAgents().length > 0 && !directMode() && !batchMode() && !deferred();
+  const otherProjects = () => store.projects.filter((p) => p.id !== selectedProjectId());
+  const [linkModeOverrides, setLinkModeOverrides] = createSignal<Record<string, LinkMode>>({});
+  const linkRules = () => {
+    const pid = selectedProjectId();
+    return (pid ? getProject(pid)?.linkRules : undefined) ?? [];
+  };
+  // Modes picked in this dialog take precedence over the project's rules
+  const taskLinkRules = () => [
+    ...Object.entries(linkModeOverrides()).map(([path, mode]) => ({ path, mode })),
+    ...linkRules(),
+  ];
   let promptRef!: HTMLTextAreaElement;
   let formRef!: HTMLFormElement;
   let buildOutputRef!: HTMLPreElement;
@@ -112,6 +167,14 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
     setError('');
     setLoading(false);
     setDirectMode(false);
+    setCarryRootChanges(false);
+    setFromBranch('');
+    setLinkedProjectIds(new Set<string>());
+    setStartWhen('');
+    setStartTime('');
+    setSplitChecklist(false);
+    setRaceAgentIds(new Set<string>());
+    setSendItemPrompts(true);
     setSkipPermissions(false);
     setDockerMode(false);
     setDockerImageReady(null);
@@ -180,6 +243,7 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
   createEffect(() => {
     const pid = selectedProjectId();
     const path = pid ? getProjectPath(pid) : undefined;
+    const rules = linkRules();
     let cancelled = false;
 
     if (!path) {
@@ -190,10 +254,15 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
 
     void (async () => {
       try {
-        const dirs = await invoke<string[]>(IPC.GetGitignoredDirs, { projectRoot: path });
+        const dirs = await invoke<string[]>(IPC.GetGitignoredDirs, {
+          projectRoot: path,
+          extraCandidates: rules.filter((r) => r.mode !== 'skip').map((r) => r.path),
+          excludedCandidates: rules.filter((r) => r.mode === 'skip').map((r) => r.path),
+        });
         if (cancelled) return;
         setIgnoredDirs(dirs);
         setSelectedDirs(new Set(dirs)); // all checked by default
+        setLinkModeOverrides({});
       } catch {
         if (cancelled) return;
         setIgnoredDirs([]);
@@ -291,13 +360,7 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
 
   const effectiveName = () => {
     const n = name().trim();
-    if (n) return n;
-    const p = prompt().trim();
-    if (!p) return '';
-    // Use first line, clean filler phrases, truncate at ~40 chars on word boundary
-    const firstLine = cleanTaskName(p.split('\n')[0]);
-    if (firstLine.length <= 40) return firstLine;
-    return firstLine.slice(0, 40).replace(/\s+\S*$/, '') || firstLine.slice(0, 40);
+    return n || taskNameFromText(prompt());
   };
 
   const branchPreview = () => {
@@ -310,6 +373,15 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
     const pid = selectedProjectId();
     return pid ? getProjectPath(pid) : undefined;
   };
+  const [rootStatus] = createResource(
+    () => (props.open ? selectedProjectPath() : undefined),
+    (path) => invoke<WorktreeStatus>(IPC.GetWorktreeStatus, { worktreePath: path }),
+  );
+  const rootDirty = () => rootStatus()?.has_uncommitted_changes ?? false;
+  const [branches] = createResource(
+    () => (props.open ? selectedProjectPath() : undefined),
+    (projectRoot) => invoke<string[]>(IPC.ListBranches, { projectRoot }),
+  );
 
   const directModeDisabled = () => {
     const pid = selectedProjectId();
@@ -346,6 +418,48 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
     setLoading(true);
     setError('');
 
+    if (batchMode()) {
+      await submitBatch(agent, projectId);
+      return;
+    }
+    if (raceMode()) {
+      await submitFanout(n, [agent, ...raceAgents()], projectId);
+      return;
+    }
+    if (deferred()) {
+      const dependency = waitFor();
+      const at = startAt();
+      if (!dependency && (at === undefined || at <= Date.now())) {
+        setError('Pick a start time in the future');
+        setLoading(false);
+        return;
+      }
+      const prefix = sanitizeBranchPrefix(branchPrefix());
+      updateProject(projectId, { branchPrefix: prefix });
+      addPendingTask({
+        name: n,
+        agentDef: agent,
+        projectId,
+        prompt: prompt().trim() || undefined,
+        afterTaskId: dependency?.id,
+        afterTaskName: dependency?.name,
+        startAt: dependency ? undefined : at,
+        branchPrefixOverride: prefix,
+        ...partitionLinkPaths(selectedDirs(), taskLinkRules()),
+        linkedProjectIds: otherProjects()
+          .filter((p) => linkedProjectIds().has(p.id))
+          .map((p) => p.id),
+        skipPermissions: agentSupportsSkipPermissions() && skipPermissions(),
+        autoRestart: autoRestart(),
+        lowPriority: lowPriority(),
+        dockerMode: dockerMode() || undefined,
+        dockerImage: dockerMode() ? store.dockerImage : undefined,
+      });
+      setLoading(false);
+      toggleNewTaskDialog(false);
+      return;
+    }
+
     const p = prompt().trim() || undefined;
     const isFromDrop = !!store.newTaskDropUrl;
     const prefix = sanitizeBranchPrefix(branchPrefix());
@@ -379,6 +493,8 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           initialPrompt: isFromDrop ? undefined : p,
           githubUrl: ghUrl,
           skipPermissions: agentSupportsSkipPermissions() && skipPermissions(),
+          autoRestart: autoRestart(),
+          lowPriority: lowPriority(),
           dockerMode: dockerMode() || undefined,
           dockerImage: dockerMode() ? store.dockerImage : undefined,
         });
@@ -387,13 +503,20 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           name: n,
           agentDef: agent,
           projectId,
-          symlinkDirs: [...selectedDirs()],
+          ...partitionLinkPaths(selectedDirs(), taskLinkRules()),
           initialPrompt: isFromDrop ? undefined : p,
           branchPrefixOverride: prefix,
           githubUrl: ghUrl,
           skipPermissions: agentSupportsSkipPermissions() && skipPermissions(),
+          autoRestart: autoRestart(),
+          lowPriority: lowPriority(),
           dockerMode: dockerMode() || undefined,
           dockerImage: dockerMode() ? store.dockerImage : undefined,
+          carryRootChanges: rootDirty() && carryRootChanges(),
+          fromBranch: fromBranch().trim() || undefined,
+          linkedProjectIds: otherProjects()
+            .filter((p) => linkedProjectIds().has(p.id))
+            .map((p) => p.id),
         });
       }
       // Drop flow: prefill prompt without auto-sending
@@ -408,6 +531,81 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
     }
   }
 
+  async function submitFanout(name: string, agents: AgentDef[], projectId: string) {
+    const prefix = sanitizeBranchPrefix(branchPrefix());
+    try {
+      updateProject(projectId, { branchPrefix: prefix });
+      const { failed } = await createFanoutTasks({
+        name,
+        prompt: prompt().trim(),
+        agentDefs: agents,
+        projectId,
+        ...partitionLinkPaths(selectedDirs(), taskLinkRules()),
+        branchPrefixOverride: prefix,
+        skipPermissions: agentSupportsSkipPermissions() && skipPermissions(),
+        autoRestart: autoRestart(),
+        lowPriority: lowPriority(),
+        dockerMode: dockerMode() || undefined,
+        dockerImage: dockerMode() ? store.dockerImage : undefined,
+        linkedProjectIds: otherProjects()
+          .filter((p) => linkedProjectIds().has(p.id))
+          .map((p) => p.id),
+      });
+      if (failed.length === 0) {
+        toggleNewTaskDialog(false);
+        return;
+      }
+      setError(failed.map((f) => `${f.name}: ${f.error}`).join('\n'));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  async function submitBatch(agent: AgentDef, projectId: string) {
+    const prefix = sanitizeBranchPrefix(branchPrefix());
+    try {
+      updateProject(projectId, { branchPrefix: prefix });
+      const { failed } = await createTaskBatch({
+        items: checklistItems().map((item) => ({
+          name: taskNameFromText(item.title),
+          prompt: item.prompt,
+        })),
+        sendPrompts: sendItemPrompts(),
+        agentDef: agent,
+        projectId,
+        ...partitionLinkPaths(selectedDirs(), taskLinkRules()),
+        branchPrefixOverride: prefix,
+        skipPermissions: agentSupportsSkipPermissions() && skipPermissions(),
+        autoRestart: autoRestart(),
+        lowPriority: lowPriority(),
+        dockerMode: dockerMode() || undefined,
+        dockerImage: dockerMode() ? store.dockerImage : undefined,
+        linkedProjectIds: otherProjects()
+          .filter((p) => linkedProjectIds().has(p.id))
+          .map((p) => p.id),
+      });
+      if (failed.length === 0) {
+        toggleNewTaskDialog(false);
+        return;
+      }
+      // Keep the dialog open with only what still needs creating
+      const failedNames = new Set(failed.map((f) => f.name));
+      setPrompt(
+        checklistItems()
+          .filter((item) => failedNames.has(taskNameFromText(item.title)))
+          .map((item) => `- [ ] ${item.prompt.replace(/\n/g, '\n  ')}`)
+          .join('\n'),
+      );
+      setError(failed.map((f) => `${f.name}: ${f.error}`).join('\n'));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  }
+
   return (
     <Dialog open={props.open} onClose={props.onClose} width="420px" panelStyle={{ gap: '20px' }}>
       <form
@@ -482,81 +680,257 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
               resize: 'vertical',
             }}
           />
-        </div>
-
-        <div
-          data-nav-field="task-name"
-          style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}
-        >
-          <label style={sectionLabelStyle}>
-            Task name{' '}
-            <span style={{ opacity: '0.5', 'text-transform': 'none' }}>
-              (optional — derived from prompt)
-            </span>
-          </label>
-          <input
-            class="input-field"
-            type="text"
-            value={name()}
-            onInput={(e) => setName(e.currentTarget.value)}
-            placeholder={effectiveName() || 'Add user authentication'}
-            style={{
-              background: theme.bgInput,
-              border: `1px solid ${theme.border}`,
-              'border-radius': '8px',
-              padding: '10px 14px',
-              color: theme.fg,
-              'font-size': '13px',
-              outline: 'none',
-            }}
-          />
-          <Show when={directMode() && selectedProjectPath()}>
-            <div
+          <Show when={!directMode() && checklistItems().length > 1}>
+            <label
               style={{
-                'font-size': '11px',
-                'font-family': "'JetBrains Mono', monospace",
-                color: theme.fgSubtle,
                 display: 'flex',
-                'flex-direction': 'column',
-                gap: '2px',
-                padding: '4px 2px 0',
+                'align-items': 'center',
+                gap: '8px',
+                'font-size': '12px',
+                color: theme.fg,
+                cursor: 'pointer',
               }}
             >
-              <span style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}>
-                <svg
-                  width="11"
-                  height="11"
-                  viewBox="0 0 16 16"
-                  fill="currentColor"
-                  style={{ 'flex-shrink': '0' }}
-                >
-                  <path d="M5 3.25a.75.75 0 1 1-1.5 0 .75.75 0 0 1 1.5 0Zm6.25 7.5a.75.75 0 1 0 0-1.5.75.75 0 0 0 0 1.5ZM5 7.75a.75.75 0 1 1-1.5 0 .75.75 0 0 1 1.5 0Zm0 0h5.5a2.5 2.5 0 0 0 2.5-2.5v-.5a.75.75 0 0 0-1.5 0v.5a1 1 0 0 1-1 1H5a3.25 3.25 0 1 0 0 6.5h6.25a.75.75 0 0 0 0-1.5H5a1.75 1.75 0 1 1 0-3.5Z" />
-                </svg>
-                main branch (detected on create)
-              </span>
-              <span style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}>
-                <svg
-                  width="11"
-                  height="11"
-                  viewBox="0 0 16 16"
-                  fill="currentColor"
-                  style={{ 'flex-shrink': '0' }}
-                >
-                  <path d="M1.75 1A1.75 1.75 0 0 0 0 2.75v10.5C0 14.216.784 15 1.75 15h12.5A1.75 1.75 0 0 0 16 13.25v-8.5A1.75 1.75 0 0 0 14.25 3H7.5a.25.25 0 0 1-.2-.1l-.9-1.2C6.07 1.26 5.55 1 5 1H1.75Z" />
-                </svg>
-                {selectedProjectPath()}
-              </span>
-            </div>
+              <input
+                type="checkbox"
+                checked={splitChecklist()}
+                onChange={(e) => setSplitChecklist(e.currentTarget.checked)}
+                style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+              />
+              Create a task for each of the {checklistItems().length} checklist items
+            </label>
+            <Show when={splitChecklist()}>
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  'font-size': '12px',
+                  color: theme.fg,
+                  cursor: 'pointer',
+                  'padding-left': '22px',
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={sendItemPrompts()}
+                  onChange={(e) => setSendItemPrompts(e.currentTarget.checked)}
+                  style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+                />
+                Send each item to its agent right away
+              </label>
+            </Show>
           </Show>
         </div>
 
+        <Show when={!batchMode()}>
+          <div
+            data-nav-field="task-name"
+            style={{ display: 'flex', 'flex-direction': 'column', gap: '8px' }}
+          >
+            <label style={sectionLabelStyle}>
+              Task name{' '}
+              <span style={{ opacity: '0.5', 'text-transform': 'none' }}>
+                (optional — derived from prompt)
+              </span>
+            </label>
+            <input
+              class="input-field"
+              type="text"
+              value={name()}
+              onInput={(e) => setName(e.currentTarget.value)}
+              placeholder={effectiveName() || 'Add user authentication'}
+              style={{
+                background: theme.bgInput,
+                border: `1px solid ${theme.border}`,
+                'border-radius': '8px',
+                padding: '10px 14px',
+                color: theme.fg,
+                'font-size': '13px',
+                outline: 'none',
+              }}
+            />
+            <Show when={directMode() && selectedProjectPath()}>
+              <div
+                style={{
+                  'font-size': '11px',
+                  'font-family': "'JetBrains Mono', monospace",
+                  color: theme.fgSubtle,
+                  display: 'flex',
+                  'flex-direction': 'column',
+                  gap: '2px',
+                  padding: '4px 2px 0',
+                }}
+              >
+                <span style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}>
+                  <svg
+                    width="11"
+                    height="11"
+                    viewBox="0 0 16 16"
+                    fill="currentColor"
+                    style={{ 'flex-shrink': '0' }}
+                  >
+                    <path d="M5 3.25a.75.75 0 1 1-1.5 0 .75.75 0 0 1 1.5 0Zm6.25 7.5a.75.75 0 1 0 0-1.5.75.75 0 0 0 0 1.5ZM5 7.75a.75.75 0 1 1-1.5 0 .75.75 0 0 1 1.5 0Zm0 0h5.5a2.5 2.5 0 0 0 2.5-2.5v-.5a.75.75 0 0 0-1.5 0v.5a1 1 0 0 1-1 1H5a3.25 3.25 0 1 0 0 6.5h6.25a.75.75 0 0 0 0-1.5H5a1.75 1.75 0 1 1 0-3.5Z" />
+                  </svg>
+                  main branch (detected on create)
+                </span>
+                <span style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}>
+                  <svg
+                    width="11"
+                    height="11"
+                    viewBox="0 0 16 16"
+                    fill="currentColor"
+                    style={{ 'flex-shrink': '0' }}
+                  >
+                    <path d="M1.75 1A1.75 1.75 0 0 0 0 2.75v10.5C0 14.216.784 15 1.75 15h12.5A1.75 1.75 0 0 0 16 13.25v-8.5A1.75 1.75 0 0 0 14.25 3H7.5a.25.25 0 0 1-.2-.1l-.9-1.2C6.07 1.26 5.55 1 5 1H1.75Z" />
+                  </svg>
+                  {selectedProjectPath()}
+                </span>
+              </div>
+            </Show>
+          </div>
+        </Show>
+
         <Show when={!directMode()}>
-          <BranchPrefixField
-            branchPrefix={branchPrefix()}
-            branchPreview={branchPreview()}
-            projectPath={selectedProjectPath()}
-            onPrefixChange={setBranchPrefix}
-          />
+          <Show when={!fromBranch().trim()}>
+            <BranchPrefixField
+              branchPrefix={branchPrefix()}
+              branchPreview={branchPreview()}
+              projectPath={selectedProjectPath()}
+              worktreeDir={getProject(selectedProjectId() ?? '')?.worktreeDir}
+              onPrefixChange={setBranchPrefix}
+            />
+          </Show>
+          <Show when={!batchMode() && !deferred()}>
+            <div
+              data-nav-field="from-branch"
+              style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}
+            >
+              <label
+                style={{ 'font-size': '11px', color: theme.fgSubtle, 'white-space': 'nowrap' }}
+              >
+                Start from branch
+              </label>
+              <input
+                class="input-field"
+                type="text"
+                list="new-task-branches"
+                value={fromBranch()}
+                onInput={(e) => setFromBranch(e.currentTarget.value)}
+                placeholder="new branch from HEAD, or origin/feature-x, pull/123/head"
+                style={{
+                  flex: '1',
+                  background: theme.bgInput,
+                  border: `1px solid ${theme.border}`,
+                  'border-radius': '6px',
+                  padding: '4px 8px',
+                  color: theme.fg,
+                  'font-size': '12px',
+                  'font-family': "'JetBrains Mono', monospace",
+                  outline: 'none',
+                }}
+              />
+              <datalist id="new-task-branches">
+                <For each={branches() ?? []}>{(name) => <option value={name} />}</For>
+              </datalist>
+            </div>
+          </Show>
+          <Show when={otherProjects().length > 0}>
+            <div
+              data-nav-field="linked-projects"
+              style={{ display: 'flex', 'flex-wrap': 'wrap', 'align-items': 'center', gap: '10px' }}
+            >
+              <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>Also work in</span>
+              <For each={otherProjects()}>
+                {(project) => (
+                  <label
+                    style={{
+                      display: 'flex',
+                      'align-items': 'center',
+                      gap: '4px',
+                      'font-size': '12px',
+                      color: theme.fg,
+                      cursor: 'pointer',
+                    }}
+                  >
+                    <input
+                      type="checkbox"
+                      checked={linkedProjectIds().has(project.id)}
+                      onChange={(e) => {
+                        const next = new Set(linkedProjectIds());
+                        if (e.currentTarget.checked) next.add(project.id);
+                        else next.delete(project.id);
+                        setLinkedProjectIds(next);
+                      }}
+                      style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+                    />
+                    {project.name}
+                  </label>
+                )}
+              </For>
+            </div>
+          </Show>
+          <Show when={!batchMode()}>
+            <div
+              data-nav-field="start-when"
+              style={{ display: 'flex', 'align-items': 'center', gap: '6px' }}
+            >
+              <label
+                style={{ 'font-size': '11px', color: theme.fgSubtle, 'white-space': 'nowrap' }}
+              >
+                Start
+              </label>
+              <select
+                value={startWhen()}
+                onChange={(e) => {
+                  const value = e.currentTarget.value;
+                  if (value === 'at' && !startTime()) {
+                    setStartTime(toDateTimeLocalValue(new Date(Date.now() + 60 * 60_000)));
+                  }
+                  setStartWhen(value);
+                }}
+                style={{
+                  flex: '1',
+                  background: theme.bgInput,
+                  border: `1px solid ${theme.border}`,
+                  'border-radius': '6px',
+                  padding: '4px 8px',
+                  color: theme.fg,
+                  'font-size': '12px',
+                }}
+              >
+                <option value="">now</option>
+                <option value="at">at a set time</option>
+                <Show when={limitResetAt()}>
+                  {(at) => (
+                    <option value="limit">
+                      when the usage limit resets ({formatStartTime(at())})
+                    </option>
+                  )}
+                </Show>
+                <For each={dependencyCandidates()}>
+                  {(task) => (
+                    <option value={`after:${task.id}`}>after "{task.name}" is merged</option>
+                  )}
+                </For>
+              </select>
+              <Show when={startWhen() === 'at'}>
+                <input
+                  type="datetime-local"
+                  value={startTime()}
+                  onInput={(e) => setStartTime(e.currentTarget.value)}
+                  style={{
+                    background: theme.bgInput,
+                    border: `1px solid ${theme.border}`,
+                    'border-radius': '6px',
+                    padding: '3px 6px',
+                    color: theme.fg,
+                    'font-size': '12px',
+                  }}
+                />
+              </Show>
+            </div>
+          </Show>
         </Show>
 
         <AgentSelector
@@ -564,6 +938,43 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           selectedAgent={selectedAgent()}
           onSelect={setSelectedAgent}
         />
+        <Show when={!directMode() && !batchMode() && !deferred() && raceCandidates().length > 0}>
+          <div
+            data-nav-field="race-agents"
+            style={{ display: 'flex', 'flex-wrap': 'wrap', 'align-items': 'center', gap: '10px' }}
+          >
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Same prompt, also with
+            </span>
+            <For each={raceCandidates()}>
+              {(agent) => (
+                <label
+                  style={{
+                    display: 'flex',
+                    'align-items': 'center',
+                    gap: '6px',
+                    'font-size': '12px',
+                    color: theme.fg,
+                    cursor: 'pointer',
+                  }}
+                >
+                  <input
+                    type="checkbox"
+                    checked={raceAgentIds().has(agent.id)}
+                    onChange={(e) => {
+                      const next = new Set(raceAgentIds());
+                      if (e.currentTarget.checked) next.add(agent.id);
+                      else next.delete(agent.id);
+                      setRaceAgentIds(next);
+                    }}
+                    style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+                  />
+                  {agent.name}
+                </label>
+              )}
+            </For>
+          </div>
+        </Show>
 
         {/* Direct mode toggle */}
         <div
@@ -607,6 +1018,30 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           </Show>
         </div>
 
+        {/* Carry root changes toggle */}
+        <Show when={!directMode() && !batchMode() && !deferred() && rootDirty()}>
+          <div data-nav-field="carry-root-changes">
+            <label
+              style={{
+                display: 'flex',
+                'align-items': 'center',
+                gap: '8px',
+                'font-size': '12px',
+                color: theme.fg,
+                cursor: 'pointer',
+              }}
+            >
+              <input
+                type="checkbox"
+                checked={carryRootChanges()}
+                onChange={(e) => setCarryRootChanges(e.currentTarget.checked)}
+                style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+              />
+              Move uncommitted changes from the project root into the new task
+            </label>
+          </div>
+        </Show>
+
         {/* Skip permissions toggle */}
         <Show when={agentSupportsSkipPermissions()}>
           <div
@@ -655,6 +1090,50 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           </div>
         </Show>
 
+        {/* Auto-restart toggle */}
+        <div data-nav-field="auto-restart">
+          <label
+            style={{
+              display: 'flex',
+              'align-items': 'center',
+              gap: '8px',
+              'font-size': '12px',
+              color: theme.fg,
+              cursor: 'pointer',
+            }}
+          >
+            <input
+              type="checkbox"
+              checked={autoRestart()}
+              onChange={(e) => setAutoRestart(e.currentTarget.checked)}
+              style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+            />
+            Restart and resume the agent if it crashes
+          </label>
+        </div>
+
+        {/* Low priority toggle */}
+        <div data-nav-field="low-priority">
+          <label
+            style={{
+              display: 'flex',
+              'align-items': 'center',
+              gap: '8px',
+              'font-size': '12px',
+              color: theme.fg,
+              cursor: 'pointer',
+            }}
+          >
+            <input
+              type="checkbox"
+              checked={lowPriority()}
+              onChange={(e) => setLowPriority(e.currentTarget.checked)}
+              style={{ 'accent-color': theme.accent, cursor: 'inherit' }}
+            />
+            Run at low CPU/IO priority
+          </label>
+        </div>
+
         {/* Docker isolation toggle */}
         <Show when={store.dockerAvailable}>
           <div
@@ -800,6 +1279,12 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
           <SymlinkDirPicker
             dirs={ignoredDirs()}
             selectedDirs={selectedDirs()}
+            modes={Object.fromEntries(
+              ignoredDirs().map((d): [string, string] => [d, linkModeFor(d, taskLinkRules())]),
+            )}
+            onModeChange={(dir, mode) =>
+              setLinkModeOverrides({ ...linkModeOverrides(), [dir]: mode })
+            }
             onToggle={(dir) => {
               const next = new Set(selectedDirs());
               if (next.has(dir)) next.delete(dir);
@@ -867,7 +1352,13 @@ export function NewTaskDialog(props: NewTaskDialogProps) {
             <Show when={loading()}>
               <span class="inline-spinner" aria-hidden="true" />
             </Show>
-            {loading() ? 'Creating...' : 'Create Task'}
+            {loading()
+              ? 'Creating...'
+              : batchMode()
+                ? `Create ${checklistItems().length} Tasks`
+                : raceMode()
+                  ? `Create ${raceAgents().length + 1} Tasks`
+                  : 'Create Task'}
           </button>
         </div>
       </form>
@@ -1,4 +1,4 @@
-import { createSignal, createEffect, onMount, onCleanup, untrack } from 'solid-js';
+import { createSignal, createEffect, onMount, onCleanup, untrack, Show } from 'solid-js';
 import { fireAndForget } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import {
@@ -18,9 +18,12 @@ import {
   isAgentAskingQuestion,
   getTaskFocusedPanel,
   setTaskFocusedPanel,
+  getQueuedPrompts,
+  queuePrompt,
 } from '../store/store';
 import { theme } from '../lib/theme';
 import { sf } from '../lib/fontScale';
+import { QueuedPromptsDialog } from './QueuedPromptsDialog';
 
 export interface PromptInputHandle {
   getText: () => string;
@@ -295,6 +298,21 @@ export function PromptInput(props: PromptInputProps) {
 
   let sendAbortController: AbortController | undefined;
 
+  const queuedPrompts = () => getQueuedPrompts(props.agentId);
+  const [showQueue, setShowQueue] = createSignal(false);
+
+  /** Queue the current text as a follow-up, delivered when the agent goes idle. */
+  async function handleQueue() {
+    const val = text().trim();
+    if (!val) return;
+    try {
+      await queuePrompt(props.agentId, val);
+      setText('');
+    } catch (e) {
+      console.error('Failed to queue prompt:', e);
+    }
+  }
+
   async function handleSend(mode: 'manual' | 'auto' = 'manual') {
     if (sending()) return;
     // Block sends while the agent is showing a question/dialog.
@@ -363,7 +381,10 @@ export function PromptInput(props: PromptInputProps) {
           disabled={questionActive()}
           onInput={(e) => setText(e.currentTarget.value)}
           onKeyDown={(e) => {
-            if (e.key === 'Enter' && !e.shiftKey) {
+            if (e.key === 'Enter' && e.altKey) {
+              e.preventDefault();
+              void handleQueue();
+            } else if (e.key === 'Enter' && !e.shiftKey) {
               e.preventDefault();
               handleSend();
             }
@@ -371,7 +392,7 @@ export function PromptInput(props: PromptInputProps) {
           placeholder={
             questionActive()
               ? 'Agent is waiting for input in terminal…'
-              : 'Send a prompt... (Enter to send, Shift+Enter for newline)'
+              : 'Send a prompt... (Enter to send, Alt+Enter to queue, Shift+Enter for newline)'
           }
           style={{
             flex: '1',
@@ -387,6 +408,34 @@ export function PromptInput(props: PromptInputProps) {
             opacity: questionActive() ? '0.5' : '1',
           }}
         />
+        <Show when={queuedPrompts().length > 0}>
+          <button
+            type="button"
+            onClick={() => setShowQueue(true)}
+            title={`Queued (sent when the agent is idle):\n${queuedPrompts()
+              .map((p, i) => `${i + 1}. ${p.text}`)
+              .join('\n')}\n\nClick to reorder, remove or send them`}
+            style={{
+              position: 'absolute',
+              right: '6px',
+              top: '6px',
+              padding: '1px 6px',
+              'border-radius': '8px',
+              border: `1px solid ${theme.border}`,
+              background: theme.bgHover,
+              color: theme.fgMuted,
+              cursor: 'pointer',
+              'font-size': sf(10),
+            }}
+          >
+            {queuedPrompts().length} queued
+          </button>
+        </Show>
+        <QueuedPromptsDialog
+          open={showQueue()}
+          agentId={props.agentId}
+          onClose={() => setShowQueue(false)}
+        />
         <button
           class="prompt-send-btn"
           type="button"
@@ -0,0 +1,187 @@
+import { Show, createEffect, createResource, createSignal, untrack } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { createPullRequest, getProjectPath } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import { RemoteSelect } from './RemoteSelect';
+import type { Task } from '../store/types';
+import type { ForgeInfo, PullRequestResult } from '../ipc/types';
+
+interface PullRequestDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+const fieldStyle = {
+  width: '100%',
+  background: theme.bgInput,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '8px',
+  padding: '8px 10px',
+  color: theme.fg,
+  'font-size': '13px',
+  outline: 'none',
+  'box-sizing': 'border-box',
+};
+
+export function PullRequestDialog(props: PullRequestDialogProps) {
+  const [title, setTitle] = createSignal('');
+  const [body, setBody] = createSignal('');
+  const [draft, setDraft] = createSignal(false);
+  const [remote, setRemote] = createSignal('origin');
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [result, setResult] = createSignal<PullRequestResult | null>(null);
+
+  const [forge] = createResource(
+    () => (props.open ? getProjectPath(props.task.projectId) : null),
+    (projectRoot) => invoke<ForgeInfo | null>(IPC.DetectForge, { projectRoot }),
+  );
+  const label = () => forge()?.request_label ?? 'pull request';
+
+  createEffect(() => {
+    if (props.open) {
+      setTitle(untrack(() => props.task.name));
+      setRemote(untrack(() => props.task.pushRemote ?? 'origin'));
+      setBody('');
+      setError('');
+      setResult(null);
+    }
+  });
+
+  async function open() {
+    setBusy(true);
+    setError('');
+    try {
+      setResult(
+        await createPullRequest(props.task.id, {
+          title: title().trim(),
+          body: body(),
+          draft: draft(),
+          remote: remote(),
+        }),
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="560px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Open a {label()}
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Pushes {props.task.branchName} to origin and opens a {label()} against main on{' '}
+        {forge()?.name ?? 'its forge'}, using the gh or glab CLI when it is logged in or the token
+        from Settings otherwise.
+      </div>
+
+      <Show when={!forge.loading && forge() === null}>
+        <div style={{ ...bannerStyle(theme.warning), 'font-size': '12px' }}>
+          origin is not hosted on GitHub, GitLab or Bitbucket.
+        </div>
+      </Show>
+
+      <Show
+        when={result()}
+        fallback={
+          <>
+            <input
+              class="input-field"
+              type="text"
+              value={title()}
+              onInput={(e) => setTitle(e.currentTarget.value)}
+              placeholder="Title"
+              style={fieldStyle}
+            />
+            <textarea
+              value={body()}
+              onInput={(e) => setBody(e.currentTarget.value)}
+              placeholder="Description (optional)"
+              rows={8}
+              style={{ ...fieldStyle, 'font-family': 'inherit', resize: 'vertical' }}
+            />
+            <label
+              style={{
+                display: 'flex',
+                'align-items': 'center',
+                gap: '8px',
+                cursor: 'pointer',
+                'font-size': '13px',
+                color: theme.fg,
+              }}
+            >
+              <input
+                type="checkbox"
+                checked={draft()}
+                onChange={(e) => setDraft(e.currentTarget.checked)}
+                style={{ cursor: 'pointer' }}
+              />
+              Open as draft
+            </label>
+            <RemoteSelect projectId={props.task.projectId} value={remote()} onChange={setRemote} />
+          </>
+        }
+      >
+        {(r) => (
+          <div style={{ 'font-size': '13px', color: theme.fg }}>
+            Opened{' '}
+            <a href={r().url} target="_blank" style={{ color: theme.accent }}>
+              {r().number ? `${r().kind === 'gitlab' ? '!' : '#'}${r().number}` : r().url}
+            </a>
+          </div>
+        )}
+      </Show>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <Show when={!result()}>
+          <button
+            type="button"
+            class="btn-primary"
+            disabled={busy() || !title().trim() || !forge()}
+            onClick={() => void open()}
+            style={{
+              padding: '9px 20px',
+              background: theme.accent,
+              border: 'none',
+              'border-radius': '8px',
+              color: theme.accentText,
+              cursor: 'pointer',
+              'font-size': '13px',
+              'font-weight': '500',
+            }}
+          >
+            {busy() ? 'Opening...' : 'Push and open'}
+          </button>
+        </Show>
+      </div>
+    </Dialog>
+  );
+}
@@ -3,6 +3,7 @@ import { pushTask } from '../store/store';
 import { Channel } from '../lib/ipc';
 import { Dialog } from './Dialog';
 import { theme, bannerStyle } from '../lib/theme';
+import { RemoteSelect } from './RemoteSelect';
 import type { Task } from '../store/types';
 
 interface PushDialogProps {
@@ -17,6 +18,8 @@ export function PushDialog(props: PushDialogProps) {
   const [pushError, setPushError] = createSignal('');
   const [pushing, setPushing] = createSignal(false);
   const [output, setOutput] = createSignal('');
+  const [forceWithLease, setForceWithLease] = createSignal(false);
+  const [remote, setRemote] = createSignal('origin');
   let channel: Channel<string> | null = null;
   let outputRef: HTMLPreElement | undefined;
 
@@ -25,6 +28,7 @@ export function PushDialog(props: PushDialogProps) {
     if (props.open && !pushing()) {
       setPushError('');
       setOutput('');
+      setRemote(props.task.pushRemote ?? 'origin');
     }
   });
 
@@ -53,12 +57,15 @@ export function PushDialog(props: PushDialogProps) {
 
     props.onStart();
 
-    void pushTask(taskId, channel)
+    void pushTask(taskId, channel, { forceWithLease: forceWithLease(), remote: remote() })
       .then(() => {
         onDone(true);
       })
       .catch((err) => {
-        setPushError(String(err));
+        const message = String(err);
+        setPushError(message);
+        // A rebased branch is rejected as non-fast-forward; offer the lease
+        if (/non-fast-forward|fetch first|rejected/.test(message)) setForceWithLease(true);
         onDone(false);
       })
       .finally(() => {
@@ -126,6 +133,30 @@ export function PushDialog(props: PushDialogProps) {
             {pushError()}
           </div>
         </Show>
+        <Show when={!pushing()}>
+          <div style={{ 'margin-top': '12px' }}>
+            <RemoteSelect projectId={props.task.projectId} value={remote()} onChange={setRemote} />
+          </div>
+          <label
+            style={{
+              display: 'flex',
+              'align-items': 'center',
+              gap: '8px',
+              'margin-top': '12px',
+              cursor: 'pointer',
+              'font-size': '13px',
+              color: theme.fg,
+            }}
+          >
+            <input
+              type="checkbox"
+              checked={forceWithLease()}
+              onChange={(e) => setForceWithLease(e.currentTarget.checked)}
+              style={{ cursor: 'pointer' }}
+            />
+            Force with lease (needed after a rebase rewrote the branch)
+          </label>
+        </Show>
       </div>
 
       <div
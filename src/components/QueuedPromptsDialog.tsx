@@ -0,0 +1,169 @@
+import { For, Show, createEffect, createSignal } from 'solid-js';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import {
+  getQueuedPrompts,
+  removeQueuedPrompt,
+  reorderQueuedPrompt,
+  sendNextQueuedPrompt,
+} from '../store/store';
+
+interface QueuedPromptsDialogProps {
+  open: boolean;
+  agentId: string;
+  onClose: () => void;
+}
+
+export function QueuedPromptsDialog(props: QueuedPromptsDialogProps) {
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const prompts = () => getQueuedPrompts(props.agentId);
+
+  createEffect(() => {
+    if (props.open) setError('');
+  });
+
+  // Nothing left to manage once the last prompt is sent or removed
+  createEffect(() => {
+    if (props.open && prompts().length === 0) props.onClose();
+  });
+
+  async function run(action: () => Promise<void>) {
+    setBusy(true);
+    setError('');
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const buttonStyle = {
+    padding: '4px 8px',
+    background: theme.bgInput,
+    border: `1px solid ${theme.border}`,
+    'border-radius': '6px',
+    color: theme.fg,
+    cursor: 'pointer',
+    'font-size': '12px',
+  };
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="520px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Queued prompts
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        The first prompt is sent when the agent finishes its turn or goes quiet, then the next.
+      </div>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{
+          display: 'flex',
+          'flex-direction': 'column',
+          gap: '4px',
+          'max-height': '320px',
+          'overflow-y': 'auto',
+        }}
+      >
+        <For each={prompts()}>
+          {(prompt, index) => (
+            <div
+              style={{
+                display: 'flex',
+                'align-items': 'center',
+                gap: '6px',
+                padding: '6px 8px',
+                'border-radius': '6px',
+                background: theme.bgInput,
+                'font-size': '12px',
+              }}
+            >
+              <div
+                style={{
+                  flex: '1',
+                  'min-width': '0',
+                  color: theme.fg,
+                  'white-space': 'pre-wrap',
+                  'word-break': 'break-word',
+                  'max-height': '60px',
+                  overflow: 'hidden',
+                }}
+                title={prompt.text}
+              >
+                {index() + 1}. {prompt.text}
+              </div>
+              <button
+                type="button"
+                disabled={busy() || index() === 0}
+                onClick={() =>
+                  void run(() => reorderQueuedPrompt(props.agentId, index(), index() - 1))
+                }
+                title="Move up"
+                style={{ ...buttonStyle, opacity: index() === 0 ? '0.4' : '1' }}
+              >
+                &uarr;
+              </button>
+              <button
+                type="button"
+                disabled={busy() || index() === prompts().length - 1}
+                onClick={() =>
+                  void run(() => reorderQueuedPrompt(props.agentId, index(), index() + 1))
+                }
+                title="Move down"
+                style={{
+                  ...buttonStyle,
+                  opacity: index() === prompts().length - 1 ? '0.4' : '1',
+                }}
+              >
+                &darr;
+              </button>
+              <button
+                type="button"
+                disabled={busy()}
+                onClick={() => void run(() => removeQueuedPrompt(props.agentId, prompt.id))}
+                title="Remove from the queue"
+                style={{ ...buttonStyle, color: theme.fgMuted }}
+              >
+                Remove
+              </button>
+            </div>
+          )}
+        </For>
+      </div>
+
+      <div style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px' }}>
+        <button
+          type="button"
+          disabled={busy()}
+          onClick={() => void run(() => sendNextQueuedPrompt(props.agentId))}
+          style={{ ...buttonStyle, padding: '9px 18px', 'font-size': '13px' }}
+        >
+          Send next now
+        </button>
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+      </div>
+    </Dialog>
+  );
+}
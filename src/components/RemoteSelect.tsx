@@ -0,0 +1,58 @@
+import { For, Show, createResource } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { getProjectPath } from '../store/store';
+import { theme } from '../lib/theme';
+import type { GitRemote } from '../ipc/types';
+
+interface RemoteSelectProps {
+  projectId: string;
+  value: string;
+  onChange: (remote: string) => void;
+}
+
+/** Remote picker for push-like actions; hidden when the repo has a single remote. */
+export function RemoteSelect(props: RemoteSelectProps) {
+  const [remotes] = createResource(
+    () => getProjectPath(props.projectId),
+    (projectRoot) => invoke<GitRemote[]>(IPC.ListRemotes, { projectRoot }),
+  );
+
+  return (
+    <Show when={(remotes() ?? []).length > 1}>
+      <label
+        style={{
+          display: 'flex',
+          'align-items': 'center',
+          gap: '8px',
+          'font-size': '13px',
+          color: theme.fg,
+        }}
+      >
+        Push to
+        <select
+          value={props.value}
+          onChange={(e) => props.onChange(e.currentTarget.value)}
+          style={{
+            flex: '1',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '6px',
+            padding: '4px 8px',
+            color: theme.fg,
+            'font-size': '12px',
+            outline: 'none',
+          }}
+        >
+          <For each={remotes()}>
+            {(r) => (
+              <option value={r.name}>
+                {r.name} ({r.push_url})
+              </option>
+            )}
+          </For>
+        </select>
+      </label>
+    </Show>
+  );
+}
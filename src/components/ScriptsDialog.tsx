@@ -0,0 +1,257 @@
+import { For, Show, createEffect, createSignal, onCleanup, untrack } from 'solid-js';
+import { IPC } from '../../electron/ipc/channels';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import {
+  getProject,
+  getTaskJobs,
+  runTaskScript,
+  cancelJob,
+  getJobOutput,
+  openTaskPreview,
+} from '../store/store';
+import type { Task } from '../store/types';
+import type { JobInfo } from '../ipc/types';
+
+interface ScriptsDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+const OUTPUT_CAP = 256 * 1024;
+
+function statusLabel(job: JobInfo): string {
+  switch (job.status) {
+    case 'running':
+      return 'running';
+    case 'succeeded':
+      return 'done';
+    case 'cancelled':
+      return 'cancelled';
+    default:
+      return job.exit_code === null ? 'failed' : `exit ${job.exit_code}`;
+  }
+}
+
+function statusColor(job: JobInfo): string {
+  if (job.status === 'running') return theme.accent;
+  if (job.status === 'succeeded') return theme.success;
+  if (job.status === 'failed') return theme.error;
+  return theme.fgSubtle;
+}
+
+const smallButtonStyle = {
+  padding: '4px 10px',
+  background: theme.bgInput,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '6px',
+  color: theme.fg,
+  cursor: 'pointer',
+  'font-size': '12px',
+};
+
+export function ScriptsDialog(props: ScriptsDialogProps) {
+  const [selectedJobId, setSelectedJobId] = createSignal<string | null>(null);
+  const [output, setOutput] = createSignal('');
+  const [error, setError] = createSignal('');
+
+  const scripts = () => getProject(props.task.projectId)?.scripts ?? [];
+  const jobs = () => getTaskJobs(props.task.id);
+  const selectedJob = () => jobs().find((j) => j.id === selectedJobId());
+
+  createEffect(() => {
+    if (props.open) {
+      setError('');
+      setSelectedJobId(untrack(() => jobs()[0]?.id) ?? null);
+    }
+  });
+
+  // Replay the retained output of the selected job, then follow it live.
+  // Chunks sent before the replay reply are already part of it.
+  createEffect(() => {
+    const jobId = selectedJobId();
+    setOutput('');
+    if (!props.open || !jobId) return;
+    let loaded = false;
+    const off = window.electron.ipcRenderer.on(IPC.JobOutput, (data: unknown) => {
+      const msg = data as { jobId: string; text: string };
+      if (!loaded || msg?.jobId !== jobId) return;
+      setOutput((prev) => (prev + msg.text).slice(-OUTPUT_CAP));
+    });
+    getJobOutput(jobId)
+      .then((text) => {
+        if (selectedJobId() !== jobId) return;
+        setOutput(text);
+        loaded = true;
+      })
+      .catch((err) => setError(String(err)));
+    onCleanup(off);
+  });
+
+  async function run(scriptId: string) {
+    setError('');
+    try {
+      const job = await runTaskScript(props.task.id, scriptId);
+      setSelectedJobId(job.id);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  let outputRef: HTMLPreElement | undefined;
+  createEffect(() => {
+    output();
+    if (outputRef) outputRef.scrollTop = outputRef.scrollHeight;
+  });
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="720px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Scripts
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Run the project's scripts in this task's {props.task.directMode ? 'project' : 'worktree'} as
+        background jobs, separate from the agent's terminal. Dev servers get a free port in $PORT.
+      </div>
+
+      <Show
+        when={scripts().length > 0}
+        fallback={
+          <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+            No scripts yet. Add them under Scripts in the project settings.
+          </div>
+        }
+      >
+        <div style={{ display: 'flex', 'flex-wrap': 'wrap', gap: '6px' }}>
+          <For each={scripts()}>
+            {(script) => (
+              <button
+                type="button"
+                onClick={() => void run(script.id)}
+                title={script.command}
+                style={smallButtonStyle}
+              >
+                ▶ {script.name}
+                {script.devServer ? ' (server)' : ''}
+              </button>
+            )}
+          </For>
+        </div>
+      </Show>
+
+      <Show when={jobs().length > 0}>
+        <div style={{ display: 'flex', 'flex-direction': 'column', gap: '4px' }}>
+          <For each={jobs()}>
+            {(job) => (
+              <div
+                onClick={() => setSelectedJobId(job.id)}
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  padding: '4px 8px',
+                  background: job.id === selectedJobId() ? theme.bgSelected : theme.bgInput,
+                  'border-radius': '6px',
+                  border: `1px solid ${theme.border}`,
+                  cursor: 'pointer',
+                  'font-size': '12px',
+                }}
+              >
+                <span style={{ color: theme.fg, 'font-weight': '500' }}>{job.name}</span>
+                <span
+                  style={{
+                    flex: '1',
+                    color: theme.fgSubtle,
+                    'font-family': "'JetBrains Mono', monospace",
+                    overflow: 'hidden',
+                    'text-overflow': 'ellipsis',
+                    'white-space': 'nowrap',
+                  }}
+                >
+                  {job.command}
+                </span>
+                <Show when={job.port !== null}>
+                  <span style={{ color: theme.fgMuted }}>{job.url ?? `port ${job.port}`}</span>
+                </Show>
+                <span style={{ color: statusColor(job) }}>{statusLabel(job)}</span>
+                <Show when={job.status === 'running' && job.port !== null}>
+                  <button
+                    type="button"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      openTaskPreview(props.task.id).catch((err) => setError(String(err)));
+                    }}
+                    style={smallButtonStyle}
+                  >
+                    Open
+                  </button>
+                </Show>
+                <Show when={job.status === 'running'}>
+                  <button
+                    type="button"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      cancelJob(job.id).catch((err) => setError(String(err)));
+                    }}
+                    style={smallButtonStyle}
+                  >
+                    Cancel
+                  </button>
+                </Show>
+              </div>
+            )}
+          </For>
+        </div>
+      </Show>
+
+      <Show when={selectedJob()}>
+        <pre
+          ref={outputRef}
+          style={{
+            margin: '0',
+            'max-height': '320px',
+            'min-height': '120px',
+            overflow: 'auto',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            padding: '8px 10px',
+            color: theme.fg,
+            'font-size': '12px',
+            'font-family': "'JetBrains Mono', monospace",
+            'white-space': 'pre-wrap',
+            'word-break': 'break-all',
+          }}
+        >
+          {output() || 'No output yet.'}
+        </pre>
+      </Show>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+      </div>
+    </Dialog>
+  );
+}
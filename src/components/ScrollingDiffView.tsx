@@ -1,9 +1,10 @@
-import { For, Show, createSignal, createEffect, onMount, onCleanup } from 'solid-js';
+import { For, Show, createSignal, createEffect, onMount, onCleanup, untrack } from 'solid-js';
 import type { JSX } from 'solid-js';
 import { theme } from '../lib/theme';
 import { sf } from '../lib/fontScale';
 import { getStatusColor } from '../lib/status-colors';
 import { openFileInEditor } from '../lib/shell';
+import { formatBytes } from '../lib/format-size';
 import { highlightLines, detectLang } from '../lib/shiki-highlighter';
 import { invoke } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
@@ -26,6 +27,10 @@ interface ScrollingDiffViewProps {
   onAnnotationUpdate: (id: string, comment: string) => void;
   scrollToAnnotation?: ReviewAnnotation | null;
   onScrollRef?: (el: HTMLDivElement) => void;
+  /** Generated files (lockfiles, build output) start collapsed. */
+  isGenerated?: (path: string) => boolean;
+  /** Fetch the next page of a file whose diff came back truncated. */
+  onLoadMore?: (path: string) => Promise<void>;
 }
 
 const STATUS_LABELS: Record<string, string> = {
@@ -323,6 +328,7 @@ function GapView(props: {
         worktreePath: props.worktreePath,
         filePath: props.filePath,
       });
+      if (result.truncated?.content_omitted) throw new Error('file too large to expand');
       const fileLines = result.newContent.split('\n');
       const gapLines: DiffLine[] = [];
       for (let n = props.startLine; n < props.endLine; n++) {
@@ -387,6 +393,51 @@ function GapView(props: {
   );
 }
 
+/** Footer of a diff cut short by the backend, paging in the rest on click. */
+function TruncatedFooter(props: {
+  truncated: { shownLines: number; totalLines: number };
+  onLoadMore?: () => Promise<void>;
+}) {
+  const [loading, setLoading] = createSignal(false);
+  const [error, setError] = createSignal('');
+
+  async function loadMore() {
+    if (!props.onLoadMore || loading()) return;
+    setLoading(true);
+    setError('');
+    try {
+      await props.onLoadMore();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  return (
+    <div
+      onClick={() => void loadMore()}
+      style={{
+        padding: '2px 0',
+        'text-align': 'center',
+        color: error() ? theme.error : theme.fgSubtle,
+        'font-size': sf(11),
+        'font-family': "'JetBrains Mono', monospace",
+        background: theme.bgElevated,
+        'border-top': `1px solid ${theme.borderSubtle}`,
+        'user-select': 'none',
+        cursor: props.onLoadMore ? 'pointer' : 'default',
+      }}
+    >
+      {loading()
+        ? 'Loading...'
+        : error() ||
+          `Showing ${props.truncated.shownLines} of ${props.truncated.totalLines} diff lines` +
+            (props.onLoadMore ? ' — load more' : '')}
+    </div>
+  );
+}
+
 function TrailingGap(props: {
   lastHunk: Hunk;
   lang: string;
@@ -406,6 +457,7 @@ function TrailingGap(props: {
       worktreePath: props.worktreePath,
       filePath: props.filePath,
     });
+    if (result.truncated?.content_omitted) throw new Error('file too large to expand');
     const fileLines = result.newContent.split('\n');
     const totalLines = result.newContent.endsWith('\n') ? fileLines.length - 1 : fileLines.length;
     const startLine = props.lastHunk.newStart + props.lastHunk.newCount;
@@ -510,9 +562,84 @@ function TrailingGap(props: {
   );
 }
 
+function formatSize(bytes: number | null): string {
+  return bytes === null ? 'none' : formatBytes(bytes);
+}
+
+/** Before/after view for image files; plain notice for other binaries. */
+function BinaryFileView(props: { worktreePath: string; filePath: string }) {
+  const [image, setImage] = createSignal<FileDiffResult['image'] | null>(null);
+  const [lfs, setLfs] = createSignal<FileDiffResult['lfs'] | null>(null);
+
+  onMount(() => {
+    if (!props.worktreePath) return;
+    invoke<FileDiffResult>(IPC.GetFileDiff, {
+      worktreePath: props.worktreePath,
+      filePath: props.filePath,
+    })
+      .then((result) => {
+        setImage(result.image ?? null);
+        setLfs(result.lfs ?? null);
+      })
+      .catch(() => {});
+  });
+
+  const side = (label: string, data: string | null, mime: string) => (
+    <div style={{ flex: '1', 'min-width': '0', 'text-align': 'center' }}>
+      <div style={{ color: theme.fgSubtle, 'font-size': sf(11), 'margin-bottom': '6px' }}>
+        {label}
+      </div>
+      <Show
+        when={data}
+        fallback={<div style={{ color: theme.fgSubtle, 'font-size': sf(11) }}>(none)</div>}
+      >
+        {(d) => (
+          <img
+            src={`data:${mime};base64,${d()}`}
+            alt={`${label}: ${props.filePath}`}
+            style={{ 'max-width': '100%', 'max-height': '400px' }}
+          />
+        )}
+      </Show>
+    </div>
+  );
+
+  return (
+    <Show
+      when={image()}
+      fallback={
+        <div
+          style={{
+            padding: '24px',
+            'text-align': 'center',
+            color: theme.fgMuted,
+            'font-size': sf(12),
+          }}
+        >
+          <Show when={lfs()} fallback="Binary file — cannot display diff">
+            {(l) => (
+              <>
+                Git LFS object: {formatSize(l().old_size)} → {formatSize(l().new_size)}
+              </>
+            )}
+          </Show>
+        </div>
+      }
+    >
+      {(img) => (
+        <div style={{ display: 'flex', gap: '16px', padding: '16px' }}>
+          {side('Before', img().old, img().mime)}
+          {side('After', img().new, img().mime)}
+        </div>
+      )}
+    </Show>
+  );
+}
+
 function FileSection(props: {
   file: FileDiff;
   worktreePath: string;
+  generated?: boolean;
   ref: (el: HTMLDivElement) => void;
   dimmed: boolean;
   searchQuery?: string;
@@ -525,8 +652,9 @@ function FileSection(props: {
   pendingInput?: { filePath: string; afterLine: number } | null;
   onSubmit: (text: string, mode: 'review' | 'ask') => void;
   onDismiss: () => void;
+  onLoadMore?: () => Promise<void>;
 }) {
-  const [collapsed, setCollapsed] = createSignal(false);
+  const [collapsed, setCollapsed] = createSignal(untrack(() => props.generated ?? false));
   const lang = () => detectLang(props.file.path);
   const added = () =>
     props.file.hunks.reduce((s, h) => s + h.lines.filter((l) => l.type === 'add').length, 0);
@@ -653,16 +781,7 @@ function FileSection(props: {
       {/* File body */}
       <Show when={!collapsed()}>
         <Show when={props.file.binary}>
-          <div
-            style={{
-              padding: '24px',
-              'text-align': 'center',
-              color: theme.fgMuted,
-              'font-size': sf(12),
-            }}
-          >
-            Binary file — cannot display diff
-          </div>
+          <BinaryFileView worktreePath={props.worktreePath} filePath={props.file.path} />
         </Show>
 
         <Show when={!props.file.binary}>
@@ -768,7 +887,16 @@ function FileSection(props: {
                 </>
               )}
             </For>
-            <Show when={props.file.hunks.length > 0 && props.file.status !== 'D'}>
+            <Show when={props.file.truncated}>
+              {(truncated) => (
+                <TruncatedFooter truncated={truncated()} onLoadMore={props.onLoadMore} />
+              )}
+            </Show>
+            <Show
+              when={
+                props.file.hunks.length > 0 && props.file.status !== 'D' && !props.file.truncated
+              }
+            >
               <TrailingGap
                 lastHunk={props.file.hunks[props.file.hunks.length - 1]}
                 lang={lang()}
@@ -801,6 +929,8 @@ export function ScrollingDiffView(props: ScrollingDiffViewProps) {
 
   onCleanup(() => clearTimeout(dimTimer));
 
+  const loadMore = (path: string) => props.onLoadMore?.(path) ?? Promise.resolve();
+
   /** Scroll to a file section when scrollToPath changes. */
   createEffect(() => {
     const target = props.scrollToPath;
@@ -940,6 +1070,7 @@ export function ScrollingDiffView(props: ScrollingDiffViewProps) {
           <FileSection
             file={file}
             worktreePath={props.worktreePath}
+            generated={props.isGenerated?.(file.path)}
             ref={(el) => sectionRefs.set(file.path, el)}
             dimmed={dimOthers() && file.path !== props.scrollToPath}
             searchQuery={props.searchQuery}
@@ -957,6 +1088,7 @@ export function ScrollingDiffView(props: ScrollingDiffViewProps) {
             })()}
             onSubmit={handleSubmit}
             onDismiss={dismissInput}
+            onLoadMore={props.onLoadMore && (() => loadMore(file.path))}
           />
         )}
       </For>
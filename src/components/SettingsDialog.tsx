@@ -1,4 +1,4 @@
-import { For, Show, createMemo } from 'solid-js';
+import { For, Show, createMemo, createSignal } from 'solid-js';
 import { Dialog } from './Dialog';
 import { getAvailableTerminalFonts, getTerminalFontFamily, LIGATURE_FONTS } from '../lib/fonts';
 import { LOOK_PRESETS } from '../lib/look';
@@ -8,16 +8,49 @@ import {
   setTerminalFont,
   setThemePreset,
   setAutoTrustFolders,
+  setPermanentlyDeleteWorktrees,
+  setRecordSessions,
+  setOsc52Clipboard,
+  setIdleTimeout,
+  setMaxRunningAgents,
   setShowPlans,
   setDesktopNotificationsEnabled,
   setInactiveColumnOpacity,
   setEditorCommand,
+  setForgeToken,
   setDockerImage,
+  exportConfig,
+  readConfigImport,
+  type ConfigImport,
 } from '../store/store';
 import { CustomAgentEditor } from './CustomAgentEditor';
+import { AutoResponseEditor } from './AutoResponseEditor';
+import { AgentArgsEditor } from './AgentArgsEditor';
+import { ImportConfigDialog } from './ImportConfigDialog';
 import { mod } from '../lib/platform';
 import type { TerminalFont } from '../lib/fonts';
 
+const tokenInputStyle = {
+  background: theme.taskPanelBg,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '6px',
+  padding: '6px 10px',
+  color: theme.fg,
+  'font-size': '13px',
+  'font-family': "'JetBrains Mono', monospace",
+  outline: 'none',
+};
+
+const tokenButtonStyle = {
+  padding: '5px 12px',
+  background: theme.bgInput,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '6px',
+  color: theme.fgMuted,
+  cursor: 'pointer',
+  'font-size': '12px',
+};
+
 interface SettingsDialogProps {
   open: boolean;
   onClose: () => void;
@@ -30,6 +63,48 @@ export function SettingsDialog(props: SettingsDialogProps) {
     if (available.includes(store.terminalFont)) return available;
     return [store.terminalFont, ...available];
   });
+  const [configMessage, setConfigMessage] = createSignal('');
+  const [pendingImport, setPendingImport] = createSignal<ConfigImport | null>(null);
+  const [tokenHost, setTokenHost] = createSignal('');
+  const [tokenValue, setTokenValue] = createSignal('');
+  const [tokenError, setTokenError] = createSignal('');
+
+  async function saveToken(host: string, token: string) {
+    setTokenError('');
+    try {
+      await setForgeToken(host, token);
+      if (token) {
+        setTokenHost('');
+        setTokenValue('');
+      }
+    } catch (err) {
+      setTokenError(String(err));
+    }
+  }
+
+  async function runConfigAction(action: 'export' | 'import') {
+    setConfigMessage('');
+    try {
+      if (action === 'export') {
+        const filePath = await exportConfig();
+        if (filePath) setConfigMessage(`Exported to ${filePath}`);
+        return;
+      }
+      setPendingImport(await readConfigImport());
+    } catch (err) {
+      setConfigMessage(String(err));
+    }
+  }
+
+  const configBtn = {
+    padding: '6px 12px',
+    background: theme.bgInput,
+    border: `1px solid ${theme.border}`,
+    'border-radius': '8px',
+    color: theme.fg,
+    cursor: 'pointer',
+    'font-size': '12px',
+  };
 
   return (
     <Dialog
@@ -173,6 +248,175 @@ export function SettingsDialog(props: SettingsDialogProps) {
             </span>
           </div>
         </label>
+        <label
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '10px',
+            cursor: 'pointer',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <input
+            type="checkbox"
+            checked={store.permanentlyDeleteWorktrees}
+            onChange={(e) => setPermanentlyDeleteWorktrees(e.currentTarget.checked)}
+            style={{ 'accent-color': theme.accent, cursor: 'pointer' }}
+          />
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px' }}>
+            <span style={{ 'font-size': '13px', color: theme.fg }}>
+              Permanently delete worktrees
+            </span>
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Skip the system trash when closing a task (uncommitted work cannot be recovered)
+            </span>
+          </div>
+        </label>
+        <label
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '10px',
+            cursor: 'pointer',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <input
+            type="checkbox"
+            checked={store.recordSessions}
+            onChange={(e) => setRecordSessions(e.currentTarget.checked)}
+            style={{ 'accent-color': theme.accent, cursor: 'pointer' }}
+          />
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px' }}>
+            <span style={{ 'font-size': '13px', color: theme.fg }}>Record agent sessions</span>
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Save each agent session as an asciinema recording you can export and replay
+            </span>
+          </div>
+        </label>
+        <label
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '10px',
+            cursor: 'pointer',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <input
+            type="checkbox"
+            checked={store.osc52Clipboard}
+            onChange={(e) => setOsc52Clipboard(e.currentTarget.checked)}
+            style={{ 'accent-color': theme.accent, cursor: 'pointer' }}
+          />
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px' }}>
+            <span style={{ 'font-size': '13px', color: theme.fg }}>
+              Allow terminal clipboard writes
+            </span>
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Let programs in agent terminals copy to the system clipboard (OSC 52)
+            </span>
+          </div>
+        </label>
+        <div
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '10px',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px', flex: '1' }}>
+            <span style={{ 'font-size': '13px', color: theme.fg }}>Idle timeout</span>
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Stop or suspend agents with no output or input for this many minutes (0 = never)
+            </span>
+          </div>
+          <input
+            type="number"
+            min="0"
+            step="5"
+            value={store.idleTimeoutMinutes}
+            onChange={(e) =>
+              setIdleTimeout(Number(e.currentTarget.value) || 0, store.idleTimeoutAction)
+            }
+            style={{
+              width: '64px',
+              padding: '4px 6px',
+              background: theme.bgElevated,
+              border: `1px solid ${theme.border}`,
+              'border-radius': '6px',
+              color: theme.fg,
+              'font-size': '12px',
+            }}
+          />
+          <select
+            value={store.idleTimeoutAction}
+            onChange={(e) =>
+              setIdleTimeout(
+                store.idleTimeoutMinutes,
+                e.currentTarget.value === 'suspend' ? 'suspend' : 'kill',
+              )
+            }
+            style={{
+              padding: '4px 6px',
+              background: theme.bgElevated,
+              border: `1px solid ${theme.border}`,
+              'border-radius': '6px',
+              color: theme.fg,
+              'font-size': '12px',
+            }}
+          >
+            <option value="kill">Stop</option>
+            <option value="suspend">Suspend</option>
+          </select>
+        </div>
+        <div
+          style={{
+            display: 'flex',
+            'align-items': 'center',
+            gap: '10px',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '2px', flex: '1' }}>
+            <span style={{ 'font-size': '13px', color: theme.fg }}>Max running agents</span>
+            <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+              Further agents wait and start as running ones exit (0 = no limit)
+            </span>
+          </div>
+          <input
+            type="number"
+            min="0"
+            step="1"
+            value={store.maxRunningAgents}
+            onChange={(e) => setMaxRunningAgents(Number(e.currentTarget.value) || 0)}
+            style={{
+              width: '64px',
+              padding: '4px 6px',
+              background: theme.bgElevated,
+              border: `1px solid ${theme.border}`,
+              'border-radius': '6px',
+              color: theme.fg,
+              'font-size': '12px',
+            }}
+          />
+        </div>
         <label
           style={{
             display: 'flex',
@@ -254,6 +498,84 @@ export function SettingsDialog(props: SettingsDialogProps) {
         </div>
       </div>
 
+      <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
+        <div
+          style={{
+            ...sectionLabelStyle,
+            'font-weight': '600',
+          }}
+        >
+          Code hosting
+        </div>
+        <div
+          style={{
+            display: 'flex',
+            'flex-direction': 'column',
+            gap: '6px',
+            padding: '8px 12px',
+            'border-radius': '8px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+          }}
+        >
+          <For each={store.forgeTokenHosts}>
+            {(host) => (
+              <div style={{ display: 'flex', 'align-items': 'center', gap: '10px' }}>
+                <span
+                  style={{
+                    flex: '1',
+                    'font-size': '13px',
+                    color: theme.fg,
+                    'font-family': "'JetBrains Mono', monospace",
+                  }}
+                >
+                  {host}
+                </span>
+                <button
+                  type="button"
+                  onClick={() => void saveToken(host, '')}
+                  style={tokenButtonStyle}
+                >
+                  Remove
+                </button>
+              </div>
+            )}
+          </For>
+          <div style={{ display: 'flex', 'align-items': 'center', gap: '10px' }}>
+            <input
+              type="text"
+              value={tokenHost()}
+              onInput={(e) => setTokenHost(e.currentTarget.value)}
+              placeholder="github.com"
+              style={{ ...tokenInputStyle, width: '160px' }}
+            />
+            <input
+              type="password"
+              value={tokenValue()}
+              onInput={(e) => setTokenValue(e.currentTarget.value)}
+              placeholder="ghp_..., glpat-... or user:app-password"
+              style={{ ...tokenInputStyle, flex: '1' }}
+            />
+            <button
+              type="button"
+              disabled={!tokenHost().trim() || !tokenValue().trim()}
+              onClick={() => void saveToken(tokenHost(), tokenValue())}
+              style={tokenButtonStyle}
+            >
+              Save
+            </button>
+          </div>
+          <Show when={tokenError()}>
+            <span style={{ 'font-size': '11px', color: theme.error }}>{tokenError()}</span>
+          </Show>
+          <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+            Used to open pull and merge requests and check CI when the gh or glab CLI is not
+            installed or logged in. A token is only sent to the host it was saved for, and is
+            encrypted with the system keychain.
+          </span>
+        </div>
+      </div>
+
       <Show when={store.dockerAvailable}>
         <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
           <div
@@ -393,6 +715,30 @@ export function SettingsDialog(props: SettingsDialogProps) {
         <CustomAgentEditor />
       </div>
 
+      <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
+        <div
+          style={{
+            ...sectionLabelStyle,
+            'font-weight': '600',
+          }}
+        >
+          Agent Arguments
+        </div>
+        <AgentArgsEditor />
+      </div>
+
+      <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
+        <div
+          style={{
+            ...sectionLabelStyle,
+            'font-weight': '600',
+          }}
+        >
+          Auto-Responses
+        </div>
+        <AutoResponseEditor />
+      </div>
+
       <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
         <div
           style={{
@@ -427,6 +773,37 @@ export function SettingsDialog(props: SettingsDialogProps) {
           </span>
         </Show>
       </div>
+
+      <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
+        <div
+          style={{
+            ...sectionLabelStyle,
+            'font-weight': '600',
+          }}
+        >
+          Export / Import
+        </div>
+        <span style={{ 'font-size': '11px', color: theme.fgSubtle }}>
+          Settings, custom agents and the project list, to set up another machine or share a
+          baseline. Tasks and access tokens are not included.
+        </span>
+        <div style={{ display: 'flex', gap: '8px', 'align-items': 'center' }}>
+          <button type="button" onClick={() => void runConfigAction('export')} style={configBtn}>
+            Export...
+          </button>
+          <button type="button" onClick={() => void runConfigAction('import')} style={configBtn}>
+            Import...
+          </button>
+          <Show when={configMessage()}>
+            <span style={{ 'font-size': '11px', color: theme.fgMuted }}>{configMessage()}</span>
+          </Show>
+        </div>
+      </div>
+      <ImportConfigDialog
+        imported={pendingImport()}
+        onClose={() => setPendingImport(null)}
+        onImported={setConfigMessage}
+      />
     </Dialog>
   );
 }
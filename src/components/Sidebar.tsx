@@ -20,17 +20,27 @@ import {
   toggleSettingsDialog,
   uncollapseTask,
   isProjectMissing,
+  getTasksBehindOriginMain,
+  getPendingTasks,
+  startPendingTask,
+  cancelPendingTask,
+  getAgentQueuePosition,
+  startQueuedAgent,
 } from '../store/store';
-import type { Project } from '../store/types';
+import type { PendingTask, Project } from '../store/types';
 import { computeGroupedTasks } from '../store/sidebar-order';
 import { ConnectPhoneModal } from './ConnectPhoneModal';
 import { ConfirmDialog } from './ConfirmDialog';
 import { EditProjectDialog } from './EditProjectDialog';
+import { MergeQueueDialog } from './MergeQueueDialog';
+import { IntegrationBranchDialog } from './IntegrationBranchDialog';
+import { CrossTaskConflictsDialog } from './CrossTaskConflictsDialog';
 import { SidebarFooter } from './SidebarFooter';
 import { IconButton } from './IconButton';
 import { StatusDot } from './StatusDot';
 import { theme } from '../lib/theme';
 import { sf } from '../lib/fontScale';
+import { formatStartTime } from '../lib/date';
 import { mod } from '../lib/platform';
 
 const DRAG_THRESHOLD = 5;
@@ -39,10 +49,26 @@ const SIDEBAR_MIN_WIDTH = 160;
 const SIDEBAR_MAX_WIDTH = 480;
 const SIDEBAR_SIZE_KEY = 'sidebar:width';
 
+/** Text buttons in a project's task group header. */
+function groupActionStyle() {
+  return {
+    background: 'transparent',
+    border: 'none',
+    color: theme.fgSubtle,
+    cursor: 'pointer',
+    'font-size': sf(10),
+    'text-transform': 'none',
+    padding: '0 2px',
+  };
+}
+
 export function Sidebar() {
   const [confirmRemove, setConfirmRemove] = createSignal<string | null>(null);
   const [editingProject, setEditingProject] = createSignal<Project | null>(null);
   const [showConnectPhone, setShowConnectPhone] = createSignal(false);
+  const [mergeQueueProjectId, setMergeQueueProjectId] = createSignal<string | null>(null);
+  const [previewProjectId, setPreviewProjectId] = createSignal<string | null>(null);
+  const [conflictsProjectId, setConflictsProjectId] = createSignal<string | null>(null);
   const [dragFromIndex, setDragFromIndex] = createSignal<number | null>(null);
   const [dropTargetIndex, setDropTargetIndex] = createSignal<number | null>(null);
   const [resizing, setResizing] = createSignal(false);
@@ -529,7 +555,9 @@ export function Sidebar() {
               const group = () => groupedTasks().grouped[project.id];
               const activeTasks = () => group()?.active ?? [];
               const collapsedTasks = () => group()?.collapsed ?? [];
-              const totalCount = () => activeTasks().length + collapsedTasks().length;
+              const pendingTasks = () => getPendingTasks(project.id);
+              const totalCount = () =>
+                activeTasks().length + collapsedTasks().length + pendingTasks().length;
               return (
                 <Show when={totalCount() > 0}>
                   <span
@@ -556,6 +584,41 @@ export function Sidebar() {
                       }}
                     />
                     {project.name} ({totalCount()})
+                    <Show when={getTasksBehindOriginMain(project.id) > 0}>
+                      <span
+                        title="origin's main branch moved since these tasks branched off"
+                        style={{ color: theme.warning, 'text-transform': 'none' }}
+                      >
+                        · main moved, {getTasksBehindOriginMain(project.id)}{' '}
+                        {getTasksBehindOriginMain(project.id) === 1 ? 'needs' : 'need'} rebase
+                      </span>
+                    </Show>
+                    <Show when={activeTasks().length > 1}>
+                      <button
+                        class="icon-btn"
+                        onClick={() => setConflictsProjectId(project.id)}
+                        title="Which of this project's tasks conflict with each other"
+                        style={{ ...groupActionStyle(), 'margin-left': 'auto' }}
+                      >
+                        Conflicts
+                      </button>
+                      <button
+                        class="icon-btn"
+                        onClick={() => setPreviewProjectId(project.id)}
+                        title="Combine several tasks' branches into a preview branch"
+                        style={groupActionStyle()}
+                      >
+                        Preview
+                      </button>
+                      <button
+                        class="icon-btn"
+                        onClick={() => setMergeQueueProjectId(project.id)}
+                        title="Merge several of this project's tasks in a row"
+                        style={groupActionStyle()}
+                      >
+                        Merge queue
+                      </button>
+                    </Show>
                   </span>
                   <For each={activeTasks()}>
                     {(taskId) => (
@@ -570,6 +633,9 @@ export function Sidebar() {
                   <For each={collapsedTasks()}>
                     {(taskId) => <CollapsedTaskRow taskId={taskId} />}
                   </For>
+                  <For each={pendingTasks()}>
+                    {(pending) => <PendingTaskRow pending={pending} />}
+                  </For>
                 </Show>
               );
             }}
@@ -663,6 +729,24 @@ export function Sidebar() {
         {/* Edit project dialog */}
         <EditProjectDialog project={editingProject()} onClose={() => setEditingProject(null)} />
 
+        <CrossTaskConflictsDialog
+          open={conflictsProjectId() !== null}
+          projectId={conflictsProjectId() ?? ''}
+          onClose={() => setConflictsProjectId(null)}
+        />
+
+        <IntegrationBranchDialog
+          open={previewProjectId() !== null}
+          projectId={previewProjectId() ?? ''}
+          onClose={() => setPreviewProjectId(null)}
+        />
+
+        <MergeQueueDialog
+          open={mergeQueueProjectId() !== null}
+          projectId={mergeQueueProjectId() ?? ''}
+          onClose={() => setMergeQueueProjectId(null)}
+        />
+
         {/* Confirm remove project dialog */}
         {(() => {
           const id = confirmRemove();
@@ -775,6 +859,60 @@ function CollapsedTaskRow(props: { taskId: string }) {
   );
 }
 
+/** A task waiting for another task to be merged, or for its start time, before it is created. */
+function PendingTaskRow(props: { pending: PendingTask }) {
+  return (
+    <div
+      class="task-item"
+      title={props.pending.prompt ?? props.pending.name}
+      style={{
+        padding: '7px 10px',
+        'border-radius': '6px',
+        color: theme.fgSubtle,
+        'font-size': sf(12),
+        display: 'flex',
+        'align-items': 'center',
+        gap: '6px',
+        'white-space': 'nowrap',
+        border: `1px dashed ${theme.border}`,
+      }}
+    >
+      <span style={{ overflow: 'hidden', 'text-overflow': 'ellipsis', flex: '1' }}>
+        {props.pending.name}
+        <span
+          style={{
+            'font-size': sf(10),
+            color: props.pending.blockedReason ? theme.warning : theme.fgSubtle,
+            opacity: props.pending.blockedReason ? '1' : '0.7',
+          }}
+        >
+          {' · '}
+          {props.pending.blockedReason ??
+            (props.pending.startAt !== undefined
+              ? `at ${formatStartTime(props.pending.startAt)}`
+              : `after ${props.pending.afterTaskName}`)}
+        </span>
+      </span>
+      <button
+        class="icon-btn"
+        onClick={() => void startPendingTask(props.pending.id)}
+        title="Create this task now without waiting"
+        style={groupActionStyle()}
+      >
+        Start now
+      </button>
+      <button
+        class="icon-btn"
+        onClick={() => cancelPendingTask(props.pending.id)}
+        title="Drop this waiting task"
+        style={groupActionStyle()}
+      >
+        Cancel
+      </button>
+    </div>
+  );
+}
+
 interface TaskRowProps {
   taskId: string;
   globalIndex: (taskId: string) => number;
@@ -784,6 +922,11 @@ interface TaskRowProps {
 
 function TaskRow(props: TaskRowProps) {
   const task = () => store.tasks[props.taskId];
+  const queuePosition = () => getAgentQueuePosition(task()?.agentIds[0] ?? '');
+  const agentTitle = () => {
+    const agentId = task()?.agentIds[0];
+    return agentId ? store.agents[agentId]?.title : undefined;
+  };
   const idx = () => props.globalIndex(props.taskId);
   return (
     <Show when={task()}>
@@ -838,6 +981,36 @@ function TaskRow(props: TaskRowProps) {
               </span>
             </Show>
             <span style={{ overflow: 'hidden', 'text-overflow': 'ellipsis' }}>{t().name}</span>
+            <Show when={queuePosition() > 0}>
+              <button
+                class="icon-btn"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  void startQueuedAgent(t().agentIds[0]);
+                }}
+                title="Waiting for a free agent slot. Click to start it now."
+                style={{ ...groupActionStyle(), color: theme.warning, 'flex-shrink': '0' }}
+              >
+                queued #{queuePosition()}
+              </button>
+            </Show>
+            <Show when={agentTitle()}>
+              {(title) => (
+                <span
+                  title={title()}
+                  style={{
+                    color: theme.fgSubtle,
+                    'font-size': sf(11),
+                    overflow: 'hidden',
+                    'text-overflow': 'ellipsis',
+                    'flex-shrink': '1',
+                    'min-width': '0',
+                  }}
+                >
+                  {title()}
+                </span>
+              )}
+            </Show>
           </div>
         </>
       )}
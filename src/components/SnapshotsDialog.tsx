@@ -0,0 +1,190 @@
+import { For, Show, createEffect, createResource, createSignal } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { Task } from '../store/types';
+import type { WorktreeSnapshot } from '../ipc/types';
+
+interface SnapshotsDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+export function SnapshotsDialog(props: SnapshotsDialogProps) {
+  const [label, setLabel] = createSignal('');
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [confirmRestore, setConfirmRestore] = createSignal<string | null>(null);
+
+  const [snapshots, { refetch }] = createResource(
+    () => (props.open ? props.task.worktreePath : null),
+    (worktreePath) => invoke<WorktreeSnapshot[]>(IPC.ListSnapshots, { worktreePath }),
+  );
+
+  createEffect(() => {
+    if (props.open) {
+      setLabel('');
+      setError('');
+      setConfirmRestore(null);
+    }
+  });
+
+  async function run(channel: IPC, args: Record<string, unknown>) {
+    setBusy(true);
+    setError('');
+    try {
+      await invoke(channel, { worktreePath: props.task.worktreePath, ...args });
+      refetch();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const buttonStyle = {
+    padding: '4px 10px',
+    background: theme.bgInput,
+    border: `1px solid ${theme.border}`,
+    'border-radius': '6px',
+    color: theme.fg,
+    cursor: 'pointer',
+    'font-size': '12px',
+  };
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="520px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Snapshots
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        A snapshot records the branch's commit and every uncommitted file. Restoring one puts the
+        worktree back to that state; the current state is snapshotted first.
+      </div>
+
+      <div style={{ display: 'flex', gap: '8px' }}>
+        <input
+          class="input-field"
+          type="text"
+          value={label()}
+          onInput={(e) => setLabel(e.currentTarget.value)}
+          placeholder="Label, e.g. before refactor"
+          style={{ flex: '1', 'font-size': '12px' }}
+        />
+        <button
+          type="button"
+          disabled={busy()}
+          onClick={() =>
+            void run(IPC.CreateSnapshot, { label: label().trim() || 'Manual snapshot' }).then(() =>
+              setLabel(''),
+            )
+          }
+          style={{ ...buttonStyle, opacity: busy() ? '0.5' : '1' }}
+        >
+          Take snapshot
+        </button>
+      </div>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{
+          display: 'flex',
+          'flex-direction': 'column',
+          gap: '4px',
+          'max-height': '320px',
+          'overflow-y': 'auto',
+        }}
+      >
+        <Show
+          when={(snapshots() ?? []).length > 0}
+          fallback={
+            <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+              {snapshots.loading ? 'Loading...' : 'No snapshots yet.'}
+            </div>
+          }
+        >
+          <For each={snapshots()}>
+            {(snap) => (
+              <div
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  padding: '6px 8px',
+                  'border-radius': '6px',
+                  background: theme.bgInput,
+                  'font-size': '12px',
+                }}
+              >
+                <div style={{ flex: '1', 'min-width': '0' }}>
+                  <div style={{ color: theme.fg }}>{snap.label}</div>
+                  <div style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                    {new Date(snap.created_at).toLocaleString()} &middot; {snap.head.slice(0, 7)}
+                  </div>
+                </div>
+                <Show
+                  when={confirmRestore() === snap.id}
+                  fallback={
+                    <button
+                      type="button"
+                      disabled={busy()}
+                      onClick={() => setConfirmRestore(snap.id)}
+                      style={buttonStyle}
+                    >
+                      Restore
+                    </button>
+                  }
+                >
+                  <button
+                    type="button"
+                    disabled={busy()}
+                    onClick={() => {
+                      setConfirmRestore(null);
+                      void run(IPC.RestoreSnapshot, { id: snap.id });
+                    }}
+                    style={{ ...buttonStyle, color: theme.warning }}
+                  >
+                    Confirm restore
+                  </button>
+                </Show>
+                <button
+                  type="button"
+                  disabled={busy()}
+                  onClick={() => void run(IPC.DeleteSnapshot, { id: snap.id })}
+                  title="Delete snapshot"
+                  style={{ ...buttonStyle, color: theme.fgMuted }}
+                >
+                  Delete
+                </button>
+              </div>
+            )}
+          </For>
+        </Show>
+      </div>
+
+      <div style={{ display: 'flex', 'justify-content': 'flex-end', 'padding-top': '4px' }}>
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+      </div>
+    </Dialog>
+  );
+}
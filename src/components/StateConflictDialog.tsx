@@ -0,0 +1,104 @@
+import { For, Show, createSignal } from 'solid-js';
+import { store, resolveStateConflicts } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { StateConflictChoice } from '../ipc/types';
+
+const secondaryButtonStyle = {
+  padding: '9px 18px',
+  background: theme.bgInput,
+  border: `1px solid ${theme.border}`,
+  'border-radius': '8px',
+  color: theme.fgMuted,
+  cursor: 'pointer',
+  'font-size': '13px',
+};
+
+/** Asks whose copy to keep when another instance changed the saved state under this one. */
+export function StateConflictDialog() {
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+
+  async function choose(choice: StateConflictChoice) {
+    setBusy(true);
+    setError('');
+    try {
+      await resolveStateConflicts(choice);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  // Needs an answer: until then this window's changes to these files aren't saved
+  return (
+    <Dialog open={store.stateConflictPaths.length > 0} onClose={() => {}} width="560px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Saved state changed elsewhere
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Another Parallel Code instance changed these files since this window loaded them. This
+        window's version is kept next to each as a .conflict file until you choose.
+      </div>
+      <div
+        style={{
+          'font-size': '11px',
+          'font-family': "'JetBrains Mono', monospace",
+          color: theme.fgSubtle,
+          'max-height': '120px',
+          'overflow-y': 'auto',
+        }}
+      >
+        <For each={store.stateConflictPaths}>{(p) => <div>{p}</div>}</For>
+      </div>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          disabled={busy()}
+          title="Keep the other instance's files and this window's copy in .conflict files"
+          onClick={() => void choose('stop')}
+          style={secondaryButtonStyle}
+        >
+          Stop saving these
+        </button>
+        <button
+          type="button"
+          class="btn-secondary"
+          disabled={busy()}
+          title="Discard this window's changes to these files and load the other instance's"
+          onClick={() => void choose('reload')}
+          style={secondaryButtonStyle}
+        >
+          Reload theirs
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy()}
+          onClick={() => void choose('keep_mine')}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          Keep this window's
+        </button>
+      </div>
+    </Dialog>
+  );
+}
@@ -1,12 +1,19 @@
-import { For } from 'solid-js';
+import { For, Show } from 'solid-js';
 import { theme } from '../lib/theme';
+import type { LinkMode } from '../lib/link-rules';
 
 interface SymlinkDirPickerProps {
   dirs: string[];
   selectedDirs: Set<string>;
+  /** Non-symlink mode per path, shown next to the entry (e.g. copy, clone). */
+  modes?: Record<string, string>;
   onToggle: (dir: string) => void;
+  /** Makes the mode selectable per entry. */
+  onModeChange?: (dir: string, mode: LinkMode) => void;
 }
 
+const LINK_MODES: LinkMode[] = ['symlink', 'clone', 'hardlink', 'copy'];
+
 export function SymlinkDirPicker(props: SymlinkDirPickerProps) {
   return (
     <div
@@ -21,7 +28,7 @@ export function SymlinkDirPicker(props: SymlinkDirPickerProps) {
           'letter-spacing': '0.05em',
         }}
       >
-        Symlink into worktree
+        Link into worktree
       </label>
       <div
         style={{
@@ -56,6 +63,32 @@ export function SymlinkDirPicker(props: SymlinkDirPickerProps) {
                   style={{ 'accent-color': theme.accent }}
                 />
                 {dir}
+                <Show
+                  when={props.onModeChange}
+                  fallback={
+                    <Show when={props.modes?.[dir]}>
+                      {(mode) => <span style={{ color: theme.fgMuted }}>({mode()})</span>}
+                    </Show>
+                  }
+                >
+                  {(onModeChange) => (
+                    <select
+                      value={props.modes?.[dir] ?? 'symlink'}
+                      onChange={(e) => onModeChange()(dir, e.currentTarget.value as LinkMode)}
+                      style={{
+                        'margin-left': 'auto',
+                        background: theme.bgInput,
+                        border: `1px solid ${theme.border}`,
+                        'border-radius': '4px',
+                        color: theme.fgMuted,
+                        'font-size': '11px',
+                        padding: '1px 4px',
+                      }}
+                    >
+                      <For each={LINK_MODES}>{(m) => <option value={m}>{m}</option>}</For>
+                    </select>
+                  )}
+                </Show>
               </label>
             );
           }}
@@ -0,0 +1,211 @@
+import { For, Show, createEffect, createSignal, untrack } from 'solid-js';
+import { findProjectLeftovers, removeProjectLeftovers } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import { formatBytes } from '../lib/format-size';
+import type { TaskLeftover } from '../ipc/types';
+
+interface TaskLeftoversDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+const leftoverKey = (l: TaskLeftover) => `${l.branch ?? ''}\0${l.path ?? ''}`;
+
+export function TaskLeftoversDialog(props: TaskLeftoversDialogProps) {
+  const [leftovers, setLeftovers] = createSignal<TaskLeftover[] | null>(null);
+  const [selected, setSelected] = createSignal<Set<string>>(new Set());
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [message, setMessage] = createSignal('');
+
+  async function scan() {
+    setBusy(true);
+    try {
+      const result = await findProjectLeftovers(props.projectId);
+      setLeftovers(result);
+      setSelected(new Set(result.map(leftoverKey)));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  createEffect(() => {
+    if (props.open) {
+      setLeftovers(null);
+      setMessage('');
+      setError('');
+      untrack(() => void scan());
+    }
+  });
+
+  function toggle(key: string, checked: boolean) {
+    const next = new Set(selected());
+    if (checked) next.add(key);
+    else next.delete(key);
+    setSelected(next);
+  }
+
+  const chosen = () => (leftovers() ?? []).filter((l) => selected().has(leftoverKey(l)));
+  const chosenBytes = () => chosen().reduce((sum, l) => sum + (l.size_bytes ?? 0), 0);
+
+  async function removeSelected() {
+    setBusy(true);
+    setError('');
+    try {
+      const result = await removeProjectLeftovers(props.projectId, chosen());
+      setMessage(`Removed ${result.removed} leftover${result.removed === 1 ? '' : 's'}`);
+      if (result.errors.length > 0) setError(result.errors.join('\n'));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+    await scan();
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="640px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Clean up old tasks
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Task branches and worktrees that no task in the sidebar owns. Removing a branch deletes
+        its commits unless they were merged or pushed.
+      </div>
+
+      <Show when={busy() && !leftovers()}>
+        <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>Looking for leftovers...</div>
+      </Show>
+
+      <Show when={leftovers()}>
+        {(list) => (
+          <Show
+            when={list().length > 0}
+            fallback={
+              <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>Nothing left over.</div>
+            }
+          >
+            <div
+              style={{
+                display: 'flex',
+                'flex-direction': 'column',
+                gap: '4px',
+                'max-height': '320px',
+                'overflow-y': 'auto',
+              }}
+            >
+              <For each={list()}>
+                {(leftover) => (
+                  <label
+                    style={{
+                      display: 'flex',
+                      'align-items': 'center',
+                      gap: '8px',
+                      cursor: 'pointer',
+                      'font-size': '12px',
+                    }}
+                  >
+                    <input
+                      type="checkbox"
+                      checked={selected().has(leftoverKey(leftover))}
+                      onChange={(e) => toggle(leftoverKey(leftover), e.currentTarget.checked)}
+                      style={{ cursor: 'pointer' }}
+                    />
+                    <span
+                      style={{
+                        flex: '1',
+                        display: 'flex',
+                        'flex-direction': 'column',
+                        'min-width': '0',
+                        'font-family': "'JetBrains Mono', monospace",
+                      }}
+                    >
+                      <span style={{ color: theme.fg }}>
+                        {leftover.branch ?? '(stray directory)'}
+                      </span>
+                      <Show when={leftover.path}>
+                        <span
+                          style={{
+                            color: theme.fgSubtle,
+                            'font-size': '11px',
+                            overflow: 'hidden',
+                            'text-overflow': 'ellipsis',
+                            'white-space': 'nowrap',
+                          }}
+                        >
+                          {leftover.path}
+                        </span>
+                      </Show>
+                    </span>
+                    <span style={{ color: theme.fgSubtle, 'font-size': '11px' }}>
+                      {leftover.size_bytes === null ? '' : formatBytes(leftover.size_bytes)}
+                    </span>
+                  </label>
+                )}
+              </For>
+            </div>
+          </Show>
+        )}
+      </Show>
+
+      <Show when={message()}>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted }}>{message()}</div>
+      </Show>
+      <Show when={error()}>
+        <div
+          style={{ ...bannerStyle(theme.error), 'font-size': '12px', 'white-space': 'pre-wrap' }}
+        >
+          {error()}
+        </div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <Show when={(leftovers()?.length ?? 0) > 0}>
+          <button
+            type="button"
+            class="btn-primary"
+            disabled={busy() || chosen().length === 0}
+            onClick={() => void removeSelected()}
+            style={{
+              padding: '9px 20px',
+              background: theme.accent,
+              border: 'none',
+              'border-radius': '8px',
+              color: theme.accentText,
+              cursor: 'pointer',
+              'font-size': '13px',
+              'font-weight': '500',
+            }}
+          >
+            {busy()
+              ? 'Working...'
+              : `Remove ${chosen().length} selected` +
+                (chosenBytes() > 0 ? ` (${formatBytes(chosenBytes())})` : '')}
+          </button>
+        </Show>
+      </div>
+    </Dialog>
+  );
+}
@@ -7,12 +7,18 @@ import {
   setActiveTask,
   markAgentExited,
   restartAgent,
+  signalAgent,
+  exportSessionRecording,
+  setAgentTitle,
+  markAgentIdleTimeout,
+  setTaskLowPriority,
   switchAgent,
   updateTaskName,
   updateTaskNotes,
   spawnShellForTask,
   runBookmarkInTask,
   closeShell,
+  markShellExited,
   setLastPrompt,
   clearInitialPrompt,
   clearPrefillPrompt,
@@ -21,6 +27,10 @@ import {
   getFontScale,
   getTaskDotStatus,
   markAgentOutput,
+  markAgentIdleFromBackend,
+  markAgentAwaitingInput,
+  markAgentBell,
+  autoResponseRulesFor,
   registerFocusFn,
   unregisterFocusFn,
   setTaskFocusedPanel,
@@ -28,6 +38,12 @@ import {
   clearPendingAction,
   showNotification,
   collapseTask,
+  getTaskJobs,
+  getTaskDevServer,
+  openTaskPreview,
+  getTaskCi,
+  getProjectCommitOptions,
+  linkedWorktreesEnv,
 } from '../store/store';
 import { ResizablePanel, type PanelChild } from './ResizablePanel';
 import { EditableText, type EditableTextHandle } from './EditableText';
@@ -41,6 +57,13 @@ import { ScalablePanel } from './ScalablePanel';
 import { CloseTaskDialog } from './CloseTaskDialog';
 import { MergeDialog } from './MergeDialog';
 import { PushDialog } from './PushDialog';
+import { SnapshotsDialog } from './SnapshotsDialog';
+import { CompareTasksDialog } from './CompareTasksDialog';
+import { DuplicateTaskDialog } from './DuplicateTaskDialog';
+import { TransferChangesDialog } from './TransferChangesDialog';
+import { ApplyPatchDialog } from './ApplyPatchDialog';
+import { PullRequestDialog } from './PullRequestDialog';
+import { ScriptsDialog } from './ScriptsDialog';
 import { DiffViewerDialog } from './DiffViewerDialog';
 import { PlanViewerDialog } from './PlanViewerDialog';
 import { EditProjectDialog } from './EditProjectDialog';
@@ -51,12 +74,33 @@ import { extractLabel, consumePendingShellCommand } from '../lib/bookmarks';
 import { handleDragReorder } from '../lib/dragReorder';
 import { createHighlightedMarkdown } from '../lib/marked-shiki';
 import type { Task } from '../store/types';
+import type { CiState, CiStatus } from '../ipc/types';
 
 interface TaskPanelProps {
   task: Task;
   isActive: boolean;
 }
 
+function formatRuntime(ms: number): string {
+  const mins = Math.round(ms / 60_000);
+  if (mins < 1) return `${Math.round(ms / 1000)}s`;
+  if (mins < 60) return `${mins} min`;
+  return `${Math.floor(mins / 60)}h ${mins % 60}m`;
+}
+
+const CI_COLORS: Record<CiState, string> = {
+  success: theme.success,
+  failure: theme.error,
+  pending: theme.warning,
+  none: 'transparent',
+};
+
+function ciTitle(ci: CiStatus): string {
+  if (ci.state === 'failure') return `CI failing: ${ci.failed} failed, ${ci.passed} passed`;
+  if (ci.state === 'pending') return `CI running: ${ci.pending} pending, ${ci.passed} passed`;
+  return `CI passing: ${ci.passed} passed`;
+}
+
 export function TaskPanel(props: TaskPanelProps) {
   const [showCloseConfirm, setShowCloseConfirm] = createSignal(false);
   const [notesTab, setNotesTab] = createSignal<'notes' | 'plan'>('notes');
@@ -78,11 +122,45 @@ export function TaskPanel(props: TaskPanelProps) {
   const [showMergeConfirm, setShowMergeConfirm] = createSignal(false);
   const [showPushConfirm, setShowPushConfirm] = createSignal(false);
   const [pushSuccess, setPushSuccess] = createSignal(false);
+  const [showSnapshots, setShowSnapshots] = createSignal(false);
+  const [showCompare, setShowCompare] = createSignal(false);
+  const [showDuplicate, setShowDuplicate] = createSignal(false);
+  const [showTransfer, setShowTransfer] = createSignal(false);
+  const [showApplyPatch, setShowApplyPatch] = createSignal(false);
+  const [showPullRequest, setShowPullRequest] = createSignal(false);
+  const [showScripts, setShowScripts] = createSignal(false);
+  const gitStatus = () => store.taskGitStatus[props.task.id];
+  const runningJobs = () => getTaskJobs(props.task.id).filter((j) => j.status === 'running').length;
+  const mergeTitle = () => {
+    const s = gitStatus();
+    if (!s) return 'Merge into main';
+    if (s.commits_ahead_of_main === 0) return 'Merge into main (nothing to merge)';
+    const commits = `${s.commits_ahead_of_main} commit${s.commits_ahead_of_main > 1 ? 's' : ''}`;
+    return s.commits_behind_main > 0
+      ? `Merge into main (${commits}; main is ${s.commits_behind_main} ahead, rebase first)`
+      : `Merge into main (${commits})`;
+  };
+  const pushTitle = () => {
+    const s = gitStatus();
+    if (!s?.has_remote_branch) return 'Push to remote';
+    const ahead = s.remote_ahead_behind?.ahead ?? 0;
+    const behind = s.remote_ahead_behind?.behind ?? 0;
+    if (behind > 0) return `Push to remote (remote has ${behind} commits not in this branch)`;
+    return ahead > 0 ? `Push to remote (${ahead} unpushed)` : 'Push to remote (up to date)';
+  };
   const [pushing, setPushing] = createSignal(false);
   let pushSuccessTimer: ReturnType<typeof setTimeout> | undefined;
   onCleanup(() => clearTimeout(pushSuccessTimer));
   const [diffScrollTarget, setDiffScrollTarget] = createSignal<string | null>(null);
+  const [showMergePreview, setShowMergePreview] = createSignal(false);
   const [editingProjectId, setEditingProjectId] = createSignal<string | null>(null);
+  // Multi-repo tasks: which worktree the changed files panel and its diffs show
+  const [changesRepoIdx, setChangesRepoIdx] = createSignal(0);
+  const changesRepos = () => [
+    { projectId: props.task.projectId, worktreePath: props.task.worktreePath },
+    ...(props.task.linkedWorktrees ?? []),
+  ];
+  const changesRepo = () => changesRepos()[changesRepoIdx()] ?? changesRepos()[0];
   const [shellExits, setShellExits] = createStore<
     Record<string, { exitCode: number | null; signal: string | null }>
   >({});
@@ -301,7 +379,7 @@ export function TaskPanel(props: TaskPanelProps) {
                   </svg>
                 }
                 onClick={() => setShowMergeConfirm(true)}
-                title="Merge into main"
+                title={mergeTitle()}
               />
               <div style={{ position: 'relative', display: 'inline-flex' }}>
                 <Show
@@ -331,7 +409,7 @@ export function TaskPanel(props: TaskPanelProps) {
                       </svg>
                     }
                     onClick={() => setShowPushConfirm(true)}
-                    title="Push to remote"
+                    title={pushTitle()}
                   />
                 </Show>
                 <Show when={pushSuccess()}>
@@ -356,7 +434,164 @@ export function TaskPanel(props: TaskPanelProps) {
                   </div>
                 </Show>
               </div>
+              <IconButton
+                icon={
+                  <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                    <path d="M1.643 3.143 .427 1.927A.25.25 0 0 0 0 2.104V5.75c0 .138.112.25.25.25h3.646a.25.25 0 0 0 .177-.427L2.715 4.215a6.5 6.5 0 1 1-1.18 4.458.75.75 0 1 0-1.493.154 8.001 8.001 0 1 0 1.6-5.684ZM7.75 4a.75.75 0 0 1 .75.75v2.992l2.028.812a.75.75 0 0 1-.557 1.392l-2.5-1A.751.751 0 0 1 7 8.25v-3.5A.75.75 0 0 1 7.75 4Z" />
+                  </svg>
+                }
+                onClick={() => setShowSnapshots(true)}
+                title="Snapshots: save or restore the worktree state"
+              />
+              <IconButton
+                icon={
+                  <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                    <path d="M5.22 14.78a.75.75 0 0 0 1.06-1.06L4.56 12h8.69a.75.75 0 0 0 0-1.5H4.56l1.72-1.72a.75.75 0 0 0-1.06-1.06l-3 3a.75.75 0 0 0 0 1.06l3 3Zm5.56-6.5a.75.75 0 1 1-1.06-1.06l1.72-1.72H2.75a.75.75 0 0 1 0-1.5h8.69L9.72 2.28a.75.75 0 0 1 1.06-1.06l3 3a.75.75 0 0 1 0 1.06l-3 3Z" />
+                  </svg>
+                }
+                onClick={() => setShowTransfer(true)}
+                title="Transfer changes to another task"
+              />
+              <Show when={!props.task.directMode}>
+                <IconButton
+                  icon={
+                    <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                      <path d="M9.573.677A.25.25 0 0 1 10 .854V2.5h1A2.5 2.5 0 0 1 13.5 5v5.628a2.251 2.251 0 1 1-1.5 0V5a1 1 0 0 0-1-1h-1v1.646a.25.25 0 0 1-.427.177L7.177 3.427a.25.25 0 0 1 0-.354ZM6 12v-1.646a.25.25 0 0 1 .427-.177l2.396 2.396a.25.25 0 0 1 0 .354l-2.396 2.396A.25.25 0 0 1 6 15.146V13.5H5A2.5 2.5 0 0 1 2.5 11V5.372a2.25 2.25 0 1 1 1.5 0V11a1 1 0 0 0 1 1ZM4 3.25a.75.75 0 1 0-1.5 0 .75.75 0 0 0 1.5 0ZM12.75 12a.75.75 0 1 0 0 1.5.75.75 0 0 0 0-1.5Z" />
+                    </svg>
+                  }
+                  onClick={() => setShowCompare(true)}
+                  title={
+                    props.task.fanoutGroupId
+                      ? 'Compare the results of the agents that got this prompt'
+                      : "Compare this task's changes with other tasks"
+                  }
+                />
+                <IconButton
+                  icon={
+                    <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                      <path d="M0 6.75C0 5.784.784 5 1.75 5h1.5a.75.75 0 0 1 0 1.5h-1.5a.25.25 0 0 0-.25.25v7.5c0 .138.112.25.25.25h7.5a.25.25 0 0 0 .25-.25v-1.5a.75.75 0 0 1 1.5 0v1.5A1.75 1.75 0 0 1 9.25 16h-7.5A1.75 1.75 0 0 1 0 14.25Z" />
+                      <path d="M5 1.75C5 .784 5.784 0 6.75 0h7.5C15.216 0 16 .784 16 1.75v7.5A1.75 1.75 0 0 1 14.25 11h-7.5A1.75 1.75 0 0 1 5 9.25Zm1.75-.25a.25.25 0 0 0-.25.25v7.5c0 .138.112.25.25.25h7.5a.25.25 0 0 0 .25-.25v-7.5a.25.25 0 0 0-.25-.25Z" />
+                    </svg>
+                  }
+                  onClick={() => setShowDuplicate(true)}
+                  title="Duplicate this task to try another approach"
+                />
+              </Show>
+              <IconButton
+                icon={
+                  <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                    <path d="M8.75 1.75V5H12a.75.75 0 0 1 0 1.5H8.75v3.25a.75.75 0 0 1-1.5 0V6.5H4a.75.75 0 0 1 0-1.5h3.25V1.75a.75.75 0 0 1 1.5 0ZM4 13h8a.75.75 0 0 1 0 1.5H4A.75.75 0 0 1 4 13Z" />
+                  </svg>
+                }
+                onClick={() => setShowApplyPatch(true)}
+                title="Apply a patch to this worktree"
+              />
+              <IconButton
+                icon={
+                  <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                    <path d="M1.5 3.25a2.25 2.25 0 1 1 3 2.122v5.256a2.251 2.251 0 1 1-1.5 0V5.372A2.25 2.25 0 0 1 1.5 3.25Zm5.677-.177L9.573.677A.25.25 0 0 1 10 .854V2.5h1A2.5 2.5 0 0 1 13.5 5v5.628a2.251 2.251 0 1 1-1.5 0V5a1 1 0 0 0-1-1h-1v1.646a.25.25 0 0 1-.427.177L7.177 3.427a.25.25 0 0 1 0-.354ZM3.75 2.5a.75.75 0 1 0 0 1.5.75.75 0 0 0 0-1.5Zm0 9.5a.75.75 0 1 0 0 1.5.75.75 0 0 0 0-1.5Zm8.25.75a.75.75 0 1 0 1.5 0 .75.75 0 0 0-1.5 0Z" />
+                  </svg>
+                }
+                onClick={() => setShowPullRequest(true)}
+                title="Open a pull or merge request"
+              />
+            </Show>
+            <Show when={(getProject(props.task.projectId)?.scripts?.length ?? 0) > 0}>
+              <IconButton
+                icon={
+                  <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                    <path d="M8 0a8 8 0 1 1 0 16A8 8 0 0 1 8 0ZM1.5 8a6.5 6.5 0 1 0 13 0 6.5 6.5 0 0 0-13 0Zm4.879-2.773 4.264 2.559a.25.25 0 0 1 0 .428l-4.264 2.559A.25.25 0 0 1 6 10.559V5.442a.25.25 0 0 1 .379-.215Z" />
+                  </svg>
+                }
+                onClick={() => setShowScripts(true)}
+                title={
+                  runningJobs() > 0
+                    ? `Scripts (${runningJobs()} running)`
+                    : 'Scripts: run project scripts as background jobs'
+                }
+              />
+            </Show>
+            <Show when={getTaskDevServer(props.task.id)}>
+              {(server) => (
+                <IconButton
+                  icon={
+                    <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                      <path d="M8 0a8 8 0 1 1 0 16A8 8 0 0 1 8 0ZM5.78 8.75a9.64 9.64 0 0 0 1.363 4.177c.255.426.542.832.857 1.215.245-.296.551-.705.857-1.215A9.64 9.64 0 0 0 10.22 8.75Zm4.44-1.5a9.64 9.64 0 0 0-1.363-4.177c-.307-.51-.612-.919-.857-1.215a9.927 9.927 0 0 0-.857 1.215A9.64 9.64 0 0 0 5.78 7.25Zm-5.944 1.5H1.543a6.507 6.507 0 0 0 4.666 5.5c-.123-.181-.24-.365-.352-.552-.715-1.192-1.437-2.874-1.581-4.948Zm-2.733-1.5h2.733c.144-2.074.866-3.756 1.58-4.948.12-.197.237-.381.353-.552a6.507 6.507 0 0 0-4.666 5.5Zm10.181 1.5c-.144 2.074-.866 3.756-1.58 4.948-.12.197-.237.381-.353.552a6.507 6.507 0 0 0 4.666-5.5Zm2.733-1.5a6.507 6.507 0 0 0-4.666-5.5c.123.181.24.365.353.552.714 1.192 1.436 2.874 1.58 4.948Z" />
+                    </svg>
+                  }
+                  onClick={() => openTaskPreview(props.task.id).catch(console.error)}
+                  title={`Open preview (${server().url ?? `port ${server().port}`})`}
+                />
+              )}
+            </Show>
+            <Show when={firstAgent()?.status === 'running' ? firstAgent() : undefined}>
+              {(a) => (
+                <>
+                  <IconButton
+                    icon={
+                      <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                        <path d="M8 1.5a6.5 6.5 0 1 0 0 13 6.5 6.5 0 0 0 0-13ZM0 8a8 8 0 1 1 16 0A8 8 0 0 1 0 8Zm5-2.25A.75.75 0 0 1 5.75 5h4.5a.75.75 0 0 1 .75.75v4.5a.75.75 0 0 1-.75.75h-4.5a.75.75 0 0 1-.75-.75Z" />
+                      </svg>
+                    }
+                    onClick={() => signalAgent(a().id, 'SIGINT').catch(console.error)}
+                    title="Interrupt agent (SIGINT)"
+                  />
+                  <IconButton
+                    icon={
+                      <Show
+                        when={a().suspended}
+                        fallback={
+                          <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                            <path d="M4.5 2a.75.75 0 0 1 .75.75v10.5a.75.75 0 0 1-1.5 0V2.75A.75.75 0 0 1 4.5 2Zm7 0a.75.75 0 0 1 .75.75v10.5a.75.75 0 0 1-1.5 0V2.75A.75.75 0 0 1 11.5 2Z" />
+                          </svg>
+                        }
+                      >
+                        <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                          <path d="M4 2.8a.75.75 0 0 1 1.14-.64l8.5 5.2a.75.75 0 0 1 0 1.28l-8.5 5.2A.75.75 0 0 1 4 13.2V2.8Z" />
+                        </svg>
+                      </Show>
+                    }
+                    onClick={() =>
+                      signalAgent(a().id, a().suspended ? 'SIGCONT' : 'SIGTSTP').catch(
+                        console.error,
+                      )
+                    }
+                    title={a().suspended ? 'Resume agent (SIGCONT)' : 'Pause agent (SIGTSTP)'}
+                  />
+                </>
+              )}
+            </Show>
+            <Show when={store.recordSessions ? firstAgent() : undefined}>
+              {(a) => (
+                <IconButton
+                  icon={
+                    <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
+                      <path d="M2.75 14A1.75 1.75 0 0 1 1 12.25v-2.5a.75.75 0 0 1 1.5 0v2.5c0 .138.112.25.25.25h10.5a.25.25 0 0 0 .25-.25v-2.5a.75.75 0 0 1 1.5 0v2.5A1.75 1.75 0 0 1 13.25 14ZM7.25 7.689V2a.75.75 0 0 1 1.5 0v5.689l1.97-1.969a.749.749 0 1 1 1.06 1.06l-3.25 3.25a.749.749 0 0 1-1.06 0L4.22 6.78a.749.749 0 1 1 1.06-1.06l1.97 1.969Z" />
+                    </svg>
+                  }
+                  onClick={() => void exportSessionRecording(a().id)}
+                  title="Export session recording (.cast)"
+                />
+              )}
             </Show>
+            <IconButton
+              icon={
+                <svg
+                  width="16"
+                  height="16"
+                  viewBox="0 0 16 16"
+                  fill={props.task.lowPriority ? theme.accent : 'currentColor'}
+                >
+                  <path d="M8 1.5a.75.75 0 0 1 .75.75v9.69l2.47-2.47a.75.75 0 1 1 1.06 1.06l-3.75 3.75a.75.75 0 0 1-1.06 0L3.72 10.53a.75.75 0 1 1 1.06-1.06l2.47 2.47V2.25A.75.75 0 0 1 8 1.5Z" />
+                </svg>
+              }
+              onClick={() => void setTaskLowPriority(props.task.id, !props.task.lowPriority)}
+              title={
+                props.task.lowPriority
+                  ? 'Restore normal CPU/IO priority'
+                  : 'Run agent at low CPU/IO priority'
+              }
+            />
             <IconButton
               icon={
                 <svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor">
@@ -527,6 +762,24 @@ export function TaskPanel(props: TaskPanelProps) {
               </span>
             </Show>
           </button>
+          <Show when={getTaskCi(props.task.id)?.state !== 'none' && getTaskCi(props.task.id)}>
+            {(ci) => (
+              <a
+                href={ci().url ?? undefined}
+                target="_blank"
+                title={ciTitle(ci())}
+                style={{
+                  width: '8px',
+                  height: '8px',
+                  'border-radius': '50%',
+                  'flex-shrink': '0',
+                  'margin-right': '12px',
+                  background: CI_COLORS[ci().state],
+                  cursor: ci().url ? 'pointer' : 'default',
+                }}
+              />
+            )}
+          </Show>
           <button
             type="button"
             title={editorTitle()}
@@ -773,14 +1026,43 @@ export function TaskPanel(props: TaskPanelProps) {
                         'letter-spacing': '0.05em',
                         'border-bottom': `1px solid ${theme.border}`,
                         'flex-shrink': '0',
+                        display: 'flex',
+                        'align-items': 'center',
+                        gap: '6px',
                       }}
                     >
                       Changed Files
+                      <Show when={changesRepos().length > 1}>
+                        <select
+                          value={changesRepoIdx()}
+                          onChange={(e) => setChangesRepoIdx(Number(e.currentTarget.value))}
+                          onClick={(e) => e.stopPropagation()}
+                          style={{
+                            background: theme.bgInput,
+                            border: `1px solid ${theme.border}`,
+                            'border-radius': '4px',
+                            color: theme.fg,
+                            'font-size': sf(10),
+                            'text-transform': 'none',
+                          }}
+                        >
+                          <For each={changesRepos()}>
+                            {(repo, i) => (
+                              <option value={i()}>
+                                {getProject(repo.projectId)?.name ?? repo.projectId}
+                              </option>
+                            )}
+                          </For>
+                        </select>
+                      </Show>
                     </div>
                     <div style={{ flex: '1', overflow: 'hidden' }}>
                       <ChangedFilesList
-                        worktreePath={props.task.worktreePath}
+                        worktreePath={changesRepo().worktreePath}
                         isActive={props.isActive}
+                        canCommit
+                        generatedPatterns={getProject(changesRepo().projectId)?.generatedFiles}
+                        commitOptions={getProjectCommitOptions(changesRepo().projectId)}
                         onFileClick={(file) => setDiffScrollTarget(file.path)}
                         ref={(el) => (changedFilesRef = el)}
                       />
@@ -1005,19 +1287,19 @@ export function TaskPanel(props: TaskPanelProps) {
                           isFocused={
                             props.isActive && store.focusedPanel[props.task.id] === `shell:${i()}`
                           }
-                          command={''}
-                          args={['-l']}
                           cwd={props.task.worktreePath}
+                          env={linkedWorktreesEnv(props.task)}
                           dockerMode={props.task.dockerMode}
                           dockerImage={props.task.dockerImage}
                           initialCommand={initialCommand}
                           onData={(data) => markAgentOutput(shellId, data, props.task.id)}
-                          onExit={(info) =>
+                          onExit={(info) => {
                             setShellExits(shellId, {
                               exitCode: info.exit_code,
                               signal: info.signal,
-                            })
-                          }
+                            });
+                            markShellExited(props.task.id, shellId, info.exit_code);
+                          }}
                           onReady={(focusFn) => {
                             shellFocusFn = focusFn;
                             if (registeredKey) registerFocusFn(registeredKey, focusFn);
@@ -1060,7 +1342,11 @@ export function TaskPanel(props: TaskPanelProps) {
             <InfoBar
               title={
                 props.task.lastPrompt ||
-                (props.task.initialPrompt ? 'Waiting to send prompt…' : 'No prompts sent yet')
+                (props.task.setupStatus === 'running'
+                  ? 'Setting up worktree…'
+                  : props.task.initialPrompt
+                    ? 'Waiting to send prompt…'
+                    : 'No prompts sent yet')
               }
               onDblClick={() => {
                 if (props.task.lastPrompt && promptHandle && !promptHandle.getText())
@@ -1070,9 +1356,11 @@ export function TaskPanel(props: TaskPanelProps) {
               <span style={{ opacity: props.task.lastPrompt ? 1 : 0.4 }}>
                 {props.task.lastPrompt
                   ? `> ${props.task.lastPrompt}`
-                  : props.task.initialPrompt
-                    ? '⏳ Waiting to send prompt…'
-                    : 'No prompts sent'}
+                  : props.task.setupStatus === 'running'
+                    ? '⏳ Setting up worktree…'
+                    : props.task.initialPrompt
+                      ? '⏳ Waiting to send prompt…'
+                      : 'No prompts sent'}
               </span>
             </InfoBar>
             <div style={{ flex: '1', position: 'relative', overflow: 'hidden' }}>
@@ -1099,10 +1387,21 @@ export function TaskPanel(props: TaskPanelProps) {
                           gap: '8px',
                         }}
                       >
-                        <span>
+                        <span
+                          title={(() => {
+                            const stats = a().exitStats;
+                            return stats
+                              ? `${Math.round(stats.outputBytes / 1024)} KB output, last active ` +
+                                  new Date(stats.lastActivityAt).toLocaleTimeString()
+                              : undefined;
+                          })()}
+                        >
                           {a().signal === 'spawn_failed'
                             ? 'Failed to start'
                             : `Process exited (${a().exitCode ?? '?'})`}
+                          {a().exitStats
+                            ? ` · ran ${formatRuntime(a().exitStats?.durationMs ?? 0)}`
+                            : ''}
                         </span>
                         {(() => {
                           const [showAgentMenu, setShowAgentMenu] = createSignal(false);
@@ -1269,6 +1568,7 @@ export function TaskPanel(props: TaskPanelProps) {
                           props.isActive && store.focusedPanel[props.task.id] === 'ai-terminal'
                         }
                         command={a().def.command}
+                        env={{ ...a().def.env, ...linkedWorktreesEnv(props.task) }}
                         args={[
                           ...(a().resumed && a().def.resume_args?.length
                             ? (a().def.resume_args ?? [])
@@ -1280,8 +1580,43 @@ export function TaskPanel(props: TaskPanelProps) {
                         cwd={props.task.worktreePath}
                         dockerMode={props.task.dockerMode}
                         dockerImage={props.task.dockerImage}
+                        supervise={
+                          props.task.autoRestart
+                            ? {
+                                resumeArgs: [
+                                  ...(a().def.resume_args?.length
+                                    ? a().def.resume_args
+                                    : a().def.args),
+                                  ...(props.task.skipPermissions &&
+                                  a().def.skip_permissions_args?.length
+                                    ? (a().def.skip_permissions_args ?? [])
+                                    : []),
+                                ],
+                              }
+                            : undefined
+                        }
+                        autoRespond={autoResponseRulesFor(a().def.id)}
+                        extraArgs={store.agentExtraArgs[a().def.id]}
+                        record={store.recordSessions}
+                        clipboard={store.osc52Clipboard}
+                        idleTimeout={
+                          store.idleTimeoutMinutes > 0
+                            ? { minutes: store.idleTimeoutMinutes, action: store.idleTimeoutAction }
+                            : undefined
+                        }
+                        priority={props.task.lowPriority ? 'low' : undefined}
                         onExit={(code) => markAgentExited(a().id, code)}
                         onData={(data) => markAgentOutput(a().id, data, props.task.id)}
+                        onIdle={() => markAgentIdleFromBackend(a().id)}
+                        onAwaitingInput={(prompt) =>
+                          markAgentAwaitingInput(a().id, props.task.id, prompt)
+                        }
+                        onAutoResponded={(entry) =>
+                          showNotification(`Auto-responded to "${entry.matched}"`)
+                        }
+                        onTitle={(title) => setAgentTitle(a().id, title)}
+                        onBell={() => markAgentBell(props.task.id)}
+                        onIdleTimeout={(mins, action) => markAgentIdleTimeout(a().id, mins, action)}
                         onPromptDetected={(text) => setLastPrompt(props.task.id, text)}
                         onReady={(focusFn) =>
                           registerFocusFn(`${props.task.id}:ai-terminal`, focusFn)
@@ -1315,7 +1650,10 @@ export function TaskPanel(props: TaskPanelProps) {
             <PromptInput
               taskId={props.task.id}
               agentId={firstAgentId()}
-              initialPrompt={props.task.initialPrompt}
+              initialPrompt={
+                // Hold the initial prompt until the worktree's dependencies are installed
+                props.task.setupStatus === 'running' ? undefined : props.task.initialPrompt
+              }
               prefillPrompt={props.task.prefillPrompt}
               onSend={() => {
                 if (props.task.initialPrompt) clearInitialPrompt(props.task.id);
@@ -1423,6 +1761,7 @@ export function TaskPanel(props: TaskPanelProps) {
         initialCleanup={getProject(props.task.projectId)?.deleteBranchOnClose ?? true}
         onDone={() => setShowMergeConfirm(false)}
         onDiffFileClick={(file) => setDiffScrollTarget(file.path)}
+        onPreviewMerge={() => setShowMergePreview(true)}
       />
       <PushDialog
         open={showPushConfirm()}
@@ -1448,12 +1787,59 @@ export function TaskPanel(props: TaskPanelProps) {
           }
         }}
       />
+      <SnapshotsDialog
+        open={showSnapshots()}
+        task={props.task}
+        onClose={() => setShowSnapshots(false)}
+      />
+      <TransferChangesDialog
+        open={showTransfer()}
+        task={props.task}
+        onClose={() => setShowTransfer(false)}
+      />
+      <CompareTasksDialog
+        open={showCompare()}
+        task={props.task}
+        onClose={() => setShowCompare(false)}
+      />
+      <DuplicateTaskDialog
+        open={showDuplicate()}
+        task={props.task}
+        onClose={() => setShowDuplicate(false)}
+      />
+      <ApplyPatchDialog
+        open={showApplyPatch()}
+        task={props.task}
+        onClose={() => setShowApplyPatch(false)}
+      />
+      <PullRequestDialog
+        open={showPullRequest()}
+        task={props.task}
+        onClose={() => setShowPullRequest(false)}
+      />
+      <ScriptsDialog
+        open={showScripts()}
+        task={props.task}
+        onClose={() => setShowScripts(false)}
+      />
       <DiffViewerDialog
         scrollToFile={diffScrollTarget()}
+        worktreePath={changesRepo().worktreePath}
+        projectRoot={getProject(changesRepo().projectId)?.path}
+        branchName={props.task.branchName}
+        generatedPatterns={getProject(changesRepo().projectId)?.generatedFiles}
+        onClose={() => setDiffScrollTarget(null)}
+        taskId={props.task.id}
+        agentId={props.task.agentIds[0]}
+      />
+      <DiffViewerDialog
+        scrollToFile={showMergePreview() ? '' : null}
         worktreePath={props.task.worktreePath}
         projectRoot={getProject(props.task.projectId)?.path}
         branchName={props.task.branchName}
-        onClose={() => setDiffScrollTarget(null)}
+        generatedPatterns={getProject(props.task.projectId)?.generatedFiles}
+        mergePreview
+        onClose={() => setShowMergePreview(false)}
         taskId={props.task.id}
         agentId={props.task.agentIds[0]}
       />
@@ -144,8 +144,6 @@ export function TerminalPanel(props: TerminalPanelProps) {
             agentId={props.terminal.agentId}
             isShell
             isFocused={props.isActive && store.focusedPanel[props.terminal.id] === 'terminal'}
-            command=""
-            args={['-l']}
             cwd=""
             onReady={(focusFn) => registerFocusFn(`${props.terminal.id}:terminal`, focusFn)}
             fontSize={Math.round(13 * getFontScale(`${props.terminal.id}:terminal`))}
@@ -11,7 +11,12 @@ import { matchesGlobalShortcut } from '../lib/shortcuts';
 import { isMac } from '../lib/platform';
 import { store } from '../store/store';
 import { registerTerminal, unregisterTerminal, markDirty } from '../lib/terminalFitManager';
-import type { PtyOutput } from '../ipc/types';
+import type {
+  AutoResponseAuditEntry,
+  ExitInfo,
+  IdleTimeoutAction,
+  PtyOutput,
+} from '../ipc/types';
 
 // Pre-computed base64 lookup table — avoids atob() intermediate string allocation.
 const B64_LOOKUP = new Uint8Array(128);
@@ -40,19 +45,42 @@ function base64ToUint8Array(b64: string): Uint8Array {
 interface TerminalViewProps {
   taskId: string;
   agentId: string;
-  command: string;
-  args: string[];
+  /** Agent command and args; shells (isShell) always run the user's login shell. */
+  command?: string;
+  args?: string[];
   cwd: string;
   env?: Record<string, string>;
   isShell?: boolean;
   dockerMode?: boolean;
   dockerImage?: string;
-  onExit?: (exitInfo: {
-    exit_code: number | null;
-    signal: string | null;
-    last_output: string[];
-  }) => void;
+  /** Restart with these args if the process crashes (backend-side supervision). */
+  supervise?: { resumeArgs: string[] };
+  /** Regex→response rules the backend answers automatically. */
+  autoRespond?: { pattern: string; response: string }[];
+  /** Per-agent args from settings; the backend appends them, including on restarts. */
+  extraArgs?: string[];
+  /** Record the session as an asciicast file in the backend. */
+  record?: boolean;
+  /** Copy OSC 52 clipboard writes from the program to the system clipboard. */
+  clipboard?: boolean;
+  /** Stop or suspend the agent after this long without output or input. */
+  idleTimeout?: { minutes: number; action: IdleTimeoutAction };
+  /** Spawn niced with a lower IO class; changed at runtime via setTaskLowPriority. */
+  priority?: 'normal' | 'below_normal' | 'low';
+  onExit?: (exitInfo: ExitInfo) => void;
   onData?: (data: Uint8Array) => void;
+  /** Backend idle/active transitions (output quiet for the idle period, then resumed). */
+  onIdle?: (idleSecs: number) => void;
+  onActive?: () => void;
+  /** Backend matched a confirmation prompt the agent is blocked on. */
+  onAwaitingInput?: (matchedPrompt: string) => void;
+  onAutoResponded?: (entry: AutoResponseAuditEntry) => void;
+  /** The program set the window title (OSC 0/2). */
+  onTitle?: (title: string) => void;
+  /** The program rang the terminal bell. */
+  onBell?: () => void;
+  /** The backend stopped or suspended the agent after the idle timeout. */
+  onIdleTimeout?: (idleMins: number, action: IdleTimeoutAction) => void;
   onPromptDetected?: (text: string) => void;
   onReady?: (focusFn: () => void) => void;
   onBufferReady?: (getBuffer: () => string) => void;
@@ -138,9 +166,7 @@ export function TerminalView(props: TerminalViewProps) {
 
       if (isPaste) {
         e.preventDefault();
-        navigator.clipboard.readText().then((text) => {
-          if (text) enqueueInput(text);
-        });
+        void pasteFromClipboard();
         return false;
       }
 
@@ -162,17 +188,9 @@ export function TerminalView(props: TerminalViewProps) {
     let ptyPaused = false;
     const FLOW_HIGH = 256 * 1024; // 256KB — pause PTY reader
     const FLOW_LOW = 32 * 1024; // 32KB — resume PTY reader
-    let pendingExitPayload: {
-      exit_code: number | null;
-      signal: string | null;
-      last_output: string[];
-    } | null = null;
-
-    function emitExit(payload: {
-      exit_code: number | null;
-      signal: string | null;
-      last_output: string[];
-    }) {
+    let pendingExitPayload: ExitInfo | null = null;
+
+    function emitExit(payload: ExitInfo) {
       if (!term) return;
       term.write('\r\n\x1b[90m[Process exited]\x1b[0m\r\n');
       props.onExit?.(payload);
@@ -277,6 +295,25 @@ export function TerminalView(props: TerminalViewProps) {
           pendingExitPayload = null;
           emitExit(exit);
         }
+      } else if (msg.type === 'Idle') {
+        props.onIdle?.(msg.data.idle_secs);
+      } else if (msg.type === 'Active') {
+        props.onActive?.();
+      } else if (msg.type === 'AwaitingInput') {
+        props.onAwaitingInput?.(msg.data.matched_prompt);
+      } else if (msg.type === 'AutoResponded') {
+        props.onAutoResponded?.(msg.data);
+      } else if (msg.type === 'Title') {
+        props.onTitle?.(msg.data);
+      } else if (msg.type === 'Bell') {
+        props.onBell?.();
+      } else if (msg.type === 'IdleTimeout') {
+        props.onIdleTimeout?.(msg.data.idle_mins, msg.data.action);
+      } else if (msg.type === 'OutputSuppressed') {
+        const kb = Math.round(msg.data.bytes / 1024);
+        enqueueOutput(
+          new TextEncoder().encode(`\r\n\x1b[2m[output suppressed: ${kb} KB]\x1b[0m\r\n`),
+        );
       }
     };
 
@@ -308,6 +345,49 @@ export function TerminalView(props: TerminalViewProps) {
       }, 8);
     }
 
+    async function pasteImage(blob: Blob) {
+      const bytes = new Uint8Array(await blob.arrayBuffer());
+      let binary = '';
+      for (let i = 0; i < bytes.length; i += 0x8000) {
+        binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+      }
+      flushPendingInput();
+      await invoke(IPC.PasteImage, { agentId, data: btoa(binary), mimeType: blob.type });
+    }
+
+    // Images on the clipboard are saved to a temp file and their path pasted,
+    // like a native terminal does; everything else pastes as text.
+    async function pasteFromClipboard() {
+      try {
+        for (const item of await navigator.clipboard.read()) {
+          const type = item.types.find((t) => t.startsWith('image/'));
+          if (type) {
+            await pasteImage(await item.getType(type));
+            return;
+          }
+        }
+      } catch (err) {
+        console.warn('Image paste failed, falling back to text:', err);
+      }
+      const text = await navigator.clipboard.readText();
+      if (text) {
+        flushPendingInput();
+        await invoke(IPC.PasteToAgent, { agentId, data: text });
+      }
+    }
+
+    containerRef.addEventListener('dragover', (e) => {
+      if (e.dataTransfer?.types.includes('Files')) e.preventDefault();
+    });
+    containerRef.addEventListener('drop', (e) => {
+      const images = [...(e.dataTransfer?.files ?? [])].filter((f) => f.type.startsWith('image/'));
+      if (images.length === 0) return;
+      e.preventDefault();
+      void (async () => {
+        for (const file of images) await pasteImage(file);
+      })().catch((err) => console.warn('Image drop failed:', err));
+    });
+
     // eslint-disable-next-line solid/reactivity -- event handler reads current prop values intentionally
     term.onData((data) => {
       if (props.onPromptDetected) {
@@ -375,21 +455,51 @@ export function TerminalView(props: TerminalViewProps) {
       // WebGL2 not supported — DOM renderer used automatically
     }
 
-    invoke(IPC.SpawnAgent, {
-      taskId,
-      agentId,
-      command: props.command,
-      args: props.args,
-      cwd: props.cwd,
-      env: props.env ?? {},
-      cols: term.cols,
-      rows: term.rows,
-      isShell: props.isShell,
-      dockerMode: props.dockerMode,
-      dockerImage: props.dockerImage,
-      onOutput,
-      // eslint-disable-next-line solid/reactivity -- promise catch handler reads current prop values intentionally
-    }).catch((err) => {
+    const spawned = props.isShell
+      ? invoke(IPC.SpawnShell, {
+          taskId,
+          shellId: agentId,
+          cwd: props.cwd,
+          env: props.env ?? {},
+          cols: term.cols,
+          rows: term.rows,
+          dockerMode: props.dockerMode,
+          dockerImage: props.dockerImage,
+          onOutput,
+        })
+      : invoke(IPC.SpawnAgent, {
+          taskId,
+          agentId,
+          command: props.command ?? '',
+          args: props.args ?? [],
+          cwd: props.cwd,
+          env: props.env ?? {},
+          cols: term.cols,
+          rows: term.rows,
+          dockerMode: props.dockerMode,
+          dockerImage: props.dockerImage,
+          supervise: props.supervise,
+          autoRespond: props.autoRespond,
+          extraArgs: props.extraArgs,
+          record: props.record,
+          clipboard: props.clipboard,
+          idleTimeout: props.idleTimeout,
+          priority: props.priority,
+          onOutput,
+        });
+    // Over the running-agent limit the backend queues the spawn and returns its position
+    spawned.then(
+      (position) => {
+        if (typeof position === 'number' && position > 0) {
+          term?.write(
+            `\x1b[33m[parallel-code] Waiting for a free agent slot (#${position})\x1b[0m\r\n`,
+          );
+        }
+      },
+      () => {}, // reported by the catch below
+    );
+    // eslint-disable-next-line solid/reactivity -- promise catch handler reads current prop values intentionally
+    spawned.catch((err) => {
       // Strip control/escape characters to prevent terminal escape injection
       // eslint-disable-next-line no-control-regex -- intentionally stripping control/escape chars to prevent terminal injection
       const safeErr = String(err).replace(/[\x00-\x1f\x7f]/g, '');
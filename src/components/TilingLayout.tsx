@@ -117,7 +117,7 @@ export function TilingLayout() {
                               const msg = task.directMode
                                 ? 'Close this task? Running agents and shells will be stopped.'
                                 : 'Close this task? The worktree and branch will be deleted.';
-                              if (window.confirm(msg)) closeTask(panelId);
+                              if (window.confirm(msg)) closeTask(panelId, true);
                             } else if (store.terminals[panelId]) {
                               closeTerminal(panelId);
                             }
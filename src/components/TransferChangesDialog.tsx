@@ -0,0 +1,198 @@
+import { For, Show, createEffect, createResource, createSignal } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, transferChanges } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { Task } from '../store/types';
+import type { ChangedFile } from '../ipc/types';
+
+interface TransferChangesDialogProps {
+  open: boolean;
+  task: Task;
+  onClose: () => void;
+}
+
+export function TransferChangesDialog(props: TransferChangesDialogProps) {
+  const [targetId, setTargetId] = createSignal('');
+  const [excluded, setExcluded] = createSignal<Set<string>>(new Set());
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [message, setMessage] = createSignal('');
+
+  const targets = () =>
+    [...store.taskOrder, ...store.collapsedTaskOrder]
+      .map((id) => store.tasks[id])
+      .filter((t) => t && t.id !== props.task.id && t.projectId === props.task.projectId);
+
+  const [files] = createResource(
+    () => (props.open ? props.task.worktreePath : null),
+    (worktreePath) => invoke<ChangedFile[]>(IPC.GetChangedFiles, { worktreePath }),
+  );
+
+  createEffect(() => {
+    if (props.open) {
+      setTargetId(targets()[0]?.id ?? '');
+      setExcluded(new Set<string>());
+      setError('');
+      setMessage('');
+    }
+  });
+
+  function toggleFile(path: string, checked: boolean) {
+    const next = new Set(excluded());
+    if (checked) next.delete(path);
+    else next.add(path);
+    setExcluded(next);
+  }
+
+  async function transfer() {
+    const target = targetId();
+    if (!target) return;
+    const all = (files() ?? []).map((f) => f.path);
+    const selected = all.filter((p) => !excluded().has(p));
+    setBusy(true);
+    setError('');
+    setMessage('');
+    try {
+      const result = await transferChanges(
+        props.task.id,
+        target,
+        excluded().size > 0 ? selected : undefined,
+      );
+      const n = result.files.length;
+      const conflicts = result.conflicted_files.length;
+      const summary = n === 0 ? 'Nothing to transfer' : `Transferred ${n} file${n > 1 ? 's' : ''}`;
+      setMessage(conflicts > 0 ? `${summary}; ${conflicts} left with conflict markers` : summary);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="520px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Transfer changes
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Copies this task's changes since it branched from main, committed or not, into another
+        task's worktree as uncommitted changes. This task is left as it is.
+      </div>
+
+      <Show
+        when={targets().length > 0}
+        fallback={
+          <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+            No other tasks in this project.
+          </div>
+        }
+      >
+        <select
+          value={targetId()}
+          onChange={(e) => setTargetId(e.currentTarget.value)}
+          style={{
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '6px',
+            color: theme.fg,
+            padding: '6px 8px',
+            'font-size': '12px',
+          }}
+        >
+          <For each={targets()}>{(t) => <option value={t.id}>{t.name}</option>}</For>
+        </select>
+      </Show>
+
+      <div
+        style={{
+          display: 'flex',
+          'flex-direction': 'column',
+          gap: '2px',
+          'max-height': '240px',
+          'overflow-y': 'auto',
+          'font-size': '12px',
+        }}
+      >
+        <Show
+          when={(files() ?? []).length > 0}
+          fallback={
+            <div style={{ color: theme.fgSubtle }}>
+              {files.loading ? 'Loading...' : 'No changes.'}
+            </div>
+          }
+        >
+          <For each={files()}>
+            {(file) => (
+              <label
+                style={{
+                  display: 'flex',
+                  'align-items': 'center',
+                  gap: '8px',
+                  color: theme.fg,
+                  cursor: 'pointer',
+                  'font-family': "'JetBrains Mono', monospace",
+                }}
+              >
+                <input
+                  type="checkbox"
+                  checked={!excluded().has(file.path)}
+                  onChange={(e) => toggleFile(file.path, e.currentTarget.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                {file.path}
+              </label>
+            )}
+          </For>
+        </Show>
+      </div>
+
+      <Show when={error()}>
+        <div style={{ ...bannerStyle(theme.error), 'font-size': '12px' }}>{error()}</div>
+      </Show>
+      <Show when={message()}>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted }}>{message()}</div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <button
+          type="button"
+          class="btn-primary"
+          disabled={busy() || !targetId() || (files() ?? []).every((f) => excluded().has(f.path))}
+          onClick={() => void transfer()}
+          style={{
+            padding: '9px 20px',
+            background: theme.accent,
+            border: 'none',
+            'border-radius': '8px',
+            color: theme.accentText,
+            cursor: 'pointer',
+            'font-size': '13px',
+            'font-weight': '500',
+          }}
+        >
+          {busy() ? 'Transferring...' : 'Transfer'}
+        </button>
+      </div>
+    </Dialog>
+  );
+}
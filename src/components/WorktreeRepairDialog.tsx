@@ -0,0 +1,258 @@
+import { For, Show, createEffect, createSignal, untrack } from 'solid-js';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, getProject, getProjectBranchPrefix } from '../store/store';
+import { Dialog } from './Dialog';
+import { theme, bannerStyle } from '../lib/theme';
+import type { WorktreeRepairReport } from '../ipc/types';
+
+interface WorktreeRepairDialogProps {
+  open: boolean;
+  projectId: string;
+  onClose: () => void;
+}
+
+const checkboxLabelStyle = {
+  display: 'flex',
+  'align-items': 'center',
+  gap: '8px',
+  cursor: 'pointer',
+  'font-size': '12px',
+  color: theme.fg,
+  'font-family': "'JetBrains Mono', monospace",
+};
+
+const listHeadingStyle = { 'font-size': '12px', color: theme.fgMuted, 'font-weight': '600' };
+
+export function WorktreeRepairDialog(props: WorktreeRepairDialogProps) {
+  const [report, setReport] = createSignal<WorktreeRepairReport | null>(null);
+  const [selected, setSelected] = createSignal<Set<string>>(new Set());
+  const [busy, setBusy] = createSignal(false);
+  const [error, setError] = createSignal('');
+  const [message, setMessage] = createSignal('');
+
+  // A task whose worktree vanished still owns its branch; don't offer it for deletion by default
+  const taskForBranch = (branch: string) =>
+    Object.values(store.tasks).find(
+      (t) => t && t.projectId === props.projectId && t.branchName === branch,
+    );
+
+  async function runRepair() {
+    const project = getProject(props.projectId);
+    if (!project) return;
+    setBusy(true);
+    try {
+      const result = await invoke<WorktreeRepairReport>(IPC.RepairWorktrees, {
+        projectRoot: project.path,
+        worktreeBaseDir: project.worktreeDir,
+        branchPrefix: getProjectBranchPrefix(project.id),
+      });
+      setReport(result);
+      setSelected(
+        new Set([
+          ...result.orphaned_dirs,
+          ...result.orphaned_branches.filter((b) => !taskForBranch(b)),
+        ]),
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  createEffect(() => {
+    if (props.open) {
+      setReport(null);
+      setMessage('');
+      setError('');
+      untrack(() => void runRepair());
+    }
+  });
+
+  function toggle(item: string, checked: boolean) {
+    const next = new Set(selected());
+    if (checked) next.add(item);
+    else next.delete(item);
+    setSelected(next);
+  }
+
+  async function removeSelected() {
+    const project = getProject(props.projectId);
+    const current = report();
+    if (!project || !current) return;
+    setBusy(true);
+    setError('');
+    try {
+      const result = await invoke<{ removed: number; errors: string[] }>(
+        IPC.RemoveWorktreeOrphans,
+        {
+          projectRoot: project.path,
+          worktreeBaseDir: project.worktreeDir,
+          dirs: current.orphaned_dirs.filter((d) => selected().has(d)),
+          branches: current.orphaned_branches.filter((b) => selected().has(b)),
+        },
+      );
+      setMessage(`Removed ${result.removed} item${result.removed === 1 ? '' : 's'}`);
+      if (result.errors.length > 0) setError(result.errors.join('\n'));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+    await runRepair();
+  }
+
+  const orphanCount = () =>
+    (report()?.orphaned_dirs.length ?? 0) + (report()?.orphaned_branches.length ?? 0);
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} width="600px">
+      <h2 style={{ margin: '0', 'font-size': '16px', color: theme.fg, 'font-weight': '600' }}>
+        Repair worktrees
+      </h2>
+      <div style={{ 'font-size': '12px', color: theme.fgMuted, 'line-height': '1.5' }}>
+        Fixes git's links to worktrees that were moved, forgets worktrees whose directory is gone,
+        and lists directories and task branches nothing uses anymore.
+      </div>
+
+      <Show when={busy() && !report()}>
+        <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>Checking worktrees...</div>
+      </Show>
+
+      <Show when={report()}>
+        {(r) => (
+          <div style={{ display: 'flex', 'flex-direction': 'column', gap: '10px' }}>
+            <Show when={r().repaired.length > 0}>
+              <div style={listHeadingStyle}>Repaired</div>
+              <For each={r().repaired}>
+                {(line) => <div style={{ 'font-size': '12px', color: theme.fg }}>{line}</div>}
+              </For>
+            </Show>
+            <Show when={r().pruned.length > 0}>
+              <div style={listHeadingStyle}>Forgotten (directory was gone)</div>
+              <For each={r().pruned}>
+                {(w) => (
+                  <div style={{ 'font-size': '12px', color: theme.fg }}>
+                    {w.path}
+                    <Show when={w.branch}>
+                      <span style={{ color: theme.fgSubtle }}> ({w.branch})</span>
+                    </Show>
+                  </div>
+                )}
+              </For>
+            </Show>
+            <Show
+              when={orphanCount() > 0}
+              fallback={
+                <div style={{ 'font-size': '12px', color: theme.fgSubtle }}>
+                  No orphaned directories or task branches.
+                </div>
+              }
+            >
+              <div
+                style={{
+                  display: 'flex',
+                  'flex-direction': 'column',
+                  gap: '4px',
+                  'max-height': '280px',
+                  'overflow-y': 'auto',
+                }}
+              >
+                <Show when={r().orphaned_dirs.length > 0}>
+                  <div style={listHeadingStyle}>Orphaned directories</div>
+                  <For each={r().orphaned_dirs}>
+                    {(dir) => (
+                      <label style={checkboxLabelStyle}>
+                        <input
+                          type="checkbox"
+                          checked={selected().has(dir)}
+                          onChange={(e) => toggle(dir, e.currentTarget.checked)}
+                          style={{ cursor: 'pointer' }}
+                        />
+                        {dir}
+                      </label>
+                    )}
+                  </For>
+                </Show>
+                <Show when={r().orphaned_branches.length > 0}>
+                  <div style={listHeadingStyle}>Branches without a worktree</div>
+                  <For each={r().orphaned_branches}>
+                    {(branch) => (
+                      <label style={checkboxLabelStyle}>
+                        <input
+                          type="checkbox"
+                          checked={selected().has(branch)}
+                          onChange={(e) => toggle(branch, e.currentTarget.checked)}
+                          style={{ cursor: 'pointer' }}
+                        />
+                        {branch}
+                        <Show when={taskForBranch(branch)}>
+                          {(task) => (
+                            <span style={{ color: theme.warning }}>task: {task().name}</span>
+                          )}
+                        </Show>
+                      </label>
+                    )}
+                  </For>
+                </Show>
+              </div>
+            </Show>
+          </div>
+        )}
+      </Show>
+
+      <Show when={message()}>
+        <div style={{ 'font-size': '12px', color: theme.fgMuted }}>{message()}</div>
+      </Show>
+      <Show when={error()}>
+        <div
+          style={{ ...bannerStyle(theme.error), 'font-size': '12px', 'white-space': 'pre-wrap' }}
+        >
+          {error()}
+        </div>
+      </Show>
+
+      <div
+        style={{ display: 'flex', 'justify-content': 'flex-end', gap: '8px', 'padding-top': '4px' }}
+      >
+        <button
+          type="button"
+          class="btn-secondary"
+          onClick={() => props.onClose()}
+          style={{
+            padding: '9px 18px',
+            background: theme.bgInput,
+            border: `1px solid ${theme.border}`,
+            'border-radius': '8px',
+            color: theme.fgMuted,
+            cursor: 'pointer',
+            'font-size': '13px',
+          }}
+        >
+          Close
+        </button>
+        <Show when={orphanCount() > 0}>
+          <button
+            type="button"
+            class="btn-primary"
+            disabled={busy() || selected().size === 0}
+            onClick={() => void removeSelected()}
+            style={{
+              padding: '9px 20px',
+              background: theme.accent,
+              border: 'none',
+              'border-radius': '8px',
+              color: theme.accentText,
+              cursor: 'pointer',
+              'font-size': '13px',
+              'font-weight': '500',
+            }}
+          >
+            {busy() ? 'Working...' : `Remove ${selected().size} selected`}
+          </button>
+        </Show>
+      </div>
+    </Dialog>
+  );
+}
@@ -1,9 +1,35 @@
 export type PtyOutput =
   | { type: 'Data'; data: string } // base64-encoded
-  | {
-      type: 'Exit';
-      data: { exit_code: number | null; signal: string | null; last_output: string[] };
-    };
+  | { type: 'Exit'; data: ExitInfo }
+  | { type: 'Idle'; data: { idle_secs: number } } // no output for the quiet period
+  | { type: 'Active' } // output resumed after Idle
+  | { type: 'AwaitingInput'; data: { matched_prompt: string } } // blocked on a confirmation
+  | { type: 'AutoResponded'; data: AutoResponseAuditEntry } // a rule answered a prompt
+  | { type: 'OutputSuppressed'; data: { bytes: number } } // dropped by backpressure
+  | { type: 'Title'; data: string } // window title set via OSC 0/2
+  | { type: 'Bell' } // the program rang the terminal bell (BEL)
+  | { type: 'IdleTimeout'; data: { idle_mins: number; action: IdleTimeoutAction } };
+
+export type IdleTimeoutAction = 'kill' | 'suspend';
+
+export interface ExitInfo {
+  exit_code: number | null;
+  signal: string | null;
+  last_output: string[];
+  /** Summary from the backend; absent when the process never started. */
+  duration_ms?: number;
+  output_bytes?: number;
+  last_activity_at?: number;
+}
+
+export interface AutoResponseAuditEntry {
+  at: number;
+  task_id: string;
+  agent_id: string;
+  pattern: string;
+  matched: string;
+  response: string;
+}
 
 export interface AgentDef {
   id: string;
@@ -13,13 +39,154 @@ export interface AgentDef {
   resume_args: string[];
   skip_permissions_args: string[];
   description: string;
+  env?: Record<string, string>;
+  custom?: boolean; // user-defined, persisted by the backend
   available?: boolean;
 }
 
+export interface QueuedPrompt {
+  id: string;
+  text: string;
+  queued_at: number;
+}
+
+/** An agent waiting for a free slot under the concurrency limit; `position` is 1-based. */
+export interface QueuedAgent {
+  agentId: string;
+  taskId: string;
+  position: number;
+  queued_at: number;
+}
+
+export type JobStatus = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+export interface JobInfo {
+  id: string;
+  task_id: string;
+  script_id: string;
+  name: string;
+  command: string;
+  status: JobStatus;
+  exit_code: number | null;
+  started_at: number;
+  finished_at: number | null;
+  port: number | null; // dev-server jobs only
+  url: string | null; // as printed by the dev server
+}
+
 export interface CreateTaskResult {
   id: string;
   branch_name: string;
   worktree_path: string;
+  linked_worktrees: LinkedWorktreeResult[];
+  /** False when the task checked out a branch that already existed. */
+  owns_branch: boolean;
+}
+
+/** A worktree a multi-repo task got in one of its other repositories. */
+export interface LinkedWorktreeResult {
+  project_root: string;
+  path: string;
+  /** The task's branch name, or a suffixed one where that name was taken. */
+  branch_name: string;
+}
+
+export interface DuplicateTaskResult extends CreateTaskResult {
+  /** Copied files left with conflict markers. */
+  conflicted_files: string[];
+}
+
+/** What the renderer keeps of an archived task to recreate it on restore. */
+export interface ArchivedTaskSettings {
+  agentDef?: AgentDef;
+  notes?: string;
+  initialPrompt?: string;
+  githubUrl?: string;
+  skipPermissions?: boolean;
+  autoRestart?: boolean;
+  lowPriority?: boolean;
+  dockerMode?: boolean;
+  dockerImage?: string;
+  ownsBranch?: boolean;
+}
+
+export interface ArchivedTask {
+  id: string;
+  name: string;
+  project_root: string;
+  branch_name: string;
+  branch_kept: boolean;
+  archived_at: number;
+  patch_bytes: number;
+  task: ArchivedTaskSettings | null;
+}
+
+export interface RestoreArchivedResult {
+  archived: ArchivedTask;
+  task: CreateTaskResult;
+  conflicted_files: string[];
+  /** Set when the archived changes didn't apply; the archive is kept then. */
+  patch_error: string | null;
+}
+
+export interface StateMigrationError {
+  from_version: number;
+  to_version: number;
+  failed: { from: number; description: string; error: string }[];
+  /** Copy of the state as it was on disk before loading. */
+  backup_path: string | null;
+}
+
+export interface LoadedAppState {
+  json: string | null;
+  migration_error: StateMigrationError | null;
+  /** Why nothing is being saved, e.g. another instance holds the state lock. */
+  read_only_reason: string | null;
+}
+
+/**
+ * Whose copy wins when another instance changed saved state: this window's,
+ * the other one's (the window reloads), or the other one's with this window's
+ * kept aside in `.conflict` files.
+ */
+export type StateConflictChoice = 'keep_mine' | 'reload' | 'stop';
+
+export interface BatchTaskResult {
+  name: string;
+  task: CreateTaskResult | null;
+  error: string | null;
+}
+
+export interface AgentSupervisionEvent {
+  agentId: string;
+  taskId: string;
+  status: 'restarting' | 'gave_up';
+  attempt: number;
+  max_attempts: number;
+  delay_ms?: number;
+  exit_code: number | null;
+  signal: string | null;
+}
+
+export interface SetupCommand {
+  manager: string;
+  command: string;
+}
+
+export interface DeleteTaskResult {
+  trashed_path: string | null;
+}
+
+export interface IgnoredEntry {
+  path: string;
+  is_dir: boolean;
+  size_bytes: number | null;
+}
+
+export interface DeleteTaskPreview {
+  unmerged_commits: number;
+  uncommitted_files: number;
+  running_agents: string[];
 }
 
 export interface TaskInfo {
@@ -37,11 +204,46 @@ export interface ChangedFile {
   lines_removed: number;
   status: string;
   committed: boolean;
+  /** Uncommitted changes are staged in the index. */
+  staged?: boolean;
+  binary?: boolean;
+  /** Tracked by Git LFS; line counts are zero rather than the pointer's. */
+  lfs?: boolean;
+  /** Source path of a renamed or copied file. */
+  old_path?: string;
+  /** Matches the project's generated-file patterns; left out of line totals. */
+  generated?: boolean;
+}
+
+export interface DiffOptions {
+  ignore_whitespace?: 'all' | 'change';
+  context_lines?: number;
+  detect_renames?: boolean;
+}
+
+export interface PatchHunk {
+  id: string;
+  header: string;
+  body: string;
+}
+
+export interface AheadBehind {
+  ahead: number;
+  behind: number;
 }
 
 export interface WorktreeStatus {
   has_committed_changes: boolean;
   has_uncommitted_changes: boolean;
+  commits_ahead_of_main: number;
+  commits_behind_main: number;
+  origin_main: AheadBehind | null;
+  has_remote_branch: boolean;
+  remote_ahead_behind: AheadBehind | null;
+}
+
+export interface WorktreeStatusSummary extends WorktreeStatus {
+  changed_files: number;
 }
 
 export interface MergeStatus {
@@ -49,14 +251,256 @@ export interface MergeStatus {
   conflicting_files: string[];
 }
 
+export interface BranchConflict {
+  branch_a: string;
+  branch_b: string;
+  files: string[];
+}
+
+export interface WorktreeDiffStats {
+  worktree_path: string;
+  commits: number;
+  files_changed: number;
+  lines_added: number;
+  lines_removed: number;
+  error?: string;
+}
+
+export interface IntegrationBranchResult {
+  branch: string;
+  merged: string[];
+  skipped: { branch: string; conflicting_files: string[] }[];
+  conflicts: BranchConflict[];
+}
+
+export interface ConflictHunk {
+  start_line: number;
+  end_line: number;
+  ours_label: string;
+  theirs_label: string;
+  ours: string;
+  base: string | null; // only for diff3-style markers
+  theirs: string;
+}
+
+export interface ConflictFile {
+  path: string;
+  status: string; // porcelain XY, e.g. UU, AA, DU
+  binary: boolean;
+  hunks: ConflictHunk[];
+}
+
+export type ConflictResolution = 'ours' | 'theirs' | { content: string };
+
+export interface CommitInfo {
+  hash: string;
+  short_hash: string;
+  author_name: string;
+  author_email: string;
+  date: string;
+  subject: string;
+  files_changed: number;
+  lines_added: number;
+  lines_removed: number;
+}
+
+export interface BlameLine {
+  line: number;
+  hash: string;
+  author: string;
+  author_email: string;
+  author_time: number;
+  summary: string;
+  uncommitted: boolean;
+}
+
+export interface WorktreeSnapshot {
+  id: string;
+  label: string;
+  created_at: number;
+  head: string;
+}
+
+export interface StashEntry {
+  ref: string;
+  branch: string | null;
+  message: string;
+  created_at: number;
+}
+
+export interface PatchApplyResult {
+  files: string[];
+  conflicted_files: string[];
+}
+
+export interface DiscoveredBranch {
+  branch: string;
+  worktree_path: string | null;
+  committed_at: number;
+}
+
+export interface UndoableOperation {
+  operation: 'merge' | 'rebase';
+  created_at: number;
+  branch: string;
+}
+
+export type ForgeKind = 'github' | 'gitlab' | 'bitbucket';
+
+export interface GitRemote {
+  name: string;
+  fetch_url: string;
+  push_url: string;
+}
+
+export interface ForgeInfo {
+  kind: ForgeKind;
+  name: string;
+  request_label: string; // "pull request" or "merge request"
+}
+
+export type CiState = 'success' | 'failure' | 'pending' | 'none';
+
+export interface CiStatus {
+  state: CiState;
+  passed: number;
+  failed: number;
+  pending: number;
+  url: string | null;
+  error: string | null; // why there is no status, e.g. the branch was never pushed
+  host_not_configured: boolean; // not asked: no saved token for the remote's host
+  checked_at: number;
+}
+
+export interface PullRequestResult {
+  kind: ForgeKind;
+  url: string;
+  number: number;
+}
+
+export interface PullMainResult {
+  main_branch: string;
+  pulled_commits: number;
+}
+
+export interface RebaseResult {
+  status: 'done' | 'conflicted';
+  conflicting_files: string[];
+  step: { current: number; total: number } | null;
+}
+
+export interface ConflictVerification {
+  ok: boolean;
+  unresolved_files: string[];
+  files_with_markers: string[];
+  check: HookResult | null;
+}
+
+export interface ConflictDetails {
+  operation: 'merge' | 'rebase' | 'cherry-pick' | 'revert' | null;
+  files: ConflictFile[];
+}
+
+export interface HookResult {
+  command: string;
+  exit_code: number | null;
+  output: string;
+}
+
 export interface MergeResult {
   main_branch: string;
   lines_added: number;
   lines_removed: number;
+  pre_merge_checks: HookResult[];
+  post_merge_hooks: HookResult[];
+  stash_conflict: boolean;
+}
+
+export interface StructuredDiffLine {
+  kind: 'context' | 'add' | 'remove';
+  old_no: number | null;
+  new_no: number | null;
+  content: string;
+  no_newline?: boolean;
+  /** Changed [start, end) offsets within content; absent means the whole line. */
+  changes?: [number, number][];
+}
+
+export interface StructuredDiffHunk {
+  header: string;
+  old_start: number;
+  old_count: number;
+  new_start: number;
+  new_count: number;
+  lines: StructuredDiffLine[];
+}
+
+export interface StructuredDiffFile {
+  old_path: string | null;
+  new_path: string | null;
+  status: 'A' | 'M' | 'D' | 'R' | 'C';
+  old_mode: string | null;
+  new_mode: string | null;
+  binary: boolean;
+  hunks: StructuredDiffHunk[];
+}
+
+export interface FileAtRevision {
+  rev: string;
+  exists: boolean;
+  binary: boolean;
+  content: string;
 }
 
 export interface FileDiffResult {
   diff: string;
   oldContent: string;
   newContent: string;
+  /** Present when the diff was too large to send whole. */
+  truncated?: {
+    total_bytes: number;
+    total_lines: number;
+    shown_lines: number;
+    content_omitted: boolean;
+  };
+  binary?: boolean;
+  /** Base64 before/after for image files; a side is null when missing or too large. */
+  image?: { mime: string; old: string | null; new: string | null };
+  /** Object sizes in bytes for Git LFS files; a side is null when missing. */
+  lfs?: { old_size: number | null; new_size: number | null };
+}
+
+export interface FileDiffRange {
+  lines: string[];
+  offset: number;
+  total_lines: number;
+}
+
+/** Options for commits and merges the app makes; unset ones follow the repo's git config. */
+export interface CommitOptions {
+  sign?: boolean;
+  skipHooks?: boolean; // pass --no-verify
+}
+
+/** merge: merge commit; squash: one commit; rebase: rebase onto main, then fast-forward. */
+export type MergeStrategy = 'merge' | 'squash' | 'rebase';
+
+export interface MergePreview {
+  main_branch: string;
+  diff: string;
+  auto_merged_files: string[];
+  conflicting_files: string[];
+}
+
+export interface WorktreeRepairReport {
+  repaired: string[];
+  pruned: { path: string; branch: string | null }[];
+  orphaned_dirs: string[];
+  orphaned_branches: string[];
+}
+
+export interface TaskLeftover {
+  branch: string | null;
+  path: string | null;
+  size_bytes: number | null;
 }
@@ -0,0 +1,35 @@
+import { describe, it, expect } from 'vitest';
+import { parseChecklist } from './checklist';
+
+describe('parseChecklist', () => {
+  it('returns open items and skips checked ones', () => {
+    const markdown = '# Sprint\n- [ ] Add login\n- [x] Fix header\n* [ ] Dark mode\n';
+    expect(parseChecklist(markdown)).toEqual([
+      { title: 'Add login', prompt: 'Add login' },
+      { title: 'Dark mode', prompt: 'Dark mode' },
+    ]);
+  });
+
+  it('folds indented lines into the prompt', () => {
+    expect(
+      parseChecklist('- [ ] Add login\n  - use OAuth\n  - keep sessions\n- [ ] Export CSV'),
+    ).toEqual([
+      { title: 'Add login', prompt: 'Add login\n\n- use OAuth\n- keep sessions' },
+      { title: 'Export CSV', prompt: 'Export CSV' },
+    ]);
+  });
+
+  it('treats plain bullets as items only when nothing has a checkbox', () => {
+    expect(parseChecklist('1. Add login\n2. Export CSV').map((i) => i.title)).toEqual([
+      'Add login',
+      'Export CSV',
+    ]);
+    expect(parseChecklist('- [ ] Add login\n- note to self').map((i) => i.title)).toEqual([
+      'Add login',
+    ]);
+  });
+
+  it('returns nothing for prose', () => {
+    expect(parseChecklist('Refactor the parser\nfor better errors')).toEqual([]);
+  });
+});
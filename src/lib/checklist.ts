@@ -0,0 +1,46 @@
+const ITEM_RE = /^(\s*)(?:[-*+]|\d+[.)])\s+(?:\[([ xX])\]\s+)?(.*)$/;
+
+export interface ChecklistItem {
+  /** The item's own line, used for the task name. */
+  title: string;
+  /** The title plus any indented notes under it, for the agent. */
+  prompt: string;
+}
+
+/**
+ * Open items of a markdown TODO list, one per top-level bullet. Checked items
+ * (`- [x]`) are skipped, and once any item has a checkbox, plain bullets
+ * count as notes. Lines indented under an item become part of its prompt.
+ */
+export function parseChecklist(markdown: string): ChecklistItem[] {
+  const lines = markdown.split('\n');
+  const items = lines.map((l) => ITEM_RE.exec(l));
+  const topIndent = Math.min(...items.map((m) => (m ? m[1].length : Infinity)));
+  if (topIndent === Infinity) return [];
+  const hasBoxes = items.some((m) => m && m[1].length === topIndent && m[2] !== undefined);
+
+  const result: ChecklistItem[] = [];
+  let current: { title: string; notes: string[] } | null = null;
+  const close = () => {
+    if (!current) return;
+    const { title, notes } = current;
+    const body = notes.join('\n').trim();
+    result.push({ title, prompt: body ? `${title}\n\n${body}` : title });
+    current = null;
+  };
+
+  lines.forEach((line, i) => {
+    const m = items[i];
+    if (m && m[1].length === topIndent) {
+      close();
+      const open = hasBoxes ? m[2] === ' ' : true;
+      if (open && m[3].trim()) current = { title: m[3].trim(), notes: [] };
+    } else if (current && (!line.trim() || /^\s/.test(line))) {
+      current.notes.push(line.slice(Math.min(topIndent + 2, line.search(/\S|$/))));
+    } else {
+      close();
+    }
+  });
+  close();
+  return result;
+}
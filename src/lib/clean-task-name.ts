@@ -41,3 +41,12 @@ export function cleanTaskName(text: string): string {
 
   return result || text.trim();
 }
+
+/** Task name from free text: its first line, cleaned, cut at ~40 chars on a word boundary. */
+export function taskNameFromText(text: string): string {
+  const p = text.trim();
+  if (!p) return '';
+  const firstLine = cleanTaskName(p.split('\n')[0]);
+  if (firstLine.length <= 40) return firstLine;
+  return firstLine.slice(0, 40).replace(/\s+\S*$/, '') || firstLine.slice(0, 40);
+}
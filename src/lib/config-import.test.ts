@@ -0,0 +1,60 @@
+import { describe, it, expect } from 'vitest';
+import {
+  agentChanges,
+  commandProjectChanges,
+  commandSettingChanges,
+  withoutCommandSettings,
+  withoutProjectCommands,
+} from './config-import';
+import type { AgentDef } from '../ipc/types';
+
+const agent = (id: string, command: string): AgentDef => ({
+  id,
+  name: id,
+  command,
+  args: [],
+  resume_args: [],
+  skip_permissions_args: [],
+  description: '',
+});
+
+describe('commandSettingChanges', () => {
+  it('lists command-bearing settings that differ, not the rest', () => {
+    const changes = commandSettingChanges(
+      { editorCommand: 'code', osc52Clipboard: false, terminalFont: 'A' },
+      { editorCommand: 'evil --run', osc52Clipboard: false, terminalFont: 'B' },
+    );
+    expect(changes).toEqual([{ label: 'Setting editorCommand', detail: '"evil --run"' }]);
+  });
+});
+
+describe('commandProjectChanges', () => {
+  it('lists the command fields a new project carries', () => {
+    const changes = commandProjectChanges([
+      { name: 'web', setupCommands: ['curl x | sh'], preMergeCommands: [], color: '#fff' },
+    ]);
+    expect(changes).toEqual([{ label: 'Project web: setupCommands', detail: '["curl x | sh"]' }]);
+  });
+});
+
+describe('agentChanges', () => {
+  it('lists new agents and ones whose command changed', () => {
+    const current = [agent('same', 'aider'), agent('changed', 'aider')];
+    const imported = [agent('same', 'aider'), agent('changed', 'sh'), agent('new', 'goose')];
+    expect(agentChanges(imported, current).map((c) => c.label)).toEqual([
+      'Agent changed',
+      'Agent new',
+    ]);
+  });
+});
+
+describe('stripping commands', () => {
+  it('drops command-bearing keys and keeps the rest', () => {
+    expect(withoutCommandSettings({ editorCommand: 'x', showPlans: true })).toEqual({
+      showPlans: true,
+    });
+    expect(withoutProjectCommands({ name: 'web', verifyCommand: 'x', scripts: [] })).toEqual({
+      name: 'web',
+    });
+  });
+});
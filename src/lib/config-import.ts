@@ -0,0 +1,83 @@
+import type { AgentDef } from '../ipc/types';
+
+// Parts of an imported config that run commands or widen what agents and
+// terminals may do. A shared "team baseline" file can carry any of them, so
+// they are listed for review and only applied when the user accepts them.
+
+export const COMMAND_SETTING_KEYS = [
+  'autoResponseRules',
+  'agentExtraArgs',
+  'editorCommand',
+  'autoTrustFolders',
+  'osc52Clipboard',
+] as const;
+
+export const COMMAND_PROJECT_KEYS = [
+  'setupCommands',
+  'preMergeCommands',
+  'postMergeCommands',
+  'verifyCommand',
+  'scripts',
+  'autoInstallDeps',
+] as const;
+
+export interface ImportChange {
+  /** What would change, e.g. "Setting editorCommand" or "Agent aider". */
+  label: string;
+  /** The imported value as JSON. */
+  detail: string;
+}
+
+function isEmpty(v: unknown): boolean {
+  if (v === undefined || v === null || v === '' || v === false) return true;
+  if (Array.isArray(v)) return v.length === 0;
+  return typeof v === 'object' && Object.keys(v).length === 0;
+}
+
+/** Command-bearing settings whose imported value differs from the current one. */
+export function commandSettingChanges(
+  current: object,
+  imported: Record<string, unknown>,
+): ImportChange[] {
+  const before = current as Record<string, unknown>;
+  return COMMAND_SETTING_KEYS.filter(
+    (key) =>
+      key in imported && JSON.stringify(imported[key]) !== JSON.stringify(before[key] ?? null),
+  ).map((key) => ({ label: `Setting ${key}`, detail: JSON.stringify(imported[key]) }));
+}
+
+/** Command-bearing fields set on the projects that would be added. */
+export function commandProjectChanges(projects: object[]): ImportChange[] {
+  return projects.flatMap((p) => {
+    const project = p as Record<string, unknown>;
+    return COMMAND_PROJECT_KEYS.filter((key) => !isEmpty(project[key])).map((key) => ({
+      label: `Project ${String(project.name)}: ${key}`,
+      detail: JSON.stringify(project[key]),
+    }));
+  });
+}
+
+/** Imported custom agents that are new or differ from the custom agent of the same id. */
+export function agentChanges(imported: AgentDef[], current: AgentDef[]): ImportChange[] {
+  const command = (a: AgentDef) => ({ command: a.command, args: a.args, env: a.env ?? {} });
+  return imported
+    .filter((agent) => {
+      const existing = current.find((a) => a.id === agent.id);
+      return !existing || JSON.stringify(command(existing)) !== JSON.stringify(command(agent));
+    })
+    .map((agent) => ({ label: `Agent ${agent.id}`, detail: JSON.stringify(command(agent)) }));
+}
+
+/** The settings without their command-bearing keys. */
+export function withoutCommandSettings(settings: Record<string, unknown>): Record<string, unknown> {
+  const rest = { ...settings };
+  for (const key of COMMAND_SETTING_KEYS) delete rest[key];
+  return rest;
+}
+
+/** The project without its command-bearing fields. */
+export function withoutProjectCommands<T extends object>(project: T): T {
+  const rest: Record<string, unknown> = { ...project };
+  for (const key of COMMAND_PROJECT_KEYS) delete rest[key];
+  return rest as T;
+}
@@ -0,0 +1,28 @@
+import { describe, it, expect } from 'vitest';
+import { conflictLookup, conflictCounts } from './conflict-matrix';
+
+const conflicts = [
+  { branch_a: 'task/a', branch_b: 'task/b', files: ['src/app.ts'] },
+  { branch_a: 'task/a', branch_b: 'task/c', files: ['README.md', 'package.json'] },
+];
+
+describe('conflictLookup', () => {
+  it('finds a pair in either order', () => {
+    const lookup = conflictLookup(conflicts);
+    expect(lookup('task/a', 'task/b')).toEqual(['src/app.ts']);
+    expect(lookup('task/c', 'task/a')).toEqual(['README.md', 'package.json']);
+  });
+
+  it('returns no files for pairs that merge cleanly', () => {
+    expect(conflictLookup(conflicts)('task/b', 'task/c')).toEqual([]);
+  });
+});
+
+describe('conflictCounts', () => {
+  it('counts conflicting partners per branch', () => {
+    const counts = conflictCounts(conflicts);
+    expect(counts.get('task/a')).toBe(2);
+    expect(counts.get('task/b')).toBe(1);
+    expect(counts.get('task/d')).toBeUndefined();
+  });
+});
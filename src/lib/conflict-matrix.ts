@@ -0,0 +1,26 @@
+import type { BranchConflict } from '../ipc/types';
+
+/**
+ * Index pairwise branch conflicts so a matrix cell can look up the files two
+ * branches conflict on, in either order.
+ */
+export function conflictLookup(
+  conflicts: BranchConflict[],
+): (branchA: string, branchB: string) => string[] {
+  const byPair = new Map<string, string[]>();
+  for (const c of conflicts) {
+    byPair.set(`${c.branch_a}\0${c.branch_b}`, c.files);
+    byPair.set(`${c.branch_b}\0${c.branch_a}`, c.files);
+  }
+  return (a, b) => byPair.get(`${a}\0${b}`) ?? [];
+}
+
+/** Number of other branches each branch conflicts with. */
+export function conflictCounts(conflicts: BranchConflict[]): Map<string, number> {
+  const counts = new Map<string, number>();
+  for (const c of conflicts) {
+    counts.set(c.branch_a, (counts.get(c.branch_a) ?? 0) + 1);
+    counts.set(c.branch_b, (counts.get(c.branch_b) ?? 0) + 1);
+  }
+  return counts;
+}
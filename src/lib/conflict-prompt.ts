@@ -0,0 +1,31 @@
+import type { ConflictVerification } from '../ipc/types';
+
+/** Prompt asking the task's agent to resolve the conflicts of a paused rebase. */
+export function conflictResolutionPrompt(files: string[]): string {
+  const list = files.map((f) => `- ${f}`).join('\n');
+  return (
+    'A rebase onto the main branch stopped with merge conflicts in these files:\n' +
+    `${list}\n\n` +
+    'Resolve the conflicts in each file, keeping the intent of both sides, and remove every ' +
+    'conflict marker (<<<<<<<, |||||||, =======, >>>>>>>). Stage the resolved files with ' +
+    '`git add`, but do not run `git rebase --continue` or `git rebase --abort`.'
+  );
+}
+
+/** Follow-up prompt when the agent's resolution did not pass verification. */
+export function conflictFollowUpPrompt(result: ConflictVerification): string {
+  const lines = ['The conflict resolution is not complete yet.'];
+  if (result.files_with_markers.length) {
+    const files = result.files_with_markers.join(', ');
+    lines.push(`These files still contain conflict markers: ${files}`);
+  }
+  if (result.unresolved_files.length) {
+    lines.push(`These files are still unmerged: ${result.unresolved_files.join(', ')}`);
+  }
+  if (result.check && result.check.exit_code !== 0) {
+    const output = result.check.output.trim().split('\n').slice(-40).join('\n');
+    lines.push(`The verify command \`${result.check.command}\` failed:\n${output}`);
+  }
+  lines.push('Fix this and stage the files, but do not continue or abort the rebase.');
+  return lines.join('\n\n');
+}
@@ -4,3 +4,18 @@ export function getLocalDateKey(value: Date = new Date()): string {
   const day = String(value.getDate()).padStart(2, '0');
   return `${year}-${month}-${day}`;
 }
+
+/** Value for an `<input type="datetime-local">`, in local time. */
+export function toDateTimeLocalValue(value: Date): string {
+  const hours = String(value.getHours()).padStart(2, '0');
+  const minutes = String(value.getMinutes()).padStart(2, '0');
+  return `${getLocalDateKey(value)}T${hours}:${minutes}`;
+}
+
+/** Short local start time: "02:00", or "Fri 02:00" when it isn't today. */
+export function formatStartTime(ms: number, now: Date = new Date()): string {
+  const at = new Date(ms);
+  const time = at.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+  if (getLocalDateKey(at) === getLocalDateKey(now)) return time;
+  return `${at.toLocaleDateString([], { weekday: 'short' })} ${time}`;
+}
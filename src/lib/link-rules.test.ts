@@ -0,0 +1,46 @@
+import { describe, it, expect } from 'vitest';
+import { parseLinkRules, formatLinkRules, partitionLinkPaths } from './link-rules';
+
+describe('parseLinkRules', () => {
+  it('parses symlink, copy and skip entries', () => {
+    expect(parseLinkRules('.env.local\nvendor copy\ntarget clone\n!node_modules\n')).toEqual([
+      { path: '.env.local', mode: 'symlink' },
+      { path: 'vendor', mode: 'copy' },
+      { path: 'target', mode: 'clone' },
+      { path: 'node_modules', mode: 'skip' },
+    ]);
+  });
+
+  it('ignores blanks, comments, trailing slashes and duplicates', () => {
+    expect(parseLinkRules('# secrets\n\n  .venv/  \n.venv copy')).toEqual([
+      { path: '.venv', mode: 'symlink' },
+    ]);
+  });
+
+  it('round-trips through formatLinkRules', () => {
+    const text = '.env\nbuild copy\nnode_modules clone\n!.idea';
+    expect(formatLinkRules(parseLinkRules(text))).toBe(text);
+  });
+});
+
+describe('partitionLinkPaths', () => {
+  it('routes copy, clone and hardlink paths by mode', () => {
+    const rules = parseLinkRules('vendor copy\ntarget clone\n.venv hardlink\n.env');
+    expect(
+      partitionLinkPaths(['node_modules', 'vendor', 'target', '.venv', '.env'], rules),
+    ).toEqual({
+      symlinkDirs: ['node_modules', '.env'],
+      copyPaths: ['vendor'],
+      clonePaths: ['target'],
+      hardlinkPaths: ['.venv'],
+    });
+  });
+
+  it('uses the first rule for a path, so per-task overrides can be prepended', () => {
+    const rules = [
+      { path: 'node_modules', mode: 'copy' as const },
+      ...parseLinkRules('node_modules'),
+    ];
+    expect(partitionLinkPaths(['node_modules'], rules).copyPaths).toEqual(['node_modules']);
+  });
+});
@@ -0,0 +1,74 @@
+import type { WorktreeLinkRule } from '../store/types';
+
+/** How a selected candidate gets into the worktree. */
+export type LinkMode = Exclude<WorktreeLinkRule['mode'], 'skip'>;
+
+/**
+ * Parse the worktree-files textarea. One entry per line:
+ *   `path`        symlink into the worktree (default)
+ *   `path copy`   copy into the worktree
+ *   `path clone`  copy-on-write clone (reflink), falling back to a symlink
+ *   `path hardlink` copy whose files are hard links to the originals
+ *   `!path`       never offer this path, even if it is a built-in candidate
+ * Blank lines and lines starting with `#` are ignored.
+ */
+export function parseLinkRules(text: string): WorktreeLinkRule[] {
+  const rules: WorktreeLinkRule[] = [];
+  const seen = new Set<string>();
+  for (const raw of text.split('\n')) {
+    const line = raw.trim();
+    if (!line || line.startsWith('#')) continue;
+    let rule: WorktreeLinkRule;
+    if (line.startsWith('!')) {
+      rule = { path: line.slice(1).trim(), mode: 'skip' };
+    } else {
+      const m = line.match(/^(.*?)\s+(copy|clone|hardlink|symlink)$/);
+      rule = m
+        ? { path: m[1], mode: m[2] as WorktreeLinkRule['mode'] }
+        : { path: line, mode: 'symlink' };
+    }
+    rule.path = rule.path.replace(/\/+$/, '');
+    if (!rule.path || seen.has(rule.path)) continue;
+    seen.add(rule.path);
+    rules.push(rule);
+  }
+  return rules;
+}
+
+export function formatLinkRules(rules: WorktreeLinkRule[]): string {
+  return rules
+    .map((r) => {
+      if (r.mode === 'skip') return `!${r.path}`;
+      return r.mode === 'symlink' ? r.path : `${r.path} ${r.mode}`;
+    })
+    .join('\n');
+}
+
+/** Split the selected candidates by link mode according to the project's rules. */
+export function partitionLinkPaths(
+  selected: Iterable<string>,
+  rules: WorktreeLinkRule[],
+): {
+  symlinkDirs: string[];
+  copyPaths: string[];
+  clonePaths: string[];
+  hardlinkPaths: string[];
+} {
+  const symlinkDirs: string[] = [];
+  const copyPaths: string[] = [];
+  const clonePaths: string[] = [];
+  const hardlinkPaths: string[] = [];
+  for (const p of selected) {
+    const mode = linkModeFor(p, rules);
+    if (mode === 'copy') copyPaths.push(p);
+    else if (mode === 'clone') clonePaths.push(p);
+    else if (mode === 'hardlink') hardlinkPaths.push(p);
+    else symlinkDirs.push(p);
+  }
+  return { symlinkDirs, copyPaths, clonePaths, hardlinkPaths };
+}
+
+/** Mode a selected candidate will be linked with, for display. */
+export function linkModeFor(path: string, rules: WorktreeLinkRule[]): WorktreeLinkRule['mode'] {
+  return rules.find((r) => r.path === path)?.mode ?? 'symlink';
+}
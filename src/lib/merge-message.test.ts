@@ -0,0 +1,51 @@
+import { describe, it, expect } from 'vitest';
+import {
+  DEFAULT_MERGE_MESSAGE_TEMPLATE,
+  renderMergeMessage,
+  squashMessageFromLog,
+} from './merge-message';
+
+describe('renderMergeMessage', () => {
+  it('fills known placeholders and keeps unknown ones', () => {
+    const vars = { name: 'Dark mode', branch: 'task/dark' };
+    expect(renderMergeMessage(DEFAULT_MERGE_MESSAGE_TEMPLATE, vars)).toBe(
+      "Merge task 'Dark mode' (task/dark)",
+    );
+    expect(renderMergeMessage('{name} {ticket}', { name: 'x' })).toBe('x {ticket}');
+  });
+});
+
+describe('squashMessageFromLog', () => {
+  it('uses a single commit subject as is', () => {
+    expect(squashMessageFromLog('- abc123 fix: handle empty input', 'Task')).toBe(
+      'fix: handle empty input',
+    );
+  });
+
+  it('falls back to the task name for an empty log', () => {
+    expect(squashMessageFromLog('', 'Add dark mode')).toBe('Add dark mode');
+  });
+
+  it('prefixes the title with the most significant conventional type', () => {
+    const log = '- a1 fix(ui): align buttons\n- b2 feat(ui): dark palette\n- c3 chore(ui): tidy';
+    expect(squashMessageFromLog(log, 'Dark mode').split('\n')).toEqual([
+      'feat(ui): Dark mode',
+      '',
+      '- fix(ui): align buttons',
+      '- feat(ui): dark palette',
+      '- chore(ui): tidy',
+    ]);
+  });
+
+  it('drops mixed scopes and keeps breaking markers', () => {
+    const log = '- a1 feat(api)!: drop v1\n- b2 fix(ui): retry';
+    expect(squashMessageFromLog(log, 'API v2').split('\n')[0]).toBe('feat!: API v2');
+  });
+
+  it('titles non-conventional branches with the task name', () => {
+    const log = '- a1 Add palette\n- b2 Wire toggle';
+    expect(squashMessageFromLog(log, 'Dark mode')).toBe(
+      'Dark mode\n\n- Add palette\n- Wire toggle',
+    );
+  });
+});
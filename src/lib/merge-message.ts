@@ -0,0 +1,63 @@
+import type { MergeStrategy } from '../ipc/types';
+
+export const DEFAULT_MERGE_MESSAGE_TEMPLATE = "Merge task '{name}' ({branch})";
+
+// Types earlier in the list win when a branch mixes several
+const TYPE_PRIORITY = ['feat', 'fix', 'perf', 'refactor', 'docs', 'test', 'build', 'ci', 'chore'];
+
+const CONVENTIONAL_RE = /^([a-z]+)(?:\(([^)]*)\))?(!)?: (.+)$/i;
+
+interface ConventionalSubject {
+  type: string;
+  scope: string | null;
+  breaking: boolean;
+}
+
+function parseConventional(subject: string): ConventionalSubject | null {
+  const m = CONVENTIONAL_RE.exec(subject);
+  if (!m) return null;
+  return { type: m[1].toLowerCase(), scope: m[2] || null, breaking: m[3] === '!' };
+}
+
+function typeRank(type: string): number {
+  const i = TYPE_PRIORITY.indexOf(type);
+  return i === -1 ? TYPE_PRIORITY.length : i;
+}
+
+/** Fill `{name}` and `{branch}` style placeholders; unknown ones are left as written. */
+export function renderMergeMessage(template: string, vars: Record<string, string>): string {
+  return template.replace(/\{(\w+)\}/g, (match, key: string) => vars[key] ?? match);
+}
+
+/**
+ * A squash commit message from `get_branch_log` output (`- <hash> <subject>`
+ * lines). When the branch uses conventional commits, the title takes the
+ * most significant type, the shared scope and any breaking marker.
+ */
+export function squashMessageFromLog(log: string, taskName: string): string {
+  const subjects = log
+    .split('\n')
+    .map((l) => l.replace(/^- [a-f0-9]+ /, '').trim())
+    .filter(Boolean);
+  if (subjects.length === 0) return taskName;
+  if (subjects.length === 1) return subjects[0];
+
+  const parsed = subjects
+    .map(parseConventional)
+    .filter((c): c is ConventionalSubject => c !== null);
+  let title = taskName;
+  if (parsed.length > 0) {
+    const type = parsed.map((c) => c.type).sort((a, b) => typeRank(a) - typeRank(b))[0];
+    const scopes = new Set(parsed.map((c) => c.scope));
+    const scope = scopes.size === 1 ? [...scopes][0] : null;
+    const breaking = parsed.some((c) => c.breaking);
+    title = `${type}${scope ? `(${scope})` : ''}${breaking ? '!' : ''}: ${taskName}`;
+  }
+  return [title, '', ...subjects.map((s) => `- ${s}`)].join('\n');
+}
+
+export const MERGE_STRATEGY_OPTIONS: { value: MergeStrategy; label: string }[] = [
+  { value: 'merge', label: 'Merge (fast-forward when possible)' },
+  { value: 'squash', label: 'Squash into one commit' },
+  { value: 'rebase', label: 'Rebase onto main, then fast-forward' },
+];
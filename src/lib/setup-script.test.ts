@@ -0,0 +1,23 @@
+import { describe, it, expect } from 'vitest';
+import { failedSetupStep, setupScript } from './setup-script';
+
+describe('setupScript', () => {
+  it('runs each command in its own process and exits with the failing step', () => {
+    expect(setupScript(['npm ci', "echo 'hi'"])).toBe(
+      `sh -c 'sh -c '\\''npm ci'\\'' || { printf '\\''%s\\n'\\'' ` +
+        `'\\''Setup step 1 failed: npm ci'\\'' >&2; exit 1; }; ` +
+        `sh -c '\\''echo '\\''\\'\\'''\\''hi'\\''\\'\\'''\\'''\\'' || { printf '\\''%s\\n'\\'' ` +
+        `'\\''Setup step 2 failed: echo '\\''\\'\\'''\\''hi'\\''\\'\\'''\\'''\\'' >&2; exit 2; }'; exit`,
+    );
+  });
+});
+
+describe('failedSetupStep', () => {
+  it('maps the exit code back to the command', () => {
+    const commands = ['npm ci', 'make'];
+    expect(failedSetupStep(commands, 2)).toBe('make');
+    expect(failedSetupStep(commands, 0)).toBeNull();
+    expect(failedSetupStep(commands, 130)).toBeNull();
+    expect(failedSetupStep(commands, null)).toBeNull();
+  });
+});
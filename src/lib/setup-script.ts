@@ -0,0 +1,25 @@
+// Builds the line typed into a task's setup shell. Each setup command runs as
+// its own `sh -c` process, so a `cd` or `export` in one doesn't leak into the
+// next, and the first failure stops the rest. The line exits with the failing
+// step's 1-based index so the caller can name it.
+
+function quote(s: string): string {
+  return `'${s.replace(/'/g, `'\\''`)}'`;
+}
+
+/** A one-line command for the user's shell (bash, zsh or fish) that runs `commands` in order. */
+export function setupScript(commands: string[]): string {
+  const steps = commands.map((command, i) => {
+    const failed = quote(`Setup step ${i + 1} failed: ${command}`);
+    return `sh -c ${quote(command)} || { printf '%s\\n' ${failed} >&2; exit ${i + 1}; }`;
+  });
+  // Wrapped in `sh -c` so the `{ }` groups parse under fish too; `exit`
+  // without an argument then propagates its status
+  return `sh -c ${quote(steps.join('; '))}; exit`;
+}
+
+/** The command that failed, given the setup shell's exit code. */
+export function failedSetupStep(commands: string[], exitCode: number | null): string | null {
+  if (exitCode === null || exitCode < 1 || exitCode > commands.length) return null;
+  return commands[exitCode - 1];
+}
@@ -0,0 +1,33 @@
+import { describe, it, expect } from 'vitest';
+import { parseScriptLines, formatScriptLines } from './task-scripts';
+
+describe('parseScriptLines', () => {
+  it('parses named and bare presets', () => {
+    expect(parseScriptLines('Dev server: pnpm dev\nnpm test\n')).toEqual([
+      { id: 'dev-server', name: 'Dev server', command: 'pnpm dev' },
+      { id: 'npm-test', name: 'npm test', command: 'npm test' },
+    ]);
+  });
+
+  it('keeps colons that are part of the command', () => {
+    const [named, bare] = parseScriptLines('Build: tsc -p a:b\nnpm run build:prod');
+    expect(named).toEqual({ id: 'build', name: 'Build', command: 'tsc -p a:b' });
+    expect(bare.command).toBe('npm run build:prod');
+  });
+
+  it('ignores blanks and comments and de-duplicates ids', () => {
+    const scripts = parseScriptLines('# presets\n\nTest: pnpm test\ntest: pnpm test:e2e');
+    expect(scripts.map((s) => s.id)).toEqual(['test', 'test-2']);
+  });
+
+  it('marks dev servers', () => {
+    expect(parseScriptLines('Web [server]: pnpm dev --port $PORT')).toEqual([
+      { id: 'web', name: 'Web', command: 'pnpm dev --port $PORT', devServer: true },
+    ]);
+  });
+
+  it('round-trips through formatScriptLines', () => {
+    const text = 'Dev [server]: pnpm dev\nLint: pnpm lint\ncargo check';
+    expect(formatScriptLines(parseScriptLines(text))).toBe(text);
+  });
+});
@@ -0,0 +1,47 @@
+import type { ProjectScript } from '../store/types';
+
+function slugify(name: string): string {
+  return (
+    name
+      .toLowerCase()
+      .replace(/[^a-z0-9]+/g, '-')
+      .replace(/^-+|-+$/g, '') || 'script'
+  );
+}
+
+/**
+ * Parse the scripts textarea. One preset per line:
+ *   `Name: command`          a named preset
+ *   `Name [server]: command` a dev server, given its own port per task
+ *   `command`                a preset named after its command
+ * Blank lines and lines starting with `#` are ignored. Ids are derived from
+ * the name, so a job stays linked to its preset while the command is edited.
+ */
+export function parseScriptLines(text: string): ProjectScript[] {
+  const scripts: ProjectScript[] = [];
+  const ids = new Set<string>();
+  for (const raw of text.split('\n')) {
+    const line = raw.trim();
+    if (!line || line.startsWith('#')) continue;
+    const m = line.match(/^([^:]+?):\s+(.+)$/);
+    const label = m ? m[1].trim() : line;
+    const command = m ? m[2].trim() : line;
+    const server = label.match(/^(.*?)\s*\[server\]$/);
+    const name = server ? server[1] || command : label;
+    const base = slugify(name);
+    let id = base;
+    for (let n = 2; ids.has(id); n++) id = `${base}-${n}`;
+    ids.add(id);
+    scripts.push(server ? { id, name, command, devServer: true } : { id, name, command });
+  }
+  return scripts;
+}
+
+export function formatScriptLines(scripts: ProjectScript[]): string {
+  return scripts
+    .map((s) => {
+      if (s.devServer) return `${s.name} [server]: ${s.command}`;
+      return s.name === s.command ? s.command : `${s.name}: ${s.command}`;
+    })
+    .join('\n');
+}
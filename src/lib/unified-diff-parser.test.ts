@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { parseUnifiedDiff } from './unified-diff-parser';
+import { appendDiffLines, parseUnifiedDiff } from './unified-diff-parser';
 
 // ---------------------------------------------------------------------------
 // Empty input
@@ -369,4 +369,57 @@ describe('parseUnifiedDiff', () => {
       expect(hunk.newCount).toBe(1);
     });
   });
+
+  // -------------------------------------------------------------------------
+  // Renamed file with a quoted path
+  // -------------------------------------------------------------------------
+  describe('renamed file', () => {
+    const diff = [
+      'diff --git a/old name.ts "b/new\\tname.ts"',
+      'similarity index 90%',
+      'rename from old name.ts',
+      'rename to "new\\tname.ts"',
+      '--- a/old name.ts',
+      '+++ "b/new\\tname.ts"',
+      '@@ -1 +1 @@',
+      '-old',
+      '+new',
+    ].join('\n');
+
+    it('uses the new, unquoted path', () => {
+      const [file] = parseUnifiedDiff(diff);
+      expect(file.path).toBe('new\tname.ts');
+      expect(file.status).toBe('M');
+      expect(file.hunks[0].lines).toHaveLength(2);
+    });
+  });
+
+  // -------------------------------------------------------------------------
+  // Truncated diffs, continued page by page
+  // -------------------------------------------------------------------------
+  describe('truncated diff', () => {
+    const diff = [
+      'diff --git a/big.lock b/big.lock',
+      '--- a/big.lock',
+      '+++ b/big.lock',
+      '@@ -1,3 +1,3 @@',
+      ' keep',
+      '-old',
+      '\\ Diff truncated: 6 of 8 lines',
+    ].join('\n');
+
+    it('reads the marker and continues the open hunk with the next lines', () => {
+      const [file] = parseUnifiedDiff(diff);
+      expect(file.truncated).toEqual({ shownLines: 6, totalLines: 8 });
+
+      const full = appendDiffLines(file, ['+new', ' tail']);
+      expect(full.truncated).toBeUndefined();
+      expect(full.hunks[0].lines.map((l) => [l.type, l.oldLine, l.newLine])).toEqual([
+        ['context', 1, 1],
+        ['remove', 2, null],
+        ['add', null, 2],
+        ['context', 3, 3],
+      ]);
+    });
+  });
 });
@@ -1,3 +1,9 @@
+// View model for the diff views. Parsing is shared with the backend's typed
+// diff API (electron/ipc/diff-structured), so renames, mode changes and
+// `\ No newline` markers are read the same way on both sides.
+
+import { parseDiffFiles, parseHunks, type DiffHunk } from '../../electron/ipc/diff-structured';
+
 /** Structured representation of a single diff line. */
 export interface DiffLine {
   type: 'add' | 'remove' | 'context';
@@ -21,117 +27,84 @@ export interface FileDiff {
   status: 'M' | 'A' | 'D';
   binary: boolean;
   hunks: Hunk[];
+  /** Set when the backend cut a huge diff short; load the rest with `appendDiffLines`. */
+  truncated?: { shownLines: number; totalLines: number };
 }
 
-const HUNK_HEADER_RE = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/;
+const TRUNCATED_RE = /^\\ Diff truncated: (\d+) of (\d+) lines$\n?/m;
+
+function toHunk(hunk: DiffHunk): Hunk {
+  return {
+    oldStart: hunk.old_start,
+    oldCount: hunk.old_count,
+    newStart: hunk.new_start,
+    newCount: hunk.new_count,
+    lines: hunk.lines.map((l) => ({
+      type: l.kind,
+      content: l.content,
+      oldLine: l.old_no,
+      newLine: l.new_no,
+    })),
+  };
+}
 
 /**
  * Parse raw `git diff` output into structured FileDiff objects.
  *
- * Handles multiple files, add/modify/delete status, binary files,
- * multiple hunks, and the "no newline at end of file" marker.
+ * Renames and copies show as modified files under their new path; a
+ * `\ Diff truncated` marker from the backend is read into `truncated`.
  */
 export function parseUnifiedDiff(raw: string): FileDiff[] {
   if (!raw.trim()) return [];
 
-  // Split on `diff --git` boundaries, keeping each file block together.
+  // One block per file, so each truncation marker stays with its file
   const blocks = raw.split(/^(?=diff --git )/m).filter((b) => b.trim());
   const files: FileDiff[] = [];
 
   for (const block of blocks) {
-    const lines = block.split('\n');
-    const fileDiff = parseFileBlock(lines);
-    if (fileDiff) files.push(fileDiff);
+    const marker = TRUNCATED_RE.exec(block);
+    const [file] = parseDiffFiles(marker ? block.replace(TRUNCATED_RE, '') : block);
+    const path = file?.new_path ?? file?.old_path;
+    if (!file || !path) continue;
+    const status = file.status === 'A' || file.status === 'D' ? file.status : 'M';
+    const hunks = file.hunks.map(toHunk);
+    if (!marker) {
+      files.push({ path, status, binary: file.binary, hunks });
+      continue;
+    }
+    const truncated = { shownLines: Number(marker[1]), totalLines: Number(marker[2]) };
+    files.push({ path, status, binary: file.binary, hunks, truncated });
   }
 
   return files;
 }
 
-/** Extract file path from the `diff --git a/<path> b/<path>` header. */
-function extractPath(headerLine: string): string {
-  // "diff --git a/foo/bar b/foo/bar" -> "foo/bar"
-  const match = headerLine.match(/^diff --git a\/(.+?) b\/(.+)$/);
-  return match ? match[2] : '';
-}
-
-/** Parse a single file's diff block into a FileDiff. */
-function parseFileBlock(lines: string[]): FileDiff | null {
-  if (lines.length === 0) return null;
-
-  const path = extractPath(lines[0]);
-  if (!path) return null;
-
-  let status: 'M' | 'A' | 'D' = 'M';
-  let binary = false;
-
-  // Scan metadata lines (everything before the first hunk header).
-  for (const line of lines) {
-    if (HUNK_HEADER_RE.test(line)) break;
-    if (line.startsWith('new file mode')) status = 'A';
-    if (line.startsWith('deleted file mode')) status = 'D';
-    if (line.includes('Binary files') && line.includes('differ')) binary = true;
-  }
-
-  if (binary) {
-    return { path, status, binary, hunks: [] };
-  }
-
-  const hunks = parseHunks(lines);
-  return { path, status, binary, hunks };
-}
-
-/** Parse all hunks from a file block's lines. */
-function parseHunks(lines: string[]): Hunk[] {
-  const hunks: Hunk[] = [];
-  let currentHunk: Hunk | null = null;
-  let oldLine = 0;
-  let newLine = 0;
-
-  for (const line of lines) {
-    const hunkMatch = line.match(HUNK_HEADER_RE);
-    if (hunkMatch) {
-      currentHunk = {
-        oldStart: parseInt(hunkMatch[1], 10),
-        oldCount: hunkMatch[2] !== undefined ? parseInt(hunkMatch[2], 10) : 1,
-        newStart: parseInt(hunkMatch[3], 10),
-        newCount: hunkMatch[4] !== undefined ? parseInt(hunkMatch[4], 10) : 1,
-        lines: [],
-      };
-      hunks.push(currentHunk);
-      oldLine = currentHunk.oldStart;
-      newLine = currentHunk.newStart;
-      continue;
-    }
-
-    if (!currentHunk) continue;
-
-    // Skip "no newline at end of file" marker.
-    if (line.startsWith('\\ ')) continue;
-
-    const prefix = line[0];
-    if (prefix === '+') {
-      currentHunk.lines.push({
-        type: 'add',
-        content: line.slice(1),
-        oldLine: null,
-        newLine: newLine++,
-      });
-    } else if (prefix === '-') {
-      currentHunk.lines.push({
-        type: 'remove',
-        content: line.slice(1),
-        oldLine: oldLine++,
-        newLine: null,
-      });
-    } else if (prefix === ' ') {
-      currentHunk.lines.push({
-        type: 'context',
-        content: line.slice(1),
-        oldLine: oldLine++,
-        newLine: newLine++,
-      });
-    }
+/**
+ * Continue a truncated file diff with its next raw diff lines (a page from
+ * `get_file_diff_range` starting at `truncated.shownLines`).
+ */
+export function appendDiffLines(file: FileDiff, lines: string[]): FileDiff {
+  const hunks = file.hunks.map((h) => ({ ...h, lines: [...h.lines] }));
+  const last = hunks[hunks.length - 1];
+  let page = lines;
+  if (last) {
+    // A header for the rest of the cut-off hunk lets the parser pick it up mid-way
+    const oldSeen = last.lines.filter((l) => l.type !== 'add').length;
+    const newSeen = last.lines.filter((l) => l.type !== 'remove').length;
+    const oldRange = `${last.oldStart + oldSeen},${last.oldCount - oldSeen}`;
+    const newRange = `${last.newStart + newSeen},${last.newCount - newSeen}`;
+    page = [`@@ -${oldRange} +${newRange} @@`, ...lines];
   }
-
-  return hunks;
+  const [resumed, ...rest] = parseHunks(page.join('\n')).map(toHunk);
+  if (last && resumed) last.lines.push(...resumed.lines);
+  else if (resumed) hunks.push(resumed);
+  hunks.push(...rest);
+
+  const shownLines = (file.truncated?.shownLines ?? 0) + lines.length;
+  const totalLines = file.truncated?.totalLines ?? shownLines;
+  return {
+    ...file,
+    hunks,
+    truncated: shownLines < totalLines ? { shownLines, totalLines } : undefined,
+  };
 }
@@ -0,0 +1,37 @@
+import { produce } from 'solid-js/store';
+import { invoke, fireAndForget } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import type { QueuedAgent } from '../ipc/types';
+
+/** 1-based place of an agent in the spawn queue, or 0 when it isn't waiting. */
+export function getAgentQueuePosition(agentId: string): number {
+  return store.queuedAgents[agentId] ?? 0;
+}
+
+/** Cap how many agents run at once; 0 lifts the limit. Waiting agents start as slots free up. */
+export function setMaxRunningAgents(max: number): void {
+  const limit = Math.max(0, Math.round(max));
+  setStore('maxRunningAgents', limit);
+  fireAndForget(IPC.SetAgentConcurrencyLimit, { limit });
+}
+
+/** Start a waiting agent right away, ignoring the limit. */
+export async function startQueuedAgent(agentId: string): Promise<void> {
+  await invoke<boolean>(IPC.StartQueuedAgent, { agentId });
+}
+
+/** Mirror the backend spawn queue into the store. */
+export function startAgentQueueListener(): () => void {
+  return window.electron.ipcRenderer.on(IPC.AgentQueueChanged, (data: unknown) => {
+    if (!Array.isArray(data)) return;
+    setStore(
+      produce((s) => {
+        s.queuedAgents = {};
+        for (const entry of data as QueuedAgent[]) {
+          if (typeof entry?.agentId === 'string') s.queuedAgents[entry.agentId] = entry.position;
+        }
+      }),
+    );
+  });
+}
@@ -2,15 +2,16 @@ import { produce } from 'solid-js/store';
 import { invoke } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import { store, setStore } from './core';
-import type { AgentDef } from '../ipc/types';
-import type { Agent } from './types';
+import type { AgentDef, AgentSupervisionEvent, ExitInfo, IdleTimeoutAction } from '../ipc/types';
+import type { Agent, AutoResponseRule } from './types';
 import { refreshTaskStatus, clearAgentActivity, markAgentSpawned } from './taskStatus';
+import { showNotification } from './notification';
 
+/** Load built-in and user-defined agents; the backend merges and persists both. */
 export async function loadAgents(): Promise<void> {
-  const defaults = await invoke<AgentDef[]>(IPC.ListAgents);
-  const custom = store.customAgents;
-  const customIds = new Set(custom.map((a) => a.id));
-  setStore('availableAgents', [...defaults.filter((d) => !customIds.has(d.id)), ...custom]);
+  const agents = await invoke<AgentDef[]>(IPC.ListAgents);
+  setStore('availableAgents', agents);
+  setStore('customAgents', agents.filter((a) => a.custom));
 }
 
 export async function addAgentToTask(taskId: string, agentDef: AgentDef): Promise<void> {
@@ -42,18 +43,24 @@ export async function addAgentToTask(taskId: string, agentDef: AgentDef): Promis
   markAgentSpawned(agentId);
 }
 
-export function markAgentExited(
-  agentId: string,
-  exitInfo: { exit_code: number | null; signal: string | null; last_output: string[] },
-): void {
+export function markAgentExited(agentId: string, exitInfo: ExitInfo): void {
   const agent = store.agents[agentId];
   setStore(
     produce((s) => {
       if (s.agents[agentId]) {
         s.agents[agentId].status = 'exited';
+        s.agents[agentId].suspended = undefined;
         s.agents[agentId].exitCode = exitInfo.exit_code;
         s.agents[agentId].signal = exitInfo.signal;
         s.agents[agentId].lastOutput = exitInfo.last_output;
+        s.agents[agentId].exitStats =
+          exitInfo.duration_ms !== undefined
+            ? {
+                durationMs: exitInfo.duration_ms,
+                outputBytes: exitInfo.output_bytes ?? 0,
+                lastActivityAt: exitInfo.last_activity_at ?? 0,
+              }
+            : undefined;
       }
     }),
   );
@@ -72,6 +79,9 @@ export function restartAgent(agentId: string, useResumeArgs: boolean): void {
         s.agents[agentId].signal = null;
         s.agents[agentId].lastOutput = [];
         s.agents[agentId].resumed = useResumeArgs;
+        s.agents[agentId].suspended = undefined;
+        s.agents[agentId].title = undefined;
+        s.agents[agentId].exitStats = undefined;
         s.agents[agentId].generation += 1;
       }
     }),
@@ -79,6 +89,16 @@ export function restartAgent(agentId: string, useResumeArgs: boolean): void {
   markAgentSpawned(agentId);
 }
 
+/** Interrupt (Ctrl+C), suspend or continue a running agent without ending its session. */
+export async function signalAgent(
+  agentId: string,
+  signal: 'SIGINT' | 'SIGTSTP' | 'SIGCONT',
+): Promise<void> {
+  await invoke(IPC.SendSignal, { agentId, signal });
+  if (signal === 'SIGINT') return;
+  setStore('agents', agentId, 'suspended', signal === 'SIGTSTP' ? true : undefined);
+}
+
 export function switchAgent(agentId: string, newDef: AgentDef): void {
   setStore(
     produce((s) => {
@@ -96,38 +116,128 @@ export function switchAgent(agentId: string, newDef: AgentDef): void {
   markAgentSpawned(agentId);
 }
 
-export function addCustomAgent(agent: AgentDef): void {
+export async function addCustomAgent(agent: AgentDef): Promise<void> {
+  await invoke(IPC.AddAgent, { agent });
+  await loadAgents();
+}
+
+export async function removeCustomAgent(agentId: string): Promise<void> {
+  await invoke(IPC.RemoveAgent, { agentId });
+  await loadAgents();
+}
+
+export async function updateCustomAgent(agentId: string, updated: AgentDef): Promise<void> {
+  await invoke(IPC.UpdateAgent, { agentId, agent: updated });
+  await loadAgents();
+}
+
+/** Hand custom agents from an old state.json to the backend, skipping ones it has. */
+/** Move custom agents from the old state.json into agents.json; returns the ones that failed. */
+export async function migrateLegacyCustomAgents(agents: AgentDef[]): Promise<AgentDef[]> {
+  const known = new Set(store.customAgents.map((a) => a.id));
+  const failed: AgentDef[] = [];
+  for (const agent of agents) {
+    if (known.has(agent.id)) continue;
+    await invoke(IPC.AddAgent, { agent }).catch((err) => {
+      console.warn(`Failed to migrate custom agent ${agent.id}:`, err);
+      failed.push(agent);
+    });
+  }
+  await loadAgents();
+  return failed;
+}
+
+export function addAutoResponseRule(rule: Omit<AutoResponseRule, 'id'>): void {
   setStore(
     produce((s) => {
-      s.customAgents.push(agent);
+      s.autoResponseRules.push({ ...rule, id: crypto.randomUUID() });
     }),
   );
-  void refreshAvailableAgents();
 }
 
-export function removeCustomAgent(agentId: string): void {
+export function removeAutoResponseRule(ruleId: string): void {
   setStore(
     produce((s) => {
-      s.customAgents = s.customAgents.filter((a) => a.id !== agentId);
+      s.autoResponseRules = s.autoResponseRules.filter((r) => r.id !== ruleId);
     }),
   );
-  void refreshAvailableAgents();
 }
 
-export function updateCustomAgent(agentId: string, updated: AgentDef): void {
+/** Set the args appended to every launch of an agent; empty clears them. */
+export function setAgentExtraArgs(agentDefId: string, args: string[]): void {
   setStore(
     produce((s) => {
-      const idx = s.customAgents.findIndex((a) => a.id === agentId);
-      if (idx >= 0) s.customAgents[idx] = updated;
+      if (args.length > 0) s.agentExtraArgs[agentDefId] = args;
+      else delete s.agentExtraArgs[agentDefId];
     }),
   );
-  void refreshAvailableAgents();
 }
 
-/** Rebuild availableAgents from backend defaults + custom agents. */
-async function refreshAvailableAgents(): Promise<void> {
-  const defaults = await invoke<AgentDef[]>(IPC.ListAgents);
-  const custom = store.customAgents;
-  const customIds = new Set(custom.map((a) => a.id));
-  setStore('availableAgents', [...defaults.filter((d) => !customIds.has(d.id)), ...custom]);
+/** Rules for an agent, in the shape the backend's spawn_agent expects. */
+export function autoResponseRulesFor(agentDefId: string): { pattern: string; response: string }[] {
+  return store.autoResponseRules
+    .filter((r) => r.agentDefId === agentDefId)
+    .map((r) => ({ pattern: r.pattern, response: r.response }));
+}
+
+/** Save the agent's latest asciicast recording via a save dialog. */
+export async function exportSessionRecording(agentId: string): Promise<void> {
+  try {
+    const saved = await invoke<string | null>(IPC.ExportSessionRecording, { agentId });
+    if (saved) showNotification(`Recording saved to ${saved}`);
+  } catch (err) {
+    showNotification(`Export failed: ${String(err)}`);
+  }
+}
+
+export function setAgentTitle(agentId: string, title: string): void {
+  if (!store.agents[agentId]) return;
+  setStore('agents', agentId, 'title', title || undefined);
+}
+
+/** Reflect a backend idle-timeout stop/suspend and tell the user why it happened. */
+export function markAgentIdleTimeout(
+  agentId: string,
+  idleMins: number,
+  action: IdleTimeoutAction,
+): void {
+  const agent = store.agents[agentId];
+  if (!agent) return;
+  if (action === 'suspend') setStore('agents', agentId, 'suspended', true);
+  const name = store.tasks[agent.taskId]?.name ?? 'Agent';
+  const verb = action === 'kill' ? 'stopped' : 'suspended';
+  showNotification(`${name}: agent ${verb} after ${idleMins} min without activity`);
+}
+
+/** Lower or restore the CPU/IO priority of a task's running agents. */
+export async function setTaskLowPriority(taskId: string, low: boolean): Promise<void> {
+  const task = store.tasks[taskId];
+  if (!task) return;
+  const priority = low ? 'low' : 'normal';
+  try {
+    for (const agentId of task.agentIds) {
+      if (store.agents[agentId]?.status !== 'running') continue;
+      await invoke(IPC.SetAgentPriority, { agentId, priority });
+    }
+    setStore('tasks', taskId, 'lowPriority', low || undefined);
+  } catch (err) {
+    showNotification(`Failed to change priority: ${String(err)}`);
+  }
+}
+
+/** Surface backend supervision restarts and give-ups for auto-restart tasks. */
+export function startAgentSupervisionListener(): () => void {
+  return window.electron.ipcRenderer.on(IPC.AgentSupervision, (data: unknown) => {
+    if (!data || typeof data !== 'object') return;
+    const ev = data as AgentSupervisionEvent;
+    const taskName = store.tasks[ev.taskId]?.name ?? 'task';
+    if (ev.status === 'restarting') {
+      markAgentSpawned(ev.agentId);
+      showNotification(
+        `Agent in "${taskName}" crashed — restarting (${ev.attempt}/${ev.max_attempts})`,
+      );
+    } else {
+      showNotification(`Agent in "${taskName}" kept crashing — gave up restarting`);
+    }
+  });
 }
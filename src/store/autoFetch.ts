@@ -0,0 +1,71 @@
+import { createEffect, onCleanup } from 'solid-js';
+import { fireAndForget } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import type { WorktreeStatus } from '../ipc/types';
+
+/** Number of a project's tasks whose branch is behind origin's main branch. */
+export function getTasksBehindOriginMain(projectId: string): number {
+  let count = 0;
+  for (const taskId of [...store.taskOrder, ...store.collapsedTaskOrder]) {
+    if (store.tasks[taskId]?.projectId !== projectId) continue;
+    if ((store.taskGitStatus[taskId]?.origin_main?.behind ?? 0) > 0) count++;
+  }
+  return count;
+}
+
+/**
+ * Keep the backend's per-project background fetchers in sync with each
+ * project's auto-fetch interval and tasks, and mirror the worktree statuses
+ * they push into the store.
+ */
+export function startAutoFetchSync(): () => void {
+  let running = new Set<string>();
+
+  createEffect(() => {
+    const wanted = new Set<string>();
+    const taskIds = [...store.taskOrder, ...store.collapsedTaskOrder];
+    for (const project of store.projects) {
+      const intervalMinutes = project.autoFetchMinutes ?? 0;
+      if (intervalMinutes <= 0) continue;
+      const worktreePaths = taskIds
+        .map((id) => store.tasks[id])
+        .filter((t) => t?.projectId === project.id && t.worktreePath)
+        .map((t) => t.worktreePath);
+      wanted.add(project.id);
+      fireAndForget(IPC.StartAutoFetch, {
+        projectId: project.id,
+        projectRoot: project.path,
+        intervalMinutes,
+        worktreePaths,
+      });
+    }
+    for (const projectId of running) {
+      if (!wanted.has(projectId)) fireAndForget(IPC.StopAutoFetch, { projectId });
+    }
+    running = wanted;
+  });
+
+  const offStatusChanged = window.electron.ipcRenderer.on(
+    IPC.WorktreeStatusChanged,
+    (data: unknown) => {
+      if (!data || typeof data !== 'object') return;
+      const msg = data as { worktreePath: string; status: WorktreeStatus };
+      if (typeof msg.worktreePath !== 'string' || !msg.status) return;
+      for (const taskId of [...store.taskOrder, ...store.collapsedTaskOrder]) {
+        if (store.tasks[taskId]?.worktreePath === msg.worktreePath) {
+          setStore('taskGitStatus', taskId, msg.status);
+        }
+      }
+    },
+  );
+
+  const cleanup = (): void => {
+    offStatusChanged();
+    for (const projectId of running) fireAndForget(IPC.StopAutoFetch, { projectId });
+    running = new Set();
+  };
+
+  onCleanup(cleanup);
+  return cleanup;
+}
@@ -25,11 +25,19 @@ function persistedSnapshot(): string {
     themePreset: store.themePreset,
     windowState: store.windowState,
     autoTrustFolders: store.autoTrustFolders,
+    permanentlyDeleteWorktrees: store.permanentlyDeleteWorktrees,
+    recordSessions: store.recordSessions,
+    osc52Clipboard: store.osc52Clipboard,
+    idleTimeoutMinutes: store.idleTimeoutMinutes,
+    idleTimeoutAction: store.idleTimeoutAction,
     showPlans: store.showPlans,
     desktopNotificationsEnabled: store.desktopNotificationsEnabled,
+    maxRunningAgents: store.maxRunningAgents,
     inactiveColumnOpacity: store.inactiveColumnOpacity,
     editorCommand: store.editorCommand,
-    customAgents: store.customAgents,
+    autoResponseRules: store.autoResponseRules,
+    agentExtraArgs: store.agentExtraArgs,
+    pendingTasks: store.pendingTasks,
     tasks: Object.fromEntries(
       [...store.taskOrder, ...store.collapsedTaskOrder]
         .filter((id) => store.tasks[id])
@@ -42,6 +50,7 @@ function persistedSnapshot(): string {
               lastPrompt: t.lastPrompt,
               name: t.name,
               directMode: t.directMode,
+              pushRemote: t.pushRemote,
               savedInitialPrompt: t.savedInitialPrompt,
               collapsed: t.collapsed,
             },
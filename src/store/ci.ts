@@ -0,0 +1,52 @@
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import { getProjectPath } from './projects';
+import type { CiStatus } from '../ipc/types';
+
+const CI_POLL_MS = 60_000;
+
+const inFlight = new Set<string>();
+
+export function getTaskCi(taskId: string): CiStatus | undefined {
+  return store.taskCiStatus[taskId];
+}
+
+/** Fetch the CI state of a task's pushed branch into the store. */
+export function refreshTaskCi(taskId: string, force = false): void {
+  const task = store.tasks[taskId];
+  if (!task || task.directMode || inFlight.has(taskId)) return;
+  const projectRoot = getProjectPath(task.projectId);
+  if (!projectRoot) return;
+
+  inFlight.add(taskId);
+  invoke<CiStatus>(IPC.GetCiStatus, {
+    projectRoot,
+    branchName: task.branchName,
+    remote: task.pushRemote,
+    force,
+  })
+    .then((status) => {
+      if (store.tasks[taskId]) setStore('taskCiStatus', taskId, status);
+    })
+    .catch(console.warn)
+    .finally(() => inFlight.delete(taskId));
+}
+
+/**
+ * Poll CI for every task. Branches that were never pushed are answered
+ * locally by the backend, so only pushed tasks cost a forge request. Remotes
+ * on a host that is neither the forge's own nor one with a saved token are
+ * never asked, and not polled again until a token is saved.
+ */
+export function startCiPolling(): () => void {
+  const refreshAll = () => {
+    for (const taskId of [...store.taskOrder, ...store.collapsedTaskOrder]) {
+      if (store.taskCiStatus[taskId]?.host_not_configured) continue;
+      refreshTaskCi(taskId);
+    }
+  };
+  refreshAll();
+  const timer = setInterval(refreshAll, CI_POLL_MS);
+  return () => clearInterval(timer);
+}
@@ -0,0 +1,92 @@
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { conflictResolutionPrompt, conflictFollowUpPrompt } from '../lib/conflict-prompt';
+import { store, setStore } from './core';
+import { getProject } from './projects';
+import { sendPrompt } from './tasks';
+import { isAgentAskingQuestion, isAgentIdle } from './taskStatus';
+import { showNotification } from './notification';
+import type { ConflictDetails, ConflictVerification, RebaseResult } from '../ipc/types';
+
+/** Follow-up prompts per conflicting commit before handing back to the user. */
+const MAX_FIX_ATTEMPTS = 2;
+const POLL_MS = 1_000;
+/** How long the agent gets to start working on a prompt before we check anyway. */
+const START_TIMEOUT_MS = 30_000;
+const FINISH_TIMEOUT_MS = 30 * 60_000;
+
+const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+/** Resolve once the agent picked up the prompt and went quiet again at its main prompt. */
+async function waitForAgentToFinish(agentId: string): Promise<void> {
+  const started = Date.now();
+  while (isAgentIdle(agentId) && Date.now() - started < START_TIMEOUT_MS) {
+    await sleep(POLL_MS);
+  }
+  let quietPolls = 0;
+  while (quietPolls < 2) {
+    if (store.agents[agentId]?.status !== 'running') throw new Error('the agent exited');
+    if (Date.now() - started > FINISH_TIMEOUT_MS) throw new Error('timed out waiting for agent');
+    // A question (e.g. a permission prompt) needs the user; keep waiting
+    quietPolls = isAgentIdle(agentId) && !isAgentAskingQuestion(agentId) ? quietPolls + 1 : 0;
+    await sleep(POLL_MS);
+  }
+}
+
+/**
+ * Rebase the task onto main (or pick up a rebase already paused on
+ * conflicts), have the task's agent resolve each conflicting commit, verify
+ * the result and continue. On failure the rebase is left paused so the user
+ * can take over.
+ */
+export async function resolveConflictsWithAgent(taskId: string): Promise<void> {
+  const task = store.tasks[taskId];
+  const agentId = task?.agentIds[0];
+  if (!task || !agentId || store.agents[agentId]?.status !== 'running') {
+    showNotification('Conflict resolution needs a running agent');
+    return;
+  }
+  if (task.conflictHandoff) return;
+  const worktreePath = task.worktreePath;
+  const verifyCommand = getProject(task.projectId)?.verifyCommand?.trim() || undefined;
+
+  setStore('tasks', taskId, 'conflictHandoff', 'rebasing');
+  try {
+    const details = await invoke<ConflictDetails>(IPC.GetConflictDetails, { worktreePath });
+    let state: RebaseResult =
+      details.operation === 'rebase'
+        ? {
+            status: 'conflicted',
+            conflicting_files: details.files.map((f) => f.path),
+            step: null,
+          }
+        : await invoke<RebaseResult>(IPC.RebaseTask, { worktreePath, pauseOnConflict: true });
+
+    while (state.status === 'conflicted') {
+      const files = state.conflicting_files;
+      let prompt = conflictResolutionPrompt(files);
+      for (let attempt = 0; ; attempt++) {
+        setStore('tasks', taskId, 'conflictHandoff', 'resolving');
+        await sendPrompt(taskId, agentId, prompt);
+        await waitForAgentToFinish(agentId);
+
+        setStore('tasks', taskId, 'conflictHandoff', 'verifying');
+        const result = await invoke<ConflictVerification>(IPC.VerifyConflictResolution, {
+          worktreePath,
+          files,
+          verifyCommand,
+        });
+        if (result.ok) break;
+        if (attempt >= MAX_FIX_ATTEMPTS) throw new Error('conflicts are still unresolved');
+        prompt = conflictFollowUpPrompt(result);
+      }
+      setStore('tasks', taskId, 'conflictHandoff', 'rebasing');
+      state = await invoke<RebaseResult>(IPC.ContinueRebase, { worktreePath });
+    }
+    showNotification(`${task.name}: conflicts resolved, rebase complete`);
+  } catch (err) {
+    showNotification(`${task.name}: conflict resolution stopped (${String(err)})`);
+  } finally {
+    setStore('tasks', taskId, 'conflictHandoff', undefined);
+  }
+}
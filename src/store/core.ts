@@ -12,16 +12,23 @@ export const [store, setStore] = createStore<AppStore>({
   tasks: {},
   terminals: {},
   agents: {},
+  promptQueues: {},
+  queuedAgents: {},
+  usageLimitResetAt: null,
+  taskJobs: {},
   activeTaskId: null,
   activeAgentId: null,
   availableAgents: [],
   customAgents: [],
+  autoResponseRules: [],
+  agentExtraArgs: {},
   showNewTaskDialog: false,
   sidebarVisible: true,
   fontScales: {},
   panelSizes: {},
   globalScale: 1,
   taskGitStatus: {},
+  taskCiStatus: {},
   focusedPanel: {},
   sidebarFocused: false,
   sidebarFocusedProjectId: null,
@@ -40,15 +47,25 @@ export const [store, setStore] = createStore<AppStore>({
   themePreset: 'minimal',
   windowState: null,
   autoTrustFolders: false,
+  permanentlyDeleteWorktrees: false,
+  recordSessions: false,
+  osc52Clipboard: false,
+  idleTimeoutMinutes: 0,
+  idleTimeoutAction: 'kill',
   showPlans: true,
   desktopNotificationsEnabled: false,
+  maxRunningAgents: 0,
   inactiveColumnOpacity: 0.6,
   editorCommand: '',
+  forgeTokenHosts: [],
   dockerImage: 'parallel-code-agent:latest',
   dockerAvailable: false,
   newTaskDropUrl: null,
   newTaskPrefillPrompt: null,
   missingProjectIds: {},
+  adoptWorktreesProjectId: null,
+  stateConflictPaths: [],
+  pendingTasks: [],
   remoteAccess: {
     enabled: false,
     token: null,
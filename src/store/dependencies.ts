@@ -0,0 +1,115 @@
+import { produce } from 'solid-js/store';
+import { fireAndForget } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import { createTask } from './tasks';
+import { showNotification } from './notification';
+import type { PendingTask } from './types';
+
+/** Queue a task to be created once `afterTaskId` is merged or `startAt` arrives. */
+export function addPendingTask(pending: Omit<PendingTask, 'id'>): string {
+  const id = crypto.randomUUID();
+  setStore('pendingTasks', (list) => [...list, { ...pending, id }]);
+  if (pending.startAt !== undefined) {
+    fireAndForget(IPC.ScheduleTaskStart, { id, at: pending.startAt });
+  }
+  return id;
+}
+
+export function cancelPendingTask(id: string): void {
+  const pending = store.pendingTasks.find((p) => p.id === id);
+  if (pending?.startAt !== undefined) fireAndForget(IPC.CancelScheduledTaskStart, { id });
+  setStore('pendingTasks', (list) => list.filter((p) => p.id !== id));
+}
+
+export function getPendingTasks(projectId: string): PendingTask[] {
+  return store.pendingTasks.filter((p) => p.projectId === projectId);
+}
+
+/**
+ * Create a waiting task now: its worktree branches off the project root as
+ * it is, so after the merge it starts from the fresh main.
+ */
+export async function startPendingTask(id: string): Promise<void> {
+  const pending = store.pendingTasks.find((p) => p.id === id);
+  if (!pending) return;
+  cancelPendingTask(id);
+  try {
+    await createTask({
+      name: pending.name,
+      agentDef: pending.agentDef,
+      projectId: pending.projectId,
+      initialPrompt: pending.prompt,
+      branchPrefixOverride: pending.branchPrefixOverride,
+      symlinkDirs: pending.symlinkDirs,
+      copyPaths: pending.copyPaths,
+      clonePaths: pending.clonePaths,
+      hardlinkPaths: pending.hardlinkPaths,
+      linkedProjectIds: pending.linkedProjectIds,
+      skipPermissions: pending.skipPermissions,
+      autoRestart: pending.autoRestart,
+      lowPriority: pending.lowPriority,
+      dockerMode: pending.dockerMode,
+      dockerImage: pending.dockerImage,
+    });
+  } catch (err) {
+    // Put it back so the user can retry or cancel it
+    setStore('pendingTasks', (list) => [
+      ...list,
+      { ...pending, blockedReason: `Starting failed: ${String(err)}` },
+    ]);
+    showNotification(`Could not start "${pending.name}": ${String(err)}`);
+  }
+}
+
+/** A task was merged: start everything that waited for it. */
+export function releaseDependents(taskId: string): void {
+  for (const pending of store.pendingTasks.filter((p) => p.afterTaskId === taskId)) {
+    void startPendingTask(pending.id);
+  }
+}
+
+/**
+ * Hand restored start times to the backend scheduler, start pending tasks
+ * when it fires, and remember when reported usage limits reset.
+ */
+export function startTaskScheduler(): () => void {
+  for (const pending of store.pendingTasks) {
+    if (pending.startAt !== undefined && !pending.blockedReason) {
+      fireAndForget(IPC.ScheduleTaskStart, { id: pending.id, at: pending.startAt });
+    }
+  }
+  const offFired = window.electron.ipcRenderer.on(
+    IPC.ScheduledTaskStartFired,
+    (data: unknown) => {
+      const id = (data as { id?: unknown } | null)?.id;
+      if (typeof id === 'string') void startPendingTask(id);
+    },
+  );
+  const offLimit = window.electron.ipcRenderer.on(IPC.UsageLimitDetected, (data: unknown) => {
+    const resetsAt = (data as { resets_at?: unknown } | null)?.resets_at;
+    if (typeof resetsAt === 'number' && resetsAt > Date.now()) {
+      setStore('usageLimitResetAt', resetsAt);
+    }
+  });
+  return () => {
+    offFired();
+    offLimit();
+  };
+}
+
+/** A task was closed without being merged: its dependents won't start on their own. */
+export function blockDependents(taskId: string): void {
+  const waiting = store.pendingTasks.filter((p) => p.afterTaskId === taskId);
+  if (waiting.length === 0) return;
+  setStore(
+    produce((s) => {
+      for (const p of s.pendingTasks) {
+        if (p.afterTaskId === taskId) {
+          p.blockedReason = `"${p.afterTaskName}" was closed without being merged`;
+        }
+      }
+    }),
+  );
+  showNotification(`Tasks waiting for "${waiting[0].afterTaskName}" now need a manual start`);
+}
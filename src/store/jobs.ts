@@ -0,0 +1,71 @@
+import { produce } from 'solid-js/store';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import { getProject } from './projects';
+import type { JobInfo } from '../ipc/types';
+
+export function getTaskJobs(taskId: string): JobInfo[] {
+  return store.taskJobs[taskId] ?? [];
+}
+
+/** Run one of the project's script presets as a background job for a task. */
+export async function runTaskScript(taskId: string, scriptId: string): Promise<JobInfo> {
+  const task = store.tasks[taskId];
+  if (!task) throw new Error('Task not found');
+  const script = getProject(task.projectId)?.scripts?.find((s) => s.id === scriptId);
+  if (!script) throw new Error('Script not found');
+  return invoke<JobInfo>(IPC.RunScript, {
+    taskId,
+    scriptId,
+    name: script.name,
+    command: script.command,
+    cwd: task.worktreePath,
+    devServer: script.devServer ?? false,
+  });
+}
+
+/** The task's running dev-server job, if any. */
+export function getTaskDevServer(taskId: string): JobInfo | undefined {
+  return getTaskJobs(taskId).find((j) => j.port !== null && j.status === 'running');
+}
+
+/** Open the task's running dev server in the browser. */
+export async function openTaskPreview(taskId: string): Promise<void> {
+  await invoke<string>(IPC.OpenPreview, { taskId });
+}
+
+export async function cancelJob(jobId: string): Promise<void> {
+  await invoke(IPC.CancelJob, { jobId });
+}
+
+export async function getJobOutput(jobId: string): Promise<string> {
+  return invoke<string>(IPC.GetJobOutput, { jobId });
+}
+
+/** Cancel a task's running jobs, free its port and drop the jobs from the store. */
+export function clearTaskJobs(taskId: string): void {
+  invoke(IPC.RemoveTaskJobs, { taskId }).catch(console.error);
+  setStore(
+    produce((s) => {
+      delete s.taskJobs[taskId];
+    }),
+  );
+}
+
+/** Mirror backend job status changes into the store, newest job first. */
+export function startJobListener(): () => void {
+  return window.electron.ipcRenderer.on(IPC.JobChanged, (data: unknown) => {
+    if (!data || typeof data !== 'object') return;
+    const job = data as JobInfo;
+    if (typeof job.id !== 'string' || typeof job.task_id !== 'string') return;
+    // Late updates for a closed task's cancelled jobs
+    if (!store.tasks[job.task_id]) return;
+    setStore(
+      produce((s) => {
+        const others = (s.taskJobs[job.task_id] ?? []).filter((j) => j.id !== job.id);
+        s.taskJobs[job.task_id] = [job, ...others].sort((a, b) => b.started_at - a.started_at);
+      }),
+    );
+  });
+}
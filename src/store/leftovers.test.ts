@@ -0,0 +1,58 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { IPC } from '../../electron/ipc/channels';
+
+const mockTasks: Record<string, unknown> = {};
+vi.mock('./core', () => ({
+  store: {
+    get tasks() {
+      return mockTasks;
+    },
+    taskOrder: ['a', 'b'],
+    collapsedTaskOrder: ['c'],
+  },
+}));
+
+vi.mock('./projects', () => ({
+  getProject: (id: string) => ({ id, path: `/repos/${id}`, worktreeDir: undefined }),
+  getProjectBranchPrefix: () => 'task',
+}));
+
+vi.mock('./notification', () => ({ showNotification: vi.fn() }));
+
+const mockInvoke = vi.fn();
+vi.mock('../lib/ipc', () => ({ invoke: (...args: unknown[]) => mockInvoke(...args) }));
+
+import { findProjectLeftovers } from './leftovers';
+
+async function knownBranchesFor(projectId: string): Promise<string[]> {
+  await findProjectLeftovers(projectId);
+  const calls = mockInvoke.mock.calls.filter(([channel]) => channel === IPC.FindTaskLeftovers);
+  return calls.at(-1)?.[1].knownBranches;
+}
+
+describe('findProjectLeftovers', () => {
+  beforeEach(() => {
+    mockInvoke.mockReset();
+    mockInvoke.mockResolvedValue([]);
+    for (const id of Object.keys(mockTasks)) delete mockTasks[id];
+    mockTasks.a = { projectId: 'main', branchName: 'task/a' };
+    mockTasks.b = {
+      projectId: 'other',
+      branchName: 'task/b',
+      linkedWorktrees: [
+        { projectId: 'main', worktreePath: '/wt/b-main', branchName: 'task/b-2' },
+        { projectId: 'third', worktreePath: '/wt/b-third', branchName: 'task/b-3' },
+      ],
+    };
+    mockTasks.c = { projectId: 'main', branchName: 'task/c' };
+  });
+
+  it('counts the branch a task linked into the project, not its own', async () => {
+    expect(await knownBranchesFor('main')).toEqual(['task/a', 'task/b-2', 'task/c']);
+  });
+
+  it("counts a task's own branch only in its own project", async () => {
+    expect(await knownBranchesFor('other')).toEqual(['task/b']);
+    expect(await knownBranchesFor('third')).toEqual(['task/b-3']);
+  });
+});
@@ -0,0 +1,78 @@
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store } from './core';
+import { getProject, getProjectBranchPrefix } from './projects';
+import { showNotification } from './notification';
+import type { ArchivedTask, TaskLeftover } from '../ipc/types';
+
+const LEFTOVER_CHECK_DELAY_MS = 60_000;
+const LEFTOVER_CHECK_INTERVAL_MS = 24 * 60 * 60_000;
+
+/** Branches of every saved task with a worktree in a project, collapsed ones included. */
+function knownTaskBranches(projectId: string): string[] {
+  return [...store.taskOrder, ...store.collapsedTaskOrder].flatMap((id) => {
+    const t = store.tasks[id];
+    if (!t) return [];
+    const linked = (t.linkedWorktrees ?? [])
+      .filter((l) => l.projectId === projectId)
+      .map((l) => l.branchName);
+    return t.projectId === projectId ? [t.branchName, ...linked] : linked;
+  });
+}
+
+/** Task branches and worktree directories in a project that no saved task owns. */
+export async function findProjectLeftovers(projectId: string): Promise<TaskLeftover[]> {
+  const project = getProject(projectId);
+  if (!project) return [];
+  // Branches kept by archived tasks are what a restore checks out again
+  const archived = await invoke<ArchivedTask[]>(IPC.ListArchivedTasks, {
+    projectRoot: project.path,
+  }).catch(() => []);
+  return invoke<TaskLeftover[]>(IPC.FindTaskLeftovers, {
+    projectRoot: project.path,
+    worktreeBaseDir: project.worktreeDir,
+    branchPrefix: getProjectBranchPrefix(projectId),
+    knownBranches: [
+      ...knownTaskBranches(projectId),
+      ...archived.filter((a) => a.branch_kept).map((a) => a.branch_name),
+    ],
+  });
+}
+
+export async function removeProjectLeftovers(
+  projectId: string,
+  leftovers: TaskLeftover[],
+): Promise<{ removed: number; errors: string[] }> {
+  const project = getProject(projectId);
+  if (!project) return { removed: 0, errors: [] };
+  return invoke(IPC.RemoveTaskLeftovers, {
+    projectRoot: project.path,
+    worktreeBaseDir: project.worktreeDir,
+    leftovers: leftovers.map((l) => ({ branch: l.branch, path: l.path })),
+  });
+}
+
+/**
+ * Look for leftovers a minute after startup and then daily, and point the
+ * user at the project settings when a project has any. Nothing is removed
+ * without the user picking it.
+ */
+export function startLeftoverCheck(): () => void {
+  const checkAll = async () => {
+    for (const project of store.projects) {
+      if (store.missingProjectIds[project.id]) continue;
+      const count = (await findProjectLeftovers(project.id).catch(() => [])).length;
+      if (count === 0) continue;
+      showNotification(
+        `${project.name}: ${count} old task leftover${count === 1 ? '' : 's'} — see Edit Project`,
+      );
+      return; // one notification at a time; the next check picks up the rest
+    }
+  };
+  const delay = setTimeout(() => void checkAll(), LEFTOVER_CHECK_DELAY_MS);
+  const timer = setInterval(() => void checkAll(), LEFTOVER_CHECK_INTERVAL_MS);
+  return () => {
+    clearTimeout(delay);
+    clearInterval(timer);
+  };
+}
@@ -0,0 +1,124 @@
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store } from './core';
+import { mergeTask } from './tasks';
+import { showNotification } from './notification';
+import type { MergeStatus, MergeStrategy, RebaseResult } from '../ipc/types';
+
+export type MergeQueueOutcome =
+  | 'pending'
+  | 'running'
+  | 'merged'
+  | 'skipped' // direct-mode or already closed task
+  | 'conflict' // conflicts with main; nothing was changed
+  | 'rebase_failed'
+  | 'checks_failed' // a blocking pre-merge check failed
+  | 'merge_failed'
+  | 'not_run'; // queued behind the task the queue stopped at
+
+export interface MergeQueueEntry {
+  task_id: string;
+  task_name: string;
+  outcome: MergeQueueOutcome;
+  conflicting_files: string[];
+  error: string | null;
+}
+
+export interface MergeQueueReport {
+  entries: MergeQueueEntry[];
+  /** Task the queue stopped at, null when every task was handled. */
+  stopped_at: string | null;
+}
+
+export interface MergeQueueOptions {
+  /** Defaults to each task's project strategy. */
+  strategy?: MergeStrategy;
+  cleanup?: boolean;
+  /** Called with a fresh copy of the entries after every step. */
+  onProgress?: (entries: MergeQueueEntry[]) => void;
+}
+
+let queueRunning = false;
+
+export function isMergeQueueRunning(): boolean {
+  return queueRunning;
+}
+
+/**
+ * Merge tasks one after another in the given order. Each task is first
+ * rebased onto main as left by the previous merge, then merged through the
+ * regular merge path (pre-merge checks included). The queue stops at the
+ * first task that conflicts or fails, leaving the remaining ones untouched.
+ */
+export async function enqueueMerge(
+  taskIds: string[],
+  options: MergeQueueOptions = {},
+): Promise<MergeQueueReport> {
+  if (queueRunning) throw new Error('A merge queue is already running');
+  queueRunning = true;
+
+  const entries: MergeQueueEntry[] = taskIds.map((id) => ({
+    task_id: id,
+    task_name: store.tasks[id]?.name ?? id,
+    outcome: 'pending',
+    conflicting_files: [],
+    error: null,
+  }));
+  const update = (i: number, patch: Partial<MergeQueueEntry>) => {
+    entries[i] = { ...entries[i], ...patch };
+    options.onProgress?.(entries.map((e) => ({ ...e })));
+  };
+
+  let stoppedAt: string | null = null;
+  try {
+    for (let i = 0; i < entries.length; i++) {
+      const task = store.tasks[entries[i].task_id];
+      if (!task || task.directMode || task.closingStatus) {
+        update(i, { outcome: 'skipped' });
+        continue;
+      }
+      update(i, { outcome: 'running' });
+
+      const worktreePath = task.worktreePath;
+      let stage: MergeQueueOutcome = 'rebase_failed';
+      try {
+        const status = await invoke<MergeStatus>(IPC.CheckMergeStatus, { worktreePath });
+        if (status.conflicting_files.length > 0) {
+          update(i, { outcome: 'conflict', conflicting_files: status.conflicting_files });
+          stoppedAt = task.id;
+          break;
+        }
+        if (status.main_ahead_count > 0) {
+          await invoke<RebaseResult>(IPC.RebaseTask, { worktreePath });
+        }
+        stage = 'merge_failed';
+        await mergeTask(task.id, { strategy: options.strategy, cleanup: options.cleanup });
+        update(i, { outcome: 'merged' });
+      } catch (err) {
+        const error = String(err);
+        const checkFailed = /Pre-merge check failed|CI is failing/.test(error);
+        if (stage === 'merge_failed' && checkFailed) {
+          stage = 'checks_failed';
+        }
+        update(i, { outcome: stage, error });
+        stoppedAt = task.id;
+        break;
+      }
+    }
+  } finally {
+    queueRunning = false;
+  }
+
+  for (let i = 0; i < entries.length; i++) {
+    if (entries[i].outcome === 'pending') update(i, { outcome: 'not_run' });
+  }
+
+  const merged = entries.filter((e) => e.outcome === 'merged').length;
+  const stopped = entries.find((e) => e.task_id === stoppedAt);
+  showNotification(
+    stopped
+      ? `Merge queue stopped at ${stopped.task_name} after ${merged} merged`
+      : `Merge queue finished: ${merged} merged`,
+  );
+  return { entries, stopped_at: stoppedAt };
+}
@@ -1,22 +1,41 @@
 import { produce } from 'solid-js/store';
-import { invoke } from '../lib/ipc';
+import { invoke, fireAndForget } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import { store, setStore } from './core';
+import { showNotification } from './notification';
 import { randomPastelColor } from './projects';
 import { markAgentSpawned } from './taskStatus';
 import { getLocalDateKey } from '../lib/date';
 import type {
   Agent,
+  AppStore,
+  AutoResponseRule,
+  PendingTask,
   Task,
   PersistedState,
   PersistedTask,
   PersistedWindowState,
   Project,
 } from './types';
-import type { AgentDef } from '../ipc/types';
+import type {
+  AgentDef,
+  LoadedAppState,
+  StateConflictChoice,
+  StateMigrationError,
+} from '../ipc/types';
 import { DEFAULT_TERMINAL_FONT, isTerminalFont } from '../lib/fonts';
 import { isLookPreset } from '../lib/look';
 import { syncTerminalCounter } from './terminals';
+import { loadAgents, migrateLegacyCustomAgents } from './agents';
+import { loadForgeTokenHosts } from './ui';
+import {
+  agentChanges,
+  commandProjectChanges,
+  commandSettingChanges,
+  withoutCommandSettings,
+  withoutProjectCommands,
+  type ImportChange,
+} from '../lib/config-import';
 
 /** Enrich an agent def with resume/skip-permissions args from fresh defaults. */
 function enrichAgentDef(agentDef: AgentDef | null | undefined, availableAgents: AgentDef[]): void {
@@ -29,6 +48,26 @@ function enrichAgentDef(agentDef: AgentDef | null | undefined, availableAgents:
   }
 }
 
+/**
+ * Last JSON saved per key ('settings', 'project:<id>', 'task:<id>'), so a
+ * save only writes what changed and removes records that are gone.
+ */
+const lastSaved = new Map<string, string>();
+
+/**
+ * Custom agents from an older state.json not yet in agents.json: invalid ones
+ * and ones the backend rejected. Saved back so they aren't lost meanwhile.
+ */
+let unmigratedCustomAgents: unknown[] = [];
+
+function saveKey(key: string, json: string | null): Promise<unknown> {
+  if (key === 'settings') return invoke(IPC.SaveSettings, { json });
+  const id = key.slice(key.indexOf(':') + 1);
+  return key.startsWith('project:')
+    ? invoke(IPC.SaveProject, { projectId: id, json })
+    : invoke(IPC.SaveTask, { taskId: id, json });
+}
+
 export async function saveState(): Promise<void> {
   const persisted: PersistedState = {
     projects: store.projects.map((p) => ({ ...p })),
@@ -50,12 +89,23 @@ export async function saveState(): Promise<void> {
     themePreset: store.themePreset,
     windowState: store.windowState ? { ...store.windowState } : undefined,
     autoTrustFolders: store.autoTrustFolders,
+    permanentlyDeleteWorktrees: store.permanentlyDeleteWorktrees || undefined,
+    recordSessions: store.recordSessions || undefined,
+    osc52Clipboard: store.osc52Clipboard || undefined,
+    idleTimeoutMinutes: store.idleTimeoutMinutes || undefined,
+    idleTimeoutAction: store.idleTimeoutAction,
     showPlans: store.showPlans,
     desktopNotificationsEnabled: store.desktopNotificationsEnabled,
+    maxRunningAgents: store.maxRunningAgents || undefined,
     inactiveColumnOpacity: store.inactiveColumnOpacity,
     editorCommand: store.editorCommand || undefined,
     dockerImage: store.dockerImage !== 'parallel-code-agent:latest' ? store.dockerImage : undefined,
-    customAgents: store.customAgents.length > 0 ? [...store.customAgents] : undefined,
+    autoResponseRules:
+      store.autoResponseRules.length > 0 ? [...store.autoResponseRules] : undefined,
+    agentExtraArgs:
+      Object.keys(store.agentExtraArgs).length > 0 ? { ...store.agentExtraArgs } : undefined,
+    pendingTasks: store.pendingTasks.length > 0 ? [...store.pendingTasks] : undefined,
+    customAgents: unmigratedCustomAgents.length > 0 ? unmigratedCustomAgents : undefined,
   };
 
   for (const taskId of store.taskOrder) {
@@ -76,11 +126,17 @@ export async function saveState(): Promise<void> {
       agentDef: firstAgent?.def ?? null,
       directMode: task.directMode,
       skipPermissions: task.skipPermissions,
+      autoRestart: task.autoRestart,
+      lowPriority: task.lowPriority,
       dockerMode: task.dockerMode,
       dockerImage: task.dockerImage,
       githubUrl: task.githubUrl,
+      pushRemote: task.pushRemote,
       savedInitialPrompt: task.savedInitialPrompt,
       planFileName: task.planFileName,
+      linkedWorktrees: task.linkedWorktrees,
+      ownsBranch: task.ownsBranch,
+      fanoutGroupId: task.fanoutGroupId,
     };
   }
 
@@ -102,11 +158,17 @@ export async function saveState(): Promise<void> {
       agentDef: firstAgent?.def ?? task.savedAgentDef ?? null,
       directMode: task.directMode,
       skipPermissions: task.skipPermissions,
+      autoRestart: task.autoRestart,
+      lowPriority: task.lowPriority,
       dockerMode: task.dockerMode,
       dockerImage: task.dockerImage,
       githubUrl: task.githubUrl,
+      pushRemote: task.pushRemote,
       savedInitialPrompt: task.savedInitialPrompt,
       planFileName: task.planFileName,
+      linkedWorktrees: task.linkedWorktrees,
+      ownsBranch: task.ownsBranch,
+      fanoutGroupId: task.fanoutGroupId,
       collapsed: true,
     };
   }
@@ -118,9 +180,22 @@ export async function saveState(): Promise<void> {
     persisted.terminals[id] = { id: terminal.id, name: terminal.name };
   }
 
-  await invoke(IPC.SaveAppState, { json: JSON.stringify(persisted) }).catch((e) =>
-    console.warn('Failed to save state:', e),
-  );
+  const { projects, tasks, ...settings } = persisted;
+  const next = new Map<string, string>([
+    ['settings', JSON.stringify({ ...settings, projectIds: projects.map((p) => p.id) })],
+    ...projects.map((p): [string, string] => [`project:${p.id}`, JSON.stringify(p)]),
+    ...Object.values(tasks).map((t): [string, string] => [`task:${t.id}`, JSON.stringify(t)]),
+  ]);
+  const saves: Promise<unknown>[] = [];
+  for (const [key, json] of next) {
+    if (lastSaved.get(key) === json) continue;
+    saves.push(saveKey(key, json).then(() => lastSaved.set(key, json)));
+  }
+  for (const key of lastSaved.keys()) {
+    if (next.has(key)) continue;
+    saves.push(saveKey(key, null).then(() => lastSaved.delete(key)));
+  }
+  await Promise.all(saves.map((s) => s.catch((e) => console.warn('Failed to save state:', e))));
 }
 
 function isStringNumberRecord(v: unknown): v is Record<string, number> {
@@ -166,7 +241,6 @@ function parsePersistedWindowState(v: unknown): PersistedWindowState | null {
 }
 
 interface LegacyPersistedState {
-  projectRoot?: string;
   projects?: Project[];
   lastProjectId?: string | null;
   lastAgentId?: string | null;
@@ -187,17 +261,254 @@ interface LegacyPersistedState {
   themePreset?: unknown;
   windowState?: unknown;
   autoTrustFolders?: unknown;
+  permanentlyDeleteWorktrees?: unknown;
+  recordSessions?: unknown;
+  osc52Clipboard?: unknown;
+  idleTimeoutMinutes?: unknown;
+  idleTimeoutAction?: unknown;
   showPlans?: unknown;
   desktopNotificationsEnabled?: unknown;
+  maxRunningAgents?: unknown;
   inactiveColumnOpacity?: unknown;
   editorCommand?: unknown;
   dockerImage?: unknown;
   customAgents?: unknown;
+  autoResponseRules?: unknown;
+  agentExtraArgs?: unknown;
+  pendingTasks?: unknown;
   terminals?: unknown;
 }
 
+/** Settings that mean the same on another machine; what a config export carries. */
+export const PORTABLE_SETTING_KEYS = [
+  'terminalFont',
+  'themePreset',
+  'autoTrustFolders',
+  'permanentlyDeleteWorktrees',
+  'recordSessions',
+  'osc52Clipboard',
+  'idleTimeoutMinutes',
+  'idleTimeoutAction',
+  'showPlans',
+  'desktopNotificationsEnabled',
+  'maxRunningAgents',
+  'inactiveColumnOpacity',
+  'editorCommand',
+  'dockerImage',
+  'autoResponseRules',
+  'agentExtraArgs',
+] as const;
+
+function parseMaxRunningAgents(v: unknown): number {
+  return typeof v === 'number' && v > 0 ? Math.round(v) : 0;
+}
+
+/** Apply the portable settings in `raw`, falling back to defaults for invalid values. */
+function applyPortableSettings(s: AppStore, raw: Partial<LegacyPersistedState>): void {
+  s.terminalFont = isTerminalFont(raw.terminalFont) ? raw.terminalFont : DEFAULT_TERMINAL_FONT;
+  s.themePreset = isLookPreset(raw.themePreset) ? raw.themePreset : 'minimal';
+  s.autoTrustFolders = typeof raw.autoTrustFolders === 'boolean' ? raw.autoTrustFolders : false;
+  s.permanentlyDeleteWorktrees = raw.permanentlyDeleteWorktrees === true;
+  s.recordSessions = raw.recordSessions === true;
+  s.osc52Clipboard = raw.osc52Clipboard === true;
+  s.idleTimeoutMinutes =
+    typeof raw.idleTimeoutMinutes === 'number' && raw.idleTimeoutMinutes > 0
+      ? Math.round(raw.idleTimeoutMinutes)
+      : 0;
+  s.idleTimeoutAction = raw.idleTimeoutAction === 'suspend' ? 'suspend' : 'kill';
+  s.showPlans = typeof raw.showPlans === 'boolean' ? raw.showPlans : true;
+  s.desktopNotificationsEnabled =
+    typeof raw.desktopNotificationsEnabled === 'boolean' ? raw.desktopNotificationsEnabled : false;
+  s.maxRunningAgents = parseMaxRunningAgents(raw.maxRunningAgents);
+  const rawOpacity = raw.inactiveColumnOpacity;
+  s.inactiveColumnOpacity =
+    typeof rawOpacity === 'number' &&
+    Number.isFinite(rawOpacity) &&
+    rawOpacity >= 0.3 &&
+    rawOpacity <= 1.0
+      ? Math.round(rawOpacity * 100) / 100
+      : 0.6;
+
+  const rawEditorCommand = raw.editorCommand;
+  s.editorCommand = typeof rawEditorCommand === 'string' ? rawEditorCommand.trim() : '';
+
+  const rawDockerImage = raw.dockerImage;
+  s.dockerImage =
+    typeof rawDockerImage === 'string' && rawDockerImage.trim()
+      ? rawDockerImage.trim()
+      : 'parallel-code-agent:latest';
+
+  if (Array.isArray(raw.autoResponseRules)) {
+    s.autoResponseRules = raw.autoResponseRules.filter(
+      (r: unknown): r is AutoResponseRule =>
+        typeof r === 'object' &&
+        r !== null &&
+        typeof (r as AutoResponseRule).id === 'string' &&
+        typeof (r as AutoResponseRule).agentDefId === 'string' &&
+        typeof (r as AutoResponseRule).pattern === 'string' &&
+        typeof (r as AutoResponseRule).response === 'string',
+    );
+  }
+
+  const rawExtraArgs = raw.agentExtraArgs;
+  if (typeof rawExtraArgs === 'object' && rawExtraArgs !== null) {
+    for (const [agentDefId, value] of Object.entries(rawExtraArgs)) {
+      if (Array.isArray(value) && value.every((v) => typeof v === 'string')) {
+        s.agentExtraArgs[agentDefId] = value;
+      }
+    }
+  }
+}
+
+function portableSettings(): Partial<LegacyPersistedState> {
+  return Object.fromEntries(PORTABLE_SETTING_KEYS.map((key) => [key, store[key]]));
+}
+
+/**
+ * Save settings, projects and custom agents to a file the user picks, to set
+ * up another machine or share a baseline. Returns the path, or null if cancelled.
+ */
+export async function exportConfig(): Promise<string | null> {
+  return invoke<string | null>(IPC.ExportState, {
+    json: JSON.stringify({ settings: portableSettings(), projects: store.projects }),
+  });
+}
+
+export interface ConfigImport {
+  settings: Record<string, unknown>;
+  /** Projects whose path isn't open yet; the others are left as they are. */
+  projects: Project[];
+  custom_agents: AgentDef[];
+  /** Agents left out because they would replace a built-in agent. */
+  refused_agents: string[];
+}
+
+/**
+ * Read an exported config the user picks, without applying anything. Returns
+ * null if they cancelled the file picker.
+ */
+export async function readConfigImport(): Promise<ConfigImport | null> {
+  const imported = await invoke<ConfigImport | null>(IPC.ImportState);
+  if (!imported) return null;
+  return {
+    ...imported,
+    projects: imported.projects.filter((p) => !store.projects.some((o) => o.path === p.path)),
+  };
+}
+
+/** The commands and command-like settings applying `imported` would bring in. */
+export function configImportChanges(imported: ConfigImport): ImportChange[] {
+  return [
+    ...commandSettingChanges(portableSettings(), imported.settings),
+    ...commandProjectChanges(imported.projects),
+    ...agentChanges(imported.custom_agents, store.customAgents),
+  ];
+}
+
+/**
+ * Take settings and new projects from a config read by readConfigImport, and
+ * its custom agents. Unless `includeCommands` (the user accepted what
+ * configImportChanges listed), command-bearing settings keep their current
+ * values, projects are added without their commands and no agents are taken.
+ */
+export async function applyConfigImport(
+  imported: ConfigImport,
+  includeCommands: boolean,
+): Promise<{ projectsAdded: number; agentsImported: number }> {
+  const settings = includeCommands
+    ? imported.settings
+    : withoutCommandSettings(imported.settings);
+  let projectsAdded = 0;
+  setStore(
+    produce((s) => {
+      applyPortableSettings(s, { ...portableSettings(), ...settings });
+      for (const imp of imported.projects) {
+        if (s.projects.some((p) => p.path === imp.path)) continue;
+        const project = includeCommands ? imp : withoutProjectCommands(imp);
+        // Project ids name files in the state directory, so never take them from the import
+        const id = crypto.randomUUID();
+        s.projects.push({ ...project, id, color: project.color || randomPastelColor() });
+        projectsAdded++;
+      }
+    }),
+  );
+  fireAndForget(IPC.SetAgentConcurrencyLimit, { limit: store.maxRunningAgents });
+  let agentsImported = 0;
+  if (includeCommands && imported.custom_agents.length > 0) {
+    agentsImported = await invoke<number>(IPC.ImportAgents, { agents: imported.custom_agents });
+    await loadAgents();
+  }
+  return { projectsAdded, agentsImported };
+}
+
+function reportMigrationError(error: StateMigrationError): void {
+  const steps = error.failed.map((f) => `${f.description || `v${f.from}`}: ${f.error}`);
+  console.warn('Saved state was not fully migrated:', error);
+  showNotification(
+    `Saved state (v${error.from_version}) could not be fully loaded. ${steps.join('; ')}` +
+      (error.backup_path ? `. The original was kept in ${error.backup_path}` : ''),
+  );
+}
+
+/**
+ * Tell the user when saving pauses or resumes, and ask them whose copy to
+ * keep when another instance changed a state file this one meant to save.
+ */
+export function startStateEventListener(): () => void {
+  const offConflict = window.electron.ipcRenderer.on(IPC.StateSaveConflict, (data: unknown) => {
+    const paths = (data as { paths?: unknown } | null)?.paths;
+    if (Array.isArray(paths)) setStore('stateConflictPaths', paths.map(String));
+  });
+  const offReadOnly = window.electron.ipcRenderer.on(
+    IPC.StateReadOnlyChanged,
+    (data: unknown) => {
+      const reason = (data as { reason?: unknown } | null)?.reason;
+      showNotification(
+        typeof reason === 'string'
+          ? `Changes are not being saved: ${reason}`
+          : 'Saving resumed; changes made meanwhile were saved',
+      );
+    },
+  );
+  return () => {
+    offConflict();
+    offReadOnly();
+  };
+}
+
+/** Settle the files listed in `stateConflictPaths`; `reload` reloads the window. */
+export async function resolveStateConflicts(choice: StateConflictChoice): Promise<void> {
+  await invoke(IPC.ResolveStateConflict, { choice });
+  setStore('stateConflictPaths', []);
+  if (choice === 'reload') window.location.reload();
+}
+
+/** Move legacy custom agents to agents.json, keeping whatever didn't make it. */
+async function finishCustomAgentMigration(valid: AgentDef[]): Promise<void> {
+  let failed: AgentDef[] = valid;
+  try {
+    failed = await migrateLegacyCustomAgents(valid);
+  } catch (err) {
+    console.warn('Failed to migrate custom agents:', err);
+  }
+  const migrated = new Set<unknown>(valid.filter((a) => !failed.includes(a)));
+  unmigratedCustomAgents = unmigratedCustomAgents.filter((a) => !migrated.has(a));
+  if (unmigratedCustomAgents.length > 0) {
+    showNotification(
+      `${unmigratedCustomAgents.length} custom agent(s) from an older version could not be ` +
+        'moved to agents.json; they are kept in the saved state',
+    );
+  }
+}
+
 export async function loadState(): Promise<void> {
-  const json = await invoke<string | null>(IPC.LoadAppState).catch(() => null);
+  void loadForgeTokenHosts();
+  const loaded = await invoke<LoadedAppState>(IPC.LoadAppState).catch(() => null);
+  if (loaded?.migration_error) reportMigrationError(loaded.migration_error);
+  if (loaded?.read_only_reason) {
+    showNotification(`Changes are not being saved: ${loaded.read_only_reason}`);
+  }
+  const json = loaded?.json;
   if (!json) return;
 
   let raw: LegacyPersistedState;
@@ -219,9 +530,12 @@ export async function loadState(): Promise<void> {
     return;
   }
 
-  // Migrate from old format if needed
-  let projects: Project[] = raw.projects ?? [];
-  let lastProjectId: string | null = raw.lastProjectId ?? null;
+  // Records on disk that the next save doesn't write again get removed
+  for (const p of raw.projects ?? []) lastSaved.set(`project:${p.id}`, '');
+  for (const id of Object.keys(raw.tasks)) lastSaved.set(`task:${id}`, '');
+
+  const projects: Project[] = raw.projects ?? [];
+  const lastProjectId: string | null = raw.lastProjectId ?? null;
   const lastAgentId: string | null = raw.lastAgentId ?? null;
 
   // Assign colors to projects that don't have one (backward compat)
@@ -229,23 +543,13 @@ export async function loadState(): Promise<void> {
     if (!p.color) p.color = randomPastelColor();
   }
 
-  if (projects.length === 0 && raw.projectRoot) {
-    const segments = raw.projectRoot.split('/');
-    const name = segments[segments.length - 1] || raw.projectRoot;
-    const id = crypto.randomUUID();
-    projects = [{ id, name, path: raw.projectRoot, color: randomPastelColor() }];
-    lastProjectId = id;
-
-    // Assign this project to all existing tasks
-    for (const taskId of raw.taskOrder) {
-      const pt = raw.tasks[taskId];
-      if (pt && !pt.projectId) {
-        pt.projectId = id;
-      }
-    }
-  }
+  // Tell the backend before tasks render: restored agents spawn as their terminals mount
+  fireAndForget(IPC.SetAgentConcurrencyLimit, {
+    limit: parseMaxRunningAgents(raw.maxRunningAgents),
+  });
 
   const restoredRunningAgentIds: string[] = [];
+  let legacyCustomAgents: AgentDef[] = [];
   const today = getLocalDateKey();
 
   setStore(
@@ -283,36 +587,14 @@ export async function loadState(): Promise<void> {
         typeof mergedLinesRemovedRaw === 'number' && Number.isFinite(mergedLinesRemovedRaw)
           ? Math.max(0, Math.floor(mergedLinesRemovedRaw))
           : 0;
-      s.terminalFont = isTerminalFont(raw.terminalFont) ? raw.terminalFont : DEFAULT_TERMINAL_FONT;
-      s.themePreset = isLookPreset(raw.themePreset) ? raw.themePreset : 'minimal';
       s.windowState = parsePersistedWindowState(raw.windowState);
-      s.autoTrustFolders = typeof raw.autoTrustFolders === 'boolean' ? raw.autoTrustFolders : false;
-      s.showPlans = typeof raw.showPlans === 'boolean' ? raw.showPlans : true;
-      s.desktopNotificationsEnabled =
-        typeof raw.desktopNotificationsEnabled === 'boolean'
-          ? raw.desktopNotificationsEnabled
-          : false;
-      const rawOpacity = raw.inactiveColumnOpacity;
-      s.inactiveColumnOpacity =
-        typeof rawOpacity === 'number' &&
-        Number.isFinite(rawOpacity) &&
-        rawOpacity >= 0.3 &&
-        rawOpacity <= 1.0
-          ? Math.round(rawOpacity * 100) / 100
-          : 0.6;
-
-      const rawEditorCommand = raw.editorCommand;
-      s.editorCommand = typeof rawEditorCommand === 'string' ? rawEditorCommand.trim() : '';
-
-      const rawDockerImage = raw.dockerImage;
-      s.dockerImage =
-        typeof rawDockerImage === 'string' && rawDockerImage.trim()
-          ? rawDockerImage.trim()
-          : 'parallel-code-agent:latest';
-
-      // Restore custom agents
+      applyPortableSettings(s, raw);
+
+      // Custom agents used to live in state.json; they are now owned by the
+      // backend. Keep legacy entries usable for this load and migrate them.
       if (Array.isArray(raw.customAgents)) {
-        s.customAgents = raw.customAgents.filter(
+        unmigratedCustomAgents = raw.customAgents;
+        legacyCustomAgents = raw.customAgents.filter(
           (a: unknown): a is AgentDef =>
             typeof a === 'object' &&
             a !== null &&
@@ -322,8 +604,21 @@ export async function loadState(): Promise<void> {
         );
       }
 
+      if (Array.isArray(raw.pendingTasks)) {
+        s.pendingTasks = raw.pendingTasks.filter(
+          (p: unknown): p is PendingTask =>
+            typeof p === 'object' &&
+            p !== null &&
+            typeof (p as PendingTask).id === 'string' &&
+            typeof (p as PendingTask).projectId === 'string' &&
+            (typeof (p as PendingTask).afterTaskId === 'string' ||
+              typeof (p as PendingTask).startAt === 'number') &&
+            typeof (p as PendingTask).agentDef === 'object',
+        );
+      }
+
       // Make custom agents findable during task restoration
-      for (const ca of s.customAgents) {
+      for (const ca of legacyCustomAgents) {
         if (!s.availableAgents.some((a) => a.id === ca.id)) {
           s.availableAgents.push(ca);
         }
@@ -355,11 +650,17 @@ export async function loadState(): Promise<void> {
           lastPrompt: pt.lastPrompt,
           directMode: pt.directMode,
           skipPermissions: pt.skipPermissions === true,
+          autoRestart: pt.autoRestart === true ? true : undefined,
+          lowPriority: pt.lowPriority === true ? true : undefined,
           dockerMode: pt.dockerMode === true ? true : undefined,
           dockerImage: typeof pt.dockerImage === 'string' ? pt.dockerImage : undefined,
           githubUrl: pt.githubUrl,
+          pushRemote: typeof pt.pushRemote === 'string' ? pt.pushRemote : undefined,
           savedInitialPrompt: pt.savedInitialPrompt,
           planFileName: pt.planFileName,
+          linkedWorktrees: Array.isArray(pt.linkedWorktrees) ? pt.linkedWorktrees : undefined,
+          ownsBranch: pt.ownsBranch === false ? false : undefined,
+          fanoutGroupId: typeof pt.fanoutGroupId === 'string' ? pt.fanoutGroupId : undefined,
         };
 
         s.tasks[taskId] = task;
@@ -414,11 +715,17 @@ export async function loadState(): Promise<void> {
           lastPrompt: pt.lastPrompt,
           directMode: pt.directMode,
           skipPermissions: pt.skipPermissions === true,
+          autoRestart: pt.autoRestart === true ? true : undefined,
+          lowPriority: pt.lowPriority === true ? true : undefined,
           dockerMode: pt.dockerMode === true ? true : undefined,
           dockerImage: typeof pt.dockerImage === 'string' ? pt.dockerImage : undefined,
           githubUrl: pt.githubUrl,
+          pushRemote: typeof pt.pushRemote === 'string' ? pt.pushRemote : undefined,
           savedInitialPrompt: pt.savedInitialPrompt,
           planFileName: pt.planFileName,
+          linkedWorktrees: Array.isArray(pt.linkedWorktrees) ? pt.linkedWorktrees : undefined,
+          ownsBranch: pt.ownsBranch === false ? false : undefined,
+          fanoutGroupId: typeof pt.fanoutGroupId === 'string' ? pt.fanoutGroupId : undefined,
           collapsed: true,
           savedAgentDef: agentDef ?? undefined,
         };
@@ -444,4 +751,6 @@ export async function loadState(): Promise<void> {
   }
 
   syncTerminalCounter();
+
+  if (unmigratedCustomAgents.length > 0) void finishCustomAgentMigration(legacyCustomAgents);
 }
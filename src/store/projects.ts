@@ -5,6 +5,7 @@ import { IPC } from '../../electron/ipc/channels';
 import { store, setStore } from './core';
 import { closeTask } from './tasks';
 import type { Project } from './types';
+import type { CommitOptions, DiscoveredBranch } from '../ipc/types';
 import { sanitizeBranchPrefix } from '../lib/branch-name';
 
 export const PASTEL_HUES = [0, 30, 60, 120, 180, 210, 260, 300, 330];
@@ -64,6 +65,22 @@ export function updateProject(
       | 'deleteBranchOnClose'
       | 'defaultDirectMode'
       | 'terminalBookmarks'
+      | 'postMergeCommands'
+      | 'preMergeCommands'
+      | 'preMergeGate'
+      | 'blockMergeOnRedCi'
+      | 'commitSigning'
+      | 'skipCommitHooks'
+      | 'mergeStrategy'
+      | 'mergeMessageTemplate'
+      | 'linkRules'
+      | 'autoInstallDeps'
+      | 'setupCommands'
+      | 'scripts'
+      | 'verifyCommand'
+      | 'generatedFiles'
+      | 'autoFetchMinutes'
+      | 'worktreeDir'
     >
   >,
 ): void {
@@ -81,6 +98,34 @@ export function updateProject(
         s.projects[idx].defaultDirectMode = updates.defaultDirectMode;
       if (updates.terminalBookmarks !== undefined)
         s.projects[idx].terminalBookmarks = updates.terminalBookmarks;
+      if (updates.postMergeCommands !== undefined)
+        s.projects[idx].postMergeCommands = updates.postMergeCommands;
+      if (updates.preMergeCommands !== undefined)
+        s.projects[idx].preMergeCommands = updates.preMergeCommands;
+      if (updates.preMergeGate !== undefined) s.projects[idx].preMergeGate = updates.preMergeGate;
+      if (updates.blockMergeOnRedCi !== undefined)
+        s.projects[idx].blockMergeOnRedCi = updates.blockMergeOnRedCi;
+      if ('commitSigning' in updates) s.projects[idx].commitSigning = updates.commitSigning;
+      if (updates.skipCommitHooks !== undefined)
+        s.projects[idx].skipCommitHooks = updates.skipCommitHooks;
+      if (updates.mergeStrategy !== undefined)
+        s.projects[idx].mergeStrategy = updates.mergeStrategy;
+      if (updates.mergeMessageTemplate !== undefined)
+        s.projects[idx].mergeMessageTemplate = updates.mergeMessageTemplate || undefined;
+      if (updates.linkRules !== undefined) s.projects[idx].linkRules = updates.linkRules;
+      if (updates.autoInstallDeps !== undefined)
+        s.projects[idx].autoInstallDeps = updates.autoInstallDeps;
+      if (updates.setupCommands !== undefined)
+        s.projects[idx].setupCommands = updates.setupCommands;
+      if (updates.scripts !== undefined) s.projects[idx].scripts = updates.scripts;
+      if (updates.verifyCommand !== undefined)
+        s.projects[idx].verifyCommand = updates.verifyCommand || undefined;
+      if (updates.generatedFiles !== undefined)
+        s.projects[idx].generatedFiles = updates.generatedFiles;
+      if (updates.autoFetchMinutes !== undefined)
+        s.projects[idx].autoFetchMinutes = updates.autoFetchMinutes || undefined;
+      if (updates.worktreeDir !== undefined)
+        s.projects[idx].worktreeDir = updates.worktreeDir || undefined;
     }),
   );
 }
@@ -90,6 +135,16 @@ export function getProjectBranchPrefix(projectId: string): string {
   return sanitizeBranchPrefix(raw);
 }
 
+/** Signing and hook options for the app's commits; unset ones follow the repo's git config. */
+export function getProjectCommitOptions(projectId: string): CommitOptions {
+  const project = store.projects.find((p) => p.id === projectId);
+  const signing = project?.commitSigning;
+  return {
+    sign: signing === undefined ? undefined : signing === 'sign',
+    skipHooks: project?.skipCommitHooks || undefined,
+  };
+}
+
 export function getProjectPath(projectId: string): string | undefined {
   return store.projects.find((p) => p.id === projectId)?.path;
 }
@@ -105,10 +160,10 @@ export async function removeProjectWithTasks(projectId: string): Promise<void> {
   // Must happen before removeProject() since closeTask needs the project path.
   for (const tid of taskIds) {
     // closeTask handles and stores its own errors, so this should not throw.
-    await closeTask(tid);
+    await closeTask(tid, true);
   }
   for (const tid of collapsedTaskIds) {
-    await closeTask(tid);
+    await closeTask(tid, true);
   }
 
   // If any tasks failed to close, keep the project so users can retry.
@@ -140,7 +195,33 @@ export async function pickAndAddProject(): Promise<string | null> {
 
   const segments = path.split('/');
   const name = segments[segments.length - 1] || path;
-  return addProject(name, path);
+  const projectId = addProject(name, path);
+  void offerWorktreeAdoption(projectId);
+  return projectId;
+}
+
+/** Branches of a project that exist in git but don't belong to any task yet. */
+export async function findAdoptableBranches(projectId: string): Promise<DiscoveredBranch[]> {
+  const projectRoot = getProject(projectId)?.path;
+  if (!projectRoot) return [];
+  const found = await invoke<DiscoveredBranch[]>(IPC.DiscoverWorktrees, { projectRoot });
+  const taken = new Set(
+    [...store.taskOrder, ...store.collapsedTaskOrder]
+      .map((id) => store.tasks[id])
+      .filter((t) => t?.projectId === projectId)
+      .map((t) => t.branchName),
+  );
+  return found.filter((b) => !taken.has(b.branch));
+}
+
+export function showAdoptWorktrees(projectId: string | null): void {
+  setStore('adoptWorktreesProjectId', projectId);
+}
+
+/** Open the adoption dialog if the project has worktrees created outside the app. */
+async function offerWorktreeAdoption(projectId: string): Promise<void> {
+  const found = await findAdoptableBranches(projectId).catch(() => []);
+  if (found.some((b) => b.worktree_path)) showAdoptWorktrees(projectId);
 }
 
 /** Check each project path and record which ones are missing. */
@@ -0,0 +1,58 @@
+import { produce } from 'solid-js/store';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
+import { store, setStore } from './core';
+import type { QueuedPrompt } from '../ipc/types';
+
+function setQueue(agentId: string, prompts: QueuedPrompt[]): void {
+  setStore(
+    produce((s) => {
+      if (prompts.length === 0) delete s.promptQueues[agentId];
+      else s.promptQueues[agentId] = prompts;
+    }),
+  );
+}
+
+export function getQueuedPrompts(agentId: string): QueuedPrompt[] {
+  return store.promptQueues[agentId] ?? [];
+}
+
+/** Queue a follow-up prompt; the backend writes it once the agent goes idle. */
+export async function queuePrompt(agentId: string, text: string): Promise<void> {
+  const prompts = await invoke<QueuedPrompt[]>(IPC.QueuePrompt, { agentId, text });
+  setQueue(agentId, prompts);
+}
+
+export async function removeQueuedPrompt(agentId: string, promptId: string): Promise<void> {
+  const prompts = await invoke<QueuedPrompt[]>(IPC.RemoveQueuedPrompt, { agentId, promptId });
+  setQueue(agentId, prompts);
+}
+
+export async function reorderQueuedPrompt(
+  agentId: string,
+  fromIndex: number,
+  toIndex: number,
+): Promise<void> {
+  if (fromIndex === toIndex) return;
+  const prompts = await invoke<QueuedPrompt[]>(IPC.ReorderQueuedPrompt, {
+    agentId,
+    fromIndex,
+    toIndex,
+  });
+  setQueue(agentId, prompts);
+}
+
+/** Send the next queued prompt right away instead of waiting for idle. */
+export async function sendNextQueuedPrompt(agentId: string): Promise<void> {
+  await invoke<boolean>(IPC.SendNextQueuedPrompt, { agentId });
+}
+
+/** Mirror backend queue changes (deliveries, cancellations) into the store. */
+export function startPromptQueueListener(): () => void {
+  return window.electron.ipcRenderer.on(IPC.PromptQueueChanged, (data: unknown) => {
+    if (!data || typeof data !== 'object') return;
+    const msg = data as { agentId: string; prompts: QueuedPrompt[] };
+    if (typeof msg.agentId !== 'string' || !Array.isArray(msg.prompts)) return;
+    setQueue(msg.agentId, msg.prompts);
+  });
+}
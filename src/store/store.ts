@@ -7,10 +7,13 @@ export {
   removeProjectWithTasks,
   updateProject,
   getProjectPath,
+  getProjectCommitOptions,
   getProjectBranchPrefix,
   pickAndAddProject,
   validateProjectPaths,
   relinkProject,
+  findAdoptableBranches,
+  showAdoptWorktrees,
   isProjectMissing,
   PASTEL_HUES,
 } from './projects';
@@ -19,18 +22,42 @@ export {
   addAgentToTask,
   markAgentExited,
   restartAgent,
+  signalAgent,
+  exportSessionRecording,
+  setAgentTitle,
+  markAgentIdleTimeout,
+  setTaskLowPriority,
   switchAgent,
   addCustomAgent,
   removeCustomAgent,
   updateCustomAgent,
+  addAutoResponseRule,
+  removeAutoResponseRule,
+  autoResponseRulesFor,
+  setAgentExtraArgs,
 } from './agents';
 export {
   createTask,
+  createTaskBatch,
+  createFanoutTasks,
+  duplicateTask,
+  LINKED_DUPLICATE_ERROR,
+  archiveTask,
+  listArchivedTasks,
+  restoreArchivedTask,
+  getLinkedBranches,
+  willDeleteTaskBranch,
+  linkedWorktreesEnv,
   createDirectTask,
   closeTask,
   retryCloseTask,
   mergeTask,
+  defaultMergeMessage,
+  generateCommitMessage,
   pushTask,
+  createPullRequest,
+  exportTask,
+  transferChanges,
   updateTaskName,
   updateTaskNotes,
   sendPrompt,
@@ -42,6 +69,7 @@ export {
   spawnShellForTask,
   runBookmarkInTask,
   closeShell,
+  markShellExited,
   hasDirectModeTask,
   collapseTask,
   uncollapseTask,
@@ -50,6 +78,7 @@ export {
   setNewTaskPrefillPrompt,
   setPlanContent,
 } from './tasks';
+export { resolveConflictsWithAgent } from './conflictHandoff';
 export {
   setActiveTask,
   setActiveAgent,
@@ -80,7 +109,16 @@ export {
   setSidebarFocusedProjectId,
 } from './focus';
 export type { PanelId, PendingAction } from './types';
-export { saveState, loadState } from './persistence';
+export {
+  saveState,
+  loadState,
+  exportConfig,
+  readConfigImport,
+  configImportChanges,
+  applyConfigImport,
+  startStateEventListener,
+  resolveStateConflicts,
+} from './persistence';
 export {
   getFontScale,
   adjustFontScale,
@@ -95,10 +133,15 @@ export {
   setTerminalFont,
   setThemePreset,
   setAutoTrustFolders,
+  setPermanentlyDeleteWorktrees,
+  setRecordSessions,
+  setOsc52Clipboard,
+  setIdleTimeout,
   setShowPlans,
   setDesktopNotificationsEnabled,
   setInactiveColumnOpacity,
   setEditorCommand,
+  setForgeToken,
   setDockerImage,
   setDockerAvailable,
   setWindowState,
@@ -106,6 +149,9 @@ export {
 export {
   getTaskDotStatus,
   markAgentOutput,
+  markAgentIdleFromBackend,
+  markAgentAwaitingInput,
+  markAgentBell,
   clearAgentActivity,
   getAgentOutputTail,
   stripAnsi,
@@ -121,6 +167,7 @@ export {
   rescheduleTaskStatusPolling,
 } from './taskStatus';
 export type { TaskDotStatus } from './taskStatus';
+export type { ConfigImport } from './persistence';
 export { showNotification, clearNotification } from './notification';
 export { getCompletedTasksTodayCount, getMergedLineTotals } from './completion';
 export {
@@ -129,4 +176,30 @@ export {
   updateTerminalName,
   syncTerminalCounter,
 } from './terminals';
+export {
+  getQueuedPrompts,
+  queuePrompt,
+  removeQueuedPrompt,
+  reorderQueuedPrompt,
+  sendNextQueuedPrompt,
+} from './promptQueue';
+export { getAgentQueuePosition, setMaxRunningAgents, startQueuedAgent } from './agentQueue';
+export {
+  getTaskJobs,
+  getTaskDevServer,
+  runTaskScript,
+  cancelJob,
+  getJobOutput,
+  openTaskPreview,
+} from './jobs';
+export { enqueueMerge, isMergeQueueRunning } from './mergeQueue';
 export { startRemoteAccess, stopRemoteAccess, refreshRemoteStatus } from './remote';
+export { getTasksBehindOriginMain } from './autoFetch';
+export { getTaskCi, refreshTaskCi } from './ci';
+export { findProjectLeftovers, removeProjectLeftovers } from './leftovers';
+export {
+  addPendingTask,
+  cancelPendingTask,
+  getPendingTasks,
+  startPendingTask,
+} from './dependencies';
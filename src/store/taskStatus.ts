@@ -1,8 +1,9 @@
 import { createSignal } from 'solid-js';
-import { invoke } from '../lib/ipc';
+import { invoke, fireAndForget } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import { store, setStore } from './core';
-import type { WorktreeStatus } from '../ipc/types';
+import { showNotification } from './notification';
+import type { WorktreeStatus, WorktreeStatusSummary } from '../ipc/types';
 
 // --- Trust-specific patterns (subset of QUESTION_PATTERNS) ---
 // These are auto-accepted when autoTrustFolders is enabled.
@@ -544,6 +545,47 @@ export function isAgentIdle(agentId: string): boolean {
   return !activeAgents().has(agentId);
 }
 
+/** Backend reported the agent has been silent for its idle period. Authoritative
+ *  over the renderer's own timer, which can lag when the window is throttled. */
+export function markAgentIdleFromBackend(agentId: string): void {
+  const state = agentStates.get(agentId);
+  if (state?.idleTimer !== undefined) {
+    clearTimeout(state.idleTimer);
+    state.idleTimer = undefined;
+  }
+  removeFromActive(agentId);
+}
+
+/** Backend matched a confirmation prompt in the agent's output. Flags the
+ *  question immediately, even for background tasks the renderer doesn't analyze. */
+export function markAgentAwaitingInput(agentId: string, taskId: string, prompt: string): void {
+  const state = agentStates.get(agentId);
+  if (state?.idleTimer !== undefined) {
+    clearTimeout(state.idleTimer);
+    state.idleTimer = undefined;
+  }
+  removeFromActive(agentId);
+  updateQuestionState(agentId, true);
+  if (taskId !== store.activeTaskId) {
+    const name = store.tasks[taskId]?.name ?? 'An agent';
+    showNotification(`${name} is waiting for input: ${prompt}`);
+  }
+}
+
+/** Alert the user that an agent rang the terminal bell, unless they're looking at its task. */
+export function markAgentBell(taskId: string): void {
+  if (taskId === store.activeTaskId && document.hasFocus()) return;
+  const name = store.tasks[taskId]?.name ?? 'An agent';
+  showNotification(`${name} rang the bell`);
+  if (store.desktopNotificationsEnabled && !document.hasFocus()) {
+    fireAndForget(IPC.ShowNotification, {
+      title: 'Task Bell',
+      body: `${name} rang the bell`,
+      taskIds: [taskId],
+    });
+  }
+}
+
 /** Lightweight busy marker — adds to active set + resets idle timer.
  *  Unlike markAgentSpawned this preserves the output tail buffer. */
 export function markAgentBusy(agentId: string): void {
@@ -602,7 +644,7 @@ let isRefreshingAll = false;
 let refreshAllStartedAt = 0;
 
 /** Refresh git status for inactive tasks (active task is handled by its own 5s timer).
- *  Limits concurrency to avoid spawning too many parallel git processes. */
+ *  Issues one batched call per project; the backend limits concurrent git processes. */
 export async function refreshAllTaskGitStatus(): Promise<void> {
   if (isRefreshingAll && Date.now() - refreshAllStartedAt < 60_000) return;
   isRefreshingAll = true;
@@ -622,12 +664,26 @@ export async function refreshAllTaskGitStatus(): Promise<void> {
       });
     });
 
-    // Process in batches of 4 to limit concurrent git processes
-    const BATCH_SIZE = 4;
-    for (let i = 0; i < toRefresh.length; i += BATCH_SIZE) {
-      const batch = toRefresh.slice(i, i + BATCH_SIZE);
-      await Promise.allSettled(batch.map((taskId) => refreshTaskGitStatus(taskId)));
+    const byProject = new Map<string, string[]>();
+    for (const taskId of toRefresh) {
+      const projectId = store.tasks[taskId].projectId;
+      byProject.set(projectId, [...(byProject.get(projectId) ?? []), taskId]);
     }
+    await Promise.allSettled(
+      [...byProject].map(async ([projectId, taskIds]) => {
+        const projectRoot = store.projects.find((p) => p.id === projectId)?.path;
+        if (!projectRoot) return;
+        const statuses = await invoke<Record<string, WorktreeStatusSummary | null>>(
+          IPC.GetAllWorktreeStatuses,
+          { projectRoot, worktreePaths: taskIds.map((id) => store.tasks[id].worktreePath) },
+        );
+        for (const taskId of taskIds) {
+          const status = statuses[store.tasks[taskId]?.worktreePath ?? ''];
+          // Missing means the worktree doesn't exist yet or was removed
+          if (status) setStore('taskGitStatus', taskId, status);
+        }
+      }),
+    );
   } finally {
     isRefreshingAll = false;
   }
@@ -3,8 +3,15 @@ import { invoke, Channel } from '../lib/ipc';
 import { IPC } from '../../electron/ipc/channels';
 import { store, setStore, cleanupPanelEntries } from './core';
 import { setTaskFocusedPanel } from './focus';
-import { getProject, getProjectPath, getProjectBranchPrefix, isProjectMissing } from './projects';
+import {
+  getProject,
+  getProjectPath,
+  getProjectBranchPrefix,
+  getProjectCommitOptions,
+  isProjectMissing,
+} from './projects';
 import { setPendingShellCommand } from '../lib/bookmarks';
+import { failedSetupStep, setupScript } from '../lib/setup-script';
 import {
   markAgentSpawned,
   markAgentBusy,
@@ -13,8 +20,33 @@ import {
   rescheduleTaskStatusPolling,
 } from './taskStatus';
 import { recordMergedLines, recordTaskCompleted } from './completion';
-import type { AgentDef, CreateTaskResult, MergeResult } from '../ipc/types';
+import { showNotification } from './notification';
+import { clearTaskJobs } from './jobs';
+import { refreshTaskCi } from './ci';
+import { blockDependents, releaseDependents } from './dependencies';
+import type {
+  AgentDef,
+  ArchivedTask,
+  ArchivedTaskSettings,
+  BatchTaskResult,
+  CreateTaskResult,
+  DeleteTaskResult,
+  DuplicateTaskResult,
+  MergeResult,
+  MergeStatus,
+  MergeStrategy,
+  PatchApplyResult,
+  PullRequestResult,
+  RestoreArchivedResult,
+  SetupCommand,
+} from '../ipc/types';
 import { parseGitHubUrl, taskNameFromGitHubUrl } from '../lib/github-url';
+import { partitionLinkPaths } from '../lib/link-rules';
+import {
+  DEFAULT_MERGE_MESSAGE_TEMPLATE,
+  renderMergeMessage,
+  squashMessageFromLog,
+} from '../lib/merge-message';
 import type { Agent, Task } from './types';
 
 function initTaskInStore(
@@ -50,13 +82,17 @@ function isAgentNotFoundError(err: unknown): boolean {
   return String(err).toLowerCase().includes('agent not found');
 }
 
-async function writeToAgentWhenReady(agentId: string, data: string): Promise<void> {
+async function writeToAgentWhenReady(
+  agentId: string,
+  data: string,
+  channel: IPC.WriteToAgent | IPC.PasteToAgent = IPC.WriteToAgent,
+): Promise<void> {
   const deadline = Date.now() + AGENT_WRITE_READY_TIMEOUT_MS;
   let lastErr: unknown;
 
   while (Date.now() <= deadline) {
     try {
-      await invoke(IPC.WriteToAgent, { agentId, data });
+      await invoke(channel, { agentId, data });
       return;
     } catch (err) {
       lastErr = err;
@@ -75,37 +111,152 @@ export interface CreateTaskOptions {
   agentDef: AgentDef;
   projectId: string;
   symlinkDirs?: string[];
+  copyPaths?: string[];
+  clonePaths?: string[];
+  hardlinkPaths?: string[];
   initialPrompt?: string;
   branchPrefixOverride?: string;
   githubUrl?: string;
   skipPermissions?: boolean;
+  autoRestart?: boolean;
+  lowPriority?: boolean;
   dockerMode?: boolean;
   dockerImage?: string;
+  /** Move the project root's uncommitted changes into the new worktree. */
+  carryRootChanges?: boolean;
+  /** Check out this existing branch (local, `origin/x`, or a fetchable ref) instead of a new one. */
+  fromBranch?: string;
+  /** Other projects to create a worktree in too, on the same branch, for cross-repo work. */
+  linkedProjectIds?: string[];
+}
+
+/**
+ * Whether closing the task deletes its branch: the project setting, but never
+ * for a branch that existed before the task.
+ */
+export function willDeleteTaskBranch(task: Task): boolean {
+  return task.ownsBranch !== false && (getProject(task.projectId)?.deleteBranchOnClose ?? true);
+}
+
+/** Project roots of a multi-repo task's other worktrees, with the branch each one is on. */
+export function getLinkedBranches(task: Task): { projectRoot: string; branchName: string }[] {
+  return (task.linkedWorktrees ?? []).flatMap((l) => {
+    const projectRoot = getProjectPath(l.projectId);
+    return projectRoot ? [{ projectRoot, branchName: l.branchName }] : [];
+  });
+}
+
+/** Env for a multi-repo task's agents and shells: its other worktrees, one per line. */
+export function linkedWorktreesEnv(task: Task): Record<string, string> {
+  const paths = (task.linkedWorktrees ?? []).map((l) => l.worktreePath);
+  return paths.length > 0 ? { PARALLEL_CODE_LINKED_WORKTREES: paths.join('\n') } : {};
+}
+
+/** Where a linked project's worktree comes from: its root, worktree folder and link rules. */
+function linkedRepoSpec(projectId: string) {
+  const project = getProject(projectId);
+  if (!project) throw new Error('Linked project not found');
+  if (isProjectMissing(projectId)) throw new Error(`Project folder not found: ${project.path}`);
+  const rules = project.linkRules ?? [];
+  return {
+    projectRoot: project.path,
+    ...partitionLinkPaths(
+      rules.filter((r) => r.mode !== 'skip').map((r) => r.path),
+      rules,
+    ),
+    worktreeBaseDir: project.worktreeDir,
+  };
+}
+
+/** Tell the agent which other repositories it's working across. */
+function withLinkedWorktreesNote(prompt: string, linked: { name: string; path: string }[]): string {
+  if (linked.length === 0) return prompt;
+  const lines = linked.map((l) => `- ${l.name}: ${l.path}`);
+  return [
+    'This task spans several repositories. Besides the current directory, work in:',
+    ...lines,
+    'All of them are on the same branch; commit in each repository you change.',
+    '',
+    prompt,
+  ].join('\n');
+}
+
+/** The open task whose worktree in a project is on `branch`, if any. */
+function taskOnBranch(projectId: string, branch: string): Task | undefined {
+  return Object.values(store.tasks).find(
+    (t) =>
+      (t.projectId === projectId && t.branchName === branch) ||
+      t.linkedWorktrees?.some((l) => l.projectId === projectId && l.branchName === branch),
+  );
 }
 
 export async function createTask(opts: CreateTaskOptions): Promise<string> {
   const {
     name,
-    agentDef,
     projectId,
     symlinkDirs = [],
-    initialPrompt,
-    githubUrl,
-    skipPermissions,
-    dockerMode,
-    dockerImage,
+    copyPaths = [],
+    clonePaths = [],
+    hardlinkPaths = [],
   } = opts;
   const projectRoot = getProjectPath(projectId);
   if (!projectRoot) throw new Error('Project not found');
   if (isProjectMissing(projectId)) throw new Error('Project folder not found');
+  if (opts.fromBranch) {
+    const owner = taskOnBranch(projectId, opts.fromBranch.replace(/^refs\/(heads\/)?/, ''));
+    if (owner) throw new Error(`Branch "${opts.fromBranch}" belongs to task "${owner.name}"`);
+  }
 
   const branchPrefix = opts.branchPrefixOverride ?? getProjectBranchPrefix(projectId);
+  const linkedProjectIds = (opts.linkedProjectIds ?? []).filter((id) => id !== projectId);
   const result = await invoke<CreateTaskResult>(IPC.CreateTask, {
     name,
     projectRoot,
     symlinkDirs,
     branchPrefix,
+    copyPaths,
+    clonePaths,
+    carryRootChanges: opts.carryRootChanges ?? false,
+    fromBranch: opts.fromBranch,
+    worktreeBaseDir: getProject(projectId)?.worktreeDir,
+    hardlinkPaths,
+    linkedRepos: linkedProjectIds.map(linkedRepoSpec),
   });
+  return addCreatedTask(result, opts, linkedProjectIds);
+}
+
+/** Put a task the backend just created into the store, with its first agent. */
+function addCreatedTask(
+  result: CreateTaskResult,
+  opts: CreateTaskOptions,
+  linkedProjectIds: string[],
+): string {
+  const {
+    name,
+    agentDef,
+    projectId,
+    initialPrompt,
+    githubUrl,
+    skipPermissions,
+    autoRestart,
+    lowPriority,
+    dockerMode,
+    dockerImage,
+  } = opts;
+  const linkedWorktrees = linkedProjectIds.map((id, i) => ({
+    projectId: id,
+    worktreePath: result.linked_worktrees[i].path,
+    branchName: result.linked_worktrees[i].branch_name,
+  }));
+  const prompt =
+    initialPrompt &&
+    withLinkedWorktreesNote(
+      initialPrompt,
+      linkedWorktrees.map((l) => ({
+        name: getProject(l.projectId)?.name ?? l.projectId,
+        path: l.worktreePath,
+      })),
+    );
 
   const agentId = crypto.randomUUID();
   const task: Task = {
@@ -118,12 +269,18 @@ export async function createTask(opts: CreateTaskOptions): Promise<string> {
     shellAgentIds: [],
     notes: '',
     lastPrompt: '',
-    initialPrompt: initialPrompt ?? undefined,
+    initialPrompt: prompt ?? undefined,
     skipPermissions: skipPermissions ?? undefined,
+    autoRestart: autoRestart || undefined,
+    lowPriority: lowPriority || undefined,
+    // Set up front so the initial prompt is held back before detection resolves
+    setupStatus: hasWorktreeSetup(projectId) ? 'running' : undefined,
     dockerMode: dockerMode ?? undefined,
     dockerImage: dockerImage ?? undefined,
     githubUrl,
-    savedInitialPrompt: initialPrompt ?? undefined,
+    savedInitialPrompt: prompt ?? undefined,
+    linkedWorktrees: linkedWorktrees.length > 0 ? linkedWorktrees : undefined,
+    ownsBranch: result.owns_branch ? undefined : false,
   };
 
   const agent: Agent = {
@@ -139,9 +296,190 @@ export async function createTask(opts: CreateTaskOptions): Promise<string> {
   };
 
   initTaskInStore(result.id, task, agent, projectId, agentDef);
+  if (task.setupStatus) void runWorktreeSetup(result.id, result.worktree_path);
   return result.id;
 }
 
+export interface CreateTaskBatchOptions
+  extends Omit<CreateTaskOptions, 'name' | 'initialPrompt' | 'carryRootChanges' | 'fromBranch'> {
+  /** An item's `agentDef` overrides the shared one. */
+  items: { name: string; prompt: string; agentDef?: AgentDef }[];
+  /** Send each item's prompt to its agent right away; otherwise it's only filled in. */
+  sendPrompts: boolean;
+}
+
+/**
+ * Create one task per item (e.g. a markdown checklist) with shared settings.
+ * Returns the created task ids and the items that failed, with the reason.
+ */
+export async function createTaskBatch(
+  opts: CreateTaskBatchOptions,
+): Promise<{ taskIds: string[]; failed: { name: string; error: string }[] }> {
+  const { projectId, items } = opts;
+  const projectRoot = getProjectPath(projectId);
+  if (!projectRoot) throw new Error('Project not found');
+  if (isProjectMissing(projectId)) throw new Error('Project folder not found');
+
+  const linkedProjectIds = (opts.linkedProjectIds ?? []).filter((id) => id !== projectId);
+  const results = await invoke<BatchTaskResult[]>(IPC.CreateTaskBatch, {
+    names: items.map((i) => i.name),
+    projectRoot,
+    symlinkDirs: opts.symlinkDirs ?? [],
+    branchPrefix: opts.branchPrefixOverride ?? getProjectBranchPrefix(projectId),
+    copyPaths: opts.copyPaths ?? [],
+    clonePaths: opts.clonePaths ?? [],
+    worktreeBaseDir: getProject(projectId)?.worktreeDir,
+    hardlinkPaths: opts.hardlinkPaths ?? [],
+    linkedRepos: linkedProjectIds.map(linkedRepoSpec),
+  });
+
+  const taskIds: string[] = [];
+  const failed: { name: string; error: string }[] = [];
+  results.forEach((r, i) => {
+    if (!r.task) {
+      failed.push({ name: r.name, error: r.error ?? 'Unknown error' });
+      return;
+    }
+    const { prompt, agentDef } = items[i];
+    const taskId = addCreatedTask(
+      r.task,
+      {
+        ...opts,
+        name: r.name,
+        agentDef: agentDef ?? opts.agentDef,
+        initialPrompt: opts.sendPrompts ? prompt : undefined,
+      },
+      linkedProjectIds,
+    );
+    if (!opts.sendPrompts) setPrefillPrompt(taskId, prompt);
+    taskIds.push(taskId);
+  });
+  return { taskIds, failed };
+}
+
+export interface CreateFanoutOptions
+  extends Omit<CreateTaskBatchOptions, 'items' | 'sendPrompts' | 'agentDef'> {
+  name: string;
+  prompt: string;
+  agentDefs: AgentDef[];
+}
+
+/**
+ * Give the same prompt to several agents, one task and worktree each, so
+ * their results can be compared and the best one merged.
+ */
+export async function createFanoutTasks(
+  opts: CreateFanoutOptions,
+): Promise<{ taskIds: string[]; failed: { name: string; error: string }[] }> {
+  const { name, prompt, agentDefs, ...shared } = opts;
+  const result = await createTaskBatch({
+    ...shared,
+    agentDef: agentDefs[0],
+    items: agentDefs.map((agentDef) => ({ name: `${name} (${agentDef.name})`, prompt, agentDef })),
+    sendPrompts: true,
+  });
+  if (result.taskIds.length > 1) {
+    const groupId = crypto.randomUUID();
+    for (const taskId of result.taskIds) setStore('tasks', taskId, 'fanoutGroupId', groupId);
+  }
+  return result;
+}
+
+/**
+ * Create a copy of a task to try another approach from the same starting
+ * point: a new branch from its current HEAD (or from main) with the same
+ * agent and settings, optionally with its uncommitted changes. The original
+ * task keeps running undisturbed. Returns the new task id and any copied
+ * files left with conflict markers.
+ */
+/** The backend copies one worktree, so a copy would lose the task's linked repos. */
+export const LINKED_DUPLICATE_ERROR =
+  'A task spanning several repos cannot be duplicated: only its primary worktree would be copied';
+
+export async function duplicateTask(
+  taskId: string,
+  opts: { fromMain: boolean; copyUncommitted: boolean },
+): Promise<{ taskId: string; conflictedFiles: string[] }> {
+  const source = store.tasks[taskId];
+  if (!source) throw new Error('Task not found');
+  if (source.directMode) throw new Error('A task working on the main branch cannot be duplicated');
+  if (source.linkedWorktrees?.length) throw new Error(LINKED_DUPLICATE_ERROR);
+  const agentDef = store.agents[source.agentIds[0]]?.def ?? source.savedAgentDef;
+  if (!agentDef) throw new Error('Agent not found');
+  const { projectRoot, worktreeBaseDir, ...linkPaths } = linkedRepoSpec(source.projectId);
+  const name = `${source.name} (copy)`;
+  const result = await invoke<DuplicateTaskResult>(IPC.DuplicateTask, {
+    name,
+    projectRoot,
+    sourcePath: source.worktreePath,
+    ...linkPaths,
+    branchPrefix: getProjectBranchPrefix(source.projectId),
+    worktreeBaseDir,
+    fromMain: opts.fromMain,
+    copyUncommitted: opts.copyUncommitted,
+  });
+  const newTaskId = addCreatedTask(
+    result,
+    {
+      name,
+      agentDef,
+      projectId: source.projectId,
+      githubUrl: source.githubUrl,
+      skipPermissions: source.skipPermissions,
+      autoRestart: source.autoRestart,
+      lowPriority: source.lowPriority,
+      dockerMode: source.dockerMode,
+      dockerImage: source.dockerImage,
+    },
+    [],
+  );
+  if (source.savedInitialPrompt) setPrefillPrompt(newTaskId, source.savedInitialPrompt);
+  return { taskId: newTaskId, conflictedFiles: result.conflicted_files };
+}
+
+function hasWorktreeSetup(projectId: string): boolean {
+  const project = getProject(projectId);
+  return !!project?.autoInstallDeps || (project?.setupCommands?.length ?? 0) > 0;
+}
+
+/** Setup commands of each running setup shell, to name the one that failed. */
+const setupCommands = new Map<string, string[]>();
+
+/**
+ * Run the detected dependency install and the project's setup commands in a
+ * setup shell that exits when done.
+ */
+async function runWorktreeSetup(taskId: string, worktreePath: string): Promise<void> {
+  const project = getProject(store.tasks[taskId]?.projectId ?? '');
+  const setup = project?.autoInstallDeps
+    ? await invoke<SetupCommand | null>(IPC.DetectSetupCommand, { worktreePath }).catch(() => null)
+    : null;
+  if (!store.tasks[taskId]) return;
+  const commands = [...(setup ? [setup.command] : []), ...(project?.setupCommands ?? [])];
+  if (commands.length === 0) {
+    setStore('tasks', taskId, 'setupStatus', undefined);
+    return;
+  }
+  const shellId = spawnShellForTask(taskId, setupScript(commands));
+  setupCommands.set(shellId, commands);
+  setStore('tasks', taskId, 'setupShellId', shellId);
+}
+
+/** Called when a task shell exits; resolves the setup gate if it was the setup shell. */
+export function markShellExited(taskId: string, shellId: string, exitCode: number | null): void {
+  const task = store.tasks[taskId];
+  if (!task || task.setupShellId !== shellId || task.setupStatus !== 'running') return;
+  const commands = setupCommands.get(shellId) ?? [];
+  setupCommands.delete(shellId);
+  const ok = exitCode === 0;
+  setStore('tasks', taskId, 'setupStatus', ok ? 'done' : 'failed');
+  if (!ok) {
+    const step = failedSetupStep(commands, exitCode);
+    const at = step ? ` at \`${step}\`` : '';
+    showNotification(`Worktree setup failed for "${task.name}"${at} — sending prompt anyway`);
+  }
+}
+
 export interface CreateDirectTaskOptions {
   name: string;
   agentDef: AgentDef;
@@ -150,6 +488,8 @@ export interface CreateDirectTaskOptions {
   initialPrompt?: string;
   githubUrl?: string;
   skipPermissions?: boolean;
+  autoRestart?: boolean;
+  lowPriority?: boolean;
   dockerMode?: boolean;
   dockerImage?: string;
 }
@@ -163,6 +503,8 @@ export async function createDirectTask(opts: CreateDirectTaskOptions): Promise<s
     initialPrompt,
     githubUrl,
     skipPermissions,
+    autoRestart,
+    lowPriority,
     dockerMode,
     dockerImage,
   } = opts;
@@ -190,6 +532,8 @@ export async function createDirectTask(opts: CreateDirectTaskOptions): Promise<s
     savedInitialPrompt: initialPrompt ?? undefined,
     directMode: true,
     skipPermissions: skipPermissions ?? undefined,
+    autoRestart: autoRestart || undefined,
+    lowPriority: lowPriority || undefined,
     dockerMode: dockerMode ?? undefined,
     dockerImage: dockerImage ?? undefined,
     githubUrl,
@@ -211,7 +555,12 @@ export async function createDirectTask(opts: CreateDirectTaskOptions): Promise<s
   return id;
 }
 
-export async function closeTask(taskId: string): Promise<void> {
+/**
+ * Close a task and remove its worktree. The backend refuses to delete a
+ * worktree with unmerged/uncommitted work or running agents unless `force`
+ * is set, so callers must only force after the user has confirmed.
+ */
+export async function closeTask(taskId: string, force = false): Promise<void> {
   const task = store.tasks[taskId];
   if (!task || task.closingStatus === 'closing' || task.closingStatus === 'removing') return;
 
@@ -219,7 +568,7 @@ export async function closeTask(taskId: string): Promise<void> {
   const shellAgentIds = [...task.shellAgentIds];
   const branchName = task.branchName;
   const projectRoot = getProjectPath(task.projectId) ?? '';
-  const deleteBranch = getProject(task.projectId)?.deleteBranchOnClose ?? true;
+  const deleteBranch = willDeleteTaskBranch(task);
 
   // Mark as closing — task stays visible but UI shows closing state
   setStore('tasks', taskId, 'closingStatus', 'closing');
@@ -229,10 +578,10 @@ export async function closeTask(taskId: string): Promise<void> {
   invoke(IPC.StopPlanWatcher, { taskId }).catch(console.error);
 
   try {
-    // Kill agents
-    for (const agentId of agentIds) {
-      await invoke(IPC.KillAgent, { agentId }).catch(console.error);
-    }
+    // Stop agents gracefully so they can persist session state for resume
+    await Promise.all(
+      agentIds.map((agentId) => invoke(IPC.TerminateAgent, { agentId }).catch(console.error)),
+    );
     for (const shellId of shellAgentIds) {
       await invoke(IPC.KillAgent, { agentId: shellId }).catch(console.error);
     }
@@ -240,16 +589,21 @@ export async function closeTask(taskId: string): Promise<void> {
     // Skip git cleanup for direct mode (no worktree/branch to remove)
     if (!task.directMode) {
       // Remove worktree + branch
-      await invoke(IPC.DeleteTask, {
+      const result = await invoke<DeleteTaskResult>(IPC.DeleteTask, {
         taskId,
         agentIds: [...agentIds, ...shellAgentIds],
         branchName,
         deleteBranch,
         projectRoot,
+        linked: getLinkedBranches(task),
+        permanent: store.permanentlyDeleteWorktrees,
+        force,
       });
+      if (result.trashed_path) showNotification(`Moved worktree to trash: ${result.trashed_path}`);
     }
 
     // Backend cleanup succeeded — remove from UI
+    blockDependents(taskId);
     removeTaskFromStore(taskId, [...agentIds, ...shellAgentIds]);
   } catch (err) {
     // Backend cleanup failed — show error, allow retry
@@ -262,7 +616,118 @@ export async function closeTask(taskId: string): Promise<void> {
 export async function retryCloseTask(taskId: string): Promise<void> {
   setStore('tasks', taskId, 'closingStatus', undefined);
   setStore('tasks', taskId, 'closingError', undefined);
-  await closeTask(taskId);
+  // Retrying only happens after a confirmed close failed
+  await closeTask(taskId, true);
+}
+
+/**
+ * Close a task but keep a record of what it did: the backend saves its
+ * changes, commit log and agent transcripts to the archive before removing
+ * the worktree. The branch is kept if `keepBranch` (by default when the
+ * project keeps branches on close). Multi-repo tasks can't be archived.
+ */
+export async function archiveTask(taskId: string, keepBranch?: boolean): Promise<void> {
+  const task = store.tasks[taskId];
+  if (!task || task.directMode || task.closingStatus) return;
+  if (task.linkedWorktrees?.length) throw new Error('Multi-repo tasks cannot be archived');
+  const agentIds = [...task.agentIds];
+  const shellAgentIds = [...task.shellAgentIds];
+  const agentDef = store.agents[agentIds[0]]?.def ?? task.savedAgentDef;
+  const settings: ArchivedTaskSettings = {
+    agentDef,
+    notes: task.notes || undefined,
+    initialPrompt: task.savedInitialPrompt,
+    githubUrl: task.githubUrl,
+    skipPermissions: task.skipPermissions,
+    autoRestart: task.autoRestart,
+    lowPriority: task.lowPriority,
+    dockerMode: task.dockerMode,
+    dockerImage: task.dockerImage,
+    ownsBranch: task.ownsBranch,
+  };
+
+  setStore('tasks', taskId, 'closingStatus', 'closing');
+  setStore('tasks', taskId, 'closingError', undefined);
+  invoke(IPC.StopPlanWatcher, { taskId }).catch(console.error);
+  try {
+    // Stopped first so their transcripts are complete when they're archived
+    await Promise.all(
+      agentIds.map((agentId) => invoke(IPC.TerminateAgent, { agentId }).catch(console.error)),
+    );
+    for (const shellId of shellAgentIds) {
+      await invoke(IPC.KillAgent, { agentId: shellId }).catch(console.error);
+    }
+    await invoke<ArchivedTask>(IPC.ArchiveTask, {
+      taskId,
+      name: task.name,
+      projectRoot: getProjectPath(task.projectId) ?? '',
+      branchName: task.branchName,
+      worktreePath: task.worktreePath,
+      agentIds: [...agentIds, ...shellAgentIds],
+      keepBranch: keepBranch ?? !willDeleteTaskBranch(task),
+      permanent: store.permanentlyDeleteWorktrees,
+      task: settings,
+    });
+    blockDependents(taskId);
+    removeTaskFromStore(taskId, [...agentIds, ...shellAgentIds]);
+    showNotification(`Archived ${task.name}`);
+  } catch (err) {
+    // The worktree is still there (agents can be restarted), so this isn't a close error
+    console.error('Failed to archive task:', err);
+    setStore('tasks', taskId, 'closingStatus', undefined);
+    showNotification(`Archiving failed: ${err}`);
+  }
+}
+
+/** The project's archived tasks, newest first. */
+export async function listArchivedTasks(projectId: string): Promise<ArchivedTask[]> {
+  const projectRoot = getProjectPath(projectId);
+  if (!projectRoot) return [];
+  return invoke<ArchivedTask[]>(IPC.ListArchivedTasks, { projectRoot });
+}
+
+/**
+ * Bring an archived task back with its agent and settings. Its prompt is
+ * filled in rather than sent. Returns the new task id.
+ */
+export async function restoreArchivedTask(archiveId: string, projectId: string): Promise<string> {
+  const spec = linkedRepoSpec(projectId);
+  const result = await invoke<RestoreArchivedResult>(IPC.RestoreArchivedTask, {
+    id: archiveId,
+    symlinkDirs: spec.symlinkDirs,
+    copyPaths: spec.copyPaths,
+    clonePaths: spec.clonePaths,
+    hardlinkPaths: spec.hardlinkPaths,
+    branchPrefix: getProjectBranchPrefix(projectId),
+    worktreeBaseDir: spec.worktreeBaseDir,
+  });
+  const settings = result.archived.task ?? {};
+  const agentDef = settings.agentDef ?? store.availableAgents[0];
+  if (!agentDef) throw new Error('No agent available to restore the task with');
+  const taskId = addCreatedTask(
+    // A kept branch is checked out again, but it is still the task's own if it created it
+    { ...result.task, owns_branch: result.task.owns_branch || settings.ownsBranch !== false },
+    {
+      name: result.archived.name,
+      agentDef,
+      projectId,
+      githubUrl: settings.githubUrl,
+      skipPermissions: settings.skipPermissions,
+      autoRestart: settings.autoRestart,
+      lowPriority: settings.lowPriority,
+      dockerMode: settings.dockerMode,
+      dockerImage: settings.dockerImage,
+    },
+    [],
+  );
+  if (settings.notes) updateTaskNotes(taskId, settings.notes);
+  if (settings.initialPrompt) setPrefillPrompt(taskId, settings.initialPrompt);
+  if (result.patch_error) {
+    showNotification(`Archived changes did not apply and were kept: ${result.patch_error}`);
+  } else if (result.conflicted_files.length > 0) {
+    showNotification(`Restored with conflict markers in ${result.conflicted_files.length} file(s)`);
+  }
+  return taskId;
 }
 
 const REMOVE_ANIMATION_MS = 300;
@@ -275,6 +740,7 @@ function removeTaskFromStore(taskId: string, agentIds: string[]): void {
   // merge+cleanup, direct-mode close), so placing it here prevents leaks
   // regardless of which path removed the task.  Idempotent if already stopped.
   invoke(IPC.StopPlanWatcher, { taskId }).catch(console.error);
+  clearTaskJobs(taskId);
 
   // Clean up agent activity tracking (timers, buffers, decoders) before
   // the store entries are deleted — otherwise markAgentExited can't find
@@ -292,6 +758,7 @@ function removeTaskFromStore(taskId: string, agentIds: string[]): void {
       produce((s) => {
         delete s.tasks[taskId];
         delete s.taskGitStatus[taskId];
+        delete s.taskCiStatus[taskId];
 
         // Compute neighbor BEFORE cleanupPanelEntries removes taskId from taskOrder
         let neighbor: string | null = null;
@@ -320,9 +787,52 @@ function removeTaskFromStore(taskId: string, agentIds: string[]): void {
   }, REMOVE_ANIMATION_MS);
 }
 
+/**
+ * The commit message a merge starts from: the project's template for merge
+ * commits, the branch log for squashes. Rebased tasks keep their commits.
+ */
+export async function defaultMergeMessage(
+  taskId: string,
+  strategy: MergeStrategy,
+): Promise<string | undefined> {
+  const task = store.tasks[taskId];
+  if (!task || strategy === 'rebase') return undefined;
+  if (strategy === 'merge') {
+    const template =
+      getProject(task.projectId)?.mergeMessageTemplate ?? DEFAULT_MERGE_MESSAGE_TEMPLATE;
+    return renderMergeMessage(template, { name: task.name, branch: task.branchName });
+  }
+  const log = await invoke<string>(IPC.GetBranchLog, { worktreePath: task.worktreePath });
+  return squashMessageFromLog(log, task.name);
+}
+
+/** Have the task's agent, run headless, write a squash commit message for the branch. */
+export async function generateCommitMessage(taskId: string): Promise<string> {
+  const task = store.tasks[taskId];
+  if (!task) throw new Error('Task not found');
+  const projectRoot = getProjectPath(task.projectId);
+  if (!projectRoot) throw new Error('Project not found');
+  return invoke<string>(IPC.GenerateCommitMessage, {
+    projectRoot,
+    branchName: task.branchName,
+    agentDefId: store.agents[task.agentIds[0]]?.def.id,
+  });
+}
+
 export async function mergeTask(
   taskId: string,
-  options?: { squash?: boolean; message?: string; cleanup?: boolean },
+  options?: {
+    /** Defaults to the project's strategy, or a merge commit. */
+    strategy?: MergeStrategy;
+    /** Defaults to `defaultMergeMessage`. */
+    message?: string;
+    cleanup?: boolean;
+    stashRootChanges?: boolean;
+    /** Also delete the task's branch on origin once merged. */
+    deleteRemoteBranch?: boolean;
+    /** Receives the output of the project's pre-merge checks. */
+    onCheckOutput?: Channel<string>;
+  },
 ): Promise<void> {
   const task = store.tasks[taskId];
   if (!task || task.closingStatus === 'removing') return;
@@ -335,6 +845,12 @@ export async function mergeTask(
   const shellAgentIds = [...task.shellAgentIds];
   const branchName = task.branchName;
   const cleanup = options?.cleanup ?? false;
+  const strategy = options?.strategy ?? getProject(task.projectId)?.mergeStrategy ?? 'merge';
+  const message = options?.message ?? (await defaultMergeMessage(taskId, strategy));
+
+  // Linked repositories go first so a failure there leaves the task's own
+  // branch unmerged and the merge can be retried
+  await mergeLinkedWorktrees(task, strategy, message);
 
   // Merge branch into main. Cleanup is optional.
   // NOTE: agents are killed AFTER merge succeeds — killing them before would
@@ -343,31 +859,209 @@ export async function mergeTask(
   const mergeResult = await invoke<MergeResult>(IPC.MergeTask, {
     projectRoot,
     branchName,
-    squash: options?.squash ?? false,
-    message: options?.message,
+    strategy,
+    message,
     cleanup,
+    keepBranch: task.ownsBranch === false,
+    postMergeCommands: getProject(task.projectId)?.postMergeCommands ?? [],
+    stashRootChanges: options?.stashRootChanges ?? false,
+    preMergeCommands: getProject(task.projectId)?.preMergeCommands ?? [],
+    preMergeGate: getProject(task.projectId)?.preMergeGate ?? 'block',
+    blockOnRedCi: getProject(task.projectId)?.blockMergeOnRedCi ?? false,
+    remote: task.pushRemote,
+    ...getProjectCommitOptions(task.projectId),
+    onCheckOutput: options?.onCheckOutput,
   });
   recordMergedLines(mergeResult.lines_added, mergeResult.lines_removed);
 
+  const failedCheck = mergeResult.pre_merge_checks.find((c) => c.exit_code !== 0);
+  if (failedCheck) {
+    showNotification(`Merged despite failing pre-merge check: ${failedCheck.command}`);
+  }
+
+  const failedHook = mergeResult.post_merge_hooks.find((h) => h.exit_code !== 0);
+  if (failedHook) {
+    console.warn('Post-merge hook failed:', failedHook.command, failedHook.output);
+    showNotification(`Post-merge hook failed: ${failedHook.command}`);
+  }
+  if (mergeResult.stash_conflict) {
+    showNotification('Stashed project root changes conflicted and were kept in the stash');
+  }
+  releaseDependents(taskId);
+  if (options?.deleteRemoteBranch) {
+    await invoke(IPC.DeleteRemoteBranch, {
+      projectRoot,
+      branchName,
+      remote: task.pushRemote,
+    }).catch((err) => showNotification(String(err)));
+  }
+
   if (cleanup) {
     await Promise.allSettled(
       [...agentIds, ...shellAgentIds].map((id) => invoke(IPC.KillAgent, { agentId: id })),
     );
+    for (const linked of getLinkedBranches(task)) {
+      await invoke<DeleteTaskResult>(IPC.DeleteTask, {
+        agentIds: [],
+        branchName: linked.branchName,
+        deleteBranch: true,
+        projectRoot: linked.projectRoot,
+        permanent: store.permanentlyDeleteWorktrees,
+        force: true,
+      }).catch((err) => showNotification(`Failed to remove linked worktree: ${String(err)}`));
+    }
     removeTaskFromStore(taskId, [...agentIds, ...shellAgentIds]);
   }
 }
 
-export async function pushTask(taskId: string, onOutput: Channel<string>): Promise<void> {
+/**
+ * Merge a multi-repo task's other worktrees with the same strategy and
+ * message, each under its own project's settings. Conflicts anywhere stop the
+ * merge before any repository is touched; repositories with nothing left to
+ * merge (e.g. merged by an earlier attempt) are skipped.
+ */
+async function mergeLinkedWorktrees(
+  task: Task,
+  strategy: MergeStrategy,
+  message: string,
+): Promise<void> {
+  const linked = (task.linkedWorktrees ?? []).map((l) => {
+    const project = getProject(l.projectId);
+    if (!project) throw new Error(`Linked project of "${task.name}" not found`);
+    return { ...l, project };
+  });
+  if (linked.length === 0) return;
+
+  const pending: typeof linked = [];
+  for (const l of linked) {
+    const log = await invoke<string>(IPC.GetBranchLog, { worktreePath: l.worktreePath });
+    if (!log.trim()) continue;
+    const status = await invoke<MergeStatus>(IPC.CheckMergeStatus, {
+      worktreePath: l.worktreePath,
+    });
+    if (status.conflicting_files.length > 0) {
+      throw new Error(
+        `${l.project.name} conflicts with main in ${status.conflicting_files.join(', ')}; ` +
+          'nothing was merged',
+      );
+    }
+    pending.push(l);
+  }
+
+  const merged: string[] = [];
+  for (const l of pending) {
+    try {
+      const result = await invoke<MergeResult>(IPC.MergeTask, {
+        projectRoot: l.project.path,
+        branchName: l.branchName,
+        strategy,
+        message,
+        cleanup: false,
+        postMergeCommands: l.project.postMergeCommands ?? [],
+        preMergeCommands: l.project.preMergeCommands ?? [],
+        preMergeGate: l.project.preMergeGate ?? 'block',
+        blockOnRedCi: l.project.blockMergeOnRedCi ?? false,
+        ...getProjectCommitOptions(l.projectId),
+      });
+      recordMergedLines(result.lines_added, result.lines_removed);
+      merged.push(l.project.name);
+    } catch (err) {
+      const done = merged.length > 0 ? ` (already merged: ${merged.join(', ')})` : '';
+      throw new Error(`Merging ${l.project.name} failed${done}: ${String(err)}`);
+    }
+  }
+}
+
+export async function pushTask(
+  taskId: string,
+  onOutput: Channel<string>,
+  options?: { forceWithLease?: boolean; remote?: string },
+): Promise<void> {
   const task = store.tasks[taskId];
   if (!task || task.directMode) return;
 
   const projectRoot = getProjectPath(task.projectId);
   if (!projectRoot) return;
 
+  const remote = options?.remote ?? task.pushRemote ?? 'origin';
   await invoke(IPC.PushTask, {
     projectRoot,
     branchName: task.branchName,
     onOutput,
+    forceWithLease: options?.forceWithLease ?? false,
+    remote,
+  });
+  setTaskPushRemote(taskId, remote);
+  refreshTaskCi(taskId, true);
+}
+
+/** Remember where the task's branch was pushed; origin is the default and not stored. */
+function setTaskPushRemote(taskId: string, remote: string): void {
+  if (!store.tasks[taskId]) return;
+  setStore('tasks', taskId, 'pushRemote', remote === 'origin' ? undefined : remote);
+}
+
+/** Push a task's branch and open a pull or merge request for it against main. */
+export async function createPullRequest(
+  taskId: string,
+  opts: { title: string; body: string; draft: boolean; remote?: string },
+): Promise<PullRequestResult> {
+  const task = store.tasks[taskId];
+  if (!task || task.directMode) throw new Error('Task has no branch of its own');
+  const projectRoot = getProjectPath(task.projectId);
+  if (!projectRoot) throw new Error('Project not found');
+
+  const remote = opts.remote ?? task.pushRemote ?? 'origin';
+  const result = await invoke<PullRequestResult>(IPC.CreatePullRequest, {
+    projectRoot,
+    branch: task.branchName,
+    remote,
+    title: opts.title,
+    body: opts.body,
+    draft: opts.draft,
+  });
+  setTaskPushRemote(taskId, remote);
+  refreshTaskCi(taskId, true);
+  return result;
+}
+
+/** Save a task's branch as a patch series or git bundle via a save dialog. */
+export async function exportTask(taskId: string, format: 'patch' | 'bundle'): Promise<void> {
+  const task = store.tasks[taskId];
+  if (!task || task.directMode) return;
+  const projectRoot = getProjectPath(task.projectId);
+  if (!projectRoot) return;
+  try {
+    const saved = await invoke<string | null>(IPC.ExportTask, {
+      projectRoot,
+      branchName: task.branchName,
+      format,
+    });
+    if (saved) showNotification(`Task exported to ${saved}`);
+  } catch (err) {
+    showNotification(`Export failed: ${String(err)}`);
+  }
+}
+
+/**
+ * Copy a task's changes since it left main, committed or not, into another
+ * task's worktree. Conflicting hunks are left as markers in the target.
+ */
+export async function transferChanges(
+  sourceTaskId: string,
+  targetTaskId: string,
+  files?: string[],
+): Promise<PatchApplyResult> {
+  const source = store.tasks[sourceTaskId];
+  const target = store.tasks[targetTaskId];
+  if (!source || !target) throw new Error('Task not found');
+  if (source.projectId !== target.projectId) {
+    throw new Error('Changes can only be transferred between tasks of the same project');
+  }
+  return invoke<PatchApplyResult>(IPC.TransferChanges, {
+    sourcePath: source.worktreePath,
+    targetPath: target.worktreePath,
+    files,
   });
 }
 
@@ -380,9 +1074,9 @@ export function updateTaskNotes(taskId: string, notes: string): void {
 }
 
 export async function sendPrompt(taskId: string, agentId: string, text: string): Promise<void> {
-  // Send text and Enter separately so TUI apps (Claude Code, Codex)
-  // don't treat the \r as part of a pasted block
-  await writeToAgentWhenReady(agentId, text);
+  // Paste the text (bracketed, so multi-line prompts stay one block), then
+  // send Enter separately so TUI apps don't treat the \r as part of the paste
+  await writeToAgentWhenReady(agentId, text, IPC.PasteToAgent);
   await new Promise((r) => setTimeout(r, 50));
   await writeToAgentWhenReady(agentId, '\r');
   setStore('tasks', taskId, 'lastPrompt', text);
@@ -1,4 +1,12 @@
-import type { AgentDef, WorktreeStatus } from '../ipc/types';
+import type {
+  AgentDef,
+  CiStatus,
+  IdleTimeoutAction,
+  JobInfo,
+  MergeStrategy,
+  QueuedPrompt,
+  WorktreeStatus,
+} from '../ipc/types';
 import type { TerminalFont } from '../lib/fonts';
 import type { LookPreset } from '../lib/look';
 
@@ -7,6 +15,25 @@ export interface TerminalBookmark {
   command: string;
 }
 
+export interface WorktreeLinkRule {
+  path: string; // relative to the project root; files or directories
+  mode: 'symlink' | 'copy' | 'clone' | 'hardlink' | 'skip';
+}
+
+export interface ProjectScript {
+  id: string; // stable across edits as long as the name stays the same
+  name: string;
+  command: string; // run through the shell in the task's working directory
+  devServer?: boolean; // gets a free port per task in PORT and its URL tracked
+}
+
+export interface AutoResponseRule {
+  id: string;
+  agentDefId: string; // AgentDef.id the rule applies to
+  pattern: string; // regex, matched against the end of the agent's output
+  response: string; // written to the terminal; \n, \r and \t escapes expanded
+}
+
 export interface Project {
   id: string;
   name: string;
@@ -16,6 +43,22 @@ export interface Project {
   deleteBranchOnClose?: boolean; // default true if unset
   defaultDirectMode?: boolean; // default false if unset
   terminalBookmarks?: TerminalBookmark[];
+  postMergeCommands?: string[]; // run in the project root after a successful merge
+  preMergeCommands?: string[]; // must pass in the task's worktree before it is merged
+  preMergeGate?: 'block' | 'warn'; // what a failing pre-merge check does; block if unset
+  blockMergeOnRedCi?: boolean; // refuse to merge while the pushed branch's CI is failing
+  commitSigning?: 'sign' | 'no-sign'; // overrides commit.gpgsign for the app's commits
+  skipCommitHooks?: boolean; // pass --no-verify to the app's commits and merges
+  mergeStrategy?: MergeStrategy; // preselected when merging; merge commit if unset
+  mergeMessageTemplate?: string; // merge commit message with {name} and {branch}
+  linkRules?: WorktreeLinkRule[]; // extra/overridden worktree link candidates
+  autoInstallDeps?: boolean; // run the detected install command in new worktrees
+  setupCommands?: string[]; // run in each new worktree before the first prompt
+  scripts?: ProjectScript[]; // presets runnable as background jobs per task
+  verifyCommand?: string; // must pass before an agent-resolved rebase continues
+  generatedFiles?: string[]; // globs listed but collapsed and left out of diff totals
+  autoFetchMinutes?: number; // background fetch of origin; off if unset or 0
+  worktreeDir?: string; // where task worktrees go; `<repo>/.worktrees` if unset
 }
 
 export interface Agent {
@@ -28,6 +71,16 @@ export interface Agent {
   signal: string | null;
   lastOutput: string[];
   generation: number;
+  suspended?: boolean; // stopped via SIGTSTP, awaiting SIGCONT
+  title?: string; // window title the CLI set via OSC 0/2, e.g. "✳ Brewing…"
+  exitStats?: { durationMs: number; outputBytes: number; lastActivityAt: number };
+}
+
+/** A task's worktree in another project and the branch it is on. */
+export interface LinkedWorktree {
+  projectId: string;
+  worktreePath: string;
+  branchName: string;
 }
 
 export interface Task {
@@ -47,13 +100,49 @@ export interface Task {
   closingError?: string;
   directMode?: boolean;
   skipPermissions?: boolean;
+  autoRestart?: boolean; // backend restarts crashed agents with resume args
+  lowPriority?: boolean; // agent processes run niced / idle IO class
+  setupStatus?: 'running' | 'done' | 'failed'; // worktree setup gate (not persisted)
+  setupShellId?: string;
+  conflictHandoff?: 'rebasing' | 'resolving' | 'verifying'; // AI conflict hand-off (not persisted)
   dockerMode?: boolean;
   dockerImage?: string;
   githubUrl?: string;
+  pushRemote?: string; // remote the branch is pushed to, e.g. a fork; origin if unset
   collapsed?: boolean;
   savedAgentDef?: AgentDef;
   planContent?: string;
   planFileName?: string;
+  linkedWorktrees?: LinkedWorktree[]; // multi-repo tasks: worktrees besides `worktreePath`
+  ownsBranch?: boolean; // false when the branch existed before the task; it is never deleted
+  fanoutGroupId?: string; // shared by tasks that got the same prompt with different agents
+}
+
+/** A task that gets created once another task is merged. */
+export interface PendingTask {
+  id: string;
+  name: string;
+  projectId: string;
+  agentDef: AgentDef;
+  prompt?: string;
+  /** Task that has to be merged first. */
+  afterTaskId?: string;
+  afterTaskName?: string;
+  /** When to start (epoch ms); the backend scheduler fires it. */
+  startAt?: number;
+  /** The task it waited for was closed without being merged, or starting failed. */
+  blockedReason?: string;
+  branchPrefixOverride?: string;
+  symlinkDirs?: string[];
+  copyPaths?: string[];
+  clonePaths?: string[];
+  hardlinkPaths?: string[];
+  linkedProjectIds?: string[];
+  skipPermissions?: boolean;
+  autoRestart?: boolean;
+  lowPriority?: boolean;
+  dockerMode?: boolean;
+  dockerImage?: string;
 }
 
 export interface Terminal {
@@ -75,12 +164,18 @@ export interface PersistedTask {
   agentDef: AgentDef | null;
   directMode?: boolean;
   skipPermissions?: boolean;
+  autoRestart?: boolean;
+  lowPriority?: boolean;
   dockerMode?: boolean;
   dockerImage?: string;
   githubUrl?: string;
+  pushRemote?: string;
   savedInitialPrompt?: string;
   collapsed?: boolean;
   planFileName?: string;
+  linkedWorktrees?: LinkedWorktree[];
+  ownsBranch?: boolean;
+  fanoutGroupId?: string;
 }
 
 export interface PersistedTerminal {
@@ -117,12 +212,22 @@ export interface PersistedState {
   themePreset?: LookPreset;
   windowState?: PersistedWindowState;
   autoTrustFolders?: boolean;
+  permanentlyDeleteWorktrees?: boolean;
+  recordSessions?: boolean;
+  osc52Clipboard?: boolean;
+  idleTimeoutMinutes?: number;
+  idleTimeoutAction?: IdleTimeoutAction;
   showPlans?: boolean;
   desktopNotificationsEnabled?: boolean;
+  maxRunningAgents?: number;
   inactiveColumnOpacity?: number;
   editorCommand?: string;
   dockerImage?: string;
-  customAgents?: AgentDef[];
+  /** Legacy custom agents that couldn't be moved to agents.json yet. */
+  customAgents?: unknown[];
+  autoResponseRules?: AutoResponseRule[];
+  agentExtraArgs?: Record<string, string[]>;
+  pendingTasks?: PendingTask[];
 }
 
 // Panel cell IDs. Shell terminals use "shell:0", "shell:1", etc.
@@ -153,16 +258,25 @@ export interface AppStore {
   tasks: Record<string, Task>;
   terminals: Record<string, Terminal>;
   agents: Record<string, Agent>;
+  promptQueues: Record<string, QueuedPrompt[]>;
+  /** Queue position of agents waiting for a free slot, keyed by agentId. */
+  queuedAgents: Record<string, number>;
+  /** When the most recent usage limit an agent reported resets (epoch ms). */
+  usageLimitResetAt: number | null;
+  taskJobs: Record<string, JobInfo[]>;
   activeTaskId: string | null;
   activeAgentId: string | null;
   availableAgents: AgentDef[];
   customAgents: AgentDef[];
+  autoResponseRules: AutoResponseRule[];
+  agentExtraArgs: Record<string, string[]>; // keyed by AgentDef.id, appended on every spawn
   showNewTaskDialog: boolean;
   sidebarVisible: boolean;
   fontScales: Record<string, number>;
   panelSizes: Record<string, number>;
   globalScale: number;
   taskGitStatus: Record<string, WorktreeStatus>;
+  taskCiStatus: Record<string, CiStatus>;
   focusedPanel: Record<string, PanelId>;
   sidebarFocused: boolean;
   sidebarFocusedProjectId: string | null;
@@ -181,15 +295,28 @@ export interface AppStore {
   themePreset: LookPreset;
   windowState: PersistedWindowState | null;
   autoTrustFolders: boolean;
+  permanentlyDeleteWorktrees: boolean;
+  recordSessions: boolean;
+  osc52Clipboard: boolean;
+  idleTimeoutMinutes: number; // 0 disables the idle timeout
+  idleTimeoutAction: IdleTimeoutAction;
   showPlans: boolean;
   desktopNotificationsEnabled: boolean;
+  maxRunningAgents: number; // 0 means no limit
   inactiveColumnOpacity: number;
   editorCommand: string;
+  /** Hosts with a saved forge API token; the tokens themselves stay in the backend. */
+  forgeTokenHosts: string[];
   dockerImage: string;
   dockerAvailable: boolean;
   newTaskDropUrl: string | null;
   newTaskPrefillPrompt: { prompt: string; projectId: string | null } | null;
   missingProjectIds: Record<string, true>;
+  /** Project whose pre-existing worktrees and branches are offered for adoption. */
+  adoptWorktreesProjectId: string | null;
+  /** Saved-state files another instance changed, waiting for the user to pick a copy. */
+  stateConflictPaths: string[];
   remoteAccess: RemoteAccess;
   showArena: boolean;
+  pendingTasks: PendingTask[]; // waiting for a merge or a start time
 }
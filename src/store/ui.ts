@@ -1,8 +1,11 @@
 import { produce } from 'solid-js/store';
+import { invoke } from '../lib/ipc';
+import { IPC } from '../../electron/ipc/channels';
 import { store, setStore } from './core';
 import type { TerminalFont } from '../lib/fonts';
 import type { LookPreset } from '../lib/look';
 import type { PersistedWindowState } from './types';
+import type { IdleTimeoutAction } from '../ipc/types';
 
 // --- Font Scale (per-panel) ---
 
@@ -83,6 +86,23 @@ export function setAutoTrustFolders(autoTrustFolders: boolean): void {
   setStore('autoTrustFolders', autoTrustFolders);
 }
 
+export function setPermanentlyDeleteWorktrees(permanent: boolean): void {
+  setStore('permanentlyDeleteWorktrees', permanent);
+}
+
+export function setRecordSessions(record: boolean): void {
+  setStore('recordSessions', record);
+}
+
+export function setOsc52Clipboard(enabled: boolean): void {
+  setStore('osc52Clipboard', enabled);
+}
+
+export function setIdleTimeout(minutes: number, action: IdleTimeoutAction): void {
+  setStore('idleTimeoutMinutes', Math.max(0, Math.round(minutes)));
+  setStore('idleTimeoutAction', action);
+}
+
 export function setShowPlans(showPlans: boolean): void {
   setStore('showPlans', showPlans);
 }
@@ -99,6 +119,24 @@ export function setEditorCommand(command: string): void {
   setStore('editorCommand', command);
 }
 
+export async function loadForgeTokenHosts(): Promise<void> {
+  const hosts = await invoke<string[]>(IPC.ListForgeTokenHosts).catch(() => []);
+  setStore('forgeTokenHosts', hosts);
+}
+
+/** Save the token sent to `host` only; an empty token removes it. */
+export async function setForgeToken(host: string, token: string): Promise<void> {
+  setStore('forgeTokenHosts', await invoke<string[]>(IPC.SetForgeToken, { host, token }));
+  // Let CI polling retry tasks that were skipped for want of a token
+  setStore(
+    produce((s) => {
+      for (const [taskId, ci] of Object.entries(s.taskCiStatus)) {
+        if (ci.host_not_configured) delete s.taskCiStatus[taskId];
+      }
+    }),
+  );
+}
+
 export function setDockerImage(image: string): void {
   setStore('dockerImage', image || 'parallel-code-agent:latest');
 }